@@ -1 +1,3 @@
+pub mod mcp;
 pub mod service;
+pub mod traces;
@@ -0,0 +1,380 @@
+//! Minimal MCP (Model Context Protocol) tool server.
+//!
+//! Speaks newline-delimited JSON-RPC 2.0 over stdio so agents and editors can drive
+//! dictation and read history through `AppService`. Only the `tools` capability is
+//! implemented; resources/prompts are not exposed. `voicewin-cli mcp` is the launcher.
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::service::{AppService, is_dictated_text};
+
+pub const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+pub const TOOL_START_DICTATION: &str = "start_dictation";
+pub const TOOL_GET_LAST_TRANSCRIPT: &str = "get_last_transcript";
+pub const TOOL_SEARCH_HISTORY: &str = "search_history";
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+const DEFAULT_DICTATION_SECS: u64 = 5;
+// Matches the controller's hard cap on a single recording.
+const MAX_DICTATION_SECS: u64 = 120;
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Clone)]
+pub struct McpServer {
+    service: AppService,
+}
+
+impl McpServer {
+    pub fn new(service: AppService) -> Self {
+        Self { service }
+    }
+
+    /// Serve requests from stdin until EOF.
+    pub async fn serve_stdio(&self) -> anyhow::Result<()> {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(resp) = self.handle_line(&line).await {
+                stdout.write_all(resp.to_string().as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle one JSON-RPC message. Notifications (no `id`) produce no response.
+    pub async fn handle_line(&self, line: &str) -> Option<Value> {
+        let msg: Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(e) => return Some(rpc_error(Value::Null, PARSE_ERROR, &e.to_string())),
+        };
+
+        let id = msg.get("id").cloned();
+        let Some(method) = msg.get("method").and_then(|m| m.as_str()) else {
+            return Some(rpc_error(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "missing method",
+            ));
+        };
+        let params = msg.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": MCP_PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "voicewin",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params).await,
+            // Notifications such as `notifications/initialized` need no handling.
+            m if m.starts_with("notifications/") => return None,
+            _ => Err((METHOD_NOT_FOUND, format!("method not found: {method}"))),
+        };
+
+        // Requests without an id are notifications; never reply to them.
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => rpc_error(id, code, &message),
+        })
+    }
+
+    async fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let Some(name) = params.get("name").and_then(|n| n.as_str()) else {
+            return Err((INVALID_PARAMS, "missing tool name".into()));
+        };
+        let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+        // Tool failures are reported in-band (`isError`) so the agent can see them.
+        let out = match name {
+            TOOL_START_DICTATION => self.start_dictation(&args).await,
+            TOOL_GET_LAST_TRANSCRIPT => self.get_last_transcript(),
+            TOOL_SEARCH_HISTORY => self.search_history(&args),
+            _ => return Err((INVALID_PARAMS, format!("unknown tool: {name}"))),
+        };
+
+        Ok(match out {
+            Ok(text) => tool_result(text, false),
+            Err(e) => tool_result(e.to_string(), true),
+        })
+    }
+
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    async fn start_dictation(&self, args: &Value) -> anyhow::Result<String> {
+        use voicewin_core::error::{ErrorKind, VoicewinError};
+        use voicewin_runtime::ipc::RunSessionRequest;
+
+        let secs = args
+            .get("duration_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_DICTATION_SECS)
+            .clamp(1, MAX_DICTATION_SECS);
+
+        self.service
+            .start_recording()
+            .await
+            .map_err(|e| anyhow::anyhow!(crate::service::user_facing_audio_error(&e)))?;
+        tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
+        let audio = self
+            .service
+            .stop_recording()
+            .await
+            .map_err(|e| anyhow::anyhow!(crate::service::user_facing_audio_error(&e)))?;
+
+        let resp = self
+            .service
+            .run_session(
+                RunSessionRequest {
+                    language: args
+                        .get("language")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    ..Default::default()
+                },
+                audio,
+            )
+            .await?;
+
+        match resp.final_text {
+            Some(text) if !text.trim().is_empty() => Ok(text),
+            _ => Err(anyhow::anyhow!(resp.error.unwrap_or_else(|| {
+                VoicewinError::new(ErrorKind::NoSpeech, "No speech detected.")
+            }))),
+        }
+    }
+
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    async fn start_dictation(&self, _args: &Value) -> anyhow::Result<String> {
+        Err(anyhow::anyhow!(
+            "Dictation is not supported on this platform."
+        ))
+    }
+
+    fn get_last_transcript(&self) -> anyhow::Result<String> {
+        let entries = self.service.history_store().load()?;
+        entries
+            .into_iter()
+            .rev()
+            .find(is_dictated_text)
+            .map(|e| e.text)
+            .ok_or_else(|| anyhow::anyhow!("History is empty."))
+    }
+
+    fn search_history(&self, args: &Value) -> anyhow::Result<String> {
+        let query = args
+            .get("query")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        if query.is_empty() {
+            return Err(anyhow::anyhow!("query must not be empty"));
+        }
+        let limit = args
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .max(1);
+
+        // Newest first.
+        let matches: Vec<_> = self
+            .service
+            .history_store()
+            .load()?
+            .into_iter()
+            .rev()
+            .filter(|e| is_dictated_text(e) && e.text.to_lowercase().contains(&query))
+            .take(limit)
+            .collect();
+
+        Ok(serde_json::to_string(&matches)?)
+    }
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": TOOL_START_DICTATION,
+            "description": "Record from the microphone for a fixed duration, transcribe it with the configured pipeline, insert it into the focused app, and return the text.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "duration_secs": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_DICTATION_SECS,
+                        "description": "How long to record (default 5)."
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "Language code for this dictation (e.g. \"de\"), or \"auto\" to detect. Defaults to the configured language."
+                    }
+                }
+            }
+        },
+        {
+            "name": TOOL_GET_LAST_TRANSCRIPT,
+            "description": "Return the text of the most recent dictation from history.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": TOOL_SEARCH_HISTORY,
+            "description": "Case-insensitive substring search over dictation history, newest first. Returns a JSON array of history entries.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum results (default 10)." }
+                },
+                "required": ["query"]
+            }
+        }
+    ])
+}
+
+fn tool_result(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use voicewin_runtime::history::HistoryEntry;
+
+    fn entry(ts: i64, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            ts_unix_ms: ts,
+            text: text.into(),
+            stage: "done".into(),
+            ..Default::default()
+        }
+    }
+
+    fn server(dir: &std::path::Path) -> McpServer {
+        let ctx = voicewin_platform::test::TestContextProvider::new(
+            voicewin_core::types::AppIdentity::new(),
+            Default::default(),
+        )
+        .boxed();
+        let inserter = Arc::new(voicewin_platform::test::StdoutInserter);
+        McpServer::new(AppService::new(dir.join("config.json"), ctx, inserter))
+    }
+
+    #[tokio::test]
+    async fn lists_tools_and_ignores_notifications() {
+        let dir = tempfile::tempdir().unwrap();
+        let srv = server(dir.path());
+
+        let init = srv
+            .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#)
+            .await
+            .unwrap();
+        assert_eq!(init["result"]["protocolVersion"], MCP_PROTOCOL_VERSION);
+
+        assert!(
+            srv.handle_line(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+                .await
+                .is_none()
+        );
+
+        let list = srv
+            .handle_line(r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#)
+            .await
+            .unwrap();
+        let names: Vec<_> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                TOOL_START_DICTATION,
+                TOOL_GET_LAST_TRANSCRIPT,
+                TOOL_SEARCH_HISTORY
+            ]
+        );
+
+        let unknown = srv
+            .handle_line(r#"{"jsonrpc":"2.0","id":3,"method":"nope"}"#)
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn history_tools_read_from_history_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let srv = server(dir.path());
+
+        let empty = srv
+            .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"get_last_transcript"}}"#)
+            .await
+            .unwrap();
+        assert_eq!(empty["result"]["isError"], true);
+
+        let store = srv.service.history_store();
+        store.append(entry(1, "Hello team")).unwrap();
+        store.append(entry(2, "Grocery list")).unwrap();
+        store.append(entry(3, "hello again")).unwrap();
+        // Neither digests nor hash-only entries are transcripts.
+        store
+            .append(HistoryEntry {
+                stage: crate::service::DIGEST_STAGE.into(),
+                ..entry(4, "- Said hello to the team")
+            })
+            .unwrap();
+        store
+            .append(entry(5, &voicewin_runtime::history::text_digest("hello")))
+            .unwrap();
+
+        let last = srv
+            .handle_line(r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"get_last_transcript"}}"#)
+            .await
+            .unwrap();
+        assert_eq!(last["result"]["content"][0]["text"], "hello again");
+
+        let found = srv
+            .handle_line(r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"search_history","arguments":{"query":"HELLO","limit":5}}}"#)
+            .await
+            .unwrap();
+        let text = found["result"]["content"][0]["text"].as_str().unwrap();
+        let hits: Vec<HistoryEntry> = serde_json::from_str(text).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].ts_unix_ms, 3);
+        assert_eq!(hits[1].ts_unix_ms, 1);
+    }
+}
@@ -1,53 +1,226 @@
 use std::future::Future;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tracing::Instrument;
 
 use voicewin_core::config::AppConfig;
-use voicewin_engine::engine::VoicewinEngine;
-use voicewin_engine::traits::{AppContextProvider, AudioInput, Inserter};
+use voicewin_core::digest::{
+    DigestItem, build_digest_user_message, digest_window, format_digest_note,
+};
+use voicewin_core::enhancement::{
+    EnhancementContext, LlmDecodingSettings, PromptTemplate, build_enhancement_prompt,
+    post_process_llm_output,
+};
+use voicewin_core::error::{ErrorKind, VoicewinError};
+use voicewin_core::key_macro::KeyChord;
+use voicewin_core::meeting::format_meeting_line;
+use voicewin_core::note::expand_note_path;
+use voicewin_core::onboarding::{OnboardingAction, OnboardingCheck, OnboardingStep};
+use voicewin_core::power_mode::{
+    GlobalDefaults, ProfileSuggestion, next_forced_profile, suggest_profiles,
+};
+use voicewin_core::stt::STT_PROVIDER_LOCAL;
+use voicewin_core::text::{adjust_for_insertion, basic_cleanup, filter_transcription_output_for};
+use voicewin_core::types::{
+    AppIdentity, ExePath, InsertMode, InsertionStrategy, OutputFormat, ProcessName, ProfileId,
+    PromptId, WindowTitle,
+};
+use voicewin_core::usage::{CostEstimate, UsageRecord};
+use voicewin_engine::engine::{EngineError, VoicewinEngine};
+use voicewin_engine::session::ms;
+use voicewin_engine::traits::{
+    AppContextProvider, AudioInput, EnhancedText, Inserter, LlmProvider, Transcript,
+};
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 use voicewin_audio::{AudioCaptureError, AudioRecorder};
-use voicewin_runtime::config_store::ConfigStore;
-use voicewin_runtime::ipc::{RunSessionRequest, RunSessionResponse};
+use voicewin_providers::error::{classify_error, missing_key_error};
+use voicewin_runtime::config_store::{ConfigLockedError, ConfigStore};
+use voicewin_runtime::history::{
+    ExportRange, HistoryEntry, HistoryFilter, HistoryStore, history_key_from_keyring,
+};
+use voicewin_runtime::ipc::{RunSessionRequest, RunSessionResponse, SessionDetails};
+use voicewin_runtime::llm::OpenAiCompatibleLlmProvider;
+use voicewin_runtime::models::{
+    choose_default_local_stt_model_path, installed_bootstrap_model_path,
+};
+use voicewin_runtime::recovery::{InFlightGuard, PendingSessionStore};
+use voicewin_runtime::scratchpad::ScratchpadStore;
+use voicewin_runtime::stats::StatsStore;
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+pub fn user_facing_audio_error(e: &voicewin_audio::AudioCaptureError) -> VoicewinError {
+    if matches!(e, voicewin_audio::AudioCaptureError::Disabled) {
+        return VoicewinError::new(
+            ErrorKind::Audio,
+            "Recording is disabled. Re-enable the microphone from the tray menu.",
+        );
+    }
+    if let voicewin_audio::AudioCaptureError::InsufficientDiskSpace(detail) = e {
+        return VoicewinError::new(
+            ErrorKind::Audio,
+            "Not enough free disk space to record. Free up some space and try again.",
+        )
+        .with_detail(detail.clone());
+    }
 
-#[cfg(any(windows, target_os = "macos"))]
-pub fn user_facing_audio_error(e: &voicewin_audio::AudioCaptureError) -> String {
     let raw = e.to_string();
 
+    // A blocking privacy switch surfaces as a generic WASAPI failure, so check it first.
+    #[cfg(windows)]
+    if let Ok(Some(block)) = voicewin_platform::windows::microphone_block() {
+        return VoicewinError::new(ErrorKind::MicrophoneAccess, block.user_message())
+            .with_detail(raw);
+    }
+
     // Keep messages actionable and short; details are in logs.
     if raw.contains("NoInputDevice") || raw.to_lowercase().contains("no input device") {
-        return "No microphone detected. Check your mic and choose the device in the app.".into();
+        return VoicewinError::new(
+            ErrorKind::Audio,
+            "No microphone detected. Check your mic and choose the device in the app.",
+        )
+        .with_detail(raw);
     }
 
     if raw.to_lowercase().contains("permission") || raw.to_lowercase().contains("access") {
         #[cfg(windows)]
-        {
-            return "Microphone access appears blocked. Check Windows Settings > Privacy & security > Microphone.".into();
-        }
+        let message = "Microphone access appears blocked. Check Windows Settings > Privacy & security > Microphone.";
 
         #[cfg(target_os = "macos")]
-        {
-            return "Microphone access appears blocked. Check System Settings > Privacy & Security > Microphone.".into();
-        }
+        let message = "Microphone access appears blocked. Check System Settings > Privacy & Security > Microphone.";
+
+        #[cfg(target_os = "linux")]
+        let message = "Microphone access appears blocked. Check your sound settings (PipeWire/PulseAudio) and that the app may use the input device.";
+
+        return VoicewinError::new(ErrorKind::MicrophoneAccess, message).with_detail(raw);
     }
 
-    "Audio recording failed. See History for recovery and check logs for details.".into()
+    VoicewinError::new(
+        ErrorKind::Audio,
+        "Audio recording failed. See History for recovery and check logs for details.",
+    )
+    .with_detail(raw)
+}
+
+/// The user-facing form of an `AppService` error, for commands and session results.
+pub fn service_error(e: &anyhow::Error) -> VoicewinError {
+    for cause in e.chain() {
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        if let Some(audio) = cause.downcast_ref::<AudioCaptureError>() {
+            return user_facing_audio_error(audio);
+        }
+        if cause.downcast_ref::<ConfigLockedError>().is_some() {
+            return VoicewinError::new(
+                ErrorKind::Internal,
+                "Settings are locked by another VoiceWin process. Close it and try again.",
+            )
+            .with_detail(format!("{e:#}"))
+            .with_retryable(true);
+        }
+        if let Some(EngineError::NoDefaultPrompt) = cause.downcast_ref::<EngineError>() {
+            return VoicewinError::new(
+                ErrorKind::Config,
+                "No enhancement prompt is configured. Add one in Settings.",
+            );
+        }
+    }
+    classify_error(e, ErrorKind::Internal)
 }
 
 
-use voicewin_runtime::runtime_engine::build_engine_from_config;
+/// In low-memory mode, keep at most this much audio in RAM before spilling to disk. This
+/// only applies to captures without a pending session file: meeting chunks after the
+/// first, or when `pending/` cannot be written.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+const LOW_MEMORY_SPILL_AFTER_SECS: u32 = 30;
+
+/// Free space the capture volume needs before a recording starts: about ten minutes of
+/// 48 kHz audio on disk.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+const RECORDING_SPILL_RESERVE_BYTES: u64 = 10 * 60 * 48_000 * 4;
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+fn apply_capture_settings(
+    recorder: &AudioRecorder,
+    cfg: Option<&AppConfig>,
+    capture_dir: &Path,
+) -> Result<(), AudioCaptureError> {
+    let low_memory = cfg.is_some_and(|c| c.performance.low_memory_mode);
+    recorder.set_spill_after_secs(Some(if low_memory {
+        LOW_MEMORY_SPILL_AFTER_SECS
+    } else {
+        voicewin_audio::DEFAULT_SPILL_AFTER_SECS
+    }))?;
+    // Recordings are written to disk as they go; refuse up front rather than lose one mid-way.
+    voicewin_runtime::disk_space::ensure_available(capture_dir, RECORDING_SPILL_RESERVE_BYTES)
+        .map_err(|e| AudioCaptureError::InsufficientDiskSpace(format!("{e:#}")))
+}
+
+use voicewin_runtime::runtime_engine::{
+    build_engine_from_config, build_llm_provider, build_stt_from_config,
+};
 use voicewin_runtime::secrets::{SecretKey, delete_secret, get_secret, set_secret};
+use voicewin_runtime::stt::fallback::{SttDowngrade, SystemProbe, resolve_stt};
+
+/// What a key check learned beyond "the provider accepted it".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct KeyValidation {
+    /// The account plan, e.g. ElevenLabs `creator`.
+    pub plan: Option<String>,
+    /// Credits used and allowed this billing period, when the provider reports them.
+    pub quota_used: Option<u64>,
+    pub quota_limit: Option<u64>,
+    pub quota_resets_at_unix: Option<i64>,
+    /// Models the key can use (LLM endpoints only).
+    pub models: Vec<String>,
+}
+
+/// ElevenLabs credits are running low (see `QuotaWarningSettings`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuotaWarning {
+    pub remaining_minutes: u64,
+    pub plan: String,
+    pub resets_at_unix: Option<i64>,
+}
+
+impl QuotaWarning {
+    pub fn message(&self) -> String {
+        format!(
+            "About {} minutes of ElevenLabs transcription left on the {} plan. Switch to a local model to keep dictating.",
+            self.remaining_minutes, self.plan
+        )
+    }
+}
 
 #[derive(Clone)]
 pub struct AppService {
     config_store: ConfigStore,
+    // Machine-local storage: `models/` and the crash-recovery `pending/` audio.
+    local_data_dir: PathBuf,
     ctx: Arc<dyn AppContextProvider>,
     inserter: Arc<dyn Inserter>,
 
-    #[cfg(any(windows, target_os = "macos"))]
+    // Shared so sessions and the scratchpad window serialize their writes.
+    scratchpad: Arc<ScratchpadStore>,
+
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     recorder: Arc<tokio::sync::Mutex<Option<AudioRecorder>>>,
+
+    // While set, the microphone is closed and every attempt to record is refused.
+    recording_disabled: Arc<AtomicBool>,
+
+    // Profile picked by hotkey for new sessions instead of matching the app in front.
+    forced_profile: Arc<std::sync::Mutex<Option<ProfileId>>>,
+
+    // The most recent session, for the details window. Kept in memory only.
+    last_session: Arc<std::sync::Mutex<Option<SessionDetails>>>,
+
+    // Pending session backing the current (or just stopped) capture, until it is run.
+    pending_capture: Arc<std::sync::Mutex<Option<String>>>,
 }
 
 impl AppService {
@@ -57,42 +230,147 @@ impl AppService {
         inserter: Arc<dyn Inserter>,
     ) -> Self {
         Self {
+            // Next to the config until the shell points it at local (non-roaming) storage.
+            local_data_dir: config_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from(".")),
+            scratchpad: Arc::new(ScratchpadStore::at_path(
+                config_path
+                    .parent()
+                    .map(|p| p.join("scratchpad.json"))
+                    .unwrap_or_else(|| PathBuf::from("scratchpad.json")),
+            )),
             config_store: ConfigStore::at_path(config_path),
             ctx,
             inserter,
-            #[cfg(any(windows, target_os = "macos"))]
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
             recorder: Arc::new(tokio::sync::Mutex::new(None)),
+            recording_disabled: Arc::new(AtomicBool::new(false)),
+            forced_profile: Arc::new(std::sync::Mutex::new(None)),
+            last_session: Arc::new(std::sync::Mutex::new(None)),
+            pending_capture: Arc::new(std::sync::Mutex::new(None)),
         }
     }
 
-    #[cfg(any(windows, target_os = "macos"))]
+    /// Look for models and keep crash-recovery audio (`dir/pending`) in `dir` instead of
+    /// next to the config.
+    pub fn with_local_data_dir(mut self, dir: PathBuf) -> Self {
+        self.local_data_dir = dir;
+        self
+    }
+
+    pub fn recording_disabled(&self) -> bool {
+        self.recording_disabled.load(Ordering::SeqCst)
+    }
+
+    pub fn forced_profile(&self) -> Option<ProfileId> {
+        self.forced_profile
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clone()
+    }
+
+    /// Force the next enabled profile for new sessions, or go back to matching by app after
+    /// the last one. Returns the name of the profile now forced.
+    pub fn cycle_forced_profile(&self) -> anyhow::Result<Option<String>> {
+        let cfg = self.config_store.load()?;
+        let mut forced = self
+            .forced_profile
+            .lock()
+            .unwrap_or_else(|p| p.into_inner());
+        let next = next_forced_profile(&cfg.profiles, forced.as_ref());
+        *forced = next.map(|p| p.id.clone());
+        Ok(next.map(|p| p.name.clone()))
+    }
+
+    /// Raw transcript, enhanced text and timings of the most recent session since startup.
+    pub fn last_session_result(&self) -> Option<SessionDetails> {
+        self.last_session
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clone()
+    }
+
+    /// Undo the last insertion by pressing the platform's undo shortcut in the focused app.
+    pub async fn undo_last_insertion(&self) -> anyhow::Result<()> {
+        self.inserter.send_keys(&[KeyChord::undo()]).await
+    }
+
+    /// Type a committed realtime segment into the focused app (live typing). The first one
+    /// of a session is fitted to the text before the caret like a regular insertion.
+    pub async fn type_live_segment(&self, text: &str, first: bool) -> anyhow::Result<()> {
+        let text = if first {
+            let before_caret = self.ctx.text_before_caret().await.ok().flatten();
+            adjust_for_insertion(text, before_caret.as_deref())
+        } else {
+            text.to_string()
+        };
+        self.inserter
+            .insert_with_strategy(&text, InsertMode::Paste, InsertionStrategy::TypeKeys)
+            .await
+    }
+
+    /// Globally disable (or re-enable) recording. Disabling discards any in-progress
+    /// capture and closes the input device so the mic is released immediately.
+    pub async fn set_recording_disabled(&self, disabled: bool) {
+        self.recording_disabled.store(disabled, Ordering::SeqCst);
+
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        if disabled {
+            let recorder = self.recorder.lock().await.take();
+            if let Some(r) = recorder
+                && let Err(e) = r.close()
+            {
+                log::warn!("failed to close recorder: {e}");
+            }
+        }
+    }
+
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     pub async fn start_recording(&self) -> Result<(), AudioCaptureError> {
         let mut recorder = self.recorder.lock().await;
+        if self.recording_disabled() {
+            return Err(AudioCaptureError::Disabled);
+        }
+        let cfg = self.load_config().ok();
         if recorder.is_none() {
-            let cfg = self.load_config().ok();
             let preferred = cfg
                 .as_ref()
                 .and_then(|c| c.defaults.microphone_device.as_deref());
             *recorder = Some(AudioRecorder::open_named(preferred)?);
         }
-        recorder
-            .as_ref()
-            .ok_or(AudioCaptureError::NoInputDevice)?
-            .start()
+        let r = recorder.as_ref().ok_or(AudioCaptureError::NoInputDevice)?;
+
+        apply_capture_settings(r, cfg.as_ref(), &self.local_data_dir)?;
+        match self.begin_pending_capture() {
+            Some(file) => r.start_into(file),
+            None => r.start(),
+        }
     }
 
-    #[cfg(any(windows, target_os = "macos"))]
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     pub async fn stop_recording(&self) -> Result<AudioInput, AudioCaptureError> {
         let mut recorder = self.recorder.lock().await;
         let r = recorder.as_mut().ok_or(AudioCaptureError::NoInputDevice)?;
 
-        let captured = r.stop_captured()?;
+        let samples = tracing::info_span!("resample").in_scope(|| r.stop_captured_16k())?;
 
-        let samples = if captured.sample_rate_hz == 16_000 {
-            captured.samples
-        } else {
-            AudioRecorder::resample_to_16k(&captured.samples, captured.sample_rate_hz)?
-        };
+        Ok(AudioInput {
+            sample_rate_hz: 16_000,
+            samples,
+        })
+    }
+
+    /// Hand over the audio captured so far (one meeting chunk) and keep recording.
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    pub async fn take_recording_chunk(&self) -> Result<AudioInput, AudioCaptureError> {
+        let recorder = self.recorder.lock().await;
+        let r = recorder.as_ref().ok_or(AudioCaptureError::NoInputDevice)?;
+
+        let samples = tracing::info_span!("resample").in_scope(|| r.take_captured_16k())?;
+        // Meeting chunks are appended as they are cut; only the first one had a capture file.
+        self.discard_pending_capture();
 
         Ok(AudioInput {
             sample_rate_hz: 16_000,
@@ -100,7 +378,7 @@ impl AppService {
         })
     }
 
-    #[cfg(any(windows, target_os = "macos"))]
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     pub async fn cancel_recording(&self) -> Result<(), AudioCaptureError> {
         // Best-effort: stop and discard captured audio.
         let mut recorder = self.recorder.lock().await;
@@ -108,11 +386,12 @@ impl AppService {
             return Ok(());
         };
 
-        let _ = r.stop();
+        let _ = r.discard();
+        self.discard_pending_capture();
         Ok(())
     }
 
-    #[cfg(any(windows, target_os = "macos"))]
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     pub async fn start_recording_with_level_callback<F>(
         &self,
         cb: F,
@@ -122,8 +401,11 @@ impl AppService {
     {
         // Set callback first, then start.
         let mut recorder = self.recorder.lock().await;
+        if self.recording_disabled() {
+            return Err(AudioCaptureError::Disabled);
+        }
+        let cfg = self.load_config().ok();
         if recorder.is_none() {
-            let cfg = self.load_config().ok();
             let preferred = cfg
                 .as_ref()
                 .and_then(|c| c.defaults.microphone_device.as_deref());
@@ -131,11 +413,50 @@ impl AppService {
         }
         let r = recorder.as_ref().ok_or(AudioCaptureError::NoInputDevice)?;
 
+        apply_capture_settings(r, cfg.as_ref(), &self.local_data_dir)?;
         r.set_level_callback(cb);
-        r.start()
+        match self.begin_pending_capture() {
+            Some(file) => r.start_into(file),
+            None => r.start(),
+        }
+    }
+
+    /// Register a pending session for the capture about to start and open the file it is
+    /// recorded into, so a crash while recording still leaves the audio behind.
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    fn begin_pending_capture(&self) -> Option<std::fs::File> {
+        match self.pending_sessions().begin(now_unix_ms()) {
+            Ok((session, file)) => {
+                let stale = self.lock_pending_capture().replace(session.id);
+                // Stopped but never run: hand it over to recovery.
+                drop(stale.as_deref().map(InFlightGuard::new));
+                Some(file)
+            }
+            Err(e) => {
+                log::warn!("failed to persist pending session: {e}");
+                None
+            }
+        }
+    }
+
+    /// Drop the pending session of a capture that will not go through `run_session`.
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    fn discard_pending_capture(&self) {
+        let id = self.lock_pending_capture().take();
+        if let Some(id) = id
+            && let Err(e) = self.pending_sessions().complete(&id)
+        {
+            log::warn!("failed to clear pending session: {e}");
+        }
     }
 
-    #[cfg(any(windows, target_os = "macos"))]
+    fn lock_pending_capture(&self) -> std::sync::MutexGuard<'_, Option<String>> {
+        self.pending_capture
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+    }
+
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     pub async fn recording_sample_rate_hz(&self) -> Option<u32> {
         let recorder = self.recorder.lock().await;
         recorder.as_ref().map(|r| r.sample_rate_hz())
@@ -145,8 +466,38 @@ impl AppService {
         self.config_store.load()
     }
 
+    /// The config sessions run with: [`AppConfig::apply_offline_mode`] applied, falling back
+    /// to the installed local model when a cloud STT provider is selected.
+    pub fn load_session_config(&self) -> anyhow::Result<AppConfig> {
+        let mut cfg = self.config_store.load()?;
+        if cfg.offline_mode {
+            let model = choose_default_local_stt_model_path(&self.local_data_dir);
+            cfg.apply_offline_mode(&model.to_string_lossy());
+        }
+        Ok(cfg)
+    }
+
+    /// A fallback for the STT `provider`/`model` a session would use, when that one is
+    /// unusable right now and `cfg.stt_fallbacks` names one that works.
+    pub async fn resolve_session_stt(
+        &self,
+        cfg: &AppConfig,
+        provider: &str,
+        model: &str,
+    ) -> Option<SttDowngrade> {
+        let probe = SystemProbe::new(&self.local_data_dir).with_command(cfg.command_stt.clone());
+        resolve_stt(provider, model, &cfg.stt_fallbacks, &probe).await
+    }
+
     pub fn save_config(&self, cfg: &AppConfig) -> anyhow::Result<()> {
-        self.config_store.save(cfg)
+        let was_encrypted = self.config_store.load().is_ok_and(|c| c.encrypt_history);
+        self.config_store.save(cfg)?;
+
+        // Toggling encryption re-encodes existing History so it never lingers in the old format.
+        if was_encrypted != cfg.encrypt_history {
+            self.history_store().migrate()?;
+        }
+        Ok(())
     }
 
     pub fn set_openai_api_key(&self, value: &str) -> anyhow::Result<()> {
@@ -161,6 +512,94 @@ impl AppService {
         delete_secret(SecretKey::OpenAiCompatibleApiKey)
     }
 
+    /// Onboarding check for the enhancement LLM: configured, reachable, and accepting the key.
+    pub async fn llm_onboarding_check(&self) -> OnboardingCheck {
+        let cfg = match self.config_store.load() {
+            Ok(cfg) => cfg,
+            Err(e) => return OnboardingCheck::failed(OnboardingStep::Llm, e.to_string(), None),
+        };
+        match get_secret(SecretKey::OpenAiCompatibleApiKey) {
+            Ok(api_key) => llm_check_with(&cfg, api_key).await,
+            Err(e) => OnboardingCheck::failed(
+                OnboardingStep::Llm,
+                e.to_string(),
+                Some(OnboardingAction::ConfigureLlm),
+            ),
+        }
+    }
+
+    /// Check an LLM key against the configured endpoint before it is saved. `None` checks the
+    /// stored key.
+    pub async fn validate_llm_key(&self, api_key: Option<&str>) -> anyhow::Result<KeyValidation> {
+        let cfg = self.config_store.load()?;
+        let api_key = match api_key {
+            Some(key) => key.trim().to_string(),
+            None => get_secret(SecretKey::OpenAiCompatibleApiKey)?.unwrap_or_default(),
+        };
+        if api_key.is_empty() {
+            return Err(missing_key_error("LLM").into());
+        }
+        let base_url = cfg.defaults.llm_base_url.trim();
+        if base_url.is_empty() {
+            return Err(VoicewinError::new(ErrorKind::Config, "Set an LLM endpoint first.").into());
+        }
+
+        let models = OpenAiCompatibleLlmProvider::new(api_key)
+            .with_endpoint_flavor(cfg.llm_endpoint_flavor.clone())
+            .list_models(base_url)
+            .await?;
+        Ok(KeyValidation {
+            models,
+            ..Default::default()
+        })
+    }
+
+    /// Check an ElevenLabs key before it is saved and report the plan and credit usage.
+    /// `None` checks the stored key.
+    pub async fn validate_elevenlabs_key(
+        &self,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<KeyValidation> {
+        let api_key = match api_key {
+            Some(key) => key.trim().to_string(),
+            None => get_secret(SecretKey::ElevenLabsApiKey)?.unwrap_or_default(),
+        };
+        let sub = voicewin_providers::elevenlabs::fetch_subscription(&api_key).await?;
+        Ok(KeyValidation {
+            plan: Some(sub.tier),
+            quota_used: Some(sub.character_count),
+            quota_limit: Some(sub.character_limit),
+            quota_resets_at_unix: sub.next_character_count_reset_unix,
+            models: Vec::new(),
+        })
+    }
+
+    /// Check the ElevenLabs credit balance with the stored key; `Some` when it is low enough
+    /// to warn about under `cfg.quota_warning`.
+    pub async fn elevenlabs_quota_warning(
+        &self,
+        cfg: &AppConfig,
+    ) -> anyhow::Result<Option<QuotaWarning>> {
+        if !cfg.quota_warning.enabled {
+            return Ok(None);
+        }
+        let api_key = get_secret(SecretKey::ElevenLabsApiKey)?.unwrap_or_default();
+        let sub = voicewin_providers::elevenlabs::fetch_subscription(&api_key).await?;
+        if !cfg
+            .quota_warning
+            .is_low(sub.character_count, sub.character_limit)
+        {
+            return Ok(None);
+        }
+        Ok(Some(QuotaWarning {
+            remaining_minutes: cfg
+                .quota_warning
+                .remaining_minutes(sub.character_count, sub.character_limit),
+            plan: sub.tier,
+            resets_at_unix: sub.next_character_count_reset_unix,
+        }))
+    }
+
     pub fn set_elevenlabs_api_key(&self, value: &str) -> anyhow::Result<()> {
         set_secret(SecretKey::ElevenLabsApiKey, value)
     }
@@ -173,12 +612,84 @@ impl AppService {
         Ok(get_secret(SecretKey::ElevenLabsApiKey)?.is_some())
     }
 
+    pub fn set_webhook_token(&self, value: &str) -> anyhow::Result<()> {
+        set_secret(SecretKey::WebhookBearerToken, value)
+    }
+
+    pub fn clear_webhook_token(&self) -> anyhow::Result<()> {
+        delete_secret(SecretKey::WebhookBearerToken)
+    }
+
+    pub fn get_webhook_token_present(&self) -> anyhow::Result<bool> {
+        Ok(get_secret(SecretKey::WebhookBearerToken)?.is_some())
+    }
+
     pub fn clear_api_keys(&self) -> anyhow::Result<()> {
         delete_secret(SecretKey::OpenAiCompatibleApiKey)?;
         delete_secret(SecretKey::ElevenLabsApiKey)?;
         Ok(())
     }
 
+    /// Dictations sent to the scratchpad (`scratchpad.json` next to the config).
+    pub fn scratchpad(&self) -> &ScratchpadStore {
+        &self.scratchpad
+    }
+
+    /// Usage stats live next to the config file (`stats.json`).
+    pub fn stats_store(&self) -> StatsStore {
+        let path = self
+            .config_store
+            .path()
+            .parent()
+            .map(|p| p.join("stats.json"))
+            .unwrap_or_else(|| PathBuf::from("stats.json"));
+        StatsStore::at_path(path)
+    }
+
+    /// Best-effort: a failed stats write must never fail the session that produced it.
+    fn record_usage(&self, records: &[UsageRecord]) {
+        if let Err(e) = self.stats_store().append(records) {
+            log::warn!("failed to record usage: {e}");
+        }
+    }
+
+    /// Estimated cloud spend (STT audio and LLM tokens) inside `range`, priced with the
+    /// config's pricing table.
+    pub fn get_cost_estimate(&self, range: &ExportRange) -> anyhow::Result<CostEstimate> {
+        let cfg = self.config_store.load()?;
+        self.stats_store().cost_estimate(range, &cfg.pricing)
+    }
+
+    /// History lives next to the config file (`history.json`).
+    pub fn history_store(&self) -> HistoryStore {
+        let history_path = self
+            .config_store
+            .path()
+            .parent()
+            .map(|p| p.join("history.json"))
+            .unwrap_or_else(|| PathBuf::from("history.json"));
+        let store = HistoryStore::at_path(history_path);
+
+        let encrypt = self.config_store.load().is_ok_and(|c| c.encrypt_history);
+        if encrypt {
+            let key = history_key_from_keyring(true).unwrap_or_else(|e| {
+                log::error!("history encryption key unavailable: {e}");
+                None
+            });
+            return store.with_encryption(key);
+        }
+
+        // Encryption was turned off but the file hasn't been migrated back yet.
+        if store.is_encrypted_on_disk() {
+            match history_key_from_keyring(false) {
+                Ok(Some(key)) => return store.with_decryption_key(key),
+                Ok(None) => log::error!("history is encrypted but no key is stored"),
+                Err(e) => log::error!("history encryption key unavailable: {e}"),
+            }
+        }
+        store
+    }
+
     pub async fn get_foreground_app(&self) -> anyhow::Result<voicewin_core::types::AppIdentity> {
         self.ctx.foreground_app().await
     }
@@ -201,69 +712,179 @@ impl AppService {
         F: Fn(&'static str) -> Fut + Send + Sync,
         Fut: Future<Output = ()> + Send,
     {
-        let cfg = self.config_store.load()?;
+        let cfg = self.load_session_config()?;
+
+        let overrides = req.overrides(self.forced_profile());
 
         // Split request fields so we can move transcript into the engine call.
-        let RunSessionRequest { transcript, warning } = req;
+        let RunSessionRequest {
+            transcript,
+            warning,
+            live_typed,
+            target_app,
+            ..
+        } = req;
+
+        // The capture's audio stays on disk (see `start_recording`) until the result is in
+        // History. Until the guard is dropped, `recover_pending_sessions` leaves it alone.
+        let pending_store = self.pending_sessions();
+        let pending_id = self.lock_pending_capture().take();
+        let _in_flight = pending_id.as_deref().map(InFlightGuard::new);
+
+        // Hotkey pressed by accident: nothing to transcribe, nothing worth keeping in History.
+        if transcript.trim().is_empty()
+            && cfg.silent_sessions.auto_cancel
+            && is_silent(&audio, cfg.silent_sessions.noise_floor())
+        {
+            log::info!("session cancelled: capture stayed below the noise floor");
+            if let Some(id) = pending_id.as_deref()
+                && let Err(e) = pending_store.complete(id)
+            {
+                log::warn!("failed to clear pending session: {e}");
+            }
+            return Ok(RunSessionResponse {
+                stage: "cancelled".into(),
+                error: Some(VoicewinError::new(ErrorKind::Audio, "No speech detected.")),
+                ..Default::default()
+            });
+        }
 
         // Design-draft UI treats History as always enabled.
         // Keep the config flag for backward compatibility, but it must not disable history.
         let history_enabled = true;
         let _ = cfg.defaults.history_enabled;
 
+        let redaction = cfg.redaction.clone();
+        let timeouts = cfg.stage_timeouts.clone();
+        let prompts = cfg.prompts.clone();
+
         let engine: VoicewinEngine =
-            build_engine_from_config(cfg, self.ctx.clone(), self.inserter.clone()).await?;
+            build_engine_from_config(cfg, self.ctx.clone(), self.inserter.clone())
+                .await?
+                .with_scratchpad(self.scratchpad.clone())
+                .with_overrides(overrides)
+                .with_live_typed(live_typed)
+                .with_target_app(target_app.clone());
+
+        if let Some(id) = pending_id.as_deref() {
+            let app = match &target_app {
+                Some(app) => Some(app.clone()),
+                None => self.ctx.foreground_app().await.ok(),
+            };
+            if let Some(app) = app
+                && let Err(e) = pending_store.set_app(id, &app)
+            {
+                log::warn!("failed to update pending session: {e}");
+            }
+        }
+
+        // Current stage and when it started, so hung stages can be timed out.
+        let (stage_tx, stage_rx) = tokio::sync::watch::channel(None);
+
+        let hook = |stage: &'static str| {
+            stage_tx.send_replace(Some((stage, tokio::time::Instant::now())));
+            if let Some(id) = pending_id.as_deref()
+                && let Err(e) = pending_store.set_stage(id, stage)
+            {
+                log::warn!("failed to update pending session: {e}");
+            }
+            on_stage(stage)
+        };
+
+        // Root span for the performance timeline (see `crate::traces`).
+        let audio_ms = audio_duration_ms(&audio);
+        let session_span = tracing::info_span!(
+            "session",
+            audio_ms,
+            realtime = !transcript.trim().is_empty()
+        );
 
         // Run the full session pipeline and emit stage progress.
         // If `req.transcript` is provided, skip STT and run from the given transcript.
-        let res = if transcript.trim().is_empty() {
-            engine.run_session_with_hook(audio, on_stage).await
-        } else {
-            engine
-                .run_session_with_transcript_with_hook(transcript, on_stage)
-                .await
+        let pipeline = async move {
+            if transcript.trim().is_empty() {
+                engine.run_session_with_hook(audio, hook).await
+            } else {
+                engine
+                    .run_session_with_transcript_with_hook(transcript, hook)
+                    .await
+            }
         };
+        let res = with_stage_timeouts(
+            pipeline.instrument(session_span.clone()),
+            stage_rx,
+            |stage| timeouts.for_stage(stage),
+        )
+        .await;
 
-        let (stage, final_text, mut error) = match res {
+        let mut details = None;
+        let mut finished = None;
+        let mut response = match res {
             Ok(result) => {
-                let stage = result
-                    .stage_label
-                    .unwrap_or_else(|| format!("{:?}", result.stage).to_lowercase());
-                (stage, result.final_text, result.error)
-            }
-            Err(e) => {
-                // On any failure, rely on History for recovery.
-                ("error".into(), None, Some(e.to_string()))
+                self.record_usage(&usage_records(
+                    now_unix_ms(),
+                    result.transcript.as_ref(),
+                    audio_ms,
+                    result.enhanced.as_ref(),
+                ));
+                let prompt_title = result
+                    .prompt_id
+                    .as_ref()
+                    .and_then(|id| prompts.iter().find(|p| &p.id == id))
+                    .map(|p| p.title.clone());
+                details = Some(SessionDetails::from_result(
+                    &result,
+                    prompt_title,
+                    now_unix_ms(),
+                ));
+                let response = RunSessionResponse::from_result(&result);
+                finished = Some(result);
+                response
             }
+            // On any failure, rely on History for recovery.
+            Err(e) => RunSessionResponse {
+                stage: "error".into(),
+                error: Some(service_error(&e)),
+                ..Default::default()
+            },
         };
 
         // Attach any extra warning requested by the caller.
         if let Some(w) = warning.as_ref().filter(|s| !s.trim().is_empty()) {
-            error = match error {
-                Some(existing) if !existing.trim().is_empty() => {
-                    Some(format!("{existing} | {w}"))
+            response.error = match response.error.take() {
+                Some(existing) if !existing.user_message.trim().is_empty() => {
+                    let combined = format!("{} | {w}", existing.user_message);
+                    Some(existing.with_user_message(combined))
                 }
-                _ => Some(w.to_string()),
+                // Caller warnings come from the realtime STT provider.
+                _ => Some(VoicewinError::new(ErrorKind::Provider, w.as_str())),
             };
         }
 
+        if let Some(mut details) = details {
+            details.error = response.error.clone();
+            *self.last_session.lock().unwrap_or_else(|p| p.into_inner()) = Some(details);
+        }
+
+        let mut history_written = true;
         if history_enabled {
-            let should_write = final_text
+            let should_write = response
+                .final_text
                 .as_ref()
                 .map(|t| !t.trim().is_empty())
                 .unwrap_or(false)
-                || error.is_some();
+                || response.error.is_some();
 
             if should_write {
-                let text = final_text.clone().unwrap_or_default();
-                let ts = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as i64;
+                let text = response.final_text.clone().unwrap_or_default();
+                let ts = now_unix_ms();
 
-                let app = self.ctx.foreground_app().await.ok();
+                let app = match target_app {
+                    Some(app) => Some(app),
+                    None => self.ctx.foreground_app().await.ok(),
+                };
 
-                let entry = voicewin_runtime::history::HistoryEntry {
+                let entry = HistoryEntry {
                     ts_unix_ms: ts,
                     app_process_name: app
                         .as_ref()
@@ -278,54 +899,890 @@ impl AppService {
                         .and_then(|a| a.window_title.as_ref())
                         .map(|t| t.0.clone()),
                     text,
-                    stage: stage.clone(),
-                    error: error.clone(),
+                    stage: response.stage.clone(),
+                    error: response.error.as_ref().map(|e| e.user_message.clone()),
+                    ..Default::default()
                 };
+                let entry = match &finished {
+                    Some(result) => entry.with_session_sources(result),
+                    None => entry,
+                }
+                .redacted(&redaction);
 
                 // Best-effort: write history alongside config.
-                let history_path = self
-                    .config_store
-                    .path()
-                    .parent()
-                    .map(|p| p.join("history.json"))
-                    .unwrap_or_else(|| PathBuf::from("history.json"));
-
-                let store = voicewin_runtime::history::HistoryStore::at_path(history_path);
-                if let Err(e) = store.append(entry) {
+                let appended = session_span.in_scope(|| {
+                    tracing::info_span!("history").in_scope(|| self.history_store().append(entry))
+                });
+                if let Err(e) = appended {
                     log::error!("failed to append history: {e}");
+                    history_written = false;
                 }
             }
         }
 
-        Ok(RunSessionResponse {
-            stage,
-            final_text,
-            error,
+        // Keep the pending audio around if History could not be written.
+        if let Some(id) = pending_id.filter(|_| history_written)
+            && let Err(e) = pending_store.complete(&id)
+        {
+            log::warn!("failed to clear pending session: {e}");
+        }
+
+        Ok(response)
+    }
+
+    /// Sessions that crashed before their result reached History.
+    pub fn pending_sessions(&self) -> PendingSessionStore {
+        PendingSessionStore::at_dir(self.local_data_dir.join("pending"))
+    }
+
+    /// Re-transcribe audio left behind by crashed sessions and store the results in History.
+    /// Sessions still running, in this process or another, are skipped.
+    ///
+    /// Recovered text is not inserted anywhere (the original target is gone). Sessions whose
+    /// transcription fails stay pending so a later call can retry them.
+    pub async fn recover_pending_sessions(&self) -> anyhow::Result<Vec<HistoryEntry>> {
+        let store = self.pending_sessions();
+        let pending = store.list()?;
+        if pending.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let cfg = self.load_session_config()?;
+        let stt = build_stt_from_config(&cfg)?;
+        let history = self.history_store();
+
+        let mut recovered = Vec::new();
+        for p in pending {
+            let audio = store
+                .audio_file(&p.id)
+                .and_then(|path| voicewin_audio::decode_file_16k(&path))
+                .map(|samples| AudioInput {
+                    sample_rate_hz: 16_000,
+                    samples,
+                });
+            let (text, stage, error) = match audio {
+                // Crashed before a word was captured: nothing to recover.
+                Ok(audio) if audio.samples.is_empty() => {
+                    store.complete(&p.id)?;
+                    continue;
+                }
+                Ok(audio) => {
+                    let res = stt
+                        .transcribe(
+                            &audio,
+                            &cfg.defaults.stt_provider,
+                            &cfg.defaults.stt_model,
+                            &cfg.defaults.language,
+                        )
+                        .await;
+                    match res {
+                        Ok(t) => {
+                            let text = filter_transcription_output_for(
+                                &t.text,
+                                OutputFormat::Plain,
+                                cfg.defaults.disfluency_filter,
+                            );
+                            if text.trim().is_empty() {
+                                let msg = "No speech detected in recovered audio.";
+                                (text, "failed", Some(msg.to_string()))
+                            } else {
+                                (text, "recovered", None)
+                            }
+                        }
+                        Err(e) => {
+                            log::warn!("recovery transcription failed for {}: {e}", p.id);
+                            continue;
+                        }
+                    }
+                }
+                // Unreadable audio will never recover; record the loss and drop it.
+                Err(e) => (
+                    String::new(),
+                    "failed",
+                    Some(format!("Recovery failed: {e}")),
+                ),
+            };
+
+            let entry = HistoryEntry {
+                ts_unix_ms: p.started_unix_ms,
+                app_process_name: p.app_process_name,
+                app_exe_path: p.app_exe_path,
+                app_window_title: p.app_window_title,
+                text,
+                stage: stage.into(),
+                error,
+                ..Default::default()
+            }
+            .redacted(&cfg.redaction);
+            history.append(entry.clone())?;
+            store.complete(&p.id)?;
+            recovered.push(entry);
+        }
+
+        Ok(recovered)
+    }
+
+    /// Transcribe an audio file (WAV/MP3/M4A/OGG) with the configured STT, enhance it when
+    /// enhancement is on, and store the result in History. Nothing is inserted anywhere.
+    pub async fn transcribe_file(&self, path: &Path) -> anyhow::Result<HistoryEntry> {
+        let owned = path.to_path_buf();
+        let samples =
+            tokio::task::spawn_blocking(move || voicewin_audio::decode_file_16k(&owned)).await??;
+        let audio = AudioInput {
+            sample_rate_hz: 16_000,
+            samples,
+        };
+
+        let cfg = self.load_session_config()?;
+        let transcript = build_stt_from_config(&cfg)?
+            .transcribe(
+                &audio,
+                &cfg.defaults.stt_provider,
+                &cfg.defaults.stt_model,
+                &cfg.defaults.language,
+            )
+            .await?;
+
+        let llm = match self.get_openai_api_key_present() {
+            Ok(true) => Some(build_llm_provider(&cfg)?),
+            _ => None,
+        };
+        let source = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let now = now_unix_ms();
+        let (entry, enhanced) =
+            file_entry_with(&cfg, &transcript.text, llm.as_deref(), source, now).await;
+        self.record_usage(&usage_records(
+            now,
+            Some(&transcript),
+            audio_duration_ms(&audio),
+            enhanced.as_ref(),
+        ));
+        let entry = entry.redacted(&cfg.redaction);
+        self.history_store().append(entry.clone())?;
+        Ok(entry)
+    }
+
+    /// Transcribe an utterance heard by the wake listener. Always uses a local model,
+    /// whichever STT provider is selected, so always-on audio never leaves the machine.
+    pub async fn transcribe_wake_utterance(&self, samples: Vec<f32>) -> anyhow::Result<String> {
+        let cfg = self.load_config()?;
+        // A few words don't need more than the bundled bootstrap model, the smallest one.
+        let local_data_dir = self.local_data_dir.as_path();
+        let bootstrap = installed_bootstrap_model_path(local_data_dir);
+        let model = if bootstrap.exists() {
+            bootstrap
+        } else {
+            choose_default_local_stt_model_path(local_data_dir)
+        };
+        if !model.exists() {
+            anyhow::bail!("The wake phrase needs a local model. Download one on the Models page.");
+        }
+
+        let audio = AudioInput {
+            sample_rate_hz: 16_000,
+            samples,
+        };
+        let transcript = build_stt_from_config(&cfg)?
+            .transcribe(
+                &audio,
+                STT_PROVIDER_LOCAL,
+                &model.to_string_lossy(),
+                &cfg.defaults.language,
+            )
+            .await?;
+        Ok(transcript.text)
+    }
+
+    /// Transcribe one meeting chunk and append it, stamped with `offset_ms` into the meeting
+    /// that started at `started_unix_ms`, to the meeting note or else the scratchpad.
+    /// Returns the appended line, or `None` when the chunk held no words.
+    pub async fn append_meeting_chunk(
+        &self,
+        audio: &AudioInput,
+        offset_ms: u64,
+        started_unix_ms: i64,
+    ) -> anyhow::Result<Option<String>> {
+        let cfg = self.load_session_config()?;
+        let transcript = build_stt_from_config(&cfg)?
+            .transcribe(
+                audio,
+                &cfg.defaults.stt_provider,
+                &cfg.defaults.stt_model,
+                &cfg.defaults.language,
+            )
+            .await?;
+        let now = now_unix_ms();
+        self.record_usage(&usage_records(
+            now,
+            Some(&transcript),
+            audio_duration_ms(audio),
+            None,
+        ));
+
+        let text = basic_cleanup(&transcript.text);
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        let line = format_meeting_line(offset_ms, &text);
+        match cfg.meeting.active_note_path() {
+            Some(template) => {
+                let path = PathBuf::from(expand_note_path(template, started_unix_ms));
+                append_note(&path, &format!("{line}\n"))?;
+            }
+            None => self.scratchpad.append(&line, None, now)?,
+        }
+        Ok(Some(line))
+    }
+
+    /// Re-run enhancement with `prompt_id` over the History entries matching `filter` and
+    /// replace their text. Entries with nothing to enhance (failures, digests, hash-only
+    /// redaction) are left alone.
+    pub async fn bulk_enhance_history<F>(
+        &self,
+        filter: &HistoryFilter,
+        prompt_id: &PromptId,
+        on_progress: F,
+    ) -> anyhow::Result<BulkEnhanceProgress>
+    where
+        F: Fn(BulkEnhanceProgress),
+    {
+        if !self.get_openai_api_key_present()? {
+            anyhow::bail!("Add an LLM API key before re-enhancing History.");
+        }
+        let cfg = self.config_store.load()?;
+        if cfg.offline_mode {
+            anyhow::bail!("Offline mode is on; re-enhancing History needs the LLM.");
+        }
+        let llm = build_llm_provider(&cfg)?;
+        self.bulk_enhance_with(&cfg, llm, filter, prompt_id, on_progress)
+            .await
+    }
+
+    async fn bulk_enhance_with<F>(
+        &self,
+        cfg: &AppConfig,
+        llm: Arc<dyn LlmProvider>,
+        filter: &HistoryFilter,
+        prompt_id: &PromptId,
+        on_progress: F,
+    ) -> anyhow::Result<BulkEnhanceProgress>
+    where
+        F: Fn(BulkEnhanceProgress),
+    {
+        let prompt = cfg
+            .prompts
+            .iter()
+            .find(|p| &p.id == prompt_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("unknown prompt"))?;
+
+        let history = self.history_store();
+        let entries: Vec<HistoryEntry> = history
+            .query(filter)?
+            .into_iter()
+            .filter(|e| e.error.is_none() && is_dictated_text(e))
+            .collect();
+
+        let mut progress = BulkEnhanceProgress {
+            total: entries.len(),
+            ..Default::default()
+        };
+        on_progress(progress);
+
+        // Request pacing and 429 back-off happen in `voicewin_providers::runtime`.
+        let permits = Arc::new(tokio::sync::Semaphore::new(BULK_ENHANCE_CONCURRENCY));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for entry in entries {
+            let (llm, defaults, prompt) = (llm.clone(), cfg.defaults.clone(), prompt.clone());
+            let decoding = cfg.llm_decoding.clone();
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                // Start from what was said, not from an earlier enhancement of it.
+                let source = entry
+                    .raw_transcript
+                    .as_deref()
+                    .filter(|t| !t.trim().is_empty())
+                    .unwrap_or(&entry.text);
+                let res =
+                    enhance_with_prompt(llm.as_ref(), &defaults, &decoding, &prompt, source).await;
+                (entry, res)
+            });
+        }
+
+        // Results are written as they arrive so an interrupted run keeps what it finished.
+        let mut usage = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (entry, res) = joined?;
+            progress.done += 1;
+            if let Ok(out) = &res {
+                usage.extend(usage_records(now_unix_ms(), None, 0, Some(out)));
+            }
+            match res {
+                Ok(out) if !out.text.trim().is_empty() => {
+                    let updated = HistoryEntry {
+                        text: out.text,
+                        ..entry.clone()
+                    }
+                    .redacted(&cfg.redaction);
+                    history.set_enhanced_text(
+                        entry.ts_unix_ms,
+                        &entry.text,
+                        updated.text,
+                        prompt.id.clone(),
+                    )?;
+                }
+                Ok(_) => {
+                    log::warn!("bulk enhancement returned no text for {}", entry.ts_unix_ms);
+                    progress.failed += 1;
+                }
+                Err(e) => {
+                    log::warn!("bulk enhancement failed for {}: {e}", entry.ts_unix_ms);
+                    progress.failed += 1;
+                }
+            }
+            on_progress(progress);
+        }
+        self.record_usage(&usage);
+
+        Ok(progress)
+    }
+
+    /// Run `transcript` through two prompts at once, for tuning a prompt side by side with
+    /// another. Nothing is inserted or written to History; usage is still recorded.
+    pub async fn compare_prompts(
+        &self,
+        transcript: &str,
+        prompt_a: &PromptId,
+        prompt_b: &PromptId,
+    ) -> anyhow::Result<PromptComparison> {
+        if !self.get_openai_api_key_present()? {
+            anyhow::bail!("Add an LLM API key before comparing prompts.");
+        }
+        let cfg = self.config_store.load()?;
+        if cfg.offline_mode {
+            anyhow::bail!("Offline mode is on; comparing prompts needs the LLM.");
+        }
+        let llm = build_llm_provider(&cfg)?;
+        self.compare_prompts_with(&cfg, llm.as_ref(), transcript, prompt_a, prompt_b)
+            .await
+    }
+
+    async fn compare_prompts_with(
+        &self,
+        cfg: &AppConfig,
+        llm: &dyn LlmProvider,
+        transcript: &str,
+        prompt_a: &PromptId,
+        prompt_b: &PromptId,
+    ) -> anyhow::Result<PromptComparison> {
+        if transcript.trim().is_empty() {
+            anyhow::bail!("Enter a transcript to compare the prompts on.");
+        }
+        let find = |id: &PromptId| {
+            cfg.prompts
+                .iter()
+                .find(|p| &p.id == id)
+                .ok_or_else(|| anyhow::anyhow!("unknown prompt"))
+        };
+        let (a, b) = (find(prompt_a)?, find(prompt_b)?);
+
+        let ((res_a, ms_a), (res_b, ms_b)) = tokio::join!(
+            timed_enhance(llm, cfg, a, transcript),
+            timed_enhance(llm, cfg, b, transcript),
+        );
+
+        let usage: Vec<UsageRecord> = [&res_a, &res_b]
+            .into_iter()
+            .filter_map(|r| r.as_ref().ok())
+            .flat_map(|out| usage_records(now_unix_ms(), None, 0, Some(out)))
+            .collect();
+        self.record_usage(&usage);
+
+        Ok(PromptComparison {
+            a: PromptRun::new(a, res_a, ms_a),
+            b: PromptRun::new(b, res_b, ms_b),
         })
     }
 
+    /// Power Mode profile drafts for the apps dictated into most often that have no profile yet.
+    pub fn suggest_profiles(&self) -> anyhow::Result<Vec<ProfileSuggestion>> {
+        let cfg = self.config_store.load()?;
+        let sessions: Vec<AppIdentity> = self
+            .history_store()
+            .load()?
+            .into_iter()
+            .filter(|e| e.stage != DIGEST_STAGE)
+            .map(|e| AppIdentity {
+                exe_path: e.app_exe_path.map(ExePath::new),
+                process_name: e.app_process_name.map(ProcessName::new),
+                window_title: e.app_window_title.map(WindowTitle::new),
+                ..AppIdentity::new()
+            })
+            .collect();
+        Ok(suggest_profiles(
+            &sessions,
+            &cfg.profiles,
+            MAX_PROFILE_SUGGESTIONS,
+        ))
+    }
+
+    /// Summarize the last completed digest period with the configured LLM.
+    ///
+    /// The digest is stored in History (stage `"digest"`) and appended to the digest note when
+    /// one is configured. Returns `None` when digests are off, the period already has a digest,
+    /// or nothing was dictated in it, so the shell can call this on a timer.
+    pub async fn generate_due_digest(&self) -> anyhow::Result<Option<HistoryEntry>> {
+        let cfg = self.config_store.load()?;
+        if !cfg.digest.enabled || cfg.offline_mode {
+            return Ok(None);
+        }
+        let llm = build_llm_provider(&cfg)?;
+        self.generate_digest_with(&cfg, llm.as_ref(), now_unix_ms())
+            .await
+    }
+
+    async fn generate_digest_with(
+        &self,
+        cfg: &AppConfig,
+        llm: &dyn LlmProvider,
+        now_unix_ms: i64,
+    ) -> anyhow::Result<Option<HistoryEntry>> {
+        let settings = &cfg.digest;
+        let window = digest_window(settings.period, now_unix_ms);
+        let history = self.history_store();
+        let entries = history.load()?;
+
+        // Digests are stamped with their period's end, which identifies them across restarts.
+        if entries
+            .iter()
+            .any(|e| e.stage == DIGEST_STAGE && e.ts_unix_ms == window.end_unix_ms)
+        {
+            return Ok(None);
+        }
+
+        let items: Vec<DigestItem> = entries
+            .into_iter()
+            .filter(|e| {
+                e.stage != DIGEST_STAGE && e.error.is_none() && window.contains(e.ts_unix_ms)
+            })
+            .filter(|e| !e.text.trim().is_empty())
+            .map(|e| DigestItem {
+                ts_unix_ms: e.ts_unix_ms,
+                app: e.app_process_name,
+                text: e.text,
+            })
+            .collect();
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let out = llm
+            .enhance(
+                &cfg.defaults.llm_base_url,
+                "",
+                &cfg.defaults.llm_model,
+                settings.system_prompt(),
+                &build_digest_user_message(settings.period, &items),
+            )
+            .await?;
+        self.record_usage(&usage_records(now_unix_ms, None, 0, Some(&out)));
+        let digest = out.text;
+        if digest.trim().is_empty() {
+            anyhow::bail!("The LLM returned an empty digest.");
+        }
+
+        if let Some(template) = settings.active_note_path() {
+            let path = PathBuf::from(expand_note_path(template, window.start_unix_ms));
+            append_note(&path, &format_digest_note(settings.period, window, &digest))?;
+        }
+
+        let entry = HistoryEntry {
+            ts_unix_ms: window.end_unix_ms,
+            text: digest.trim().to_string(),
+            stage: DIGEST_STAGE.into(),
+            tags: vec!["digest".into()],
+            ..Default::default()
+        }
+        .redacted(&cfg.redaction);
+        history.append(entry.clone())?;
+        Ok(Some(entry))
+    }
+}
+
+const MAX_PROFILE_SUGGESTIONS: usize = 5;
+
+/// One side of `compare_prompts`. A failed call has `error` set and no `text`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromptRun {
+    pub prompt_id: PromptId,
+    pub prompt_title: String,
+    pub text: Option<String>,
+    pub model: Option<String>,
+    pub elapsed_ms: u64,
+    pub error: Option<VoicewinError>,
+}
+
+impl PromptRun {
+    fn new(prompt: &PromptTemplate, res: anyhow::Result<EnhancedText>, elapsed_ms: u64) -> Self {
+        let (text, model, error) = match res {
+            Ok(out) => (Some(out.text), Some(out.model), None),
+            Err(e) => (None, None, Some(classify_error(&e, ErrorKind::Provider))),
+        };
+        Self {
+            prompt_id: prompt.id.clone(),
+            prompt_title: prompt.title.clone(),
+            text,
+            model,
+            elapsed_ms,
+            error,
+        }
+    }
+}
+
+/// Outputs of the same transcript under two prompts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromptComparison {
+    pub a: PromptRun,
+    pub b: PromptRun,
+}
+
+/// Enhancement requests in flight at once during `bulk_enhance_history`.
+const BULK_ENHANCE_CONCURRENCY: usize = 4;
+
+/// Progress of `bulk_enhance_history`, reported once up front and after every entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BulkEnhanceProgress {
+    pub total: usize,
+    /// Entries finished so far, including failed ones.
+    pub done: usize,
+    pub failed: usize,
+}
+
+/// Run `prompt` over text that is not headed for a window, so no app context is attached.
+/// The returned text is already post-processed.
+async fn enhance_with_prompt(
+    llm: &dyn LlmProvider,
+    defaults: &GlobalDefaults,
+    decoding: &LlmDecodingSettings,
+    prompt: &PromptTemplate,
+    text: &str,
+) -> anyhow::Result<EnhancedText> {
+    let ctx = EnhancementContext {
+        currently_selected_text: None,
+        clipboard_context: None,
+        current_window_context: None,
+        custom_vocabulary: None,
+        output_format: defaults.output_format,
+        disfluency_filter: defaults.disfluency_filter,
+    };
+    let built = build_enhancement_prompt(text, prompt, &ctx);
+    let mut out = llm
+        .enhance_with_decoding(
+            &defaults.llm_base_url,
+            "",
+            &defaults.llm_model,
+            &built.system_message,
+            &built.user_message,
+            &prompt.decoding.or(decoding),
+        )
+        .await?;
+    out.text = post_process_llm_output(&out.text);
+    Ok(out)
+}
+
+/// [`enhance_with_prompt`] and how long the call took.
+async fn timed_enhance(
+    llm: &dyn LlmProvider,
+    cfg: &AppConfig,
+    prompt: &PromptTemplate,
+    text: &str,
+) -> (anyhow::Result<EnhancedText>, u64) {
+    let t0 = std::time::Instant::now();
+    let res = enhance_with_prompt(llm, &cfg.defaults, &cfg.llm_decoding, prompt, text).await;
+    (res, ms(t0.elapsed()))
+}
+
+fn audio_duration_ms(audio: &AudioInput) -> u64 {
+    audio.samples.len() as u64 * 1000 / u64::from(audio.sample_rate_hz.max(1))
+}
+
+/// True when no sample in the capture reaches `floor` (linear amplitude).
+fn is_silent(audio: &AudioInput, floor: f32) -> bool {
+    audio.samples.iter().all(|s| s.abs() < floor)
+}
+
+/// Billable records for one STT and/or LLM call. Local transcription is free and not recorded.
+fn usage_records(
+    ts_unix_ms: i64,
+    transcript: Option<&Transcript>,
+    audio_ms: u64,
+    enhanced: Option<&EnhancedText>,
+) -> Vec<UsageRecord> {
+    let mut records = Vec::new();
+    if let Some(t) = transcript.filter(|t| t.provider != STT_PROVIDER_LOCAL && audio_ms > 0) {
+        records.push(UsageRecord::audio(
+            ts_unix_ms,
+            &t.provider,
+            &t.model,
+            audio_ms,
+        ));
+    }
+    if let Some(e) = enhanced
+        && let Some(usage) = e.usage
+    {
+        records.push(UsageRecord::tokens(
+            ts_unix_ms,
+            &e.provider,
+            &e.model,
+            usage,
+        ));
+    }
+    records
+}
+
+/// History stage of transcribed audio files.
+pub const FILE_STAGE: &str = "file";
+
+/// Turn a file's transcript into a History entry, running the default enhancement prompt
+/// when enhancement is on and `llm` is available. The file name stands in for the window
+/// title so History shows where the text came from.
+async fn file_entry_with(
+    cfg: &AppConfig,
+    raw_text: &str,
+    llm: Option<&dyn LlmProvider>,
+    source: String,
+    now_unix_ms: i64,
+) -> (HistoryEntry, Option<EnhancedText>) {
+    let defaults = &cfg.defaults;
+    let mut text = filter_transcription_output_for(
+        raw_text,
+        defaults.output_format,
+        defaults.disfluency_filter,
+    );
+    let mut error = None;
+
+    let prompt = defaults
+        .prompt_id
+        .as_ref()
+        .and_then(|id| cfg.prompts.iter().find(|p| &p.id == id))
+        .or_else(|| cfg.prompts.first());
+    let mut enhanced = None;
+    if text.trim().is_empty() {
+        error = Some("No speech detected in the audio file.".to_string());
+    } else if let (true, Some(llm), Some(prompt)) = (defaults.enable_enhancement, llm, prompt) {
+        match enhance_with_prompt(llm, defaults, &cfg.llm_decoding, prompt, &text).await {
+            Ok(out) => {
+                text = out.text.clone();
+                enhanced = Some(out);
+            }
+            Err(e) => error = Some(format!("Enhancement failed; kept raw transcript. ({e})")),
+        }
+    }
+    if enhanced.is_none() && defaults.basic_cleanup && defaults.output_format == OutputFormat::Plain
+    {
+        text = basic_cleanup(&text);
+    }
+
+    let entry = HistoryEntry {
+        ts_unix_ms: now_unix_ms,
+        app_window_title: Some(source),
+        text,
+        stage: FILE_STAGE.into(),
+        error,
+        tags: vec!["file".into()],
+        raw_transcript: Some(raw_text.to_string()),
+        enhanced_text: enhanced.as_ref().map(|e| e.text.clone()),
+        prompt_id: enhanced.as_ref().and(prompt).map(|p| p.id.clone()),
+        ..Default::default()
+    };
+    (entry, enhanced)
+}
+
+/// History stage of LLM-written digests.
+pub const DIGEST_STAGE: &str = "digest";
+
+/// Whether `entry` holds dictated text: not an LLM-written digest, and not stored as a hash
+/// only by redaction.
+pub fn is_dictated_text(entry: &HistoryEntry) -> bool {
+    entry.stage != DIGEST_STAGE
+        && !entry.text.trim().is_empty()
+        && !entry.text.starts_with("sha256:")
+}
+
+fn append_note(path: &std::path::Path, text: &str) -> anyhow::Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+type StageStart = Option<(&'static str, tokio::time::Instant)>;
+
+/// Drive a session pipeline, failing it once the stage last reported on `stage` has run
+/// longer than its limit. Dropping the pipeline aborts the in-flight request.
+async fn with_stage_timeouts<T>(
+    pipeline: impl Future<Output = anyhow::Result<T>>,
+    mut stage: tokio::sync::watch::Receiver<StageStart>,
+    limit_for: impl Fn(&str) -> Option<Duration>,
+) -> anyhow::Result<T> {
+    tokio::pin!(pipeline);
+    loop {
+        let current = *stage.borrow_and_update();
+        let limit = current.and_then(|(name, started)| {
+            limit_for(name).map(|limit| (name, limit, started + limit))
+        });
+        let deadline = limit
+            .map(|(_, _, at)| at)
+            .unwrap_or_else(tokio::time::Instant::now);
+
+        // Biased so a stage change reported while polling the pipeline wins over the
+        // previous stage's deadline.
+        tokio::select! {
+            biased;
+            res = &mut pipeline => return res,
+            Ok(()) = stage.changed() => {}
+            _ = tokio::time::sleep_until(deadline), if limit.is_some() => {
+                let (name, limit, _) = limit.expect("guarded by select condition");
+                return Err(stage_timeout_error(name, limit));
+            }
+        }
+    }
+}
+
+fn stage_timeout_error(stage: &str, limit: Duration) -> anyhow::Error {
+    let what = match stage {
+        "transcribing" => "Transcription",
+        "enhancing" => "Enhancement",
+        "inserting" => "Inserting text",
+        other => other,
+    };
+    VoicewinError::new(
+        ErrorKind::Network,
+        format!(
+            "{what} timed out after {}s. Check your network connection and try again.",
+            limit.as_secs()
+        ),
+    )
+    .into()
+}
+
+fn now_unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+async fn llm_check_with(cfg: &AppConfig, api_key: Option<String>) -> OnboardingCheck {
+    let uses_llm = cfg.defaults.enable_enhancement
+        || cfg
+            .profiles
+            .iter()
+            .any(|p| p.enabled && p.overrides.enable_enhancement == Some(true))
+        || cfg.digest.enabled;
+    if !uses_llm {
+        return OnboardingCheck::skipped(OnboardingStep::Llm, "AI enhancement is turned off.");
+    }
+
+    let base_url = cfg.defaults.llm_base_url.trim();
+    let api_key = api_key.unwrap_or_default();
+    if base_url.is_empty() || api_key.trim().is_empty() {
+        return OnboardingCheck::pending(
+            OnboardingStep::Llm,
+            "Set an LLM endpoint and API key to use AI enhancement.",
+            OnboardingAction::ConfigureLlm,
+        );
+    }
+
+    match OpenAiCompatibleLlmProvider::new(api_key)
+        .with_endpoint_flavor(cfg.llm_endpoint_flavor.clone())
+        .check_reachable(base_url)
+        .await
+    {
+        Ok(()) => OnboardingCheck::ok(OnboardingStep::Llm),
+        Err(e) => OnboardingCheck::failed(
+            OnboardingStep::Llm,
+            e.to_string(),
+            Some(OnboardingAction::ConfigureLlm),
+        ),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use voicewin_core::enhancement::{PromptMode, PromptTemplate};
-    use voicewin_core::power_mode::GlobalDefaults;
-    use voicewin_core::types::{InsertMode, PromptId};
-
-    #[tokio::test]
-    async fn service_roundtrip_and_run_session_smoke() {
-        let dir = tempfile::tempdir().unwrap();
-        let config_path = dir.path().join("config.json");
+    use voicewin_core::enhancement::PromptMode;
+    use voicewin_core::usage::estimate_cost;
 
+    /// A service keeping its config and stores in `dir`, with no foreground app.
+    fn test_service(dir: &tempfile::TempDir) -> AppService {
         let ctx = voicewin_platform::test::TestContextProvider::new(
-            voicewin_core::types::AppIdentity::new().with_process_name("slack.exe"),
+            voicewin_core::types::AppIdentity::new(),
             Default::default(),
         )
         .boxed();
-        let inserter = Arc::new(voicewin_platform::test::StdoutInserter);
+        AppService::new(
+            dir.path().join("config.json"),
+            ctx,
+            Arc::new(voicewin_platform::test::StdoutInserter),
+        )
+    }
 
-        let svc = AppService::new(config_path.clone(), ctx, inserter);
+    #[tokio::test]
+    async fn hung_stage_times_out_while_unlimited_stages_run() {
+        let (tx, rx) = tokio::sync::watch::channel(None);
+        let limit_for = |stage: &str| (stage == "transcribing").then(|| Duration::from_millis(50));
+
+        let hung = async {
+            tx.send_replace(Some(("transcribing", tokio::time::Instant::now())));
+            std::future::pending::<anyhow::Result<()>>().await
+        };
+        let err = with_stage_timeouts(hung, rx.clone(), limit_for)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().starts_with("Transcription timed out"),
+            "{err}"
+        );
+
+        let slow_but_unlimited = async {
+            tx.send_replace(Some(("enhancing", tokio::time::Instant::now())));
+            tokio::time::sleep(Duration::from_millis(120)).await;
+            Ok(42)
+        };
+        let res = with_stage_timeouts(slow_but_unlimited, rx, limit_for).await;
+        assert_eq!(res.unwrap(), 42);
+    }
+
+    #[test]
+    fn silence_is_judged_by_the_loudest_sample() {
+        let floor = voicewin_core::config::SilentSessionSettings::default().noise_floor();
+        let mut audio = AudioInput {
+            sample_rate_hz: 16_000,
+            samples: vec![0.001; 16_000],
+        };
+        assert!(is_silent(&audio, floor));
+
+        audio.samples[8_000] = -0.2;
+        assert!(!is_silent(&audio, floor));
+    }
+
+    #[tokio::test]
+    async fn service_roundtrip_and_run_session_smoke() {
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
 
         // CI runners (and some dev machines) have no audio input device.
         // The service should still be constructible without panicking.
@@ -338,30 +1795,18 @@ mod tests {
             let _ = svc.cancel_recording().await;
         }
 
-        let cfg = AppConfig {
-            defaults: GlobalDefaults {
-                enable_enhancement: false,
-                prompt_id: None,
-                insert_mode: InsertMode::Paste,
-                stt_provider: "local".into(),
-                stt_model: "./missing.bin".into(),
-                language: "en".into(),
-                llm_base_url: "https://example.com/v1".into(),
-                llm_model: "gpt-4o-mini".into(),
-                microphone_device: None,
-                history_enabled: true,
-                context: voicewin_core::context::ContextToggles::default(),
-            },
-            profiles: vec![],
+        let mut cfg = AppConfig {
             prompts: vec![PromptTemplate {
                 id: PromptId::new(),
                 title: "Default".into(),
                 mode: PromptMode::Enhancer,
                 prompt_text: "Fix.".into(),
                 trigger_words: vec!["rewrite".into()],
+                decoding: Default::default(),
             }],
-            llm_api_key_present: false,
+            ..AppConfig::for_tests()
         };
+        cfg.defaults.stt_model = "./missing.bin".into();
 
         svc.save_config(&cfg).unwrap();
         let loaded = svc.load_config().unwrap();
@@ -378,10 +1823,432 @@ mod tests {
             .run_session(
                 RunSessionRequest {
                     transcript: "hi".into(),
-                    warning: None,
+                    ..Default::default()
                 },
                 audio,
             )
             .await;
     }
+
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    #[tokio::test]
+    async fn disabled_recording_refuses_to_open_the_mic() {
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
+
+        svc.set_recording_disabled(true).await;
+        assert!(svc.recording_disabled());
+        assert!(matches!(
+            svc.start_recording().await,
+            Err(AudioCaptureError::Disabled)
+        ));
+        assert!(svc.recorder.lock().await.is_none());
+
+        svc.set_recording_disabled(false).await;
+        assert!(!svc.recording_disabled());
+    }
+
+    #[tokio::test]
+    async fn llm_check_reports_skipped_missing_rejected_and_ok() {
+        use voicewin_core::onboarding::CheckStatus;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(header("authorization", "Bearer good"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": [] })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let mut cfg = AppConfig::for_tests();
+        cfg.defaults.enable_enhancement = false;
+        assert_eq!(
+            llm_check_with(&cfg, Some("good".into())).await.status,
+            CheckStatus::Skipped
+        );
+
+        cfg.defaults.enable_enhancement = true;
+        cfg.defaults.llm_base_url = server.uri();
+        let missing = llm_check_with(&cfg, None).await;
+        assert_eq!(missing.status, CheckStatus::Pending);
+        assert_eq!(missing.action, Some(OnboardingAction::ConfigureLlm));
+
+        let rejected = llm_check_with(&cfg, Some("bad".into())).await;
+        assert_eq!(rejected.status, CheckStatus::Failed);
+        assert!(rejected.detail.unwrap().contains("rejected the API key"));
+
+        assert_eq!(
+            llm_check_with(&cfg, Some("good".into())).await.status,
+            CheckStatus::Ok
+        );
+    }
+
+    #[tokio::test]
+    async fn digest_summarizes_the_last_period_once() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "- Shipped the release." } }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
+
+        let mut cfg = AppConfig::for_tests();
+        cfg.defaults.llm_base_url = server.uri();
+        cfg.digest.enabled = true;
+        cfg.digest.note_path = Some(
+            dir.path()
+                .join("digests")
+                .join("{date}.md")
+                .display()
+                .to_string(),
+        );
+
+        // Thursday 2026-10-15T18:24:09Z; the daily digest covers 2026-10-14.
+        let now = 1_792_088_649_000;
+        let yesterday = now - 24 * 60 * 60 * 1000;
+        let history = svc.history_store();
+        for (ts, text, error) in [
+            (yesterday, "Ship the release", None),
+            (yesterday + 1, "", Some("No speech detected.")),
+            (now, "Not part of the digest", None),
+        ] {
+            history
+                .append(HistoryEntry {
+                    ts_unix_ms: ts,
+                    app_process_name: Some("slack.exe".into()),
+                    text: text.into(),
+                    stage: "done".into(),
+                    error: error.map(Into::into),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let llm = voicewin_runtime::llm::OpenAiCompatibleLlmProvider::new("test-key");
+        let entry = svc
+            .generate_digest_with(&cfg, &llm, now)
+            .await
+            .unwrap()
+            .expect("a digest for yesterday");
+        assert_eq!(entry.stage, DIGEST_STAGE);
+        assert_eq!(entry.text, "- Shipped the release.");
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("[slack.exe] Ship the release"), "{body}");
+        assert!(!body.contains("Not part of the digest"), "{body}");
+
+        let note =
+            std::fs::read_to_string(dir.path().join("digests").join("2026-10-14.md")).unwrap();
+        assert_eq!(
+            note,
+            "\n## Daily digest 2026-10-14\n\n- Shipped the release.\n"
+        );
+
+        // The same period is never summarized twice (the mock expects exactly one call).
+        assert!(
+            svc.generate_digest_with(&cfg, &llm, now)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn file_transcripts_are_enhanced_into_history_entries() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "Call Sam at noon." } }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut cfg = AppConfig {
+            prompts: voicewin_runtime::defaults::default_prompt_templates(),
+            ..AppConfig::for_tests()
+        };
+        cfg.defaults.llm_base_url = server.uri();
+
+        // Enhancement off: the raw transcript is kept and the LLM is not called.
+        let llm = voicewin_runtime::llm::OpenAiCompatibleLlmProvider::new("test-key");
+        let (raw, _) =
+            file_entry_with(&cfg, "call sam at noon", Some(&llm), "memo.m4a".into(), 7).await;
+        assert_eq!(raw.text, "call sam at noon");
+        assert_eq!(raw.stage, FILE_STAGE);
+        assert_eq!(raw.app_window_title.as_deref(), Some("memo.m4a"));
+
+        cfg.defaults.enable_enhancement = true;
+        let (enhanced, out) =
+            file_entry_with(&cfg, "call sam at noon", Some(&llm), "memo.m4a".into(), 7).await;
+        assert_eq!(
+            out.map(|o| o.provider).as_deref(),
+            Some("openai-compatible")
+        );
+        assert_eq!(enhanced.text, "Call Sam at noon.");
+        assert_eq!(enhanced.error, None);
+
+        let (silent, _) = file_entry_with(&cfg, "  ", Some(&llm), "memo.m4a".into(), 7).await;
+        assert!(silent.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn bulk_enhance_rewrites_matching_entries() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "Polished." } }],
+                "usage": { "prompt_tokens": 500_000, "completion_tokens": 50_000 }
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
+
+        let mut cfg = AppConfig {
+            prompts: voicewin_runtime::defaults::default_prompt_templates(),
+            ..AppConfig::for_tests()
+        };
+        cfg.defaults.llm_base_url = server.uri();
+        let prompt_id = cfg.prompts[0].id.clone();
+
+        let history = svc.history_store();
+        for (ts, text, stage, error) in [
+            (10, "first raw", "done", None),
+            (11, "Second, enhanced.", "done", None),
+            (12, "", "error", Some("No speech detected.")),
+            (13, "- A digest", DIGEST_STAGE, None),
+            (99, "outside the range", "done", None),
+        ] {
+            history
+                .append(HistoryEntry {
+                    ts_unix_ms: ts,
+                    text: text.into(),
+                    stage: stage.into(),
+                    error: error.map(Into::into),
+                    // An earlier enhancement of what was said.
+                    raw_transcript: (ts == 11).then(|| "second raw".into()),
+                    enhanced_text: (ts == 11).then(|| text.into()),
+                    prompt_id: (ts == 11).then(PromptId::new),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let filter = HistoryFilter {
+            range: ExportRange {
+                from_unix_ms: Some(10),
+                to_unix_ms: Some(50),
+            },
+            ..Default::default()
+        };
+        let seen = std::sync::Mutex::new(Vec::new());
+        let llm = Arc::new(voicewin_runtime::llm::OpenAiCompatibleLlmProvider::new(
+            "test-key",
+        ));
+        let progress = svc
+            .bulk_enhance_with(&cfg, llm, &filter, &prompt_id, |p| {
+                seen.lock().unwrap().push(p)
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            progress,
+            BulkEnhanceProgress {
+                total: 2,
+                done: 2,
+                failed: 0
+            }
+        );
+        assert_eq!(seen.lock().unwrap().len(), 3);
+
+        let texts: Vec<String> = history
+            .load()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.text)
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "Polished.",
+                "Polished.",
+                "",
+                "- A digest",
+                "outside the range"
+            ]
+        );
+
+        // The LLM got the transcript, and the entry now records the new enhancement.
+        let bodies: Vec<String> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| String::from_utf8_lossy(&r.body).into_owned())
+            .collect();
+        assert!(bodies.iter().any(|b| b.contains("second raw")));
+        assert!(!bodies.iter().any(|b| b.contains("Second, enhanced.")));
+        let second = history
+            .load()
+            .unwrap()
+            .into_iter()
+            .find(|e| e.ts_unix_ms == 11)
+            .unwrap();
+        assert_eq!(second.raw_transcript.as_deref(), Some("second raw"));
+        assert_eq!(second.enhanced_text.as_deref(), Some("Polished."));
+        assert_eq!(second.prompt_id, Some(prompt_id.clone()));
+
+        // Both calls were billed to the default model.
+        let usage = svc.stats_store().load().unwrap();
+        assert_eq!(usage.len(), 2);
+        let est = estimate_cost(&usage, &cfg.pricing);
+        assert_eq!(est.lines[0].model, "gpt-4o-mini");
+        assert_eq!(est.lines[0].prompt_tokens, 1_000_000);
+        assert_eq!(est.total_micros, 150_000 + 60_000);
+
+        let unknown = PromptId::new();
+        assert!(
+            svc.bulk_enhance_with(
+                &cfg,
+                Arc::new(voicewin_runtime::llm::OpenAiCompatibleLlmProvider::new("k")),
+                &filter,
+                &unknown,
+                |_| {}
+            )
+            .await
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn compare_prompts_runs_both_and_keeps_a_failed_side() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_string_contains("Be formal."))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "Dear team, hello." } }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_string_contains("Be casual."))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
+
+        let prompt = |title: &str, text: &str| PromptTemplate {
+            id: PromptId::new(),
+            title: title.into(),
+            mode: PromptMode::Enhancer,
+            prompt_text: text.into(),
+            trigger_words: vec![],
+            decoding: Default::default(),
+        };
+        let mut cfg = AppConfig::for_tests();
+        cfg.defaults.llm_base_url = server.uri();
+        cfg.prompts = vec![
+            prompt("Formal", "Be formal."),
+            prompt("Casual", "Be casual."),
+        ];
+        let (a, b) = (cfg.prompts[0].id.clone(), cfg.prompts[1].id.clone());
+
+        let llm = voicewin_runtime::llm::OpenAiCompatibleLlmProvider::new("test-key");
+        let cmp = svc
+            .compare_prompts_with(&cfg, &llm, "hello team", &a, &b)
+            .await
+            .unwrap();
+        assert_eq!(cmp.a.prompt_title, "Formal");
+        assert_eq!(cmp.a.text.as_deref(), Some("Dear team, hello."));
+        assert!(cmp.a.error.is_none());
+        assert_eq!(cmp.b.prompt_id, b);
+        assert!(cmp.b.text.is_none());
+        assert!(cmp.b.error.is_some());
+
+        assert!(
+            svc.compare_prompts_with(&cfg, &llm, "hello", &a, &PromptId::new())
+                .await
+                .is_err()
+        );
+        assert!(
+            svc.compare_prompts_with(&cfg, &llm, "  ", &a, &b)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn recovery_skips_the_session_still_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
+
+        let (running, _file) = svc.pending_sessions().begin(1).unwrap();
+        let guard = InFlightGuard::new(&running.id);
+
+        // No config exists, so anything past the pending list would fail.
+        assert!(svc.recover_pending_sessions().await.unwrap().is_empty());
+        assert!(svc.history_store().load().unwrap().is_empty());
+
+        drop(guard);
+        assert_eq!(svc.pending_sessions().list().unwrap()[0].id, running.id);
+    }
+
+    #[tokio::test]
+    async fn recovery_drops_a_capture_that_crashed_before_any_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let svc = test_service(&dir);
+        svc.save_config(&AppConfig::for_tests()).unwrap();
+
+        let (crashed, file) = svc.pending_sessions().begin(1).unwrap();
+        // Header only, as the recorder leaves it before the first samples arrive.
+        std::io::Write::write_all(
+            &mut &file,
+            &voicewin_runtime::stt::encode_wav_mono_f32le(&[], 48_000),
+        )
+        .unwrap();
+        drop(InFlightGuard::new(&crashed.id));
+
+        assert!(svc.recover_pending_sessions().await.unwrap().is_empty());
+        assert!(svc.pending_sessions().list().unwrap().is_empty());
+        assert!(svc.history_store().load().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,340 @@
+//! Per-session performance timelines built from `tracing` spans.
+//!
+//! The pipeline crates only emit spans (`session`, `transcribe`, `enhance`, `insert`, plus
+//! provider-specific children). This module provides the subscriber that collects them:
+//! every span tree rooted at a `session` span becomes one [`SessionTrace`]. A few top-level
+//! spans that finish just before a session (`resample` when recording stops) are attached to
+//! the next session, since they belong to its preparation; other stray spans are dropped.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// How many finished sessions are kept for the performance panel.
+pub const MAX_SESSION_TRACES: usize = 20;
+
+const SESSION_SPAN: &str = "session";
+const PRELUDE_SPANS: &[&str] = &["resample"];
+const MAX_PRELUDE_SPANS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpanTiming {
+    pub name: String,
+    /// Nesting level below the session span (0 = direct child or prelude span).
+    pub depth: usize,
+    /// Offset from the session start; negative for spans that finished before it.
+    pub start_ms: i64,
+    pub duration_ms: u64,
+    pub fields: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionTrace {
+    pub started_unix_ms: i64,
+    pub total_ms: u64,
+    pub fields: BTreeMap<String, String>,
+    /// Ordered by start time.
+    pub spans: Vec<SpanTiming>,
+}
+
+struct OpenSpan {
+    name: &'static str,
+    fields: BTreeMap<String, String>,
+    root: u64,
+    depth: usize,
+    started: Instant,
+    started_unix_ms: i64,
+    refs: usize,
+}
+
+struct ClosedSpan {
+    name: &'static str,
+    fields: BTreeMap<String, String>,
+    depth: usize,
+    started: Instant,
+    duration: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    open: HashMap<u64, OpenSpan>,
+    // Finished descendants, keyed by the id of their (still open) root span.
+    children: HashMap<u64, Vec<ClosedSpan>>,
+    prelude: Vec<ClosedSpan>,
+    traces: VecDeque<SessionTrace>,
+}
+
+thread_local! {
+    static ENTERED: std::cell::RefCell<Vec<u64>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// `tracing` subscriber that records span timings for VoiceWin crates only.
+#[derive(Clone, Default)]
+pub struct SessionTraceCollector {
+    state: Arc<Mutex<State>>,
+}
+
+static GLOBAL: OnceLock<SessionTraceCollector> = OnceLock::new();
+
+/// Install the collector as the global `tracing` subscriber (idempotent).
+pub fn install() -> &'static SessionTraceCollector {
+    GLOBAL.get_or_init(|| {
+        let collector = SessionTraceCollector::default();
+        if let Err(e) = tracing::subscriber::set_global_default(collector.clone()) {
+            log::warn!("session tracing unavailable: {e}");
+        }
+        collector
+    })
+}
+
+/// Most recent session timelines from the installed collector, newest first.
+pub fn recent_session_traces(limit: usize) -> Vec<SessionTrace> {
+    GLOBAL.get().map(|c| c.recent(limit)).unwrap_or_default()
+}
+
+impl SessionTraceCollector {
+    /// Most recent session timelines, newest first.
+    pub fn recent(&self, limit: usize) -> Vec<SessionTrace> {
+        let state = self.lock();
+        state.traces.iter().rev().take(limit).cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    fn finish(state: &mut State, id: u64, span: OpenSpan) {
+        let closed = ClosedSpan {
+            name: span.name,
+            fields: span.fields.clone(),
+            depth: span.depth,
+            started: span.started,
+            duration: span.started.elapsed(),
+        };
+
+        if span.root != id {
+            state.children.entry(span.root).or_default().push(closed);
+            return;
+        }
+
+        let mut children = state.children.remove(&id).unwrap_or_default();
+        if span.name != SESSION_SPAN {
+            if !PRELUDE_SPANS.contains(&span.name) {
+                return;
+            }
+            // Preparation work outside a session; keep it for the next one.
+            state.prelude.push(closed);
+            state.prelude.append(&mut children);
+            let excess = state.prelude.len().saturating_sub(MAX_PRELUDE_SPANS);
+            state.prelude.drain(..excess);
+            return;
+        }
+
+        let mut spans: Vec<ClosedSpan> = std::mem::take(&mut state.prelude);
+        spans.append(&mut children);
+        spans.sort_by_key(|s| s.started);
+
+        let offset_ms = |t: Instant| -> i64 {
+            if t >= span.started {
+                (t - span.started).as_millis() as i64
+            } else {
+                -((span.started - t).as_millis() as i64)
+            }
+        };
+        let trace = SessionTrace {
+            started_unix_ms: span.started_unix_ms,
+            total_ms: closed.duration.as_millis() as u64,
+            fields: span.fields,
+            spans: spans
+                .into_iter()
+                .map(|s| SpanTiming {
+                    name: s.name.to_string(),
+                    depth: s.depth.saturating_sub(1),
+                    start_ms: offset_ms(s.started),
+                    duration_ms: s.duration.as_millis() as u64,
+                    fields: s.fields,
+                })
+                .collect(),
+        };
+
+        state.traces.push_back(trace);
+        while state.traces.len() > MAX_SESSION_TRACES {
+            state.traces.pop_front();
+        }
+    }
+}
+
+struct FieldCollector<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for FieldCollector<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+impl Subscriber for SessionTraceCollector {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_span() && metadata.target().starts_with("voicewin")
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let parent = if let Some(p) = attrs.parent() {
+            Some(p.into_u64())
+        } else if attrs.is_contextual() {
+            ENTERED.with(|stack| stack.borrow().last().copied())
+        } else {
+            None
+        };
+
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut FieldCollector(&mut fields));
+
+        let mut state = self.lock();
+        state.next_id += 1;
+        let id = state.next_id;
+
+        let (root, depth) = parent
+            .and_then(|p| state.open.get(&p))
+            .map(|p| (p.root, p.depth + 1))
+            .unwrap_or((id, 0));
+        let started_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        state.open.insert(
+            id,
+            OpenSpan {
+                name: attrs.metadata().name(),
+                fields,
+                root,
+                depth,
+                started: Instant::now(),
+                started_unix_ms,
+                refs: 1,
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(s) = self.lock().open.get_mut(&span.into_u64()) {
+            values.record(&mut FieldCollector(&mut s.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|stack| stack.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        let id = span.into_u64();
+        ENTERED.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().rposition(|&s| s == id) {
+                stack.remove(pos);
+            }
+        });
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        if let Some(s) = self.lock().open.get_mut(&id.into_u64()) {
+            s.refs += 1;
+        }
+        id.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let id = id.into_u64();
+        let mut state = self.lock();
+        let Some(s) = state.open.get_mut(&id) else {
+            return false;
+        };
+        s.refs -= 1;
+        if s.refs > 0 {
+            return false;
+        }
+
+        let span = state.open.remove(&id).expect("span is open");
+        Self::finish(&mut state, id, span);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_spans_into_session_traces() {
+        let collector = SessionTraceCollector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            tracing::info_span!(target: "voicewin_test", "resample", capture_ms = 1200u64)
+                .in_scope(|| {});
+            // e.g. crash recovery transcribing outside any session.
+            tracing::info_span!(target: "voicewin_test", "stt.local_whisper").in_scope(|| {});
+
+            let session = tracing::info_span!(target: "voicewin_test", "session");
+            session.in_scope(|| {
+                tracing::info_span!(target: "voicewin_test", "transcribe", provider = "local")
+                    .in_scope(|| {
+                        tracing::info_span!(target: "voicewin_test", "stt.local_whisper")
+                            .in_scope(|| {});
+                    });
+                tracing::info_span!(target: "voicewin_test", "insert").in_scope(|| {});
+
+                // Spans from other crates are ignored.
+                tracing::info_span!(target: "hyper", "request").in_scope(|| {});
+            });
+            drop(session);
+        });
+
+        let traces = collector.recent(10);
+        assert_eq!(traces.len(), 1);
+        let names: Vec<_> = traces[0]
+            .spans
+            .iter()
+            .map(|s| (s.name.as_str(), s.depth))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("resample", 0),
+                ("transcribe", 0),
+                ("stt.local_whisper", 1),
+                ("insert", 0)
+            ]
+        );
+        assert_eq!(traces[0].spans[0].fields["capture_ms"], "1200");
+        assert_eq!(traces[0].spans[1].fields["provider"], "local");
+    }
+
+    #[test]
+    fn keeps_only_the_most_recent_sessions() {
+        let collector = SessionTraceCollector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            for i in 0..MAX_SESSION_TRACES + 3 {
+                tracing::info_span!(target: "voicewin_test", "session", n = i).in_scope(|| {});
+            }
+        });
+
+        let traces = collector.recent(usize::MAX);
+        assert_eq!(traces.len(), MAX_SESSION_TRACES);
+        assert_eq!(traces[0].fields["n"], (MAX_SESSION_TRACES + 2).to_string());
+    }
+}
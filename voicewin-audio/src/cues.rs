@@ -0,0 +1,163 @@
+// Short synthesized beeps for session state changes.
+//
+// Tones are generated on the fly (no bundled assets) and played on the default output
+// device from a throwaway thread, so callers never block on audio I/O.
+
+use std::f32::consts::TAU;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundCue {
+    Start,
+    Stop,
+    Success,
+    Error,
+}
+
+// Per-note fade in/out, to avoid clicks.
+const FADE_MS: u32 = 6;
+
+impl SoundCue {
+    /// Notes as `(frequency_hz, duration_ms)`; a frequency of 0 is a rest.
+    fn notes(self) -> &'static [(f32, u32)] {
+        match self {
+            // Rising pair: "go".
+            SoundCue::Start => &[(660.0, 70), (880.0, 90)],
+            // Falling pair: "stopped".
+            SoundCue::Stop => &[(880.0, 70), (660.0, 90)],
+            SoundCue::Success => &[(1046.5, 110)],
+            // Two low blips.
+            SoundCue::Error => &[(220.0, 120), (0.0, 60), (220.0, 120)],
+        }
+    }
+}
+
+/// Render `cue` as mono samples at `sample_rate_hz`, scaled by `volume` (0..=1).
+pub fn cue_samples(cue: SoundCue, sample_rate_hz: u32, volume: f32) -> Vec<f32> {
+    let volume = volume.clamp(0.0, 1.0);
+    let sr = sample_rate_hz as f32;
+    let fade = (sample_rate_hz * FADE_MS / 1000).max(1) as usize;
+
+    let mut out = Vec::new();
+    for &(freq, ms) in cue.notes() {
+        let n = (sample_rate_hz as u64 * ms as u64 / 1000) as usize;
+        for i in 0..n {
+            if freq <= 0.0 {
+                out.push(0.0);
+                continue;
+            }
+            let env = (i.min(n - 1 - i) as f32 / fade as f32).min(1.0);
+            out.push((TAU * freq * i as f32 / sr).sin() * env * volume);
+        }
+    }
+    out
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+pub use playback::play_cue;
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod playback {
+    use std::time::Duration;
+
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{FromSample, SampleFormat, SizedSample};
+
+    use super::{SoundCue, cue_samples};
+
+    // Extra time after the last sample so the device drains before the stream drops.
+    const TAIL_MS: u64 = 80;
+
+    /// Play `cue` on the default output device without blocking. Failures are logged.
+    pub fn play_cue(cue: SoundCue, volume: f32) {
+        let spawned = std::thread::Builder::new()
+            .name("voicewin-cue".into())
+            .spawn(move || {
+                if let Err(e) = play_blocking(cue, volume) {
+                    log::warn!("failed to play {cue:?} cue: {e}");
+                }
+            });
+        if let Err(e) = spawned {
+            log::warn!("failed to spawn cue thread: {e}");
+        }
+    }
+
+    fn play_blocking(cue: SoundCue, volume: f32) -> anyhow::Result<()> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| anyhow::anyhow!("no output device found"))?;
+        let config = device.default_output_config()?;
+        let sample_rate_hz = config.sample_rate().0;
+        let channels = config.channels() as usize;
+
+        let samples = cue_samples(cue, sample_rate_hz, volume);
+        let duration_ms = samples.len() as u64 * 1000 / sample_rate_hz.max(1) as u64;
+
+        let stream_config: cpal::StreamConfig = config.clone().into();
+        let stream = match config.sample_format() {
+            SampleFormat::I16 => {
+                build_output_stream::<i16>(&device, &stream_config, channels, samples)?
+            }
+            SampleFormat::U16 => {
+                build_output_stream::<u16>(&device, &stream_config, channels, samples)?
+            }
+            SampleFormat::I32 => {
+                build_output_stream::<i32>(&device, &stream_config, channels, samples)?
+            }
+            SampleFormat::F64 => {
+                build_output_stream::<f64>(&device, &stream_config, channels, samples)?
+            }
+            _ => build_output_stream::<f32>(&device, &stream_config, channels, samples)?,
+        };
+        stream.play()?;
+        std::thread::sleep(Duration::from_millis(duration_ms + TAIL_MS));
+        Ok(())
+    }
+
+    fn build_output_stream<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        channels: usize,
+        samples: Vec<f32>,
+    ) -> Result<cpal::Stream, cpal::BuildStreamError>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let mut pos = 0usize;
+        device.build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels.max(1)) {
+                    let s = samples.get(pos).copied().unwrap_or(0.0);
+                    pos += 1;
+                    for out in frame.iter_mut() {
+                        *out = T::from_sample(s);
+                    }
+                }
+            },
+            |err| log::warn!("cue output stream error: {err}"),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cues_respect_volume_and_fade_to_silence() {
+        let s = cue_samples(SoundCue::Start, 48_000, 0.5);
+        // 160 ms of notes.
+        assert_eq!(s.len(), 48_000 * 160 / 1000);
+        assert!(s.iter().all(|v| v.abs() <= 0.5 + f32::EPSILON));
+        assert!(s.iter().any(|v| v.abs() > 0.4));
+        assert!(s[0].abs() < 1e-3);
+        assert!(s[s.len() - 1].abs() < 0.05);
+
+        assert!(
+            cue_samples(SoundCue::Error, 16_000, 0.0)
+                .iter()
+                .all(|v| *v == 0.0)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// Decode audio files (voice memos, recordings) into the 16 kHz mono samples STT expects.
+//
+// Symphonia handles the containers and codecs; every decoded packet is downmixed and fed
+// straight into the streaming resampler so the source-rate audio is never held in full.
+
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::Context;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::resample::StreamingResampler;
+
+/// File extensions `decode_file_16k` accepts (lowercase, without the dot).
+pub const SUPPORTED_AUDIO_EXTENSIONS: [&str; 4] = ["wav", "mp3", "m4a", "ogg"];
+
+const TARGET_SAMPLE_RATE_HZ: u32 = 16_000;
+
+pub fn is_supported_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SUPPORTED_AUDIO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Decode the first audio track of `path` to 16 kHz mono.
+pub fn decode_file_16k(path: &Path) -> anyhow::Result<Vec<f32>> {
+    if !is_supported_audio_file(path) {
+        anyhow::bail!(
+            "unsupported audio file: {} (supported: WAV, MP3, M4A, OGG)",
+            path.display()
+        );
+    }
+
+    let file = File::open(path).with_context(|| format!("open: {}", path.display()))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .with_context(|| format!("unrecognized audio file: {}", path.display()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow::anyhow!("no audio track in {}", path.display()))?;
+    let track_id = track.id;
+    let sample_rate_hz = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow::anyhow!("unknown sample rate in {}", path.display()))?;
+    // Opus-in-Ogg (common for messenger voice notes) has no Symphonia decoder.
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .with_context(|| format!("unsupported audio codec in {}", path.display()))?;
+
+    let mut resampler = StreamingResampler::new(sample_rate_hz, TARGET_SAMPLE_RATE_HZ)?;
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(p) => p,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(e) => return Err(e).context("read audio packet"),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(d) => d,
+            // A corrupt packet costs a few milliseconds of audio, not the whole file.
+            Err(SymphoniaError::DecodeError(e)) => {
+                log::debug!("skipping undecodable packet: {e}");
+                continue;
+            }
+            Err(e) => return Err(e).context("decode audio"),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count().max(1);
+        let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buf.copy_interleaved_ref(decoded);
+
+        mono.clear();
+        mono.extend(
+            buf.samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+        );
+        resampler.push(&mono)?;
+    }
+
+    resampler.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 16-bit PCM WAV.
+    fn write_wav(path: &Path, sample_rate_hz: u32, channels: u16, frames: &[i16]) {
+        let data_len = (frames.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate_hz.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate_hz * u32::from(channels) * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for s in frames {
+            bytes.extend_from_slice(&s.to_le_bytes());
+        }
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn decodes_stereo_wav_to_16k_mono() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memo.WAV");
+        // One second of stereo 8 kHz audio; the channels cancel to silence when mixed.
+        let frames: Vec<i16> = (0..8_000).flat_map(|_| [8_000, -8_000]).collect();
+        write_wav(&path, 8_000, 2, &frames);
+
+        let samples = decode_file_16k(&path).unwrap();
+        assert!(
+            (15_900..=16_100).contains(&samples.len()),
+            "{}",
+            samples.len()
+        );
+        assert!(samples.iter().all(|s| s.abs() < 1e-3));
+    }
+
+    #[test]
+    fn rejects_unsupported_extensions() {
+        assert!(is_supported_audio_file(Path::new("a/b/Memo.M4A")));
+        assert!(!is_supported_audio_file(Path::new("notes.txt")));
+        assert!(decode_file_16k(Path::new("voice.flac")).is_err());
+    }
+}
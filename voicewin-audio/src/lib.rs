@@ -1,7 +1,23 @@
+mod cues;
+mod decode;
 mod resample;
+mod vad;
+mod wake;
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 mod recorder;
 
-#[cfg(any(windows, target_os = "macos"))]
-pub use recorder::{AudioCaptureError, AudioRecorder, CapturedAudio};
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod spill;
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+pub use recorder::{AudioCaptureError, AudioRecorder, CapturedAudio, DEFAULT_SPILL_AFTER_SECS};
+
+pub use cues::{SoundCue, cue_samples};
+pub use decode::{SUPPORTED_AUDIO_EXTENSIONS, decode_file_16k, is_supported_audio_file};
+pub use vad::PauseDetector;
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+pub use cues::play_cue;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+pub use wake::WakeListener;
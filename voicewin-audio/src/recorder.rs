@@ -4,17 +4,18 @@
 // Supported platforms:
 // - Windows
 // - macOS
-//
-// Linux support is intentionally not enabled yet because we don't want to introduce
-// new platform dependencies without committing to a full Linux UX.
+// - Linux (CPAL's ALSA host; PipeWire and PulseAudio are reached through their ALSA
+//   plugins, which is how the default device resolves on modern desktops)
 
+use std::fs::File;
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::Duration;
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Sample, SampleFormat, SizedSample, Stream};
 
-use crate::resample::resample_mono_f32;
+use crate::resample::{StreamingResampler, resample_mono_f32};
+use crate::spill::CaptureBuffer;
 
 #[derive(Debug, thiserror::Error)]
 pub enum AudioCaptureError {
@@ -51,10 +52,19 @@ pub enum AudioCaptureError {
     #[error("recording not started")]
     NotStarted,
 
+    #[error("recording is disabled")]
+    Disabled,
+
+    #[error("{0}")]
+    InsufficientDiskSpace(String),
+
     #[error("internal channel error")]
     Channel,
 }
 
+/// Captures longer than this are spilled to a temp file instead of being held in RAM.
+pub const DEFAULT_SPILL_AFTER_SECS: u32 = 5 * 60;
+
 pub struct CapturedAudio {
     pub sample_rate_hz: u32,
     pub samples: Vec<f32>,
@@ -78,8 +88,12 @@ impl AudioRecorder {
 }
 
 enum Cmd {
-    Start,
-    Stop(mpsc::Sender<Vec<f32>>),
+    // With a file, the capture is written into it as it arrives.
+    Start(Option<File>),
+    Stop(mpsc::Sender<CaptureBuffer>),
+    // Like `Stop`, but the capture carries on into a fresh buffer.
+    Take(mpsc::Sender<CaptureBuffer>),
+    SetSpillThreshold(Option<usize>),
     Shutdown,
 }
 
@@ -208,7 +222,7 @@ impl AudioRecorder {
 
             let _ = worker_tx.send(WorkerMsg::Ready);
 
-            run_consumer(sample_rx, cmd_rx, level_cb_worker);
+            run_consumer(sample_rx, cmd_rx, level_cb_worker, sample_rate_hz);
             drop(stream);
         });
 
@@ -230,14 +244,45 @@ impl AudioRecorder {
 
     pub fn start(&self) -> Result<(), AudioCaptureError> {
         self.cmd_tx
-            .send(Cmd::Start)
+            .send(Cmd::Start(None))
+            .map_err(|_| AudioCaptureError::Channel)
+    }
+
+    /// Like [`Self::start`], but every sample is written to `file` (a mono 32-bit float WAV
+    /// at the device rate) as it arrives, so the recording survives a crash. The file is
+    /// left in place once the capture is stopped and read.
+    pub fn start_into(&self, file: File) -> Result<(), AudioCaptureError> {
+        self.cmd_tx
+            .send(Cmd::Start(Some(file)))
             .map_err(|_| AudioCaptureError::Channel)
     }
 
+    /// Stop and return the whole capture at the device sample rate.
+    ///
+    /// This reads spilled audio back into one buffer; sessions use
+    /// [`Self::stop_captured_16k`], and [`Self::discard`] drops a capture without reading it.
     pub fn stop(&self) -> Result<Vec<f32>, AudioCaptureError> {
+        self.stop_buffer()?
+            .into_samples()
+            .map_err(|e| AudioCaptureError::Worker(format!("read spilled audio: {e}")))
+    }
+
+    /// Stop and throw the capture away; a spill file is deleted unread.
+    pub fn discard(&self) -> Result<(), AudioCaptureError> {
+        self.stop_buffer().map(drop)
+    }
+
+    fn stop_buffer(&self) -> Result<CaptureBuffer, AudioCaptureError> {
+        self.swap_buffer(Cmd::Stop)
+    }
+
+    fn swap_buffer(
+        &self,
+        cmd: fn(mpsc::Sender<CaptureBuffer>) -> Cmd,
+    ) -> Result<CaptureBuffer, AudioCaptureError> {
         let (resp_tx, resp_rx) = mpsc::channel();
         self.cmd_tx
-            .send(Cmd::Stop(resp_tx))
+            .send(cmd(resp_tx))
             .map_err(|_| AudioCaptureError::Channel)?;
 
         resp_rx
@@ -248,6 +293,16 @@ impl AudioRecorder {
             })
     }
 
+    /// Once a capture holds more than `seconds` of audio, the rest is streamed to a temp
+    /// WAV file instead of RAM (default: [`DEFAULT_SPILL_AFTER_SECS`]). `None` keeps the
+    /// whole capture in memory.
+    pub fn set_spill_after_secs(&self, seconds: Option<u32>) -> Result<(), AudioCaptureError> {
+        let threshold = seconds.map(|s| s as usize * self.sample_rate_hz as usize);
+        self.cmd_tx
+            .send(Cmd::SetSpillThreshold(threshold))
+            .map_err(|_| AudioCaptureError::Channel)
+    }
+
     pub fn close(mut self) -> Result<(), AudioCaptureError> {
         let _ = self.cmd_tx.send(Cmd::Shutdown);
         if let Some(h) = self.worker_handle.take() {
@@ -256,6 +311,32 @@ impl AudioRecorder {
         Ok(())
     }
 
+    /// Stop and return the capture resampled to 16 kHz.
+    ///
+    /// Spilled audio is streamed back from disk through the resampler chunk by chunk, so
+    /// the full capture never has to exist in memory at the device sample rate. The 16 kHz
+    /// result is still one buffer, a third of a 48 kHz capture.
+    pub fn stop_captured_16k(&self) -> Result<Vec<f32>, AudioCaptureError> {
+        Self::resampled_16k(self.stop_buffer()?, self.sample_rate_hz)
+    }
+
+    /// Return what was captured so far, resampled to 16 kHz, and keep recording into an
+    /// empty buffer. Samples arriving during the swap land in exactly one of the two.
+    pub fn take_captured_16k(&self) -> Result<Vec<f32>, AudioCaptureError> {
+        Self::resampled_16k(self.swap_buffer(Cmd::Take)?, self.sample_rate_hz)
+    }
+
+    fn resampled_16k(
+        buffer: CaptureBuffer,
+        sample_rate_hz: u32,
+    ) -> Result<Vec<f32>, AudioCaptureError> {
+        let mut resampler = StreamingResampler::new(sample_rate_hz, 16_000)?;
+        buffer
+            .drain_into(|chunk| resampler.push(chunk))
+            .map_err(|e| AudioCaptureError::Worker(format!("read captured audio: {e}")))?;
+        Ok(resampler.finish()?)
+    }
+
     pub fn stop_captured(&self) -> Result<CapturedAudio, AudioCaptureError> {
         let samples = self.stop()?;
         Ok(CapturedAudio {
@@ -337,23 +418,38 @@ fn run_consumer(
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Arc<Mutex<Option<Arc<dyn Fn(&[f32]) + Send + Sync + 'static>>>>,
+    sample_rate_hz: u32,
 ) {
     let mut recording = false;
-    let mut captured: Vec<f32> = Vec::new();
+    let mut captured = CaptureBuffer::new(
+        sample_rate_hz,
+        Some(DEFAULT_SPILL_AFTER_SECS as usize * sample_rate_hz as usize),
+    );
 
     loop {
         // Always drain commands promptly, even if the stream is stalled.
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
-                Cmd::Start => {
+                Cmd::Start(file) => {
                     recording = true;
                     captured.clear();
+                    if let Some(file) = file {
+                        match captured.empty_into(file) {
+                            Ok(buffer) => captured = buffer,
+                            Err(e) => log::error!("capture file failed; keeping audio in RAM: {e}"),
+                        }
+                    }
                 }
                 Cmd::Stop(resp) => {
                     recording = false;
-                    let out = std::mem::take(&mut captured);
-                    let _ = resp.send(out);
+                    let out = captured.empty_like();
+                    let _ = resp.send(std::mem::replace(&mut captured, out));
+                }
+                Cmd::Take(resp) => {
+                    let out = captured.empty_like();
+                    let _ = resp.send(std::mem::replace(&mut captured, out));
                 }
+                Cmd::SetSpillThreshold(threshold) => captured.set_spill_threshold(threshold),
                 Cmd::Shutdown => return,
             }
         }
@@ -364,7 +460,7 @@ fn run_consumer(
                     cb(&samples);
                 }
                 if recording {
-                    captured.extend_from_slice(&samples);
+                    captured.extend(&samples);
                 }
             }
             Err(mpsc::RecvTimeoutError::Timeout) => {
@@ -21,18 +21,10 @@ pub fn resample_mono_f32(
         .try_into()
         .context("invalid target sample rate")?;
 
-    let params = rubato::SincInterpolationParameters {
-        sinc_len: 256,
-        f_cutoff: 0.95,
-        interpolation: rubato::SincInterpolationType::Cubic,
-        oversampling_factor: 256,
-        window: rubato::WindowFunction::BlackmanHarris2,
-    };
-
     let mut resampler = rubato::SincFixedIn::<f32>::new(
         target_sample_rate_hz as f64 / input_sample_rate_hz as f64,
         2.0,
-        params,
+        sinc_params(),
         input_samples.len(),
         1,
     )
@@ -43,6 +35,103 @@ pub fn resample_mono_f32(
     Ok(out.into_iter().next().unwrap_or_default())
 }
 
+fn sinc_params() -> rubato::SincInterpolationParameters {
+    rubato::SincInterpolationParameters {
+        sinc_len: 256,
+        f_cutoff: 0.95,
+        interpolation: rubato::SincInterpolationType::Cubic,
+        oversampling_factor: 256,
+        window: rubato::WindowFunction::BlackmanHarris2,
+    }
+}
+
+/// Chunked mono resampler for audio that arrives (or is read back) piecewise.
+///
+/// Memory use is bounded by the output plus one input chunk, instead of holding the whole
+/// input at the source rate like [`resample_mono_f32`].
+pub struct StreamingResampler {
+    // `None` when input and output rates match (pass-through).
+    inner: Option<rubato::SincFixedIn<f32>>,
+    ratio: f64,
+    pending: Vec<f32>,
+    input_len: usize,
+    out: Vec<f32>,
+}
+
+impl StreamingResampler {
+    const CHUNK: usize = 4096;
+
+    pub fn new(input_sample_rate_hz: u32, target_sample_rate_hz: u32) -> anyhow::Result<Self> {
+        let ratio = target_sample_rate_hz as f64 / input_sample_rate_hz as f64;
+        let inner = if input_sample_rate_hz == target_sample_rate_hz {
+            None
+        } else {
+            Some(
+                rubato::SincFixedIn::<f32>::new(ratio, 2.0, sinc_params(), Self::CHUNK, 1)
+                    .context("create resampler")?,
+            )
+        };
+
+        Ok(Self {
+            inner,
+            ratio,
+            pending: Vec::new(),
+            input_len: 0,
+            out: Vec::new(),
+        })
+    }
+
+    pub fn push(&mut self, samples: &[f32]) -> anyhow::Result<()> {
+        self.input_len += samples.len();
+        let Some(inner) = self.inner.as_mut() else {
+            self.out.extend_from_slice(samples);
+            return Ok(());
+        };
+
+        self.pending.extend_from_slice(samples);
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= Self::CHUNK {
+            let chunk = &self.pending[consumed..consumed + Self::CHUNK];
+            let out = inner.process(&[chunk], None).context("resample")?;
+            self.out.extend_from_slice(&out[0]);
+            consumed += Self::CHUNK;
+        }
+        self.pending.drain(..consumed);
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> anyhow::Result<Vec<f32>> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Ok(self.out);
+        };
+
+        let expected = (self.input_len as f64 * self.ratio).ceil() as usize;
+        let delay = inner.output_delay();
+
+        if !self.pending.is_empty() {
+            let out = inner
+                .process_partial(Some(&[&self.pending[..]]), None)
+                .context("resample")?;
+            self.out.extend_from_slice(&out[0]);
+        }
+
+        // Flush the filter tail until the delayed output covers the whole input.
+        while self.out.len() < delay + expected {
+            let out = inner
+                .process_partial::<&[f32]>(None, None)
+                .context("resample")?;
+            if out[0].is_empty() {
+                break;
+            }
+            self.out.extend_from_slice(&out[0]);
+        }
+
+        let mut out: Vec<f32> = self.out.split_off(delay.min(self.out.len()));
+        out.truncate(expected);
+        Ok(out)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,4 +142,22 @@ mod tests {
         let y = resample_mono_f32(&x, 16_000, 16_000).unwrap();
         assert_eq!(x, y);
     }
+
+    #[test]
+    fn streaming_resampler_produces_expected_length() {
+        let input: Vec<f32> = (0..48_000)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 48_000.0).sin() * 0.5)
+            .collect();
+
+        let mut r = StreamingResampler::new(48_000, 16_000).unwrap();
+        for chunk in input.chunks(1000) {
+            r.push(chunk).unwrap();
+        }
+        let out = r.finish().unwrap();
+        assert_eq!(out.len(), 16_000);
+
+        // Signal energy survives resampling (no silence from a botched delay trim).
+        let rms = (out.iter().map(|s| s * s).sum::<f32>() / out.len() as f32).sqrt();
+        assert!(rms > 0.3, "rms={rms}");
+    }
 }
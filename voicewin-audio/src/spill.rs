@@ -0,0 +1,271 @@
+// Capture buffer that spills to a temp WAV file.
+//
+// Samples stay in memory until `spill_threshold` is reached; from then on every chunk is
+// appended to disk (mono 32-bit float WAV) and only streamed back when the capture is
+// drained. This keeps the resident set flat during long (or forgotten) recordings.
+//
+// A buffer made with `CaptureBuffer::empty_into` writes to the caller's file from the first
+// sample instead, so the audio outlives a crash mid-recording.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+
+const WAV_HEADER_LEN: u64 = 44;
+
+// Samples per read when streaming spilled audio back.
+const READ_CHUNK_SAMPLES: usize = 16 * 1024;
+
+pub(crate) struct CaptureBuffer {
+    sample_rate_hz: u32,
+    mem: Vec<f32>,
+    spill_threshold: Option<usize>,
+    spill: Option<Spill>,
+}
+
+struct Spill {
+    writer: BufWriter<File>,
+    samples: usize,
+}
+
+impl CaptureBuffer {
+    pub(crate) fn new(sample_rate_hz: u32, spill_threshold: Option<usize>) -> Self {
+        Self {
+            sample_rate_hz,
+            mem: Vec::new(),
+            spill_threshold,
+            spill: None,
+        }
+    }
+
+    /// Fresh, empty buffer with the same settings that streams every sample into `file`.
+    /// The file is left on disk once the buffer is drained.
+    pub(crate) fn empty_into(&self, file: File) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(file);
+        write_wav_header(&mut writer, self.sample_rate_hz, 0)?;
+        // A valid (empty) WAV on disk from the start.
+        writer.flush()?;
+        Ok(Self {
+            spill: Some(Spill { writer, samples: 0 }),
+            ..self.empty_like()
+        })
+    }
+
+    /// Fresh, empty buffer with the same settings.
+    pub(crate) fn empty_like(&self) -> Self {
+        Self::new(self.sample_rate_hz, self.spill_threshold)
+    }
+
+    pub(crate) fn set_spill_threshold(&mut self, spill_threshold: Option<usize>) {
+        self.spill_threshold = spill_threshold;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.mem.clear();
+        self.spill = None;
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.mem.len() + self.spill.as_ref().map(|s| s.samples).unwrap_or(0)
+    }
+
+    pub(crate) fn extend(&mut self, samples: &[f32]) {
+        // Once spilled, `mem` only grows again if a disk write failed; from then on keep
+        // appending in memory so the sample order is preserved.
+        if let Some(spill) = self.spill.as_mut() {
+            if self.mem.is_empty() {
+                match write_samples(&mut spill.writer, samples) {
+                    Ok(()) => spill.samples += samples.len(),
+                    Err(e) => {
+                        log::error!("audio spill write failed; keeping samples in memory: {e}");
+                        self.mem.extend_from_slice(samples);
+                    }
+                }
+            } else {
+                self.mem.extend_from_slice(samples);
+            }
+            return;
+        }
+
+        self.mem.extend_from_slice(samples);
+
+        let Some(threshold) = self.spill_threshold else {
+            return;
+        };
+        if self.mem.len() < threshold {
+            return;
+        }
+
+        match self.start_spill() {
+            Ok(spill) => {
+                self.spill = Some(spill);
+                self.mem = Vec::new();
+            }
+            Err(e) => {
+                log::error!("failed to spill audio to disk; keeping samples in memory: {e}");
+                self.spill_threshold = None;
+            }
+        }
+    }
+
+    fn start_spill(&self) -> std::io::Result<Spill> {
+        let mut writer = BufWriter::new(tempfile::tempfile()?);
+        // Sizes are patched in when the capture is drained.
+        write_wav_header(&mut writer, self.sample_rate_hz, 0)?;
+        write_samples(&mut writer, &self.mem)?;
+        Ok(Spill {
+            writer,
+            samples: self.mem.len(),
+        })
+    }
+
+    /// Stream all captured samples (spilled prefix, then in-memory tail) to `sink` in
+    /// bounded chunks, consuming the buffer.
+    pub(crate) fn drain_into(
+        mut self,
+        mut sink: impl FnMut(&[f32]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        if let Some(spill) = self.spill.take() {
+            let mut file = spill.writer.into_inner().map_err(|e| e.into_error())?;
+            finalize_wav_header(&mut file, spill.samples)?;
+            file.seek(SeekFrom::Start(WAV_HEADER_LEN))?;
+
+            let mut reader = BufReader::new(file);
+            let mut bytes = vec![0u8; READ_CHUNK_SAMPLES * 4];
+            let mut chunk = Vec::with_capacity(READ_CHUNK_SAMPLES);
+            let mut remaining = spill.samples;
+            while remaining > 0 {
+                let n = remaining.min(READ_CHUNK_SAMPLES);
+                reader.read_exact(&mut bytes[..n * 4])?;
+                chunk.clear();
+                chunk.extend(
+                    bytes[..n * 4]
+                        .chunks_exact(4)
+                        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                );
+                sink(&chunk)?;
+                remaining -= n;
+            }
+        }
+
+        if !self.mem.is_empty() {
+            sink(&self.mem)?;
+        }
+        Ok(())
+    }
+
+    /// Collect all captured samples into memory.
+    pub(crate) fn into_samples(self) -> anyhow::Result<Vec<f32>> {
+        let mut out = Vec::with_capacity(self.len());
+        self.drain_into(|chunk| {
+            out.extend_from_slice(chunk);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+fn write_samples(w: &mut impl Write, samples: &[f32]) -> std::io::Result<()> {
+    for s in samples {
+        w.write_all(&s.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_wav_header(
+    w: &mut impl Write,
+    sample_rate_hz: u32,
+    samples: usize,
+) -> std::io::Result<()> {
+    let data_len = (samples as u64 * 4).min(u32::MAX as u64 - 36) as u32;
+    w.write_all(b"RIFF")?;
+    w.write_all(&(36 + data_len).to_le_bytes())?;
+    w.write_all(b"WAVE")?;
+    w.write_all(b"fmt ")?;
+    w.write_all(&16u32.to_le_bytes())?;
+    w.write_all(&3u16.to_le_bytes())?; // IEEE float
+    w.write_all(&1u16.to_le_bytes())?; // mono
+    w.write_all(&sample_rate_hz.to_le_bytes())?;
+    w.write_all(&(sample_rate_hz * 4).to_le_bytes())?;
+    w.write_all(&4u16.to_le_bytes())?;
+    w.write_all(&32u16.to_le_bytes())?;
+    w.write_all(b"data")?;
+    w.write_all(&data_len.to_le_bytes())?;
+    Ok(())
+}
+
+fn finalize_wav_header(file: &mut File, samples: usize) -> std::io::Result<()> {
+    let data_len = (samples as u64 * 4).min(u32::MAX as u64 - 36) as u32;
+    file.seek(SeekFrom::Start(4))?;
+    file.write_all(&(36 + data_len).to_le_bytes())?;
+    file.seek(SeekFrom::Start(40))?;
+    file.write_all(&data_len.to_le_bytes())?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spills_past_threshold_and_preserves_order() {
+        let mut buf = CaptureBuffer::new(16_000, Some(4));
+        buf.extend(&[0.1, 0.2, 0.3]);
+        assert!(buf.spill.is_none());
+
+        buf.extend(&[0.4, 0.5]);
+        assert!(buf.spill.is_some());
+        buf.extend(&[0.6]);
+        assert_eq!(buf.len(), 6);
+
+        assert_eq!(
+            buf.into_samples().unwrap(),
+            vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6]
+        );
+    }
+
+    #[test]
+    fn streams_spilled_audio_in_bounded_chunks() {
+        let mut buf = CaptureBuffer::new(16_000, Some(8));
+        let input: Vec<f32> = (0..READ_CHUNK_SAMPLES * 2 + 5)
+            .map(|i| i as f32 / 1e6)
+            .collect();
+        buf.extend(&input);
+
+        let mut chunks = 0;
+        let mut out = Vec::new();
+        buf.drain_into(|c| {
+            assert!(c.len() <= READ_CHUNK_SAMPLES);
+            chunks += 1;
+            out.extend_from_slice(c);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(chunks, 3);
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn writes_into_the_given_file_from_the_first_sample() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut buf = CaptureBuffer::new(16_000, None)
+            .empty_into(file.reopen().unwrap())
+            .unwrap();
+        buf.extend(&[0.1, 0.2]);
+        assert!(buf.mem.is_empty());
+
+        assert_eq!(buf.into_samples().unwrap(), vec![0.1, 0.2]);
+        // Drained with its sizes patched in, and still there for the caller.
+        let bytes = std::fs::read(file.path()).unwrap();
+        assert_eq!(bytes.len(), WAV_HEADER_LEN as usize + 8);
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 8);
+    }
+
+    #[test]
+    fn stays_in_memory_without_threshold() {
+        let mut buf = CaptureBuffer::new(16_000, None);
+        buf.extend(&vec![0.0; 10_000]);
+        assert!(buf.spill.is_none());
+        assert_eq!(buf.into_samples().unwrap().len(), 10_000);
+    }
+}
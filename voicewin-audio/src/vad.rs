@@ -0,0 +1,91 @@
+//! Local pause detection for realtime STT with manual commits.
+//!
+//! The provider's server-side VAD behaves differently from one provider (and one release) to
+//! the next. With [`PauseDetector`] deciding instead, a segment is finalized when the user
+//! pauses, the same way everywhere.
+
+/// Blocks at or above this RMS level count as speech; matches the wake listener.
+const SPEECH_RMS: f32 = 0.02;
+
+/// Watches a live mono stream and reports the first pause after each stretch of speech.
+#[derive(Debug, Clone)]
+pub struct PauseDetector {
+    min_speech: usize,
+    pause: usize,
+    // Samples in loud blocks since the last pause, and in quiet blocks since the last
+    // loud one.
+    voiced: usize,
+    silence: usize,
+}
+
+impl PauseDetector {
+    /// A pause is `pause_ms` of quiet after at least `min_speech_ms` of speech.
+    pub fn new(sample_rate_hz: u32, min_speech_ms: u32, pause_ms: u32) -> Self {
+        let samples_per_ms = (sample_rate_hz as usize / 1000).max(1);
+        Self {
+            min_speech: min_speech_ms as usize * samples_per_ms,
+            pause: (pause_ms as usize).max(1) * samples_per_ms,
+            voiced: 0,
+            silence: 0,
+        }
+    }
+
+    /// Feed the next block; `true` when it completes a pause, i.e. the segment before it
+    /// should be committed now. Silence alone never commits.
+    pub fn push(&mut self, block: &[f32]) -> bool {
+        if rms(block) >= SPEECH_RMS {
+            self.voiced += block.len();
+            self.silence = 0;
+            return false;
+        }
+
+        self.silence += block.len();
+        if self.silence < self.pause {
+            return false;
+        }
+        // Either way the speech so far is dealt with; clicks don't add up across pauses.
+        let commit = self.voiced >= self.min_speech;
+        self.voiced = 0;
+        commit
+    }
+}
+
+fn rms(block: &[f32]) -> f32 {
+    if block.is_empty() {
+        return 0.0;
+    }
+    (block.iter().map(|s| s * s).sum::<f32>() / block.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1 kHz: one sample per millisecond. Returns the number of commits.
+    fn feed(d: &mut PauseDetector, level: f32, ms: usize) -> usize {
+        (0..ms / 10).filter(|_| d.push(&[level; 10])).count()
+    }
+
+    #[test]
+    fn commits_once_per_pause_after_speech() {
+        let mut d = PauseDetector::new(1_000, 100, 300);
+        assert_eq!(feed(&mut d, 0.0, 2_000), 0);
+        assert_eq!(feed(&mut d, 0.5, 800), 0);
+        // A breath shorter than the pause doesn't split the sentence.
+        assert_eq!(feed(&mut d, 0.0, 200), 0);
+        assert_eq!(feed(&mut d, 0.5, 400), 0);
+        assert_eq!(feed(&mut d, 0.0, 2_000), 1);
+
+        assert_eq!(feed(&mut d, 0.5, 500), 0);
+        assert_eq!(feed(&mut d, 0.0, 300), 1);
+    }
+
+    #[test]
+    fn ignores_clicks() {
+        let mut d = PauseDetector::new(1_000, 100, 300);
+        assert_eq!(feed(&mut d, 0.5, 50), 0);
+        assert_eq!(feed(&mut d, 0.0, 1_000), 0);
+        assert_eq!(feed(&mut d, 0.5, 50), 0);
+        assert_eq!(feed(&mut d, 0.0, 300), 0);
+    }
+}
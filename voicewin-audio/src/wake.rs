@@ -0,0 +1,179 @@
+//! Always-on listening for the wake phrase.
+//!
+//! [`WakeListener`] keeps its own input stream open and hands over each short burst of
+//! speech (a couple of seconds at most); deciding whether it was the wake phrase is up to
+//! the caller. Longer speech is conversation, not a command, and is dropped unheard.
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use std::sync::Mutex;
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use crate::recorder::{AudioCaptureError, AudioRecorder};
+
+/// Blocks at or above this RMS level count as speech.
+const SPEECH_RMS: f32 = 0.02;
+/// Kept from before the speech onset so the first syllable isn't clipped.
+const PRE_ROLL_MS: usize = 300;
+/// Silence that ends an utterance.
+const END_SILENCE_MS: usize = 400;
+/// Shorter bursts are clicks and coughs.
+const MIN_UTTERANCE_MS: usize = 300;
+const MAX_UTTERANCE_MS: usize = 2_500;
+
+/// Cuts a live mono stream into short utterances by level.
+pub(crate) struct UtteranceGate {
+    samples_per_ms: usize,
+    pre_roll: Vec<f32>,
+    utterance: Vec<f32>,
+    in_speech: bool,
+    // The current utterance ran past the maximum; wait for silence, then start over.
+    overlong: bool,
+    // Samples in loud blocks, and in quiet blocks since the last loud one.
+    voiced: usize,
+    silence: usize,
+}
+
+impl UtteranceGate {
+    pub(crate) fn new(sample_rate_hz: u32) -> Self {
+        Self {
+            samples_per_ms: (sample_rate_hz as usize / 1000).max(1),
+            pre_roll: Vec::new(),
+            utterance: Vec::new(),
+            in_speech: false,
+            overlong: false,
+            voiced: 0,
+            silence: 0,
+        }
+    }
+
+    /// Feed the next block; returns a finished utterance (with pre-roll) when it ends.
+    pub(crate) fn push(&mut self, block: &[f32]) -> Option<Vec<f32>> {
+        let speech = rms(block) >= SPEECH_RMS;
+
+        if !self.in_speech {
+            self.pre_roll.extend_from_slice(block);
+            if speech {
+                self.in_speech = true;
+                self.voiced = block.len();
+                self.silence = 0;
+                self.utterance = std::mem::take(&mut self.pre_roll);
+            } else {
+                let keep = PRE_ROLL_MS * self.samples_per_ms;
+                let excess = self.pre_roll.len().saturating_sub(keep);
+                self.pre_roll.drain(..excess);
+            }
+            return None;
+        }
+
+        if !self.overlong {
+            self.utterance.extend_from_slice(block);
+            if self.utterance.len() > MAX_UTTERANCE_MS * self.samples_per_ms {
+                self.overlong = true;
+                self.utterance = Vec::new();
+            }
+        }
+        if speech {
+            self.voiced += block.len();
+            self.silence = 0;
+        } else {
+            self.silence += block.len();
+        }
+        if self.silence < END_SILENCE_MS * self.samples_per_ms {
+            return None;
+        }
+
+        self.in_speech = false;
+        let overlong = std::mem::take(&mut self.overlong);
+        let utterance = std::mem::take(&mut self.utterance);
+        (!overlong && self.voiced >= MIN_UTTERANCE_MS * self.samples_per_ms).then_some(utterance)
+    }
+}
+
+fn rms(block: &[f32]) -> f32 {
+    if block.is_empty() {
+        return 0.0;
+    }
+    (block.iter().map(|s| s * s).sum::<f32>() / block.len() as f32).sqrt()
+}
+
+/// An open microphone listening for short utterances. Dropping or closing it releases the
+/// device.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+pub struct WakeListener {
+    recorder: AudioRecorder,
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+impl WakeListener {
+    /// Listen on `device_name` (the default input when `None`) and call `on_utterance` with
+    /// each utterance as 16 kHz mono. The callback runs on the capture thread; hand the
+    /// audio off rather than transcribing it there.
+    pub fn open(
+        device_name: Option<&str>,
+        on_utterance: impl Fn(Vec<f32>) + Send + Sync + 'static,
+    ) -> Result<Self, AudioCaptureError> {
+        let recorder = AudioRecorder::open_named(device_name)?;
+        let sample_rate_hz = recorder.sample_rate_hz();
+        let gate = Mutex::new(UtteranceGate::new(sample_rate_hz));
+
+        // Nothing is recorded: the level callback sees every block whether or not a capture
+        // is running, which is all the gate needs.
+        recorder.set_level_callback(move |block| {
+            let utterance = gate.lock().unwrap_or_else(|p| p.into_inner()).push(block);
+            let Some(utterance) = utterance else {
+                return;
+            };
+            match AudioRecorder::resample_to_16k(&utterance, sample_rate_hz) {
+                Ok(samples) => on_utterance(samples),
+                Err(e) => log::warn!("wake listener: resample failed: {e}"),
+            }
+        });
+        Ok(Self { recorder })
+    }
+
+    pub fn close(self) -> Result<(), AudioCaptureError> {
+        self.recorder.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1 kHz keeps the numbers readable: one sample per millisecond.
+    fn feed(gate: &mut UtteranceGate, level: f32, ms: usize) -> Vec<Vec<f32>> {
+        (0..ms / 10)
+            .filter_map(|_| gate.push(&[level; 10]))
+            .collect()
+    }
+
+    #[test]
+    fn returns_short_utterances_with_pre_roll() {
+        let mut gate = UtteranceGate::new(1_000);
+        assert!(feed(&mut gate, 0.0, 1_000).is_empty());
+        assert!(feed(&mut gate, 0.5, 800).is_empty());
+        // A short dip inside the phrase doesn't end it.
+        assert!(feed(&mut gate, 0.0, 200).is_empty());
+        assert!(feed(&mut gate, 0.5, 200).is_empty());
+
+        let out = feed(&mut gate, 0.0, 400);
+        assert_eq!(out.len(), 1);
+        // Pre-roll, the phrase with its dip, and the trailing silence.
+        assert_eq!(out[0].len(), 300 + 800 + 200 + 200 + 400);
+    }
+
+    #[test]
+    fn drops_clicks_and_long_speech() {
+        let mut gate = UtteranceGate::new(1_000);
+        assert!(feed(&mut gate, 0.0, 1_000).is_empty());
+        assert!(feed(&mut gate, 0.5, 100).is_empty());
+        assert!(feed(&mut gate, 0.0, 1_000).is_empty());
+
+        assert!(feed(&mut gate, 0.5, 5_000).is_empty());
+        assert!(feed(&mut gate, 0.0, 1_000).is_empty());
+
+        // Back to normal afterwards.
+        feed(&mut gate, 0.5, 500);
+        assert_eq!(feed(&mut gate, 0.0, 400).len(), 1);
+    }
+}
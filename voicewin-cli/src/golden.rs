@@ -0,0 +1,407 @@
+//! Golden-transcript harness: runs the WAV fixtures in `fixtures/golden` through the full
+//! engine and scores the inserted text against the expected output by word error rate.
+//!
+//! Each fixture's `stt_text` stands in for the recognizer, so runs are deterministic and
+//! the output must match `expected` exactly; any difference is a pipeline or text-filter
+//! regression. Enhancement stays off: LLM output is not reproducible.
+//!
+//! Each fixture is a `<name>.json` next to a 16 kHz PCM16 `<name>.wav`. The bundled WAVs are
+//! short tones, not speech: they exercise WAV decoding and the audio side of the session
+//! timings, while the words come from `stt_text`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+use voicewin_core::power_mode::GlobalDefaults;
+use voicewin_core::types::{AppIdentity, DisfluencyFilter, InsertMode, OutputFormat};
+use voicewin_engine::engine::{EngineConfig, VoicewinEngine};
+use voicewin_engine::traits::{
+    AppContextProvider, AudioInput, ContextSnapshot, EnhancedText, Inserter, LlmProvider,
+    SttProvider, Transcript,
+};
+
+/// Fixtures shipped with the crate.
+pub const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/golden");
+
+const SAMPLE_RATE_HZ: u32 = 16_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub audio_path: PathBuf,
+
+    /// What the mock STT "hears".
+    pub stt_text: String,
+    /// Text the engine should insert.
+    pub expected: String,
+
+    #[serde(default = "default_language")]
+    pub language: String,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    #[serde(default)]
+    pub disfluency_filter: DisfluencyFilter,
+    #[serde(default)]
+    pub basic_cleanup: bool,
+}
+
+fn default_language() -> String {
+    "en".into()
+}
+
+/// Every `*.json` fixture in `dir`, by name.
+pub fn load_fixtures(dir: &Path) -> anyhow::Result<Vec<Fixture>> {
+    let mut fixtures = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let raw = std::fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+        let mut fixture: Fixture = serde_json::from_slice(&raw)
+            .with_context(|| format!("parse fixture {}", path.display()))?;
+        fixture.name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        fixture.audio_path = path.with_extension("wav");
+        fixtures.push(fixture);
+    }
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(fixtures)
+}
+
+fn le_u16(b: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([b[at], b[at + 1]])
+}
+
+fn le_u32(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+/// Read a 16 kHz 16-bit PCM WAV, mixing channels down to mono.
+pub fn read_wav_16k(bytes: &[u8]) -> anyhow::Result<AudioInput> {
+    if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(anyhow!("not a WAV file"));
+    }
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let len = le_u32(bytes, pos + 4) as usize;
+        let body = pos + 8;
+        let end = (body + len).min(bytes.len());
+        match id {
+            b"fmt " if len >= 16 => {
+                format = Some((
+                    le_u16(bytes, body),
+                    le_u16(bytes, body + 2),
+                    le_u32(bytes, body + 4),
+                    le_u16(bytes, body + 14),
+                ));
+            }
+            b"data" => {
+                let (audio_format, channels, rate, bits) =
+                    format.ok_or_else(|| anyhow!("WAV data before fmt chunk"))?;
+                if audio_format != 1 || bits != 16 || rate != SAMPLE_RATE_HZ || channels == 0 {
+                    return Err(anyhow!(
+                        "fixtures must be 16 kHz 16-bit PCM (got format={audio_format} \
+                         bits={bits} rate={rate} channels={channels})"
+                    ));
+                }
+                let channels = usize::from(channels);
+                let samples = bytes[body..end]
+                    .chunks_exact(2 * channels)
+                    .map(|frame| {
+                        frame
+                            .chunks_exact(2)
+                            .map(|s| f32::from(i16::from_le_bytes([s[0], s[1]])) / 32_768.0)
+                            .sum::<f32>()
+                            / channels as f32
+                    })
+                    .collect();
+                return Ok(AudioInput {
+                    sample_rate_hz: SAMPLE_RATE_HZ,
+                    samples,
+                });
+            }
+            _ => {}
+        }
+        // Chunks are word-aligned.
+        pos = body + len + (len & 1);
+    }
+    Err(anyhow!("WAV has no data chunk"))
+}
+
+/// Lowercased words with surrounding punctuation stripped, the usual WER normalization.
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Word error rate: (substitutions + deletions + insertions) / reference words.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let r = words(reference);
+    let h = words(hypothesis);
+    if r.is_empty() {
+        return if h.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    // Levenshtein distance over words, one row at a time.
+    let mut prev: Vec<usize> = (0..=h.len()).collect();
+    for (i, rw) in r.iter().enumerate() {
+        let mut row = vec![i + 1; h.len() + 1];
+        for (j, hw) in h.iter().enumerate() {
+            let substitution = prev[j] + usize::from(rw != hw);
+            row[j + 1] = substitution.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    prev[h.len()] as f64 / r.len() as f64
+}
+
+/// Replays a fixture's `stt_text`.
+struct FixtureStt {
+    text: String,
+}
+
+#[async_trait::async_trait]
+impl SttProvider for FixtureStt {
+    async fn transcribe(
+        &self,
+        _audio: &AudioInput,
+        provider: &str,
+        model: &str,
+        _language: &str,
+    ) -> anyhow::Result<Transcript> {
+        Ok(Transcript {
+            text: self.text.clone(),
+            provider: provider.into(),
+            model: model.into(),
+            segments: vec![],
+        })
+    }
+}
+
+struct NoContext;
+
+#[async_trait::async_trait]
+impl AppContextProvider for NoContext {
+    async fn foreground_app(&self) -> anyhow::Result<AppIdentity> {
+        Ok(AppIdentity::new().with_process_name("golden.exe"))
+    }
+
+    async fn snapshot_context(&self) -> anyhow::Result<ContextSnapshot> {
+        Ok(ContextSnapshot::default())
+    }
+}
+
+struct NoLlm;
+
+#[async_trait::async_trait]
+impl LlmProvider for NoLlm {
+    async fn enhance(
+        &self,
+        _base_url: &str,
+        _api_key: &str,
+        _model: &str,
+        _system_message: &str,
+        _user_message: &str,
+    ) -> anyhow::Result<EnhancedText> {
+        Err(anyhow!("the golden harness runs without enhancement"))
+    }
+}
+
+#[derive(Default)]
+struct CaptureInserter {
+    inserted: Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl Inserter for CaptureInserter {
+    async fn insert(&self, text: &str, _mode: InsertMode) -> anyhow::Result<()> {
+        self.inserted
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(text.to_string());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FixtureResult {
+    pub name: String,
+    pub output: String,
+    pub wer: f64,
+    pub passed: bool,
+    pub audio: Duration,
+    pub transcription_ms: Option<u64>,
+    pub elapsed: Duration,
+}
+
+pub async fn run_fixture(fixture: &Fixture) -> anyhow::Result<FixtureResult> {
+    let wav = std::fs::read(&fixture.audio_path)
+        .with_context(|| format!("read {}", fixture.audio_path.display()))?;
+    let audio = read_wav_16k(&wav).with_context(|| format!("decode {}", fixture.name))?;
+    let audio_len = Duration::from_secs_f64(audio.samples.len() as f64 / f64::from(SAMPLE_RATE_HZ));
+
+    let cfg = EngineConfig {
+        defaults: GlobalDefaults {
+            enable_enhancement: false,
+            prompt_id: None,
+            insert_mode: InsertMode::Paste,
+            stt_provider: "local".into(),
+            stt_model: "mock".into(),
+            language: fixture.language.clone(),
+            llm_base_url: String::new(),
+            llm_model: String::new(),
+            microphone_device: None,
+            history_enabled: false,
+            basic_cleanup: fixture.basic_cleanup,
+            output_format: fixture.output_format,
+            disfluency_filter: fixture.disfluency_filter,
+            text_pipeline: Default::default(),
+            realtime_vad: Default::default(),
+            context: voicewin_core::context::ContextToggles::default(),
+        },
+        profiles: vec![],
+        prompts: vec![],
+        llm_api_key: String::new(),
+        spoken_replacements: Default::default(),
+        insert_commands: Default::default(),
+        snippets: vec![],
+    };
+
+    let inserter = Arc::new(CaptureInserter::default());
+    let engine = VoicewinEngine::new(
+        cfg,
+        Arc::new(NoContext),
+        Arc::new(FixtureStt {
+            text: fixture.stt_text.clone(),
+        }),
+        Arc::new(NoLlm),
+        inserter.clone(),
+    );
+
+    let started = Instant::now();
+    let result = engine.run_session(audio).await?;
+    let elapsed = started.elapsed();
+    if let Some(err) = result.error {
+        return Err(anyhow!("{}: session failed: {err}", fixture.name));
+    }
+
+    let output = inserter
+        .inserted
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .join("\n");
+    Ok(FixtureResult {
+        name: fixture.name.clone(),
+        wer: word_error_rate(&fixture.expected, &output),
+        passed: output == fixture.expected,
+        output,
+        audio: audio_len,
+        transcription_ms: result.timings.transcription_ms,
+        elapsed,
+    })
+}
+
+/// `voicewin-cli golden [fixtures dir]`.
+///
+/// Prints one line per fixture and returns whether all of them passed.
+pub async fn run(args: &[String]) -> anyhow::Result<bool> {
+    let dir = match args {
+        [] => PathBuf::from(FIXTURES_DIR),
+        [dir] => PathBuf::from(dir),
+        _ => return Err(anyhow!("usage: voicewin-cli golden [fixtures dir]")),
+    };
+
+    let fixtures = load_fixtures(&dir)?;
+    let mut failed = 0;
+    let mut total_wer = 0.0;
+    for fixture in &fixtures {
+        let r = run_fixture(fixture).await?;
+        total_wer += r.wer;
+        if !r.passed {
+            failed += 1;
+        }
+        println!(
+            "{} {:<24} wer={:.3} audio={:.1}s stt={}ms total={}ms",
+            if r.passed { "ok  " } else { "FAIL" },
+            r.name,
+            r.wer,
+            r.audio.as_secs_f64(),
+            r.transcription_ms.unwrap_or_default(),
+            r.elapsed.as_millis(),
+        );
+        if !r.passed {
+            println!("     expected: {:?}", fixture.expected);
+            println!("     got:      {:?}", r.output);
+        }
+    }
+
+    let mean_wer = total_wer / fixtures.len().max(1) as f64;
+    println!(
+        "{} fixture(s), {failed} failed, mean wer={mean_wer:.3}",
+        fixtures.len()
+    );
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_error_rate_counts_edits_per_reference_word() {
+        assert_eq!(word_error_rate("Hello, team.", "hello team"), 0.0);
+        // One substitution and one deletion over four words.
+        assert_eq!(
+            word_error_rate("ship it on friday", "ship that friday"),
+            0.5
+        );
+        assert_eq!(word_error_rate("a b", "a b c d"), 1.0);
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "noise"), 1.0);
+    }
+
+    #[test]
+    fn reads_pcm16_wav_and_rejects_other_rates() {
+        let fixtures = load_fixtures(Path::new(FIXTURES_DIR)).unwrap();
+        let wav = std::fs::read(&fixtures[0].audio_path).unwrap();
+        let audio = read_wav_16k(&wav).unwrap();
+        assert_eq!(audio.sample_rate_hz, 16_000);
+        assert!(!audio.samples.is_empty());
+
+        let mut resampled = wav.clone();
+        resampled[24..28].copy_from_slice(&8_000u32.to_le_bytes());
+        assert!(read_wav_16k(&resampled).is_err());
+        assert!(read_wav_16k(b"not a wav").is_err());
+    }
+
+    #[tokio::test]
+    async fn bundled_fixtures_match_their_golden_output() {
+        let fixtures = load_fixtures(Path::new(FIXTURES_DIR)).unwrap();
+        assert!(!fixtures.is_empty());
+        for fixture in &fixtures {
+            let r = run_fixture(fixture).await.unwrap();
+            assert!(
+                r.passed,
+                "{}: expected {:?}, got {:?}",
+                r.name, fixture.expected, r.output
+            );
+            assert_eq!(r.wer, 0.0, "{}", r.name);
+        }
+    }
+}
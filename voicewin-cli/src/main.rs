@@ -1,3 +1,6 @@
+mod golden;
+mod mcp;
+
 use std::sync::Arc;
 use voicewin_core::enhancement::{PromptMode, PromptTemplate};
 use voicewin_core::power_mode::{GlobalDefaults, PowerModeOverrides, PowerModeProfile};
@@ -58,6 +61,7 @@ impl SttProvider for MockSttProvider {
             text: "rewrite um hello team this is a quick update rewrite".into(),
             provider: provider.into(),
             model: model.into(),
+            segments: vec![],
         })
     }
 }
@@ -79,6 +83,8 @@ impl LlmProvider for OpenAiCompatibleLlm {
             base_url: base_url.to_string(),
             api_key: api_key.to_string(),
             model: model.to_string(),
+            decoding: Default::default(),
+            flavor: Default::default(),
         };
 
         let messages = vec![
@@ -107,12 +113,25 @@ impl LlmProvider for OpenAiCompatibleLlm {
             text,
             provider: "openai-compatible".into(),
             model: model.into(),
+            usage: None,
         })
     }
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // `voicewin-cli golden ...` scores the pipeline against the golden fixtures instead, and
+    // `voicewin-cli mcp` serves the MCP tools over stdio.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("golden") => {
+            let passed = golden::run(&args[1..]).await?;
+            std::process::exit(if passed { 0 } else { 1 });
+        }
+        Some("mcp") => return mcp::run(&args[1..]).await,
+        _ => {}
+    }
+
     // MVP CLI behavior: run an end-to-end session using mock STT + real LLM call.
     // If you don't want network calls, set LLM_API_KEY="" and we will skip enhancement.
 
@@ -132,6 +151,11 @@ async fn main() -> anyhow::Result<()> {
         llm_model,
         microphone_device: None,
         history_enabled: true,
+        basic_cleanup: false,
+        output_format: Default::default(),
+        disfluency_filter: Default::default(),
+        text_pipeline: Default::default(),
+        realtime_vad: Default::default(),
         context: voicewin_core::context::ContextToggles::default(),
     };
 
@@ -142,6 +166,7 @@ async fn main() -> anyhow::Result<()> {
         matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
             "slack.exe".into(),
         )],
+        auto_dictate: Default::default(),
         overrides: PowerModeOverrides {
             insert_mode: Some(InsertMode::PasteAndEnter),
             ..Default::default()
@@ -154,6 +179,7 @@ async fn main() -> anyhow::Result<()> {
         mode: PromptMode::Enhancer,
         prompt_text: "Clean up grammar and punctuation.".into(),
         trigger_words: vec!["rewrite".into()],
+        decoding: Default::default(),
     }];
 
     let cfg = EngineConfig {
@@ -161,6 +187,9 @@ async fn main() -> anyhow::Result<()> {
         profiles: vec![profile],
         prompts,
         llm_api_key,
+        spoken_replacements: Default::default(),
+        insert_commands: Default::default(),
+        snippets: vec![],
     };
 
     let engine = VoicewinEngine::new(
@@ -0,0 +1,102 @@
+//! `voicewin-cli mcp`: runs the MCP tool server from `voicewin_appcore::mcp` over stdio.
+//!
+//! It reads the same config, History and models as the desktop app, so register it with an
+//! agent or editor as a stdio server, e.g.
+//!
+//! ```json
+//! { "mcpServers": { "voicewin": { "command": "voicewin-cli", "args": ["mcp"] } } }
+//! ```
+//!
+//! For a portable install pass `--data-dir <VoiceWin>/data` to use that folder instead of the
+//! per-user directories. stdout carries the protocol, so nothing else is printed there.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use voicewin_appcore::mcp::McpServer;
+use voicewin_appcore::service::AppService;
+use voicewin_engine::traits::{AppContextProvider, Inserter};
+
+/// Matches `identifier` in the shell's `tauri.conf.json`.
+const APP_IDENTIFIER: &str = "com.voicewin.app";
+
+/// Roaming and local app data dirs, resolved the way the Tauri shell resolves them.
+fn installed_dirs() -> anyhow::Result<(PathBuf, PathBuf)> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .with_context(|| format!("{name} is not set; pass --data-dir"))
+    };
+
+    #[cfg(windows)]
+    let (data, local) = (env_dir("APPDATA")?, env_dir("LOCALAPPDATA")?);
+    #[cfg(target_os = "macos")]
+    let (data, local) = {
+        let dir = env_dir("HOME")?.join("Library/Application Support");
+        (dir.clone(), dir)
+    };
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let (data, local) = {
+        let dir = match env_dir("XDG_DATA_HOME") {
+            Ok(dir) => dir,
+            Err(_) => env_dir("HOME")?.join(".local/share"),
+        };
+        (dir.clone(), dir)
+    };
+
+    Ok((data.join(APP_IDENTIFIER), local.join(APP_IDENTIFIER)))
+}
+
+fn platform_providers() -> (Arc<dyn AppContextProvider>, Arc<dyn Inserter>) {
+    #[cfg(windows)]
+    let ctx: Arc<dyn AppContextProvider> =
+        Arc::new(voicewin_platform::windows::WindowsContextProvider);
+    #[cfg(target_os = "macos")]
+    let ctx: Arc<dyn AppContextProvider> = Arc::new(voicewin_platform::macos::MacosContextProvider);
+    #[cfg(target_os = "linux")]
+    let ctx: Arc<dyn AppContextProvider> = Arc::new(voicewin_platform::linux::LinuxContextProvider);
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    let ctx: Arc<dyn AppContextProvider> = voicewin_platform::test::TestContextProvider::new(
+        voicewin_core::types::AppIdentity::new().with_process_name("unknown"),
+        Default::default(),
+    )
+    .boxed();
+
+    #[cfg(windows)]
+    let inserter: Arc<dyn Inserter> = Arc::new(voicewin_platform::windows::WindowsInserter);
+    #[cfg(target_os = "macos")]
+    let inserter: Arc<dyn Inserter> = Arc::new(voicewin_platform::macos::MacosInserter);
+    #[cfg(target_os = "linux")]
+    let inserter: Arc<dyn Inserter> = Arc::new(voicewin_platform::linux::LinuxInserter);
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    let inserter: Arc<dyn Inserter> = Arc::new(voicewin_platform::test::StdoutInserter);
+
+    (ctx, inserter)
+}
+
+pub async fn run(args: &[String]) -> anyhow::Result<()> {
+    let mut data_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--data-dir" => {
+                data_dir = Some(PathBuf::from(
+                    args.next().context("--data-dir needs a value")?,
+                ))
+            }
+            other => return Err(anyhow!("unknown mcp argument {other} (--data-dir)")),
+        }
+    }
+
+    let (data_dir, local_data_dir) = match data_dir {
+        Some(dir) => (dir.clone(), dir),
+        None => installed_dirs()?,
+    };
+    let (ctx, inserter) = platform_providers();
+    let service = AppService::new(data_dir.join("config.json"), ctx, inserter)
+        .with_local_data_dir(local_data_dir);
+
+    McpServer::new(service).serve_stdio().await
+}
@@ -1,5 +1,11 @@
+use crate::diarization::DiarizationSettings;
 use crate::enhancement::PromptTemplate;
-use crate::power_mode::{GlobalDefaults, PowerModeProfile};
+use crate::insert_command::InsertCommandSettings;
+use crate::power_mode::{AppMatcher, GlobalDefaults, PowerModeProfile};
+use crate::redaction::RedactionSettings;
+use crate::stt::STT_PROVIDER_LOCAL;
+use crate::text::SpokenReplacementSettings;
+use crate::types::AppIdentity;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,4 +17,878 @@ pub struct AppConfig {
     // Secrets are stored outside this struct at rest.
     #[serde(default)]
     pub llm_api_key_present: bool,
+
+    #[serde(default)]
+    pub performance: PerformanceSettings,
+
+    #[serde(default)]
+    pub overlay: OverlaySettings,
+
+    #[serde(default)]
+    pub sound_cues: SoundCueSettings,
+
+    /// Drop sessions whose audio never got above the noise floor (hotkey pressed by accident).
+    #[serde(default)]
+    pub silent_sessions: SilentSessionSettings,
+
+    /// Apps where dictation must never start (password managers, banking apps, ...).
+    #[serde(default)]
+    pub blocked_apps: Vec<AppMatcher>,
+
+    #[serde(default)]
+    pub redaction: RedactionSettings,
+
+    /// Encrypt `history.json` at rest with a key kept in the OS keyring.
+    #[serde(default)]
+    pub encrypt_history: bool,
+
+    #[serde(default)]
+    pub stage_timeouts: StageTimeouts,
+
+    /// Temperature, max_tokens, stop sequences and response format for the LLM; prompts can
+    /// override each field.
+    #[serde(default)]
+    pub llm_decoding: crate::enhancement::LlmDecodingSettings,
+
+    /// OpenAI-style or Azure OpenAI endpoint.
+    #[serde(default)]
+    pub llm_endpoint_flavor: crate::enhancement::LlmEndpointFlavor,
+
+    /// Checks that fall back to the raw transcript when enhancement output looks wrong.
+    #[serde(default)]
+    pub enhancement_guardrails: crate::guardrails::OutputGuardrailSettings,
+
+    #[serde(default)]
+    pub insertion: InsertionSettings,
+
+    /// Label speakers ("Speaker 1: ...") when the STT provider supports it.
+    #[serde(default)]
+    pub diarization: DiarizationSettings,
+
+    #[serde(default)]
+    pub spoken_replacements: SpokenReplacementSettings,
+
+    /// Trailing phrases ("send it", "just copy") that change the insert mode for one session.
+    #[serde(default)]
+    pub insert_commands: InsertCommandSettings,
+
+    /// Named texts expanded when the user says "insert <name>".
+    #[serde(default)]
+    pub snippets: Vec<crate::enhancement::Snippet>,
+
+    /// POST each session result to a URL (n8n, Zapier, ...). The bearer token is kept in
+    /// the OS keyring.
+    #[serde(default)]
+    pub webhook: WebhookSettings,
+
+    /// Scheduled LLM summary of the day's (or week's) History.
+    #[serde(default)]
+    pub digest: crate::digest::DigestSettings,
+
+    #[serde(default)]
+    pub updates: UpdateSettings,
+
+    /// Prices for the cost estimate of cloud STT and LLM usage.
+    #[serde(default)]
+    pub pricing: crate::usage::PricingSettings,
+
+    /// Outbound proxy for cloud providers, model downloads and update checks.
+    #[serde(default)]
+    pub proxy: ProxySettings,
+
+    #[serde(default)]
+    pub tls: TlsSettings,
+
+    /// Opt-in in-memory log of provider HTTP traffic, for diagnosing failed requests.
+    #[serde(default)]
+    pub provider_log: ProviderLogSettings,
+
+    /// Never touch the network: local STT only, no enhancement, webhook, downloads or
+    /// update checks.
+    #[serde(default)]
+    pub offline_mode: bool,
+
+    /// While the OS is in do-not-disturb, play no sound cues and don't pop the overlay back
+    /// up for results; the recording pill still shows when dictation is started.
+    #[serde(default)]
+    pub respect_do_not_disturb: bool,
+
+    #[serde(default)]
+    pub meeting: crate::meeting::MeetingSettings,
+
+    /// Start dictation by saying a wake phrase instead of pressing the hotkey.
+    #[serde(default)]
+    pub wake_word: crate::wake_word::WakeWordSettings,
+
+    /// When the dictation language changes, switch to the installed local model that is most
+    /// accurate for it instead of only suggesting it.
+    #[serde(default)]
+    pub auto_select_model_for_language: bool,
+
+    /// STT providers to try, in order, when the selected one can't be used at session start
+    /// (no API key, model missing, no network), e.g. `["elevenlabs", "local"]`.
+    #[serde(default)]
+    pub stt_fallbacks: Vec<String>,
+
+    #[serde(default)]
+    pub quota_warning: QuotaWarningSettings,
+
+    /// The program run by the `command` STT provider.
+    #[serde(default)]
+    pub command_stt: crate::stt::CommandSttSettings,
+}
+
+impl AppConfig {
+    /// Local STT, no enhancement, no profiles or prompts, and every other setting at its
+    /// default. For tests across the workspace, which then set only what they exercise.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn for_tests() -> Self {
+        Self {
+            defaults: GlobalDefaults {
+                enable_enhancement: false,
+                prompt_id: None,
+                insert_mode: crate::types::InsertMode::Paste,
+                stt_provider: "local".into(),
+                stt_model: "mock".into(),
+                language: "en".into(),
+                llm_base_url: "https://example.com/v1".into(),
+                llm_model: "gpt-4o-mini".into(),
+                microphone_device: None,
+                history_enabled: true,
+                basic_cleanup: false,
+                output_format: Default::default(),
+                disfluency_filter: Default::default(),
+                text_pipeline: Default::default(),
+                realtime_vad: Default::default(),
+                context: Default::default(),
+            },
+            profiles: vec![],
+            prompts: vec![],
+            llm_api_key_present: false,
+            performance: Default::default(),
+            overlay: Default::default(),
+            sound_cues: Default::default(),
+            silent_sessions: Default::default(),
+            blocked_apps: vec![],
+            redaction: Default::default(),
+            encrypt_history: false,
+            stage_timeouts: Default::default(),
+            llm_decoding: Default::default(),
+            llm_endpoint_flavor: Default::default(),
+            enhancement_guardrails: Default::default(),
+            insertion: Default::default(),
+            diarization: Default::default(),
+            spoken_replacements: Default::default(),
+            insert_commands: Default::default(),
+            snippets: vec![],
+            webhook: Default::default(),
+            digest: Default::default(),
+            updates: Default::default(),
+            pricing: Default::default(),
+            proxy: Default::default(),
+            tls: Default::default(),
+            provider_log: Default::default(),
+            offline_mode: false,
+            respect_do_not_disturb: false,
+            meeting: Default::default(),
+            wake_word: Default::default(),
+            auto_select_model_for_language: false,
+            stt_fallbacks: vec![],
+            quota_warning: Default::default(),
+            command_stt: Default::default(),
+        }
+    }
+
+    /// The first deny-list entry matching `app`, if any.
+    pub fn blocked_app_matcher(&self, app: &AppIdentity) -> Option<&AppMatcher> {
+        self.blocked_apps.iter().find(|m| m.matches(app))
+    }
+
+    /// With `offline_mode` on, rewrite the settings a session uses so nothing needs the
+    /// network: cloud STT becomes `local_stt_model`, enhancement and the webhook are off.
+    /// Meant for the in-memory copy a session runs with, never for the saved config.
+    pub fn apply_offline_mode(&mut self, local_stt_model: &str) {
+        if !self.offline_mode {
+            return;
+        }
+        let d = &mut self.defaults;
+        d.enable_enhancement = false;
+        if d.stt_provider != STT_PROVIDER_LOCAL {
+            d.stt_provider = STT_PROVIDER_LOCAL.into();
+            d.stt_model = local_stt_model.into();
+        }
+        for p in &mut self.profiles {
+            let o = &mut p.overrides;
+            o.enable_enhancement = None;
+            if o.stt_provider
+                .as_deref()
+                .is_some_and(|v| v != STT_PROVIDER_LOCAL)
+            {
+                o.stt_provider = None;
+                o.stt_model = None;
+            }
+        }
+        self.webhook.enabled = false;
+    }
+}
+
+/// Audible feedback for session state changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoundCueSettings {
+    /// Master switch; the per-event toggles only apply when this is on.
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_true")]
+    pub on_start: bool,
+    #[serde(default = "default_true")]
+    pub on_stop: bool,
+    #[serde(default = "default_true")]
+    pub on_success: bool,
+    #[serde(default = "default_true")]
+    pub on_error: bool,
+
+    /// Cue volume in percent.
+    #[serde(default = "default_cue_volume_pct")]
+    pub volume_pct: u8,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_cue_volume_pct() -> u8 {
+    50
+}
+
+impl Default for SoundCueSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            on_start: true,
+            on_stop: true,
+            on_success: true,
+            on_error: true,
+            volume_pct: default_cue_volume_pct(),
+        }
+    }
+}
+
+impl SoundCueSettings {
+    /// Playback gain (0..=1).
+    pub fn volume(&self) -> f32 {
+        self.volume_pct.min(100) as f32 / 100.0
+    }
+}
+
+/// Cancels a session before STT when nothing in the capture is louder than the noise floor,
+/// instead of paying for a round-trip that returns an empty transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentSessionSettings {
+    #[serde(default = "default_true")]
+    pub auto_cancel: bool,
+
+    /// Peak level (dBFS) the capture must reach at least once to count as speech.
+    #[serde(default = "default_noise_floor_dbfs")]
+    pub noise_floor_dbfs: i32,
+}
+
+fn default_noise_floor_dbfs() -> i32 {
+    -45
+}
+
+impl Default for SilentSessionSettings {
+    fn default() -> Self {
+        Self {
+            auto_cancel: true,
+            noise_floor_dbfs: default_noise_floor_dbfs(),
+        }
+    }
+}
+
+impl SilentSessionSettings {
+    /// Noise floor as a linear sample amplitude (0..=1).
+    pub fn noise_floor(&self) -> f32 {
+        10f32.powf(self.noise_floor_dbfs.min(0) as f32 / 20.0)
+    }
+}
+
+/// Resource usage knobs for constrained devices (e.g. 8 GB ARM laptops).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerformanceSettings {
+    /// Prefer quantized local models, cap whisper threads, and keep less captured audio in RAM.
+    ///
+    /// The audio limit only covers recording. STT still gets the session as one in-memory
+    /// 16 kHz buffer (about 230 MB per hour), because every provider takes its audio as a
+    /// single `AudioInput`; memory-mapping it would mean reworking each of them.
+    #[serde(default)]
+    pub low_memory_mode: bool,
+
+    /// Upper bound for local whisper inference threads. `None` uses the library default
+    /// (or [`LOW_MEMORY_WHISPER_THREADS`] in low-memory mode).
+    #[serde(default)]
+    pub max_whisper_threads: Option<u32>,
+
+    /// Open the realtime streaming connection before audio is ready to flow.
+    #[serde(default)]
+    pub realtime_preconnect: RealtimePreconnect,
+
+    /// Total bandwidth shared by concurrent model downloads, in KiB/s. `None`: unlimited.
+    #[serde(default)]
+    pub max_download_kib_per_sec: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimePreconnect {
+    /// Connect once recording has started.
+    #[default]
+    Off,
+    /// Connect as soon as the hotkey is pressed, in parallel with opening the microphone.
+    OnHotkey,
+    /// Also reconnect right after each session, so the next one starts with a live socket.
+    KeepWarm,
+}
+
+pub const LOW_MEMORY_WHISPER_THREADS: u32 = 2;
+
+impl PerformanceSettings {
+    pub fn effective_whisper_threads(&self) -> Option<u32> {
+        match (self.max_whisper_threads, self.low_memory_mode) {
+            (Some(n), _) => Some(n.max(1)),
+            (None, true) => Some(LOW_MEMORY_WHISPER_THREADS),
+            (None, false) => None,
+        }
+    }
+
+    /// [`Self::max_download_kib_per_sec`] in bytes per second; 0 counts as unlimited.
+    pub fn download_limit_bytes_per_sec(&self) -> Option<u64> {
+        self.max_download_kib_per_sec
+            .filter(|&kib| kib > 0)
+            .map(|kib| u64::from(kib) * 1024)
+    }
+}
+
+/// How text gets into the target app, and what the clipboard inserters do to the user's
+/// clipboard.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InsertionSettings {
+    #[serde(default)]
+    pub clipboard_restore: ClipboardRestoreSettings,
+
+    /// Experimental: with realtime STT, type committed segments into the target app while
+    /// the user is still talking (see `crate::live_typing`). Enhancement and the text
+    /// pipeline are skipped for those sessions, since typed text cannot be rewritten.
+    #[serde(default)]
+    pub live_typing: bool,
+}
+
+/// Putting the user's clipboard back after a paste.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipboardRestoreSettings {
+    /// How long the target app gets to read the pasted text. `None` uses the platform's
+    /// default (1000 ms on macOS, 150 ms on Linux, 50 ms on Windows).
+    #[serde(default)]
+    pub delay_ms: Option<u32>,
+
+    /// Leave the clipboard alone when something else wrote to it during the delay, usually a
+    /// clipboard manager taking ownership of the pasted text.
+    #[serde(default = "default_true")]
+    pub skip_if_changed: bool,
+}
+
+impl Default for ClipboardRestoreSettings {
+    fn default() -> Self {
+        Self {
+            delay_ms: None,
+            skip_if_changed: true,
+        }
+    }
+}
+
+/// Upper bounds for the network-bound pipeline stages, so a hung request fails the
+/// session instead of leaving it stuck. `0` disables the limit for that stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTimeouts {
+    #[serde(default = "default_transcribe_timeout_secs")]
+    pub transcribe_secs: u32,
+    #[serde(default = "default_enhance_timeout_secs")]
+    pub enhance_secs: u32,
+    #[serde(default = "default_insert_timeout_secs")]
+    pub insert_secs: u32,
+}
+
+fn default_transcribe_timeout_secs() -> u32 {
+    120
+}
+
+fn default_enhance_timeout_secs() -> u32 {
+    30
+}
+
+fn default_insert_timeout_secs() -> u32 {
+    15
+}
+
+impl Default for StageTimeouts {
+    fn default() -> Self {
+        Self {
+            transcribe_secs: default_transcribe_timeout_secs(),
+            enhance_secs: default_enhance_timeout_secs(),
+            insert_secs: default_insert_timeout_secs(),
+        }
+    }
+}
+
+impl StageTimeouts {
+    /// The limit for an engine stage label (`"transcribing"`, `"enhancing"`, `"inserting"`).
+    pub fn for_stage(&self, stage: &str) -> Option<std::time::Duration> {
+        let secs = match stage {
+            "transcribing" => self.transcribe_secs,
+            "enhancing" => self.enhance_secs,
+            "inserting" => self.insert_secs,
+            _ => 0,
+        };
+        (secs > 0).then(|| std::time::Duration::from_secs(u64::from(secs)))
+    }
+}
+
+/// Output sink that posts the session result JSON to a user-configured URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookSettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub url: String,
+
+    #[serde(default)]
+    pub mode: WebhookMode,
+}
+
+impl WebhookSettings {
+    /// The URL to post to, when the webhook is switched on and configured.
+    pub fn active_url(&self) -> Option<&str> {
+        let url = self.url.trim();
+        (self.enabled && !url.is_empty()).then_some(url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookMode {
+    /// Insert as usual, then post the result.
+    #[default]
+    AfterInsert,
+    /// Post the result and skip insertion entirely.
+    InsteadOfInsert,
+}
+
+/// Checks for newer releases of the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateSettings {
+    #[serde(default)]
+    pub channel: UpdateChannel,
+
+    /// Look for an update shortly after launch.
+    #[serde(default = "default_true")]
+    pub check_on_startup: bool,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            channel: UpdateChannel::default(),
+            check_on_startup: true,
+        }
+    }
+}
+
+/// HTTP proxy used for every outbound connection, including CONNECT tunnels for websockets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    /// `http://[user:pass@]host:port`. Empty falls back to the `HTTPS_PROXY`/`HTTP_PROXY`
+    /// environment variables.
+    #[serde(default)]
+    pub url: String,
+
+    /// Comma-separated hosts reached directly; `.corp.example` (or `corp.example`) also
+    /// matches subdomains, `*` disables the proxy.
+    #[serde(default)]
+    pub no_proxy: String,
+}
+
+/// Provider request/response log. API keys are always redacted and bodies go through the
+/// redaction filters, but the log still holds what was sent to providers, so it is off by
+/// default and kept in memory only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderLogSettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Exchanges kept; the oldest is dropped first.
+    #[serde(default = "default_provider_log_max_entries")]
+    pub max_entries: u32,
+
+    /// Request and response bodies are cut to this many bytes.
+    #[serde(default = "default_provider_log_max_body_bytes")]
+    pub max_body_bytes: u32,
+}
+
+fn default_provider_log_max_entries() -> u32 {
+    50
+}
+
+fn default_provider_log_max_body_bytes() -> u32 {
+    2048
+}
+
+impl Default for ProviderLogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: default_provider_log_max_entries(),
+            max_body_bytes: default_provider_log_max_body_bytes(),
+        }
+    }
+}
+
+/// Warn before the ElevenLabs credits run out, so dictation can move to a local model
+/// instead of failing mid-sentence with `quota_exceeded`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaWarningSettings {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Warn when fewer minutes of transcription than this are left.
+    #[serde(default = "default_quota_warning_min_minutes")]
+    pub min_minutes: u32,
+
+    /// Credits one minute of transcription costs. An estimate; adjust it if the plan bills
+    /// differently.
+    #[serde(default = "default_quota_credits_per_minute")]
+    pub credits_per_minute: u64,
+}
+
+fn default_quota_warning_min_minutes() -> u32 {
+    30
+}
+
+fn default_quota_credits_per_minute() -> u64 {
+    67
+}
+
+impl Default for QuotaWarningSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_minutes: default_quota_warning_min_minutes(),
+            credits_per_minute: default_quota_credits_per_minute(),
+        }
+    }
+}
+
+impl QuotaWarningSettings {
+    /// Minutes of transcription left with `used` of `limit` credits spent.
+    pub fn remaining_minutes(&self, used: u64, limit: u64) -> u64 {
+        limit.saturating_sub(used) / self.credits_per_minute.max(1)
+    }
+
+    /// Whether that is few enough to warn about.
+    pub fn is_low(&self, used: u64, limit: u64) -> bool {
+        self.enabled && self.remaining_minutes(used, limit) < u64::from(self.min_minutes)
+    }
+}
+
+/// Extra TLS trust for self-hosted gateways whose certificate comes from an internal CA.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsSettings {
+    /// PEM certificate(s), or the path to a PEM or DER file. Trusted on top of the built-in
+    /// roots, never instead of them.
+    #[serde(default)]
+    pub extra_root_cert: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// Also offer pre-releases.
+    Beta,
+}
+
+/// Recording overlay (HUD) behavior.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverlaySettings {
+    /// Let clicks pass through the overlay to the window underneath. Holding the overlay
+    /// modifier (Ctrl, or Cmd on macOS) while hovering makes it interactive again.
+    #[serde(default)]
+    pub click_through: bool,
+
+    /// Which monitor the overlay appears on.
+    #[serde(default)]
+    pub placement: OverlayPlacement,
+
+    /// When the overlay is on screen.
+    #[serde(default)]
+    pub mode: OverlayMode,
+
+    #[serde(default)]
+    pub appearance: OverlayAppearance,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayMode {
+    /// Shown for each session and hidden shortly after it ends.
+    #[default]
+    Auto,
+    /// Always on screen, showing Idle between sessions (status-bar style).
+    Persistent,
+    /// Only while recording and for errors; processing and results stay out of sight.
+    Minimal,
+}
+
+/// Overlay look; applied by the overlay webview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverlayAppearance {
+    #[serde(default)]
+    pub style: OverlayStyle,
+
+    #[serde(default)]
+    pub theme: OverlayTheme,
+
+    /// CSS hex color (`#rgb` or `#rrggbb`). `None` uses the built-in accent.
+    #[serde(default)]
+    pub accent_color: Option<String>,
+
+    /// Overlay opacity in percent.
+    #[serde(default = "default_overlay_opacity_pct")]
+    pub opacity_pct: u8,
+}
+
+pub const MIN_OVERLAY_OPACITY_PCT: u8 = 20;
+
+fn default_overlay_opacity_pct() -> u8 {
+    100
+}
+
+impl Default for OverlayAppearance {
+    fn default() -> Self {
+        Self {
+            style: OverlayStyle::default(),
+            theme: OverlayTheme::default(),
+            accent_color: None,
+            opacity_pct: default_overlay_opacity_pct(),
+        }
+    }
+}
+
+impl OverlayAppearance {
+    /// Clamp opacity to a visible range and drop accent colors that aren't hex colors.
+    pub fn normalized(mut self) -> Self {
+        self.opacity_pct = self.opacity_pct.clamp(MIN_OVERLAY_OPACITY_PCT, 100);
+        self.accent_color = self
+            .accent_color
+            .map(|c| c.trim().to_string())
+            .filter(|c| is_hex_color(c));
+        self
+    }
+}
+
+fn is_hex_color(s: &str) -> bool {
+    s.strip_prefix('#')
+        .is_some_and(|h| matches!(h.len(), 3 | 6) && h.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayStyle {
+    /// Status icon, text, and controls.
+    #[default]
+    Full,
+    /// Icon, level meter, and controls; no status text.
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayTheme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum OverlayPlacement {
+    /// Monitor under the mouse cursor.
+    #[default]
+    FollowCursor,
+    /// Monitor containing the focused window (falls back to the cursor's monitor when the
+    /// focused window can't be located).
+    FollowFocusedWindow,
+    /// A specific monitor, by index in the OS monitor list (falls back to the primary
+    /// monitor when out of range).
+    FixedMonitor { index: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocked_apps_match_foreground_app() {
+        let mut cfg = AppConfig::for_tests();
+        cfg.blocked_apps = vec![
+            AppMatcher::ProcessNameEquals("1Password.exe".into()),
+            AppMatcher::WindowTitleContains("online banking".into()),
+        ];
+
+        let vault = AppIdentity::new().with_process_name("1password.exe");
+        assert_eq!(
+            cfg.blocked_app_matcher(&vault),
+            Some(&AppMatcher::ProcessNameEquals("1Password.exe".into()))
+        );
+
+        let bank = AppIdentity::new()
+            .with_process_name("chrome.exe")
+            .with_window_title("My Bank - Online Banking");
+        assert!(cfg.blocked_app_matcher(&bank).is_some());
+
+        let editor = AppIdentity::new().with_process_name("code.exe");
+        assert_eq!(cfg.blocked_app_matcher(&editor), None);
+    }
+
+    #[test]
+    fn stage_timeouts_map_engine_stages_and_zero_disables() {
+        let mut t = StageTimeouts::default();
+        assert_eq!(
+            t.for_stage("transcribing"),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(t.for_stage("done"), None);
+
+        t.enhance_secs = 0;
+        assert_eq!(t.for_stage("enhancing"), None);
+    }
+
+    #[test]
+    fn low_memory_caps_whisper_threads_unless_overridden() {
+        let mut p = PerformanceSettings::default();
+        assert_eq!(p.effective_whisper_threads(), None);
+
+        p.low_memory_mode = true;
+        assert_eq!(
+            p.effective_whisper_threads(),
+            Some(LOW_MEMORY_WHISPER_THREADS)
+        );
+
+        p.max_whisper_threads = Some(0);
+        assert_eq!(p.effective_whisper_threads(), Some(1));
+    }
+
+    #[test]
+    fn overlay_appearance_normalizes_opacity_and_accent() {
+        let a = OverlayAppearance {
+            accent_color: Some(" #1A2b3C ".into()),
+            opacity_pct: 5,
+            ..Default::default()
+        }
+        .normalized();
+        assert_eq!(a.accent_color.as_deref(), Some("#1A2b3C"));
+        assert_eq!(a.opacity_pct, MIN_OVERLAY_OPACITY_PCT);
+
+        let a = OverlayAppearance {
+            accent_color: Some("red; background: url(x)".into()),
+            opacity_pct: 250,
+            ..Default::default()
+        }
+        .normalized();
+        assert_eq!(a.accent_color, None);
+        assert_eq!(a.opacity_pct, 100);
+    }
+
+    #[test]
+    fn offline_mode_forces_local_stt_without_enhancement() {
+        let mut cfg = AppConfig::for_tests();
+        cfg.defaults.enable_enhancement = true;
+        cfg.defaults.stt_provider = "elevenlabs".into();
+        cfg.defaults.stt_model = "scribe_v2".into();
+        cfg.webhook.enabled = true;
+        let overrides = |provider: &str, model: &str| crate::power_mode::PowerModeOverrides {
+            enable_enhancement: Some(true),
+            stt_provider: Some(provider.into()),
+            stt_model: Some(model.into()),
+            ..Default::default()
+        };
+        let profile = |overrides| PowerModeProfile {
+            id: crate::types::ProfileId::new(),
+            name: "p".into(),
+            enabled: true,
+            matchers: vec![],
+            auto_dictate: Default::default(),
+            overrides,
+        };
+        cfg.profiles = vec![
+            profile(overrides("elevenlabs", "scribe_v2_realtime")),
+            profile(overrides("local", "tiny.bin")),
+        ];
+
+        let online = cfg.clone();
+        let mut unchanged = online.clone();
+        unchanged.apply_offline_mode("base.bin");
+        assert_eq!(unchanged, online);
+
+        cfg.offline_mode = true;
+        cfg.apply_offline_mode("base.bin");
+        assert!(!cfg.defaults.enable_enhancement);
+        assert_eq!(cfg.defaults.stt_provider, "local");
+        assert_eq!(cfg.defaults.stt_model, "base.bin");
+        assert!(!cfg.webhook.enabled);
+        assert_eq!(cfg.profiles[0].overrides.stt_provider, None);
+        assert_eq!(cfg.profiles[0].overrides.stt_model, None);
+        assert_eq!(cfg.profiles[0].overrides.enable_enhancement, None);
+        // A profile already on local STT keeps its model.
+        assert_eq!(
+            cfg.profiles[1].overrides.stt_model.as_deref(),
+            Some("tiny.bin")
+        );
+    }
+
+    #[test]
+    fn webhook_is_active_only_when_enabled_with_a_url() {
+        let mut w = WebhookSettings {
+            enabled: false,
+            url: " https://hooks.example.com/voicewin ".into(),
+            mode: WebhookMode::AfterInsert,
+        };
+        assert_eq!(w.active_url(), None);
+
+        w.enabled = true;
+        assert_eq!(w.active_url(), Some("https://hooks.example.com/voicewin"));
+
+        w.url = "  ".into();
+        assert_eq!(w.active_url(), None);
+    }
+
+    #[test]
+    fn quota_warning_converts_credits_to_minutes() {
+        let mut q = QuotaWarningSettings {
+            enabled: true,
+            min_minutes: 30,
+            credits_per_minute: 100,
+        };
+        assert_eq!(q.remaining_minutes(7_000, 10_000), 30);
+        assert!(!q.is_low(7_000, 10_000));
+        assert!(q.is_low(7_001, 10_000));
+        assert_eq!(q.remaining_minutes(12_000, 10_000), 0);
+
+        q.enabled = false;
+        assert!(!q.is_low(10_000, 10_000));
+        q.credits_per_minute = 0;
+        assert_eq!(q.remaining_minutes(0, 10), 10);
+    }
 }
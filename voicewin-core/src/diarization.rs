@@ -0,0 +1,126 @@
+// Speaker-labelled transcripts for meeting-style dictation.
+//
+// Providers that support diarization return per-speaker segments; before enhancement the
+// transcript is rewritten as one "Speaker N: ..." line per turn so the prompt (e.g. "summarize
+// this meeting") can attribute what was said.
+
+use serde::{Deserialize, Serialize};
+
+use crate::text::filter_transcription_output_for;
+use crate::types::{DisfluencyFilter, OutputFormat};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiarizationSettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Expected number of speakers; `None` lets the provider decide.
+    #[serde(default)]
+    pub num_speakers: Option<u32>,
+}
+
+/// A run of speech attributed to one speaker. Speakers are numbered from 1 in order of
+/// first appearance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeakerSegment {
+    pub speaker: u32,
+    pub text: String,
+}
+
+/// Maps provider speaker ids ("speaker_0", "A", ...) to 1-based numbers by first appearance.
+#[derive(Debug, Default)]
+pub struct SpeakerNumbering {
+    seen: Vec<String>,
+}
+
+impl SpeakerNumbering {
+    pub fn number(&mut self, id: &str) -> u32 {
+        let idx = match self.seen.iter().position(|s| s == id) {
+            Some(i) => i,
+            None => {
+                self.seen.push(id.to_string());
+                self.seen.len() - 1
+            }
+        };
+        idx as u32 + 1
+    }
+}
+
+/// "Speaker N: ..." lines, one per turn (consecutive segments of the same speaker are
+/// merged). `None` when fewer than two speakers were heard, so single-speaker dictation keeps
+/// its plain text.
+pub fn format_speaker_turns(
+    segments: &[SpeakerSegment],
+    disfluency: DisfluencyFilter,
+) -> Option<String> {
+    let mut turns: Vec<(u32, String)> = Vec::new();
+    for seg in segments {
+        let text = filter_transcription_output_for(&seg.text, OutputFormat::Plain, disfluency);
+        if text.is_empty() {
+            continue;
+        }
+        match turns.last_mut() {
+            Some((speaker, acc)) if *speaker == seg.speaker => {
+                acc.push(' ');
+                acc.push_str(&text);
+            }
+            _ => turns.push((seg.speaker, text)),
+        }
+    }
+
+    let first = turns.first()?.0;
+    if turns.iter().all(|(s, _)| *s == first) {
+        return None;
+    }
+
+    Some(
+        turns
+            .iter()
+            .map(|(s, t)| format!("Speaker {s}: {t}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(speaker: u32, text: &str) -> SpeakerSegment {
+        SpeakerSegment {
+            speaker,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn numbers_speakers_by_first_appearance() {
+        let mut n = SpeakerNumbering::default();
+        assert_eq!(n.number("speaker_3"), 1);
+        assert_eq!(n.number("speaker_0"), 2);
+        assert_eq!(n.number("speaker_3"), 1);
+    }
+
+    #[test]
+    fn formats_turns_and_merges_consecutive_segments() {
+        let segments = vec![
+            seg(1, "Let's start."),
+            seg(1, " um  Budget first."),
+            seg(2, "Sounds good."),
+            seg(1, "[noise]"),
+            seg(1, "Next week then."),
+        ];
+        assert_eq!(
+            format_speaker_turns(&segments, DisfluencyFilter::Light).as_deref(),
+            Some(
+                "Speaker 1: Let's start. Budget first.\nSpeaker 2: Sounds good.\nSpeaker 1: Next week then."
+            )
+        );
+
+        assert_eq!(
+            format_speaker_turns(&[seg(1, "Just me.")], DisfluencyFilter::Light),
+            None
+        );
+        assert_eq!(format_speaker_turns(&[], DisfluencyFilter::Light), None);
+    }
+}
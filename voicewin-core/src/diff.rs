@@ -0,0 +1,122 @@
+// Word-level diff between the raw transcript and the enhanced text, so the session details
+// window can show exactly what the LLM changed.
+//
+// Words are whitespace-separated tokens compared verbatim: "hello," and "Hello" differ, which
+// is what a user auditing punctuation and casing edits wants to see.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Removed,
+    Added,
+}
+
+/// A run of consecutive words with the same [`DiffOp`], joined by single spaces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSpan {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// Diff `before` against `after` word by word (longest common subsequence). Removals are
+/// listed before the additions that replace them.
+pub fn word_diff(before: &str, after: &str) -> Vec<DiffSpan> {
+    let a: Vec<&str> = before.split_whitespace().collect();
+    let b: Vec<&str> = after.split_whitespace().collect();
+
+    // lcs[i][j]: length of the LCS of a[i..] and b[j..].
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut spans: Vec<DiffSpan> = Vec::new();
+    let mut push = |op: DiffOp, word: &str| match spans.last_mut() {
+        Some(last) if last.op == op => {
+            last.text.push(' ');
+            last.text.push_str(word);
+        }
+        _ => spans.push(DiffSpan {
+            op,
+            text: word.to_string(),
+        }),
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            push(DiffOp::Equal, a[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push(DiffOp::Removed, a[i]);
+            i += 1;
+        } else {
+            push(DiffOp::Added, b[j]);
+            j += 1;
+        }
+    }
+    for w in &a[i..] {
+        push(DiffOp::Removed, w);
+    }
+    for w in &b[j..] {
+        push(DiffOp::Added, w);
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(op: DiffOp, text: &str) -> DiffSpan {
+        DiffSpan {
+            op,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn identical_text_is_one_equal_span() {
+        assert_eq!(
+            word_diff("send the  report", "send the report"),
+            vec![span(DiffOp::Equal, "send the report")]
+        );
+        assert!(word_diff("", " ").is_empty());
+    }
+
+    #[test]
+    fn groups_removed_and_added_words() {
+        assert_eq!(
+            word_diff("um so send the the report", "So, send the report."),
+            vec![
+                span(DiffOp::Removed, "um so"),
+                span(DiffOp::Added, "So,"),
+                span(DiffOp::Equal, "send the"),
+                span(DiffOp::Removed, "the report"),
+                span(DiffOp::Added, "report."),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_empty_sides() {
+        assert_eq!(
+            word_diff("", "hi there"),
+            vec![span(DiffOp::Added, "hi there")]
+        );
+        assert_eq!(
+            word_diff("hi there", ""),
+            vec![span(DiffOp::Removed, "hi there")]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+// Daily/weekly digests: a summary of the period's dictations written by the configured LLM.
+//
+// Periods are aligned to UTC (days start at midnight, weeks on Monday), matching the
+// timestamps used for note files. A digest always covers the most recent *completed*
+// period, so it can be generated any time after that period ends.
+
+use serde::{Deserialize, Serialize};
+
+use crate::note::utc_date_time;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Default instructions for the digest LLM call; `DigestSettings::prompt` overrides them.
+pub const DEFAULT_DIGEST_PROMPT: &str = "You summarize a person's dictation log. \
+Write a short Markdown digest of the period: a one-paragraph overview, then bullet points \
+grouped by topic or app covering decisions, tasks, and follow-ups. Only use information \
+from the log. Do not add a title.";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DigestSettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub period: DigestPeriod,
+
+    /// Custom system prompt; empty uses `DEFAULT_DIGEST_PROMPT`.
+    #[serde(default)]
+    pub prompt: String,
+
+    /// Also append the digest to this Markdown file (`{date}` expands to the period start).
+    /// The digest is always kept in History.
+    #[serde(default)]
+    pub note_path: Option<String>,
+}
+
+impl DigestSettings {
+    pub fn system_prompt(&self) -> &str {
+        match self.prompt.trim() {
+            "" => DEFAULT_DIGEST_PROMPT,
+            p => p,
+        }
+    }
+
+    /// The note file to append to, if one is configured.
+    pub fn active_note_path(&self) -> Option<&str> {
+        self.note_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+/// A half-open `[start, end)` span of Unix milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestWindow {
+    pub start_unix_ms: i64,
+    pub end_unix_ms: i64,
+}
+
+impl DigestWindow {
+    pub fn contains(&self, ts_unix_ms: i64) -> bool {
+        (self.start_unix_ms..self.end_unix_ms).contains(&ts_unix_ms)
+    }
+}
+
+/// The most recent period that ended at or before `now_unix_ms`.
+pub fn digest_window(period: DigestPeriod, now_unix_ms: i64) -> DigestWindow {
+    let today = now_unix_ms.div_euclid(DAY_MS) * DAY_MS;
+    match period {
+        DigestPeriod::Daily => DigestWindow {
+            start_unix_ms: today - DAY_MS,
+            end_unix_ms: today,
+        },
+        DigestPeriod::Weekly => {
+            // 1970-01-01 was a Thursday, three days after a Monday.
+            let days_since_monday = (now_unix_ms.div_euclid(DAY_MS) + 3).rem_euclid(7);
+            let this_monday = today - days_since_monday * DAY_MS;
+            DigestWindow {
+                start_unix_ms: this_monday - 7 * DAY_MS,
+                end_unix_ms: this_monday,
+            }
+        }
+    }
+}
+
+/// One dictation fed into the digest prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestItem {
+    pub ts_unix_ms: i64,
+    pub app: Option<String>,
+    pub text: String,
+}
+
+/// The user message for the digest LLM call: one line per dictation, oldest first.
+pub fn build_digest_user_message(period: DigestPeriod, items: &[DigestItem]) -> String {
+    let label = match period {
+        DigestPeriod::Daily => "the day",
+        DigestPeriod::Weekly => "the week",
+    };
+    let mut out = format!("Dictations from {label} (times are UTC):\n");
+    for item in items {
+        let (date, time) = utc_date_time(item.ts_unix_ms);
+        let app = item.app.as_deref().unwrap_or("unknown app");
+        let text = item.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        out.push_str(&format!("- {date} {time} [{app}] {text}\n"));
+    }
+    out
+}
+
+/// The digest as appended to a note file.
+pub fn format_digest_note(period: DigestPeriod, window: DigestWindow, digest: &str) -> String {
+    let (date, _) = utc_date_time(window.start_unix_ms);
+    let heading = match period {
+        DigestPeriod::Daily => format!("Daily digest {date}"),
+        DigestPeriod::Weekly => format!("Weekly digest (week of {date})"),
+    };
+    format!("\n## {heading}\n\n{}\n", digest.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Thursday 2026-10-15T18:24:09Z
+    const TS: i64 = 1_792_088_649_000;
+
+    #[test]
+    fn windows_cover_the_last_completed_utc_period() {
+        let daily = digest_window(DigestPeriod::Daily, TS);
+        assert_eq!(utc_date_time(daily.start_unix_ms).0, "2026-10-14");
+        assert_eq!(daily.end_unix_ms - daily.start_unix_ms, DAY_MS);
+        assert!(!daily.contains(TS));
+
+        let weekly = digest_window(DigestPeriod::Weekly, TS);
+        assert_eq!(utc_date_time(weekly.start_unix_ms).0, "2026-10-05");
+        assert_eq!(utc_date_time(weekly.end_unix_ms).0, "2026-10-12");
+
+        // Exactly at midnight the day that just ended is the one summarized.
+        let midnight = TS.div_euclid(DAY_MS) * DAY_MS;
+        assert_eq!(
+            digest_window(DigestPeriod::Daily, midnight).end_unix_ms,
+            midnight
+        );
+    }
+
+    #[test]
+    fn user_message_lists_dictations_on_single_lines() {
+        let items = [DigestItem {
+            ts_unix_ms: TS,
+            app: Some("slack.exe".into()),
+            text: "Ship the\nrelease  Friday".into(),
+        }];
+        assert_eq!(
+            build_digest_user_message(DigestPeriod::Daily, &items),
+            "Dictations from the day (times are UTC):\n- 2026-10-15 18:24 [slack.exe] Ship the release Friday\n"
+        );
+    }
+
+    #[test]
+    fn empty_prompt_falls_back_to_default() {
+        let mut s = DigestSettings::default();
+        assert_eq!(s.system_prompt(), DEFAULT_DIGEST_PROMPT);
+        s.prompt = " Be brief. ".into();
+        assert_eq!(s.system_prompt(), "Be brief.");
+        s.note_path = Some("  ".into());
+        assert_eq!(s.active_note_path(), None);
+    }
+}
@@ -1,5 +1,7 @@
-use crate::text::{filter_enhancement_output, filter_transcription_output};
-use crate::types::PromptId;
+use crate::text::{
+    filter_enhancement_output, filter_transcription_output, filter_transcription_output_for,
+};
+use crate::types::{DisfluencyFilter, OutputFormat, PromptId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,14 +17,117 @@ pub struct PromptTemplate {
     pub mode: PromptMode,
     pub prompt_text: String,
     pub trigger_words: Vec<String>,
+
+    /// Overrides of the global `llm_decoding` for this prompt; unset fields use the global.
+    #[serde(default, skip_serializing_if = "LlmDecodingSettings::is_empty")]
+    pub decoding: LlmDecodingSettings,
+}
+
+/// How the chat completions endpoint should decode. Self-hosted models often need a specific
+/// temperature, a token cap or stop sequences to behave.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LlmDecodingSettings {
+    /// Temperature × 100 (`30` = 0.3). Unset uses [`DEFAULT_TEMPERATURE_PCT`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature_pct: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+
+    /// `json_object` asks for JSON output; a top-level `"text"` string is then inserted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+}
+
+pub const DEFAULT_TEMPERATURE_PCT: u16 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    Text,
+    JsonObject,
+}
+
+/// Which URL scheme and auth header the chat completions endpoint expects.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LlmEndpointFlavor {
+    /// `{base_url}/chat/completions` with `Authorization: Bearer <key>`.
+    #[default]
+    OpenAi,
+    /// Azure OpenAI: `base_url` is the resource endpoint
+    /// (`https://<resource>.openai.azure.com`), the model is the deployment name, and the key
+    /// goes in an `api-key` header.
+    Azure {
+        #[serde(default = "default_azure_api_version")]
+        api_version: String,
+    },
+}
+
+pub const DEFAULT_AZURE_API_VERSION: &str = "2024-10-21";
+
+fn default_azure_api_version() -> String {
+    DEFAULT_AZURE_API_VERSION.into()
+}
+
+impl LlmDecodingSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// These settings, with unset fields taken from `base` (a prompt's overrides over the
+    /// global settings).
+    pub fn or(&self, base: &Self) -> Self {
+        Self {
+            temperature_pct: self.temperature_pct.or(base.temperature_pct),
+            max_tokens: self.max_tokens.or(base.max_tokens),
+            stop: if self.stop.is_empty() {
+                base.stop.clone()
+            } else {
+                self.stop.clone()
+            },
+            response_format: self.response_format.or(base.response_format),
+        }
+    }
+
+    pub fn temperature(&self) -> f64 {
+        f64::from(self.temperature_pct.unwrap_or(DEFAULT_TEMPERATURE_PCT)) / 100.0
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct EnhancementContext {
     pub currently_selected_text: Option<String>,
     pub clipboard_context: Option<String>,
     pub current_window_context: Option<String>,
     pub custom_vocabulary: Option<String>,
+    pub output_format: OutputFormat,
+    pub disfluency_filter: DisfluencyFilter,
+}
+
+/// Stored text inserted verbatim when the user says "insert <name>".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub name: String,
+    pub text: String,
+}
+
+/// Spoken word that introduces a snippet name ("insert my address").
+pub const SNIPPET_COMMAND: &str = "insert";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetExpansion {
+    pub processed_transcript: String,
+
+    /// Names of the snippets that were expanded, in transcript order.
+    pub expanded: Vec<String>,
+
+    /// The whole utterance was a single snippet command; the text is the snippet, verbatim.
+    pub whole_utterance: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -90,6 +195,72 @@ pub fn detect_trigger_word(transcript: &str, prompts: &[PromptTemplate]) -> Prom
     }
 }
 
+/// Replace every "insert <snippet name>" in the transcript with the snippet's text.
+///
+/// Matching is case-insensitive on whole words and the longest name wins ("my address book"
+/// over "my address"). A transcript that is nothing but one command becomes the snippet text
+/// exactly, without the punctuation the STT provider added around it.
+pub fn expand_snippets(transcript: &str, snippets: &[Snippet]) -> SnippetExpansion {
+    let mut candidates: Vec<(String, &Snippet)> = snippets
+        .iter()
+        .filter_map(|s| {
+            let name = s.name.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!name.is_empty()).then(|| (format!("{SNIPPET_COMMAND} {name}"), s))
+        })
+        .collect();
+    candidates.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.chars().count()));
+
+    let bare = transcript.trim_matches(|c: char| c.is_whitespace() || is_punct(c));
+    for (phrase, snippet) in &candidates {
+        if match_prefix_ignore_ascii_case(bare, phrase) == Some(bare.len()) {
+            return SnippetExpansion {
+                processed_transcript: snippet.text.clone(),
+                expanded: vec![snippet.name.clone()],
+                whole_utterance: true,
+            };
+        }
+    }
+
+    let mut out = String::with_capacity(transcript.len());
+    let mut expanded = vec![];
+    let mut rest = transcript;
+    let mut prev: Option<char> = None;
+    while let Some(ch) = rest.chars().next() {
+        let at_word_start = !prev.is_some_and(char::is_alphanumeric);
+        let hit = candidates
+            .iter()
+            .filter(|_| at_word_start)
+            .find_map(|(phrase, snippet)| {
+                let end = match_prefix_ignore_ascii_case(rest, phrase)?;
+                let whole_word = !rest[end..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric);
+                whole_word.then_some((end, *snippet))
+            });
+
+        match hit {
+            Some((end, snippet)) => {
+                out.push_str(snippet.text.trim());
+                expanded.push(snippet.name.clone());
+                prev = rest[..end].chars().next_back();
+                rest = &rest[end..];
+            }
+            None => {
+                out.push(ch);
+                prev = Some(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+
+    SnippetExpansion {
+        processed_transcript: out,
+        expanded,
+        whole_utterance: false,
+    }
+}
+
 fn strip_leading_trigger(text: &str, trigger: &str) -> Option<String> {
     let trimmed = text.trim();
     let trigger = trigger.trim();
@@ -212,7 +383,8 @@ pub fn build_enhancement_prompt(
     prompt: &PromptTemplate,
     ctx: &EnhancementContext,
 ) -> BuiltPrompt {
-    let transcript = filter_transcription_output(transcript);
+    let transcript =
+        filter_transcription_output_for(transcript, ctx.output_format, ctx.disfluency_filter);
 
     let user = format!("<TRANSCRIPT>\n{}\n</TRANSCRIPT>", transcript);
 
@@ -274,6 +446,9 @@ You are a TRANSCRIPTION ENHANCER, not a conversational chatbot. DO NOT respond;
             v
         ));
     }
+    if let Some(v) = output_format_instructions(ctx.output_format) {
+        system.push_str(&format!("\n\n<OUTPUT_FORMAT>\n{}\n</OUTPUT_FORMAT>", v));
+    }
 
     let messages = vec![
         LlmMessage {
@@ -293,6 +468,21 @@ You are a TRANSCRIPTION ENHANCER, not a conversational chatbot. DO NOT respond;
     }
 }
 
+fn output_format_instructions(format: OutputFormat) -> Option<&'static str> {
+    match format {
+        OutputFormat::Plain => None,
+        OutputFormat::Markdown => Some(
+            "The text goes into a Markdown editor. Keep Markdown syntax (lists, headings, backticks, links) \
+intact and use lists or code spans where the speaker dictates them. Do not rewrite into flowing prose.",
+        ),
+        OutputFormat::Code => Some(
+            "The text goes into a code editor or terminal. Output exactly the code, command, or comment \
+dictated: preserve identifiers, symbols, and casing; no sentence capitalization, closing period, \
+explanations, or code fences.",
+        ),
+    }
+}
+
 pub fn post_process_llm_output(text: &str) -> String {
     filter_enhancement_output(text)
 }
@@ -309,6 +499,7 @@ mod tests {
             mode: PromptMode::Enhancer,
             prompt_text: "Rewrite as email".into(),
             trigger_words: vec!["email".into()],
+            decoding: Default::default(),
         };
         let r = detect_trigger_word("email hello there", &[p.clone()]);
         assert!(r.should_enable_enhancement);
@@ -324,6 +515,7 @@ mod tests {
             mode: PromptMode::Enhancer,
             prompt_text: "Rewrite".into(),
             trigger_words: vec!["rewrite".into()],
+            decoding: Default::default(),
         };
         let r = detect_trigger_word("hello there rewrite.", &[p.clone()]);
         assert!(r.should_enable_enhancement);
@@ -338,12 +530,54 @@ mod tests {
             mode: PromptMode::Enhancer,
             prompt_text: "Rewrite".into(),
             trigger_words: vec!["rewrite".into()],
+            decoding: Default::default(),
         };
         let r = detect_trigger_word("rewrite hello there rewrite", &[p.clone()]);
         assert!(r.should_enable_enhancement);
         assert_eq!(r.processed_transcript, "Hello there");
     }
 
+    #[test]
+    fn snippets_expand_inline_and_as_whole_utterance() {
+        let snippets = [
+            Snippet {
+                name: "my address".into(),
+                text: "1 Main St\nSpringfield".into(),
+            },
+            Snippet {
+                name: "My  address book".into(),
+                text: "https://contacts.example".into(),
+            },
+            Snippet {
+                name: "sig".into(),
+                text: "Best, Ana".into(),
+            },
+        ];
+
+        let whole = expand_snippets("Insert my address.", &snippets);
+        assert!(whole.whole_utterance);
+        assert_eq!(whole.processed_transcript, "1 Main St\nSpringfield");
+
+        let inline = expand_snippets(
+            "Ship it to insert my address, and see insert my address book. Insert sig",
+            &snippets,
+        );
+        assert!(!inline.whole_utterance);
+        assert_eq!(
+            inline.processed_transcript,
+            "Ship it to 1 Main St\nSpringfield, and see https://contacts.example. Best, Ana"
+        );
+        assert_eq!(inline.expanded, ["my address", "My  address book", "sig"]);
+
+        // Only whole words count.
+        let none = expand_snippets("reinsert my address or insert signature", &snippets);
+        assert!(none.expanded.is_empty());
+        assert_eq!(
+            none.processed_transcript,
+            "reinsert my address or insert signature"
+        );
+    }
+
     #[test]
     fn prompt_builder_includes_context_blocks() {
         let p = PromptTemplate {
@@ -352,6 +586,7 @@ mod tests {
             mode: PromptMode::Enhancer,
             prompt_text: "Fix transcript".into(),
             trigger_words: vec![],
+            decoding: Default::default(),
         };
         let ctx = EnhancementContext {
             clipboard_context: Some("foo".into()),
@@ -363,6 +598,28 @@ mod tests {
         assert!(built.system_message.contains("<CLIPBOARD_CONTEXT>"));
         assert!(built.system_message.contains("<CURRENT_WINDOW_CONTEXT>"));
         assert!(built.user_message.contains("<TRANSCRIPT>"));
+        assert!(!built.system_message.contains("<OUTPUT_FORMAT>"));
+    }
+
+    #[test]
+    fn prompt_builder_adds_output_format_and_keeps_code_brackets() {
+        let p = PromptTemplate {
+            id: PromptId::new(),
+            title: "Enhance".into(),
+            mode: PromptMode::Enhancer,
+            prompt_text: "Fix transcript".into(),
+            trigger_words: vec![],
+            decoding: Default::default(),
+        };
+        let ctx = EnhancementContext {
+            output_format: OutputFormat::Code,
+            ..Default::default()
+        };
+
+        let built = build_enhancement_prompt("let v = xs[0]", &p, &ctx);
+        assert!(built.system_message.contains("<OUTPUT_FORMAT>"));
+        assert!(built.system_message.contains("code editor"));
+        assert!(built.user_message.contains("xs[0]"));
     }
 
     #[test]
@@ -370,4 +627,26 @@ mod tests {
         let out = post_process_llm_output("<reasoning>no</reasoning>\nHi");
         assert_eq!(out, "Hi");
     }
+
+    #[test]
+    fn prompt_decoding_overrides_only_what_it_sets() {
+        let global = LlmDecodingSettings {
+            temperature_pct: Some(70),
+            max_tokens: Some(512),
+            stop: vec!["###".into()],
+            response_format: None,
+        };
+        let prompt = LlmDecodingSettings {
+            temperature_pct: Some(0),
+            response_format: Some(ResponseFormat::JsonObject),
+            ..Default::default()
+        };
+
+        let merged = prompt.or(&global);
+        assert_eq!(merged.temperature(), 0.0);
+        assert_eq!(merged.max_tokens, Some(512));
+        assert_eq!(merged.stop, vec!["###".to_string()]);
+        assert_eq!(merged.response_format, Some(ResponseFormat::JsonObject));
+        assert_eq!(LlmDecodingSettings::default().temperature(), 0.3);
+    }
 }
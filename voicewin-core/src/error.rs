@@ -0,0 +1,121 @@
+// User-facing error taxonomy shared by the service layer, the Tauri commands, and the UI.
+//
+// The `kind` drives what the overlay offers (open Settings, retry, copy the text); the
+// message is shown as is and `detail` is for logs and bug reports.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// A setting needs attention: missing or rejected API key, no model, offline mode.
+    Config,
+    /// The OS denied microphone access.
+    MicrophoneAccess,
+    /// The OS denied Accessibility (synthetic input) access.
+    AccessibilityAccess,
+    /// No usable input device, or capture failed.
+    Audio,
+    /// The recording had no words in it.
+    NoSpeech,
+    /// A provider could not be reached (timeout, DNS, proxy).
+    Network,
+    /// A provider answered with an error (rate limit, quota, server error).
+    Provider,
+    /// The text could not be delivered to the target; it is kept for copying.
+    Insertion,
+    /// The target window runs elevated (as administrator) and ignores our input; the text is
+    /// kept for copying.
+    ElevatedTarget,
+    /// The window being dictated into closed before the text arrived; the text was copied to
+    /// the clipboard instead of pasted into whatever took focus.
+    TargetClosed,
+    /// Anything else; `detail` has the cause.
+    Internal,
+}
+
+impl ErrorKind {
+    /// Whether the same action is worth trying again as is.
+    pub fn retryable_by_default(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::NoSpeech | ErrorKind::Network | ErrorKind::Insertion
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("{user_message}")]
+pub struct VoicewinError {
+    pub kind: ErrorKind,
+    pub user_message: String,
+
+    #[serde(default)]
+    pub detail: Option<String>,
+
+    #[serde(default)]
+    pub retryable: bool,
+}
+
+impl VoicewinError {
+    pub fn new(kind: ErrorKind, user_message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            user_message: user_message.into(),
+            detail: None,
+            retryable: kind.retryable_by_default(),
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        let detail = detail.into();
+        // Repeating the message adds nothing.
+        if detail != self.user_message {
+            self.detail = Some(detail);
+        }
+        self
+    }
+
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Same kind and detail, different message (e.g. to say what was done instead).
+    pub fn with_user_message(mut self, user_message: impl Into<String>) -> Self {
+        self.user_message = user_message.into();
+        self
+    }
+}
+
+/// Errors that were only ever strings are `Internal`.
+impl From<String> for VoicewinError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
+impl From<&str> for VoicewinError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_pick_retryability_and_detail_skips_repeats() {
+        let e = VoicewinError::new(ErrorKind::Network, "Could not reach ElevenLabs.")
+            .with_detail("Could not reach ElevenLabs.");
+        assert!(e.retryable);
+        assert_eq!(e.detail, None);
+        assert_eq!(e.to_string(), "Could not reach ElevenLabs.");
+
+        let e = VoicewinError::from("boom").with_detail("io error: boom");
+        assert_eq!(e.kind, ErrorKind::Internal);
+        assert!(!e.retryable);
+        assert_eq!(e.detail.as_deref(), Some("io error: boom"));
+    }
+}
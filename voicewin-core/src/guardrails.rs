@@ -0,0 +1,304 @@
+// Sanity checks on enhancement output before it replaces the transcript.
+//
+// Models sometimes answer the dictation instead of cleaning it up ("Sure! Here's..."),
+// ramble, drop most of it, or translate it. When an Enhancer prompt's output trips one of
+// these checks the raw transcript is inserted instead, with a warning.
+
+use serde::{Deserialize, Serialize};
+
+/// Inputs shorter than this many words skip the length checks; "ok" -> "Okay." is fine.
+const MIN_WORDS_FOR_LENGTH_CHECK: usize = 6;
+
+/// Letters needed on both sides before their scripts are compared.
+const MIN_LETTERS_FOR_LANGUAGE_CHECK: usize = 12;
+
+/// Openers of a chat reply rather than a cleaned-up transcript (lowercase).
+const CHAT_REPLY_OPENERS: &[&str] = &[
+    "sure",
+    "certainly",
+    "of course",
+    "absolutely",
+    "here's",
+    "here is",
+    "i'm sorry",
+    "i am sorry",
+    "as an ai",
+    "i can't",
+    "i cannot",
+    "i'd be happy",
+    "i would be happy",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputGuardrailSettings {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Reject output longer than this percentage of the input (300 = three times as long).
+    /// `0` disables the check.
+    #[serde(default = "default_max_growth_percent")]
+    pub max_growth_percent: u32,
+
+    /// Reject output shorter than this percentage of the input. `0` disables the check.
+    #[serde(default = "default_min_shrink_percent")]
+    pub min_shrink_percent: u32,
+
+    /// Reject output written in a different script than the input (Latin vs. Cyrillic,
+    /// CJK, ...). Turn off for prompts that translate.
+    #[serde(default = "default_true")]
+    pub check_language: bool,
+
+    /// Reject output that opens like a chat reply ("Sure! Here's...") when the dictation
+    /// didn't.
+    #[serde(default = "default_true")]
+    pub reject_chat_replies: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_growth_percent() -> u32 {
+    300
+}
+
+fn default_min_shrink_percent() -> u32 {
+    30
+}
+
+impl Default for OutputGuardrailSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_growth_percent: default_max_growth_percent(),
+            min_shrink_percent: default_min_shrink_percent(),
+            check_language: true,
+            reject_chat_replies: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardrailViolation {
+    TooLong,
+    TooShort,
+    LanguageChanged,
+    ChatReply,
+}
+
+impl GuardrailViolation {
+    /// Short reason for the session warning.
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::TooLong => "the output was much longer than the dictation",
+            Self::TooShort => "the output was much shorter than the dictation",
+            Self::LanguageChanged => "the output was in a different language",
+            Self::ChatReply => "the output looked like a chat reply",
+        }
+    }
+}
+
+/// The first check `output` fails as an enhancement of `input`, if any.
+pub fn check_enhancement_output(
+    input: &str,
+    output: &str,
+    settings: &OutputGuardrailSettings,
+) -> Option<GuardrailViolation> {
+    if !settings.enabled {
+        return None;
+    }
+
+    if settings.reject_chat_replies
+        && opens_like_chat_reply(output)
+        && !opens_like_chat_reply(input)
+    {
+        return Some(GuardrailViolation::ChatReply);
+    }
+
+    if input.split_whitespace().count() >= MIN_WORDS_FOR_LENGTH_CHECK {
+        let (in_len, out_len) = (
+            input.trim().chars().count() as u64,
+            output.trim().chars().count() as u64,
+        );
+        let max = u64::from(settings.max_growth_percent);
+        if max > 0 && out_len * 100 > in_len * max {
+            return Some(GuardrailViolation::TooLong);
+        }
+        let min = u64::from(settings.min_shrink_percent);
+        if min > 0 && out_len * 100 < in_len * min {
+            return Some(GuardrailViolation::TooShort);
+        }
+    }
+
+    if settings.check_language
+        && let (Some(a), Some(b)) = (dominant_script(input), dominant_script(output))
+        && a != b
+    {
+        return Some(GuardrailViolation::LanguageChanged);
+    }
+
+    None
+}
+
+fn opens_like_chat_reply(text: &str) -> bool {
+    let head: String = text
+        .trim_start()
+        .chars()
+        .take(24)
+        .flat_map(char::to_lowercase)
+        .map(|c| if c == '\u{2019}' { '\'' } else { c })
+        .collect();
+    CHAT_REPLY_OPENERS.iter().any(|opener| {
+        head.strip_prefix(opener)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric()))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    // Han, kana and Hangul together: Japanese mixes them, and so may a transcript.
+    Cjk,
+}
+
+fn script_of(c: char) -> Option<Script> {
+    let script = match c as u32 {
+        0x0041..=0x024F => Script::Latin,
+        0x0370..=0x03FF => Script::Greek,
+        0x0400..=0x04FF => Script::Cyrillic,
+        0x0590..=0x05FF => Script::Hebrew,
+        0x0600..=0x06FF => Script::Arabic,
+        0x0900..=0x097F => Script::Devanagari,
+        0x0E00..=0x0E7F => Script::Thai,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF => Script::Cjk,
+        _ => return None,
+    };
+    c.is_alphabetic().then_some(script)
+}
+
+/// The script of at least 60% of the letters in `text`, when there are enough to tell.
+fn dominant_script(text: &str) -> Option<Script> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    let mut total = 0;
+    for script in text.chars().filter_map(script_of) {
+        total += 1;
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    if total < MIN_LETTERS_FOR_LANGUAGE_CHECK {
+        return None;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(_, n)| *n)
+        .filter(|(_, n)| n * 10 >= total * 6)
+        .map(|(s, _)| s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(input: &str, output: &str) -> Option<GuardrailViolation> {
+        check_enhancement_output(input, output, &OutputGuardrailSettings::default())
+    }
+
+    #[test]
+    fn accepts_ordinary_cleanup() {
+        assert_eq!(
+            check(
+                "um so can you send me the the report by friday",
+                "Can you send me the report by Friday?"
+            ),
+            None
+        );
+        assert_eq!(check("ok", "Okay."), None);
+        assert_eq!(
+            check(
+                "sure i can do that tomorrow",
+                "Sure, I can do that tomorrow."
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_chat_replies() {
+        assert_eq!(
+            check(
+                "send me the report",
+                "Sure! Here's the cleaned text: Send me the report."
+            ),
+            Some(GuardrailViolation::ChatReply)
+        );
+        assert_eq!(
+            check(
+                "what time is it",
+                "I\u{2019}m sorry, I can't tell the time."
+            ),
+            Some(GuardrailViolation::ChatReply)
+        );
+        // "Surely" is not "Sure".
+        assert_eq!(check("surely not", "Surely not."), None);
+    }
+
+    #[test]
+    fn rejects_wild_length_changes() {
+        let input = "please remind me to call the dentist tomorrow morning";
+        assert_eq!(
+            check(input, &"Please remind me to call the dentist. ".repeat(6)),
+            Some(GuardrailViolation::TooLong)
+        );
+        assert_eq!(check(input, "Dentist."), Some(GuardrailViolation::TooShort));
+
+        let off = OutputGuardrailSettings {
+            max_growth_percent: 0,
+            min_shrink_percent: 0,
+            ..Default::default()
+        };
+        assert_eq!(check_enhancement_output(input, "Dentist.", &off), None);
+    }
+
+    #[test]
+    fn rejects_a_different_script_unless_disabled() {
+        let input = "please remind me to call the dentist tomorrow";
+        let output = "Пожалуйста, напомни мне позвонить стоматологу завтра.";
+        assert_eq!(
+            check(input, output),
+            Some(GuardrailViolation::LanguageChanged)
+        );
+        assert_eq!(
+            check(
+                "明天提醒我给牙医打电话好吗谢谢你",
+                "明日、歯医者に電話するようにリマインドしてください。"
+            ),
+            None
+        );
+
+        let translating = OutputGuardrailSettings {
+            check_language: false,
+            ..Default::default()
+        };
+        assert_eq!(check_enhancement_output(input, output, &translating), None);
+        assert_eq!(
+            check_enhancement_output(
+                input,
+                output,
+                &OutputGuardrailSettings {
+                    enabled: false,
+                    ..Default::default()
+                }
+            ),
+            None
+        );
+    }
+}
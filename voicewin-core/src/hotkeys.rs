@@ -0,0 +1,143 @@
+// Global shortcuts, one per action. The desktop shell registers them with the OS and keeps
+// the map in its UI state store; this module only decides what counts as the same shortcut,
+// so two actions can't end up bound to one key combination.
+//
+// Shortcuts use the shell's syntax ("Ctrl+Alt+Space", "CommandOrControl+Shift+Z"), which
+// accepts several spellings for each modifier and any order.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    /// Start dictating, or stop and insert.
+    Toggle,
+    /// Record while held.
+    PushToTalk,
+    /// Stop and insert; does nothing when not recording.
+    Stop,
+    Cancel,
+    /// Undo the last insertion in the app it went to.
+    Undo,
+    /// Force the next profile for new sessions, then back to matching by app.
+    CycleProfile,
+    Dismiss,
+    OpenHistory,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 8] = [
+        HotkeyAction::Toggle,
+        HotkeyAction::PushToTalk,
+        HotkeyAction::Stop,
+        HotkeyAction::Cancel,
+        HotkeyAction::Undo,
+        HotkeyAction::CycleProfile,
+        HotkeyAction::Dismiss,
+        HotkeyAction::OpenHistory,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HotkeyAction::Toggle => "Start/stop dictation",
+            HotkeyAction::PushToTalk => "Push to talk",
+            HotkeyAction::Stop => "Stop recording",
+            HotkeyAction::Cancel => "Cancel",
+            HotkeyAction::Undo => "Undo last insertion",
+            HotkeyAction::CycleProfile => "Switch profile",
+            HotkeyAction::Dismiss => "Dismiss overlay",
+            HotkeyAction::OpenHistory => "Open History",
+        }
+    }
+}
+
+/// Assigned shortcut per action; unassigned actions are absent.
+pub type HotkeyMap = BTreeMap<HotkeyAction, String>;
+
+/// A canonical spelling of `shortcut` for comparisons: lowercase, one name per modifier,
+/// modifiers sorted. `CommandOrControl` becomes the modifier it means on this platform.
+pub fn normalize_shortcut(shortcut: &str) -> String {
+    let lower = shortcut.to_ascii_lowercase();
+    let mut parts: Vec<&str> = lower
+        .split('+')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    let key = parts.pop().unwrap_or_default();
+
+    let mut modifiers: Vec<&str> = parts
+        .into_iter()
+        .map(|m| match m {
+            "control" | "ctrl" => "ctrl",
+            "option" | "alt" => "alt",
+            "command" | "cmd" | "super" | "meta" => "super",
+            "commandorcontrol" | "commandorctrl" | "cmdorctrl" | "cmdorcontrol" => {
+                if cfg!(target_os = "macos") {
+                    "super"
+                } else {
+                    "ctrl"
+                }
+            }
+            other => other,
+        })
+        .collect();
+    modifiers.sort_unstable();
+    modifiers.dedup();
+    modifiers.push(key);
+    modifiers.join("+")
+}
+
+/// The other action already bound to `shortcut`, if any.
+pub fn conflicting_action(
+    map: &HotkeyMap,
+    action: HotkeyAction,
+    shortcut: &str,
+) -> Option<HotkeyAction> {
+    let wanted = normalize_shortcut(shortcut);
+    map.iter()
+        .find(|(a, s)| **a != action && normalize_shortcut(s) == wanted)
+        .map(|(a, _)| *a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spellings_of_the_same_shortcut_normalize_equal() {
+        assert_eq!(normalize_shortcut("Ctrl+Alt+Space"), "alt+ctrl+space");
+        assert_eq!(
+            normalize_shortcut(" alt + Control + SPACE "),
+            "alt+ctrl+space"
+        );
+        assert_eq!(normalize_shortcut("Option+Cmd+Z"), "alt+super+z");
+        assert_eq!(normalize_shortcut("Super+Meta+Z"), "super+z");
+        assert_ne!(
+            normalize_shortcut("Ctrl+Z"),
+            normalize_shortcut("Ctrl+Shift+Z")
+        );
+    }
+
+    #[test]
+    fn finds_conflicts_with_other_actions_only() {
+        let map = HotkeyMap::from([
+            (HotkeyAction::Toggle, "Ctrl+Alt+Space".to_string()),
+            (HotkeyAction::Cancel, "Ctrl+Alt+Backspace".to_string()),
+        ]);
+        assert_eq!(
+            conflicting_action(&map, HotkeyAction::Undo, "alt+ctrl+space"),
+            Some(HotkeyAction::Toggle)
+        );
+        // Re-assigning an action its own shortcut is not a conflict.
+        assert_eq!(
+            conflicting_action(&map, HotkeyAction::Toggle, "Control+Alt+Space"),
+            None
+        );
+        assert_eq!(
+            conflicting_action(&map, HotkeyAction::Undo, "Ctrl+Alt+Z"),
+            None
+        );
+    }
+}
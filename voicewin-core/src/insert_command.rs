@@ -0,0 +1,170 @@
+// Spoken commands that change how the current dictation is inserted.
+//
+// Ending a dictation with "send it" pastes and presses Enter; "just copy" leaves the text on
+// the clipboard without pasting. The command is stripped from the text and only affects the
+// session it was spoken in (see `EphemeralOverrides::insert_mode`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::InsertMode;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InsertCommand {
+    pub phrase: String,
+    pub mode: InsertMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InsertCommandSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    #[serde(default = "default_commands")]
+    pub commands: Vec<InsertCommand>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_commands() -> Vec<InsertCommand> {
+    [
+        ("send it", InsertMode::PasteAndEnter),
+        ("just copy", InsertMode::ClipboardOnly),
+    ]
+    .into_iter()
+    .map(|(phrase, mode)| InsertCommand {
+        phrase: phrase.into(),
+        mode,
+    })
+    .collect()
+}
+
+impl Default for InsertCommandSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            commands: default_commands(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsertCommandDetection {
+    pub insert_mode: Option<InsertMode>,
+    pub processed_transcript: String,
+    pub detected_phrase: Option<String>,
+}
+
+/// Look for a command phrase at the end of the transcript ("..., send it."). Longest phrase
+/// wins; a phrase must be a whole trailing word sequence, so "resend it" does not match.
+pub fn detect_insert_command(
+    transcript: &str,
+    settings: &InsertCommandSettings,
+) -> InsertCommandDetection {
+    let none = || InsertCommandDetection {
+        insert_mode: None,
+        processed_transcript: transcript.to_string(),
+        detected_phrase: None,
+    };
+    if !settings.enabled {
+        return none();
+    }
+
+    let body = transcript.trim_end_matches(|c: char| c.is_whitespace() || is_edge_punct(c));
+    let lower = body.to_lowercase();
+
+    let mut commands: Vec<&InsertCommand> = settings
+        .commands
+        .iter()
+        .filter(|c| !c.phrase.trim().is_empty())
+        .collect();
+    commands.sort_by_key(|c| std::cmp::Reverse(c.phrase.trim().chars().count()));
+
+    for cmd in commands {
+        let phrase = cmd.phrase.trim().to_lowercase();
+        if !lower.ends_with(&phrase) {
+            continue;
+        }
+        // Offsets into `lower` are only valid in `body` if lowercasing kept byte lengths.
+        let Some(cut) = body.len().checked_sub(phrase.len()) else {
+            continue;
+        };
+        if lower.len() != body.len() || !body.is_char_boundary(cut) {
+            continue;
+        }
+        if body[..cut]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+        {
+            continue;
+        }
+
+        let rest = body[..cut].trim_end_matches(|c: char| c.is_whitespace() || is_edge_punct(c));
+        if rest.is_empty() {
+            // A bare "send it" has nothing to send; keep it as dictated text.
+            break;
+        }
+        return InsertCommandDetection {
+            insert_mode: Some(cmd.mode),
+            processed_transcript: restore_terminal_punct(rest, transcript),
+            detected_phrase: Some(cmd.phrase.trim().to_string()),
+        };
+    }
+
+    none()
+}
+
+fn is_edge_punct(c: char) -> bool {
+    matches!(c, ',' | '.' | '!' | '?' | ';' | ':' | '-')
+}
+
+/// "Ship it, send it." -> "Ship it." when the dictation closed with sentence punctuation.
+fn restore_terminal_punct(rest: &str, original: &str) -> String {
+    let closing = original
+        .trim_end()
+        .chars()
+        .next_back()
+        .filter(|c| matches!(c, '.' | '!' | '?'));
+    match closing {
+        Some(c) => format!("{rest}{c}"),
+        _ => rest.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_command_sets_mode_and_is_stripped() {
+        let settings = InsertCommandSettings::default();
+
+        let d = detect_insert_command("Ship the release today, send it.", &settings);
+        assert_eq!(d.insert_mode, Some(InsertMode::PasteAndEnter));
+        assert_eq!(d.processed_transcript, "Ship the release today.");
+        assert_eq!(d.detected_phrase.as_deref(), Some("send it"));
+
+        let d = detect_insert_command("meeting notes for later Just copy", &settings);
+        assert_eq!(d.insert_mode, Some(InsertMode::ClipboardOnly));
+        assert_eq!(d.processed_transcript, "meeting notes for later");
+
+        // Only at the end, and only as whole words.
+        for text in ["Send it to Bob please.", "I will resend it."] {
+            let d = detect_insert_command(text, &settings);
+            assert_eq!(d.insert_mode, None);
+            assert_eq!(d.processed_transcript, text);
+        }
+
+        let off = InsertCommandSettings {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(detect_insert_command("ok send it", &off).insert_mode, None);
+        assert_eq!(
+            detect_insert_command("Send it!", &settings).insert_mode,
+            None
+        );
+    }
+}
@@ -0,0 +1,179 @@
+// Key sequences pressed after a successful paste (per Power Mode profile).
+//
+// Each chord is written like a shortcut: "enter", "tab", "ctrl+enter", "cmd+shift+k".
+// Chords serialize as those strings so config files stay readable; the platform inserters
+// turn them into native key events.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MacroKey {
+    Enter,
+    Tab,
+    Escape,
+    Space,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+    Insert,
+    /// An ASCII letter (lowercase) or digit.
+    Char(char),
+}
+
+impl MacroKey {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "enter" | "return" => MacroKey::Enter,
+            "tab" => MacroKey::Tab,
+            "esc" | "escape" => MacroKey::Escape,
+            "space" => MacroKey::Space,
+            "backspace" => MacroKey::Backspace,
+            "up" => MacroKey::Up,
+            "down" => MacroKey::Down,
+            "left" => MacroKey::Left,
+            "right" => MacroKey::Right,
+            "insert" | "ins" => MacroKey::Insert,
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_alphanumeric() => MacroKey::Char(c),
+                    _ => return None,
+                }
+            }
+        })
+    }
+
+    fn name(self) -> String {
+        match self {
+            MacroKey::Enter => "enter".into(),
+            MacroKey::Tab => "tab".into(),
+            MacroKey::Escape => "esc".into(),
+            MacroKey::Space => "space".into(),
+            MacroKey::Backspace => "backspace".into(),
+            MacroKey::Up => "up".into(),
+            MacroKey::Down => "down".into(),
+            MacroKey::Left => "left".into(),
+            MacroKey::Right => "right".into(),
+            MacroKey::Insert => "insert".into(),
+            MacroKey::Char(c) => c.to_string(),
+        }
+    }
+}
+
+/// One key press with the modifiers held while it is pressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyChord {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    /// Cmd on macOS, the Windows key elsewhere.
+    pub meta: bool,
+    pub key: MacroKey,
+}
+
+impl KeyChord {
+    pub fn key(key: MacroKey) -> Self {
+        Self {
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+            key,
+        }
+    }
+
+    /// The platform's undo shortcut: Cmd+Z on macOS, Ctrl+Z elsewhere.
+    pub fn undo() -> Self {
+        let mut chord = Self::key(MacroKey::Char('z'));
+        if cfg!(target_os = "macos") {
+            chord.meta = true;
+        } else {
+            chord.ctrl = true;
+        }
+        chord
+    }
+}
+
+impl std::str::FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let mut parts: Vec<&str> = lower.split('+').map(str::trim).collect();
+        let key = parts.pop().unwrap_or_default();
+        let mut chord = KeyChord::key(
+            MacroKey::parse(key).ok_or_else(|| format!("unknown key \"{key}\" in \"{s}\""))?,
+        );
+        for m in parts {
+            match m {
+                "ctrl" | "control" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" | "option" => chord.alt = true,
+                "cmd" | "meta" | "super" | "win" => chord.meta = true,
+                _ => return Err(format!("unknown modifier \"{m}\" in \"{s}\"")),
+            }
+        }
+        Ok(chord)
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(c: KeyChord) -> Self {
+        let mut out = String::new();
+        for (held, name) in [
+            (c.ctrl, "ctrl+"),
+            (c.shift, "shift+"),
+            (c.alt, "alt+"),
+            (c.meta, "cmd+"),
+        ] {
+            if held {
+                out.push_str(name);
+            }
+        }
+        out.push_str(&c.key.name());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_chords_case_insensitively() {
+        let c: KeyChord = " Ctrl + Enter ".parse().unwrap();
+        assert!(c.ctrl && !c.shift);
+        assert_eq!(c.key, MacroKey::Enter);
+
+        let c: KeyChord = "cmd+shift+K".parse().unwrap();
+        assert!(c.meta && c.shift);
+        assert_eq!(c.key, MacroKey::Char('k'));
+
+        let c: KeyChord = "Shift+Ins".parse().unwrap();
+        assert!(c.shift && !c.ctrl);
+        assert_eq!(c.key, MacroKey::Insert);
+
+        assert!("hyper+enter".parse::<KeyChord>().is_err());
+        assert!("ctrl+f13".parse::<KeyChord>().is_err());
+        assert!("".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn chords_roundtrip_as_strings() {
+        let c = KeyChord::try_from("Control+Return".to_string()).unwrap();
+        assert_eq!(String::from(c), "ctrl+enter");
+        assert_eq!(String::from(KeyChord::key(MacroKey::Tab)), "tab");
+        assert!(KeyChord::try_from("ctrl+".to_string()).is_err());
+    }
+}
@@ -1,16 +1,46 @@
 pub mod config;
 pub mod context;
+pub mod diarization;
+pub mod diff;
+pub mod digest;
 pub mod enhancement;
+pub mod error;
+pub mod guardrails;
+pub mod hotkeys;
+pub mod insert_command;
+pub mod key_macro;
+pub mod live_typing;
+pub mod meeting;
+pub mod note;
+pub mod onboarding;
 pub mod power_mode;
+pub mod redaction;
 pub mod stt;
 pub mod text;
 pub mod types;
+pub mod usage;
+pub mod wake_word;
 
 // Keep the public surface small and intentional.
 pub use config::*;
 pub use context::*;
+pub use diarization::*;
+pub use diff::*;
+pub use digest::*;
 pub use enhancement::*;
+pub use error::*;
+pub use guardrails::*;
+pub use hotkeys::*;
+pub use insert_command::*;
+pub use key_macro::*;
+pub use live_typing::*;
+pub use meeting::*;
+pub use note::*;
+pub use onboarding::*;
 pub use power_mode::*;
+pub use redaction::*;
 pub use stt::*;
 pub use text::*;
 pub use types::*;
+pub use usage::*;
+pub use wake_word::*;
@@ -0,0 +1,105 @@
+// Live typing (experimental): with realtime STT, each committed segment is typed into the
+// target app as soon as it arrives, instead of the whole text after stop.
+//
+// Text typed into another app cannot be taken back, so this is append-only: only text that
+// extends what is already on screen goes out, and once the provider rewrites typed words
+// nothing more is typed live. Whitespace is normalized because providers join segments with
+// varying spacing.
+
+/// What has been typed live during one session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveTyping {
+    typed: String,
+    diverged: bool,
+}
+
+impl LiveTyping {
+    /// The text to type for the latest committed transcript, or `None` when there is nothing
+    /// new (or the transcript no longer starts with what was typed).
+    pub fn advance(&mut self, committed: &str) -> Option<String> {
+        if self.diverged {
+            return None;
+        }
+        let Some(rest) = untyped_suffix(&self.typed, committed) else {
+            self.diverged = true;
+            return None;
+        };
+        if rest.is_empty() {
+            return None;
+        }
+        self.typed.push_str(&rest);
+        Some(rest)
+    }
+
+    /// Forget `text` again when typing it failed. Only undoes the latest [`Self::advance`].
+    pub fn rewind(&mut self, text: &str) {
+        if self.typed.ends_with(text) {
+            self.typed.truncate(self.typed.len() - text.len());
+        }
+    }
+
+    /// Everything typed so far, as one whitespace-normalized string.
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+}
+
+/// The part of `full` that still has to be typed after `typed`, including the space that
+/// separates it from the typed words. `None` when `full` does not start with the typed words.
+pub fn untyped_suffix(typed: &str, full: &str) -> Option<String> {
+    let typed = normalize(typed);
+    let full = normalize(full);
+    let rest = full.strip_prefix(&typed)?;
+    // "hello wor" is not a prefix of "hello world" word for word.
+    if !typed.is_empty() && !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some(rest.to_string())
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn types_only_what_each_commit_adds() {
+        let mut live = LiveTyping::default();
+        assert_eq!(live.advance("").as_deref(), None);
+        assert_eq!(
+            live.advance("Hello there.").as_deref(),
+            Some("Hello there.")
+        );
+        assert_eq!(live.advance("Hello there.").as_deref(), None);
+        assert_eq!(
+            live.advance("Hello there.  How are you?").as_deref(),
+            Some(" How are you?")
+        );
+        assert_eq!(live.typed(), "Hello there. How are you?");
+
+        live.rewind(" How are you?");
+        assert_eq!(live.typed(), "Hello there.");
+    }
+
+    #[test]
+    fn stops_once_typed_words_are_rewritten() {
+        let mut live = LiveTyping::default();
+        live.advance("Hello there.");
+        assert_eq!(live.advance("Hello, there. How are you?"), None);
+        // Even if a later commit happens to match again.
+        assert_eq!(live.advance("Hello there. Fine."), None);
+        assert_eq!(live.typed(), "Hello there.");
+    }
+
+    #[test]
+    fn suffix_respects_word_boundaries() {
+        assert_eq!(untyped_suffix("", " Hi ").as_deref(), Some("Hi"));
+        assert_eq!(untyped_suffix("Hi", "Hi").as_deref(), Some(""));
+        assert_eq!(untyped_suffix("Hi", "Hi  you").as_deref(), Some(" you"));
+        assert_eq!(untyped_suffix("Hi", "Hippo"), None);
+        assert_eq!(untyped_suffix("Hi you", "Hi"), None);
+    }
+}
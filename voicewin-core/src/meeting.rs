@@ -0,0 +1,186 @@
+// Meeting mode: one long recording that is cut into chunks at pauses in speech. Each chunk
+// is transcribed on its own and appended with its offset into the meeting ("[00:12:41] ..."),
+// so nothing reaches the foreground app and the session never has to stop on its own.
+//
+// Pause detection is a plain RMS threshold over the mic level callback; it does not need to
+// tell speech from noise, only find a quiet gap to cut at instead of mid-word.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeetingSettings {
+    /// Append chunks to this Markdown file (`{date}` expands to the meeting's start date)
+    /// instead of the scratchpad.
+    #[serde(default)]
+    pub note_path: Option<String>,
+}
+
+impl MeetingSettings {
+    /// The note file to append to, if one is configured.
+    pub fn active_note_path(&self) -> Option<&str> {
+        self.note_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+    }
+}
+
+/// Below this RMS level the microphone counts as silent.
+pub const MEETING_SILENCE_RMS: f32 = 0.01;
+
+/// Decides where to cut a running meeting recording into chunks.
+#[derive(Debug, Clone)]
+pub struct ChunkSegmenter {
+    /// Don't cut at pauses before a chunk is this long; short chunks transcribe poorly.
+    pub min_chunk_ms: u64,
+    /// Cut here even without a pause, so a chunk never grows unbounded.
+    pub max_chunk_ms: u64,
+    /// A pause must last this long to end a chunk.
+    pub min_silence_ms: u64,
+
+    chunk_ms: u64,
+    silence_ms: u64,
+    heard_speech: bool,
+}
+
+/// A chunk boundary reported by [`ChunkSegmenter::observe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkCut {
+    /// Length of the chunk that just ended.
+    pub duration_ms: u64,
+    /// False when the chunk was silent throughout and need not be transcribed.
+    pub had_speech: bool,
+}
+
+impl Default for ChunkSegmenter {
+    fn default() -> Self {
+        Self {
+            min_chunk_ms: 15_000,
+            max_chunk_ms: 60_000,
+            min_silence_ms: 800,
+            chunk_ms: 0,
+            silence_ms: 0,
+            heard_speech: false,
+        }
+    }
+}
+
+impl ChunkSegmenter {
+    /// Account for `dt_ms` of audio at level `rms`; returns the cut when the current chunk
+    /// should end now. The next chunk starts right after it.
+    pub fn observe(&mut self, rms: f32, dt_ms: u64) -> Option<ChunkCut> {
+        self.chunk_ms += dt_ms;
+        if rms < MEETING_SILENCE_RMS {
+            self.silence_ms += dt_ms;
+        } else {
+            self.silence_ms = 0;
+            self.heard_speech = true;
+        }
+
+        let paused = self.heard_speech
+            && self.chunk_ms >= self.min_chunk_ms
+            && self.silence_ms >= self.min_silence_ms;
+        if !paused && self.chunk_ms < self.max_chunk_ms {
+            return None;
+        }
+
+        let cut = ChunkCut {
+            duration_ms: self.chunk_ms,
+            had_speech: self.heard_speech,
+        };
+        self.chunk_ms = 0;
+        self.silence_ms = 0;
+        self.heard_speech = false;
+        Some(cut)
+    }
+}
+
+/// One transcript line: the chunk's offset into the meeting, then its text.
+pub fn format_meeting_line(offset_ms: u64, text: &str) -> String {
+    let secs = offset_ms / 1000;
+    format!(
+        "[{:02}:{:02}:{:02}] {}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        text.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(seg: &mut ChunkSegmenter, rms: f32, ms: u64) -> Option<ChunkCut> {
+        let mut cut = None;
+        for _ in 0..ms / 100 {
+            if let Some(c) = seg.observe(rms, 100) {
+                assert!(cut.is_none(), "cut twice");
+                cut = Some(c);
+            }
+        }
+        cut
+    }
+
+    #[test]
+    fn cuts_at_the_first_pause_after_the_minimum_length() {
+        let mut seg = ChunkSegmenter::default();
+
+        // A pause before the minimum length does not end the chunk.
+        assert_eq!(feed(&mut seg, 0.2, 5_000), None);
+        assert_eq!(feed(&mut seg, 0.0, 1_000), None);
+        assert_eq!(feed(&mut seg, 0.2, 10_000), None);
+
+        // A breath is too short to cut at.
+        assert_eq!(feed(&mut seg, 0.0, 500), None);
+        assert_eq!(feed(&mut seg, 0.2, 1_000), None);
+
+        assert_eq!(
+            feed(&mut seg, 0.0, 800),
+            Some(ChunkCut {
+                duration_ms: 18_300,
+                had_speech: true
+            })
+        );
+    }
+
+    #[test]
+    fn forces_a_cut_at_the_maximum_length() {
+        let mut seg = ChunkSegmenter::default();
+        assert_eq!(
+            feed(&mut seg, 0.2, 60_000),
+            Some(ChunkCut {
+                duration_ms: 60_000,
+                had_speech: true
+            })
+        );
+
+        // Silence alone never reaches a pause cut, but is dropped at the maximum.
+        assert_eq!(
+            feed(&mut seg, 0.0, 60_000),
+            Some(ChunkCut {
+                duration_ms: 60_000,
+                had_speech: false
+            })
+        );
+    }
+
+    #[test]
+    fn formats_offsets_as_hours_minutes_seconds() {
+        assert_eq!(format_meeting_line(0, " Hello. \n"), "[00:00:00] Hello.");
+        assert_eq!(
+            format_meeting_line(3_723_999, "Next item."),
+            "[01:02:03] Next item."
+        );
+    }
+
+    #[test]
+    fn blank_note_path_means_scratchpad() {
+        let mut s = MeetingSettings::default();
+        assert_eq!(s.active_note_path(), None);
+        s.note_path = Some("  ".into());
+        assert_eq!(s.active_note_path(), None);
+        s.note_path = Some(" meetings/{date}.md ".into());
+        assert_eq!(s.active_note_path(), Some("meetings/{date}.md"));
+    }
+}
@@ -0,0 +1,68 @@
+// Appending dictations to a Markdown note (voice journaling) instead of inserting them.
+//
+// The note path may contain `{date}`, which expands to the session's date so each day gets
+// its own file ("~/Notes/Journal/{date}.md"). Dates and times are UTC; there is no time-zone
+// database in the core crate.
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// `YYYY-MM-DD` and `HH:MM` for a Unix timestamp in milliseconds (UTC).
+pub fn utc_date_time(unix_ms: i64) -> (String, String) {
+    let days = unix_ms.div_euclid(DAY_MS);
+    let minutes = unix_ms.rem_euclid(DAY_MS) / 60_000;
+
+    // Howard Hinnant's days-to-civil conversion.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (
+        format!("{year:04}-{month:02}-{day:02}"),
+        format!("{:02}:{:02}", minutes / 60, minutes % 60),
+    )
+}
+
+/// The note file for a session, with `{date}` replaced by the session's date.
+pub fn expand_note_path(template: &str, unix_ms: i64) -> String {
+    let (date, _) = utc_date_time(unix_ms);
+    template.trim().replace("{date}", &date)
+}
+
+/// One appended note entry: a timestamp heading followed by the dictated text.
+pub fn format_note_entry(text: &str, unix_ms: i64) -> String {
+    let (date, time) = utc_date_time(unix_ms);
+    format!("\n### {date} {time} UTC\n\n{}\n", text.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-10-15T18:24:09Z
+    const TS: i64 = 1_792_088_649_000;
+
+    #[test]
+    fn expands_date_placeholder() {
+        assert_eq!(
+            expand_note_path(" notes/{date}.md ", TS),
+            "notes/2026-10-15.md"
+        );
+        assert_eq!(expand_note_path("inbox.md", TS), "inbox.md");
+        assert_eq!(expand_note_path("{date}", 0), "1970-01-01");
+        assert_eq!(expand_note_path("{date}", 951_782_400_000), "2000-02-29");
+    }
+
+    #[test]
+    fn formats_timestamped_entry() {
+        assert_eq!(
+            format_note_entry(" Call the dentist. \n", TS),
+            "\n### 2026-10-15 18:24 UTC\n\nCall the dentist.\n"
+        );
+    }
+}
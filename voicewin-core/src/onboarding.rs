@@ -0,0 +1,219 @@
+// First-run checklist: everything that must be in place before dictation works.
+//
+// The checks themselves live where the facts are (platform permissions, installed models,
+// the registered hotkey, the LLM endpoint); this module only defines the shape the UI
+// renders and how a probe result maps to a status and a suggested fix.
+
+use serde::{Deserialize, Serialize};
+
+/// An OS privacy permission as reported by the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionState {
+    Granted,
+    Denied,
+    /// The user has not been asked yet; requesting it will show a system prompt.
+    NotDetermined,
+    /// This platform does not gate the capability behind a permission.
+    NotRequired,
+    /// The platform gates it but the state could not be read.
+    Unknown,
+}
+
+/// A system settings page the app can open for the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyPane {
+    Microphone,
+    Accessibility,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    Microphone,
+    Accessibility,
+    Model,
+    Hotkey,
+    Llm,
+}
+
+impl OnboardingStep {
+    /// Dictation works without an LLM (enhancement is optional); everything else is required.
+    pub fn is_required(self) -> bool {
+        !matches!(self, OnboardingStep::Llm)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Not done yet; `action` says what to do.
+    Pending,
+    Failed,
+    /// Does not apply (wrong platform, or the feature is turned off).
+    Skipped,
+}
+
+/// What the UI should offer to fix a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingAction {
+    RequestMicPermission,
+    OpenMicrophoneSettings,
+    OpenAccessibilitySettings,
+    DownloadModel,
+    ChangeHotkey,
+    ConfigureLlm,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingCheck {
+    pub step: OnboardingStep,
+    pub status: CheckStatus,
+    #[serde(default)]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub action: Option<OnboardingAction>,
+}
+
+impl OnboardingAction {
+    pub fn open_settings(pane: PrivacyPane) -> Self {
+        match pane {
+            PrivacyPane::Microphone => OnboardingAction::OpenMicrophoneSettings,
+            PrivacyPane::Accessibility => OnboardingAction::OpenAccessibilitySettings,
+        }
+    }
+}
+
+impl OnboardingCheck {
+    pub fn ok(step: OnboardingStep) -> Self {
+        Self {
+            step,
+            status: CheckStatus::Ok,
+            detail: None,
+            action: None,
+        }
+    }
+
+    pub fn skipped(step: OnboardingStep, detail: impl Into<String>) -> Self {
+        Self {
+            step,
+            status: CheckStatus::Skipped,
+            detail: Some(detail.into()),
+            action: None,
+        }
+    }
+
+    pub fn pending(
+        step: OnboardingStep,
+        detail: impl Into<String>,
+        action: OnboardingAction,
+    ) -> Self {
+        Self {
+            step,
+            status: CheckStatus::Pending,
+            detail: Some(detail.into()),
+            action: Some(action),
+        }
+    }
+
+    pub fn failed(
+        step: OnboardingStep,
+        detail: impl Into<String>,
+        action: Option<OnboardingAction>,
+    ) -> Self {
+        Self {
+            step,
+            status: CheckStatus::Failed,
+            detail: Some(detail.into()),
+            action,
+        }
+    }
+
+    /// Map a permission probe to a check, suggesting a prompt only while one can still be shown.
+    pub fn permission(pane: PrivacyPane, state: PermissionState) -> Self {
+        let (step, label) = match pane {
+            PrivacyPane::Microphone => (OnboardingStep::Microphone, "Microphone"),
+            PrivacyPane::Accessibility => (OnboardingStep::Accessibility, "Accessibility"),
+        };
+        match state {
+            PermissionState::Granted => Self::ok(step),
+            PermissionState::NotRequired => Self::skipped(
+                step,
+                format!("{label} access is not restricted on this platform."),
+            ),
+            PermissionState::NotDetermined => {
+                let action = match pane {
+                    PrivacyPane::Microphone => OnboardingAction::RequestMicPermission,
+                    PrivacyPane::Accessibility => OnboardingAction::open_settings(pane),
+                };
+                Self::pending(
+                    step,
+                    format!("{label} access has not been granted yet."),
+                    action,
+                )
+            }
+            PermissionState::Denied => Self::failed(
+                step,
+                format!("{label} access is turned off for VoiceWin in system settings."),
+                Some(OnboardingAction::open_settings(pane)),
+            ),
+            PermissionState::Unknown => Self::pending(
+                step,
+                format!("Could not read the {label} permission; check it in system settings."),
+                OnboardingAction::open_settings(pane),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingStatus {
+    pub checks: Vec<OnboardingCheck>,
+    /// Every required check is OK or skipped.
+    pub ready: bool,
+}
+
+impl OnboardingStatus {
+    pub fn new(checks: Vec<OnboardingCheck>) -> Self {
+        let ready = checks.iter().all(|c| {
+            !c.step.is_required() || matches!(c.status, CheckStatus::Ok | CheckStatus::Skipped)
+        });
+        Self { checks, ready }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permission_states_map_to_checks_and_readiness() {
+        let mic =
+            OnboardingCheck::permission(PrivacyPane::Microphone, PermissionState::NotDetermined);
+        assert_eq!(mic.status, CheckStatus::Pending);
+        assert_eq!(mic.action, Some(OnboardingAction::RequestMicPermission));
+
+        let ax = OnboardingCheck::permission(PrivacyPane::Accessibility, PermissionState::Denied);
+        assert_eq!(ax.status, CheckStatus::Failed);
+        assert_eq!(ax.action, Some(OnboardingAction::OpenAccessibilitySettings));
+
+        let skipped =
+            OnboardingCheck::permission(PrivacyPane::Accessibility, PermissionState::NotRequired);
+        assert_eq!(skipped.status, CheckStatus::Skipped);
+
+        // An unreachable LLM does not block dictation.
+        let status = OnboardingStatus::new(vec![
+            OnboardingCheck::permission(PrivacyPane::Microphone, PermissionState::Granted),
+            skipped,
+            OnboardingCheck::ok(OnboardingStep::Model),
+            OnboardingCheck::ok(OnboardingStep::Hotkey),
+            OnboardingCheck::failed(OnboardingStep::Llm, "timed out", None),
+        ]);
+        assert!(status.ready);
+
+        assert!(!OnboardingStatus::new(vec![mic]).ready);
+    }
+}
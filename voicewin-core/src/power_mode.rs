@@ -1,4 +1,10 @@
-use crate::types::{AppIdentity, InsertMode, ProfileId};
+use crate::key_macro::KeyChord;
+use crate::stt::RealtimeVadSettings;
+use crate::text::TextPipeline;
+use crate::types::{
+    AppIdentity, DisfluencyFilter, InsertMode, InsertTarget, InsertionStrategy, OutputFormat,
+    ProfileId,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,16 +44,66 @@ pub struct PowerModeOverrides {
     pub llm_base_url: Option<String>,
     pub llm_model: Option<String>,
 
+    #[serde(default)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Route this profile's dictation to a specific app instead of the focused window.
+    #[serde(default)]
+    pub insert_target: Option<InsertTarget>,
+
+    /// Pin apps where pasting misbehaves (terminals, RDP, Citrix) to a strategy that works.
+    #[serde(default)]
+    pub insertion_strategy: Option<InsertionStrategy>,
+
+    /// The app's paste shortcut when it isn't the platform's, e.g. Ctrl+Shift+V in a terminal.
+    #[serde(default)]
+    pub paste_keys: Option<KeyChord>,
+
+    /// Keys pressed after a successful paste, e.g. `["tab", "enter"]` or `["ctrl+enter"]`.
+    #[serde(default)]
+    pub post_insert_keys: Option<Vec<KeyChord>>,
+
+    /// Text stages this profile runs, e.g. no LLM or no replacements for a terminal.
+    #[serde(default)]
+    pub text_pipeline: Option<TextPipeline>,
+
+    /// Realtime STT pause detection for this profile's environment.
+    #[serde(default)]
+    pub realtime_vad: Option<RealtimeVadSettings>,
+
     // Context toggles (best-effort on Windows)
     pub context: Option<crate::context::ContextToggles>,
 }
 
+/// What happens when focus moves into a profile's app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoDictateMode {
+    #[default]
+    Off,
+    /// Tell the user dictation is ready; they still start it.
+    Prompt,
+    /// Start recording.
+    Record,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AutoDictate {
+    pub mode: AutoDictateMode,
+    /// Wait this long after the app gains focus, so passing through it does nothing.
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PowerModeProfile {
     pub id: ProfileId,
     pub name: String,
     pub enabled: bool,
     pub matchers: Vec<AppMatcher>,
+    /// For kiosk-like data entry: prompt or record as soon as the app is in front.
+    #[serde(default)]
+    pub auto_dictate: AutoDictate,
     pub overrides: PowerModeOverrides,
 }
 
@@ -82,6 +138,25 @@ pub struct GlobalDefaults {
     #[serde(default = "default_history_enabled")]
     pub history_enabled: bool,
 
+    /// Tidy casing, punctuation spacing, and numbers when the text is not enhanced by an LLM.
+    #[serde(default)]
+    pub basic_cleanup: bool,
+
+    #[serde(default)]
+    pub output_format: OutputFormat,
+
+    /// Filler words and stutters stripped before insertion or enhancement.
+    #[serde(default)]
+    pub disfluency_filter: DisfluencyFilter,
+
+    /// Post-STT text stages, in order.
+    #[serde(default)]
+    pub text_pipeline: TextPipeline,
+
+    /// Server-side pause detection for realtime STT.
+    #[serde(default)]
+    pub realtime_vad: RealtimeVadSettings,
+
     pub context: crate::context::ContextToggles,
 }
 
@@ -102,6 +177,34 @@ pub struct EffectiveConfig {
     pub llm_base_url: String,
     pub llm_model: String,
 
+    #[serde(default)]
+    pub basic_cleanup: bool,
+
+    #[serde(default)]
+    pub output_format: OutputFormat,
+
+    #[serde(default)]
+    pub disfluency_filter: DisfluencyFilter,
+
+    #[serde(default)]
+    pub insert_target: InsertTarget,
+
+    #[serde(default)]
+    pub insertion_strategy: InsertionStrategy,
+
+    /// Replaces the platform's paste shortcut when set.
+    #[serde(default)]
+    pub paste_keys: Option<KeyChord>,
+
+    #[serde(default)]
+    pub post_insert_keys: Vec<KeyChord>,
+
+    #[serde(default)]
+    pub text_pipeline: TextPipeline,
+
+    #[serde(default)]
+    pub realtime_vad: RealtimeVadSettings,
+
     pub context: crate::context::ContextToggles,
 
     // The active profile resolved for the current foreground app.
@@ -117,6 +220,21 @@ pub struct EphemeralOverrides {
     pub forced_profile_id: Option<ProfileId>,
     pub forced_prompt_id: Option<crate::types::PromptId>,
     pub forced_enable_enhancement: Option<bool>,
+
+    /// Set by a spoken insert command ("send it") for this session only.
+    #[serde(default)]
+    pub insert_mode: Option<InsertMode>,
+
+    /// Dictation language for this session only (`"auto"` to detect).
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// STT provider and model for this session only, e.g. a fallback picked because the
+    /// configured provider was unavailable. Applied only when both are set.
+    #[serde(default)]
+    pub stt_provider: Option<String>,
+    #[serde(default)]
+    pub stt_model: Option<String>,
 }
 
 pub fn resolve_effective_config(
@@ -144,6 +262,15 @@ pub fn resolve_effective_config(
         language: defaults.language.clone(),
         llm_base_url: defaults.llm_base_url.clone(),
         llm_model: defaults.llm_model.clone(),
+        basic_cleanup: defaults.basic_cleanup,
+        output_format: defaults.output_format,
+        disfluency_filter: defaults.disfluency_filter,
+        insert_target: InsertTarget::Foreground,
+        insertion_strategy: InsertionStrategy::ClipboardPaste,
+        paste_keys: None,
+        post_insert_keys: vec![],
+        text_pipeline: defaults.text_pipeline.clone(),
+        realtime_vad: defaults.realtime_vad.clone(),
         context: defaults.context.clone(),
         matched_profile_id: matched_profile.map(|p| p.id.clone()),
         matched_profile_name: matched_profile.map(|p| p.name.clone()),
@@ -162,6 +289,16 @@ pub fn resolve_effective_config(
         cfg.prompt_id = Some(prompt_id.clone());
         cfg.enable_enhancement = true; // selecting a prompt implies enhancement.
     }
+    if let Some(mode) = ephemeral.insert_mode {
+        cfg.insert_mode = mode;
+    }
+    if let Some(language) = &ephemeral.language {
+        cfg.language = language.clone();
+    }
+    if let (Some(provider), Some(model)) = (&ephemeral.stt_provider, &ephemeral.stt_model) {
+        cfg.stt_provider = provider.clone();
+        cfg.stt_model = model.clone();
+    }
 
     cfg
 }
@@ -191,11 +328,145 @@ fn apply_overrides(cfg: &mut EffectiveConfig, overrides: &PowerModeOverrides) {
     if let Some(v) = &overrides.llm_model {
         cfg.llm_model = v.clone();
     }
+    if let Some(v) = overrides.output_format {
+        cfg.output_format = v;
+    }
+    if let Some(v) = &overrides.insert_target {
+        cfg.insert_target = v.clone();
+    }
+    if let Some(v) = overrides.insertion_strategy {
+        cfg.insertion_strategy = v;
+    }
+    if let Some(v) = overrides.paste_keys {
+        cfg.paste_keys = Some(v);
+    }
+    if let Some(v) = &overrides.post_insert_keys {
+        cfg.post_insert_keys = v.clone();
+    }
+    if let Some(v) = &overrides.text_pipeline {
+        cfg.text_pipeline = v.clone();
+    }
+    if let Some(v) = &overrides.realtime_vad {
+        cfg.realtime_vad = v.clone();
+    }
     if let Some(v) = &overrides.context {
         cfg.context = v.clone();
     }
 }
 
+/// Follows which profile the foreground app matches, to fire auto-dictate when focus moves
+/// into a profile (a title change within the same profile doesn't count).
+#[derive(Debug, Clone, Default)]
+pub struct AutoDictateTracker {
+    current: Option<ProfileId>,
+}
+
+impl AutoDictateTracker {
+    pub const fn new() -> Self {
+        Self { current: None }
+    }
+
+    /// Record that `app` is now in front. Returns the matched profile when focus just
+    /// entered it and it has auto-dictate on.
+    pub fn focus_changed<'a>(
+        &mut self,
+        profiles: &'a [PowerModeProfile],
+        app: &AppIdentity,
+    ) -> Option<&'a PowerModeProfile> {
+        let matched = profiles.iter().find(|p| p.matches(app));
+        let id = matched.map(|p| p.id.clone());
+        if id == self.current {
+            return None;
+        }
+        self.current = id;
+        matched.filter(|p| p.auto_dictate.mode != AutoDictateMode::Off)
+    }
+}
+
+/// A ready-to-save profile for an app the user often dictates into.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileSuggestion {
+    pub profile: PowerModeProfile,
+    pub session_count: usize,
+}
+
+/// Apps need at least this many sessions before a profile is suggested for them.
+pub const MIN_SESSIONS_FOR_SUGGESTION: usize = 3;
+
+/// Propose profiles for the most-used apps in `sessions` that no existing profile matches
+/// (disabled profiles count, so a profile the user switched off is not suggested again).
+/// Suggestions are ordered by session count, most used first.
+pub fn suggest_profiles(
+    sessions: &[AppIdentity],
+    profiles: &[PowerModeProfile],
+    limit: usize,
+) -> Vec<ProfileSuggestion> {
+    // (normalized name, name as first seen, count), in first-seen order for stable ties.
+    let mut counts: Vec<(String, String, usize)> = vec![];
+    for app in sessions {
+        let Some(name) = app.process_name.as_ref().map(|p| p.0.trim()) else {
+            continue;
+        };
+        if name.is_empty()
+            || profiles
+                .iter()
+                .any(|p| p.matchers.iter().any(|m| m.matches(app)))
+        {
+            continue;
+        }
+        let key = normalize(name);
+        match counts.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, _, n)) => *n += 1,
+            None => counts.push((key, name.to_string(), 1)),
+        }
+    }
+
+    counts.retain(|(_, _, n)| *n >= MIN_SESSIONS_FOR_SUGGESTION);
+    counts.sort_by_key(|(_, _, n)| std::cmp::Reverse(*n));
+    counts
+        .into_iter()
+        .take(limit)
+        .map(|(_, process_name, session_count)| ProfileSuggestion {
+            profile: PowerModeProfile {
+                id: ProfileId::new(),
+                name: display_app_name(&process_name),
+                enabled: true,
+                matchers: vec![AppMatcher::ProcessNameEquals(process_name)],
+                auto_dictate: Default::default(),
+                overrides: PowerModeOverrides::default(),
+            },
+            session_count,
+        })
+        .collect()
+}
+
+/// The profile to force after `current` when switching by hotkey: enabled profiles in
+/// order, then `None` (back to matching by app) after the last.
+pub fn next_forced_profile<'a>(
+    profiles: &'a [PowerModeProfile],
+    current: Option<&ProfileId>,
+) -> Option<&'a PowerModeProfile> {
+    let mut enabled = profiles.iter().filter(|p| p.enabled);
+    match current {
+        None => enabled.next(),
+        Some(id) => enabled.skip_while(|p| &p.id != id).nth(1),
+    }
+}
+
+/// "slack.exe" -> "Slack".
+fn display_app_name(process_name: &str) -> String {
+    let stem = if process_name.to_ascii_lowercase().ends_with(".exe") {
+        &process_name[..process_name.len() - 4]
+    } else {
+        process_name
+    };
+    let mut chars = stem.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => process_name.to_string(),
+    }
+}
+
 fn normalize(s: &str) -> String {
     s.trim().to_lowercase()
 }
@@ -219,6 +490,41 @@ mod tests {
         assert!(m.matches(&app));
     }
 
+    #[test]
+    fn suggestions_cover_frequent_apps_without_a_profile() {
+        let app = |name: &str| AppIdentity::new().with_process_name(name);
+        let mut sessions = vec![app("slack.exe"); 2];
+        sessions.extend(vec![app("Code.exe"); 5]);
+        sessions.extend(vec![app("SLACK.EXE"); 2]);
+        sessions.extend(vec![app("notepad.exe"); 2]);
+        sessions.extend(vec![app("outlook.exe"); 4]);
+        sessions.push(AppIdentity::new());
+
+        // Disabled profiles still count as "has a profile".
+        let existing = PowerModeProfile {
+            id: ProfileId::new(),
+            name: "Mail".into(),
+            enabled: false,
+            matchers: vec![AppMatcher::ProcessNameEquals("OUTLOOK.exe".into())],
+            auto_dictate: Default::default(),
+            overrides: PowerModeOverrides::default(),
+        };
+
+        let suggestions = suggest_profiles(&sessions, &[existing], 5);
+        let summary: Vec<_> = suggestions
+            .iter()
+            .map(|s| (s.profile.name.as_str(), s.session_count))
+            .collect();
+        assert_eq!(summary, [("Code", 5), ("Slack", 4)]);
+        assert_eq!(
+            suggestions[1].profile.matchers,
+            [AppMatcher::ProcessNameEquals("slack.exe".into())]
+        );
+        assert!(suggestions[0].profile.enabled);
+
+        assert_eq!(suggest_profiles(&sessions, &[], 1).len(), 1);
+    }
+
     #[test]
     fn resolve_uses_forced_profile_when_present() {
         let defaults = GlobalDefaults {
@@ -232,6 +538,11 @@ mod tests {
             llm_model: "gpt-4o-mini".into(),
             microphone_device: None,
             history_enabled: true,
+            basic_cleanup: false,
+            output_format: Default::default(),
+            disfluency_filter: Default::default(),
+            text_pipeline: Default::default(),
+            realtime_vad: Default::default(),
             context: crate::context::ContextToggles::default(),
         };
 
@@ -240,6 +551,7 @@ mod tests {
             name: "Slack".into(),
             enabled: true,
             matchers: vec![AppMatcher::ProcessNameEquals("slack.exe".into())],
+            auto_dictate: Default::default(),
             overrides: PowerModeOverrides {
                 enable_enhancement: Some(true),
                 ..Default::default()
@@ -252,6 +564,7 @@ mod tests {
             name: "VS Code".into(),
             enabled: true,
             matchers: vec![AppMatcher::ProcessNameEquals("code.exe".into())],
+            auto_dictate: Default::default(),
             overrides: PowerModeOverrides {
                 enable_enhancement: Some(false),
                 ..Default::default()
@@ -272,4 +585,124 @@ mod tests {
 
         assert_eq!(cfg.enable_enhancement, false);
     }
+
+    #[test]
+    fn session_overrides_win_over_the_matched_profile() {
+        let defaults = GlobalDefaults {
+            enable_enhancement: false,
+            prompt_id: None,
+            insert_mode: crate::types::InsertMode::Paste,
+            stt_provider: "local".into(),
+            stt_model: "whisper".into(),
+            language: "en".into(),
+            llm_base_url: "http://localhost".into(),
+            llm_model: "gpt-4o-mini".into(),
+            microphone_device: None,
+            history_enabled: true,
+            basic_cleanup: false,
+            output_format: Default::default(),
+            disfluency_filter: Default::default(),
+            text_pipeline: Default::default(),
+            realtime_vad: Default::default(),
+            context: crate::context::ContextToggles::default(),
+        };
+        let profile = PowerModeProfile {
+            id: ProfileId::new(),
+            name: "Slack".into(),
+            enabled: true,
+            matchers: vec![AppMatcher::ProcessNameEquals("slack.exe".into())],
+            auto_dictate: Default::default(),
+            overrides: PowerModeOverrides {
+                language: Some("fr".into()),
+                enable_enhancement: Some(false),
+                ..Default::default()
+            },
+        };
+        let app = AppIdentity::new().with_process_name("slack.exe");
+        let prompt_id = crate::types::PromptId::new();
+
+        let cfg = resolve_effective_config(
+            &defaults,
+            std::slice::from_ref(&profile),
+            &app,
+            &EphemeralOverrides {
+                forced_prompt_id: Some(prompt_id.clone()),
+                language: Some("de".into()),
+                stt_provider: Some("elevenlabs".into()),
+                stt_model: Some("scribe_v2".into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(cfg.language, "de");
+        assert_eq!(cfg.stt_provider, "elevenlabs");
+        assert_eq!(cfg.stt_model, "scribe_v2");
+        assert_eq!(cfg.prompt_id, Some(prompt_id));
+        assert!(cfg.enable_enhancement);
+        assert_eq!(cfg.matched_profile_name.as_deref(), Some("Slack"));
+    }
+
+    #[test]
+    fn forced_profile_cycles_through_enabled_profiles_then_automatic() {
+        let profile = |name: &str, enabled: bool| PowerModeProfile {
+            id: ProfileId::new(),
+            name: name.into(),
+            enabled,
+            matchers: vec![],
+            auto_dictate: Default::default(),
+            overrides: PowerModeOverrides::default(),
+        };
+        let profiles = [
+            profile("Slack", true),
+            profile("Off", false),
+            profile("Code", true),
+        ];
+
+        let first = next_forced_profile(&profiles, None).unwrap();
+        assert_eq!(first.name, "Slack");
+        let second = next_forced_profile(&profiles, Some(&first.id)).unwrap();
+        assert_eq!(second.name, "Code");
+        assert!(next_forced_profile(&profiles, Some(&second.id)).is_none());
+
+        // A forced profile deleted or disabled meanwhile falls back to matching by app.
+        assert!(next_forced_profile(&profiles, Some(&profiles[1].id)).is_none());
+        assert!(next_forced_profile(&[], None).is_none());
+    }
+
+    #[test]
+    fn auto_dictate_fires_once_per_focus_entry() {
+        let profile = |process: &str, mode| PowerModeProfile {
+            id: ProfileId::new(),
+            name: process.into(),
+            enabled: true,
+            matchers: vec![AppMatcher::ProcessNameEquals(process.into())],
+            auto_dictate: AutoDictate { mode, delay_ms: 0 },
+            overrides: PowerModeOverrides::default(),
+        };
+        let profiles = [
+            profile("emr.exe", AutoDictateMode::Record),
+            profile("Slack.exe", AutoDictateMode::Off),
+        ];
+        let emr = AppIdentity::new().with_process_name("EMR.exe");
+        let slack = AppIdentity::new().with_process_name("Slack.exe");
+        let mut tracker = AutoDictateTracker::default();
+
+        assert_eq!(
+            tracker
+                .focus_changed(&profiles, &emr)
+                .map(|p| p.name.as_str()),
+            Some("emr.exe")
+        );
+        // Another window title in the same app is not a new entry.
+        let retitled = emr.clone().with_window_title("Patient 2");
+        assert!(tracker.focus_changed(&profiles, &retitled).is_none());
+
+        assert!(tracker.focus_changed(&profiles, &slack).is_none());
+        assert!(tracker.focus_changed(&profiles, &emr).is_some());
+        assert!(
+            tracker
+                .focus_changed(&profiles, &AppIdentity::new())
+                .is_none()
+        );
+        assert!(tracker.focus_changed(&profiles, &emr).is_some());
+    }
 }
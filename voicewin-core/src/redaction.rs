@@ -0,0 +1,172 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Built-in patterns for common sensitive data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionPreset {
+    CreditCard,
+    Ssn,
+    Email,
+}
+
+impl RedactionPreset {
+    pub const ALL: [RedactionPreset; 3] = [
+        RedactionPreset::CreditCard,
+        RedactionPreset::Ssn,
+        RedactionPreset::Email,
+    ];
+
+    fn pattern(self) -> &'static str {
+        match self {
+            // 13-19 digits, optionally grouped with spaces or dashes.
+            RedactionPreset::CreditCard => r"\b(?:\d[ -]?){12,18}\d\b",
+            RedactionPreset::Ssn => r"\b\d{3}-\d{2}-\d{4}\b",
+            RedactionPreset::Email => r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b",
+        }
+    }
+
+    fn placeholder(self) -> &'static str {
+        match self {
+            RedactionPreset::CreditCard => "[REDACTED CARD]",
+            RedactionPreset::Ssn => "[REDACTED SSN]",
+            RedactionPreset::Email => "[REDACTED EMAIL]",
+        }
+    }
+}
+
+/// Scrubbing applied to dictated text before it is persisted to History or logged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionSettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_presets")]
+    pub presets: Vec<RedactionPreset>,
+
+    /// Extra user-supplied regular expressions (Rust `regex` syntax).
+    #[serde(default)]
+    pub custom_patterns: Vec<String>,
+
+    /// Store only a hash of the text in History instead of the (redacted) text itself.
+    #[serde(default)]
+    pub hash_only: bool,
+}
+
+fn default_presets() -> Vec<RedactionPreset> {
+    RedactionPreset::ALL.to_vec()
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            presets: default_presets(),
+            custom_patterns: Vec::new(),
+            hash_only: false,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid redaction pattern `{pattern}`: {source}")]
+pub struct RedactionError {
+    pub pattern: String,
+    #[source]
+    pub source: regex::Error,
+}
+
+const CUSTOM_PLACEHOLDER: &str = "[REDACTED]";
+
+/// Compiled form of [`RedactionSettings`].
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    rules: Vec<(Regex, &'static str)>,
+}
+
+impl Redactor {
+    /// A redactor for `settings`; empty (a no-op) when redaction is disabled.
+    pub fn from_settings(settings: &RedactionSettings) -> Result<Self, RedactionError> {
+        if !settings.enabled {
+            return Ok(Self::default());
+        }
+
+        let mut rules = Vec::new();
+        for preset in &settings.presets {
+            let re = Regex::new(preset.pattern()).expect("valid redaction preset regex");
+            rules.push((re, preset.placeholder()));
+        }
+        for pattern in settings
+            .custom_patterns
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+        {
+            let re = Regex::new(pattern).map_err(|source| RedactionError {
+                pattern: pattern.clone(),
+                source,
+            })?;
+            rules.push((re, CUSTOM_PLACEHOLDER));
+        }
+
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for (re, placeholder) in &self.rules {
+            out = re.replace_all(&out, *placeholder).into_owned();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> RedactionSettings {
+        RedactionSettings {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn presets_redact_cards_ssns_and_emails() {
+        let r = Redactor::from_settings(&enabled()).unwrap();
+        let out = r.redact(
+            "card 4111 1111 1111 1111, ssn 123-45-6789, mail jane.doe@example.com, order 12345",
+        );
+        assert_eq!(
+            out,
+            "card [REDACTED CARD], ssn [REDACTED SSN], mail [REDACTED EMAIL], order 12345"
+        );
+    }
+
+    #[test]
+    fn custom_patterns_apply_and_invalid_ones_are_rejected() {
+        let mut s = enabled();
+        s.presets.clear();
+        s.custom_patterns = vec![r"(?i)project\s+\w+".into()];
+        let r = Redactor::from_settings(&s).unwrap();
+        assert_eq!(
+            r.redact("about Project Falcon today"),
+            "about [REDACTED] today"
+        );
+
+        s.custom_patterns.push("(unclosed".into());
+        let err = Redactor::from_settings(&s).unwrap_err();
+        assert_eq!(err.pattern, "(unclosed");
+    }
+
+    #[test]
+    fn disabled_settings_are_a_no_op() {
+        let r = Redactor::from_settings(&RedactionSettings::default()).unwrap();
+        assert!(r.is_empty());
+        assert_eq!(r.redact("jane@example.com"), "jane@example.com");
+    }
+}
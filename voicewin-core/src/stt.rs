@@ -1,7 +1,13 @@
 // Small helpers/constants for interpreting STT selections in config.
 
+use serde::{Deserialize, Serialize};
+
 pub const STT_PROVIDER_LOCAL: &str = "local";
 pub const STT_PROVIDER_ELEVENLABS: &str = "elevenlabs";
+/// A user-supplied program; see [`CommandSttSettings`].
+pub const STT_PROVIDER_COMMAND: &str = "command";
+/// The `stt_model` of the command provider unless the user picks their own.
+pub const COMMAND_MODEL_DEFAULT: &str = "default";
 
 // ElevenLabs model selectors as exposed by VoiceWin.
 //
@@ -10,6 +16,119 @@ pub const STT_PROVIDER_ELEVENLABS: &str = "elevenlabs";
 pub const ELEVENLABS_MODEL_SCRIBE_V2: &str = "scribe_v2";
 pub const ELEVENLABS_MODEL_SCRIBE_V2_REALTIME: &str = "scribe_v2_realtime";
 
+/// Who decides when a realtime segment is final.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeCommitStrategy {
+    /// The provider's server-side VAD, tuned by [`RealtimeVadSettings`].
+    #[default]
+    Vad,
+    /// We send the commits: at each pause detected locally (`vad_silence_threshold_ms` of
+    /// quiet after `min_speech_duration_ms` of speech), and when recording stops.
+    Manual,
+}
+
+impl RealtimeCommitStrategy {
+    /// The `commit_strategy` query value ElevenLabs expects.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Vad => "vad",
+            Self::Manual => "manual",
+        }
+    }
+}
+
+/// Server-side voice activity detection for realtime STT. The defaults favour latency in a
+/// quiet room; a noisy one wants a higher `vad_threshold_milli` and longer durations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RealtimeVadSettings {
+    #[serde(default)]
+    pub commit_strategy: RealtimeCommitStrategy,
+
+    /// Silence that ends a segment.
+    #[serde(default = "default_vad_silence_threshold_ms")]
+    pub vad_silence_threshold_ms: u32,
+
+    /// Speech probability (0..1000 = 0.0..1.0) above which audio counts as voice.
+    #[serde(default = "default_vad_threshold_milli")]
+    pub vad_threshold_milli: u32,
+
+    #[serde(default = "default_min_speech_duration_ms")]
+    pub min_speech_duration_ms: u32,
+
+    #[serde(default = "default_min_silence_duration_ms")]
+    pub min_silence_duration_ms: u32,
+}
+
+fn default_vad_silence_threshold_ms() -> u32 {
+    600
+}
+
+fn default_vad_threshold_milli() -> u32 {
+    400
+}
+
+fn default_min_speech_duration_ms() -> u32 {
+    100
+}
+
+fn default_min_silence_duration_ms() -> u32 {
+    150
+}
+
+impl Default for RealtimeVadSettings {
+    fn default() -> Self {
+        Self {
+            commit_strategy: RealtimeCommitStrategy::default(),
+            vad_silence_threshold_ms: default_vad_silence_threshold_ms(),
+            vad_threshold_milli: default_vad_threshold_milli(),
+            min_speech_duration_ms: default_min_speech_duration_ms(),
+            min_silence_duration_ms: default_min_silence_duration_ms(),
+        }
+    }
+}
+
+/// The program behind the `command` STT provider, for engines VoiceWin doesn't ship
+/// (whisperX, faster-whisper scripts, in-house models).
+///
+/// It runs with `args`, where `{wav}` becomes the path of a mono 16 kHz WAV of the recording,
+/// `{language}` the dictation language (`auto` to detect) and `{model}` the selected
+/// `stt_model`; without a `{wav}` argument the path is appended. It must exit with status 0
+/// and print `{"text": "..."}` to stdout, optionally with
+/// `"segments": [{"speaker": 1, "text": "..."}]` for diarized output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandSttSettings {
+    /// Executable path, or a name looked up on `PATH`.
+    #[serde(default)]
+    pub program: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl CommandSttSettings {
+    pub fn is_configured(&self) -> bool {
+        !self.program.trim().is_empty()
+    }
+
+    /// `args` with the placeholders filled in.
+    pub fn expand_args(&self, wav: &str, language: &str, model: &str) -> Vec<String> {
+        let mut args: Vec<String> = self
+            .args
+            .iter()
+            .map(|a| {
+                a.replace("{wav}", wav)
+                    .replace("{language}", language)
+                    .replace("{model}", model)
+            })
+            .collect();
+        if !self.args.iter().any(|a| a.contains("{wav}")) {
+            args.push(wav.to_string());
+        }
+        args
+    }
+}
+
 pub fn is_elevenlabs_realtime_selected(provider: &str, model: &str) -> bool {
     provider == STT_PROVIDER_ELEVENLABS && model == ELEVENLABS_MODEL_SCRIBE_V2_REALTIME
 }
@@ -67,6 +186,32 @@ mod tests {
         assert_eq!(normalize_elevenlabs_batch_model("other"), "other");
     }
 
+    #[test]
+    fn command_args_fill_placeholders_or_append_the_wav() {
+        let mut cmd = CommandSttSettings {
+            program: "whisperx".into(),
+            args: vec![
+                "--language".into(),
+                "{language}".into(),
+                "--model={model}".into(),
+            ],
+        };
+        assert!(cmd.is_configured());
+        assert_eq!(
+            cmd.expand_args("/tmp/a.wav", "de", "large-v3"),
+            ["--language", "de", "--model=large-v3", "/tmp/a.wav"]
+        );
+
+        cmd.args = vec!["-i".into(), "{wav}".into()];
+        assert_eq!(
+            cmd.expand_args("/tmp/a.wav", "auto", "default"),
+            ["-i", "/tmp/a.wav"]
+        );
+
+        cmd.program = " ".into();
+        assert!(!cmd.is_configured());
+    }
+
     #[test]
     fn transcript_override_accepts_only_non_empty() {
         assert_eq!(accept_transcript_override("".to_string()), None);
@@ -1,4 +1,6 @@
+use crate::types::{DisfluencyFilter, OutputFormat};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
 
 fn tag_block_re() -> &'static Regex {
@@ -30,11 +32,111 @@ fn filler_words_re() -> &'static Regex {
     })
 }
 
+fn discourse_fillers_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // Only comma-delimited uses, so "I like it" and "you know the answer" are kept.
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i),[ \t]*\b(?:like|you know|i mean)[ \t]*,|(^[ \t]*|[.!?\n][ \t]*)(?:like|you know|i mean|basically|well),[ \t]*",
+        )
+        .expect("valid discourse filler regex")
+    })
+}
+
 fn whitespace_re() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| Regex::new(r"\s{2,}").expect("valid whitespace regex"))
 }
 
+fn space_before_punct_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "good .the" -> "good. the": a detached mark also separates the following word.
+    RE.get_or_init(|| {
+        Regex::new(r"[ \t]+([,.;:!?])(\p{L})?").expect("valid punctuation spacing regex")
+    })
+}
+
+fn missing_space_after_punct_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // Only between letters, so decimals, times, and URLs (`a.com/?q=1`) are left alone.
+    RE.get_or_init(|| Regex::new(r"(\p{L}[,;!?])(\p{L})").expect("valid punctuation spacing regex"))
+}
+
+fn split_thousands_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "1, 000" -> "1,000"
+    RE.get_or_init(|| Regex::new(r"\b(\d{1,3}),[ \t]+(\d{3})\b").expect("valid thousands regex"))
+}
+
+fn percent_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)(\d)[ \t]*(?:%|\bper ?cent\b)").expect("valid percent regex")
+    })
+}
+
+fn lone_i_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\bi('m|'ve|'ll|'d)?\b").expect("valid pronoun regex"))
+}
+
+fn inline_whitespace_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[ \t]{2,}").expect("valid whitespace regex"))
+}
+
+fn speech_annotation_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // Whisper's non-speech markers ("[BLANK_AUDIO]", "(music)"), without touching code brackets.
+    RE.get_or_init(|| {
+        Regex::new(r"\[[A-Z][A-Z_ ]*\]|(?i)\((?:music|applause|laughter|laughs|silence|inaudible|noise|coughs?)\)")
+            .expect("valid annotation regex")
+    })
+}
+
+fn spoken_symbol_rules(format: OutputFormat) -> &'static [(Regex, &'static str)] {
+    static MARKDOWN: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    static CODE: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+
+    let common: [(&str, &str); 4] = [
+        (r"(?i)\btriple back ?ticks?\b", "```"),
+        (r"(?i)\bback ?ticks?\s+(.+?)\s+back ?ticks?\b", "`$1`"),
+        (r"(?i)\bdash dash\s*", "--"),
+        (r"(?i)[ \t]*\bnew ?line\b[,.]?[ \t]*", "\n"),
+    ];
+    let build = |extra: &[(&str, &'static str)]| {
+        common
+            .iter()
+            .chain(extra)
+            .map(|(re, to)| (Regex::new(re).expect("valid spoken symbol regex"), *to))
+            .collect::<Vec<_>>()
+    };
+
+    match format {
+        OutputFormat::Plain => &[],
+        OutputFormat::Markdown => MARKDOWN.get_or_init(|| {
+            build(&[
+                (r"(?i)[ \t]*\bnew bullet(?: point)?\b[,.]?[ \t]*", "\n- "),
+                (r"(?im)^[ \t]*bullet(?: point)?\b[,.]?[ \t]*", "- "),
+            ])
+        }),
+        OutputFormat::Code => CODE.get_or_init(|| {
+            build(&[
+                (r"(?i)\s*\bopen paren\b\s*", "("),
+                (r"(?i)\s*\bclose paren\b", ")"),
+                (r"(?i)\s*\bopen bracket\b\s*", "["),
+                (r"(?i)\s*\bclose bracket\b", "]"),
+                (r"(?i)\s*\bopen brace\b\s*", "{"),
+                (r"(?i)\s*\bclose brace\b", "}"),
+                (r"(?i)\s*\bunderscore\b\s*", "_"),
+                (r"(?i)\bequals equals\b", "=="),
+                (r"(?i)\barrow\b", "->"),
+                (r"(?i)\s*\bsemicolon\b", ";"),
+            ])
+        }),
+    }
+}
+
 fn enhancement_thinking_re() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| {
@@ -44,6 +146,10 @@ fn enhancement_thinking_re() -> &'static Regex {
 }
 
 pub fn filter_transcription_output(text: &str) -> String {
+    filter_plain_transcription(text, DisfluencyFilter::default())
+}
+
+fn filter_plain_transcription(text: &str, disfluency: DisfluencyFilter) -> String {
     // Mirrors VoiceInk’s intent:
     // - remove <TAG>...</TAG> blocks
     // - remove bracketed hallucinations
@@ -56,18 +162,451 @@ pub fn filter_transcription_output(text: &str) -> String {
     out = hallucination_brackets_re()
         .replace_all(&out, "")
         .to_string();
-    out = filler_words_re().replace_all(&out, "").to_string();
+    out = remove_disfluencies(&out, disfluency);
     out = whitespace_re().replace_all(&out, " ").to_string();
 
     out.trim().to_string()
 }
 
+fn remove_disfluencies(text: &str, level: DisfluencyFilter) -> String {
+    match level {
+        DisfluencyFilter::Off => text.to_string(),
+        DisfluencyFilter::Light => filler_words_re().replace_all(text, "").to_string(),
+        DisfluencyFilter::Aggressive => {
+            let out = filler_words_re().replace_all(text, "");
+            let out = discourse_fillers_re().replace_all(&out, "$1");
+            out.split('\n')
+                .map(collapse_repeated_words)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+/// "I I think" -> "I think", "the, the plan" -> "the plan". Numbers are left alone ("10 10").
+fn collapse_repeated_words(line: &str) -> String {
+    fn key(word: &str) -> String {
+        word.trim_end_matches(|c: char| c.is_ascii_punctuation() && c != '\'')
+            .to_lowercase()
+    }
+
+    let mut words: Vec<&str> = Vec::new();
+    for word in line.split([' ', '\t']).filter(|w| !w.is_empty()) {
+        if let Some(prev) = words.last_mut() {
+            let k = key(word);
+            let stutter = !prev.ends_with(['.', '!', '?', ':', ';'])
+                && k.chars().all(|c| c.is_alphabetic() || c == '\'')
+                && !k.is_empty()
+                && key(prev) == k;
+            if stutter {
+                // The later copy keeps whatever punctuation follows the word.
+                *prev = word;
+                continue;
+            }
+        }
+        words.push(word);
+    }
+    words.join(" ")
+}
+
+/// A spoken phrase replaced before enhancement ("thumbs up emoji" -> 👍).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpokenReplacement {
+    pub phrase: String,
+    pub replacement: String,
+    /// Language code the phrase is spoken in; `None` applies to every language.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpokenReplacementSettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Include the built-in emoji and layout phrases for the session language.
+    #[serde(default = "default_use_builtin")]
+    pub use_builtin: bool,
+
+    /// User entries; checked before the built-ins.
+    #[serde(default)]
+    pub custom: Vec<SpokenReplacement>,
+}
+
+fn default_use_builtin() -> bool {
+    true
+}
+
+impl Default for SpokenReplacementSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            use_builtin: default_use_builtin(),
+            custom: Vec::new(),
+        }
+    }
+}
+
+/// (language, phrase, replacement)
+const BUILTIN_REPLACEMENTS: &[(&str, &str, &str)] = &[
+    ("en", "thumbs up emoji", "👍"),
+    ("en", "thumbs down emoji", "👎"),
+    ("en", "smiley face emoji", "😊"),
+    ("en", "smiley emoji", "🙂"),
+    ("en", "laughing emoji", "😂"),
+    ("en", "wink emoji", "😉"),
+    ("en", "heart emoji", "❤️"),
+    ("en", "fire emoji", "🔥"),
+    ("en", "party emoji", "🎉"),
+    ("en", "thinking emoji", "🤔"),
+    ("en", "crying emoji", "😢"),
+    ("en", "clapping emoji", "👏"),
+    ("en", "check mark emoji", "✅"),
+    ("en", "new paragraph", "\n\n"),
+    ("en", "new line", "\n"),
+    ("en", "bullet point", "\n- "),
+    ("de", "Daumen hoch Emoji", "👍"),
+    ("de", "Smiley Emoji", "🙂"),
+    ("de", "Herz Emoji", "❤️"),
+    ("de", "lachendes Emoji", "😂"),
+    ("de", "neuer Absatz", "\n\n"),
+    ("de", "neue Zeile", "\n"),
+    ("de", "Aufzählungspunkt", "\n- "),
+    ("es", "emoji pulgar arriba", "👍"),
+    ("es", "emoji sonriente", "🙂"),
+    ("es", "emoji corazón", "❤️"),
+    ("es", "emoji riendo", "😂"),
+    ("es", "nuevo párrafo", "\n\n"),
+    ("es", "nueva línea", "\n"),
+    ("es", "viñeta", "\n- "),
+    ("fr", "emoji pouce levé", "👍"),
+    ("fr", "emoji sourire", "🙂"),
+    ("fr", "emoji cœur", "❤️"),
+    ("fr", "emoji mort de rire", "😂"),
+    ("fr", "nouveau paragraphe", "\n\n"),
+    ("fr", "nouvelle ligne", "\n"),
+    ("fr", "puce", "\n- "),
+];
+
+/// Replace spoken phrases with emoji / line breaks / user text. `language` is the session
+/// language ("en", "de-DE", or "auto", which uses every table).
+pub fn apply_spoken_replacements(
+    text: &str,
+    settings: &SpokenReplacementSettings,
+    language: &str,
+) -> String {
+    if !settings.enabled {
+        return text.to_string();
+    }
+
+    let lang = language.split(['-', '_']).next().unwrap_or_default();
+    let matches_lang = |l: Option<&str>| match l {
+        None => true,
+        Some(_) if lang == "auto" => true,
+        Some(l) => l.eq_ignore_ascii_case(lang),
+    };
+
+    let custom = settings
+        .custom
+        .iter()
+        .filter(|r| matches_lang(r.language.as_deref()))
+        .map(|r| (r.phrase.as_str(), r.replacement.as_str()));
+    let builtin = BUILTIN_REPLACEMENTS
+        .iter()
+        .filter(|_| settings.use_builtin)
+        .filter(|(l, _, _)| matches_lang(Some(l)))
+        .map(|(_, p, r)| (*p, *r));
+
+    let mut out = text.to_string();
+    for (phrase, replacement) in custom.chain(builtin) {
+        let Some(re) = spoken_phrase_re(phrase, replacement) else {
+            continue;
+        };
+        out = re
+            .replace_all(&out, regex::NoExpand(replacement))
+            .to_string();
+    }
+    out.trim_start_matches('\n').to_string()
+}
+
+/// Case-insensitive, whitespace-tolerant matcher for `phrase`. Layout replacements (line
+/// breaks, bullets) also swallow the spaces and comma/period STT puts around them.
+fn spoken_phrase_re(phrase: &str, replacement: &str) -> Option<Regex> {
+    let words: Vec<String> = phrase.split_whitespace().map(regex::escape).collect();
+    let first = phrase.trim().chars().next()?;
+    let last = phrase.trim().chars().last()?;
+
+    let mut pattern = String::from("(?i)");
+    let layout = replacement.starts_with('\n');
+    if layout {
+        pattern.push_str(r"[ \t]*");
+    }
+    if first.is_alphanumeric() {
+        pattern.push_str(r"\b");
+    }
+    pattern.push_str(&words.join(r"\s+"));
+    if last.is_alphanumeric() {
+        pattern.push_str(r"\b");
+    }
+    if layout {
+        pattern.push_str(r"[,.]?[ \t]*");
+    }
+    Regex::new(&pattern).ok()
+}
+
+/// [`filter_transcription_output`] for the target's [`OutputFormat`]: Markdown and code keep
+/// brackets, tags, and line breaks, and only drop whisper's non-speech markers and fillers.
+pub fn filter_transcription_output_for(
+    text: &str,
+    format: OutputFormat,
+    disfluency: DisfluencyFilter,
+) -> String {
+    if format == OutputFormat::Plain {
+        return filter_plain_transcription(text, disfluency);
+    }
+    let out = speech_annotation_re().replace_all(text, "");
+    let out = remove_disfluencies(&out, disfluency);
+    let out = inline_whitespace_re().replace_all(&out, " ");
+    out.lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Map spoken symbols ("dash dash", "backtick foo backtick", "open paren") for Markdown and
+/// code targets. Code also loses the sentence-final period STT adds.
+pub fn apply_spoken_symbols(text: &str, format: OutputFormat) -> String {
+    let mut out = text.to_string();
+    for (re, to) in spoken_symbol_rules(format) {
+        out = re.replace_all(&out, *to).to_string();
+    }
+    if format == OutputFormat::Code && out.ends_with('.') && !out.ends_with("..") {
+        out.pop();
+    }
+    out
+}
+
+/// Deterministic tidy-up for transcripts that skip LLM enhancement: spacing around
+/// punctuation, sentence casing, a standalone "i", "1, 000" / "5 percent" style numbers, and a
+/// closing period. Line breaks are kept (e.g. speaker-labelled turns).
+pub fn basic_cleanup(text: &str) -> String {
+    text.lines()
+        .map(cleanup_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn cleanup_line(line: &str) -> String {
+    let mut out = inline_whitespace_re()
+        .replace_all(line.trim(), " ")
+        .to_string();
+    out = space_before_punct_re()
+        .replace_all(&out, |c: &regex::Captures| match c.get(2) {
+            Some(next) => format!("{} {}", &c[1], next.as_str()),
+            None => c[1].to_string(),
+        })
+        .to_string();
+    out = missing_space_after_punct_re()
+        .replace_all(&out, "$1 $2")
+        .to_string();
+    out = split_thousands_re().replace_all(&out, "$1,$2").to_string();
+    out = percent_re().replace_all(&out, "$1%").to_string();
+    out = lone_i_re().replace_all(&out, "I$1").to_string();
+    out = capitalize_sentences(&out);
+
+    let words = out.split_whitespace().count();
+    let is_list_item = out.starts_with("- ");
+    if words > 1 && !is_list_item && out.chars().last().is_some_and(|c| c.is_alphanumeric()) {
+        out.push('.');
+    }
+    out
+}
+
+fn capitalize_sentences(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut at_sentence_start = true;
+    let mut after_terminator = false;
+    for c in text.chars() {
+        if at_sentence_start && c.is_alphanumeric() {
+            out.extend(c.to_uppercase());
+            at_sentence_start = false;
+            continue;
+        }
+        if matches!(c, '.' | '!' | '?') {
+            after_terminator = true;
+        } else if c.is_whitespace() {
+            if after_terminator {
+                at_sentence_start = true;
+            }
+            after_terminator = false;
+        } else {
+            after_terminator = false;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Fit dictated text to what precedes the caret: a separating space after a word or
+/// punctuation, and a lowercase first word when continuing a sentence. Unknown context
+/// (`None`) or the start of a field/line leaves the text as is.
+pub fn adjust_for_insertion(text: &str, before_caret: Option<&str>) -> String {
+    let text = text.trim();
+    let Some(before) = before_caret else {
+        return text.to_string();
+    };
+    let Some(prev) = before.chars().last() else {
+        return text.to_string();
+    };
+    if prev == '\n' || prev == '\r' {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len() + 1);
+    let starts_with_word = text.chars().next().is_some_and(|c| c.is_alphanumeric());
+    if starts_with_word
+        && !prev.is_whitespace()
+        && !matches!(prev, '(' | '[' | '{' | '"' | '\'' | '/' | '-')
+    {
+        out.push(' ');
+    }
+
+    let last_mark = before.trim_end().chars().last();
+    let continues_sentence =
+        last_mark.is_some_and(|c| c.is_alphanumeric() || matches!(c, ',' | ';' | ':' | '-'));
+    if continues_sentence {
+        out.push_str(&lowercase_first_word(text));
+    } else {
+        out.push_str(text);
+    }
+    out
+}
+
+/// Lowercase a capitalized first word ("The" -> "the"), leaving "I", acronyms, and mixed-case
+/// names ("iPhone", "NASA") alone.
+fn lowercase_first_word(text: &str) -> String {
+    let word_end = text
+        .find(|c: char| !c.is_alphanumeric() && c != '\'')
+        .unwrap_or(text.len());
+    let (word, rest) = text.split_at(word_end);
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return text.to_string();
+    };
+    let tail: String = chars.collect();
+    let is_pronoun_i = word == "I" || word.starts_with("I'");
+    if !first.is_uppercase() || is_pronoun_i || tail.chars().any(|c| c.is_uppercase()) {
+        return text.to_string();
+    }
+    format!("{}{tail}{rest}", first.to_lowercase())
+}
+
 pub fn filter_enhancement_output(text: &str) -> String {
     // Strip <thinking>, <think>, <reasoning> blocks.
     let out = enhancement_thinking_re().replace_all(text, "");
     out.trim().to_string()
 }
 
+/// A named step of the post-STT text pipeline. Profiles reorder or drop steps; the
+/// engine runs them in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextStage {
+    /// Non-speech markers, hallucinated tags, and the session's disfluency level.
+    DisfluencyFilter,
+    /// A trailing "send it" / "just copy" picks the insert mode.
+    InsertCommands,
+    /// "insert my address" expands to the stored snippet.
+    Snippets,
+    /// A leading trigger word selects a prompt and turns on enhancement.
+    Triggers,
+    /// Spoken symbols for Markdown/code, then the spoken replacement tables.
+    Replacements,
+    /// LLM enhancement.
+    Llm,
+    /// [`basic_cleanup`] for plain text that was not enhanced.
+    Casing,
+}
+
+/// The stages a session runs, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TextPipeline(pub Vec<TextStage>);
+
+impl Default for TextPipeline {
+    /// The order VoiceWin has always used.
+    fn default() -> Self {
+        Self(vec![
+            TextStage::DisfluencyFilter,
+            TextStage::InsertCommands,
+            TextStage::Snippets,
+            TextStage::Triggers,
+            TextStage::Replacements,
+            TextStage::Llm,
+            TextStage::Casing,
+        ])
+    }
+}
+
+impl TextPipeline {
+    pub fn stages(&self) -> &[TextStage] {
+        &self.0
+    }
+
+    pub fn contains(&self, stage: TextStage) -> bool {
+        self.0.contains(&stage)
+    }
+}
+
+/// Session settings a [`TextProcessor`] may depend on.
+#[derive(Debug, Clone, Copy)]
+pub struct TextContext<'a> {
+    pub output_format: OutputFormat,
+    pub disfluency_filter: DisfluencyFilter,
+    pub language: &'a str,
+    pub spoken_replacements: &'a SpokenReplacementSettings,
+    pub basic_cleanup: bool,
+    /// An earlier stage already rewrote the text with an LLM.
+    pub enhanced: bool,
+}
+
+/// A pure text rewrite run as one pipeline stage. Stages that change the session itself
+/// (insert commands, snippets, triggers, the LLM) are run by the engine.
+pub trait TextProcessor: Send + Sync {
+    fn process(&self, text: &str, cx: &TextContext<'_>) -> String;
+}
+
+pub struct DisfluencyFilterProcessor;
+
+impl TextProcessor for DisfluencyFilterProcessor {
+    fn process(&self, text: &str, cx: &TextContext<'_>) -> String {
+        filter_transcription_output_for(text, cx.output_format, cx.disfluency_filter)
+    }
+}
+
+pub struct ReplacementsProcessor;
+
+impl TextProcessor for ReplacementsProcessor {
+    fn process(&self, text: &str, cx: &TextContext<'_>) -> String {
+        let out = apply_spoken_symbols(text, cx.output_format);
+        apply_spoken_replacements(&out, cx.spoken_replacements, cx.language)
+    }
+}
+
+pub struct CasingProcessor;
+
+impl TextProcessor for CasingProcessor {
+    fn process(&self, text: &str, cx: &TextContext<'_>) -> String {
+        // Prose cleanup would capitalize code and reflow Markdown.
+        if cx.enhanced || !cx.basic_cleanup || cx.output_format != OutputFormat::Plain {
+            return text.to_string();
+        }
+        basic_cleanup(text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,9 +623,186 @@ mod tests {
         assert_eq!(filter_transcription_output(input), "hello world");
     }
 
+    #[test]
+    fn disfluency_levels_remove_fillers_and_stutters() {
+        let raw = "Um, like, I I think it was, like, the the plan. You know, we like it it.";
+        let plain = |level| filter_transcription_output_for(raw, OutputFormat::Plain, level);
+
+        assert_eq!(plain(DisfluencyFilter::Off), raw);
+        assert_eq!(
+            plain(DisfluencyFilter::Light),
+            "like, I I think it was, like, the the plan. You know, we like it it."
+        );
+        assert_eq!(
+            plain(DisfluencyFilter::Aggressive),
+            "I think it was the plan. we like it."
+        );
+        assert_eq!(
+            filter_transcription_output_for(
+                "room 10 10, I mean",
+                OutputFormat::Plain,
+                DisfluencyFilter::Aggressive
+            ),
+            "room 10 10, I mean"
+        );
+    }
+
+    #[test]
+    fn basic_cleanup_fixes_casing_spacing_and_numbers() {
+        let input = "so i think we're good .the budget is 1, 000 dollars,up 5 percent!next steps ? call bob";
+        assert_eq!(
+            basic_cleanup(input),
+            "So I think we're good. The budget is 1,000 dollars, up 5%! Next steps? Call bob."
+        );
+
+        // Decimals, URLs, and line breaks survive; a single word doesn't get a period.
+        assert_eq!(
+            basic_cleanup("version 3.5 at example.com/?q=1 today\nspeaker 2: ok"),
+            "Version 3.5 at example.com/?q=1 today.\nSpeaker 2: ok."
+        );
+        assert_eq!(basic_cleanup("hello"), "Hello");
+    }
+
+    #[test]
+    fn insertion_adjusts_spacing_and_casing_to_caret_context() {
+        assert_eq!(adjust_for_insertion("Hello there.", None), "Hello there.");
+        assert_eq!(
+            adjust_for_insertion("Hello there.", Some("")),
+            "Hello there."
+        );
+        assert_eq!(adjust_for_insertion("Hello.", Some("Done.\n")), "Hello.");
+
+        // Mid-sentence: separating space and lowercase continuation.
+        assert_eq!(
+            adjust_for_insertion("The budget is fine.", Some("As we said,")),
+            " the budget is fine."
+        );
+        assert_eq!(
+            adjust_for_insertion("The budget", Some("and ")),
+            "the budget"
+        );
+        assert_eq!(adjust_for_insertion("I agree.", Some("well")), " I agree.");
+        assert_eq!(
+            adjust_for_insertion("NASA called.", Some("so")),
+            " NASA called."
+        );
+
+        // After a finished sentence: space, keep the capital.
+        assert_eq!(
+            adjust_for_insertion("Next item.", Some("Done.")),
+            " Next item."
+        );
+        assert_eq!(adjust_for_insertion("quote", Some("say \"")), "quote");
+        assert_eq!(adjust_for_insertion(", right?", Some("fine")), ", right?");
+    }
+
+    #[test]
+    fn markdown_and_code_keep_brackets_and_map_spoken_symbols() {
+        let raw = "[BLANK_AUDIO] um run cargo test dash dash release (music) in [crate]";
+        assert_eq!(
+            filter_transcription_output(raw),
+            "run cargo test dash dash release in"
+        );
+        let kept =
+            filter_transcription_output_for(raw, OutputFormat::Code, DisfluencyFilter::Light);
+        assert_eq!(kept, "run cargo test dash dash release in [crate]");
+        assert_eq!(
+            apply_spoken_symbols(&kept, OutputFormat::Code),
+            "run cargo test --release in [crate]"
+        );
+
+        assert_eq!(
+            apply_spoken_symbols("Call foo open paren x close paren.", OutputFormat::Code),
+            "Call foo(x)"
+        );
+        assert_eq!(
+            apply_spoken_symbols(
+                "Todo new bullet use backtick cargo fmt backtick new bullet ship it.",
+                OutputFormat::Markdown
+            ),
+            "Todo\n- use `cargo fmt`\n- ship it."
+        );
+        assert_eq!(
+            apply_spoken_symbols("dash dash verbose", OutputFormat::Plain),
+            "dash dash verbose"
+        );
+    }
+
+    #[test]
+    fn spoken_replacements_follow_language_and_custom_entries() {
+        let mut settings = SpokenReplacementSettings::default();
+        let input = "Great job thumbs up emoji new line see you, Neue Zeile";
+        assert_eq!(apply_spoken_replacements(input, &settings, "en"), input);
+
+        settings.enabled = true;
+        assert_eq!(
+            apply_spoken_replacements(input, &settings, "en-US"),
+            "Great job 👍\nsee you, Neue Zeile"
+        );
+        assert_eq!(
+            apply_spoken_replacements(input, &settings, "auto"),
+            "Great job 👍\nsee you,\n"
+        );
+        assert_eq!(
+            apply_spoken_replacements(
+                "Groceries. Bullet point milk, bullet point eggs",
+                &settings,
+                "en"
+            ),
+            "Groceries.\n- milk,\n- eggs"
+        );
+
+        settings.custom.push(SpokenReplacement {
+            phrase: "thumbs up emoji".into(),
+            replacement: "(+1)".into(),
+            language: None,
+        });
+        settings.custom.push(SpokenReplacement {
+            phrase: "my email".into(),
+            replacement: "me@example.com".into(),
+            language: Some("en".into()),
+        });
+        assert_eq!(
+            apply_spoken_replacements("Thumbs up emoji, write to my email", &settings, "en"),
+            "(+1), write to me@example.com"
+        );
+        assert_eq!(
+            apply_spoken_replacements("my email", &settings, "de"),
+            "my email"
+        );
+    }
+
     #[test]
     fn enhancement_filter_strips_thinking() {
         let input = "<thinking>plan</thinking>\nResult";
         assert_eq!(filter_enhancement_output(input), "Result");
     }
+
+    #[test]
+    fn casing_processor_only_tidies_unenhanced_plain_text() {
+        let replacements = SpokenReplacementSettings::default();
+        let cx = TextContext {
+            output_format: OutputFormat::Plain,
+            disfluency_filter: DisfluencyFilter::Light,
+            language: "en",
+            spoken_replacements: &replacements,
+            basic_cleanup: true,
+            enhanced: false,
+        };
+        assert_eq!(CasingProcessor.process("so i agree", &cx), "So I agree.");
+
+        let enhanced = TextContext {
+            enhanced: true,
+            ..cx
+        };
+        assert_eq!(
+            CasingProcessor.process("so i agree", &enhanced),
+            "so i agree"
+        );
+        let code = TextContext {
+            output_format: OutputFormat::Code,
+            ..cx
+        };
+        assert_eq!(CasingProcessor.process("so i agree", &code), "so i agree");
+    }
 }
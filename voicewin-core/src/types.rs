@@ -55,6 +55,11 @@ pub struct AppIdentity {
     pub exe_path: Option<ExePath>,
     pub process_name: Option<ProcessName>,
     pub window_title: Option<WindowTitle>,
+
+    /// Native handle of the focused window (HWND on Windows, process id on macOS), used to
+    /// return focus to it before inserting. Only meaningful in-process, so never serialized.
+    #[serde(skip)]
+    pub window_handle: Option<u64>,
 }
 
 impl AppIdentity {
@@ -63,6 +68,7 @@ impl AppIdentity {
             exe_path: None,
             process_name: None,
             window_title: None,
+            window_handle: None,
         }
     }
 
@@ -87,4 +93,78 @@ pub enum InsertMode {
     Paste,
     PasteAndEnter,
     ShiftInsert,
+    /// Leave the text on the clipboard without pasting.
+    ClipboardOnly,
+}
+
+/// How the text gets into the target app. The clipboard paste suits almost every app; the
+/// others are for apps where it misbehaves, pinned per profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsertionStrategy {
+    #[default]
+    ClipboardPaste,
+    /// Type the text as key presses. Slow for long text, but terminals and remote desktop
+    /// sessions (RDP, Citrix) that mangle pastes take it.
+    TypeKeys,
+    /// Windows: write into the focused field through UI Automation, without the keyboard.
+    UiaDirect,
+    /// macOS: replace the focused element's selection through the Accessibility API.
+    MacosAx,
+}
+
+impl InsertionStrategy {
+    pub fn label(self) -> &'static str {
+        match self {
+            InsertionStrategy::ClipboardPaste => "Clipboard paste",
+            InsertionStrategy::TypeKeys => "Typing",
+            InsertionStrategy::UiaDirect => "UI Automation",
+            InsertionStrategy::MacosAx => "macOS Accessibility",
+        }
+    }
+}
+
+/// Which window receives the dictated text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsertTarget {
+    /// The window that was focused when dictation stopped.
+    #[default]
+    Foreground,
+    /// A running app found by process name ("Obsidian.exe"), focused before pasting.
+    App(String),
+    /// Append to a Markdown note file instead of typing anywhere; `{date}` in the path
+    /// expands to the session date (daily notes).
+    NoteFile(String),
+    /// The built-in scratchpad window, where dictations pile up with timestamps until they
+    /// are copied out.
+    Scratchpad,
+}
+
+/// What kind of text the target app expects; changes filtering, spoken-symbol mapping, and
+/// the enhancement instructions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    /// Prose: sentence casing and punctuation.
+    #[default]
+    Plain,
+    /// Markdown editors: keep backticks/brackets, spoken "bullet" / "new line".
+    Markdown,
+    /// Code editors and terminals: no capitalization or closing period, spoken symbols.
+    Code,
+}
+
+/// How much spoken disfluency is stripped from transcripts before insertion or enhancement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisfluencyFilter {
+    /// Keep the transcript as heard.
+    Off,
+    /// Hesitation sounds only ("um", "uh", "hmm").
+    #[default]
+    Light,
+    /// Also comma-delimited fillers ("like,", "you know,", "I mean,") and stuttered repeats
+    /// ("I I think", "the the").
+    Aggressive,
 }
@@ -0,0 +1,287 @@
+// Cloud usage accounting: LLM tokens and STT audio time per session, priced with the
+// user-editable table in `PricingSettings`.
+//
+// Prices are integer micro-dollars (millionths of a USD) so the config stays exact and `Eq`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::stt::STT_PROVIDER_ELEVENLABS;
+
+/// Provider name reported by OpenAI-compatible LLM endpoints.
+pub const LLM_PROVIDER_OPENAI_COMPATIBLE: &str = "openai-compatible";
+
+const MICROS_PER_USD: u64 = 1_000_000;
+const TOKENS_PER_PRICE_UNIT: u128 = 1_000_000;
+const MS_PER_HOUR: u128 = 60 * 60 * 1000;
+
+/// Token counts from an OpenAI-compatible `usage` object.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+/// One billable provider call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub ts_unix_ms: i64,
+    pub provider: String,
+    pub model: String,
+
+    #[serde(default)]
+    pub prompt_tokens: u64,
+
+    #[serde(default)]
+    pub completion_tokens: u64,
+
+    /// Audio sent to a cloud STT provider.
+    #[serde(default)]
+    pub audio_ms: u64,
+}
+
+impl UsageRecord {
+    pub fn tokens(ts_unix_ms: i64, provider: &str, model: &str, usage: TokenUsage) -> Self {
+        Self {
+            ts_unix_ms,
+            provider: provider.into(),
+            model: model.into(),
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            audio_ms: 0,
+        }
+    }
+
+    pub fn audio(ts_unix_ms: i64, provider: &str, model: &str, audio_ms: u64) -> Self {
+        Self {
+            ts_unix_ms,
+            provider: provider.into(),
+            model: model.into(),
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            audio_ms,
+        }
+    }
+}
+
+/// What a provider (or one of its models) charges.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderPricing {
+    pub provider: String,
+
+    /// Applies to every model of `provider` when `None`; an exact model entry wins.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    #[serde(default)]
+    pub input_micros_per_million_tokens: u64,
+
+    #[serde(default)]
+    pub output_micros_per_million_tokens: u64,
+
+    #[serde(default)]
+    pub audio_micros_per_hour: u64,
+}
+
+impl ProviderPricing {
+    fn cost_micros(&self, line: &CostLine) -> u64 {
+        let tokens = u128::from(line.prompt_tokens)
+            * u128::from(self.input_micros_per_million_tokens)
+            + u128::from(line.completion_tokens)
+                * u128::from(self.output_micros_per_million_tokens);
+        let audio = u128::from(line.audio_ms) * u128::from(self.audio_micros_per_hour);
+        let micros = tokens.div_ceil(TOKENS_PER_PRICE_UNIT) + audio.div_ceil(MS_PER_HOUR);
+        micros.try_into().unwrap_or(u64::MAX)
+    }
+}
+
+/// Pricing used for cost estimates. Defaults are list prices at the time of writing; users
+/// on other plans or endpoints edit them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PricingSettings {
+    #[serde(default)]
+    pub prices: Vec<ProviderPricing>,
+}
+
+impl Default for PricingSettings {
+    fn default() -> Self {
+        let llm = |model: &str, input: u64, output: u64| ProviderPricing {
+            provider: LLM_PROVIDER_OPENAI_COMPATIBLE.into(),
+            model: Some(model.into()),
+            input_micros_per_million_tokens: input,
+            output_micros_per_million_tokens: output,
+            audio_micros_per_hour: 0,
+        };
+        Self {
+            prices: vec![
+                llm("gpt-4o-mini", 150_000, 600_000),
+                llm("gpt-4.1-mini", 400_000, 1_600_000),
+                llm("gpt-4.1-nano", 100_000, 400_000),
+                llm("gpt-4o", 2_500_000, 10_000_000),
+                ProviderPricing {
+                    provider: STT_PROVIDER_ELEVENLABS.into(),
+                    model: None,
+                    input_micros_per_million_tokens: 0,
+                    output_micros_per_million_tokens: 0,
+                    audio_micros_per_hour: 400_000,
+                },
+            ],
+        }
+    }
+}
+
+impl PricingSettings {
+    /// The entry for `provider`/`model`: an exact model match, else the provider-wide one.
+    pub fn price_for(&self, provider: &str, model: &str) -> Option<&ProviderPricing> {
+        let same_provider = |p: &&ProviderPricing| p.provider.eq_ignore_ascii_case(provider);
+        self.prices
+            .iter()
+            .filter(same_provider)
+            .find(|p| {
+                p.model
+                    .as_deref()
+                    .is_some_and(|m| m.trim().eq_ignore_ascii_case(model))
+            })
+            .or_else(|| {
+                self.prices
+                    .iter()
+                    .filter(same_provider)
+                    .find(|p| p.model.is_none())
+            })
+    }
+}
+
+/// Usage and cost of one provider/model over the estimated period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostLine {
+    pub provider: String,
+    pub model: String,
+    /// Number of billable calls.
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub audio_ms: u64,
+    /// `None` when the pricing table has no entry for this provider/model.
+    pub cost_micros: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    /// Most expensive first.
+    pub lines: Vec<CostLine>,
+    /// Sum of the priced lines.
+    pub total_micros: u64,
+}
+
+impl CostEstimate {
+    pub fn total_usd(&self) -> f64 {
+        self.total_micros as f64 / MICROS_PER_USD as f64
+    }
+}
+
+/// Group `records` by provider and model and price each group.
+pub fn estimate_cost<'a>(
+    records: impl IntoIterator<Item = &'a UsageRecord>,
+    pricing: &PricingSettings,
+) -> CostEstimate {
+    let mut lines: Vec<CostLine> = Vec::new();
+    for r in records {
+        let idx = match lines
+            .iter()
+            .position(|l| l.provider == r.provider && l.model == r.model)
+        {
+            Some(i) => i,
+            None => {
+                lines.push(CostLine {
+                    provider: r.provider.clone(),
+                    model: r.model.clone(),
+                    calls: 0,
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    audio_ms: 0,
+                    cost_micros: None,
+                });
+                lines.len() - 1
+            }
+        };
+        let line = &mut lines[idx];
+        line.calls += 1;
+        line.prompt_tokens += r.prompt_tokens;
+        line.completion_tokens += r.completion_tokens;
+        line.audio_ms += r.audio_ms;
+    }
+
+    for line in &mut lines {
+        line.cost_micros = pricing
+            .price_for(&line.provider, &line.model)
+            .map(|p| p.cost_micros(line));
+    }
+    lines.sort_by(|a, b| {
+        b.cost_micros
+            .cmp(&a.cost_micros)
+            .then_with(|| a.provider.cmp(&b.provider))
+            .then_with(|| a.model.cmp(&b.model))
+    });
+
+    CostEstimate {
+        total_micros: lines.iter().filter_map(|l| l.cost_micros).sum(),
+        lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_tokens_and_audio_per_provider_and_model() {
+        let pricing = PricingSettings::default();
+        let tokens = |prompt, completion| TokenUsage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+        };
+        let records = [
+            UsageRecord::tokens(
+                1,
+                "openai-compatible",
+                "gpt-4o-mini",
+                tokens(600_000, 100_000),
+            ),
+            UsageRecord::tokens(2, "openai-compatible", "gpt-4o-mini", tokens(400_000, 0)),
+            // One hour of audio.
+            UsageRecord::audio(3, "elevenlabs", "scribe_v2", 3_600_000),
+            UsageRecord::tokens(4, "openai-compatible", "llama3", tokens(10, 10)),
+        ];
+
+        let est = estimate_cost(&records, &pricing);
+        assert_eq!(est.lines.len(), 3);
+        assert_eq!(est.lines[0].model, "scribe_v2");
+        assert_eq!(est.lines[0].cost_micros, Some(400_000));
+        assert_eq!(est.lines[1].calls, 2);
+        assert_eq!(est.lines[1].prompt_tokens, 1_000_000);
+        assert_eq!(est.lines[1].cost_micros, Some(150_000 + 60_000));
+        // Self-hosted models have no list price.
+        assert_eq!(est.lines[2].cost_micros, None);
+        assert_eq!(est.total_micros, 610_000);
+        assert!((est.total_usd() - 0.61).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exact_model_prices_win_over_provider_wide_ones() {
+        let mut pricing = PricingSettings::default();
+        pricing.prices.push(ProviderPricing {
+            provider: "ElevenLabs".into(),
+            model: Some("scribe_v2_realtime".into()),
+            input_micros_per_million_tokens: 0,
+            output_micros_per_million_tokens: 0,
+            audio_micros_per_hour: 1_000_000,
+        });
+
+        let realtime = pricing
+            .price_for("elevenlabs", "scribe_v2_realtime")
+            .unwrap();
+        assert_eq!(realtime.audio_micros_per_hour, 1_000_000);
+        let batch = pricing.price_for("elevenlabs", "scribe_v2").unwrap();
+        assert_eq!(batch.audio_micros_per_hour, 400_000);
+        assert!(pricing.price_for("local", "ggml-base.bin").is_none());
+    }
+}
@@ -0,0 +1,70 @@
+// Hands-free activation: a short utterance heard while idle starts dictation when it
+// contains the wake phrase ("Hey VoiceWin").
+//
+// Matching runs on a transcript from the local model, so it only has to survive the ways
+// Whisper spells the phrase: case, punctuation and spacing ("Hey, Voice Win!").
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_WAKE_PHRASE: &str = "Hey VoiceWin";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WakeWordSettings {
+    /// Keep the microphone open while idle to listen for the phrase. Off unless the user
+    /// opts in; nothing heard is stored or sent anywhere.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Empty uses [`DEFAULT_WAKE_PHRASE`].
+    #[serde(default)]
+    pub phrase: String,
+}
+
+impl WakeWordSettings {
+    pub fn phrase(&self) -> &str {
+        match self.phrase.trim() {
+            "" => DEFAULT_WAKE_PHRASE,
+            p => p,
+        }
+    }
+}
+
+/// Whether `transcript` contains `phrase`, ignoring case, punctuation and spacing.
+pub fn heard_wake_phrase(transcript: &str, phrase: &str) -> bool {
+    let squash = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let phrase = squash(phrase);
+    !phrase.is_empty() && squash(transcript).contains(&phrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_ways_whisper_spells_the_phrase() {
+        let phrase = WakeWordSettings::default();
+        assert_eq!(phrase.phrase(), "Hey VoiceWin");
+        for heard in ["Hey VoiceWin.", " hey, voice win!", "Okay. Hey Voicewin"] {
+            assert!(heard_wake_phrase(heard, phrase.phrase()), "{heard}");
+        }
+        for heard in ["Hey Siri", "voice", "", "[BLANK_AUDIO]"] {
+            assert!(!heard_wake_phrase(heard, phrase.phrase()), "{heard}");
+        }
+    }
+
+    #[test]
+    fn custom_phrase_replaces_the_default() {
+        let s = WakeWordSettings {
+            enabled: true,
+            phrase: " Computer, take a note ".into(),
+        };
+        assert!(heard_wake_phrase("computer take a note", s.phrase()));
+        assert!(!heard_wake_phrase("Hey VoiceWin", s.phrase()));
+        assert!(!heard_wake_phrase("anything", "  ?! "));
+    }
+}
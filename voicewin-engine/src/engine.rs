@@ -1,18 +1,36 @@
-use crate::session::{SessionResult, SessionStage, ms};
-use crate::traits::{AppContextProvider, AudioInput, Inserter, LlmProvider, SttProvider};
+use crate::session::{SessionResult, SessionStage, SessionWebhookPayload, ms};
+use crate::traits::{
+    AppContextProvider, AudioInput, ContextSnapshot, EnhancedText, Inserter, LlmProvider,
+    ScratchpadSink, SttProvider, WebhookSink,
+};
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
+use tracing::Instrument;
+use voicewin_core::config::WebhookMode;
+use voicewin_core::diarization::format_speaker_turns;
 use voicewin_core::enhancement::{
-    EnhancementContext, PromptTemplate, build_enhancement_prompt, detect_trigger_word,
-    post_process_llm_output,
+    EnhancementContext, LlmDecodingSettings, PromptMode, PromptTemplate, Snippet,
+    build_enhancement_prompt, detect_trigger_word, expand_snippets, post_process_llm_output,
 };
+use voicewin_core::error::{ErrorKind, VoicewinError};
+use voicewin_core::guardrails::{OutputGuardrailSettings, check_enhancement_output};
+use voicewin_core::insert_command::{InsertCommandSettings, detect_insert_command};
+use voicewin_core::live_typing::untyped_suffix;
+use voicewin_core::note::{expand_note_path, format_note_entry};
 use voicewin_core::power_mode::{
-    EphemeralOverrides, GlobalDefaults, PowerModeProfile, resolve_effective_config,
+    EffectiveConfig, EphemeralOverrides, GlobalDefaults, PowerModeProfile, resolve_effective_config,
+};
+use voicewin_core::text::{
+    CasingProcessor, DisfluencyFilterProcessor, ReplacementsProcessor, SpokenReplacementSettings,
+    TextContext, TextProcessor, TextStage, adjust_for_insertion,
 };
-use voicewin_core::text::filter_transcription_output;
-use voicewin_core::types::InsertMode;
+use voicewin_core::types::{
+    AppIdentity, DisfluencyFilter, InsertMode, InsertTarget, InsertionStrategy, ProfileId, PromptId,
+};
+use voicewin_providers::error::classify_error;
 
 const STAGE_RECORDING: &str = "recording";
 const STAGE_TRANSCRIBING: &str = "transcribing";
@@ -34,6 +52,10 @@ pub struct EngineConfig {
 
     // LLM auth is currently global in MVP.
     pub llm_api_key: String,
+
+    pub spoken_replacements: SpokenReplacementSettings,
+    pub insert_commands: InsertCommandSettings,
+    pub snippets: Vec<Snippet>,
 }
 
 impl std::fmt::Debug for EngineConfig {
@@ -43,16 +65,61 @@ impl std::fmt::Debug for EngineConfig {
             .field("profiles", &self.profiles)
             .field("prompts", &self.prompts)
             .field("llm_api_key", &"[REDACTED]")
+            .field("spoken_replacements", &self.spoken_replacements)
+            .field("insert_commands", &self.insert_commands)
+            .field("snippets", &self.snippets)
             .finish()
     }
 }
 
+/// The processor run for each text stage. Stages without one (insert commands, snippets,
+/// triggers, the LLM) use the engine's built-in handling, since they change the session and
+/// not only the text.
+#[derive(Clone)]
+pub struct TextProcessorRegistry {
+    processors: HashMap<TextStage, Arc<dyn TextProcessor>>,
+}
+
+impl Default for TextProcessorRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            processors: HashMap::new(),
+        };
+        registry.register(
+            TextStage::DisfluencyFilter,
+            Arc::new(DisfluencyFilterProcessor),
+        );
+        registry.register(TextStage::Replacements, Arc::new(ReplacementsProcessor));
+        registry.register(TextStage::Casing, Arc::new(CasingProcessor));
+        registry
+    }
+}
+
+impl TextProcessorRegistry {
+    /// Run `processor` for `stage`, replacing any built-in behavior.
+    pub fn register(&mut self, stage: TextStage, processor: Arc<dyn TextProcessor>) {
+        self.processors.insert(stage, processor);
+    }
+
+    pub fn get(&self, stage: TextStage) -> Option<&Arc<dyn TextProcessor>> {
+        self.processors.get(&stage)
+    }
+}
+
 pub struct VoicewinEngine {
     cfg: EngineConfig,
+    processors: TextProcessorRegistry,
     context_provider: Arc<dyn AppContextProvider>,
     stt: Arc<dyn SttProvider>,
     llm: Arc<dyn LlmProvider>,
     inserter: Arc<dyn Inserter>,
+    webhook: Option<(Arc<dyn WebhookSink>, WebhookMode)>,
+    scratchpad: Option<Arc<dyn ScratchpadSink>>,
+    overrides: EphemeralOverrides,
+    guardrails: OutputGuardrailSettings,
+    decoding: LlmDecodingSettings,
+    live_typed: Option<String>,
+    target_app: Option<AppIdentity>,
 }
 
 impl VoicewinEngine {
@@ -65,13 +132,92 @@ impl VoicewinEngine {
     ) -> Self {
         Self {
             cfg,
+            processors: TextProcessorRegistry::default(),
             context_provider,
             stt,
             llm,
             inserter,
+            webhook: None,
+            scratchpad: None,
+            overrides: EphemeralOverrides::default(),
+            guardrails: OutputGuardrailSettings::default(),
+            decoding: LlmDecodingSettings::default(),
+            live_typed: None,
+            target_app: None,
+        }
+    }
+
+    /// Also deliver each successful session to `sink`, after or instead of inserting it.
+    pub fn with_webhook(mut self, sink: Arc<dyn WebhookSink>, mode: WebhookMode) -> Self {
+        self.webhook = Some((sink, mode));
+        self
+    }
+
+    /// Where `InsertTarget::Scratchpad` sessions go; without one they fail with the text kept.
+    pub fn with_scratchpad(mut self, sink: Arc<dyn ScratchpadSink>) -> Self {
+        self.scratchpad = Some(sink);
+        self
+    }
+
+    /// Use this profile whatever app is in front (switched by hotkey); `None` matches by app.
+    pub fn with_forced_profile(mut self, id: Option<ProfileId>) -> Self {
+        self.overrides.forced_profile_id = id;
+        self
+    }
+
+    /// Language, profile, prompt and enhancement choices for this session only.
+    pub fn with_overrides(mut self, overrides: EphemeralOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Checks Enhancer output must pass to replace the transcript.
+    pub fn with_output_guardrails(mut self, settings: OutputGuardrailSettings) -> Self {
+        self.guardrails = settings;
+        self
+    }
+
+    /// Global decoding settings; a prompt's own `decoding` overrides them field by field.
+    pub fn with_llm_decoding(mut self, settings: LlmDecodingSettings) -> Self {
+        self.decoding = settings;
+        self
+    }
+
+    /// Text already typed into the target app while recording (live typing). The transcript
+    /// is then kept as heard, and only the part after `typed` is typed at the end.
+    pub fn with_live_typed(mut self, typed: Option<String>) -> Self {
+        self.live_typed = typed;
+        self
+    }
+
+    /// The app that was in front when recording started. Power Mode and insertion use it
+    /// instead of whatever has focus by the time the pipeline runs.
+    pub fn with_target_app(mut self, app: Option<AppIdentity>) -> Self {
+        self.target_app = app;
+        self
+    }
+
+    async fn target_app(&self) -> anyhow::Result<AppIdentity> {
+        match &self.target_app {
+            Some(app) => Ok(app.clone()),
+            None => self.context_provider.foreground_app().await,
         }
     }
 
+    /// Run `processor` for `stage` instead of the built-in behavior.
+    pub fn with_text_processor(
+        mut self,
+        stage: TextStage,
+        processor: Arc<dyn TextProcessor>,
+    ) -> Self {
+        self.processors.register(stage, processor);
+        self
+    }
+
+    fn ephemeral_overrides(&self) -> EphemeralOverrides {
+        self.overrides.clone()
+    }
+
     /// Runs the full pipeline (transcribe -> optional enhance -> insert).
     pub async fn run_session(&self, audio: AudioInput) -> anyhow::Result<SessionResult> {
         self.run_session_with_hook(audio, |_stage| async {}).await
@@ -89,14 +235,9 @@ impl VoicewinEngine {
         F: Fn(&'static str) -> Fut,
         Fut: Future<Output = ()>,
     {
-        let app = self.context_provider.foreground_app().await?;
-        let ctx_snapshot = self
-            .context_provider
-            .snapshot_context()
-            .await
-            .unwrap_or_default();
+        let app = self.target_app().await?;
 
-        let ephemeral = EphemeralOverrides::default();
+        let ephemeral = self.ephemeral_overrides();
         let eff =
             resolve_effective_config(&self.cfg.defaults, &self.cfg.profiles, &app, &ephemeral);
 
@@ -106,7 +247,7 @@ impl VoicewinEngine {
             eff.clone(),
             String::new(),
             eff.insert_mode,
-            ctx_snapshot.clone(),
+            ContextSnapshot::default(),
         );
 
         // 0) Recording (performed by caller)
@@ -119,12 +260,25 @@ impl VoicewinEngine {
         result.stage_label = Some(STAGE_TRANSCRIBING.into());
         on_stage(STAGE_TRANSCRIBING).await;
 
-        let t0 = Instant::now();
-        let transcript = self
-            .stt
-            .transcribe(&audio, &eff.stt_provider, &eff.stt_model, &eff.language)
-            .await?;
-        let transcription_ms = ms(t0.elapsed());
+        let transcribe = async {
+            let t0 = Instant::now();
+            let transcript = self
+                .stt
+                .transcribe(&audio, &eff.stt_provider, &eff.stt_model, &eff.language)
+                .instrument(tracing::info_span!(
+                    "transcribe",
+                    provider = %eff.stt_provider,
+                    model = %eff.stt_model
+                ))
+                .await;
+            (transcript, ms(t0.elapsed()))
+        };
+
+        // Context capture (clipboard, selection, window text) overlaps with STT instead of
+        // delaying it.
+        let ((transcript, transcription_ms), ctx_snapshot) =
+            tokio::join!(transcribe, self.snapshot_context_for(&eff));
+        let transcript = transcript?;
 
         self.run_post_stt_pipeline(result, eff, ctx_snapshot, transcript, Some(transcription_ms), on_stage)
             .await
@@ -142,16 +296,12 @@ impl VoicewinEngine {
         F: Fn(&'static str) -> Fut,
         Fut: Future<Output = ()>,
     {
-        let app = self.context_provider.foreground_app().await?;
-        let ctx_snapshot = self
-            .context_provider
-            .snapshot_context()
-            .await
-            .unwrap_or_default();
+        let app = self.target_app().await?;
 
-        let ephemeral = EphemeralOverrides::default();
+        let ephemeral = self.ephemeral_overrides();
         let eff =
             resolve_effective_config(&self.cfg.defaults, &self.cfg.profiles, &app, &ephemeral);
+        let ctx_snapshot = self.snapshot_context_for(&eff).await;
 
         let mut result = SessionResult::success(
             app.clone(),
@@ -173,17 +323,103 @@ impl VoicewinEngine {
             text: transcript_text,
             provider: eff.stt_provider.clone(),
             model: eff.stt_model.clone(),
+            segments: vec![],
         };
 
         self.run_post_stt_pipeline(result, eff, ctx_snapshot, transcript, None, on_stage)
             .await
     }
 
+    /// Context for the enhancement prompt. Skipped entirely when this session cannot be
+    /// enhanced (no LLM key or LLM stage, enhancement off and no trigger words configured).
+    async fn snapshot_context_for(&self, eff: &EffectiveConfig) -> ContextSnapshot {
+        let has_trigger_words = self
+            .cfg
+            .prompts
+            .iter()
+            .any(|p| p.trigger_words.iter().any(|w| !w.trim().is_empty()));
+        let may_enhance = !self.cfg.llm_api_key.trim().is_empty()
+            && eff.text_pipeline.contains(TextStage::Llm)
+            && (eff.enable_enhancement || has_trigger_words);
+        if !may_enhance {
+            return ContextSnapshot::default();
+        }
+
+        self.context_provider
+            .snapshot_context()
+            .instrument(tracing::info_span!("context"))
+            .await
+            .unwrap_or_default()
+    }
+
+    /// The prompt with `prompt_id`, or the first one when it is unset or gone.
+    fn select_prompt(&self, prompt_id: Option<&PromptId>) -> Option<&PromptTemplate> {
+        prompt_id
+            .and_then(|id| self.cfg.prompts.iter().find(|p| &p.id == id))
+            .or_else(|| self.cfg.prompts.first())
+    }
+
+    /// Enhance `text` with the selected prompt (or the first one). The outer error is a
+    /// configuration problem that fails the session; the inner one is the LLM call, after
+    /// which the unenhanced text is inserted.
+    async fn enhance(
+        &self,
+        text: &str,
+        prompt_id: Option<&PromptId>,
+        eff: &EffectiveConfig,
+        ctx_snapshot: &ContextSnapshot,
+    ) -> anyhow::Result<anyhow::Result<EnhancedText>> {
+        let prompt = self
+            .select_prompt(prompt_id)
+            .ok_or(EngineError::NoDefaultPrompt)?;
+
+        let ctx = EnhancementContext {
+            clipboard_context: eff
+                .context
+                .use_clipboard
+                .then(|| ctx_snapshot.clipboard.clone())
+                .flatten(),
+            currently_selected_text: eff
+                .context
+                .use_selected_text
+                .then(|| ctx_snapshot.selected_text.clone())
+                .flatten(),
+            current_window_context: eff
+                .context
+                .use_window_context
+                .then(|| ctx_snapshot.window_context.clone())
+                .flatten(),
+            custom_vocabulary: eff
+                .context
+                .use_custom_vocabulary
+                .then(|| ctx_snapshot.custom_vocabulary.clone())
+                .flatten(),
+            output_format: eff.output_format,
+            disfluency_filter: eff.disfluency_filter,
+        };
+
+        let built = build_enhancement_prompt(text, prompt, &ctx);
+        let decoding = prompt.decoding.or(&self.decoding);
+
+        Ok(self
+            .llm
+            .enhance_with_decoding(
+                &eff.llm_base_url,
+                &self.cfg.llm_api_key,
+                &eff.llm_model,
+                &built.system_message,
+                &built.user_message,
+                &decoding,
+            )
+            .instrument(tracing::info_span!("enhance", model = %eff.llm_model))
+            .await)
+    }
+
     async fn run_post_stt_pipeline<F, Fut>(
         &self,
         mut result: SessionResult,
-        eff: voicewin_core::power_mode::EffectiveConfig,
-        ctx_snapshot: crate::traits::ContextSnapshot,
+        mut eff: EffectiveConfig,
+        ctx_snapshot: ContextSnapshot,
         transcript: crate::traits::Transcript,
         transcription_ms: Option<u64>,
         on_stage: F,
@@ -192,99 +428,161 @@ impl VoicewinEngine {
         F: Fn(&'static str) -> Fut,
         Fut: Future<Output = ()>,
     {
-        let mut final_text = filter_transcription_output(&transcript.text);
-
+        result.context = ctx_snapshot.clone();
+        let pipeline = eff.text_pipeline.clone();
+
+        // Diarized transcripts become one "Speaker N: ..." line per turn before enhancement.
+        // The turns are filtered as they are formatted.
+        let turn_filter = if pipeline.contains(TextStage::DisfluencyFilter) {
+            eff.disfluency_filter
+        } else {
+            DisfluencyFilter::Off
+        };
+        let diarized = format_speaker_turns(&transcript.segments, turn_filter);
+        let is_diarized = diarized.is_some();
+        let mut final_text = diarized.unwrap_or_else(|| transcript.text.clone());
         if final_text.trim().is_empty() {
-            result.stage = SessionStage::Failed;
-            result.stage_label = Some("failed".into());
-            result.transcript = Some(transcript);
-            result.timings.transcription_ms = transcription_ms;
-            result.error = Some(
-                "No speech detected. Try speaking louder or selecting the correct microphone.".into(),
-            );
-            return Ok(result);
+            return Ok(no_speech(result, transcript, transcription_ms));
         }
 
         let has_llm_key = !self.cfg.llm_api_key.trim().is_empty();
-
-        // Trigger word prompt override (VoiceInk behavior)
         let mut prompt_id = eff.prompt_id.clone();
-        let detection = detect_trigger_word(&final_text, &self.cfg.prompts);
-        if has_llm_key && detection.should_enable_enhancement {
-            final_text = detection.processed_transcript;
-            prompt_id = detection.selected_prompt_id;
-        }
-
+        let mut triggered = false;
         let mut enhanced = None;
         let mut enhancement_ms = None;
 
-        let wants_enhancement = eff.enable_enhancement || detection.should_enable_enhancement;
-        if wants_enhancement && has_llm_key {
-            result.stage = SessionStage::Enhancing;
-            result.stage_label = Some(STAGE_ENHANCING.into());
-            on_stage(STAGE_ENHANCING).await;
-
-            let selected = prompt_id
-                .as_ref()
-                .and_then(|id| self.cfg.prompts.iter().find(|p| &p.id == id))
-                .or_else(|| self.cfg.prompts.first());
-
-            let prompt = selected.ok_or(EngineError::NoDefaultPrompt)?;
-
-            let ctx = EnhancementContext {
-                clipboard_context: eff
-                    .context
-                    .use_clipboard
-                    .then(|| ctx_snapshot.clipboard.clone())
-                    .flatten(),
-                currently_selected_text: eff
-                    .context
-                    .use_selected_text
-                    .then(|| ctx_snapshot.selected_text.clone())
-                    .flatten(),
-                current_window_context: eff
-                    .context
-                    .use_window_context
-                    .then(|| ctx_snapshot.window_context.clone())
-                    .flatten(),
-                custom_vocabulary: eff
-                    .context
-                    .use_custom_vocabulary
-                    .then(|| ctx_snapshot.custom_vocabulary.clone())
-                    .flatten(),
-            };
+        // Live-typed text is already on screen as heard; nothing may rewrite it now.
+        let stages = if self.live_typed.is_some() {
+            &[][..]
+        } else {
+            pipeline.stages()
+        };
+        for &stage in stages {
+            if stage == TextStage::DisfluencyFilter && is_diarized {
+                continue;
+            }
 
-            let built = build_enhancement_prompt(&final_text, prompt, &ctx);
-
-            let e0 = Instant::now();
-            match self
-                .llm
-                .enhance(
-                    &eff.llm_base_url,
-                    &self.cfg.llm_api_key,
-                    &eff.llm_model,
-                    &built.system_message,
-                    &built.user_message,
-                )
-                .await
-            {
-                Ok(llm_out) => {
-                    enhancement_ms = Some(ms(e0.elapsed()));
-                    let cleaned = post_process_llm_output(&llm_out.text);
-                    final_text = cleaned;
-                    enhanced = Some(llm_out);
-                }
-                Err(e) => {
-                    let mut msg = e.to_string();
-                    if msg.len() > 140 {
-                        msg.truncate(140);
-                        msg.push_str("...");
+            if let Some(processor) = self.processors.get(stage) {
+                let cx = TextContext {
+                    output_format: eff.output_format,
+                    disfluency_filter: eff.disfluency_filter,
+                    language: &eff.language,
+                    spoken_replacements: &self.cfg.spoken_replacements,
+                    basic_cleanup: eff.basic_cleanup,
+                    enhanced: enhanced.is_some(),
+                };
+                final_text = processor.process(&final_text, &cx);
+            } else {
+                match stage {
+                    // A trailing "send it" / "just copy" changes how this session's text is
+                    // inserted.
+                    TextStage::InsertCommands => {
+                        let command = detect_insert_command(&final_text, &self.cfg.insert_commands);
+                        if let Some(mode) = command.insert_mode {
+                            final_text = command.processed_transcript;
+                            let ephemeral = EphemeralOverrides {
+                                insert_mode: Some(mode),
+                                ..self.ephemeral_overrides()
+                            };
+                            eff = resolve_effective_config(
+                                &self.cfg.defaults,
+                                &self.cfg.profiles,
+                                &result.app,
+                                &ephemeral,
+                            );
+                            result.config = eff.clone();
+                            result.insert_mode = mode;
+                        }
+                    }
+                    // "insert my address" expands to the stored snippet. A bare snippet command
+                    // is inserted verbatim: the remaining stages are skipped.
+                    TextStage::Snippets => {
+                        let expansion = expand_snippets(&final_text, &self.cfg.snippets);
+                        final_text = expansion.processed_transcript;
+                        if expansion.whole_utterance {
+                            break;
+                        }
                     }
-                    result.error = Some(format!(
-                        "Enhancement failed; inserted raw transcript. ({msg})"
-                    ));
+                    // Trigger word prompt override (VoiceInk behavior)
+                    TextStage::Triggers => {
+                        let detection = detect_trigger_word(&final_text, &self.cfg.prompts);
+                        triggered = detection.should_enable_enhancement;
+                        if has_llm_key && triggered {
+                            final_text = detection.processed_transcript;
+                            prompt_id = detection.selected_prompt_id;
+                        }
+                    }
+                    TextStage::Llm => {
+                        if !(eff.enable_enhancement || triggered) || !has_llm_key {
+                            continue;
+                        }
+                        result.stage = SessionStage::Enhancing;
+                        result.stage_label = Some(STAGE_ENHANCING.into());
+                        on_stage(STAGE_ENHANCING).await;
+
+                        let e0 = Instant::now();
+                        result.prompt_id =
+                            self.select_prompt(prompt_id.as_ref()).map(|p| p.id.clone());
+                        match self
+                            .enhance(&final_text, prompt_id.as_ref(), &eff, &ctx_snapshot)
+                            .await?
+                        {
+                            Ok(llm_out) => {
+                                enhancement_ms = Some(ms(e0.elapsed()));
+                                let processed = post_process_llm_output(&llm_out.text);
+                                // Assistant prompts answer the dictation by design.
+                                let is_enhancer = self
+                                    .select_prompt(prompt_id.as_ref())
+                                    .is_some_and(|p| p.mode == PromptMode::Enhancer);
+                                let violation = is_enhancer
+                                    .then(|| {
+                                        check_enhancement_output(
+                                            &final_text,
+                                            &processed,
+                                            &self.guardrails,
+                                        )
+                                    })
+                                    .flatten();
+                                match violation {
+                                    Some(v) => {
+                                        result.error = Some(VoicewinError::new(
+                                            ErrorKind::Provider,
+                                            format!(
+                                                "Enhancement output rejected ({}); inserted raw transcript.",
+                                                v.reason()
+                                            ),
+                                        ));
+                                    }
+                                    None => final_text = processed,
+                                }
+                                // Kept even when rejected: the tokens were billed, and the
+                                // details window shows what the model returned.
+                                enhanced = Some(llm_out);
+                            }
+                            Err(e) => {
+                                let mut msg = e.to_string();
+                                if msg.len() > 140 {
+                                    msg.truncate(140);
+                                    msg.push_str("...");
+                                }
+                                result.error = Some(
+                                    classify_error(&e, ErrorKind::Provider).with_user_message(
+                                        format!(
+                                            "Enhancement failed; inserted raw transcript. ({msg})"
+                                        ),
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    // No processor registered for a pure stage: nothing to do.
+                    TextStage::DisfluencyFilter | TextStage::Replacements | TextStage::Casing => {}
                 }
             }
+
+            if stage == TextStage::DisfluencyFilter && final_text.trim().is_empty() {
+                return Ok(no_speech(result, transcript, transcription_ms));
+            }
         }
 
         result.final_text = Some(final_text.clone());
@@ -294,14 +592,139 @@ impl VoicewinEngine {
         on_stage(STAGE_INSERTING).await;
 
         let mode: InsertMode = eff.insert_mode;
-        if let Err(e) = self.inserter.insert(&final_text, mode).await {
+        let inserts = !matches!(self.webhook, Some((_, WebhookMode::InsteadOfInsert)));
+        let insert = async {
+            // The webhook is the only output sink.
+            if !inserts {
+                return Ok(());
+            }
+
+            // Focus may have moved while transcribing/enhancing; paste into the window the
+            // user was dictating into (or the profile's target app), or fail with the text
+            // kept for recovery. Journaling profiles write to a note file (or the scratchpad)
+            // instead and never touch a window or the clipboard.
+            match &eff.insert_target {
+                InsertTarget::NoteFile(path) => {
+                    return append_to_note(path, &final_text)
+                        .instrument(tracing::info_span!("append_note", chars = final_text.len()))
+                        .await;
+                }
+                InsertTarget::Scratchpad => {
+                    let sink = self.scratchpad.as_ref().ok_or_else(|| {
+                        anyhow::anyhow!("The scratchpad is not available; the text was not saved.")
+                    })?;
+                    return sink
+                        .append(&final_text, &result.app)
+                        .instrument(tracing::info_span!("scratchpad", chars = final_text.len()))
+                        .await;
+                }
+                _ if mode == InsertMode::ClipboardOnly => {}
+                InsertTarget::Foreground => {
+                    if let Err(e) = self.inserter.focus_target(&result.app).await {
+                        // Pasting now would land in whatever took focus; the clipboard is the
+                        // next best place for the text.
+                        if classify_error(&e, ErrorKind::Insertion).kind == ErrorKind::TargetClosed
+                            && self
+                                .inserter
+                                .insert(&final_text, InsertMode::ClipboardOnly)
+                                .await
+                                .is_ok()
+                        {
+                            return Err(VoicewinError::new(
+                                ErrorKind::TargetClosed,
+                                "The window you were dictating into closed; the text was copied to the clipboard.",
+                            )
+                            .into());
+                        }
+                        return Err(e);
+                    }
+                }
+                InsertTarget::App(name) => self.inserter.focus_app(name).await?,
+            }
+
+            // Fit the text to what's already before the caret (separating space, lowercase
+            // continuation). History keeps the unadjusted text.
+            let before_caret = match mode {
+                // Nothing is typed at the caret, so there is nothing to fit to.
+                InsertMode::ClipboardOnly => None,
+                _ => self
+                    .context_provider
+                    .text_before_caret()
+                    .await
+                    .ok()
+                    .flatten(),
+            };
+            let (to_insert, strategy) = match self.live_typed.as_deref() {
+                // Keep typing where live typing left off.
+                Some(typed) => (
+                    untyped_suffix(typed, &final_text).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "The final transcript differs from what was typed live; the full text is in History."
+                        )
+                    })?,
+                    InsertionStrategy::TypeKeys,
+                ),
+                None => (
+                    adjust_for_insertion(&final_text, before_caret.as_deref()),
+                    eff.insertion_strategy,
+                ),
+            };
+
+            let insert_span = tracing::info_span!(
+                "insert",
+                mode = ?mode,
+                strategy = ?strategy,
+                chars = to_insert.len()
+            );
+            match (strategy, eff.paste_keys) {
+                _ if to_insert.is_empty() => {}
+                // Copying to the clipboard is the same whatever the strategy.
+                _ if mode == InsertMode::ClipboardOnly => {
+                    self.inserter
+                        .insert(&to_insert, mode)
+                        .instrument(insert_span)
+                        .await?
+                }
+                (InsertionStrategy::ClipboardPaste, Some(keys)) => {
+                    self.inserter
+                        .paste_with_keys(&to_insert, mode, keys)
+                        .instrument(insert_span)
+                        .await?
+                }
+                (InsertionStrategy::ClipboardPaste, None) => {
+                    self.inserter
+                        .insert(&to_insert, mode)
+                        .instrument(insert_span)
+                        .await?
+                }
+                _ => {
+                    self.inserter
+                        .insert_with_strategy(&to_insert, mode, strategy)
+                        .instrument(insert_span)
+                        .await?
+                }
+            }
+
+            // Only a paste puts the caret in a field the macro is meant for.
+            if mode != InsertMode::ClipboardOnly && !eff.post_insert_keys.is_empty() {
+                self.inserter
+                    .send_keys(&eff.post_insert_keys)
+                    .instrument(tracing::info_span!("post_insert_keys"))
+                    .await
+                    .map_err(|e| {
+                        anyhow::anyhow!("Text was inserted, but the key macro failed: {e}")
+                    })?;
+            }
+            Ok(())
+        };
+        if let Err(e) = insert.await {
             result.stage = SessionStage::Failed;
             result.stage_label = Some("failed".into());
             result.transcript = Some(transcript);
             result.enhanced = enhanced;
             result.timings.transcription_ms = transcription_ms;
             result.timings.enhancement_ms = enhancement_ms;
-            result.error = Some(e.to_string());
+            result.error = Some(classify_error(&e, ErrorKind::Insertion));
             return Ok(result);
         }
 
@@ -311,6 +734,81 @@ impl VoicewinEngine {
         result.enhanced = enhanced;
         result.timings.transcription_ms = transcription_ms;
         result.timings.enhancement_ms = enhancement_ms;
+
+        if let Some((sink, mode)) = &self.webhook {
+            let payload = SessionWebhookPayload::from_result(&result, inserts);
+            if let Err(e) = sink
+                .deliver(&payload)
+                .instrument(tracing::info_span!("webhook"))
+                .await
+            {
+                let msg = format!("Webhook delivery failed. ({e})");
+                if *mode == WebhookMode::InsteadOfInsert {
+                    // Nothing else received the text; fail so it is kept for recovery.
+                    result.stage = SessionStage::Failed;
+                    result.stage_label = Some("failed".into());
+                    result.error =
+                        Some(classify_error(&e, ErrorKind::Network).with_user_message(msg));
+                } else {
+                    result.error = Some(match result.error.take() {
+                        Some(existing) => {
+                            let combined = format!("{} | {msg}", existing.user_message);
+                            existing.with_user_message(combined)
+                        }
+                        None => classify_error(&e, ErrorKind::Network).with_user_message(msg),
+                    });
+                }
+            }
+        }
         Ok(result)
     }
 }
+
+/// The transcript (after filtering) has no words; nothing is enhanced or inserted.
+fn no_speech(
+    mut result: SessionResult,
+    transcript: crate::traits::Transcript,
+    transcription_ms: Option<u64>,
+) -> SessionResult {
+    result.stage = SessionStage::Failed;
+    result.stage_label = Some("failed".into());
+    result.transcript = Some(transcript);
+    result.timings.transcription_ms = transcription_ms;
+    result.error = Some(VoicewinError::new(
+        ErrorKind::NoSpeech,
+        "No speech detected. Try speaking louder or selecting the correct microphone.",
+    ));
+    result
+}
+
+/// Append a timestamped entry to the profile's note file, creating it (and its folder) if
+/// needed.
+async fn append_to_note(path_template: &str, text: &str) -> anyhow::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    let path = expand_note_path(path_template, now_ms);
+    if path.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No note file is configured for this profile; the text was not saved."
+        ));
+    }
+    let path = std::path::PathBuf::from(path);
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to open note file {}: {e}", path.display()))?;
+    file.write_all(format_note_entry(text, now_ms).as_bytes())
+        .await?;
+    file.flush().await?;
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use crate::traits::{ContextSnapshot, EnhancedText, Transcript};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use voicewin_core::error::VoicewinError;
 use voicewin_core::power_mode::EffectiveConfig;
-use voicewin_core::types::{AppIdentity, InsertMode};
+use voicewin_core::types::{AppIdentity, InsertMode, PromptId};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SessionStage {
@@ -42,13 +43,17 @@ pub struct SessionResult {
     pub transcript: Option<Transcript>,
     pub enhanced: Option<EnhancedText>,
 
+    // The prompt sent to the LLM, when enhancement ran.
+    #[serde(default)]
+    pub prompt_id: Option<PromptId>,
+
     // The best final text we have, even if insertion fails.
     pub final_text: Option<String>,
 
     pub insert_mode: InsertMode,
     pub context: ContextSnapshot,
     pub timings: SessionTimings,
-    pub error: Option<String>,
+    pub error: Option<VoicewinError>,
 }
 
 impl SessionResult {
@@ -66,6 +71,7 @@ impl SessionResult {
             config,
             transcript: None,
             enhanced: None,
+            prompt_id: None,
             final_text: Some(final_text),
             insert_mode: mode,
             context: ctx,
@@ -78,7 +84,7 @@ impl SessionResult {
         app: AppIdentity,
         config: EffectiveConfig,
         ctx: ContextSnapshot,
-        error: impl Into<String>,
+        error: impl Into<VoicewinError>,
     ) -> Self {
         let insert_mode = config.insert_mode;
         Self {
@@ -88,6 +94,7 @@ impl SessionResult {
             config,
             transcript: None,
             enhanced: None,
+            prompt_id: None,
             final_text: None,
             insert_mode,
             context: ctx,
@@ -97,6 +104,38 @@ impl SessionResult {
     }
 }
 
+/// What a webhook receives for a finished session. Context snapshots (clipboard, selection)
+/// are deliberately left out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionWebhookPayload {
+    pub final_text: String,
+    pub transcript: Option<String>,
+    pub enhanced: bool,
+    pub app: AppIdentity,
+    pub profile: Option<String>,
+    pub insert_mode: InsertMode,
+    /// Whether the text was also inserted (false when the webhook replaces insertion).
+    pub inserted: bool,
+    pub timings: SessionTimings,
+    pub warning: Option<String>,
+}
+
+impl SessionWebhookPayload {
+    pub fn from_result(result: &SessionResult, inserted: bool) -> Self {
+        Self {
+            final_text: result.final_text.clone().unwrap_or_default(),
+            transcript: result.transcript.as_ref().map(|t| t.text.clone()),
+            enhanced: result.enhanced.is_some(),
+            app: result.app.clone(),
+            profile: result.config.matched_profile_name.clone(),
+            insert_mode: result.insert_mode,
+            inserted,
+            timings: result.timings.clone(),
+            warning: result.error.as_ref().map(|e| e.user_message.clone()),
+        }
+    }
+}
+
 pub fn ms(d: Duration) -> u64 {
     d.as_millis().try_into().unwrap_or(u64::MAX)
 }
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use voicewin_core::diarization::SpeakerSegment;
 use voicewin_core::types::AppIdentity;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -15,6 +16,10 @@ pub struct Transcript {
     pub text: String,
     pub provider: String,
     pub model: String,
+
+    /// Per-speaker segments when the provider diarized the audio; empty otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<SpeakerSegment>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,6 +27,10 @@ pub struct EnhancedText {
     pub text: String,
     pub provider: String,
     pub model: String,
+
+    /// Billed tokens, when the endpoint reports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<voicewin_core::usage::TokenUsage>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -36,6 +45,12 @@ pub struct ContextSnapshot {
 pub trait AppContextProvider: Send + Sync {
     async fn foreground_app(&self) -> anyhow::Result<AppIdentity>;
     async fn snapshot_context(&self) -> anyhow::Result<ContextSnapshot>;
+
+    /// A few characters immediately before the caret in the focused field, when the platform
+    /// can read them. Used to fit spacing/casing of the inserted text.
+    async fn text_before_caret(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
 }
 
 #[async_trait]
@@ -59,6 +74,21 @@ pub trait LlmProvider: Send + Sync {
         system_message: &str,
         user_message: &str,
     ) -> anyhow::Result<EnhancedText>;
+
+    /// [`Self::enhance`] with the prompt's decoding settings. Providers without decoding
+    /// controls ignore them.
+    async fn enhance_with_decoding(
+        &self,
+        base_url: &str,
+        api_key: &str,
+        model: &str,
+        system_message: &str,
+        user_message: &str,
+        _decoding: &voicewin_core::enhancement::LlmDecodingSettings,
+    ) -> anyhow::Result<EnhancedText> {
+        self.enhance(base_url, api_key, model, system_message, user_message)
+            .await
+    }
 }
 
 #[async_trait]
@@ -68,4 +98,74 @@ pub trait Inserter: Send + Sync {
         text: &str,
         mode: voicewin_core::types::InsertMode,
     ) -> anyhow::Result<()>;
+
+    /// Insert with a strategy other than the default paste (a profile's
+    /// `insertion_strategy`). Platforms implement the ones they support; the rest fail
+    /// rather than quietly pasting into an app the user pinned away from pasting.
+    async fn insert_with_strategy(
+        &self,
+        text: &str,
+        mode: voicewin_core::types::InsertMode,
+        strategy: voicewin_core::types::InsertionStrategy,
+    ) -> anyhow::Result<()> {
+        match strategy {
+            voicewin_core::types::InsertionStrategy::ClipboardPaste => {
+                self.insert(text, mode).await
+            }
+            other => Err(unsupported_strategy(other)),
+        }
+    }
+
+    /// Paste with an app's own paste shortcut (a profile's `paste_keys`) instead of the
+    /// platform's Ctrl+V / Cmd+V.
+    async fn paste_with_keys(
+        &self,
+        _text: &str,
+        _mode: voicewin_core::types::InsertMode,
+        _keys: voicewin_core::key_macro::KeyChord,
+    ) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "Custom paste shortcuts are not supported on this platform."
+        ))
+    }
+
+    /// Bring the window that was focused when the session started back to the front. Fails
+    /// when it is gone or can't be activated, so text never lands in a different app.
+    async fn focus_target(&self, _app: &AppIdentity) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Find a running app by process name and focus its main window (dictation routing).
+    async fn focus_app(&self, process_name: &str) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "Sending dictation to {process_name} is not supported on this platform."
+        ))
+    }
+
+    /// Press a profile's post-insert key macro in the focused window.
+    async fn send_keys(&self, _keys: &[voicewin_core::key_macro::KeyChord]) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "Key macros after pasting are not supported on this platform."
+        ))
+    }
+}
+
+/// The error for an `insertion_strategy` the platform can't do.
+pub fn unsupported_strategy(strategy: voicewin_core::types::InsertionStrategy) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} insertion is not supported on this platform; change the profile's insertion strategy.",
+        strategy.label()
+    )
+}
+
+/// Keeps dictations sent to `InsertTarget::Scratchpad`.
+#[async_trait]
+pub trait ScratchpadSink: Send + Sync {
+    async fn append(&self, text: &str, app: &AppIdentity) -> anyhow::Result<()>;
+}
+
+/// Delivers finished sessions to an external endpoint (see `WebhookSettings`).
+#[async_trait]
+pub trait WebhookSink: Send + Sync {
+    async fn deliver(&self, payload: &crate::session::SessionWebhookPayload) -> anyhow::Result<()>;
 }
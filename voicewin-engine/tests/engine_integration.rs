@@ -1,6 +1,8 @@
 use std::sync::Arc;
 use voicewin_core::enhancement::{PromptMode, PromptTemplate};
+use voicewin_core::error::ErrorKind;
 use voicewin_core::power_mode::{GlobalDefaults, PowerModeOverrides, PowerModeProfile};
+use voicewin_core::text::{TextContext, TextPipeline, TextProcessor, TextStage};
 use voicewin_core::types::{AppIdentity, InsertMode, ProfileId, PromptId};
 use voicewin_engine::engine::{EngineConfig, VoicewinEngine};
 use voicewin_engine::traits::{
@@ -55,6 +57,31 @@ impl SttProvider for TestStt {
             text: "rewrite um hello world rewrite".into(),
             provider: provider.into(),
             model: model.into(),
+            segments: vec![],
+        })
+    }
+}
+
+struct DiarizedStt;
+
+#[async_trait::async_trait]
+impl SttProvider for DiarizedStt {
+    async fn transcribe(
+        &self,
+        _audio: &AudioInput,
+        provider: &str,
+        model: &str,
+        _language: &str,
+    ) -> anyhow::Result<Transcript> {
+        let seg = |speaker, text: &str| voicewin_core::diarization::SpeakerSegment {
+            speaker,
+            text: text.into(),
+        };
+        Ok(Transcript {
+            text: "Ship it Friday? Yes, Friday.".into(),
+            provider: provider.into(),
+            model: model.into(),
+            segments: vec![seg(1, "Ship it Friday?"), seg(2, "Yes, Friday.")],
         })
     }
 }
@@ -90,6 +117,8 @@ impl LlmProvider for OpenAiCompatibleLlm {
             base_url: base_url.to_string(),
             api_key: api_key.to_string(),
             model: model.to_string(),
+            decoding: Default::default(),
+            flavor: Default::default(),
         };
 
         let messages = vec![
@@ -115,6 +144,7 @@ impl LlmProvider for OpenAiCompatibleLlm {
             text,
             provider: "openai-compatible".into(),
             model: model.into(),
+            usage: None,
         })
     }
 }
@@ -135,6 +165,41 @@ impl LlmProvider for PanicLlm {
     }
 }
 
+/// Realtime ElevenLabs STT, no enhancement; tests override only the fields they exercise.
+fn global_defaults() -> GlobalDefaults {
+    GlobalDefaults {
+        enable_enhancement: false,
+        prompt_id: None,
+        insert_mode: InsertMode::Paste,
+        stt_provider: "elevenlabs".into(),
+        stt_model: "scribe_v2_realtime".into(),
+        language: "en".into(),
+        llm_base_url: "https://api.example.com/v1".into(),
+        llm_model: "gpt-4o-mini".into(),
+        microphone_device: None,
+        history_enabled: true,
+        basic_cleanup: false,
+        output_format: Default::default(),
+        disfluency_filter: Default::default(),
+        text_pipeline: Default::default(),
+        realtime_vad: Default::default(),
+        context: voicewin_core::context::ContextToggles::default(),
+    }
+}
+
+/// No profiles, prompts, LLM key, replacements or snippets.
+fn engine_config(defaults: GlobalDefaults) -> EngineConfig {
+    EngineConfig {
+        defaults,
+        profiles: vec![],
+        prompts: vec![],
+        llm_api_key: "".into(),
+        spoken_replacements: Default::default(),
+        insert_commands: Default::default(),
+        snippets: vec![],
+    }
+}
+
 #[tokio::test]
 async fn end_to_end_session_uses_power_mode_and_llm() {
     let server = MockServer::start().await;
@@ -150,16 +215,10 @@ async fn end_to_end_session_uses_power_mode_and_llm() {
 
     let defaults = GlobalDefaults {
         enable_enhancement: true,
-        prompt_id: None,
-        insert_mode: InsertMode::Paste,
         stt_provider: "local".into(),
         stt_model: "mock".into(),
-        language: "en".into(),
         llm_base_url: server.uri(),
-        llm_model: "gpt-4o-mini".into(),
-        microphone_device: None,
-        history_enabled: true,
-        context: voicewin_core::context::ContextToggles::default(),
+        ..global_defaults()
     };
 
     let profile = PowerModeProfile {
@@ -169,28 +228,31 @@ async fn end_to_end_session_uses_power_mode_and_llm() {
         matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
             "slack.exe".into(),
         )],
+        auto_dictate: Default::default(),
         overrides: PowerModeOverrides {
             insert_mode: Some(InsertMode::PasteAndEnter),
             ..Default::default()
         },
     };
 
+    let prompt_id = PromptId::new();
     let prompts = vec![PromptTemplate {
-        id: PromptId::new(),
+        id: prompt_id.clone(),
         title: "Rewrite".into(),
         mode: PromptMode::Enhancer,
         prompt_text: "Clean up.".into(),
         trigger_words: vec!["rewrite".into()],
+        decoding: Default::default(),
     }];
 
     let inserted = Arc::new(std::sync::Mutex::new(vec![]));
 
     let engine = VoicewinEngine::new(
         EngineConfig {
-            defaults,
             profiles: vec![profile],
             prompts,
             llm_api_key: "k".into(),
+            ..engine_config(defaults)
         },
         Arc::new(TestContext),
         Arc::new(TestStt),
@@ -207,6 +269,9 @@ async fn end_to_end_session_uses_power_mode_and_llm() {
 
     let res = engine.run_session(audio).await.unwrap();
     assert_eq!(res.final_text.as_deref(), Some("Hello, world."));
+    assert_eq!(res.context.clipboard.as_deref(), Some("VOICE-123"));
+    // The details window shows which prompt rewrote the transcript.
+    assert_eq!(res.prompt_id, Some(prompt_id));
 
     let inserted = inserted.lock().unwrap();
     assert_eq!(inserted.len(), 1);
@@ -214,21 +279,65 @@ async fn end_to_end_session_uses_power_mode_and_llm() {
     assert_eq!(inserted[0].1, InsertMode::PasteAndEnter);
 }
 
+#[tokio::test]
+async fn forced_profile_applies_whatever_app_is_in_front() {
+    let defaults = GlobalDefaults {
+        stt_provider: "local".into(),
+        stt_model: "mock".into(),
+        ..global_defaults()
+    };
+    let profile = |name: &str, process: &str, mode| PowerModeProfile {
+        id: ProfileId::new(),
+        name: name.into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            process.into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            insert_mode: Some(mode),
+            ..Default::default()
+        },
+    };
+    let slack = profile("Slack", "slack.exe", InsertMode::PasteAndEnter);
+    let notes = profile("Notes", "notes.exe", InsertMode::ShiftInsert);
+    let notes_id = notes.id.clone();
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    // Slack is in front (see `TestContext`), but Notes was picked by hotkey.
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![slack, notes],
+            ..engine_config(defaults)
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    )
+    .with_forced_profile(Some(notes_id));
+
+    let res = engine
+        .run_session_with_transcript_with_hook("Buy milk".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.config.matched_profile_name.as_deref(), Some("Notes"));
+    assert_eq!(
+        inserted.lock().unwrap().as_slice(),
+        &[("Buy milk".to_string(), InsertMode::ShiftInsert)]
+    );
+}
+
 #[tokio::test]
 async fn trigger_words_do_not_strip_without_llm_key() {
     let defaults = GlobalDefaults {
         // User enabled enhancement, but has not configured an API key.
         enable_enhancement: true,
-        prompt_id: None,
-        insert_mode: InsertMode::Paste,
         stt_provider: "local".into(),
         stt_model: "mock".into(),
-        language: "en".into(),
-        llm_base_url: "https://api.example.com/v1".into(),
-        llm_model: "gpt-4o-mini".into(),
-        microphone_device: None,
-        history_enabled: true,
-        context: voicewin_core::context::ContextToggles::default(),
+        ..global_defaults()
     };
 
     let prompts = vec![PromptTemplate {
@@ -237,16 +346,15 @@ async fn trigger_words_do_not_strip_without_llm_key() {
         mode: PromptMode::Enhancer,
         prompt_text: "Clean up.".into(),
         trigger_words: vec!["rewrite".into()],
+        decoding: Default::default(),
     }];
 
     let inserted = Arc::new(std::sync::Mutex::new(vec![]));
 
     let engine = VoicewinEngine::new(
         EngineConfig {
-            defaults,
-            profiles: vec![],
             prompts,
-            llm_api_key: "".into(),
+            ..engine_config(defaults)
         },
         Arc::new(TestContext),
         Arc::new(TestStt),
@@ -267,33 +375,16 @@ async fn trigger_words_do_not_strip_without_llm_key() {
         text.contains("rewrite"),
         "trigger word should not be stripped when enhancement is unavailable"
     );
+    // Context is only captured for sessions that can be enhanced.
+    assert_eq!(res.context, ContextSnapshot::default());
 }
 
 #[tokio::test]
 async fn transcript_override_skips_stt_and_inserts() {
-    let defaults = GlobalDefaults {
-        enable_enhancement: false,
-        prompt_id: None,
-        insert_mode: InsertMode::Paste,
-        stt_provider: "elevenlabs".into(),
-        stt_model: "scribe_v2_realtime".into(),
-        language: "en".into(),
-        llm_base_url: "https://api.example.com/v1".into(),
-        llm_model: "gpt-4o-mini".into(),
-        microphone_device: None,
-        history_enabled: true,
-        context: voicewin_core::context::ContextToggles::default(),
-    };
-
     let inserted = Arc::new(std::sync::Mutex::new(vec![]));
 
     let engine = VoicewinEngine::new(
-        EngineConfig {
-            defaults,
-            profiles: vec![],
-            prompts: vec![],
-            llm_api_key: "".into(),
-        },
+        engine_config(global_defaults()),
         Arc::new(TestContext),
         Arc::new(PanicStt),
         Arc::new(PanicLlm),
@@ -315,43 +406,1090 @@ async fn transcript_override_skips_stt_and_inserts() {
 
 #[tokio::test]
 async fn transcript_override_empty_is_failure() {
+    let engine = VoicewinEngine::new(
+        engine_config(global_defaults()),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: Arc::new(std::sync::Mutex::new(vec![])),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("   ".into(), |_stage| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage_label.as_deref(), Some("failed"));
+    let err = res.error.unwrap();
+    assert_eq!(err.kind, ErrorKind::NoSpeech);
+    assert!(err.user_message.contains("No speech detected"));
+}
+
+#[tokio::test]
+async fn diarized_transcript_is_labelled_by_speaker() {
     let defaults = GlobalDefaults {
-        enable_enhancement: false,
-        prompt_id: None,
-        insert_mode: InsertMode::Paste,
-        stt_provider: "elevenlabs".into(),
-        stt_model: "scribe_v2_realtime".into(),
-        language: "en".into(),
-        llm_base_url: "https://api.example.com/v1".into(),
-        llm_model: "gpt-4o-mini".into(),
-        microphone_device: None,
-        history_enabled: true,
-        context: voicewin_core::context::ContextToggles::default(),
+        stt_model: "scribe_v2".into(),
+        ..global_defaults()
+    };
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(defaults),
+        Arc::new(TestContext),
+        Arc::new(DiarizedStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let audio = AudioInput {
+        sample_rate_hz: 16_000,
+        samples: vec![0.0; 8],
+    };
+    let res = engine.run_session(audio).await.unwrap();
+    assert_eq!(
+        res.final_text.as_deref(),
+        Some("Speaker 1: Ship it Friday?\nSpeaker 2: Yes, Friday.")
+    );
+    assert_eq!(res.transcript.unwrap().segments.len(), 2);
+}
+
+#[tokio::test]
+async fn basic_cleanup_applies_when_not_enhanced() {
+    let defaults = GlobalDefaults {
+        basic_cleanup: true,
+        ..global_defaults()
+    };
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(defaults),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("so i think ,we ship".into(), |_stage| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.final_text.as_deref(), Some("So I think, we ship."));
+    assert_eq!(inserted.lock().unwrap()[0].0, "So I think, we ship.");
+}
+
+struct ShoutShip;
+
+impl TextProcessor for ShoutShip {
+    fn process(&self, text: &str, _cx: &TextContext<'_>) -> String {
+        text.replace("ship", "SHIP")
+    }
+}
+
+#[tokio::test]
+async fn profile_text_pipeline_orders_stages_and_uses_registered_processors() {
+    let defaults = GlobalDefaults {
+        enable_enhancement: true,
+        stt_provider: "local".into(),
+        stt_model: "mock".into(),
+        basic_cleanup: true,
+        ..global_defaults()
+    };
+
+    // No LLM stage for Slack, even though enhancement is on and a key is set.
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Slack".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            text_pipeline: Some(TextPipeline(vec![
+                TextStage::DisfluencyFilter,
+                TextStage::Replacements,
+                TextStage::Casing,
+            ])),
+            ..Default::default()
+        },
     };
 
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
     let engine = VoicewinEngine::new(
         EngineConfig {
-            defaults,
-            profiles: vec![],
-            prompts: vec![],
-            llm_api_key: "".into(),
+            profiles: vec![profile],
+            llm_api_key: "test-key".into(),
+            ..engine_config(defaults)
         },
         Arc::new(TestContext),
         Arc::new(PanicStt),
         Arc::new(PanicLlm),
         Arc::new(TestInserter {
-            inserted: Arc::new(std::sync::Mutex::new(vec![])),
+            inserted: inserted.clone(),
+        }),
+    )
+    .with_text_processor(TextStage::Replacements, Arc::new(ShoutShip));
+
+    let res = engine
+        .run_session_with_transcript_with_hook("um so i think ,we ship".into(), |_stage| async {})
+        .await
+        .unwrap();
+    assert!(res.error.is_none(), "{:?}", res.error);
+    assert!(res.enhanced.is_none());
+    assert_eq!(inserted.lock().unwrap()[0].0, "So I think, we SHIP.");
+}
+
+struct CaretContext;
+
+#[async_trait::async_trait]
+impl AppContextProvider for CaretContext {
+    async fn foreground_app(&self) -> anyhow::Result<AppIdentity> {
+        Ok(AppIdentity::new().with_process_name("notepad.exe"))
+    }
+
+    async fn snapshot_context(&self) -> anyhow::Result<ContextSnapshot> {
+        Ok(ContextSnapshot::default())
+    }
+
+    async fn text_before_caret(&self) -> anyhow::Result<Option<String>> {
+        Ok(Some("ep,".into()))
+    }
+}
+
+#[tokio::test]
+async fn insertion_is_adjusted_to_text_before_caret() {
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(global_defaults()),
+        Arc::new(CaretContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
         }),
     );
 
     let res = engine
-        .run_session_with_transcript_with_hook("   ".into(), |_stage| async {})
+        .run_session_with_transcript_with_hook("The second point.".into(), |_stage| async {})
         .await
         .unwrap();
-    assert_eq!(res.stage_label.as_deref(), Some("failed"));
-    assert!(res
-        .error
-        .as_deref()
-        .unwrap_or_default()
-        .contains("No speech detected"));
+    assert_eq!(res.final_text.as_deref(), Some("The second point."));
+    assert_eq!(inserted.lock().unwrap()[0].0, " the second point.");
+}
+
+#[tokio::test]
+async fn code_output_format_maps_symbols_and_skips_prose_cleanup() {
+    let defaults = GlobalDefaults {
+        basic_cleanup: true,
+        output_format: voicewin_core::types::OutputFormat::Code,
+        ..global_defaults()
+    };
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(defaults),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook(
+            "cargo build dash dash release [BLANK_AUDIO] xs[0].".into(),
+            |_stage| async {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        res.final_text.as_deref(),
+        Some("cargo build --release xs[0]")
+    );
+}
+
+#[tokio::test]
+async fn spoken_replacements_apply_before_insertion() {
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            spoken_replacements: voicewin_core::text::SpokenReplacementSettings {
+                enabled: true,
+                ..Default::default()
+            },
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook(
+            "Ship it fire emoji new line thanks".into(),
+            |_stage| async {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.final_text.as_deref(), Some("Ship it 🔥\nthanks"));
+}
+
+#[tokio::test]
+async fn trailing_send_it_upgrades_insert_mode_for_one_session() {
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(global_defaults()),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("See you at noon, send it.".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.insert_mode, InsertMode::PasteAndEnter);
+    assert_eq!(res.final_text.as_deref(), Some("See you at noon."));
+
+    engine
+        .run_session_with_transcript_with_hook("See you at noon".into(), |_| async {})
+        .await
+        .unwrap();
+
+    let inserted = inserted.lock().unwrap();
+    assert_eq!(inserted[0].1, InsertMode::PasteAndEnter);
+    assert_eq!(inserted[1].1, InsertMode::Paste);
+}
+
+#[tokio::test]
+async fn bare_snippet_command_inserts_stored_text_without_llm() {
+    let defaults = GlobalDefaults {
+        enable_enhancement: true,
+        basic_cleanup: true,
+        ..global_defaults()
+    };
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            // A key is set and enhancement is on, yet the LLM must not be called.
+            llm_api_key: "sk-test".into(),
+            snippets: vec![voicewin_core::enhancement::Snippet {
+                name: "signature".into(),
+                text: "best regards,\nana".into(),
+            }],
+            ..engine_config(defaults)
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("Insert signature, send it.".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.final_text.as_deref(), Some("best regards,\nana"));
+    assert!(res.enhanced.is_none());
+
+    let inserted = inserted.lock().unwrap();
+    assert_eq!(
+        inserted[0],
+        ("best regards,\nana".to_string(), InsertMode::PasteAndEnter)
+    );
+}
+
+struct TargetClosedInserter {
+    inserted: Arc<std::sync::Mutex<Vec<(String, InsertMode)>>>,
+}
+
+#[async_trait::async_trait]
+impl Inserter for TargetClosedInserter {
+    async fn insert(&self, text: &str, mode: InsertMode) -> anyhow::Result<()> {
+        self.inserted.lock().unwrap().push((text.to_string(), mode));
+        Ok(())
+    }
+
+    async fn focus_target(&self, _app: &AppIdentity) -> anyhow::Result<()> {
+        anyhow::bail!("The window you were dictating into was closed")
+    }
+}
+
+#[tokio::test]
+async fn insertion_aborts_when_target_window_cannot_be_focused() {
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(global_defaults()),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TargetClosedInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("Meeting moved to three".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage_label.as_deref(), Some("failed"));
+    let err = res.error.unwrap();
+    assert_eq!(err.kind, ErrorKind::Insertion);
+    assert!(err.user_message.contains("closed"));
+    // The text survives for history/recovery.
+    assert_eq!(res.final_text.as_deref(), Some("Meeting moved to three"));
+    assert!(inserted.lock().unwrap().is_empty());
+
+    // Copy-only doesn't need the target window.
+    let res = engine
+        .run_session_with_transcript_with_hook("Moved to three, just copy".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage_label.as_deref(), Some("done"));
+    assert_eq!(
+        inserted.lock().unwrap().as_slice(),
+        &[("Moved to three".to_string(), InsertMode::ClipboardOnly)]
+    );
+}
+
+struct ClosedWindowInserter {
+    inserted: Arc<std::sync::Mutex<Vec<(String, InsertMode)>>>,
+}
+
+#[async_trait::async_trait]
+impl Inserter for ClosedWindowInserter {
+    async fn insert(&self, text: &str, mode: InsertMode) -> anyhow::Result<()> {
+        self.inserted.lock().unwrap().push((text.to_string(), mode));
+        Ok(())
+    }
+
+    async fn focus_target(&self, _app: &AppIdentity) -> anyhow::Result<()> {
+        Err(voicewin_core::error::VoicewinError::new(
+            ErrorKind::TargetClosed,
+            "The window you were dictating into was closed; the text was not inserted.",
+        )
+        .into())
+    }
+}
+
+#[tokio::test]
+async fn closed_target_window_diverts_the_text_to_the_clipboard() {
+    let defaults = GlobalDefaults {
+        insert_mode: InsertMode::PasteAndEnter,
+        ..global_defaults()
+    };
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        engine_config(defaults),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(ClosedWindowInserter {
+            inserted: inserted.clone(),
+        }),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("Meeting moved to three".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage_label.as_deref(), Some("failed"));
+    let err = res.error.unwrap();
+    assert_eq!(err.kind, ErrorKind::TargetClosed);
+    assert!(err.user_message.contains("clipboard"));
+    assert_eq!(res.final_text.as_deref(), Some("Meeting moved to three"));
+    // Copied only; nothing was pasted or sent with Enter.
+    assert_eq!(
+        inserted.lock().unwrap().as_slice(),
+        &[(
+            "Meeting moved to three".to_string(),
+            InsertMode::ClipboardOnly
+        )]
+    );
+}
+
+struct ElevatedTargetInserter;
+
+#[async_trait::async_trait]
+impl Inserter for ElevatedTargetInserter {
+    async fn insert(&self, _text: &str, _mode: InsertMode) -> anyhow::Result<()> {
+        Err(voicewin_core::error::VoicewinError::new(
+            ErrorKind::ElevatedTarget,
+            "The window in front is running as administrator.",
+        )
+        .into())
+    }
+}
+
+#[tokio::test]
+async fn elevated_target_error_kind_reaches_the_result() {
+    let engine = VoicewinEngine::new(
+        engine_config(global_defaults()),
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(ElevatedTargetInserter),
+    );
+
+    let res = engine
+        .run_session_with_transcript_with_hook("Meeting moved to three".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage_label.as_deref(), Some("failed"));
+    let err = res.error.unwrap();
+    assert_eq!(err.kind, ErrorKind::ElevatedTarget);
+    assert!(!err.retryable);
+    // The text survives for history/recovery.
+    assert_eq!(res.final_text.as_deref(), Some("Meeting moved to three"));
+}
+
+struct RoutingInserter {
+    log: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Inserter for RoutingInserter {
+    async fn insert(&self, text: &str, _mode: InsertMode) -> anyhow::Result<()> {
+        self.log.lock().unwrap().push(format!("insert:{text}"));
+        Ok(())
+    }
+
+    async fn insert_with_strategy(
+        &self,
+        text: &str,
+        _mode: InsertMode,
+        strategy: voicewin_core::types::InsertionStrategy,
+    ) -> anyhow::Result<()> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("insert_{strategy:?}:{text}"));
+        Ok(())
+    }
+
+    async fn paste_with_keys(
+        &self,
+        text: &str,
+        _mode: InsertMode,
+        keys: voicewin_core::key_macro::KeyChord,
+    ) -> anyhow::Result<()> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("paste_{}:{text}", String::from(keys)));
+        Ok(())
+    }
+
+    async fn focus_target(&self, _app: &AppIdentity) -> anyhow::Result<()> {
+        self.log.lock().unwrap().push("focus_target".into());
+        Ok(())
+    }
+
+    async fn focus_app(&self, process_name: &str) -> anyhow::Result<()> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("focus_app:{process_name}"));
+        Ok(())
+    }
+
+    async fn send_keys(&self, keys: &[voicewin_core::key_macro::KeyChord]) -> anyhow::Result<()> {
+        let keys: Vec<String> = keys.iter().map(|k| String::from(*k)).collect();
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("keys:{}", keys.join(",")));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn profile_insert_target_routes_to_named_app() {
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Notes".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            insert_target: Some(voicewin_core::types::InsertTarget::App(
+                "Obsidian.exe".into(),
+            )),
+            ..Default::default()
+        },
+    };
+
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![profile],
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(RoutingInserter { log: log.clone() }),
+    );
+
+    engine
+        .run_session_with_transcript_with_hook("Buy milk".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(
+        log.lock().unwrap().as_slice(),
+        &[
+            "focus_app:Obsidian.exe".to_string(),
+            "insert:Buy milk".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn target_app_captured_at_start_wins_over_the_current_foreground() {
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Code".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "code.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            insert_mode: Some(InsertMode::PasteAndEnter),
+            ..Default::default()
+        },
+    };
+    let profile_id = profile.id.clone();
+
+    let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+    // The context provider reports Slack in front, as if focus moved after the hotkey.
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![profile],
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(TestInserter {
+            inserted: inserted.clone(),
+        }),
+    )
+    .with_target_app(Some(AppIdentity::new().with_process_name("code.exe")));
+
+    let res = engine
+        .run_session_with_transcript_with_hook("Run the tests".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(
+        res.app.process_name.as_ref().map(|p| p.0.as_str()),
+        Some("code.exe")
+    );
+    assert_eq!(res.config.matched_profile_id, Some(profile_id));
+    assert_eq!(
+        inserted.lock().unwrap().as_slice(),
+        &[("Run the tests".to_string(), InsertMode::PasteAndEnter)]
+    );
+}
+
+#[tokio::test]
+async fn profile_insertion_strategy_is_passed_to_the_inserter() {
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Terminal".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            insertion_strategy: Some(voicewin_core::types::InsertionStrategy::TypeKeys),
+            ..Default::default()
+        },
+    };
+
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![profile],
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(RoutingInserter { log: log.clone() }),
+    );
+
+    engine
+        .run_session_with_transcript_with_hook("ls -la".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(
+        log.lock().unwrap().as_slice(),
+        &[
+            "focus_target".to_string(),
+            "insert_TypeKeys:ls -la".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn live_typed_sessions_only_type_the_rest_of_the_transcript() {
+    let defaults = GlobalDefaults {
+        basic_cleanup: true,
+        ..global_defaults()
+    };
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = |typed: &str| {
+        VoicewinEngine::new(
+            engine_config(defaults.clone()),
+            Arc::new(TestContext),
+            Arc::new(PanicStt),
+            Arc::new(PanicLlm),
+            Arc::new(RoutingInserter { log: log.clone() }),
+        )
+        .with_live_typed(Some(typed.into()))
+    };
+
+    // Cleanup is skipped: it would rewrite words that are already on screen.
+    let res = engine("um hello there")
+        .run_session_with_transcript_with_hook("um hello there how are you".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, voicewin_engine::session::SessionStage::Done);
+    assert_eq!(
+        res.final_text.as_deref(),
+        Some("um hello there how are you")
+    );
+    assert_eq!(
+        log.lock().unwrap().as_slice(),
+        &[
+            "focus_target".to_string(),
+            "insert_TypeKeys: how are you".to_string()
+        ]
+    );
+
+    log.lock().unwrap().clear();
+    let res = engine("hello their")
+        .run_session_with_transcript_with_hook("hello there how are you".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, voicewin_engine::session::SessionStage::Failed);
+    assert_eq!(
+        log.lock().unwrap().as_slice(),
+        &["focus_target".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn profile_paste_keys_replace_the_default_paste() {
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Terminal".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            paste_keys: Some("ctrl+shift+v".parse().unwrap()),
+            ..Default::default()
+        },
+    };
+
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![profile],
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(RoutingInserter { log: log.clone() }),
+    );
+
+    engine
+        .run_session_with_transcript_with_hook("ls -la".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(
+        log.lock().unwrap().as_slice(),
+        &[
+            "focus_target".to_string(),
+            "paste_ctrl+shift+v:ls -la".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn note_file_target_appends_without_inserting() {
+    let dir = tempfile::tempdir().unwrap();
+    let template = dir.path().join("journal").join("{date}.md");
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Journal".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            insert_target: Some(voicewin_core::types::InsertTarget::NoteFile(
+                template.to_string_lossy().to_string(),
+            )),
+            ..Default::default()
+        },
+    };
+
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![profile],
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(RoutingInserter { log: log.clone() }),
+    );
+
+    for text in ["First thought", "Second thought"] {
+        let res = engine
+            .run_session_with_transcript_with_hook(text.into(), |_| async {})
+            .await
+            .unwrap();
+        assert_eq!(res.stage, voicewin_engine::session::SessionStage::Done);
+    }
+    assert!(log.lock().unwrap().is_empty());
+
+    let notes: Vec<_> = std::fs::read_dir(dir.path().join("journal"))
+        .unwrap()
+        .flatten()
+        .collect();
+    assert_eq!(notes.len(), 1);
+    let content = std::fs::read_to_string(notes[0].path()).unwrap();
+    let first = content.find("First thought").unwrap();
+    let second = content.find("Second thought").unwrap();
+    assert!(first < second);
+    assert_eq!(content.matches("### ").count(), 2);
+}
+
+// (text, process name) per appended entry.
+type ScratchpadLog = Arc<std::sync::Mutex<Vec<(String, Option<String>)>>>;
+
+struct TestScratchpad {
+    entries: ScratchpadLog,
+}
+
+#[async_trait::async_trait]
+impl voicewin_engine::traits::ScratchpadSink for TestScratchpad {
+    async fn append(&self, text: &str, app: &AppIdentity) -> anyhow::Result<()> {
+        let app = app.process_name.as_ref().map(|p| p.0.clone());
+        self.entries.lock().unwrap().push((text.into(), app));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn scratchpad_target_collects_text_without_inserting() {
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Scratch".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            insert_target: Some(voicewin_core::types::InsertTarget::Scratchpad),
+            ..Default::default()
+        },
+    };
+    let cfg = EngineConfig {
+        profiles: vec![profile],
+        ..engine_config(global_defaults())
+    };
+
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        cfg,
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(RoutingInserter { log: log.clone() }),
+    );
+    // Without a scratchpad the text is kept for recovery rather than pasted elsewhere.
+    let res = engine
+        .run_session_with_transcript_with_hook("Lost thought".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, voicewin_engine::session::SessionStage::Failed);
+    assert_eq!(res.final_text.as_deref(), Some("Lost thought"));
+
+    let entries = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = engine.with_scratchpad(Arc::new(TestScratchpad {
+        entries: entries.clone(),
+    }));
+    let res = engine
+        .run_session_with_transcript_with_hook("Call the dentist".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, voicewin_engine::session::SessionStage::Done);
+    assert!(log.lock().unwrap().is_empty());
+    assert_eq!(
+        entries.lock().unwrap().as_slice(),
+        &[(
+            "Call the dentist".to_string(),
+            Some("slack.exe".to_string())
+        )]
+    );
+}
+
+struct TestWebhook {
+    delivered: Arc<std::sync::Mutex<Vec<voicewin_engine::session::SessionWebhookPayload>>>,
+    fail: bool,
+}
+
+#[async_trait::async_trait]
+impl voicewin_engine::traits::WebhookSink for TestWebhook {
+    async fn deliver(
+        &self,
+        payload: &voicewin_engine::session::SessionWebhookPayload,
+    ) -> anyhow::Result<()> {
+        if self.fail {
+            return Err(anyhow::anyhow!("connection refused"));
+        }
+        self.delivered.lock().unwrap().push(payload.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn webhook_receives_result_after_or_instead_of_insertion() {
+    use voicewin_core::config::WebhookMode;
+    use voicewin_engine::session::SessionStage;
+    let cfg = engine_config(global_defaults());
+
+    let engine_with = |mode: WebhookMode, fail: bool| {
+        let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+        let delivered = Arc::new(std::sync::Mutex::new(vec![]));
+        let engine = VoicewinEngine::new(
+            cfg.clone(),
+            Arc::new(TestContext),
+            Arc::new(PanicStt),
+            Arc::new(PanicLlm),
+            Arc::new(TestInserter {
+                inserted: inserted.clone(),
+            }),
+        )
+        .with_webhook(
+            Arc::new(TestWebhook {
+                delivered: delivered.clone(),
+                fail,
+            }),
+            mode,
+        );
+        (engine, inserted, delivered)
+    };
+
+    let (engine, inserted, delivered) = engine_with(WebhookMode::AfterInsert, false);
+    let res = engine
+        .run_session_with_transcript_with_hook("Ship it today".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, SessionStage::Done);
+    assert_eq!(inserted.lock().unwrap().len(), 1);
+    let payloads = delivered.lock().unwrap().clone();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0].final_text, "Ship it today");
+    assert_eq!(
+        payloads[0].app.process_name.as_ref().map(|p| p.0.as_str()),
+        Some("slack.exe")
+    );
+    assert!(payloads[0].inserted);
+
+    let (engine, inserted, delivered) = engine_with(WebhookMode::InsteadOfInsert, false);
+    engine
+        .run_session_with_transcript_with_hook("Ship it today".into(), |_| async {})
+        .await
+        .unwrap();
+    assert!(inserted.lock().unwrap().is_empty());
+    assert!(!delivered.lock().unwrap()[0].inserted);
+
+    // A failed delivery only warns when the text was inserted, but fails the session when
+    // the webhook was the only sink.
+    let (engine, _, _) = engine_with(WebhookMode::AfterInsert, true);
+    let res = engine
+        .run_session_with_transcript_with_hook("Ship it today".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, SessionStage::Done);
+    assert!(
+        res.error
+            .unwrap()
+            .user_message
+            .contains("Webhook delivery failed")
+    );
+
+    let (engine, _, _) = engine_with(WebhookMode::InsteadOfInsert, true);
+    let res = engine
+        .run_session_with_transcript_with_hook("Ship it today".into(), |_| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.stage, SessionStage::Failed);
+    assert_eq!(res.final_text.as_deref(), Some("Ship it today"));
+}
+
+#[tokio::test]
+async fn profile_key_macro_runs_after_paste_but_not_for_clipboard_only() {
+    let profile = PowerModeProfile {
+        id: ProfileId::new(),
+        name: "Teams".into(),
+        enabled: true,
+        matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
+            "slack.exe".into(),
+        )],
+        auto_dictate: Default::default(),
+        overrides: PowerModeOverrides {
+            post_insert_keys: Some(vec!["tab".parse().unwrap(), "ctrl+enter".parse().unwrap()]),
+            ..Default::default()
+        },
+    };
+
+    let log = Arc::new(std::sync::Mutex::new(vec![]));
+    let engine = VoicewinEngine::new(
+        EngineConfig {
+            profiles: vec![profile],
+            ..engine_config(global_defaults())
+        },
+        Arc::new(TestContext),
+        Arc::new(PanicStt),
+        Arc::new(PanicLlm),
+        Arc::new(RoutingInserter { log: log.clone() }),
+    );
+
+    engine
+        .run_session_with_transcript_with_hook("Looks good".into(), |_| async {})
+        .await
+        .unwrap();
+    // "just copy" leaves nothing pasted for the macro to act on.
+    engine
+        .run_session_with_transcript_with_hook("Looks good, just copy".into(), |_| async {})
+        .await
+        .unwrap();
+
+    assert_eq!(
+        log.lock().unwrap().as_slice(),
+        &[
+            "focus_target".to_string(),
+            "insert:Looks good".to_string(),
+            "keys:tab,ctrl+enter".to_string(),
+            "insert:Looks good".to_string(),
+        ]
+    );
+}
+
+struct CannedLlm(&'static str);
+
+#[async_trait::async_trait]
+impl LlmProvider for CannedLlm {
+    async fn enhance(
+        &self,
+        _base_url: &str,
+        _api_key: &str,
+        model: &str,
+        _system_message: &str,
+        _user_message: &str,
+    ) -> anyhow::Result<EnhancedText> {
+        Ok(EnhancedText {
+            text: self.0.into(),
+            provider: "openai-compatible".into(),
+            model: model.into(),
+            usage: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn chat_reply_from_the_llm_falls_back_to_the_transcript() {
+    let run = |llm: CannedLlm, guardrails: voicewin_core::guardrails::OutputGuardrailSettings| {
+        let defaults = GlobalDefaults {
+            enable_enhancement: true,
+            stt_provider: "local".into(),
+            stt_model: "mock".into(),
+            ..global_defaults()
+        };
+        let inserted = Arc::new(std::sync::Mutex::new(vec![]));
+        let engine = VoicewinEngine::new(
+            EngineConfig {
+                prompts: vec![PromptTemplate {
+                    id: PromptId::new(),
+                    title: "Clean".into(),
+                    mode: PromptMode::Enhancer,
+                    prompt_text: "Clean up.".into(),
+                    trigger_words: vec![],
+                    decoding: Default::default(),
+                }],
+                llm_api_key: "k".into(),
+                ..engine_config(defaults)
+            },
+            Arc::new(TestContext),
+            Arc::new(PanicStt),
+            Arc::new(llm),
+            Arc::new(TestInserter {
+                inserted: inserted.clone(),
+            }),
+        )
+        .with_output_guardrails(guardrails);
+        (engine, inserted)
+    };
+
+    let reply = "Sure! Here's the cleaned text: Send the report.";
+    let (engine, inserted) = run(CannedLlm(reply), Default::default());
+    let res = engine
+        .run_session_with_transcript_with_hook("send the report".into(), |_stage| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.final_text.as_deref(), Some("send the report"));
+    assert_eq!(inserted.lock().unwrap()[0].0, "send the report");
+    let warning = res.error.unwrap();
+    assert_eq!(warning.kind, ErrorKind::Provider);
+    assert!(
+        warning.user_message.contains("chat reply"),
+        "{}",
+        warning.user_message
+    );
+    // The rejected output stays visible for auditing.
+    assert_eq!(res.enhanced.unwrap().text, reply);
+
+    let off = voicewin_core::guardrails::OutputGuardrailSettings {
+        enabled: false,
+        ..Default::default()
+    };
+    let (engine, _) = run(CannedLlm(reply), off);
+    let res = engine
+        .run_session_with_transcript_with_hook("send the report".into(), |_stage| async {})
+        .await
+        .unwrap();
+    assert_eq!(res.final_text.as_deref(), Some(reply));
+    assert!(res.error.is_none());
 }
@@ -24,6 +24,11 @@ async fn main() -> anyhow::Result<()> {
         llm_model: std::env::var("LLM_MODEL").unwrap_or_else(|_| "gpt-4o-mini".into()),
         microphone_device: None,
         history_enabled: true,
+        basic_cleanup: false,
+        output_format: Default::default(),
+        disfluency_filter: Default::default(),
+        text_pipeline: Default::default(),
+        realtime_vad: Default::default(),
         context: voicewin_core::context::ContextToggles::default(),
     };
 
@@ -33,6 +38,7 @@ async fn main() -> anyhow::Result<()> {
         mode: PromptMode::Enhancer,
         prompt_text: "Clean up grammar and punctuation.".into(),
         trigger_words: vec!["rewrite".into()],
+        decoding: Default::default(),
     }];
 
     let profiles = vec![PowerModeProfile {
@@ -42,6 +48,7 @@ async fn main() -> anyhow::Result<()> {
         matchers: vec![voicewin_core::power_mode::AppMatcher::ProcessNameEquals(
             "slack.exe".into(),
         )],
+        auto_dictate: Default::default(),
         overrides: PowerModeOverrides {
             insert_mode: Some(InsertMode::PasteAndEnter),
             ..Default::default()
@@ -69,6 +76,9 @@ async fn main() -> anyhow::Result<()> {
             profiles,
             prompts,
             llm_api_key,
+            spoken_replacements: Default::default(),
+            insert_commands: Default::default(),
+            snippets: vec![],
         },
         ctx_provider,
         stt,
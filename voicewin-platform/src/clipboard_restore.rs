@@ -0,0 +1,133 @@
+//! Putting the user's clipboard back after a clipboard paste
+//! (`AppConfig.insertion.clipboard_restore`).
+//!
+//! The target app reads the clipboard some time after the paste keystroke, and nothing
+//! reports when it has. So the inserters wait, but watch the clipboard while they do: once
+//! something else writes to it (a clipboard manager taking ownership of the pasted text, or
+//! the user copying), restoring would clobber that, and the wait ends without it.
+
+// Only the platform inserters paste.
+#![cfg_attr(
+    not(any(windows, target_os = "macos", target_os = "linux")),
+    allow(dead_code)
+)]
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use voicewin_core::config::ClipboardRestoreSettings;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+// `None` until the app applies a config; the defaults apply meanwhile.
+static SETTINGS: Mutex<Option<ClipboardRestoreSettings>> = Mutex::new(None);
+
+/// Apply `AppConfig.insertion.clipboard_restore` to later pastes.
+pub fn set_settings(settings: ClipboardRestoreSettings) {
+    *SETTINGS.lock().unwrap_or_else(|p| p.into_inner()) = Some(settings);
+}
+
+fn settings() -> ClipboardRestoreSettings {
+    SETTINGS
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Restore {
+    Restored,
+    /// Something else wrote to the clipboard during the wait.
+    SkippedChanged,
+}
+
+/// Wait for the target app to read the pasted text, then `restore` the user's clipboard,
+/// unless `changed` reports another write first. `platform_delay` applies when the settings
+/// don't set one.
+pub(crate) fn restore_after_paste(
+    platform_delay: Duration,
+    changed: impl FnMut() -> bool,
+    restore: impl FnOnce(),
+) {
+    if restore_with(&settings(), platform_delay, changed, restore) == Restore::SkippedChanged {
+        tracing::debug!("clipboard was written after the paste; not restoring it");
+    }
+}
+
+fn restore_with(
+    settings: &ClipboardRestoreSettings,
+    platform_delay: Duration,
+    mut changed: impl FnMut() -> bool,
+    restore: impl FnOnce(),
+) -> Restore {
+    let delay = settings
+        .delay_ms
+        .map_or(platform_delay, |ms| Duration::from_millis(u64::from(ms)));
+    let deadline = Instant::now() + delay;
+    loop {
+        if settings.skip_if_changed && changed() {
+            return Restore::SkippedChanged;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        thread::sleep(POLL_INTERVAL.min(deadline - now));
+    }
+    restore();
+    Restore::Restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_after_the_delay_unless_the_clipboard_changed() {
+        let settings = ClipboardRestoreSettings {
+            delay_ms: Some(60),
+            skip_if_changed: true,
+        };
+
+        let mut restored = false;
+        let started = Instant::now();
+        let res = restore_with(&settings, Duration::ZERO, || false, || restored = true);
+        assert_eq!(res, Restore::Restored);
+        assert!(restored);
+        assert!(started.elapsed() >= Duration::from_millis(60));
+
+        // A clipboard manager writes on the second poll: no restore, no full wait.
+        let mut polls = 0;
+        let started = Instant::now();
+        let res = restore_with(
+            &ClipboardRestoreSettings {
+                delay_ms: Some(10_000),
+                ..settings.clone()
+            },
+            Duration::ZERO,
+            || {
+                polls += 1;
+                polls > 1
+            },
+            || panic!("must not restore"),
+        );
+        assert_eq!(res, Restore::SkippedChanged);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // With skipping off, a change is ignored.
+        let mut restored = false;
+        let res = restore_with(
+            &ClipboardRestoreSettings {
+                delay_ms: Some(0),
+                skip_if_changed: false,
+            },
+            Duration::from_secs(10),
+            || true,
+            || restored = true,
+        );
+        assert_eq!(res, Restore::Restored);
+        assert!(restored);
+    }
+}
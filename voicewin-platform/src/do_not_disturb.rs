@@ -0,0 +1,80 @@
+//! Whether the OS is in a do-not-disturb state (Windows Focus Assist or a full-screen
+//! app, macOS Focus, GNOME "Do Not Disturb").
+//!
+//! Best-effort: anything that cannot be read counts as "not active", so callers only ever
+//! get quieter than usual when the OS says so.
+
+/// Whether the user asked the OS not to be interrupted right now.
+pub fn do_not_disturb_active() -> bool {
+    imp::do_not_disturb_active()
+}
+
+#[cfg(windows)]
+mod imp {
+    use windows::Win32::UI::Shell::{QUNS_ACCEPTS_NOTIFICATIONS, SHQueryUserNotificationState};
+
+    pub(super) fn do_not_disturb_active() -> bool {
+        // Besides quiet time and Focus Assist this covers presentations and full-screen
+        // games (QUNS_BUSY and friends), which the user would not want interrupted either.
+        match unsafe { SHQueryUserNotificationState() } {
+            Ok(state) => state != QUNS_ACCEPTS_NOTIFICATIONS,
+            Err(e) => {
+                tracing::debug!("notification state query failed: {e}");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::path::PathBuf;
+
+    // NSUserNotificationCenter never exposed the Focus state. Since macOS 12 the active
+    // Focus assertions are stored here; older releases kept a defaults flag instead.
+    const ASSERTIONS: &str = "Library/DoNotDisturb/DB/Assertions.json";
+
+    pub(super) fn do_not_disturb_active() -> bool {
+        if let Some(active) = focus_assertions_present() {
+            return active;
+        }
+        std::process::Command::new("defaults")
+            .args([
+                "-currentHost",
+                "read",
+                "com.apple.notificationcenterui",
+                "doNotDisturb",
+            ])
+            .output()
+            .map(|out| out.status.success() && String::from_utf8_lossy(&out.stdout).trim() == "1")
+            .unwrap_or(false)
+    }
+
+    fn focus_assertions_present() -> Option<bool> {
+        let path = PathBuf::from(std::env::var_os("HOME")?).join(ASSERTIONS);
+        let raw = std::fs::read_to_string(path).ok()?;
+        // `{"data":[{"storeAssertionRecords":[...]}]}`; the key is absent with no Focus on.
+        Some(raw.contains("\"storeAssertionRecords\""))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    pub(super) fn do_not_disturb_active() -> bool {
+        // GNOME turns banners off for Do Not Disturb; other desktops have no common switch.
+        crate::linux::run_tool(
+            "gsettings",
+            &["get", "org.gnome.desktop.notifications", "show-banners"],
+            None,
+        )
+        .map(|out| out.trim() == "false")
+        .unwrap_or(false)
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+mod imp {
+    pub(super) fn do_not_disturb_active() -> bool {
+        false
+    }
+}
@@ -0,0 +1,101 @@
+//! Location of the focused (foreground) window, used to pick which monitor UI should
+//! appear on.
+
+/// Center of the focused window in physical screen coordinates, if it can be resolved.
+///
+/// Not implemented on macOS (and only sees X11/XWayland windows on Linux); callers should
+/// fall back to another heuristic such as the cursor position.
+pub fn foreground_window_center() -> Option<(i32, i32)> {
+    imp::foreground_window_center()
+}
+
+#[cfg(windows)]
+mod imp {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowRect};
+
+    pub(super) fn foreground_window_center() -> Option<(i32, i32)> {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.0.is_null() {
+                return None;
+            }
+            let mut r = RECT::default();
+            GetWindowRect(hwnd, &mut r).ok()?;
+            Some(((r.left + r.right) / 2, (r.top + r.bottom) / 2))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use crate::linux::run_tool;
+
+    pub(super) fn foreground_window_center() -> Option<(i32, i32)> {
+        let out = run_tool(
+            "xdotool",
+            &["getactivewindow", "getwindowgeometry", "--shell"],
+            None,
+        )
+        .ok()?;
+        let (x, y, w, h) = super::parse_xdotool_geometry(&out)?;
+        Some((x + w / 2, y + h / 2))
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+mod imp {
+    pub(super) fn foreground_window_center() -> Option<(i32, i32)> {
+        None
+    }
+}
+
+/// Parse `xdotool getwindowgeometry --shell` output into `(x, y, width, height)`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xdotool_geometry(out: &str) -> Option<(i32, i32, i32, i32)> {
+    let field = |name: &str| {
+        out.lines()
+            .find_map(|l| l.trim().strip_prefix(name)?.strip_prefix('='))
+            .and_then(|v| v.trim().parse::<i32>().ok())
+    };
+    Some((field("X")?, field("Y")?, field("WIDTH")?, field("HEIGHT")?))
+}
+
+/// Whether a running process named `actual` is the app the user configured as `wanted`
+/// (case-insensitive; "Obsidian" also matches "Obsidian.exe").
+#[cfg_attr(
+    not(any(windows, target_os = "macos", target_os = "linux")),
+    allow(dead_code)
+)]
+pub(crate) fn process_name_matches(actual: &str, wanted: &str) -> bool {
+    let strip = |s: &str| {
+        let s = s.trim();
+        match s.len().checked_sub(4) {
+            Some(i) if s.is_char_boundary(i) && s[i..].eq_ignore_ascii_case(".exe") => {
+                s[..i].to_string()
+            }
+            _ => s.to_string(),
+        }
+    };
+    !wanted.trim().is_empty() && strip(actual).eq_ignore_ascii_case(&strip(wanted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_xdotool_shell_geometry() {
+        let out = "WINDOW=62914567\nX=1920\nY=40\nWIDTH=1280\nHEIGHT=720\nSCREEN=0\n";
+        assert_eq!(parse_xdotool_geometry(out), Some((1920, 40, 1280, 720)));
+        assert_eq!(parse_xdotool_geometry("WINDOW=1\nX=5\n"), None);
+    }
+
+    #[test]
+    fn process_names_match_with_or_without_exe() {
+        assert!(process_name_matches("Obsidian.exe", "obsidian"));
+        assert!(process_name_matches("obsidian", "Obsidian.EXE"));
+        assert!(!process_name_matches("obsidian-helper", "obsidian"));
+        assert!(!process_name_matches("obsidian", " "));
+    }
+}
@@ -0,0 +1,341 @@
+//! Foreground-app change notifications.
+//!
+//! Windows uses a WinEvent hook (focus and title changes), macOS the NSWorkspace
+//! activation notification; Linux has no portable focus event, so it polls `xdotool`.
+//! Subscribers only hear about actual changes: repeated identical identities are dropped.
+
+use std::sync::{Arc, Mutex};
+
+use voicewin_core::types::AppIdentity;
+use voicewin_engine::traits::{AppContextProvider, ContextSnapshot};
+
+/// Keeps a subscription alive; dropping it unsubscribes.
+pub struct ForegroundWatch {
+    _watch: imp::Watch,
+}
+
+/// Whether foreground changes are delivered as they happen. When false (Linux) they are
+/// polled, so a cached identity may lag behind a quick app switch.
+pub const FOREGROUND_CHANGES_ARE_PUSHED: bool = cfg!(any(windows, target_os = "macos"));
+
+/// Call `on_change` with the current foreground app now and after every change.
+pub fn watch_foreground_app(
+    on_change: impl Fn(AppIdentity) + Send + Sync + 'static,
+) -> anyhow::Result<ForegroundWatch> {
+    let last = Mutex::new(None);
+    let notify = Arc::new(move || {
+        let Ok(app) = imp::foreground_app_identity() else {
+            return;
+        };
+        if record_change(&last, &app) {
+            on_change(app);
+        }
+    });
+
+    notify();
+    let watch = imp::watch(Box::new(move || notify()))?;
+    Ok(ForegroundWatch { _watch: watch })
+}
+
+/// Whether `app` is one of our own windows (e.g. Settings). They are reported like any other
+/// app; a "current app" display will usually want to skip them.
+pub fn is_own_app(app: &AppIdentity) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        // `window_handle` is the process id on macOS.
+        app.window_handle == Some(u64::from(std::process::id()))
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        match (&app.exe_path, std::env::current_exe()) {
+            (Some(exe), Ok(own)) => std::path::Path::new(&exe.0) == own,
+            _ => false,
+        }
+    }
+}
+
+/// Store `app` as the latest identity; false when it is the same as the previous one.
+fn record_change(last: &Mutex<Option<AppIdentity>>, app: &AppIdentity) -> bool {
+    let mut last = last.lock().unwrap_or_else(|p| p.into_inner());
+    if last.as_ref() == Some(app) {
+        return false;
+    }
+    *last = Some(app.clone());
+    true
+}
+
+/// The latest identity reported by [`watch_foreground_app`], shared with whoever feeds it.
+#[derive(Debug, Clone, Default)]
+pub struct ForegroundCache(Arc<Mutex<Option<AppIdentity>>>);
+
+impl ForegroundCache {
+    pub fn set(&self, app: AppIdentity) {
+        *self.0.lock().unwrap_or_else(|p| p.into_inner()) = Some(app);
+    }
+
+    pub fn get(&self) -> Option<AppIdentity> {
+        self.0.lock().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+}
+
+/// Answers `foreground_app` from a [`ForegroundCache`] instead of querying the OS at
+/// session start; asks `inner` until the first change has been recorded.
+pub struct CachedContextProvider {
+    inner: Arc<dyn AppContextProvider>,
+    cache: ForegroundCache,
+}
+
+impl CachedContextProvider {
+    pub fn new(inner: Arc<dyn AppContextProvider>, cache: ForegroundCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait::async_trait]
+impl AppContextProvider for CachedContextProvider {
+    async fn foreground_app(&self) -> anyhow::Result<AppIdentity> {
+        match self.cache.get() {
+            Some(app) => Ok(app),
+            None => self.inner.foreground_app().await,
+        }
+    }
+
+    async fn snapshot_context(&self) -> anyhow::Result<ContextSnapshot> {
+        self.inner.snapshot_context().await
+    }
+
+    async fn text_before_caret(&self) -> anyhow::Result<Option<String>> {
+        self.inner.text_before_caret().await
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::cell::RefCell;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::Accessibility::{HWINEVENTHOOK, SetWinEventHook, UnhookWinEvent};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DispatchMessageW, EVENT_OBJECT_NAMECHANGE, EVENT_SYSTEM_FOREGROUND, GetForegroundWindow,
+        GetMessageW, MSG, OBJID_WINDOW, PM_NOREMOVE, PeekMessageW, PostThreadMessageW,
+        TranslateMessage, WINEVENT_OUTOFCONTEXT, WM_QUIT, WM_USER,
+    };
+
+    pub(super) use crate::windows::get_foreground_app_identity as foreground_app_identity;
+
+    thread_local! {
+        // WinEvent callbacks carry no user data; the hook thread owns exactly one watcher.
+        static ON_EVENT: RefCell<Option<Box<dyn Fn() + Send>>> = RefCell::new(None);
+    }
+
+    pub(super) struct Watch {
+        thread_id: u32,
+        thread: Option<thread::JoinHandle<()>>,
+    }
+
+    pub(super) fn watch(notify: Box<dyn Fn() + Send>) -> anyhow::Result<Watch> {
+        let (tx, rx) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("foreground-watch".into())
+            .spawn(move || {
+                ON_EVENT.with(|f| *f.borrow_mut() = Some(notify));
+                let flags = WINEVENT_OUTOFCONTEXT;
+                let mut msg = MSG::default();
+                unsafe {
+                    let hooks = [
+                        SetWinEventHook(
+                            EVENT_SYSTEM_FOREGROUND,
+                            EVENT_SYSTEM_FOREGROUND,
+                            None,
+                            Some(on_win_event),
+                            0,
+                            0,
+                            flags,
+                        ),
+                        SetWinEventHook(
+                            EVENT_OBJECT_NAMECHANGE,
+                            EVENT_OBJECT_NAMECHANGE,
+                            None,
+                            Some(on_win_event),
+                            0,
+                            0,
+                            flags,
+                        ),
+                    ];
+                    if hooks[0].is_invalid() {
+                        let _ = tx.send(Err(anyhow::anyhow!("SetWinEventHook failed")));
+                        return;
+                    }
+
+                    // Create the message queue before `Drop` can post `WM_QUIT` to it.
+                    let _ = PeekMessageW(&mut msg, None, WM_USER, WM_USER, PM_NOREMOVE);
+                    let _ = tx.send(Ok(GetCurrentThreadId()));
+
+                    while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                        let _ = TranslateMessage(&msg);
+                        DispatchMessageW(&msg);
+                    }
+                    for hook in hooks.into_iter().filter(|h| !h.is_invalid()) {
+                        let _ = UnhookWinEvent(hook);
+                    }
+                }
+            })?;
+
+        let thread_id = rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("foreground watch thread exited"))??;
+        Ok(Watch {
+            thread_id,
+            thread: Some(thread),
+        })
+    }
+
+    unsafe extern "system" fn on_win_event(
+        _hook: HWINEVENTHOOK,
+        event: u32,
+        hwnd: HWND,
+        id_object: i32,
+        _id_child: i32,
+        _event_thread: u32,
+        _event_time: u32,
+    ) {
+        // Title changes of every window land here; only the foreground window's matter.
+        if event == EVENT_OBJECT_NAMECHANGE
+            && (id_object != OBJID_WINDOW.0 || hwnd != unsafe { GetForegroundWindow() })
+        {
+            return;
+        }
+        ON_EVENT.with(|f| {
+            if let Some(notify) = f.borrow().as_ref() {
+                notify();
+            }
+        });
+    }
+
+    impl Drop for Watch {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::ptr::NonNull;
+
+    use block2::RcBlock;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, NSObjectProtocol, ProtocolObject};
+    use objc2_app_kit::{NSWorkspace, NSWorkspaceDidActivateApplicationNotification};
+    use objc2_foundation::{NSNotification, NSNotificationCenter};
+
+    pub(super) use crate::macos::get_foreground_app_identity as foreground_app_identity;
+
+    pub(super) struct Watch {
+        center: Retained<NSNotificationCenter>,
+        observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
+    }
+
+    // SAFETY: NSNotificationCenter is thread-safe, and the observer token is only ever
+    // handed back to it.
+    unsafe impl Send for Watch {}
+
+    pub(super) fn watch(notify: Box<dyn Fn() + Send>) -> anyhow::Result<Watch> {
+        let block = RcBlock::new(move |_note: NonNull<NSNotification>| notify());
+        unsafe {
+            let center = NSWorkspace::sharedWorkspace().notificationCenter();
+            let observer = center.addObserverForName_object_queue_usingBlock(
+                Some(NSWorkspaceDidActivateApplicationNotification),
+                None,
+                None,
+                &block,
+            );
+            Ok(Watch { center, observer })
+        }
+    }
+
+    impl Drop for Watch {
+        fn drop(&mut self) {
+            let observer: &AnyObject = self.observer.as_ref();
+            unsafe { self.center.removeObserver(observer) };
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    pub(super) use crate::linux::get_foreground_app_identity as foreground_app_identity;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    pub(super) struct Watch {
+        stop: Arc<AtomicBool>,
+    }
+
+    pub(super) fn watch(notify: Box<dyn Fn() + Send>) -> anyhow::Result<Watch> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        thread::Builder::new()
+            .name("foreground-watch".into())
+            .spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    thread::sleep(POLL_INTERVAL);
+                    notify();
+                }
+            })?;
+        Ok(Watch { stop })
+    }
+
+    impl Drop for Watch {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+mod imp {
+    use voicewin_core::types::AppIdentity;
+
+    pub(super) struct Watch;
+
+    pub(super) fn foreground_app_identity() -> anyhow::Result<AppIdentity> {
+        Ok(AppIdentity::new())
+    }
+
+    pub(super) fn watch(_notify: Box<dyn Fn() + Send>) -> anyhow::Result<Watch> {
+        Err(anyhow::anyhow!(
+            "foreground app changes are not available on this platform"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_changed_identities_are_recorded() {
+        let last = Mutex::new(None);
+        let code = AppIdentity::new().with_process_name("Code.exe");
+        assert!(record_change(&last, &code));
+        assert!(!record_change(&last, &code));
+
+        let retitled = code.clone().with_window_title("main.rs");
+        assert!(record_change(&last, &retitled));
+        assert!(record_change(&last, &code));
+    }
+}
@@ -1,7 +1,17 @@
+pub mod clipboard_restore;
+pub mod do_not_disturb;
+pub mod foreground;
+pub mod foreground_watch;
+pub mod modifiers;
+pub mod permissions;
 pub mod test;
+pub mod trigger_input;
 
 #[cfg(windows)]
 pub mod windows;
 
 #[cfg(target_os = "macos")]
 pub mod macos;
+
+#[cfg(target_os = "linux")]
+pub mod linux;
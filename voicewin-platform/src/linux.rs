@@ -0,0 +1,208 @@
+// Linux implementations (X11 and Wayland).
+//
+// These shell out to the standard desktop tools instead of linking display-server
+// libraries, so the crate keeps building on headless CI:
+// - foreground window: `xdotool` (X11, and XWayland windows under Wayland)
+// - clipboard: `wl-copy`/`wl-paste` on Wayland, `xclip` on X11
+// - keystrokes: `wtype` (wlr virtual-keyboard protocol) on Wayland, `xdotool` on X11
+
+#[path = "linux_foreground.rs"]
+mod linux_foreground;
+
+pub(crate) use linux_foreground::get_foreground_app_identity;
+
+#[path = "linux_insert.rs"]
+mod linux_insert;
+
+#[path = "linux_ime.rs"]
+mod linux_ime;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use voicewin_core::types::{AppIdentity, InsertMode, InsertionStrategy};
+use voicewin_engine::traits::{
+    AppContextProvider, ContextSnapshot, Inserter, unsupported_strategy,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayServer {
+    X11,
+    Wayland,
+}
+
+impl DisplayServer {
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("WAYLAND_DISPLAY").ok().as_deref(),
+            std::env::var("XDG_SESSION_TYPE").ok().as_deref(),
+        )
+    }
+
+    fn from_env(wayland_display: Option<&str>, session_type: Option<&str>) -> Self {
+        let wayland_display = wayland_display.is_some_and(|s| !s.trim().is_empty());
+        let wayland_session =
+            session_type.is_some_and(|s| s.trim().eq_ignore_ascii_case("wayland"));
+        if wayland_display || wayland_session {
+            Self::Wayland
+        } else {
+            Self::X11
+        }
+    }
+}
+
+/// Run a helper tool and return its stdout.
+pub(crate) fn run_tool(
+    program: &str,
+    args: &[&str],
+    stdin: Option<&str>,
+) -> anyhow::Result<String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            anyhow::anyhow!("`{program}` not found; install it to enable this feature on Linux")
+        } else {
+            anyhow::anyhow!("failed to run `{program}`: {e}")
+        }
+    })?;
+
+    if let Some(input) = stdin {
+        // Dropping the handle closes the pipe so the tool sees EOF.
+        let mut pipe = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("failed to open stdin for `{program}`"))?;
+        pipe.write_all(input.as_bytes())?;
+    }
+
+    let out = child.wait_with_output()?;
+    if !out.status.success() {
+        return Err(anyhow::anyhow!(
+            "`{program}` failed ({}): {}",
+            out.status,
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+#[derive(Debug, Default)]
+pub struct LinuxContextProvider;
+
+#[async_trait::async_trait]
+impl AppContextProvider for LinuxContextProvider {
+    async fn foreground_app(&self) -> anyhow::Result<AppIdentity> {
+        linux_foreground::get_foreground_app_identity()
+    }
+
+    async fn snapshot_context(&self) -> anyhow::Result<ContextSnapshot> {
+        // MVP: provide window/app identity and clipboard text (best-effort).
+        let app = self.foreground_app().await?;
+        let mut ctx = ContextSnapshot::default();
+
+        let proc = app
+            .process_name
+            .as_ref()
+            .map(|p| p.0.clone())
+            .unwrap_or_else(|| "unknown".into());
+        let title = app
+            .window_title
+            .as_ref()
+            .map(|t| t.0.clone())
+            .unwrap_or_default();
+
+        ctx.window_context = Some(format!("Application: {}\nActive Window: {}", proc, title));
+        ctx.clipboard = linux_insert::get_clipboard_string(DisplayServer::detect()).ok();
+        Ok(ctx)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LinuxInserter;
+
+#[async_trait::async_trait]
+impl Inserter for LinuxInserter {
+    #[tracing::instrument(name = "insert.clipboard_paste", skip_all)]
+    async fn insert(&self, text: &str, mode: InsertMode) -> anyhow::Result<()> {
+        // Same strategy as Windows: clipboard swap + paste keystroke + restore.
+        let _ime = prepare_input(mode);
+        linux_insert::paste_text_via_clipboard(DisplayServer::detect(), text, mode, None)
+    }
+
+    #[tracing::instrument(name = "insert.clipboard_paste", skip_all)]
+    async fn paste_with_keys(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        keys: voicewin_core::key_macro::KeyChord,
+    ) -> anyhow::Result<()> {
+        let _ime = prepare_input(mode);
+        linux_insert::paste_text_via_clipboard(DisplayServer::detect(), text, mode, Some(&keys))
+    }
+
+    async fn insert_with_strategy(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        strategy: InsertionStrategy,
+    ) -> anyhow::Result<()> {
+        match strategy {
+            InsertionStrategy::ClipboardPaste => self.insert(text, mode).await,
+            InsertionStrategy::TypeKeys => {
+                let _ime = prepare_input(mode);
+                linux_insert::type_text(DisplayServer::detect(), text, mode)
+            }
+            other => Err(unsupported_strategy(other)),
+        }
+    }
+
+    async fn focus_app(&self, process_name: &str) -> anyhow::Result<()> {
+        // Wayland gives clients no way to focus other windows; only X11/XWayland apps work.
+        linux_foreground::activate_app_window(process_name)
+    }
+
+    async fn send_keys(&self, keys: &[voicewin_core::key_macro::KeyChord]) -> anyhow::Result<()> {
+        linux_insert::send_key_macro(DisplayServer::detect(), keys)
+    }
+}
+
+/// Commits any input method composition first, unless only copying. Keep the returned guard
+/// until the text is in.
+fn prepare_input(mode: InsertMode) -> Option<linux_ime::SuspendedInputMethod> {
+    if mode == InsertMode::ClipboardOnly {
+        return None;
+    }
+    linux_ime::suspend_input_method()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_display_server_from_env() {
+        assert_eq!(DisplayServer::from_env(None, None), DisplayServer::X11);
+        assert_eq!(
+            DisplayServer::from_env(Some(""), Some("x11")),
+            DisplayServer::X11
+        );
+        assert_eq!(
+            DisplayServer::from_env(Some("wayland-0"), None),
+            DisplayServer::Wayland
+        );
+        assert_eq!(
+            DisplayServer::from_env(None, Some("Wayland")),
+            DisplayServer::Wayland
+        );
+    }
+}
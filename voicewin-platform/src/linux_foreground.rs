@@ -0,0 +1,90 @@
+// Linux foreground app detection.
+// Kept behind cfg(target_os = "linux") at the module level in linux.rs.
+//
+// Wayland compositors do not expose the focused window to other clients, so under
+// Wayland this only sees XWayland windows. When nothing can be resolved we return an
+// empty identity (Power Mode then falls back to global defaults).
+
+use voicewin_core::types::{AppIdentity, WindowTitle};
+
+use super::run_tool;
+use crate::foreground::process_name_matches;
+
+pub fn get_foreground_app_identity() -> anyhow::Result<AppIdentity> {
+    let Ok(window_id) = run_tool("xdotool", &["getactivewindow"], None) else {
+        return Ok(AppIdentity::new());
+    };
+    let window_id = window_id.trim();
+    if window_id.is_empty() {
+        return Ok(AppIdentity::new());
+    }
+
+    let title = run_tool("xdotool", &["getwindowname", window_id], None)
+        .ok()
+        .map(|t| t.trim_end_matches('\n').to_string());
+
+    let pid = run_tool("xdotool", &["getwindowpid", window_id], None)
+        .ok()
+        .and_then(|p| p.trim().parse::<u32>().ok());
+
+    let exe_path = pid.and_then(|pid| {
+        std::fs::read_link(format!("/proc/{pid}/exe"))
+            .ok()
+            .map(|p| p.to_string_lossy().to_string())
+    });
+    let process_name = exe_path.as_ref().and_then(|p| {
+        std::path::Path::new(p)
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+    });
+
+    let mut app = AppIdentity::new();
+    if let Some(path) = exe_path {
+        app = app.with_exe_path(path);
+    }
+    if let Some(name) = process_name {
+        app = app.with_process_name(name);
+    }
+    if let Some(t) = title {
+        app.window_title = Some(WindowTitle(t));
+    }
+
+    Ok(app)
+}
+
+/// Raise and focus the first visible X11 window of a running app, by process name.
+pub fn activate_app_window(process_name: &str) -> anyhow::Result<()> {
+    let pids: Vec<String> = std::fs::read_dir("/proc")?
+        .flatten()
+        .filter_map(|e| {
+            let pid = e.file_name().to_string_lossy().to_string();
+            let exe = std::fs::read_link(e.path().join("exe")).ok()?;
+            let name = exe.file_name()?.to_string_lossy().to_string();
+            process_name_matches(&name, process_name).then_some(pid)
+        })
+        .collect();
+    if pids.is_empty() {
+        return Err(anyhow::anyhow!(
+            "{process_name} is not running; the text was not inserted."
+        ));
+    }
+
+    let window_id = pids
+        .iter()
+        .filter_map(|pid| {
+            run_tool("xdotool", &["search", "--onlyvisible", "--pid", pid], None).ok()
+        })
+        .find_map(|out| {
+            out.lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty())
+                .map(String::from)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!("No window of {process_name} was found; the text was not inserted.")
+        })?;
+
+    run_tool("xdotool", &["windowactivate", "--sync", &window_id], None)
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("failed to focus {process_name}: {e}"))
+}
@@ -0,0 +1,54 @@
+// IME composition (Chinese, Japanese, Korean input) before inserting. Fcitx, the usual input
+// method framework for CJK on Linux desktops, commits its preedit text when it is switched
+// off, so it is switched off for the insertion and back on afterwards. IBus has no
+// command-line equivalent; under it a composition in progress is left as is.
+
+use super::run_tool;
+
+// Fcitx 5 first; `fcitx-remote` is Fcitx 4 (and an alias in some Fcitx 5 packages).
+const FCITX_REMOTES: [&str; 2] = ["fcitx5-remote", "fcitx-remote"];
+
+/// The input method that was on before an insertion; switched back on when dropped.
+pub struct SuspendedInputMethod {
+    remote: &'static str,
+}
+
+impl Drop for SuspendedInputMethod {
+    fn drop(&mut self) {
+        let _ = run_tool(self.remote, &["-o"], None);
+    }
+}
+
+/// Switch Fcitx off if it is on, committing any composition. `None` when there was nothing
+/// to switch off (or no Fcitx).
+pub fn suspend_input_method() -> Option<SuspendedInputMethod> {
+    for remote in FCITX_REMOTES {
+        let Ok(state) = run_tool(remote, &[], None) else {
+            continue;
+        };
+        if !is_active(&state) {
+            return None;
+        }
+        run_tool(remote, &["-c"], None).ok()?;
+        return Some(SuspendedInputMethod { remote });
+    }
+    None
+}
+
+/// `fcitx-remote` prints 0 (not running), 1 (off) or 2 (on).
+fn is_active(state: &str) -> bool {
+    state.trim() == "2"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_state_two_is_active() {
+        assert!(is_active("2\n"));
+        assert!(!is_active("1\n"));
+        assert!(!is_active("0"));
+        assert!(!is_active(""));
+    }
+}
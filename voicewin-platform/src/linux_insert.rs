@@ -0,0 +1,281 @@
+// Linux clipboard-based paste.
+//
+// Strategy (mirrors windows_insert.rs):
+// - Save current clipboard text (best-effort)
+// - Write our text to clipboard
+// - Send the paste keystroke (then optional Enter)
+// - Restore clipboard
+
+use std::thread;
+use std::time::Duration;
+
+use voicewin_core::key_macro::{KeyChord, MacroKey};
+use voicewin_core::types::InsertMode;
+
+use super::{DisplayServer, run_tool};
+use crate::clipboard_restore;
+
+const RESTORE_DELAY: Duration = Duration::from_millis(150);
+
+pub fn get_clipboard_string(server: DisplayServer) -> anyhow::Result<String> {
+    match server {
+        DisplayServer::Wayland => run_tool("wl-paste", &["--no-newline"], None),
+        DisplayServer::X11 => run_tool("xclip", &["-selection", "clipboard", "-o"], None),
+    }
+}
+
+fn set_clipboard_string(server: DisplayServer, text: &str) -> anyhow::Result<()> {
+    match server {
+        DisplayServer::Wayland => run_tool("wl-copy", &[], Some(text)),
+        DisplayServer::X11 => run_tool("xclip", &["-selection", "clipboard"], Some(text)),
+    }
+    .map(|_| ())
+}
+
+/// Tool + arguments that send the paste chord for `mode`.
+fn paste_keystroke(
+    server: DisplayServer,
+    mode: InsertMode,
+) -> (&'static str, &'static [&'static str]) {
+    match (server, mode) {
+        (DisplayServer::Wayland, InsertMode::ShiftInsert) => {
+            ("wtype", &["-M", "shift", "-k", "Insert", "-m", "shift"])
+        }
+        (DisplayServer::Wayland, _) => ("wtype", &["-M", "ctrl", "v", "-m", "ctrl"]),
+        (DisplayServer::X11, InsertMode::ShiftInsert) => {
+            ("xdotool", &["key", "--clearmodifiers", "shift+Insert"])
+        }
+        (DisplayServer::X11, _) => ("xdotool", &["key", "--clearmodifiers", "ctrl+v"]),
+    }
+}
+
+fn enter_keystroke(server: DisplayServer) -> (&'static str, &'static [&'static str]) {
+    match server {
+        DisplayServer::Wayland => ("wtype", &["-k", "Return"]),
+        DisplayServer::X11 => ("xdotool", &["key", "--clearmodifiers", "Return"]),
+    }
+}
+
+/// Tool + arguments that type `text` as key presses.
+fn type_keystrokes(server: DisplayServer, text: &str) -> (&'static str, Vec<String>) {
+    match server {
+        DisplayServer::Wayland => ("wtype", vec!["--".into(), text.into()]),
+        DisplayServer::X11 => (
+            "xdotool",
+            vec![
+                "type".into(),
+                "--clearmodifiers".into(),
+                "--".into(),
+                text.into(),
+            ],
+        ),
+    }
+}
+
+/// Type the text instead of pasting it (the `type_keys` insertion strategy).
+pub fn type_text(server: DisplayServer, text: &str, mode: InsertMode) -> anyhow::Result<()> {
+    let (program, args) = type_keystrokes(server, text);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run_tool(program, &args, None).map_err(|e| anyhow::anyhow!("failed to type text: {e}"))?;
+
+    if matches!(mode, InsertMode::PasteAndEnter) {
+        thread::sleep(Duration::from_millis(50));
+        let (program, args) = enter_keystroke(server);
+        run_tool(program, args, None).map_err(|e| anyhow::anyhow!("failed to press enter: {e}"))?;
+    }
+    Ok(())
+}
+
+/// X11/Wayland keysym name for a macro key.
+fn keysym(key: MacroKey) -> String {
+    match key {
+        MacroKey::Enter => "Return".into(),
+        MacroKey::Tab => "Tab".into(),
+        MacroKey::Escape => "Escape".into(),
+        MacroKey::Space => "space".into(),
+        MacroKey::Backspace => "BackSpace".into(),
+        MacroKey::Up => "Up".into(),
+        MacroKey::Down => "Down".into(),
+        MacroKey::Left => "Left".into(),
+        MacroKey::Right => "Right".into(),
+        MacroKey::Insert => "Insert".into(),
+        MacroKey::Char(c) => c.to_string(),
+    }
+}
+
+/// Tool + arguments that press one macro chord.
+fn chord_keystroke(server: DisplayServer, chord: &KeyChord) -> (&'static str, Vec<String>) {
+    let held: Vec<&str> = [
+        (chord.ctrl, "ctrl"),
+        (chord.shift, "shift"),
+        (chord.alt, "alt"),
+        (chord.meta, "super"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect();
+    let key = keysym(chord.key);
+
+    match server {
+        DisplayServer::Wayland => {
+            // wtype's modifier names: "logo" is the Super/Windows key.
+            let held = held.iter().map(|m| if *m == "super" { "logo" } else { m });
+            let mut args = vec![];
+            for m in held.clone() {
+                args.extend(["-M".to_string(), m.to_string()]);
+            }
+            args.extend(["-k".to_string(), key]);
+            for m in held {
+                args.extend(["-m".to_string(), m.to_string()]);
+            }
+            ("wtype", args)
+        }
+        DisplayServer::X11 => {
+            let combo = held.into_iter().chain([key.as_str()]).collect::<Vec<_>>();
+            (
+                "xdotool",
+                vec!["key".into(), "--clearmodifiers".into(), combo.join("+")],
+            )
+        }
+    }
+}
+
+/// Press a profile's post-insert key macro, one chord at a time.
+pub fn send_key_macro(server: DisplayServer, keys: &[KeyChord]) -> anyhow::Result<()> {
+    for chord in keys {
+        thread::sleep(Duration::from_millis(50));
+        let (program, args) = chord_keystroke(server, chord);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        run_tool(program, &args, None)
+            .map_err(|e| anyhow::anyhow!("failed to press {}: {e}", String::from(*chord)))?;
+    }
+    Ok(())
+}
+
+/// `paste_keys` replaces the usual paste chord, for apps that paste with something else.
+pub fn paste_text_via_clipboard(
+    server: DisplayServer,
+    text: &str,
+    mode: InsertMode,
+    paste_keys: Option<&KeyChord>,
+) -> anyhow::Result<()> {
+    if mode == InsertMode::ClipboardOnly {
+        return set_clipboard_string(server, text)
+            .map_err(|e| anyhow::anyhow!("failed to write clipboard: {e}"));
+    }
+
+    // Preserve user's clipboard and always attempt to restore it.
+    let original = get_clipboard_string(server).ok();
+
+    // 1) Put our text on clipboard.
+    set_clipboard_string(server, text)
+        .map_err(|e| anyhow::anyhow!("failed to write clipboard: {e}"))?;
+    thread::sleep(Duration::from_millis(50));
+
+    // 2) Send paste keystroke.
+    let paste_result = (|| {
+        match paste_keys {
+            Some(chord) => {
+                let (program, args) = chord_keystroke(server, chord);
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                run_tool(program, &args, None)
+            }
+            None => {
+                let (program, args) = paste_keystroke(server, mode);
+                run_tool(program, args, None)
+            }
+        }
+        .map_err(|e| anyhow::anyhow!("failed to paste: {e}"))?;
+
+        if matches!(mode, InsertMode::PasteAndEnter) {
+            thread::sleep(Duration::from_millis(50));
+            let (program, args) = enter_keystroke(server);
+            run_tool(program, args, None)
+                .map_err(|e| anyhow::anyhow!("failed to press enter: {e}"))?;
+        }
+
+        Ok::<(), anyhow::Error>(())
+    })();
+
+    // 3) Restore user's clipboard (best-effort) once the target app has had time to read
+    // it. The tools offer no change counter; different contents mean someone else wrote it.
+    if let Some(original) = original {
+        clipboard_restore::restore_after_paste(
+            RESTORE_DELAY,
+            || get_clipboard_string(server).is_ok_and(|current| current != text),
+            || {
+                let _ = set_clipboard_string(server, &original);
+            },
+        );
+    }
+
+    // 4) Return the keystroke result.
+    paste_result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paste_keystroke_matches_insert_mode() {
+        assert_eq!(
+            paste_keystroke(DisplayServer::X11, InsertMode::Paste),
+            ("xdotool", &["key", "--clearmodifiers", "ctrl+v"][..])
+        );
+        assert_eq!(
+            paste_keystroke(DisplayServer::X11, InsertMode::ShiftInsert).1,
+            &["key", "--clearmodifiers", "shift+Insert"][..]
+        );
+        assert_eq!(
+            paste_keystroke(DisplayServer::Wayland, InsertMode::PasteAndEnter),
+            ("wtype", &["-M", "ctrl", "v", "-m", "ctrl"][..])
+        );
+        assert_eq!(
+            paste_keystroke(DisplayServer::Wayland, InsertMode::ShiftInsert).0,
+            "wtype"
+        );
+    }
+
+    #[test]
+    fn typed_text_is_passed_after_the_option_separator() {
+        assert_eq!(
+            type_keystrokes(DisplayServer::X11, "-rf /tmp").1,
+            ["type", "--clearmodifiers", "--", "-rf /tmp"]
+        );
+        assert_eq!(
+            type_keystrokes(DisplayServer::Wayland, "ls"),
+            ("wtype", vec!["--".to_string(), "ls".to_string()])
+        );
+    }
+
+    #[test]
+    fn macro_chords_map_to_tool_arguments() {
+        let chord: KeyChord = "ctrl+enter".parse().unwrap();
+        assert_eq!(
+            chord_keystroke(DisplayServer::X11, &chord),
+            (
+                "xdotool",
+                vec![
+                    "key".into(),
+                    "--clearmodifiers".into(),
+                    "ctrl+Return".into()
+                ]
+            )
+        );
+        assert_eq!(
+            chord_keystroke(DisplayServer::Wayland, &chord).1,
+            ["-M", "ctrl", "-k", "Return", "-m", "ctrl"]
+        );
+
+        let chord: KeyChord = "cmd+k".parse().unwrap();
+        assert_eq!(
+            chord_keystroke(DisplayServer::Wayland, &chord).1,
+            ["-M", "logo", "-k", "k", "-m", "logo"]
+        );
+        assert_eq!(
+            chord_keystroke(DisplayServer::X11, &KeyChord::key(MacroKey::Tab)).1[2],
+            "Tab"
+        );
+    }
+}
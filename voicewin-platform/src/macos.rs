@@ -1,10 +1,17 @@
 //! macOS platform implementations.
 
 mod macos_foreground;
-mod macos_insert;
+pub(crate) mod macos_insert;
 
-use voicewin_core::types::{AppIdentity, InsertMode};
-use voicewin_engine::traits::{AppContextProvider, ContextSnapshot, Inserter};
+#[path = "macos_ime.rs"]
+mod macos_ime;
+
+pub(crate) use macos_foreground::get_foreground_app_identity;
+
+use voicewin_core::types::{AppIdentity, InsertMode, InsertionStrategy};
+use voicewin_engine::traits::{
+    AppContextProvider, ContextSnapshot, Inserter, unsupported_strategy,
+};
 
 #[derive(Debug, Default)]
 pub struct MacosContextProvider;
@@ -42,7 +49,67 @@ pub struct MacosInserter;
 
 #[async_trait::async_trait]
 impl Inserter for MacosInserter {
+    #[tracing::instrument(name = "insert.clipboard_paste", skip_all)]
     async fn insert(&self, text: &str, mode: InsertMode) -> anyhow::Result<()> {
-        macos_insert::paste_text_via_clipboard(text, mode)
+        let _ime = prepare_input(mode);
+        macos_insert::paste_text_via_clipboard(text, mode, None)
+    }
+
+    #[tracing::instrument(name = "insert.clipboard_paste", skip_all)]
+    async fn paste_with_keys(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        keys: voicewin_core::key_macro::KeyChord,
+    ) -> anyhow::Result<()> {
+        let _ime = prepare_input(mode);
+        macos_insert::paste_text_via_clipboard(text, mode, Some(&keys))
+    }
+
+    async fn insert_with_strategy(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        strategy: InsertionStrategy,
+    ) -> anyhow::Result<()> {
+        match strategy {
+            InsertionStrategy::ClipboardPaste => self.insert(text, mode).await,
+            InsertionStrategy::TypeKeys => {
+                let _ime = prepare_input(mode);
+                macos_insert::type_text(text, mode)
+            }
+            InsertionStrategy::MacosAx => {
+                let _ime = prepare_input(mode);
+                macos_insert::insert_via_accessibility(text, mode)
+            }
+            other => Err(unsupported_strategy(other)),
+        }
+    }
+
+    async fn focus_target(&self, app: &AppIdentity) -> anyhow::Result<()> {
+        match app.window_handle {
+            Some(pid) => macos_foreground::activate_app(pid),
+            None => Ok(()),
+        }
+    }
+
+    async fn focus_app(&self, process_name: &str) -> anyhow::Result<()> {
+        let pid = macos_foreground::find_app_pid(process_name).ok_or_else(|| {
+            anyhow::anyhow!("{process_name} is not running; the text was not inserted.")
+        })?;
+        macos_foreground::activate_app(pid)
+    }
+
+    async fn send_keys(&self, keys: &[voicewin_core::key_macro::KeyChord]) -> anyhow::Result<()> {
+        macos_insert::send_key_macro(keys)
+    }
+}
+
+/// Commits any input method composition first, unless only copying. Keep the returned guard
+/// until the text is in.
+fn prepare_input(mode: InsertMode) -> Option<macos_ime::SuspendedInputMethod> {
+    if mode == InsertMode::ClipboardOnly {
+        return None;
     }
+    macos_ime::suspend_input_method()
 }
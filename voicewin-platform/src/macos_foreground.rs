@@ -6,11 +6,17 @@
 
 #![cfg(target_os = "macos")]
 
+use std::thread;
+use std::time::Duration;
+
 use objc2::rc::Retained;
-use objc2_app_kit::{NSRunningApplication, NSWorkspace};
+use objc2_app_kit::{NSApplicationActivationOptions, NSRunningApplication, NSWorkspace};
 
+use voicewin_core::error::{ErrorKind, VoicewinError};
 use voicewin_core::types::AppIdentity;
 
+use crate::foreground::process_name_matches;
+
 pub fn get_foreground_app_identity() -> anyhow::Result<AppIdentity> {
     // SAFETY: Accessing AppKit APIs is generally expected on the main thread,
     // but in practice reading `frontmostApplication` is commonly done off-main.
@@ -32,5 +38,58 @@ pub fn get_foreground_app_identity() -> anyhow::Result<AppIdentity> {
         out = out.with_process_name(name.to_string());
     }
 
+    let pid = unsafe { app.processIdentifier() };
+    if pid > 0 {
+        out.window_handle = Some(pid as u64);
+    }
+
     Ok(out)
 }
+
+/// Re-activate the app with process id `pid` (from `AppIdentity::window_handle`).
+pub fn activate_app(pid: u64) -> anyhow::Result<()> {
+    let app = i32::try_from(pid).ok().and_then(|pid| unsafe {
+        NSRunningApplication::runningApplicationWithProcessIdentifier(pid)
+    });
+    let Some(app) = app.filter(|a| unsafe { !a.isTerminated() }) else {
+        return Err(VoicewinError::new(
+            ErrorKind::TargetClosed,
+            "The app you were dictating into has quit; the text was not inserted.",
+        )
+        .into());
+    };
+
+    if unsafe { app.isActive() } {
+        return Ok(());
+    }
+
+    #[allow(deprecated)]
+    unsafe {
+        app.activateWithOptions(NSApplicationActivationOptions::ActivateIgnoringOtherApps);
+    }
+
+    // Activation completes asynchronously.
+    for _ in 0..10 {
+        if unsafe { app.isActive() } {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    Err(anyhow::anyhow!(
+        "Could not switch back to the app you were dictating into; the text was not inserted."
+    ))
+}
+
+/// Process id of a running app whose name or bundle id is `name`.
+pub fn find_app_pid(name: &str) -> Option<u64> {
+    let apps = unsafe { NSWorkspace::sharedWorkspace().runningApplications() };
+    (0..apps.count()).find_map(|i| {
+        let app = apps.objectAtIndex(i);
+        let localized = unsafe { app.localizedName() }.map(|n| n.to_string());
+        let bundle_id = unsafe { app.bundleIdentifier() }.map(|b| b.to_string());
+        let matches = localized.is_some_and(|n| process_name_matches(&n, name))
+            || bundle_id.is_some_and(|b| b.eq_ignore_ascii_case(name.trim()));
+        let pid = unsafe { app.processIdentifier() };
+        (matches && pid > 0).then_some(pid as u64)
+    })
+}
@@ -0,0 +1,119 @@
+// IME composition (Chinese, Japanese, Korean input) before inserting. With an input method
+// such as Pinyin or Kotoeri selected, a paste can land inside the marked (uncommitted) text,
+// or the marked text is committed on top of it afterwards.
+//
+// The marked text lives in the target app, out of our reach, but switching to an
+// ASCII-capable keyboard layout makes the input method commit it. The input method is
+// selected again after the insertion, so the user keeps typing in the same mode.
+
+#![cfg(target_os = "macos")]
+
+use std::ffi::c_void;
+use std::thread;
+use std::time::Duration;
+
+use core_foundation::base::{CFRelease, TCFType};
+use core_foundation::boolean::{CFBoolean, CFBooleanRef};
+use core_foundation::string::CFStringRef;
+
+type TISInputSourceRef = *const c_void;
+
+#[link(name = "Carbon", kind = "framework")]
+unsafe extern "C" {
+    fn TISCopyCurrentKeyboardInputSource() -> TISInputSourceRef;
+    fn TISCopyCurrentASCIICapableKeyboardLayoutInputSource() -> TISInputSourceRef;
+    fn TISGetInputSourceProperty(source: TISInputSourceRef, key: CFStringRef) -> *const c_void;
+    fn TISSelectInputSource(source: TISInputSourceRef) -> i32;
+    static kTISPropertyInputSourceIsASCIICapable: CFStringRef;
+}
+
+#[repr(C)]
+struct DispatchQueue {
+    _private: [u8; 0],
+}
+
+unsafe extern "C" {
+    static _dispatch_main_q: DispatchQueue;
+    fn dispatch_sync_f(
+        queue: *const DispatchQueue,
+        context: *mut c_void,
+        work: extern "C" fn(*mut c_void),
+    );
+    fn pthread_main_np() -> i32;
+}
+
+/// The input method that was selected before an insertion; selected again on drop.
+pub struct SuspendedInputMethod {
+    input_method: TISInputSourceRef,
+}
+
+impl Drop for SuspendedInputMethod {
+    fn drop(&mut self) {
+        let input_method = self.input_method;
+        on_main_thread(|| unsafe {
+            TISSelectInputSource(input_method);
+            CFRelease(input_method);
+        });
+    }
+}
+
+/// Switch from an input method to a keyboard layout, committing any marked text. `None`
+/// when a plain layout was already selected.
+pub fn suspend_input_method() -> Option<SuspendedInputMethod> {
+    let suspended = on_main_thread(|| unsafe {
+        let current = TISCopyCurrentKeyboardInputSource();
+        if current.is_null() {
+            return None;
+        }
+        if is_ascii_capable(current) {
+            CFRelease(current);
+            return None;
+        }
+        let layout = TISCopyCurrentASCIICapableKeyboardLayoutInputSource();
+        let status = if layout.is_null() {
+            -1
+        } else {
+            let status = TISSelectInputSource(layout);
+            CFRelease(layout);
+            status
+        };
+        if status != 0 {
+            CFRelease(current);
+            return None;
+        }
+        Some(SuspendedInputMethod {
+            input_method: current,
+        })
+    })?;
+    // Give the input method a moment to commit before the paste arrives.
+    thread::sleep(Duration::from_millis(50));
+    Some(suspended)
+}
+
+unsafe fn is_ascii_capable(source: TISInputSourceRef) -> bool {
+    unsafe {
+        let value = TISGetInputSourceProperty(source, kTISPropertyInputSourceIsASCIICapable);
+        !value.is_null() && CFBoolean::wrap_under_get_rule(value as CFBooleanRef).into()
+    }
+}
+
+/// Text Input Sources calls belong on the main thread; recent macOS versions assert on it.
+fn on_main_thread<F: FnOnce() -> R, R>(f: F) -> R {
+    if unsafe { pthread_main_np() } != 0 {
+        return f();
+    }
+    let mut job = (Some(f), None);
+    unsafe {
+        dispatch_sync_f(
+            &raw const _dispatch_main_q,
+            &mut job as *mut _ as *mut c_void,
+            run_job::<F, R>,
+        );
+    }
+    job.1.expect("main queue ran the job")
+}
+
+extern "C" fn run_job<F: FnOnce() -> R, R>(context: *mut c_void) {
+    let (f, out) = unsafe { &mut *(context as *mut (Option<F>, Option<R>)) };
+    *out = f.take().map(|f| f());
+}
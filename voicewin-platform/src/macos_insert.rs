@@ -12,9 +12,11 @@
 use std::thread;
 use std::time::Duration;
 
-use core_foundation::base::TCFType;
+use std::ffi::c_void;
+
+use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
 use core_foundation::dictionary::CFDictionary;
-use core_foundation::string::CFString;
+use core_foundation::string::{CFString, CFStringRef};
 use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
 use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
 use objc2::rc::Retained;
@@ -25,15 +27,40 @@ use objc2_app_kit::{
 };
 use objc2_foundation::{NSArray, NSData, NSString};
 
+use voicewin_core::error::{ErrorKind, VoicewinError};
+use voicewin_core::key_macro::{KeyChord, MacroKey};
 use voicewin_core::types::InsertMode;
 
+use crate::clipboard_restore;
+
 #[link(name = "ApplicationServices", kind = "framework")]
 unsafe extern "C" {
     fn AXIsProcessTrustedWithOptions(options: *const AnyObject) -> bool;
     static kAXTrustedCheckOptionPrompt: *const AnyObject;
+    fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+    fn AXUIElementCopyAttributeValue(
+        element: AXUIElementRef,
+        attribute: CFStringRef,
+        value: *mut CFTypeRef,
+    ) -> i32;
+    fn AXUIElementSetAttributeValue(
+        element: AXUIElementRef,
+        attribute: CFStringRef,
+        value: CFTypeRef,
+    ) -> i32;
 }
 
-fn is_accessibility_trusted() -> bool {
+type AXUIElementRef = *const c_void;
+
+// kAXErrorSuccess
+const AX_SUCCESS: i32 = 0;
+
+const RESTORE_DELAY: Duration = Duration::from_millis(1000);
+
+// Apps read at most this many UTF-16 units from one keyboard event's string.
+const MAX_UNITS_PER_KEY_EVENT: usize = 20;
+
+pub(crate) fn is_accessibility_trusted() -> bool {
     // Mirror enigo's approach: AXIsProcessTrustedWithOptions({ prompt: false }).
     unsafe {
         let key = CFString::wrap_under_create_rule(kAXTrustedCheckOptionPrompt.cast());
@@ -196,16 +223,184 @@ fn post_enter() -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn paste_text_via_clipboard(text: &str, mode: InsertMode) -> anyhow::Result<()> {
+/// ANSI virtual keycode for a macro key.
+fn macro_keycode(key: MacroKey) -> Option<u16> {
+    Some(match key {
+        MacroKey::Enter => 0x24,
+        MacroKey::Tab => 0x30,
+        MacroKey::Space => 0x31,
+        MacroKey::Backspace => 0x33,
+        MacroKey::Escape => 0x35,
+        MacroKey::Left => 0x7B,
+        MacroKey::Right => 0x7C,
+        // kVK_Help, where PC keyboards have Insert.
+        MacroKey::Insert => 0x72,
+        MacroKey::Down => 0x7D,
+        MacroKey::Up => 0x7E,
+        MacroKey::Char(c) => {
+            const LETTERS: [u16; 26] = [
+                0x00, 0x0B, 0x08, 0x02, 0x0E, 0x03, 0x05, 0x04, 0x22, 0x26, 0x28, 0x25, 0x2E, 0x2D,
+                0x1F, 0x23, 0x0C, 0x0F, 0x01, 0x11, 0x20, 0x09, 0x0D, 0x07, 0x10, 0x06,
+            ];
+            const DIGITS: [u16; 10] = [0x1D, 0x12, 0x13, 0x14, 0x15, 0x17, 0x16, 0x1A, 0x1C, 0x19];
+            match c.to_ascii_lowercase() {
+                c @ 'a'..='z' => LETTERS[(c as u8 - b'a') as usize],
+                c @ '0'..='9' => DIGITS[(c as u8 - b'0') as usize],
+                _ => return None,
+            }
+        }
+    })
+}
+
+/// Press a profile's post-insert key macro, one chord at a time.
+pub fn send_key_macro(keys: &[KeyChord]) -> anyhow::Result<()> {
     if !is_accessibility_trusted() {
-        return Err(anyhow::anyhow!(
-            "Accessibility permission is required to paste into other apps (enable it in System Settings → Privacy & Security → Accessibility)."
-        ));
+        return Err(VoicewinError::new(
+            ErrorKind::AccessibilityAccess,
+            "Accessibility permission is required to press keys in other apps.",
+        )
+        .into());
     }
 
-    let pasteboard = NSPasteboard::generalPasteboard();
+    for chord in keys {
+        thread::sleep(Duration::from_millis(50));
+        let keycode = macro_keycode(chord.key)
+            .ok_or_else(|| anyhow::anyhow!("unsupported key: {}", String::from(*chord)))?;
+
+        let mut flags = CGEventFlags::CGEventFlagNull;
+        for (held, flag) in [
+            (chord.ctrl, CGEventFlags::CGEventFlagControl),
+            (chord.shift, CGEventFlags::CGEventFlagShift),
+            (chord.alt, CGEventFlags::CGEventFlagAlternate),
+            (chord.meta, CGEventFlags::CGEventFlagCommand),
+        ] {
+            if held {
+                flags.insert(flag);
+            }
+        }
+
+        let src = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+            .map_err(|_| anyhow::anyhow!("failed to create CGEventSource"))?;
+        // Modifier flags on the key events are enough; apps see the chord without separate
+        // modifier key presses.
+        for down in [true, false] {
+            let ev = CGEvent::new_keyboard_event(src.clone(), keycode, down)
+                .ok_or_else(|| anyhow::anyhow!("failed to create key event"))?;
+            ev.set_flags(flags);
+            ev.post(CGEventTapLocation::HID);
+        }
+    }
+    Ok(())
+}
+
+fn require_accessibility() -> anyhow::Result<()> {
+    if is_accessibility_trusted() {
+        return Ok(());
+    }
+    Err(VoicewinError::new(
+        ErrorKind::AccessibilityAccess,
+        "Accessibility permission is required to insert text into other apps (enable it in System Settings → Privacy & Security → Accessibility).",
+    )
+    .into())
+}
+
+/// Type the text instead of pasting it (the `type_keys` insertion strategy). Each event
+/// carries a few characters, so the keyboard layout doesn't matter.
+pub fn type_text(text: &str, mode: InsertMode) -> anyhow::Result<()> {
+    require_accessibility()?;
+    let src = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|_| anyhow::anyhow!("failed to create CGEventSource"))?;
+
+    let mut chunk = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        chunk.push(c);
+        let full = chars.peek().is_none_or(|next| {
+            chunk.encode_utf16().count() + next.len_utf16() > MAX_UNITS_PER_KEY_EVENT
+        });
+        if !full {
+            continue;
+        }
+        for down in [true, false] {
+            let ev = CGEvent::new_keyboard_event(src.clone(), 0, down)
+                .ok_or_else(|| anyhow::anyhow!("failed to create key event"))?;
+            ev.set_string(&chunk);
+            ev.post(CGEventTapLocation::HID);
+        }
+        chunk.clear();
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    if matches!(mode, InsertMode::PasteAndEnter) {
+        thread::sleep(Duration::from_millis(50));
+        post_enter()?;
+    }
+    Ok(())
+}
+
+/// Replace the focused element's selection through the Accessibility API (the `macos_ax`
+/// insertion strategy); with nothing selected, the text goes in at the caret.
+pub fn insert_via_accessibility(text: &str, mode: InsertMode) -> anyhow::Result<()> {
+    require_accessibility()?;
+    let focused_attr = CFString::new("AXFocusedUIElement");
+    let selected_attr = CFString::new("AXSelectedText");
+    let value = CFString::new(text);
+
+    unsafe {
+        let system = AXUIElementCreateSystemWide();
+        let mut focused: CFTypeRef = std::ptr::null();
+        let err =
+            AXUIElementCopyAttributeValue(system, focused_attr.as_concrete_TypeRef(), &mut focused);
+        CFRelease(system);
+        if err != AX_SUCCESS || focused.is_null() {
+            anyhow::bail!("No focused text field was found; the text was not inserted.");
+        }
+
+        let err = AXUIElementSetAttributeValue(
+            focused,
+            selected_attr.as_concrete_TypeRef(),
+            value.as_CFTypeRef(),
+        );
+        CFRelease(focused);
+        if err != AX_SUCCESS {
+            anyhow::bail!(
+                "The focused field doesn't accept text through Accessibility (error {err}); the text was not inserted."
+            );
+        }
+    }
+
+    if matches!(mode, InsertMode::PasteAndEnter) {
+        thread::sleep(Duration::from_millis(50));
+        post_enter()?;
+    }
+    Ok(())
+}
 
-    let original_change = pasteboard.changeCount();
+/// `paste_keys` replaces Cmd+V, for apps that paste with something else.
+pub fn paste_text_via_clipboard(
+    text: &str,
+    mode: InsertMode,
+    paste_keys: Option<&KeyChord>,
+) -> anyhow::Result<()> {
+    // Copying needs no synthetic keystrokes, so no Accessibility permission either.
+    if mode == InsertMode::ClipboardOnly {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        unsafe {
+            pasteboard.clearContents();
+        }
+        let _ = pasteboard.setString_forType(&NSString::from_str(text), NSPasteboardTypeString);
+        return Ok(());
+    }
+
+    if !is_accessibility_trusted() {
+        return Err(VoicewinError::new(
+            ErrorKind::AccessibilityAccess,
+            "Accessibility permission is required to paste into other apps (enable it in System Settings → Privacy & Security → Accessibility).",
+        )
+        .into());
+    }
+
+    let pasteboard = NSPasteboard::generalPasteboard();
 
     // Snapshot full pasteboard.
     let snapshot = snapshot_pasteboard(&pasteboard);
@@ -222,7 +417,10 @@ pub fn paste_text_via_clipboard(text: &str, mode: InsertMode) -> anyhow::Result<
     // Small delay to ensure the target app sees clipboard update.
     thread::sleep(Duration::from_millis(50));
 
-    post_cmd_v()?;
+    match paste_keys {
+        Some(chord) => send_key_macro(std::slice::from_ref(chord))?,
+        None => post_cmd_v()?,
+    }
 
     if matches!(mode, InsertMode::PasteAndEnter) {
         thread::sleep(Duration::from_millis(50));
@@ -232,13 +430,13 @@ pub fn paste_text_via_clipboard(text: &str, mode: InsertMode) -> anyhow::Result<
     // macOS has no Shift+Insert paste convention; treat it like regular paste.
     // Nothing to do here since we already sent Cmd+V.
 
-    // Restore pasteboard after a delay, but only if the user/app hasn't changed it.
-    thread::sleep(Duration::from_millis(1000));
-
-    let current_change = pasteboard.changeCount();
-    if current_change == after_write_change || current_change == original_change {
-        restore_pasteboard(&pasteboard, &snapshot);
-    }
+    // Restore pasteboard once the target app has had time to read it, but only if the
+    // user/app hasn't changed it.
+    clipboard_restore::restore_after_paste(
+        RESTORE_DELAY,
+        || pasteboard.changeCount() != after_write_change,
+        || restore_pasteboard(&pasteboard, &snapshot),
+    );
 
     Ok(())
 }
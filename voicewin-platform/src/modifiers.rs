@@ -0,0 +1,46 @@
+//! Global modifier-key state, read without focus or a keyboard hook.
+
+/// Whether the overlay "interact" modifier is currently held: Ctrl on Windows,
+/// Cmd on macOS.
+///
+/// Linux has no portable way to query this (Wayland forbids it outright), so it always
+/// reports `false` there.
+pub fn overlay_modifier_held() -> bool {
+    imp::overlay_modifier_held()
+}
+
+#[cfg(windows)]
+mod imp {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_CONTROL};
+
+    pub(super) fn overlay_modifier_held() -> bool {
+        // High bit set = key is down right now.
+        let state = unsafe { GetAsyncKeyState(VK_CONTROL.0 as i32) };
+        (state as u16 & 0x8000) != 0
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    // kCGEventSourceStateCombinedSessionState
+    const COMBINED_SESSION_STATE: i32 = 0;
+    // kCGEventFlagMaskCommand
+    const FLAG_MASK_COMMAND: u64 = 0x0010_0000;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    unsafe extern "C" {
+        fn CGEventSourceFlagsState(state_id: i32) -> u64;
+    }
+
+    pub(super) fn overlay_modifier_held() -> bool {
+        let flags = unsafe { CGEventSourceFlagsState(COMBINED_SESSION_STATE) };
+        flags & FLAG_MASK_COMMAND != 0
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    pub(super) fn overlay_modifier_held() -> bool {
+        false
+    }
+}
@@ -0,0 +1,190 @@
+//! OS privacy permissions: microphone access everywhere, plus Accessibility on macOS
+//! (needed to synthesize the paste keystroke).
+
+pub use voicewin_core::onboarding::{PermissionState, PrivacyPane};
+
+/// Whether this app may record from the microphone.
+pub fn microphone_permission() -> PermissionState {
+    imp::microphone_permission()
+}
+
+/// Show the system microphone prompt where the OS supports one (macOS).
+///
+/// Blocks until the user answers, so call it off the UI thread. If access was already
+/// decided, or the platform has no in-app prompt, this just returns the current state.
+pub fn request_microphone_permission() -> PermissionState {
+    imp::request_microphone_permission()
+}
+
+/// Whether this app may post synthetic key events into other apps.
+pub fn accessibility_permission() -> PermissionState {
+    imp::accessibility_permission()
+}
+
+/// Open the system settings page where the user grants `pane`.
+pub fn open_privacy_settings(pane: PrivacyPane) -> anyhow::Result<()> {
+    imp::open_privacy_settings(pane)
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::{PermissionState, PrivacyPane};
+
+    pub(super) fn microphone_permission() -> PermissionState {
+        match crate::windows::microphone_block() {
+            Ok(None) => PermissionState::Granted,
+            Ok(Some(_)) => PermissionState::Denied,
+            Err(e) => {
+                tracing::debug!("microphone privacy check failed: {e:#}");
+                PermissionState::Unknown
+            }
+        }
+    }
+
+    pub(super) fn request_microphone_permission() -> PermissionState {
+        // Desktop apps cannot trigger the consent prompt; the user flips the toggle in Settings.
+        microphone_permission()
+    }
+
+    pub(super) fn accessibility_permission() -> PermissionState {
+        PermissionState::NotRequired
+    }
+
+    pub(super) fn open_privacy_settings(pane: PrivacyPane) -> anyhow::Result<()> {
+        let uri = match pane {
+            PrivacyPane::Microphone => "ms-settings:privacy-microphone",
+            PrivacyPane::Accessibility => {
+                anyhow::bail!("Windows does not need an Accessibility permission")
+            }
+        };
+        // explorer.exe exits non-zero even on success, so only spawn failures are errors.
+        std::process::Command::new("explorer.exe")
+            .arg(uri)
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("failed to open Microphone settings: {e}"))?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use block2::RcBlock;
+    use objc2::msg_send;
+    use objc2::runtime::{AnyClass, Bool};
+    use objc2_foundation::NSString;
+
+    use super::{PermissionState, PrivacyPane};
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    unsafe extern "C" {
+        static AVMediaTypeAudio: &'static NSString;
+    }
+
+    // AVAuthorizationStatus
+    const STATUS_NOT_DETERMINED: isize = 0;
+    const STATUS_RESTRICTED: isize = 1;
+    const STATUS_DENIED: isize = 2;
+    const STATUS_AUTHORIZED: isize = 3;
+
+    // Give up waiting on an unanswered prompt; the caller re-reads the state later.
+    const PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+    fn capture_device_class() -> Option<&'static AnyClass> {
+        AnyClass::get(c"AVCaptureDevice")
+    }
+
+    pub(super) fn microphone_permission() -> PermissionState {
+        let Some(cls) = capture_device_class() else {
+            return PermissionState::Unknown;
+        };
+        let status: isize =
+            unsafe { msg_send![cls, authorizationStatusForMediaType: AVMediaTypeAudio] };
+        match status {
+            STATUS_NOT_DETERMINED => PermissionState::NotDetermined,
+            STATUS_RESTRICTED | STATUS_DENIED => PermissionState::Denied,
+            STATUS_AUTHORIZED => PermissionState::Granted,
+            _ => PermissionState::Unknown,
+        }
+    }
+
+    pub(super) fn request_microphone_permission() -> PermissionState {
+        let current = microphone_permission();
+        if current != PermissionState::NotDetermined {
+            // macOS only prompts once; after that the answer lives in System Settings.
+            return current;
+        }
+        let Some(cls) = capture_device_class() else {
+            return PermissionState::Unknown;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let handler = RcBlock::new(move |granted: Bool| {
+            let _ = tx.send(granted.as_bool());
+        });
+        unsafe {
+            let _: () = msg_send![
+                cls,
+                requestAccessForMediaType: AVMediaTypeAudio,
+                completionHandler: &*handler
+            ];
+        }
+
+        match rx.recv_timeout(PROMPT_TIMEOUT) {
+            Ok(true) => PermissionState::Granted,
+            Ok(false) => PermissionState::Denied,
+            Err(_) => microphone_permission(),
+        }
+    }
+
+    pub(super) fn accessibility_permission() -> PermissionState {
+        // AX trust has no "denied" state: the app is either in the list and enabled, or not.
+        if crate::macos::macos_insert::is_accessibility_trusted() {
+            PermissionState::Granted
+        } else {
+            PermissionState::NotDetermined
+        }
+    }
+
+    pub(super) fn open_privacy_settings(pane: PrivacyPane) -> anyhow::Result<()> {
+        let (url, label) = match pane {
+            PrivacyPane::Microphone => (
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone",
+                "Microphone",
+            ),
+            PrivacyPane::Accessibility => (
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility",
+                "Accessibility",
+            ),
+        };
+        let status = std::process::Command::new("open").arg(url).status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("failed to open {label} settings"))
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    use super::{PermissionState, PrivacyPane};
+
+    pub(super) fn microphone_permission() -> PermissionState {
+        PermissionState::NotRequired
+    }
+
+    pub(super) fn request_microphone_permission() -> PermissionState {
+        PermissionState::NotRequired
+    }
+
+    pub(super) fn accessibility_permission() -> PermissionState {
+        PermissionState::NotRequired
+    }
+
+    pub(super) fn open_privacy_settings(_pane: PrivacyPane) -> anyhow::Result<()> {
+        anyhow::bail!("this platform has no privacy settings page for VoiceWin")
+    }
+}
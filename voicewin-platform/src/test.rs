@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use voicewin_core::types::{AppIdentity, InsertMode};
+use voicewin_core::types::{AppIdentity, InsertMode, InsertionStrategy};
 use voicewin_engine::traits::{AppContextProvider, ContextSnapshot, Inserter};
 
 #[derive(Debug, Clone)]
@@ -38,6 +38,26 @@ impl Inserter for StdoutInserter {
         println!("[insert:{:?}] {}", mode, text);
         Ok(())
     }
+
+    async fn insert_with_strategy(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        strategy: InsertionStrategy,
+    ) -> anyhow::Result<()> {
+        println!("[insert:{:?}:{:?}] {}", mode, strategy, text);
+        Ok(())
+    }
+
+    async fn paste_with_keys(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        keys: voicewin_core::key_macro::KeyChord,
+    ) -> anyhow::Result<()> {
+        println!("[insert:{:?}:{}] {}", mode, String::from(keys), text);
+        Ok(())
+    }
 }
 
 #[derive(Debug, Default)]
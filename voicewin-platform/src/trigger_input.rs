@@ -0,0 +1,637 @@
+//! Extra mouse buttons and USB foot pedals as dictation triggers.
+//!
+//! Windows uses a low-level mouse hook for the side buttons and Raw Input for pedals;
+//! macOS a CGEvent tap for the buttons and an IOHIDManager for pedals. Linux has neither
+//! without root or a compositor protocol, so triggers are not available there.
+//!
+//! Pedals are recognized by vendor/product id ([`KNOWN_PEDALS`]) and report their pedals
+//! as a bit mask; pedals that emulate a keyboard work as ordinary hotkeys instead.
+
+use std::sync::Arc;
+
+/// A button that can start or stop dictation besides the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriggerInput {
+    /// The "back" side button (XButton1).
+    Mouse4,
+    /// The "forward" side button (XButton2).
+    Mouse5,
+    /// Pedal `n` of a foot pedal, counted from 1 (left to right on common three-pedal models).
+    Pedal(u8),
+}
+
+impl TriggerInput {
+    /// Parse a name as used in hotkey settings ("Mouse4", "XButton2", "Pedal1"), ignoring case.
+    /// `None` means the string is a keyboard shortcut.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        match name.as_str() {
+            "mouse4" | "xbutton1" => Some(Self::Mouse4),
+            "mouse5" | "xbutton2" => Some(Self::Mouse5),
+            _ => {
+                let n: u8 = name.strip_prefix("pedal")?.parse().ok()?;
+                (1..=MAX_PEDALS).contains(&n).then_some(Self::Pedal(n))
+            }
+        }
+    }
+
+    /// The canonical name, which [`TriggerInput::parse`] accepts.
+    pub fn name(self) -> String {
+        match self {
+            Self::Mouse4 => "Mouse4".to_string(),
+            Self::Mouse5 => "Mouse5".to_string(),
+            Self::Pedal(n) => format!("Pedal{n}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerEvent {
+    pub input: TriggerInput,
+    pub pressed: bool,
+}
+
+/// USB vendor and product ids of supported foot pedals.
+pub const KNOWN_PEDALS: &[(u16, u16)] = &[
+    // VEC Infinity IN-USB-1/IN-USB-2 (P.I. Engineering).
+    (0x05f3, 0x00ff),
+];
+
+// Pedal reports are bit masks; bits past this are not pedals on any model we know.
+const MAX_PEDALS: u8 = 32;
+
+/// Keeps the input hooks alive; dropping it removes them.
+pub struct TriggerWatch {
+    _watch: imp::Watch,
+}
+
+/// Call `on_event` for every press and release of a side mouse button or pedal. Returning
+/// true consumes a mouse button event, so the app under the pointer doesn't also go "back";
+/// pedal reports can't be consumed.
+///
+/// Runs on a dedicated thread; `on_event` must return quickly (Windows drops hooks that
+/// stall for more than a few hundred milliseconds).
+pub fn watch_trigger_inputs(
+    on_event: impl Fn(TriggerEvent) -> bool + Send + Sync + 'static,
+) -> anyhow::Result<TriggerWatch> {
+    let watch = imp::watch(Arc::new(on_event))?;
+    Ok(TriggerWatch { _watch: watch })
+}
+
+type OnEvent = Arc<dyn Fn(TriggerEvent) -> bool + Send + Sync>;
+
+/// Pedal presses and releases between two reports of the same device (report id excluded).
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+fn pedal_changes(previous: &[u8], report: &[u8]) -> Vec<TriggerEvent> {
+    let mut events = Vec::new();
+    for (i, byte) in report.iter().enumerate() {
+        let changed = byte ^ previous.get(i).copied().unwrap_or(0);
+        for bit in 0..8 {
+            let n = i * 8 + bit + 1;
+            if changed & (1 << bit) == 0 || n > usize::from(MAX_PEDALS) {
+                continue;
+            }
+            events.push(TriggerEvent {
+                input: TriggerInput::Pedal(n as u8),
+                pressed: byte & (1 << bit) != 0,
+            });
+        }
+    }
+    events
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::ffi::c_void;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use windows::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::Input::{
+        GetRawInputData, GetRawInputDeviceInfoW, GetRawInputDeviceList, HRAWINPUT, RAWINPUT,
+        RAWINPUTDEVICE, RAWINPUTDEVICELIST, RAWINPUTHEADER, RID_DEVICE_INFO, RID_INPUT,
+        RIDEV_INPUTSINK, RIDI_DEVICEINFO, RIM_TYPEHID, RegisterRawInputDevices,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, CreateWindowExW, DestroyWindow, DispatchMessageW, GetMessageW,
+        HWND_MESSAGE, MSG, MSLLHOOKSTRUCT, PM_NOREMOVE, PeekMessageW, PostThreadMessageW,
+        SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx, WH_MOUSE_LL, WINDOW_EX_STYLE,
+        WINDOW_STYLE, WM_INPUT, WM_QUIT, WM_USER, WM_XBUTTONDOWN, WM_XBUTTONUP,
+    };
+    use windows::core::w;
+
+    use super::{KNOWN_PEDALS, OnEvent, TriggerEvent, TriggerInput, pedal_changes};
+
+    thread_local! {
+        // Hook callbacks carry no user data; the hook thread owns exactly one watcher.
+        static ON_EVENT: RefCell<Option<OnEvent>> = RefCell::new(None);
+    }
+
+    fn is_known_pedal(vendor_id: u32, product_id: u32) -> bool {
+        KNOWN_PEDALS
+            .iter()
+            .any(|&(v, p)| u32::from(v) == vendor_id && u32::from(p) == product_id)
+    }
+
+    pub(super) struct Watch {
+        thread_id: u32,
+        thread: Option<thread::JoinHandle<()>>,
+    }
+
+    pub(super) fn watch(on_event: OnEvent) -> anyhow::Result<Watch> {
+        let (tx, rx) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("trigger-input".into())
+            .spawn(move || {
+                ON_EVENT.with(|f| *f.borrow_mut() = Some(on_event.clone()));
+                let mut msg = MSG::default();
+                unsafe {
+                    let hook = match SetWindowsHookExW(WH_MOUSE_LL, Some(on_mouse), None, 0) {
+                        Ok(hook) => hook,
+                        Err(e) => {
+                            let _ = tx.send(Err(anyhow::anyhow!("SetWindowsHookExW failed: {e}")));
+                            return;
+                        }
+                    };
+                    // Raw Input needs a window to deliver to, even with no UI.
+                    let sink = CreateWindowExW(
+                        WINDOW_EX_STYLE(0),
+                        w!("STATIC"),
+                        w!("voicewin-trigger-input"),
+                        WINDOW_STYLE(0),
+                        0,
+                        0,
+                        0,
+                        0,
+                        Some(HWND_MESSAGE),
+                        None,
+                        None,
+                        None,
+                    )
+                    .ok();
+                    if let Some(sink) = sink {
+                        register_pedals(sink);
+                    }
+
+                    // Create the message queue before `Drop` can post `WM_QUIT` to it.
+                    let _ = PeekMessageW(&mut msg, None, WM_USER, WM_USER, PM_NOREMOVE);
+                    let _ = tx.send(Ok(GetCurrentThreadId()));
+
+                    let mut pedals = Pedals::default();
+                    while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                        if msg.message == WM_INPUT {
+                            for event in pedals.read(HRAWINPUT(msg.lParam.0 as *mut c_void)) {
+                                on_event(event);
+                            }
+                        }
+                        let _ = TranslateMessage(&msg);
+                        DispatchMessageW(&msg);
+                    }
+                    if let Some(sink) = sink {
+                        let _ = DestroyWindow(sink);
+                    }
+                    let _ = UnhookWindowsHookEx(hook);
+                }
+            })?;
+
+        let thread_id = rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("trigger input thread exited"))??;
+        Ok(Watch {
+            thread_id,
+            thread: Some(thread),
+        })
+    }
+
+    unsafe extern "system" fn on_mouse(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        let message = wparam.0 as u32;
+        if code >= 0 && (message == WM_XBUTTONDOWN || message == WM_XBUTTONUP) {
+            let info = unsafe { &*(lparam.0 as *const MSLLHOOKSTRUCT) };
+            // The high word names the button: 1 = XButton1, 2 = XButton2.
+            let input = match info.mouseData >> 16 {
+                1 => Some(TriggerInput::Mouse4),
+                2 => Some(TriggerInput::Mouse5),
+                _ => None,
+            };
+            let consumed = input.is_some_and(|input| {
+                ON_EVENT.with(|f| {
+                    f.borrow().as_ref().is_some_and(|on_event| {
+                        on_event(TriggerEvent {
+                            input,
+                            pressed: message == WM_XBUTTONDOWN,
+                        })
+                    })
+                })
+            });
+            if consumed {
+                return LRESULT(1);
+            }
+        }
+        unsafe { CallNextHookEx(None, code, wparam, lparam) }
+    }
+
+    /// Ask for input from the usage pages of pedals plugged in now. Raw Input has no
+    /// vendor filter, so reports are checked against `KNOWN_PEDALS` again when they arrive.
+    unsafe fn register_pedals(sink: HWND) {
+        let mut count = 0u32;
+        let entry_size = size_of::<RAWINPUTDEVICELIST>() as u32;
+        unsafe { GetRawInputDeviceList(None, &mut count, entry_size) };
+        let mut devices = vec![RAWINPUTDEVICELIST::default(); count as usize];
+        if unsafe { GetRawInputDeviceList(Some(devices.as_mut_ptr()), &mut count, entry_size) }
+            == u32::MAX
+        {
+            return;
+        }
+
+        let mut wanted: Vec<RAWINPUTDEVICE> = Vec::new();
+        for device in devices.iter().filter(|d| d.dwType == RIM_TYPEHID) {
+            let Some(info) = (unsafe { device_info(device.hDevice) }) else {
+                continue;
+            };
+            let hid = unsafe { info.Anonymous.hid };
+            if !is_known_pedal(hid.dwVendorId, hid.dwProductId)
+                || wanted
+                    .iter()
+                    .any(|r| r.usUsagePage == hid.usUsagePage && r.usUsage == hid.usUsage)
+            {
+                continue;
+            }
+            wanted.push(RAWINPUTDEVICE {
+                usUsagePage: hid.usUsagePage,
+                usUsage: hid.usUsage,
+                dwFlags: RIDEV_INPUTSINK,
+                hwndTarget: sink,
+            });
+        }
+        if wanted.is_empty() {
+            return;
+        }
+        if let Err(e) =
+            unsafe { RegisterRawInputDevices(&wanted, size_of::<RAWINPUTDEVICE>() as u32) }
+        {
+            tracing::warn!("failed to register foot pedal input: {e}");
+        }
+    }
+
+    unsafe fn device_info(device: HANDLE) -> Option<RID_DEVICE_INFO> {
+        let mut info = RID_DEVICE_INFO {
+            cbSize: size_of::<RID_DEVICE_INFO>() as u32,
+            ..Default::default()
+        };
+        let mut size = info.cbSize;
+        let read = unsafe {
+            GetRawInputDeviceInfoW(
+                Some(device),
+                RIDI_DEVICEINFO,
+                Some(&mut info as *mut _ as *mut c_void),
+                &mut size,
+            )
+        };
+        (read != u32::MAX && read != 0).then_some(info)
+    }
+
+    /// The last report of each pedal, by device handle.
+    #[derive(Default)]
+    struct Pedals {
+        is_pedal: HashMap<isize, bool>,
+        last: HashMap<isize, Vec<u8>>,
+    }
+
+    impl Pedals {
+        unsafe fn read(&mut self, input: HRAWINPUT) -> Vec<TriggerEvent> {
+            let header_size = size_of::<RAWINPUTHEADER>() as u32;
+            let mut size = 0u32;
+            unsafe { GetRawInputData(input, RID_INPUT, None, &mut size, header_size) };
+            // u64 elements keep the buffer aligned for `RAWINPUT`.
+            let mut buf = vec![0u64; (size as usize).div_ceil(8)];
+            let read = unsafe {
+                GetRawInputData(
+                    input,
+                    RID_INPUT,
+                    Some(buf.as_mut_ptr() as *mut c_void),
+                    &mut size,
+                    header_size,
+                )
+            };
+            if read == u32::MAX || read == 0 {
+                return Vec::new();
+            }
+
+            let raw = unsafe { &*(buf.as_ptr() as *const RAWINPUT) };
+            if raw.header.dwType != RIM_TYPEHID.0 {
+                return Vec::new();
+            }
+            let device = raw.header.hDevice;
+            let key = device.0 as isize;
+            let is_pedal = *self.is_pedal.entry(key).or_insert_with(|| {
+                unsafe { device_info(device) }.is_some_and(|info| {
+                    let hid = unsafe { info.Anonymous.hid };
+                    is_known_pedal(hid.dwVendorId, hid.dwProductId)
+                })
+            });
+            if !is_pedal {
+                return Vec::new();
+            }
+
+            let hid = unsafe { &raw.data.hid };
+            let report_size = hid.dwSizeHid as usize;
+            let bytes = unsafe {
+                std::slice::from_raw_parts(
+                    hid.bRawData.as_ptr(),
+                    report_size * hid.dwCount as usize,
+                )
+            };
+            let last = self.last.entry(key).or_default();
+            let mut events = Vec::new();
+            // Windows always puts the report id first (0 for devices without ids).
+            for report in bytes.chunks(report_size.max(1)).filter(|r| r.len() > 1) {
+                events.extend(pedal_changes(last, &report[1..]));
+                *last = report[1..].to_vec();
+            }
+            events
+        }
+    }
+
+    impl Drop for Watch {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::collections::HashMap;
+    use std::ffi::c_void;
+    use std::sync::{Mutex, mpsc};
+    use std::thread;
+
+    use core_foundation::array::{CFArray, CFArrayRef};
+    use core_foundation::base::{CFAllocatorRef, CFRelease, TCFType, kCFAllocatorDefault};
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::number::CFNumber;
+    use core_foundation::runloop::{CFRunLoop, kCFRunLoopCommonModes, kCFRunLoopDefaultMode};
+    use core_foundation::string::{CFString, CFStringRef};
+    use core_graphics::event::{
+        CGEventTap, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement, CGEventType,
+        EventField,
+    };
+
+    use super::{KNOWN_PEDALS, OnEvent, TriggerEvent, TriggerInput, pedal_changes};
+
+    type IOHIDManagerRef = *mut c_void;
+    type IOHIDReportCallback = extern "C" fn(
+        context: *mut c_void,
+        result: i32,
+        sender: *mut c_void,
+        report_type: u32,
+        report_id: u32,
+        report: *mut u8,
+        report_length: isize,
+    );
+
+    // kIOHIDOptionsTypeNone
+    const OPTIONS_NONE: u32 = 0;
+    // kIOReturnSuccess
+    const IO_RETURN_SUCCESS: i32 = 0;
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IOHIDManagerCreate(allocator: CFAllocatorRef, options: u32) -> IOHIDManagerRef;
+        fn IOHIDManagerSetDeviceMatchingMultiple(manager: IOHIDManagerRef, multiple: CFArrayRef);
+        fn IOHIDManagerRegisterInputReportCallback(
+            manager: IOHIDManagerRef,
+            callback: IOHIDReportCallback,
+            context: *mut c_void,
+        );
+        fn IOHIDManagerScheduleWithRunLoop(
+            manager: IOHIDManagerRef,
+            run_loop: core_foundation::runloop::CFRunLoopRef,
+            mode: CFStringRef,
+        );
+        fn IOHIDManagerOpen(manager: IOHIDManagerRef, options: u32) -> i32;
+        fn IOHIDManagerClose(manager: IOHIDManagerRef, options: u32) -> i32;
+    }
+
+    pub(super) struct Watch {
+        run_loop: SendRunLoop,
+        thread: Option<thread::JoinHandle<()>>,
+    }
+
+    struct SendRunLoop(CFRunLoop);
+
+    // SAFETY: CFRunLoopStop may be called from any thread; nothing else is used off the
+    // watch thread.
+    unsafe impl Send for SendRunLoop {}
+
+    /// Shared with the IOKit callback through its context pointer.
+    struct PedalState {
+        on_event: OnEvent,
+        last: Mutex<HashMap<usize, Vec<u8>>>,
+    }
+
+    pub(super) fn watch(on_event: OnEvent) -> anyhow::Result<Watch> {
+        let (tx, rx) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("trigger-input".into())
+            .spawn(move || {
+                let run_loop = CFRunLoop::get_current();
+
+                // Side buttons: needs the Accessibility permission, which pasting needs too.
+                let tap_callback = {
+                    let on_event = on_event.clone();
+                    move |_proxy, event_type, event: &core_graphics::event::CGEvent| {
+                        let input = match event
+                            .get_integer_value_field(EventField::MOUSE_EVENT_BUTTON_NUMBER)
+                        {
+                            3 => TriggerInput::Mouse4,
+                            4 => TriggerInput::Mouse5,
+                            _ => return None,
+                        };
+                        let pressed = matches!(event_type, CGEventType::OtherMouseDown);
+                        if on_event(TriggerEvent { input, pressed }) {
+                            // A null event is how a tap drops the original.
+                            event.set_type(CGEventType::Null);
+                        }
+                        None
+                    }
+                };
+                let tap = CGEventTap::new(
+                    CGEventTapLocation::Session,
+                    CGEventTapPlacement::HeadInsertEventTap,
+                    CGEventTapOptions::Default,
+                    vec![CGEventType::OtherMouseDown, CGEventType::OtherMouseUp],
+                    tap_callback,
+                );
+                match &tap {
+                    Ok(tap) => match tap.mach_port.create_runloop_source(0) {
+                        Ok(source) => unsafe {
+                            run_loop.add_source(&source, kCFRunLoopCommonModes);
+                            tap.enable();
+                        },
+                        Err(()) => tracing::warn!("mouse button tap has no run loop source"),
+                    },
+                    Err(()) => tracing::warn!(
+                        "mouse buttons unavailable: the Accessibility permission is missing"
+                    ),
+                }
+
+                // Pedals: IOKit keeps matching devices as they are plugged in and out.
+                let state = Box::into_raw(Box::new(PedalState {
+                    on_event,
+                    last: Mutex::new(HashMap::new()),
+                }));
+                let manager = unsafe { open_pedals(&run_loop, state) };
+
+                let _ = tx.send(Ok(SendRunLoop(run_loop)));
+                CFRunLoop::run_current();
+
+                if let Some(manager) = manager {
+                    unsafe {
+                        IOHIDManagerClose(manager, OPTIONS_NONE);
+                        CFRelease(manager as *const c_void);
+                    }
+                }
+                drop(tap);
+                drop(unsafe { Box::from_raw(state) });
+            })?;
+
+        let run_loop = rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("trigger input thread exited"))??;
+        Ok(Watch {
+            run_loop,
+            thread: Some(thread),
+        })
+    }
+
+    unsafe fn open_pedals(run_loop: &CFRunLoop, state: *mut PedalState) -> Option<IOHIDManagerRef> {
+        let matching: Vec<CFDictionary<CFString, CFNumber>> = KNOWN_PEDALS
+            .iter()
+            .map(|&(vendor, product)| {
+                CFDictionary::from_CFType_pairs(&[
+                    (CFString::new("VendorID"), CFNumber::from(i32::from(vendor))),
+                    (
+                        CFString::new("ProductID"),
+                        CFNumber::from(i32::from(product)),
+                    ),
+                ])
+            })
+            .collect();
+        let matching = CFArray::from_CFTypes(&matching);
+
+        unsafe {
+            let manager = IOHIDManagerCreate(kCFAllocatorDefault, OPTIONS_NONE);
+            if manager.is_null() {
+                return None;
+            }
+            IOHIDManagerSetDeviceMatchingMultiple(manager, matching.as_concrete_TypeRef());
+            IOHIDManagerRegisterInputReportCallback(manager, on_report, state as *mut c_void);
+            IOHIDManagerScheduleWithRunLoop(
+                manager,
+                run_loop.as_concrete_TypeRef(),
+                kCFRunLoopDefaultMode,
+            );
+            let opened = IOHIDManagerOpen(manager, OPTIONS_NONE);
+            if opened != IO_RETURN_SUCCESS {
+                // Usually the Input Monitoring permission.
+                tracing::warn!("foot pedals unavailable: IOHIDManagerOpen returned {opened:#x}");
+            }
+            Some(manager)
+        }
+    }
+
+    extern "C" fn on_report(
+        context: *mut c_void,
+        _result: i32,
+        sender: *mut c_void,
+        _report_type: u32,
+        report_id: u32,
+        report: *mut u8,
+        report_length: isize,
+    ) {
+        if context.is_null() || report.is_null() || report_length <= 0 {
+            return;
+        }
+        let state = unsafe { &*(context as *const PedalState) };
+        let report = unsafe { std::slice::from_raw_parts(report, report_length as usize) };
+        // The report id, when the device uses them, is the first byte.
+        let report = if report_id != 0 { &report[1..] } else { report };
+
+        let events = {
+            let mut last = state.last.lock().unwrap_or_else(|p| p.into_inner());
+            let last = last.entry(sender as usize).or_default();
+            let events = pedal_changes(last, report);
+            *last = report.to_vec();
+            events
+        };
+        for event in events {
+            (state.on_event)(event);
+        }
+    }
+
+    impl Drop for Watch {
+        fn drop(&mut self) {
+            self.run_loop.0.stop();
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    use super::OnEvent;
+
+    pub(super) struct Watch;
+
+    pub(super) fn watch(_on_event: OnEvent) -> anyhow::Result<Watch> {
+        Err(anyhow::anyhow!(
+            "mouse button and foot pedal triggers are not available on this platform"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_trigger_names() {
+        assert_eq!(TriggerInput::parse("Mouse4"), Some(TriggerInput::Mouse4));
+        assert_eq!(
+            TriggerInput::parse(" xbutton2 "),
+            Some(TriggerInput::Mouse5)
+        );
+        assert_eq!(TriggerInput::parse("PEDAL2"), Some(TriggerInput::Pedal(2)));
+        assert_eq!(TriggerInput::parse("Pedal0"), None);
+        assert_eq!(TriggerInput::parse("Ctrl+Space"), None);
+        for input in [TriggerInput::Mouse5, TriggerInput::Pedal(3)] {
+            assert_eq!(TriggerInput::parse(&input.name()), Some(input));
+        }
+    }
+
+    #[test]
+    fn pedal_reports_become_presses_and_releases() {
+        let pedal = |n, pressed| TriggerEvent {
+            input: TriggerInput::Pedal(n),
+            pressed,
+        };
+        assert_eq!(pedal_changes(&[], &[0b010, 0]), vec![pedal(2, true)]);
+        assert_eq!(
+            pedal_changes(&[0b010, 0], &[0b101, 0]),
+            vec![pedal(1, true), pedal(2, false), pedal(3, true)]
+        );
+        assert_eq!(pedal_changes(&[0, 0], &[0, 1]), vec![pedal(9, true)]);
+        assert!(pedal_changes(&[0b1], &[0b1]).is_empty());
+    }
+}
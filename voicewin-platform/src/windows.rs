@@ -5,14 +5,38 @@
 #[path = "windows_foreground.rs"]
 mod windows_foreground;
 
-use voicewin_core::types::{AppIdentity, InsertMode};
-use voicewin_engine::traits::{AppContextProvider, ContextSnapshot, Inserter};
+pub(crate) use windows_foreground::get_foreground_app_identity;
+
+use voicewin_core::types::{AppIdentity, InsertMode, InsertionStrategy};
+use voicewin_engine::traits::{
+    AppContextProvider, ContextSnapshot, Inserter, unsupported_strategy,
+};
 
 use clipboard_win::get_clipboard_string;
 
 #[path = "windows_insert.rs"]
 mod windows_insert;
 
+#[path = "windows_caret.rs"]
+mod windows_caret;
+
+#[path = "windows_uia_insert.rs"]
+mod windows_uia_insert;
+
+#[path = "windows_elevation.rs"]
+mod windows_elevation;
+
+#[path = "windows_ime.rs"]
+mod windows_ime;
+
+#[path = "windows_privacy.rs"]
+mod windows_privacy;
+
+pub use windows_privacy::{MicrophoneBlock, microphone_block};
+
+/// Characters read before the caret; enough to see the previous word and punctuation.
+const CARET_CONTEXT_CHARS: i32 = 3;
+
 #[derive(Debug, Default)]
 pub struct WindowsContextProvider;
 
@@ -42,6 +66,10 @@ impl AppContextProvider for WindowsContextProvider {
         ctx.clipboard = get_clipboard_string().ok();
         Ok(ctx)
     }
+
+    async fn text_before_caret(&self) -> anyhow::Result<Option<String>> {
+        windows_caret::text_before_caret(CARET_CONTEXT_CHARS)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -49,8 +77,70 @@ pub struct WindowsInserter;
 
 #[async_trait::async_trait]
 impl Inserter for WindowsInserter {
+    #[tracing::instrument(name = "insert.clipboard_paste", skip_all)]
     async fn insert(&self, text: &str, mode: InsertMode) -> anyhow::Result<()> {
         // MVP (reliable): clipboard swap + Ctrl+V + optional Enter + restore.
-        windows_insert::paste_text_via_clipboard(text, mode)
+        let _ime = prepare_input(mode)?;
+        windows_insert::paste_text_via_clipboard(text, mode, None)
+    }
+
+    #[tracing::instrument(name = "insert.clipboard_paste", skip_all)]
+    async fn paste_with_keys(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        keys: voicewin_core::key_macro::KeyChord,
+    ) -> anyhow::Result<()> {
+        let _ime = prepare_input(mode)?;
+        windows_insert::paste_text_via_clipboard(text, mode, Some(&keys))
+    }
+
+    async fn insert_with_strategy(
+        &self,
+        text: &str,
+        mode: InsertMode,
+        strategy: InsertionStrategy,
+    ) -> anyhow::Result<()> {
+        match strategy {
+            InsertionStrategy::ClipboardPaste => self.insert(text, mode).await,
+            InsertionStrategy::TypeKeys => {
+                let _ime = prepare_input(mode)?;
+                windows_insert::type_text(text, mode)
+            }
+            // UI Automation is held to the same rules as input.
+            InsertionStrategy::UiaDirect => {
+                let _ime = prepare_input(mode)?;
+                windows_uia_insert::insert_at_caret(text, mode)
+            }
+            other => Err(unsupported_strategy(other)),
+        }
+    }
+
+    async fn focus_target(&self, app: &AppIdentity) -> anyhow::Result<()> {
+        match app.window_handle {
+            Some(handle) => windows_foreground::activate_window(handle),
+            None => Ok(()),
+        }
+    }
+
+    async fn focus_app(&self, process_name: &str) -> anyhow::Result<()> {
+        let handle = windows_foreground::find_app_window(process_name).ok_or_else(|| {
+            anyhow::anyhow!("{process_name} is not running; the text was not inserted.")
+        })?;
+        windows_foreground::activate_window(handle)
+    }
+
+    async fn send_keys(&self, keys: &[voicewin_core::key_macro::KeyChord]) -> anyhow::Result<()> {
+        windows_insert::send_key_macro(keys)
+    }
+}
+
+/// Copying works whatever is in front; anything that sends input needs the window to accept
+/// it, and any IME composition committed first. Keep the returned guard until the text is in.
+fn prepare_input(mode: InsertMode) -> anyhow::Result<Option<windows_ime::SuspendedIme>> {
+    if mode == InsertMode::ClipboardOnly {
+        return Ok(None);
     }
+    windows_elevation::ensure_foreground_accepts_input()?;
+    Ok(windows_ime::suspend_ime())
 }
@@ -0,0 +1,45 @@
+// Text before the caret via UI Automation (works for standard edit controls, browsers, and
+// most Electron apps; returns `None` where the focused element has no text pattern).
+
+use windows::Win32::System::Com::{
+    CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx,
+};
+use windows::Win32::UI::Accessibility::{
+    CUIAutomation, IUIAutomation, IUIAutomationTextPattern, TextPatternRangeEndpoint_End,
+    TextPatternRangeEndpoint_Start, TextUnit_Character, UIA_TextPatternId,
+};
+
+pub fn text_before_caret(max_chars: i32) -> anyhow::Result<Option<String>> {
+    unsafe {
+        // Already-initialized threads report S_FALSE / RPC_E_CHANGED_MODE; both are fine here.
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+
+        let automation: IUIAutomation =
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)?;
+        let focused = automation.GetFocusedElement()?;
+        let Ok(pattern) =
+            focused.GetCurrentPatternAs::<IUIAutomationTextPattern>(UIA_TextPatternId)
+        else {
+            return Ok(None);
+        };
+
+        let selection = pattern.GetSelection()?;
+        if selection.Length()? < 1 {
+            return Ok(None);
+        }
+        let range = selection.GetElement(0)?;
+
+        // Collapse to the start of the selection (the caret), then extend backwards.
+        range.MoveEndpointByRange(
+            TextPatternRangeEndpoint_End,
+            &range,
+            TextPatternRangeEndpoint_Start,
+        )?;
+        range.MoveEndpointByUnit(
+            TextPatternRangeEndpoint_Start,
+            TextUnit_Character,
+            -max_chars,
+        )?;
+        Ok(Some(range.GetText(max_chars)?.to_string()))
+    }
+}
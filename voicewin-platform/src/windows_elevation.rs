@@ -0,0 +1,68 @@
+// Elevated (run as administrator) foreground windows. Windows drops synthetic input sent
+// from a normal process to an elevated one (UIPI) without reporting an error, so pasting into
+// an admin terminal would look like it worked while nothing arrived.
+
+use std::ffi::c_void;
+
+use voicewin_core::error::{ErrorKind, VoicewinError};
+use windows::Win32::Foundation::{CloseHandle, ERROR_ACCESS_DENIED, HANDLE};
+use windows::Win32::Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+/// Fails with `ErrorKind::ElevatedTarget` when keystrokes can't reach the foreground window:
+/// it belongs to an elevated process and we are not elevated ourselves.
+pub fn ensure_foreground_accepts_input() -> anyhow::Result<()> {
+    if !foreground_is_elevated() || unsafe { token_is_elevated(GetCurrentProcess()) } == Some(true)
+    {
+        return Ok(());
+    }
+    Err(VoicewinError::new(
+        ErrorKind::ElevatedTarget,
+        "The window in front is running as administrator, so Windows blocks VoiceWin from typing into it. Copy the text and paste it yourself, or run VoiceWin as administrator too.",
+    )
+    .into())
+}
+
+fn foreground_is_elevated() -> bool {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0.is_null() {
+            return false;
+        }
+        let mut pid: u32 = 0;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        // When in doubt, let the insertion go ahead.
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return false;
+        };
+        let elevated = token_is_elevated(process);
+        let _ = CloseHandle(process);
+        elevated.unwrap_or(false)
+    }
+}
+
+/// `None` when the token can't be queried for a reason other than access.
+unsafe fn token_is_elevated(process: HANDLE) -> Option<bool> {
+    unsafe {
+        let mut token = HANDLE::default();
+        if let Err(e) = OpenProcessToken(process, TOKEN_QUERY, &mut token) {
+            // Elevated processes usually don't let a normal one open their token at all.
+            return (e.code() == ERROR_ACCESS_DENIED.to_hresult()).then_some(true);
+        }
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut len = 0u32;
+        let res = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut len,
+        );
+        let _ = CloseHandle(token);
+        res.ok()?;
+        Some(elevation.TokenIsElevated != 0)
+    }
+}
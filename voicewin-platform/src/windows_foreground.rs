@@ -3,18 +3,25 @@
 
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
+use std::thread;
+use std::time::Duration;
 
+use voicewin_core::error::{ErrorKind, VoicewinError};
 use voicewin_core::types::{AppIdentity, WindowTitle};
-use windows::core::PWSTR;
 use windows::Win32::Foundation::{CloseHandle, HWND};
 use windows::Win32::System::ProcessStatus::K32GetModuleFileNameExW;
 use windows::Win32::System::Threading::{
-    OpenProcess, QueryFullProcessImageNameW, PROCESS_QUERY_INFORMATION,
-    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_NAME_WIN32,
+    AttachThreadInput, GetCurrentThreadId, OpenProcess, PROCESS_NAME_WIN32,
+    PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION, QueryFullProcessImageNameW,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
+    FindWindowExW, GW_OWNER, GetForegroundWindow, GetWindow, GetWindowTextLengthW, GetWindowTextW,
+    GetWindowThreadProcessId, IsIconic, IsWindow, IsWindowVisible, SW_RESTORE, SetForegroundWindow,
+    ShowWindow,
 };
+use windows::core::{PCWSTR, PWSTR};
+
+use crate::foreground::process_name_matches;
 
 pub fn get_foreground_app_identity() -> anyhow::Result<AppIdentity> {
     unsafe {
@@ -38,6 +45,7 @@ pub fn get_foreground_app_identity() -> anyhow::Result<AppIdentity> {
         });
 
         let mut app = AppIdentity::new();
+        app.window_handle = Some(hwnd.0 as usize as u64);
         if let Some(path) = exe_path {
             app = app.with_exe_path(path);
         }
@@ -52,6 +60,78 @@ pub fn get_foreground_app_identity() -> anyhow::Result<AppIdentity> {
     }
 }
 
+/// Make `handle` (from `AppIdentity::window_handle`) the foreground window again.
+pub fn activate_window(handle: u64) -> anyhow::Result<()> {
+    unsafe {
+        let hwnd = HWND(handle as usize as *mut core::ffi::c_void);
+        if GetForegroundWindow() == hwnd {
+            return Ok(());
+        }
+        if !IsWindow(Some(hwnd)).as_bool() {
+            return Err(VoicewinError::new(
+                ErrorKind::TargetClosed,
+                "The window you were dictating into was closed; the text was not inserted.",
+            )
+            .into());
+        }
+
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        if !SetForegroundWindow(hwnd).as_bool() {
+            // Windows only lets the foreground thread hand over focus; borrow its input state.
+            let fg_thread = GetWindowThreadProcessId(GetForegroundWindow(), None);
+            let own_thread = GetCurrentThreadId();
+            let _ = AttachThreadInput(own_thread, fg_thread, true);
+            let _ = SetForegroundWindow(hwnd);
+            let _ = AttachThreadInput(own_thread, fg_thread, false);
+        }
+
+        // Activation completes asynchronously.
+        for _ in 0..10 {
+            if GetForegroundWindow() == hwnd {
+                return Ok(());
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        Err(anyhow::anyhow!(
+            "Could not switch back to the window you were dictating into; the text was not inserted."
+        ))
+    }
+}
+
+/// The frontmost visible top-level window of a running app, by process name.
+pub fn find_app_window(process_name: &str) -> Option<u64> {
+    unsafe {
+        // Top-level windows are enumerated in Z order, so the first match is the one the
+        // user saw last.
+        let mut after: Option<HWND> = None;
+        loop {
+            let hwnd = FindWindowExW(None, after, PCWSTR::null(), PCWSTR::null()).ok()?;
+            after = Some(hwnd);
+
+            if !IsWindowVisible(hwnd).as_bool() || GetWindowTextLengthW(hwnd) == 0 {
+                continue;
+            }
+            // Skip tool windows and dialogs owned by another window.
+            if GetWindow(hwnd, GW_OWNER).is_ok_and(|owner| !owner.0.is_null()) {
+                continue;
+            }
+
+            let mut pid: u32 = 0;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            let name = get_process_exe_path(pid).ok().and_then(|p| {
+                std::path::Path::new(&p)
+                    .file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+            });
+            if name.is_some_and(|n| process_name_matches(&n, process_name)) {
+                return Some(hwnd.0 as usize as u64);
+            }
+        }
+    }
+}
+
 fn get_window_title(hwnd: HWND) -> anyhow::Result<String> {
     unsafe {
         let len = GetWindowTextLengthW(hwnd);
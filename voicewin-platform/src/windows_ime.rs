@@ -0,0 +1,84 @@
+// IME composition (Chinese, Japanese, Korean input) before inserting. Pasting while the
+// focused window's IME still holds uncommitted text mixes the two: the paste lands inside the
+// composition, or the composition is committed on top of it.
+//
+// The input context belongs to the target process, so ImmGetContext can't reach it from here.
+// The IME's default window answers WM_IME_CONTROL across processes, though, and closing the
+// IME through it commits (or, for some IMEs, cancels) the composition. It is reopened after
+// the insertion, so the user keeps typing in the same input mode.
+
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::Input::Ime::ImmGetDefaultIMEWnd;
+use windows::Win32::UI::WindowsAndMessaging::{
+    GUITHREADINFO, GetForegroundWindow, GetGUIThreadInfo, GetWindowThreadProcessId,
+    SMTO_ABORTIFHUNG, SendMessageTimeoutW, WM_IME_CONTROL,
+};
+
+// imm.h
+const IMC_GETOPENSTATUS: usize = 0x0005;
+const IMC_SETOPENSTATUS: usize = 0x0006;
+
+// An IME that doesn't answer quickly is left alone rather than holding up the insertion.
+const IME_TIMEOUT_MS: u32 = 200;
+
+/// The focused window's IME, closed for the duration of an insertion; reopened on drop.
+pub struct SuspendedIme {
+    ime_window: HWND,
+}
+
+impl Drop for SuspendedIme {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = ime_control(self.ime_window, IMC_SETOPENSTATUS, 1);
+        }
+    }
+}
+
+/// Close the focused window's IME if it is open, committing any composition in progress.
+/// `None` when there was nothing to close.
+pub fn suspend_ime() -> Option<SuspendedIme> {
+    unsafe {
+        let ime_window = ImmGetDefaultIMEWnd(focused_window()?);
+        if ime_window.0.is_null() || ime_control(ime_window, IMC_GETOPENSTATUS, 0)? == 0 {
+            return None;
+        }
+        ime_control(ime_window, IMC_SETOPENSTATUS, 0)?;
+        Some(SuspendedIme { ime_window })
+    }
+}
+
+/// The window with keyboard focus, which may be a child of the foreground window.
+unsafe fn focused_window() -> Option<HWND> {
+    unsafe {
+        let foreground = GetForegroundWindow();
+        if foreground.0.is_null() {
+            return None;
+        }
+        let thread = GetWindowThreadProcessId(foreground, None);
+        let mut info = GUITHREADINFO {
+            cbSize: std::mem::size_of::<GUITHREADINFO>() as u32,
+            ..Default::default()
+        };
+        if GetGUIThreadInfo(thread, &mut info).is_ok() && !info.hwndFocus.0.is_null() {
+            return Some(info.hwndFocus);
+        }
+        Some(foreground)
+    }
+}
+
+/// `None` when the IME window didn't answer in time.
+unsafe fn ime_control(ime_window: HWND, command: usize, value: isize) -> Option<isize> {
+    unsafe {
+        let mut result = 0usize;
+        let answered = SendMessageTimeoutW(
+            ime_window,
+            WM_IME_CONTROL,
+            WPARAM(command),
+            LPARAM(value),
+            SMTO_ABORTIFHUNG,
+            IME_TIMEOUT_MS,
+            Some(&mut result),
+        );
+        (answered.0 != 0).then_some(result as isize)
+    }
+}
@@ -14,7 +14,13 @@ use std::time::Duration;
 
 use clipboard_win::{get_clipboard_string, set_clipboard_string};
 use enigo::Keyboard;
+use voicewin_core::key_macro::{KeyChord, MacroKey};
 use voicewin_core::types::InsertMode;
+use windows::Win32::System::DataExchange::GetClipboardSequenceNumber;
+
+use crate::clipboard_restore;
+
+const RESTORE_DELAY: Duration = Duration::from_millis(50);
 
 fn send_paste_ctrl_v(enigo: &mut enigo::Enigo) -> anyhow::Result<()> {
     // VK_V (0x56) avoids layout issues.
@@ -53,7 +59,92 @@ fn send_paste(enigo: &mut enigo::Enigo, mode: InsertMode) -> anyhow::Result<()>
     }
 }
 
-pub fn paste_text_via_clipboard(text: &str, mode: InsertMode) -> anyhow::Result<()> {
+fn macro_key(key: MacroKey) -> enigo::Key {
+    match key {
+        MacroKey::Enter => enigo::Key::Return,
+        MacroKey::Tab => enigo::Key::Tab,
+        MacroKey::Escape => enigo::Key::Escape,
+        MacroKey::Space => enigo::Key::Space,
+        MacroKey::Backspace => enigo::Key::Backspace,
+        MacroKey::Up => enigo::Key::UpArrow,
+        MacroKey::Down => enigo::Key::DownArrow,
+        MacroKey::Left => enigo::Key::LeftArrow,
+        MacroKey::Right => enigo::Key::RightArrow,
+        MacroKey::Insert => enigo::Key::Other(0x2D),
+        // Virtual-key codes for A-Z and 0-9 are their uppercase ASCII values; like Ctrl+V
+        // above, this avoids layout issues.
+        MacroKey::Char(c) => enigo::Key::Other(c.to_ascii_uppercase() as u32),
+    }
+}
+
+/// Press a profile's post-insert key macro, one chord at a time.
+pub fn send_key_macro(keys: &[KeyChord]) -> anyhow::Result<()> {
+    let mut enigo = enigo::Enigo::new(&enigo::Settings::default())
+        .map_err(|e| anyhow::anyhow!("failed to init enigo: {e}"))?;
+
+    for chord in keys {
+        thread::sleep(Duration::from_millis(50));
+        let held: Vec<enigo::Key> = [
+            (chord.ctrl, enigo::Key::Control),
+            (chord.shift, enigo::Key::Shift),
+            (chord.alt, enigo::Key::Alt),
+            (chord.meta, enigo::Key::Meta),
+        ]
+        .into_iter()
+        .filter_map(|(on, key)| on.then_some(key))
+        .collect();
+
+        let mut result = Ok(());
+        for key in &held {
+            result = enigo.key(*key, enigo::Direction::Press);
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = enigo.key(macro_key(chord.key), enigo::Direction::Click);
+        }
+        // Never leave a modifier stuck down, even if a press failed.
+        for key in held.iter().rev() {
+            let _ = enigo.key(*key, enigo::Direction::Release);
+        }
+        result.map_err(|e| anyhow::anyhow!("failed to press {}: {e}", String::from(*chord)))?;
+    }
+    Ok(())
+}
+
+/// Type the text instead of pasting it (the `type_keys` insertion strategy). enigo sends
+/// Unicode key events, so the keyboard layout doesn't matter.
+pub fn type_text(text: &str, mode: InsertMode) -> anyhow::Result<()> {
+    let mut enigo = enigo::Enigo::new(&enigo::Settings::default())
+        .map_err(|e| anyhow::anyhow!("failed to init enigo: {e}"))?;
+    enigo
+        .text(text)
+        .map_err(|e| anyhow::anyhow!("failed to type text: {e}"))?;
+    if matches!(mode, InsertMode::PasteAndEnter) {
+        thread::sleep(Duration::from_millis(50));
+        press_enter(&mut enigo)?;
+    }
+    Ok(())
+}
+
+pub fn press_enter(enigo: &mut enigo::Enigo) -> anyhow::Result<()> {
+    enigo
+        .key(enigo::Key::Return, enigo::Direction::Click)
+        .map_err(|e| anyhow::anyhow!("failed to press enter: {e}"))
+}
+
+/// `paste_keys` replaces the usual paste chord, for apps that paste with something else.
+pub fn paste_text_via_clipboard(
+    text: &str,
+    mode: InsertMode,
+    paste_keys: Option<&KeyChord>,
+) -> anyhow::Result<()> {
+    if mode == InsertMode::ClipboardOnly {
+        return set_clipboard_string(text)
+            .map_err(|e| anyhow::anyhow!("failed to write clipboard: {e}"));
+    }
+
     // Preserve user's clipboard and always attempt to restore it.
     // If the paste/enter keystrokes fail, the error propagates, but restoration
     // should still happen.
@@ -62,6 +153,7 @@ pub fn paste_text_via_clipboard(text: &str, mode: InsertMode) -> anyhow::Result<
 
     // 1) Put our text on clipboard.
     set_clipboard_string(text).map_err(|e| anyhow::anyhow!("failed to write clipboard: {e}"))?;
+    let written = unsafe { GetClipboardSequenceNumber() };
     thread::sleep(Duration::from_millis(50));
 
     // 2) Send paste keystroke.
@@ -69,22 +161,28 @@ pub fn paste_text_via_clipboard(text: &str, mode: InsertMode) -> anyhow::Result<
         let mut enigo = enigo::Enigo::new(&enigo::Settings::default())
             .map_err(|e| anyhow::anyhow!("failed to init enigo: {e}"))?;
 
-        send_paste(&mut enigo, mode)?;
+        match paste_keys {
+            Some(chord) => send_key_macro(std::slice::from_ref(chord))?,
+            None => send_paste(&mut enigo, mode)?,
+        }
 
         if matches!(mode, InsertMode::PasteAndEnter) {
             thread::sleep(Duration::from_millis(50));
-            enigo
-                .key(enigo::Key::Return, enigo::Direction::Click)
-                .map_err(|e| anyhow::anyhow!("failed to press enter: {e}"))?;
+            press_enter(&mut enigo)?;
         }
 
         Ok::<(), anyhow::Error>(())
     })();
 
-    // 3) Always restore user's clipboard (best-effort).
-    thread::sleep(Duration::from_millis(50));
+    // 3) Restore user's clipboard (best-effort) once the target app has had time to read it.
     if let Some(original) = original {
-        let _ = set_clipboard_string(&original);
+        clipboard_restore::restore_after_paste(
+            RESTORE_DELAY,
+            || unsafe { GetClipboardSequenceNumber() } != written,
+            || {
+                let _ = set_clipboard_string(&original);
+            },
+        );
     }
 
     // 4) Return the keystroke result.
@@ -0,0 +1,122 @@
+// Windows microphone privacy switches (Settings > Privacy & security > Microphone).
+//
+// The toggles are stored in the capability consent store as "Allow"/"Deny" strings. WASAPI
+// does not report a distinct error when they block us, so we read them directly.
+
+use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+use windows::Win32::System::Registry::{
+    HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ, RegGetValueW,
+};
+use windows::core::HSTRING;
+
+const CONSENT_KEY: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+const NON_PACKAGED_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged";
+
+/// Which privacy switch is keeping the microphone from desktop apps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MicrophoneBlock {
+    /// "Microphone access" is off for the whole device (set by an administrator).
+    Device,
+    /// "Microphone access" is off for this user.
+    AllApps,
+    /// "Let desktop apps access your microphone" is off.
+    DesktopApps,
+}
+
+impl MicrophoneBlock {
+    /// Names the exact toggle to flip.
+    pub fn user_message(self) -> &'static str {
+        match self {
+            MicrophoneBlock::Device => {
+                "Microphone access is turned off for this device. Turn on \"Microphone access\" in Windows Settings > Privacy & security > Microphone (this may need an administrator)."
+            }
+            MicrophoneBlock::AllApps => {
+                "Microphone access is turned off. Turn on \"Microphone access\" in Windows Settings > Privacy & security > Microphone."
+            }
+            MicrophoneBlock::DesktopApps => {
+                "Windows is blocking desktop apps from the microphone. Turn on \"Let desktop apps access your microphone\" in Windows Settings > Privacy & security > Microphone."
+            }
+        }
+    }
+}
+
+/// The privacy switch blocking microphone capture, if any.
+///
+/// Missing values mean the switch was never touched, which Windows treats as allowed.
+pub fn microphone_block() -> anyhow::Result<Option<MicrophoneBlock>> {
+    Ok(classify(
+        read_consent(HKEY_LOCAL_MACHINE, CONSENT_KEY)?.as_deref(),
+        read_consent(HKEY_CURRENT_USER, CONSENT_KEY)?.as_deref(),
+        read_consent(HKEY_CURRENT_USER, NON_PACKAGED_KEY)?.as_deref(),
+    ))
+}
+
+fn classify(
+    device: Option<&str>,
+    user: Option<&str>,
+    desktop_apps: Option<&str>,
+) -> Option<MicrophoneBlock> {
+    let denied = |v: Option<&str>| v.is_some_and(|v| v.eq_ignore_ascii_case("Deny"));
+    if denied(device) {
+        Some(MicrophoneBlock::Device)
+    } else if denied(user) {
+        Some(MicrophoneBlock::AllApps)
+    } else if denied(desktop_apps) {
+        Some(MicrophoneBlock::DesktopApps)
+    } else {
+        None
+    }
+}
+
+fn read_consent(root: HKEY, subkey: &str) -> anyhow::Result<Option<String>> {
+    let subkey = HSTRING::from(subkey);
+    let value = HSTRING::from("Value");
+    // "Allow"/"Deny" plus NUL; anything longer is not a value we understand.
+    let mut buf = [0u16; 16];
+    let mut len = std::mem::size_of_val(&buf) as u32;
+
+    let rc = unsafe {
+        RegGetValueW(
+            root,
+            &subkey,
+            &value,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buf.as_mut_ptr().cast()),
+            Some(&mut len),
+        )
+    };
+    if rc == ERROR_FILE_NOT_FOUND {
+        return Ok(None);
+    }
+    rc.ok()
+        .map_err(|e| anyhow::anyhow!("failed to read microphone privacy setting: {e}"))?;
+
+    // `len` is in bytes and includes the terminating NUL.
+    let chars = (len as usize / 2).saturating_sub(1);
+    Ok(Some(String::from_utf16_lossy(&buf[..chars])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_switch_wins_over_user_switches() {
+        assert_eq!(classify(None, None, None), None);
+        assert_eq!(classify(Some("Allow"), Some("Allow"), Some("Allow")), None);
+        assert_eq!(
+            classify(None, Some("Allow"), Some("Deny")),
+            Some(MicrophoneBlock::DesktopApps)
+        );
+        assert_eq!(
+            classify(Some("Deny"), Some("Deny"), None),
+            Some(MicrophoneBlock::Device)
+        );
+        assert_eq!(
+            classify(None, Some("Deny"), Some("Deny")),
+            Some(MicrophoneBlock::AllApps)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+// Insertion through UI Automation (the `uia_direct` strategy): the focused field's value is
+// rewritten with the text spliced in at the caret, without touching the clipboard or the
+// keyboard. Only fields with a writable value pattern qualify (standard edit controls and
+// most form fields); the caret usually ends up at the end of the field afterwards.
+
+use windows::Win32::System::Com::{
+    CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx,
+};
+use windows::Win32::UI::Accessibility::{
+    CUIAutomation, IUIAutomation, IUIAutomationElement, IUIAutomationTextPattern,
+    IUIAutomationValuePattern, TextPatternRangeEndpoint_End, TextPatternRangeEndpoint_Start,
+    UIA_TextPatternId, UIA_ValuePatternId,
+};
+use windows::core::BSTR;
+
+use voicewin_core::types::InsertMode;
+
+pub fn insert_at_caret(text: &str, mode: InsertMode) -> anyhow::Result<()> {
+    unsafe {
+        // Already-initialized threads report S_FALSE / RPC_E_CHANGED_MODE; both are fine here.
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+
+        let automation: IUIAutomation =
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)?;
+        let focused = automation.GetFocusedElement()?;
+        let value = focused
+            .GetCurrentPatternAs::<IUIAutomationValuePattern>(UIA_ValuePatternId)
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "The focused field can't be written through UI Automation; the text was not inserted."
+                )
+            })?;
+        if value.CurrentIsReadOnly()?.as_bool() {
+            anyhow::bail!("The focused field is read-only; the text was not inserted.");
+        }
+
+        let current: Vec<u16> = value.CurrentValue()?.as_wide().to_vec();
+        // Without a text pattern there is no caret to find; append.
+        let (start, end) = selection_offsets(&focused).unwrap_or((current.len(), current.len()));
+        let (start, end) = (start.min(current.len()), end.min(current.len()));
+
+        let mut updated = current[..start].to_vec();
+        updated.extend(text.encode_utf16());
+        updated.extend_from_slice(&current[end..]);
+        value.SetValue(&BSTR::from_wide(&updated))?;
+    }
+
+    if matches!(mode, InsertMode::PasteAndEnter) {
+        let mut enigo = enigo::Enigo::new(&enigo::Settings::default())
+            .map_err(|e| anyhow::anyhow!("failed to init enigo: {e}"))?;
+        super::windows_insert::press_enter(&mut enigo)?;
+    }
+    Ok(())
+}
+
+/// The selection as UTF-16 offsets into the field's value, or `None` when the field has no
+/// text pattern.
+unsafe fn selection_offsets(element: &IUIAutomationElement) -> Option<(usize, usize)> {
+    unsafe {
+        let pattern = element
+            .GetCurrentPatternAs::<IUIAutomationTextPattern>(UIA_TextPatternId)
+            .ok()?;
+        let selection = pattern.GetSelection().ok()?;
+        if selection.Length().ok()? < 1 {
+            return None;
+        }
+        let range = selection.GetElement(0).ok()?;
+
+        // Everything from the start of the field up to the selection.
+        let before = pattern.DocumentRange().ok()?;
+        before
+            .MoveEndpointByRange(
+                TextPatternRangeEndpoint_End,
+                &range,
+                TextPatternRangeEndpoint_Start,
+            )
+            .ok()?;
+        let start = before.GetText(-1).ok()?.len();
+        let selected = range.GetText(-1).ok()?.len();
+        Some((start, start + selected))
+    }
+}
@@ -1,10 +1,16 @@
+use crate::error::{missing_key_error, status_error};
+use crate::parse::{ElevenLabsSubscription, ElevenLabsTranscription};
 use crate::request::{Body, HttpRequest};
+use crate::runtime::HttpResponse;
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct ElevenLabsSttConfig {
     pub api_key: String,
     pub model_id: String,
     pub language_code: Option<String>,
+    /// Ask for per-speaker segments (word timestamps are needed for the speaker ids).
+    pub diarize: bool,
+    pub num_speakers: Option<u32>,
 }
 
 impl std::fmt::Debug for ElevenLabsSttConfig {
@@ -13,6 +19,8 @@ impl std::fmt::Debug for ElevenLabsSttConfig {
             .field("api_key", &"[REDACTED]")
             .field("model_id", &self.model_id)
             .field("language_code", &self.language_code)
+            .field("diarize", &self.diarize)
+            .field("num_speakers", &self.num_speakers)
             .finish()
     }
 }
@@ -39,9 +47,17 @@ pub fn build_elevenlabs_stt_request(cfg: &ElevenLabsSttConfig, audio: &AudioFile
     );
     append_field(&mut body, &boundary, "model_id", &cfg.model_id);
     append_field(&mut body, &boundary, "temperature", "0.0");
-    // Dictation defaults (smaller response + lower overhead).
-    append_field(&mut body, &boundary, "timestamps_granularity", "none");
-    append_field(&mut body, &boundary, "diarize", "false");
+    // Dictation defaults (smaller response + lower overhead), unless speakers are wanted.
+    if cfg.diarize {
+        append_field(&mut body, &boundary, "timestamps_granularity", "word");
+        append_field(&mut body, &boundary, "diarize", "true");
+        if let Some(n) = cfg.num_speakers.filter(|n| *n > 0) {
+            append_field(&mut body, &boundary, "num_speakers", &n.to_string());
+        }
+    } else {
+        append_field(&mut body, &boundary, "timestamps_granularity", "none");
+        append_field(&mut body, &boundary, "diarize", "false");
+    }
     append_field(&mut body, &boundary, "tag_audio_events", "false");
     append_field(&mut body, &boundary, "file_format", "pcm_s16le_16");
 
@@ -69,6 +85,97 @@ pub fn build_elevenlabs_stt_request(cfg: &ElevenLabsSttConfig, audio: &AudioFile
     }
 }
 
+/// Batch (non-realtime) Scribe transcription of a complete recording.
+///
+/// Used at stop time when realtime streaming was unavailable or failed, so ElevenLabs users
+/// get the same model either way.
+pub async fn transcribe_batch(
+    cfg: &ElevenLabsSttConfig,
+    audio: &AudioFile,
+) -> anyhow::Result<ElevenLabsTranscription> {
+    if cfg.api_key.trim().is_empty() {
+        return Err(missing_key_error("ElevenLabs").into());
+    }
+
+    let req = build_elevenlabs_stt_request(cfg, audio);
+    let resp = crate::runtime::execute(&req).await?;
+    transcription_from_response(&resp)
+}
+
+fn transcription_from_response(resp: &HttpResponse) -> anyhow::Result<ElevenLabsTranscription> {
+    match resp.status {
+        200..=299 => crate::parse::parse_elevenlabs_diarized_transcription(&resp.body),
+        401 | 403 => Err(status_error(
+            resp.status,
+            format!(
+                "ElevenLabs rejected the API key (status={}). Check the key in Settings.",
+                resp.status
+            ),
+        )
+        .into()),
+        429 => Err(status_error(
+            429,
+            format!(
+                "ElevenLabs rate limit or quota exceeded (status=429): {}",
+                String::from_utf8_lossy(&resp.body)
+            ),
+        )
+        .into()),
+        status => Err(status_error(
+            status,
+            format!(
+                "ElevenLabs STT failed: status={status} body={}",
+                String::from_utf8_lossy(&resp.body)
+            ),
+        )
+        .into()),
+    }
+}
+
+pub fn build_subscription_request(api_key: &str) -> HttpRequest {
+    HttpRequest {
+        method: "GET".into(),
+        url: "https://api.elevenlabs.io/v1/user/subscription".into(),
+        headers: vec![
+            ("Accept".into(), "application/json".into()),
+            ("xi-api-key".into(), api_key.into()),
+        ],
+        body: Body::Empty,
+    }
+}
+
+/// The account's plan and credit usage. Cheap and free of charge, so it doubles as a key check.
+pub async fn fetch_subscription(api_key: &str) -> anyhow::Result<ElevenLabsSubscription> {
+    if api_key.trim().is_empty() {
+        return Err(missing_key_error("ElevenLabs").into());
+    }
+
+    let resp = crate::runtime::execute(&build_subscription_request(api_key)).await?;
+    subscription_from_response(&resp)
+}
+
+fn subscription_from_response(resp: &HttpResponse) -> anyhow::Result<ElevenLabsSubscription> {
+    match resp.status {
+        200..=299 => crate::parse::parse_elevenlabs_subscription(&resp.body),
+        401 | 403 => Err(status_error(
+            resp.status,
+            format!(
+                "ElevenLabs rejected the API key (status={}). Check the key in Settings.",
+                resp.status
+            ),
+        )
+        .into()),
+        status => Err(status_error(
+            status,
+            format!(
+                "ElevenLabs subscription check failed: status={status} body={}",
+                String::from_utf8_lossy(&resp.body)
+            ),
+        )
+        .into()),
+    }
+}
+
 fn append_field(body: &mut Vec<u8>, boundary: &str, name: &str, value: &str) {
     body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
     body.extend_from_slice(
@@ -109,6 +216,8 @@ mod tests {
             api_key: "k".into(),
             model_id: "scribe_v2".into(),
             language_code: Some("en".into()),
+            diarize: false,
+            num_speakers: None,
         };
         let audio = AudioFile {
             filename: "a.pcm".into(),
@@ -137,4 +246,76 @@ mod tests {
             _ => panic!("expected multipart"),
         }
     }
+
+    #[test]
+    fn diarized_request_asks_for_word_speakers() {
+        let cfg = ElevenLabsSttConfig {
+            api_key: "k".into(),
+            model_id: "scribe_v2".into(),
+            language_code: None,
+            diarize: true,
+            num_speakers: Some(2),
+        };
+        let audio = AudioFile {
+            filename: "a.pcm".into(),
+            mime_type: "application/octet-stream".into(),
+            bytes: vec![],
+        };
+        match build_elevenlabs_stt_request(&cfg, &audio).body {
+            Body::MultipartFormData { bytes, .. } => {
+                let s = String::from_utf8_lossy(&bytes);
+                assert!(s.contains("name=\"diarize\"\r\n\r\ntrue"));
+                assert!(s.contains("name=\"timestamps_granularity\"\r\n\r\nword"));
+                assert!(s.contains("name=\"num_speakers\"\r\n\r\n2"));
+            }
+            _ => panic!("expected multipart"),
+        }
+    }
+
+    #[test]
+    fn batch_response_maps_status_to_text_or_error() {
+        let ok = HttpResponse {
+            status: 200,
+            body: br#"{"text":"hello world"}"#.to_vec(),
+        };
+        assert_eq!(
+            transcription_from_response(&ok).unwrap().text,
+            "hello world"
+        );
+
+        let unauthorized = HttpResponse {
+            status: 401,
+            body: b"{}".to_vec(),
+        };
+        let err = transcription_from_response(&unauthorized).unwrap_err();
+        assert!(err.to_string().contains("API key"));
+
+        let server = HttpResponse {
+            status: 500,
+            body: b"boom".to_vec(),
+        };
+        let err = transcription_from_response(&server).unwrap_err();
+        assert!(err.to_string().contains("status=500 body=boom"));
+    }
+
+    #[test]
+    fn subscription_check_sends_the_key_and_maps_rejection() {
+        let req = build_subscription_request("k");
+        assert_eq!(req.method, "GET");
+        assert!(req.url.ends_with("/v1/user/subscription"));
+        assert_eq!(req.header("xi-api-key"), Some("k"));
+
+        let ok = HttpResponse {
+            status: 200,
+            body: br#"{"tier":"free","character_count":0,"character_limit":10000}"#.to_vec(),
+        };
+        assert_eq!(subscription_from_response(&ok).unwrap().tier, "free");
+
+        let unauthorized = HttpResponse {
+            status: 401,
+            body: b"{}".to_vec(),
+        };
+        let err = subscription_from_response(&unauthorized).unwrap_err();
+        assert!(err.to_string().contains("API key"));
+    }
 }
@@ -7,10 +7,20 @@ use futures_util::{SinkExt, StreamExt, future};
 use tokio::sync::{mpsc, oneshot};
 use tokio_tungstenite::tungstenite::{Message, client::IntoClientRequest};
 use url::Url;
+use voicewin_core::stt::RealtimeVadSettings;
 
 const WS_SEND_TIMEOUT: Duration = Duration::from_secs(3);
 const FINALIZE_FAST_PATH_DURATION: Duration = Duration::from_millis(450);
 
+// Reconnect after a mid-recording drop: a few quick attempts per drop, and a cap per session
+// so a flapping network falls back to batch instead of looping.
+const RECONNECT_BACKOFF: [Duration; 3] = [
+    Duration::from_millis(250),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+];
+const MAX_RECONNECTS_PER_SESSION: u32 = 5;
+
 fn join_committed_and_partial(committed: &str, partial: &str) -> String {
     let c = committed.trim();
     let p = partial.trim();
@@ -105,8 +115,24 @@ pub struct ElevenLabsRealtimeVadParams {
     pub min_silence_duration_ms: u32,
 }
 
+impl From<&RealtimeVadSettings> for ElevenLabsRealtimeVadParams {
+    fn from(s: &RealtimeVadSettings) -> Self {
+        Self {
+            vad_silence_threshold_ms: s.vad_silence_threshold_ms,
+            vad_threshold_milli: s.vad_threshold_milli.min(1000),
+            min_speech_duration_ms: s.min_speech_duration_ms,
+            min_silence_duration_ms: s.min_silence_duration_ms,
+        }
+    }
+}
+
 impl ElevenLabsRealtimeConfig {
-    pub fn production(api_key: impl Into<String>, sample_rate_hz: u32) -> anyhow::Result<Self> {
+    /// The live endpoint, with pause detection from the effective profile settings.
+    pub fn production(
+        api_key: impl Into<String>,
+        sample_rate_hz: u32,
+        vad: &RealtimeVadSettings,
+    ) -> anyhow::Result<Self> {
         Ok(Self {
             ws_url: Url::parse("wss://api.elevenlabs.io/v1/speech-to-text/realtime")
                 .context("parse elevenlabs realtime url")?,
@@ -114,14 +140,8 @@ impl ElevenLabsRealtimeConfig {
             model_id: "scribe_v2".into(),
             language_code: None,
             sample_rate_hz,
-            commit_strategy: "vad".into(),
-            // Latency-oriented defaults for dictation.
-            vad: Some(ElevenLabsRealtimeVadParams {
-                vad_silence_threshold_ms: 600,
-                min_silence_duration_ms: 150,
-                vad_threshold_milli: 400,
-                min_speech_duration_ms: 100,
-            }),
+            commit_strategy: vad.commit_strategy.as_str().into(),
+            vad: Some(vad.into()),
             connect_timeout: Duration::from_secs(10),
             finalize_timeout: Duration::from_secs(5),
         })
@@ -159,11 +179,13 @@ impl ElevenLabsRealtimeHandle {
     }
 
     pub async fn send_audio_chunk(&self, pcm_s16le: Vec<u8>) -> bool {
+        self.send_audio_chunk_with_commit(pcm_s16le, false).await
+    }
+
+    /// With `commit_strategy = "manual"`, `commit = true` finalizes the segment so far.
+    pub async fn send_audio_chunk_with_commit(&self, pcm_s16le: Vec<u8>, commit: bool) -> bool {
         self.tx
-            .send(RealtimeCmd::AudioChunk {
-                pcm_s16le,
-                commit: false,
-            })
+            .send(RealtimeCmd::AudioChunk { pcm_s16le, commit })
             .await
             .is_ok()
     }
@@ -180,82 +202,24 @@ impl ElevenLabsRealtimeHandle {
     pub async fn shutdown(&self) {
         let _ = self.tx.send(RealtimeCmd::Shutdown).await;
     }
+
+    /// The session task has ended (connection lost for good, or shut down).
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
 }
 
 pub async fn spawn_realtime_session(
     cfg: ElevenLabsRealtimeConfig,
 ) -> anyhow::Result<(ElevenLabsRealtimeHandle, mpsc::Receiver<RealtimeEvent>)> {
     if cfg.api_key.trim().is_empty() {
-        return Err(anyhow!("missing ElevenLabs API key"));
+        return Err(crate::error::missing_key_error("ElevenLabs").into());
     }
 
-    let url = build_realtime_ws_url(&cfg)?;
-
-    // `IntoClientRequest` isn't implemented for `url::Url` in tungstenite 0.26 without extra
-    // features; convert to string-ish form first.
-    let mut req = url
-        .as_str()
-        .into_client_request()
-        .context("build websocket request")?;
-    req.headers_mut().insert(
-        "xi-api-key",
-        cfg.api_key
-            .parse()
-            .map_err(|_| anyhow!("invalid ElevenLabs API key header"))?,
-    );
-
     let (cmd_tx, mut cmd_rx) = mpsc::channel::<RealtimeCmd>(64);
     let (evt_tx, evt_rx) = mpsc::channel::<RealtimeEvent>(64);
 
-    // Connect with a hard timeout so we can't hang on a bad network.
-    let (ws, _resp) = tokio::time::timeout(cfg.connect_timeout, tokio_tungstenite::connect_async(req))
-        .await
-        .map_err(|_| anyhow!("ElevenLabs realtime connect timed out"))?
-        .context("connect elevenlabs realtime websocket")?;
-
-    let (ws_write, mut ws_read) = ws.split();
-
-    // Writer task: keeps reads responsive by ensuring we never await socket writes in the main loop.
-    // We keep control messages separate so pongs/finalize flush can't be starved by audio backlog.
-    let (out_ctrl_tx, mut out_ctrl_rx) = mpsc::channel::<Message>(32);
-    let (out_audio_tx, mut out_audio_rx) = mpsc::channel::<Message>(256);
-    tokio::spawn(async move {
-        let mut ws_write = ws_write;
-        let mut ctrl_closed = false;
-        let mut audio_closed = false;
-
-        loop {
-            let next_msg: Option<Message> = tokio::select! {
-                biased;
-                msg = out_ctrl_rx.recv(), if !ctrl_closed => {
-                    match msg {
-                        Some(m) => Some(m),
-                        None => { ctrl_closed = true; None }
-                    }
-                }
-                msg = out_audio_rx.recv(), if !audio_closed => {
-                    match msg {
-                        Some(m) => Some(m),
-                        None => { audio_closed = true; None }
-                    }
-                }
-            };
-
-            let Some(msg) = next_msg else {
-                if ctrl_closed && audio_closed {
-                    break;
-                }
-                continue;
-            };
-
-            let res = tokio::time::timeout(WS_SEND_TIMEOUT, ws_write.send(msg)).await;
-            if !matches!(res, Ok(Ok(()))) {
-                break;
-            }
-        }
-
-        let _ = ws_write.send(Message::Close(None)).await;
-    });
+    let mut conn = connect(&cfg).await?;
 
     let finalize_timeout = cfg.finalize_timeout;
     let sample_rate_hz = cfg.sample_rate_hz;
@@ -285,7 +249,44 @@ pub async fn spawn_realtime_session(
             Ok(join_committed_and_partial(committed, partial))
         };
 
+        // Set when the connection drops mid-recording, with the reason to report if it can't
+        // be re-established.
+        let mut lost: Option<Option<&'static str>> = None;
+        let mut reconnects: u32 = 0;
+        let mut finalize_requested = false;
+        // Sent with the first chunk of a reconnected session so it continues the transcript.
+        let mut previous_text: Option<String> = None;
+
         loop {
+            if let Some(reason) = lost.take() {
+                let can_reconnect = !finalize_requested
+                    && fatal_error.is_none()
+                    && reconnects < MAX_RECONNECTS_PER_SESSION;
+                let restored = if can_reconnect {
+                    reconnects += 1;
+                    reconnect(&cfg, &evt_tx).await
+                } else {
+                    None
+                };
+
+                let Some(new_conn) = restored else {
+                    if let Some(error) = reason.or(can_reconnect.then_some("websocket closed")) {
+                        let _ = evt_tx.try_send(RealtimeEvent::Error {
+                            message_type: "disconnect".into(),
+                            error: error.into(),
+                        });
+                    }
+                    break;
+                };
+
+                // The new server session starts empty: keep the uncommitted text from before the
+                // drop and pass everything so far as context.
+                conn = new_conn;
+                committed = join_committed_and_partial(&committed, &partial);
+                partial.clear();
+                previous_text = Some(committed.clone()).filter(|t| !t.is_empty());
+            }
+
             tokio::select! {
                 cmd = cmd_rx.recv() => {
                     let Some(cmd) = cmd else { break; };
@@ -295,8 +296,8 @@ pub async fn spawn_realtime_session(
                                 continue;
                             }
 
-                            let msg = build_input_audio_chunk_message(&pcm_s16le, sample_rate_hz, commit, None);
-                            match out_audio_tx.try_send(Message::Text(msg.into())) {
+                            let msg = build_input_audio_chunk_message(&pcm_s16le, sample_rate_hz, commit, previous_text.take().as_deref());
+                            match conn.audio_tx.try_send(Message::Text(msg.into())) {
                                 Ok(()) => {}
                                 Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
                                     // Best-effort: drop the chunk rather than stalling reads.
@@ -312,8 +313,7 @@ pub async fn spawn_realtime_session(
                                     }
                                 }
                                 Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {
-                                    let _ = evt_tx.try_send(RealtimeEvent::Error { message_type: "disconnect".into(), error: "websocket closed".into() });
-                                    break;
+                                    lost = Some(Some("websocket closed"));
                                 }
                             }
                         }
@@ -322,6 +322,7 @@ pub async fn spawn_realtime_session(
                                 let _ = respond_to.send(Err(anyhow!("finalize already in progress")));
                                 continue;
                             }
+                            finalize_requested = true;
 
                             if let Some((t, e)) = fatal_error.take() {
                                 let _ = respond_to.send(Err(anyhow!("ElevenLabs realtime error ({t}): {e}")));
@@ -331,11 +332,11 @@ pub async fn spawn_realtime_session(
                             // Combine VAD during recording with a final manual flush at stop.
                             // We send a short silence chunk with commit=true to force a final commit.
                             let silence = silence_pcm_s16le(sample_rate_hz, 120);
-                            let msg = build_input_audio_chunk_message(&silence, sample_rate_hz, true, None);
+                            let msg = build_input_audio_chunk_message(&silence, sample_rate_hz, true, previous_text.take().as_deref());
 
                             let sent = tokio::time::timeout(
                                 Duration::from_secs(1),
-                                out_ctrl_tx.send(Message::Text(msg.into())),
+                                conn.ctrl_tx.send(Message::Text(msg.into())),
                             )
                             .await;
                             if !matches!(sent, Ok(Ok(()))) {
@@ -357,28 +358,30 @@ pub async fn spawn_realtime_session(
                     }
                 }
 
-                msg = ws_read.next() => {
-                    let Some(msg) = msg else { break; };
+                msg = conn.read.next() => {
+                    let Some(msg) = msg else {
+                        lost = Some(None);
+                        continue;
+                    };
                     let msg = match msg {
                         Ok(m) => m,
                         Err(_) => {
-                            let _ = evt_tx.send(RealtimeEvent::Error { message_type: "disconnect".into(), error: "websocket read failed".into() }).await;
-                            break;
+                            lost = Some(Some("websocket read failed"));
+                            continue;
                         }
                     };
 
                     let text = match msg {
                         Message::Text(t) => t.to_string(),
                         Message::Binary(b) => String::from_utf8_lossy(&b).to_string(),
-                        Message::Close(_) => break,
+                        Message::Close(_) => {
+                            lost = Some(None);
+                            continue;
+                        }
                         Message::Ping(p) => {
                             // Best-effort: if we can't respond with Pong, treat as disconnect.
-                            match out_ctrl_tx.try_send(Message::Pong(p)) {
-                                Ok(()) => {}
-                                Err(_) => {
-                                    let _ = evt_tx.try_send(RealtimeEvent::Error { message_type: "disconnect".into(), error: "failed to send pong".into() });
-                                    break;
-                                }
+                            if conn.ctrl_tx.try_send(Message::Pong(p)).is_err() {
+                                lost = Some(Some("failed to send pong"));
                             }
                             continue;
                         }
@@ -529,6 +532,136 @@ pub async fn spawn_realtime_session(
     Ok((ElevenLabsRealtimeHandle { tx: cmd_tx }, evt_rx))
 }
 
+type WsRead = futures_util::stream::SplitStream<
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+>;
+
+/// One websocket connection: the read half plus the queues feeding its writer task.
+struct Connection {
+    read: WsRead,
+    ctrl_tx: mpsc::Sender<Message>,
+    audio_tx: mpsc::Sender<Message>,
+}
+
+async fn connect(cfg: &ElevenLabsRealtimeConfig) -> anyhow::Result<Connection> {
+    let url = build_realtime_ws_url(cfg)?;
+    crate::runtime::ensure_online(url.as_str())?;
+
+    // `IntoClientRequest` isn't implemented for `url::Url` in tungstenite 0.26 without extra
+    // features; convert to string-ish form first.
+    let mut req = url
+        .as_str()
+        .into_client_request()
+        .context("build websocket request")?;
+    req.headers_mut().insert(
+        "xi-api-key",
+        cfg.api_key
+            .parse()
+            .map_err(|_| anyhow!("invalid ElevenLabs API key header"))?,
+    );
+
+    // tungstenite ignores proxy settings, so tunnel through the proxy ourselves and run the
+    // TLS + websocket handshake over that stream.
+    let tls = crate::runtime::websocket_tls_connector()?;
+    let handshake = async {
+        match crate::runtime::proxy_for(&url) {
+            Some(proxy) => {
+                let host = url
+                    .host_str()
+                    .ok_or_else(|| anyhow!("realtime url has no host"))?;
+                let port = url.port_or_known_default().unwrap_or(443);
+                let stream = crate::runtime::connect_tunnel(&proxy, host, port).await?;
+                Ok(tokio_tungstenite::client_async_tls_with_config(req, stream, None, tls).await?)
+            }
+            None => Ok::<_, anyhow::Error>(
+                tokio_tungstenite::connect_async_tls_with_config(req, None, false, tls).await?,
+            ),
+        }
+    };
+
+    // Connect with a hard timeout so we can't hang on a bad network.
+    let (ws, _resp) = tokio::time::timeout(cfg.connect_timeout, handshake)
+        .await
+        .map_err(|_| anyhow!("ElevenLabs realtime connect timed out"))?
+        .context("connect elevenlabs realtime websocket")?;
+
+    let (ws_write, ws_read) = ws.split();
+
+    // Writer task: keeps reads responsive by ensuring we never await socket writes in the main loop.
+    // We keep control messages separate so pongs/finalize flush can't be starved by audio backlog.
+    let (out_ctrl_tx, mut out_ctrl_rx) = mpsc::channel::<Message>(32);
+    let (out_audio_tx, mut out_audio_rx) = mpsc::channel::<Message>(256);
+    tokio::spawn(async move {
+        let mut ws_write = ws_write;
+        let mut ctrl_closed = false;
+        let mut audio_closed = false;
+
+        loop {
+            let next_msg: Option<Message> = tokio::select! {
+                biased;
+                msg = out_ctrl_rx.recv(), if !ctrl_closed => {
+                    match msg {
+                        Some(m) => Some(m),
+                        None => { ctrl_closed = true; None }
+                    }
+                }
+                msg = out_audio_rx.recv(), if !audio_closed => {
+                    match msg {
+                        Some(m) => Some(m),
+                        None => { audio_closed = true; None }
+                    }
+                }
+            };
+
+            let Some(msg) = next_msg else {
+                if ctrl_closed && audio_closed {
+                    break;
+                }
+                continue;
+            };
+
+            let res = tokio::time::timeout(WS_SEND_TIMEOUT, ws_write.send(msg)).await;
+            if !matches!(res, Ok(Ok(()))) {
+                break;
+            }
+        }
+
+        let _ = ws_write.send(Message::Close(None)).await;
+    });
+
+    Ok(Connection {
+        read: ws_read,
+        ctrl_tx: out_ctrl_tx,
+        audio_tx: out_audio_tx,
+    })
+}
+
+/// Try to re-establish a dropped connection, with a short backoff between attempts.
+async fn reconnect(
+    cfg: &ElevenLabsRealtimeConfig,
+    evt_tx: &mpsc::Sender<RealtimeEvent>,
+) -> Option<Connection> {
+    let _ = evt_tx.try_send(RealtimeEvent::Warning {
+        kind: "reconnecting".into(),
+        message: "ElevenLabs realtime connection lost; reconnecting...".into(),
+    });
+
+    for delay in RECONNECT_BACKOFF {
+        tokio::time::sleep(delay).await;
+        match connect(cfg).await {
+            Ok(conn) => {
+                let _ = evt_tx.try_send(RealtimeEvent::Warning {
+                    kind: "reconnected".into(),
+                    message: "ElevenLabs realtime reconnected.".into(),
+                });
+                return Some(conn);
+            }
+            Err(_) => continue,
+        }
+    }
+    None
+}
+
 fn build_realtime_ws_url(cfg: &ElevenLabsRealtimeConfig) -> anyhow::Result<Url> {
     let audio_format = audio_format_query(cfg.sample_rate_hz)?;
 
@@ -715,7 +848,7 @@ mod tests {
         let qp: std::collections::HashMap<String, String> = url.query_pairs().into_owned().collect();
         assert_eq!(qp.get("include_timestamps").map(|s| s.as_str()), Some("false"));
         assert_eq!(qp.get("include_language_detection").map(|s| s.as_str()), Some("false"));
-        assert!(qp.get("language_code").is_none());
+        assert!(!qp.contains_key("language_code"));
     }
 
     #[test]
@@ -768,6 +901,43 @@ mod tests {
         assert_eq!(qp.get("min_silence_duration_ms").map(|s| s.as_str()), Some("150"));
     }
 
+    #[test]
+    fn production_config_follows_vad_settings() {
+        let noisy = RealtimeVadSettings {
+            vad_threshold_milli: 650,
+            min_speech_duration_ms: 250,
+            ..Default::default()
+        };
+        let cfg = ElevenLabsRealtimeConfig::production("k", 16_000, &noisy).unwrap();
+        let qp: std::collections::HashMap<String, String> = build_realtime_ws_url(&cfg)
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect();
+        assert_eq!(qp.get("commit_strategy").map(|s| s.as_str()), Some("vad"));
+        assert_eq!(qp.get("vad_threshold").map(|s| s.as_str()), Some("0.65"));
+        assert_eq!(
+            qp.get("min_speech_duration_ms").map(|s| s.as_str()),
+            Some("250")
+        );
+
+        let manual = RealtimeVadSettings {
+            commit_strategy: voicewin_core::stt::RealtimeCommitStrategy::Manual,
+            ..Default::default()
+        };
+        let cfg = ElevenLabsRealtimeConfig::production("k", 16_000, &manual).unwrap();
+        let qp: std::collections::HashMap<String, String> = build_realtime_ws_url(&cfg)
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect();
+        assert_eq!(
+            qp.get("commit_strategy").map(|s| s.as_str()),
+            Some("manual")
+        );
+        assert!(!qp.contains_key("vad_threshold"));
+    }
+
     #[test]
     fn parses_partial_and_committed() {
         let p = parse_realtime_message(r#"{"message_type":"partial_transcript","text":"hi"}"#).unwrap();
@@ -948,6 +1118,88 @@ mod tests {
         handle.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn integration_ws_reconnects_after_drop_and_keeps_committed_text() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (prev_tx, prev_rx) = oneshot::channel::<String>();
+
+        tokio::spawn(async move {
+            // First connection: commit some text, then drop without a close frame.
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            while let Some(Ok(msg)) = ws.next().await {
+                if let Message::Text(_) = msg {
+                    let _ = ws
+                        .send(Message::Text(
+                            r#"{"message_type":"committed_transcript","text":"hello"}"#.into(),
+                        ))
+                        .await;
+                    break;
+                }
+            }
+            drop(ws);
+
+            // Second connection: the first chunk carries the transcript so far.
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            let mut prev_tx = Some(prev_tx);
+            while let Some(Ok(msg)) = ws.next().await {
+                if let Message::Text(txt) = msg {
+                    if let Some(tx) = prev_tx.take() {
+                        let v: serde_json::Value = serde_json::from_str(&txt).unwrap();
+                        let _ = tx.send(v["previous_text"].as_str().unwrap_or("").to_string());
+                    }
+                    if txt.contains("\"commit\":true") {
+                        let _ = ws
+                            .send(Message::Text(
+                                r#"{"message_type":"committed_transcript","text":"world"}"#.into(),
+                            ))
+                            .await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        let cfg = ElevenLabsRealtimeConfig {
+            ws_url: Url::parse(&format!("ws://{addr}/v1/speech-to-text/realtime")).unwrap(),
+            api_key: "k".into(),
+            model_id: "scribe_v2".into(),
+            language_code: None,
+            sample_rate_hz: 16_000,
+            commit_strategy: "vad".into(),
+            vad: None,
+            connect_timeout: Duration::from_secs(2),
+            finalize_timeout: Duration::from_secs(2),
+        };
+
+        let (handle, mut events) = spawn_realtime_session(cfg).await.unwrap();
+        assert!(handle.send_audio_chunk(vec![0u8; 8]).await);
+
+        let reconnected = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match events.recv().await {
+                    Some(RealtimeEvent::Warning { kind, .. }) if kind == "reconnected" => {
+                        return true;
+                    }
+                    Some(RealtimeEvent::Error { .. }) | None => return false,
+                    Some(_) => continue,
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert!(reconnected);
+
+        assert!(handle.send_audio_chunk(vec![0u8; 8]).await);
+        assert_eq!(prev_rx.await.unwrap(), "hello");
+
+        let out = handle.finalize().await.unwrap();
+        assert_eq!(out, "hello world");
+        handle.shutdown().await;
+    }
+
     #[tokio::test]
     async fn integration_ws_finalize_fast_path_returns_existing_text_quickly() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -0,0 +1,96 @@
+// Map provider failures onto the user-facing `ErrorKind`s.
+//
+// Code that knows what went wrong raises a `VoicewinError` directly (wrapped in anyhow);
+// everything else is classified here from the transport errors in the cause chain.
+
+use voicewin_core::error::{ErrorKind, VoicewinError};
+
+use crate::runtime::OfflineModeError;
+
+/// `VoicewinError` for the first recognizable cause in `e`, else `fallback` with `e`'s
+/// message.
+pub fn classify_error(e: &anyhow::Error, fallback: ErrorKind) -> VoicewinError {
+    let detail = format!("{e:#}");
+    for cause in e.chain() {
+        if let Some(known) = cause.downcast_ref::<VoicewinError>() {
+            return known.clone();
+        }
+        if let Some(offline) = cause.downcast_ref::<OfflineModeError>() {
+            return VoicewinError::new(ErrorKind::Config, offline.to_string());
+        }
+        let network = cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|re| re.is_timeout() || re.is_connect())
+            || cause
+                .downcast_ref::<tokio::time::error::Elapsed>()
+                .is_some()
+            || cause.downcast_ref::<std::io::Error>().is_some_and(|io| {
+                matches!(
+                    io.kind(),
+                    std::io::ErrorKind::TimedOut
+                        | std::io::ErrorKind::ConnectionRefused
+                        | std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::ConnectionAborted
+                )
+            });
+        if network {
+            return VoicewinError::new(
+                ErrorKind::Network,
+                "Could not reach the provider. Check your connection or proxy settings.",
+            )
+            .with_detail(detail);
+        }
+    }
+    VoicewinError::new(fallback, e.to_string()).with_detail(detail)
+}
+
+/// A non-2xx answer from a provider: a rejected key needs Settings, rate limits and server
+/// errors are worth retrying.
+pub fn status_error(status: u16, user_message: impl Into<String>) -> VoicewinError {
+    match status {
+        401 | 403 => VoicewinError::new(ErrorKind::Config, user_message),
+        429 | 500..=599 => {
+            VoicewinError::new(ErrorKind::Provider, user_message).with_retryable(true)
+        }
+        _ => VoicewinError::new(ErrorKind::Provider, user_message),
+    }
+}
+
+/// No API key is stored for `provider`.
+pub fn missing_key_error(provider: &str) -> VoicewinError {
+    VoicewinError::new(ErrorKind::Config, format!("missing {provider} API key"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn classifies_raised_offline_and_io_errors() {
+        let raised: anyhow::Error = status_error(503, "busy").into();
+        let e = classify_error(&raised.context("transcribe"), ErrorKind::Internal);
+        assert_eq!((e.kind, e.retryable), (ErrorKind::Provider, true));
+        assert_eq!(e.user_message, "busy");
+
+        let offline = anyhow::Error::new(OfflineModeError {
+            target: "https://api.elevenlabs.io".into(),
+        });
+        assert_eq!(
+            classify_error(&offline, ErrorKind::Internal).kind,
+            ErrorKind::Config
+        );
+
+        let refused = Err::<(), _>(std::io::Error::from(std::io::ErrorKind::ConnectionRefused))
+            .context("connect")
+            .unwrap_err();
+        let e = classify_error(&refused, ErrorKind::Internal);
+        assert_eq!((e.kind, e.retryable), (ErrorKind::Network, true));
+
+        let other = anyhow::anyhow!("unknown message_type: foo");
+        let e = classify_error(&other, ErrorKind::Provider);
+        assert_eq!(e.kind, ErrorKind::Provider);
+        assert_eq!(e.user_message, "unknown message_type: foo");
+        assert_eq!(e.detail, None);
+    }
+}
@@ -1,6 +1,10 @@
 pub mod elevenlabs;
 pub mod elevenlabs_realtime;
+pub mod error;
 pub mod openai_compatible;
 pub mod parse;
+pub mod realtime_pool;
 pub mod request;
 pub mod runtime;
+pub mod webhook;
+pub mod wire_log;
@@ -1,11 +1,17 @@
 use crate::request::{Body, HttpRequest};
 use serde_json::json;
+use voicewin_core::enhancement::{LlmDecodingSettings, LlmEndpointFlavor, ResponseFormat};
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct OpenAiCompatibleChatConfig {
     pub base_url: String,
     pub api_key: String,
     pub model: String,
+
+    /// Temperature, max_tokens, stop sequences and response_format.
+    pub decoding: LlmDecodingSettings,
+
+    pub flavor: LlmEndpointFlavor,
 }
 
 impl std::fmt::Debug for OpenAiCompatibleChatConfig {
@@ -14,6 +20,8 @@ impl std::fmt::Debug for OpenAiCompatibleChatConfig {
             .field("base_url", &self.base_url)
             .field("api_key", &"[REDACTED]")
             .field("model", &self.model)
+            .field("decoding", &self.decoding)
+            .field("flavor", &self.flavor)
             .finish()
     }
 }
@@ -28,25 +36,80 @@ pub fn build_chat_completions_request(
     cfg: &OpenAiCompatibleChatConfig,
     messages: &[ChatMessage],
 ) -> HttpRequest {
-    let url = join_url(&cfg.base_url, "/chat/completions");
+    let url = match &cfg.flavor {
+        LlmEndpointFlavor::OpenAi => join_url(&cfg.base_url, "/chat/completions"),
+        LlmEndpointFlavor::Azure { api_version } => format!(
+            "{}?api-version={}",
+            join_url(
+                &cfg.base_url,
+                &format!("/openai/deployments/{}/chat/completions", cfg.model.trim())
+            ),
+            api_version
+        ),
+    };
 
-    let payload = json!({
+    let decoding = &cfg.decoding;
+    let mut payload = json!({
         "model": cfg.model,
         "messages": messages.iter().map(|m| json!({"role": m.role, "content": m.content})).collect::<Vec<_>>(),
-        "temperature": 0.3,
+        "temperature": decoding.temperature(),
     });
+    // Only send what was configured; some servers reject fields they don't know.
+    if let Some(max_tokens) = decoding.max_tokens {
+        payload["max_tokens"] = json!(max_tokens);
+    }
+    if !decoding.stop.is_empty() {
+        payload["stop"] = json!(decoding.stop);
+    }
+    match decoding.response_format {
+        Some(ResponseFormat::JsonObject) => {
+            payload["response_format"] = json!({ "type": "json_object" })
+        }
+        Some(ResponseFormat::Text) => payload["response_format"] = json!({ "type": "text" }),
+        None => {}
+    }
 
     HttpRequest {
         method: "POST".into(),
         url,
         headers: vec![
             ("Content-Type".into(), "application/json".into()),
-            ("Authorization".into(), format!("Bearer {}", cfg.api_key)),
+            auth_header(&cfg.flavor, &cfg.api_key),
         ],
         body: Body::Json(payload.to_string()),
     }
 }
 
+/// `GET {base_url}/models`: a cheap authenticated call used to check that the endpoint is
+/// reachable and accepts the key, without spending tokens.
+pub fn build_list_models_request(
+    base_url: &str,
+    api_key: &str,
+    flavor: &LlmEndpointFlavor,
+) -> HttpRequest {
+    let url = match flavor {
+        LlmEndpointFlavor::OpenAi => join_url(base_url, "/models"),
+        LlmEndpointFlavor::Azure { api_version } => format!(
+            "{}?api-version={}",
+            join_url(base_url, "/openai/models"),
+            api_version
+        ),
+    };
+    HttpRequest {
+        method: "GET".into(),
+        url,
+        headers: vec![auth_header(flavor, api_key)],
+        body: Body::Empty,
+    }
+}
+
+fn auth_header(flavor: &LlmEndpointFlavor, api_key: &str) -> (String, String) {
+    match flavor {
+        LlmEndpointFlavor::OpenAi => ("Authorization".into(), format!("Bearer {}", api_key)),
+        LlmEndpointFlavor::Azure { .. } => ("api-key".into(), api_key.into()),
+    }
+}
+
 fn join_url(base: &str, path: &str) -> String {
     let base = base.trim_end_matches('/');
     let path = path.trim_start_matches('/');
@@ -75,6 +138,8 @@ mod tests {
             base_url: "https://api.example.com/v1".into(),
             api_key: "k".into(),
             model: "gpt-4o-mini".into(),
+            decoding: Default::default(),
+            flavor: Default::default(),
         };
         let req = build_chat_completions_request(
             &cfg,
@@ -92,4 +157,79 @@ mod tests {
             _ => panic!("expected json"),
         }
     }
+
+    #[test]
+    fn sends_only_configured_decoding_settings() {
+        let body = |decoding: LlmDecodingSettings| {
+            let cfg = OpenAiCompatibleChatConfig {
+                base_url: "https://api.example.com/v1".into(),
+                api_key: "k".into(),
+                model: "llama3".into(),
+                decoding,
+                flavor: Default::default(),
+            };
+            match build_chat_completions_request(&cfg, &[]).body {
+                Body::Json(s) => serde_json::from_str::<serde_json::Value>(&s).unwrap(),
+                _ => panic!("expected json"),
+            }
+        };
+
+        let plain = body(Default::default());
+        assert_eq!(plain["temperature"].as_f64(), Some(0.3));
+        assert!(plain.get("max_tokens").is_none());
+        assert!(plain.get("stop").is_none());
+        assert!(plain.get("response_format").is_none());
+
+        let tuned = body(LlmDecodingSettings {
+            temperature_pct: Some(0),
+            max_tokens: Some(512),
+            stop: vec!["</s>".into()],
+            response_format: Some(ResponseFormat::JsonObject),
+        });
+        assert_eq!(tuned["temperature"].as_f64(), Some(0.0));
+        assert_eq!(tuned["max_tokens"], 512);
+        assert_eq!(tuned["stop"], serde_json::json!(["</s>"]));
+        assert_eq!(tuned["response_format"]["type"], "json_object");
+    }
+
+    #[test]
+    fn builds_list_models_probe() {
+        let req = build_list_models_request(
+            "https://api.example.com/v1/",
+            "k",
+            &LlmEndpointFlavor::OpenAi,
+        );
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.url, "https://api.example.com/v1/models");
+        assert_eq!(req.header("authorization"), Some("Bearer k"));
+        assert_eq!(req.body, Body::Empty);
+    }
+
+    #[test]
+    fn azure_flavor_uses_deployment_url_and_api_key_header() {
+        let azure = LlmEndpointFlavor::Azure {
+            api_version: "2024-10-21".into(),
+        };
+        let cfg = OpenAiCompatibleChatConfig {
+            base_url: "https://contoso.openai.azure.com/".into(),
+            api_key: "k".into(),
+            model: "gpt-4o-prod".into(),
+            decoding: Default::default(),
+            flavor: azure.clone(),
+        };
+        let req = build_chat_completions_request(&cfg, &[]);
+        assert_eq!(
+            req.url,
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(req.header("api-key"), Some("k"));
+        assert_eq!(req.header("authorization"), None);
+
+        let probe = build_list_models_request("https://contoso.openai.azure.com", "k", &azure);
+        assert_eq!(
+            probe.url,
+            "https://contoso.openai.azure.com/openai/models?api-version=2024-10-21"
+        );
+        assert_eq!(probe.header("api-key"), Some("k"));
+    }
 }
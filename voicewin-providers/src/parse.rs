@@ -1,5 +1,7 @@
 use anyhow::{Context, anyhow};
 use serde::Deserialize;
+use voicewin_core::diarization::{SpeakerNumbering, SpeakerSegment};
+use voicewin_core::usage::TokenUsage;
 
 #[derive(Debug, Deserialize)]
 pub struct ElevenLabsTranscriptionResponse {
@@ -12,9 +14,74 @@ pub fn parse_elevenlabs_transcription(body: &[u8]) -> anyhow::Result<String> {
     Ok(resp.text)
 }
 
+#[derive(Debug, Deserialize)]
+struct ElevenLabsDiarizedResponse {
+    text: String,
+    #[serde(default)]
+    words: Vec<ElevenLabsWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ElevenLabsWord {
+    text: String,
+    #[serde(default)]
+    speaker_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElevenLabsTranscription {
+    pub text: String,
+    /// Empty unless the request asked for diarization.
+    pub segments: Vec<SpeakerSegment>,
+}
+
+/// Text plus speaker segments built from the word list (`spacing` entries carry the spaces
+/// between words, so concatenating the entries reproduces the text).
+pub fn parse_elevenlabs_diarized_transcription(
+    body: &[u8],
+) -> anyhow::Result<ElevenLabsTranscription> {
+    let resp: ElevenLabsDiarizedResponse =
+        serde_json::from_slice(body).context("decode ElevenLabs JSON")?;
+
+    let mut numbering = SpeakerNumbering::default();
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+    for word in &resp.words {
+        let Some(id) = word.speaker_id.as_deref() else {
+            continue;
+        };
+        let speaker = numbering.number(id);
+        match segments.last_mut() {
+            Some(seg) if seg.speaker == speaker => seg.text.push_str(&word.text),
+            _ => segments.push(SpeakerSegment {
+                speaker,
+                text: word.text.clone(),
+            }),
+        }
+    }
+    for seg in &mut segments {
+        seg.text = seg.text.trim().to_string();
+    }
+
+    Ok(ElevenLabsTranscription {
+        text: resp.text,
+        segments,
+    })
+}
+
 #[derive(Debug, Deserialize)]
 struct OpenAiChatResponse {
     choices: Vec<OpenAiChoice>,
+    /// Missing on some self-hosted servers.
+    #[serde(default)]
+    usage: Option<OpenAiUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,15 +94,66 @@ struct OpenAiMessage {
     content: Option<String>,
 }
 
+/// The plan and this billing period's credit usage, from `GET /v1/user/subscription`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ElevenLabsSubscription {
+    pub tier: String,
+    /// Credits used this period; speech-to-text bills them by audio duration.
+    pub character_count: u64,
+    pub character_limit: u64,
+    #[serde(default)]
+    pub next_character_count_reset_unix: Option<i64>,
+}
+
+pub fn parse_elevenlabs_subscription(body: &[u8]) -> anyhow::Result<ElevenLabsSubscription> {
+    serde_json::from_slice(body).context("decode ElevenLabs subscription JSON")
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiModelList {
+    data: Vec<OpenAiModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiModel {
+    id: String,
+}
+
+/// Model ids from a `GET /models` response.
+pub fn parse_openai_model_ids(body: &[u8]) -> anyhow::Result<Vec<String>> {
+    let list: OpenAiModelList = serde_json::from_slice(body).context("decode models JSON")?;
+    Ok(list.data.into_iter().map(|m| m.id).collect())
+}
+
 pub fn parse_openai_chat_completion(body: &[u8]) -> anyhow::Result<String> {
+    Ok(parse_openai_chat_completion_with_usage(body)?.0)
+}
+
+/// The completion text plus the token counts the endpoint billed, when it reports them.
+pub fn parse_openai_chat_completion_with_usage(
+    body: &[u8],
+) -> anyhow::Result<(String, Option<TokenUsage>)> {
     let resp: OpenAiChatResponse = serde_json::from_slice(body).context("decode chat JSON")?;
+    let usage = resp.usage.map(|u| TokenUsage {
+        prompt_tokens: u.prompt_tokens,
+        completion_tokens: u.completion_tokens,
+    });
     let content = resp
         .choices
         .into_iter()
         .next()
         .and_then(|c| c.message.content)
         .ok_or_else(|| anyhow!("no content in chat completion response"))?;
-    Ok(content)
+    Ok((content, usage))
+}
+
+/// The text to insert from a JSON-mode completion: a top-level `"text"` string when there is
+/// one, otherwise the content unchanged.
+pub fn json_mode_text(content: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(content)
+        .ok()
+        .and_then(|v| v.get("text")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| content.to_string())
 }
 
 #[cfg(test)]
@@ -48,10 +166,80 @@ mod tests {
         assert_eq!(parse_elevenlabs_transcription(body).unwrap(), "hello");
     }
 
+    #[test]
+    fn groups_elevenlabs_words_by_speaker() {
+        let body = br#"{"text":"Hi there. Hello.","words":[
+            {"text":"Hi","type":"word","speaker_id":"speaker_1"},
+            {"text":" ","type":"spacing","speaker_id":"speaker_1"},
+            {"text":"there.","type":"word","speaker_id":"speaker_1"},
+            {"text":" ","type":"spacing","speaker_id":"speaker_0"},
+            {"text":"Hello.","type":"word","speaker_id":"speaker_0"}]}"#;
+        let t = parse_elevenlabs_diarized_transcription(body).unwrap();
+        assert_eq!(t.text, "Hi there. Hello.");
+        assert_eq!(
+            t.segments,
+            vec![
+                SpeakerSegment {
+                    speaker: 1,
+                    text: "Hi there.".into()
+                },
+                SpeakerSegment {
+                    speaker: 2,
+                    text: "Hello.".into()
+                },
+            ]
+        );
+
+        let plain = parse_elevenlabs_diarized_transcription(br#"{"text":"hi"}"#).unwrap();
+        assert!(plain.segments.is_empty());
+    }
+
     #[test]
     fn parses_openai_chat_content() {
         let body = br#"{"choices":[{"message":{"content":"hi"}}]}"#;
         assert_eq!(parse_openai_chat_completion(body).unwrap(), "hi");
+        assert_eq!(
+            parse_openai_chat_completion_with_usage(body).unwrap().1,
+            None
+        );
+
+        let body = br#"{"choices":[{"message":{"content":"hi"}}],
+            "usage":{"prompt_tokens":12,"completion_tokens":3,"total_tokens":15}}"#;
+        assert_eq!(
+            parse_openai_chat_completion_with_usage(body).unwrap(),
+            (
+                "hi".to_string(),
+                Some(TokenUsage {
+                    prompt_tokens: 12,
+                    completion_tokens: 3
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn json_mode_takes_the_text_field() {
+        assert_eq!(
+            json_mode_text(r#"{"text": "Hello, team."}"#),
+            "Hello, team."
+        );
+        assert_eq!(json_mode_text(r#"{"answer": "x"}"#), r#"{"answer": "x"}"#);
+        assert_eq!(json_mode_text("not json"), "not json");
+    }
+
+    #[test]
+    fn parses_subscription_and_model_list() {
+        let body = br#"{"tier":"creator","character_count":1200,"character_limit":100000,
+            "next_character_count_reset_unix":1760000000,"status":"active"}"#;
+        let sub = parse_elevenlabs_subscription(body).unwrap();
+        assert_eq!(sub.tier, "creator");
+        assert_eq!(sub.character_limit - sub.character_count, 98_800);
+        assert_eq!(sub.next_character_count_reset_unix, Some(1_760_000_000));
+
+        let body =
+            br#"{"object":"list","data":[{"id":"gpt-4o-mini","object":"model"},{"id":"o3"}]}"#;
+        assert_eq!(parse_openai_model_ids(body).unwrap(), ["gpt-4o-mini", "o3"]);
+        assert!(parse_openai_model_ids(b"[]").is_err());
     }
 
     #[test]
@@ -0,0 +1,176 @@
+//! Pre-connected ElevenLabs realtime sessions.
+//!
+//! Opening the websocket takes ~0.5–1 s. The connection manager lets callers start that
+//! handshake early (when the hotkey goes down, or right after the previous session ends) and
+//! hand the ready session to the next recording. A warm session that is stale, closed, or was
+//! opened with a different config is discarded and a fresh connection is made instead.
+
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Mutex, mpsc};
+
+use crate::elevenlabs_realtime::{
+    ElevenLabsRealtimeConfig, ElevenLabsRealtimeHandle, RealtimeEvent, spawn_realtime_session,
+};
+
+/// Idle sessions older than this are not reused (the server may have dropped them).
+pub const MAX_WARM_AGE: Duration = Duration::from_secs(60);
+
+pub type RealtimeSession = (ElevenLabsRealtimeHandle, mpsc::Receiver<RealtimeEvent>);
+
+struct Warm {
+    cfg: ElevenLabsRealtimeConfig,
+    started: Instant,
+    session: tokio::task::JoinHandle<anyhow::Result<RealtimeSession>>,
+}
+
+#[derive(Default)]
+struct State {
+    // Config of the most recent session; used to pre-connect before the next one is known.
+    last_cfg: Option<ElevenLabsRealtimeConfig>,
+    warm: Option<Warm>,
+}
+
+#[derive(Default)]
+pub struct RealtimeConnectionManager {
+    state: Mutex<State>,
+}
+
+impl RealtimeConnectionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start connecting in the background with `cfg` (no-op if a fresh one is already warm).
+    pub async fn prewarm(&self, cfg: ElevenLabsRealtimeConfig) {
+        let mut state = self.state.lock().await;
+        if let Some(w) = state.warm.as_ref()
+            && w.cfg == cfg
+            && w.started.elapsed() < MAX_WARM_AGE
+        {
+            return;
+        }
+        if let Some(old) = state.warm.take() {
+            discard(old);
+        }
+
+        state.warm = Some(Warm {
+            cfg: cfg.clone(),
+            started: Instant::now(),
+            session: tokio::spawn(spawn_realtime_session(cfg.clone())),
+        });
+        state.last_cfg = Some(cfg);
+    }
+
+    /// Pre-connect with the config of the previous session, if there was one.
+    pub async fn prewarm_last(&self) {
+        let last = self.state.lock().await.last_cfg.clone();
+        if let Some(cfg) = last {
+            self.prewarm(cfg).await;
+        }
+    }
+
+    /// A session for `cfg`: the warm one when it matches and is still usable, otherwise a
+    /// fresh connection.
+    pub async fn connect(&self, cfg: ElevenLabsRealtimeConfig) -> anyhow::Result<RealtimeSession> {
+        let warm = {
+            let mut state = self.state.lock().await;
+            state.last_cfg = Some(cfg.clone());
+            state.warm.take()
+        };
+
+        if let Some(w) = warm {
+            if w.cfg == cfg && w.started.elapsed() < MAX_WARM_AGE {
+                // Joins a handshake that is still in flight instead of starting a second one.
+                match w.session.await {
+                    Ok(Ok(session)) if !session.0.is_closed() => return Ok(session),
+                    Ok(Ok((handle, _))) => handle.shutdown().await,
+                    _ => {}
+                }
+            } else {
+                discard(w);
+            }
+        }
+
+        spawn_realtime_session(cfg).await
+    }
+
+    /// Close any warm session (e.g. pre-connect was turned off).
+    pub async fn discard_warm(&self) {
+        if let Some(w) = self.state.lock().await.warm.take() {
+            discard(w);
+        }
+    }
+}
+
+fn discard(w: Warm) {
+    tokio::spawn(async move {
+        if let Ok(Ok((handle, _))) = w.session.await {
+            handle.shutdown().await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+    use url::Url;
+
+    fn cfg(addr: std::net::SocketAddr, api_key: &str) -> ElevenLabsRealtimeConfig {
+        ElevenLabsRealtimeConfig {
+            ws_url: Url::parse(&format!("ws://{addr}/v1/speech-to-text/realtime")).unwrap(),
+            api_key: api_key.into(),
+            model_id: "scribe_v2".into(),
+            language_code: None,
+            sample_rate_hz: 16_000,
+            commit_strategy: "vad".into(),
+            vad: None,
+            connect_timeout: Duration::from_secs(2),
+            finalize_timeout: Duration::from_secs(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn reuses_matching_warm_session_and_replaces_mismatched_one() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let accepted_for_server = accepted.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                accepted_for_server.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut ws = accept_async(stream).await.unwrap();
+                    while let Some(Ok(_)) = ws.next().await {}
+                });
+            }
+        });
+
+        let pool = RealtimeConnectionManager::new();
+        pool.prewarm_last().await;
+        assert_eq!(
+            accepted.load(Ordering::SeqCst),
+            0,
+            "no previous session yet"
+        );
+
+        pool.prewarm(cfg(addr, "k")).await;
+        pool.prewarm(cfg(addr, "k")).await;
+        let (handle, _events) = pool.connect(cfg(addr, "k")).await.unwrap();
+        assert!(!handle.is_closed());
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+        handle.shutdown().await;
+
+        // A different config (e.g. new API key or sample rate) can't use the warm session.
+        pool.prewarm_last().await;
+        let (handle, _events) = pool.connect(cfg(addr, "other")).await.unwrap();
+        handle.shutdown().await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(accepted.load(Ordering::SeqCst), 3);
+    }
+}
@@ -14,10 +14,11 @@ impl std::fmt::Debug for HttpRequest {
             .headers
             .iter()
             .map(|(k, v)| {
-                let sensitive = k.eq_ignore_ascii_case("authorization")
-                    || k.eq_ignore_ascii_case("xi-api-key")
-                    || k.to_ascii_lowercase().contains("api-key");
-                let v = if sensitive { "[REDACTED]".into() } else { v.clone() };
+                let v = if is_sensitive_header(k) {
+                    "[REDACTED]".into()
+                } else {
+                    v.clone()
+                };
                 (k.clone(), v)
             })
             .collect();
@@ -39,6 +40,13 @@ impl std::fmt::Debug for HttpRequest {
     }
 }
 
+/// Headers carrying credentials; never printed or logged.
+pub(crate) fn is_sensitive_header(name: &str) -> bool {
+    name.eq_ignore_ascii_case("authorization")
+        || name.eq_ignore_ascii_case("xi-api-key")
+        || name.to_ascii_lowercase().contains("api-key")
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Body {
     Empty,
@@ -0,0 +1,40 @@
+use crate::request::{Body, HttpRequest};
+
+/// POST a JSON session result to a user-configured webhook. An empty token sends no
+/// `Authorization` header (e.g. for URLs that embed their own secret).
+pub fn build_webhook_request(url: &str, bearer_token: &str, json_body: String) -> HttpRequest {
+    let mut headers = vec![("Content-Type".into(), "application/json".into())];
+    let token = bearer_token.trim();
+    if !token.is_empty() {
+        headers.push(("Authorization".into(), format!("Bearer {token}")));
+    }
+
+    HttpRequest {
+        method: "POST".into(),
+        url: url.trim().to_string(),
+        headers,
+        body: Body::Json(json_body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_bearer_token_only_when_set() {
+        let req = build_webhook_request(
+            " https://hooks.example.com/dictation ",
+            "tok",
+            "{\"final_text\":\"hi\"}".into(),
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.url, "https://hooks.example.com/dictation");
+        assert_eq!(req.header("authorization"), Some("Bearer tok"));
+        assert_eq!(req.body, Body::Json("{\"final_text\":\"hi\"}".into()));
+
+        let req = build_webhook_request("https://hooks.example.com/x", " ", "{}".into());
+        assert_eq!(req.header("authorization"), None);
+        assert_eq!(req.header("content-type"), Some("application/json"));
+    }
+}
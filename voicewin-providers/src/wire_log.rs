@@ -0,0 +1,320 @@
+// Opt-in log of provider HTTP exchanges, so a failed enhancement ("status=400") can be
+// diagnosed without a packet capture.
+//
+// Off by default. Credential headers and key-like query parameters are always masked,
+// bodies go through the user's redaction filters before being truncated, and entries live
+// in a bounded in-memory ring buffer that is never written to disk.
+
+use std::collections::VecDeque;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+use voicewin_core::config::ProviderLogSettings;
+use voicewin_core::redaction::Redactor;
+
+use crate::request::{Body, HttpRequest, is_sensitive_header};
+use crate::runtime::HttpResponse;
+
+const MASK: &str = "REDACTED";
+
+/// One request and what came back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WireLogEntry {
+    pub ts_unix_ms: i64,
+    pub method: String,
+    pub url: String,
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: String,
+    /// `None` when no response arrived (offline, connection error, timeout).
+    pub status: Option<u16>,
+    pub response_body: Option<String>,
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct WireLog {
+    settings: ProviderLogSettings,
+    redactor: Redactor,
+    entries: VecDeque<WireLogEntry>,
+}
+
+impl WireLog {
+    fn push(&mut self, entry: WireLogEntry) {
+        let cap = self.settings.max_entries.max(1) as usize;
+        while self.entries.len() >= cap {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+static WIRE_LOG: LazyLock<Mutex<WireLog>> = LazyLock::new(|| Mutex::new(WireLog::default()));
+
+fn with_log<T>(f: impl FnOnce(&mut WireLog) -> T) -> T {
+    f(&mut WIRE_LOG.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Apply the config. Turning the log off drops everything it held.
+pub fn set_settings(settings: &ProviderLogSettings) {
+    with_log(|log| {
+        log.settings = settings.clone();
+        if !settings.enabled {
+            log.entries.clear();
+        }
+        let cap = settings.max_entries.max(1) as usize;
+        while log.entries.len() > cap {
+            log.entries.pop_front();
+        }
+    });
+}
+
+/// Filters applied to logged bodies, from the app's redaction settings.
+pub fn set_redactor(redactor: Redactor) {
+    with_log(|log| log.redactor = redactor);
+}
+
+pub fn is_enabled() -> bool {
+    with_log(|log| log.settings.enabled)
+}
+
+/// Logged exchanges, oldest first.
+pub fn entries() -> Vec<WireLogEntry> {
+    with_log(|log| log.entries.iter().cloned().collect())
+}
+
+pub fn clear() {
+    with_log(|log| log.entries.clear());
+}
+
+/// Called by [`crate::runtime::execute`] once a request has finished.
+pub(crate) fn record(
+    req: &HttpRequest,
+    outcome: Result<&HttpResponse, &anyhow::Error>,
+    elapsed: Duration,
+) {
+    with_log(|log| {
+        if !log.settings.enabled {
+            return;
+        }
+        let entry = entry_for(req, outcome, elapsed, &log.settings, &log.redactor);
+        log.push(entry);
+    });
+}
+
+fn entry_for(
+    req: &HttpRequest,
+    outcome: Result<&HttpResponse, &anyhow::Error>,
+    elapsed: Duration,
+    settings: &ProviderLogSettings,
+    redactor: &Redactor,
+) -> WireLogEntry {
+    let limit = settings.max_body_bytes as usize;
+    let scrub = |text: &str| truncate(&redactor.redact(text), limit);
+
+    let request_body = match &req.body {
+        Body::Empty => String::new(),
+        Body::Json(s) => scrub(s),
+        // Audio; its bytes would tell nobody anything.
+        Body::MultipartFormData { bytes, .. } => format!("[multipart body, {} bytes]", bytes.len()),
+    };
+    let (status, response_body, error) = match outcome {
+        Ok(resp) => (
+            Some(resp.status),
+            Some(scrub(&String::from_utf8_lossy(&resp.body))),
+            None,
+        ),
+        Err(e) => (None, None, Some(redactor.redact(&format!("{e:#}")))),
+    };
+
+    WireLogEntry {
+        ts_unix_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0),
+        method: req.method.clone(),
+        url: redact_url(&req.url),
+        request_headers: req
+            .headers
+            .iter()
+            .map(|(k, v)| {
+                let v = if is_sensitive_header(k) {
+                    MASK.to_string()
+                } else {
+                    v.clone()
+                };
+                (k.clone(), v)
+            })
+            .collect(),
+        request_body,
+        status,
+        response_body,
+        elapsed_ms: elapsed.as_millis() as u64,
+        error,
+    }
+}
+
+fn is_sensitive_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    ["key", "token", "secret", "sig", "password"]
+        .iter()
+        .any(|s| name.contains(s))
+}
+
+/// `url` without credentials in the userinfo or key-like query parameters.
+fn redact_url(raw: &str) -> String {
+    let Ok(mut url) = Url::parse(raw) else {
+        return raw.to_string();
+    };
+    if url.password().is_some() {
+        let _ = url.set_password(Some(MASK));
+    }
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| {
+                let v = if is_sensitive_param(&k) {
+                    MASK.to_string()
+                } else {
+                    v.into_owned()
+                };
+                (k.into_owned(), v)
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+fn truncate(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… [{} more bytes]", &text[..end], text.len() - end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voicewin_core::redaction::RedactionSettings;
+
+    fn settings(max_entries: u32, max_body_bytes: u32) -> ProviderLogSettings {
+        ProviderLogSettings {
+            enabled: true,
+            max_entries,
+            max_body_bytes,
+        }
+    }
+
+    fn get(url: &str) -> HttpRequest {
+        HttpRequest {
+            method: "GET".into(),
+            url: url.into(),
+            headers: vec![],
+            body: Body::Empty,
+        }
+    }
+
+    #[test]
+    fn masks_credentials_and_redacts_bodies() {
+        let req = HttpRequest {
+            method: "POST".into(),
+            url: "https://gw.example.com/v1/chat?api_key=sk-1&model=x".into(),
+            headers: vec![
+                ("Authorization".into(), "Bearer sk-123".into()),
+                ("api-key".into(), "az-456".into()),
+                ("Content-Type".into(), "application/json".into()),
+            ],
+            body: Body::Json(r#"{"content":"mail me at jane@example.com"}"#.into()),
+        };
+        let resp = HttpResponse {
+            status: 400,
+            body: b"{\"error\":\"bad request\"}".to_vec(),
+        };
+        let redactor = Redactor::from_settings(&RedactionSettings {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let entry = entry_for(
+            &req,
+            Ok(&resp),
+            Duration::from_millis(120),
+            &settings(10, 2048),
+            &redactor,
+        );
+        let dump = serde_json::to_string(&entry).unwrap();
+        assert!(!dump.contains("sk-1"));
+        assert!(!dump.contains("az-456"));
+        assert!(!dump.contains("jane@example.com"));
+        assert!(entry.url.contains("model=x"));
+        assert!(entry.request_body.contains("[REDACTED EMAIL]"));
+        assert_eq!(entry.status, Some(400));
+        assert_eq!(entry.elapsed_ms, 120);
+        assert_eq!(
+            entry.request_headers[2],
+            ("Content-Type".into(), "application/json".into())
+        );
+    }
+
+    #[test]
+    fn truncates_bodies_and_records_errors() {
+        let resp = HttpResponse {
+            status: 200,
+            body: "é".repeat(100).into_bytes(),
+        };
+        let entry = entry_for(
+            &get("https://api.example.com/v1/models"),
+            Ok(&resp),
+            Duration::ZERO,
+            &settings(10, 15),
+            &Redactor::default(),
+        );
+        assert_eq!(
+            entry.response_body.as_deref(),
+            Some("ééééééé… [186 more bytes]")
+        );
+
+        let err = anyhow::anyhow!("http request failed");
+        let entry = entry_for(
+            &get("https://api.example.com/v1/models"),
+            Err(&err),
+            Duration::ZERO,
+            &settings(10, 15),
+            &Redactor::default(),
+        );
+        assert_eq!(entry.status, None);
+        assert_eq!(entry.error.as_deref(), Some("http request failed"));
+    }
+
+    #[test]
+    fn ring_buffer_drops_the_oldest() {
+        let mut log = WireLog {
+            settings: settings(2, 100),
+            ..Default::default()
+        };
+        for path in ["a", "b", "c"] {
+            let req = get(&format!("https://api.example.com/{path}"));
+            let entry = entry_for(
+                &req,
+                Err(&anyhow::anyhow!("x")),
+                Duration::ZERO,
+                &log.settings,
+                &log.redactor,
+            );
+            log.push(entry);
+        }
+        let urls: Vec<_> = log.entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            ["https://api.example.com/b", "https://api.example.com/c"]
+        );
+    }
+}
@@ -1,26 +1,69 @@
 use anyhow::Context;
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use voicewin_core::config::AppConfig;
 
+/// How long `save` waits for another process to release the config lock.
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+const LOCK_POLL: Duration = Duration::from_millis(50);
+
+/// Another process (often a stuck older instance) holds the config lock.
+#[derive(Debug, thiserror::Error)]
+#[error("config is locked by another VoiceWin process: {}", path.display())]
+pub struct ConfigLockedError {
+    pub path: PathBuf,
+}
+
+/// `config.json` plus its siblings: `config.json.lock` (advisory lock held while writing)
+/// and `config.json.bak` (the previous good config, used if the main file is unreadable).
 #[derive(Debug, Clone)]
 pub struct ConfigStore {
     path: PathBuf,
+    lock_timeout: Duration,
 }
 
 impl ConfigStore {
     pub fn at_path(path: impl Into<PathBuf>) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+        }
+    }
+
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
     }
 
     pub fn path(&self) -> &Path {
         &self.path
     }
 
+    pub fn backup_path(&self) -> PathBuf {
+        self.sibling(".bak")
+    }
+
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut s = self.path.as_os_str().to_owned();
+        s.push(suffix);
+        PathBuf::from(s)
+    }
+
+    /// Load the config, falling back to the `.bak` copy if the main file is corrupt.
     pub fn load(&self) -> anyhow::Result<AppConfig> {
-        let bytes = std::fs::read(&self.path)
-            .with_context(|| format!("read config: {}", self.path.display()))?;
-        let cfg: AppConfig = serde_json::from_slice(&bytes).context("decode config JSON")?;
-        Ok(cfg)
+        match read_config(&self.path) {
+            Ok(cfg) => Ok(cfg),
+            Err(e) => match read_config(&self.backup_path()) {
+                Ok(cfg) => {
+                    log::warn!("config unreadable ({e:#}); using backup");
+                    Ok(cfg)
+                }
+                Err(_) => Err(e),
+            },
+        }
     }
 
     pub fn save(&self, cfg: &AppConfig) -> anyhow::Result<()> {
@@ -30,56 +73,147 @@ impl ConfigStore {
                 .with_context(|| format!("create config directory: {}", parent.display()))?;
         }
 
-        // Atomic-ish write: write temp then replace.
+        // Released when dropped at the end of the write.
+        let _lock = self.lock()?;
+
+        // Write to a temp file, verify what landed on disk, then rename over the config.
         let tmp = self.path.with_extension("json.tmp");
-        std::fs::write(&tmp, json).with_context(|| format!("write temp: {}", tmp.display()))?;
+        write_synced(&tmp, &json).with_context(|| format!("write temp: {}", tmp.display()))?;
+        let written =
+            std::fs::read(&tmp).with_context(|| format!("read back: {}", tmp.display()))?;
+        if Sha256::digest(&written) != Sha256::digest(&json) {
+            let _ = std::fs::remove_file(&tmp);
+            anyhow::bail!("config write verification failed: {}", tmp.display());
+        }
+
+        // Keep the previous config as `.bak`, but never overwrite a good backup with junk.
+        if read_config(&self.path).is_ok() {
+            let bak = self.backup_path();
+            std::fs::copy(&self.path, &bak)
+                .with_context(|| format!("write backup: {}", bak.display()))?;
+        }
+
         crate::models::replace_file(&tmp, &self.path)
             .with_context(|| format!("replace file: {}", self.path.display()))?;
         Ok(())
     }
+
+    fn lock(&self) -> anyhow::Result<File> {
+        let lock_path = self.sibling(".lock");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("open lock file: {}", lock_path.display()))?;
+
+        let deadline = Instant::now() + self.lock_timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(file),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    std::thread::sleep(LOCK_POLL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(ConfigLockedError {
+                        path: self.path.clone(),
+                    }
+                    .into());
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(anyhow::Error::new(e)
+                        .context(format!("lock config: {}", lock_path.display())));
+                }
+            }
+        }
+    }
+}
+
+fn read_config(path: &Path) -> anyhow::Result<AppConfig> {
+    let bytes = std::fs::read(path).with_context(|| format!("read config: {}", path.display()))?;
+    let cfg: AppConfig = serde_json::from_slice(&bytes).context("decode config JSON")?;
+    Ok(cfg)
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut f = File::create(path)?;
+    f.write_all(bytes)?;
+    f.sync_all()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use voicewin_core::enhancement::{PromptMode, PromptTemplate};
-    use voicewin_core::power_mode::GlobalDefaults;
-    use voicewin_core::types::{InsertMode, PromptId};
+    use voicewin_core::types::PromptId;
 
-    #[test]
-    fn round_trips_config() {
-        let dir = tempfile::tempdir().unwrap();
-        let path = dir.path().join("config.json");
-        let store = ConfigStore::at_path(path);
-
-        let cfg = AppConfig {
-            defaults: GlobalDefaults {
-                enable_enhancement: true,
-                prompt_id: None,
-                insert_mode: InsertMode::Paste,
-                stt_provider: "local".into(),
-                stt_model: "mock".into(),
-                language: "en".into(),
-                llm_base_url: "https://example.com/v1".into(),
-                llm_model: "gpt-4o-mini".into(),
-                microphone_device: None,
-                history_enabled: true,
-                context: voicewin_core::context::ContextToggles::default(),
-            },
-            profiles: vec![],
+    fn sample_config() -> AppConfig {
+        AppConfig {
             prompts: vec![PromptTemplate {
                 id: PromptId::new(),
                 title: "Default".into(),
                 mode: PromptMode::Enhancer,
                 prompt_text: "Fix.".into(),
                 trigger_words: vec!["rewrite".into()],
+                decoding: Default::default(),
             }],
-            llm_api_key_present: false,
-        };
+            ..AppConfig::for_tests()
+        }
+    }
 
+    #[test]
+    fn round_trips_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let store = ConfigStore::at_path(path);
+
+        let cfg = sample_config();
         store.save(&cfg).unwrap();
         let loaded = store.load().unwrap();
         assert_eq!(loaded.defaults.llm_model, "gpt-4o-mini");
         assert_eq!(loaded.prompts.len(), 1);
+
+        // The second save rotates the first into `.bak`.
+        let mut next = cfg.clone();
+        next.defaults.llm_model = "gpt-4.1".into();
+        store.save(&next).unwrap();
+        assert_eq!(
+            read_config(&store.backup_path())
+                .unwrap()
+                .defaults
+                .llm_model,
+            "gpt-4o-mini"
+        );
+
+        // A torn write is recovered from the backup instead of failing.
+        std::fs::write(store.path(), b"{\"defaults\": ").unwrap();
+        assert_eq!(store.load().unwrap().defaults.llm_model, "gpt-4o-mini");
+
+        // ...and a corrupt main file never replaces the good backup.
+        store.save(&next).unwrap();
+        assert_eq!(
+            read_config(&store.backup_path())
+                .unwrap()
+                .defaults
+                .llm_model,
+            "gpt-4o-mini"
+        );
+        assert_eq!(store.load().unwrap().defaults.llm_model, "gpt-4.1");
+    }
+
+    #[test]
+    fn save_reports_a_held_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConfigStore::at_path(dir.path().join("config.json"))
+            .with_lock_timeout(Duration::from_millis(100));
+
+        let held = store.lock().unwrap();
+        let cfg = sample_config();
+        let err = store.save(&cfg).unwrap_err();
+        assert!(err.downcast_ref::<ConfigLockedError>().is_some());
+        assert!(!store.path().exists());
+
+        drop(held);
+        store.save(&cfg).unwrap();
     }
 }
@@ -10,6 +10,7 @@ pub fn default_prompt_templates() -> Vec<PromptTemplate> {
         prompt_text:
             "Fix grammar, punctuation, and capitalization. Output only the corrected text.".into(),
         trigger_words: vec!["rewrite".into(), "clean up".into()],
+        decoding: Default::default(),
     }]
 }
 
@@ -27,6 +28,11 @@ pub fn default_global_defaults() -> GlobalDefaults {
         llm_model: "gpt-4o-mini".into(),
         microphone_device: None,
         history_enabled: true,
+        basic_cleanup: false,
+        output_format: Default::default(),
+        disfluency_filter: Default::default(),
+        text_pipeline: Default::default(),
+        realtime_vad: Default::default(),
         context: voicewin_core::context::ContextToggles::default(),
     }
 }
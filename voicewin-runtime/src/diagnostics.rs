@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use voicewin_core::config::AppConfig;
+use voicewin_core::power_mode::AppMatcher;
+
+const REDACTED: &str = "<redacted>";
+
+/// The config as JSON with user content removed, for attaching to bug reports.
+///
+/// Prompt texts, trigger words, window-title matchers, and custom redaction patterns are
+/// replaced, and the user's home directory is shortened to `~` in every path.
+pub fn anonymized_config(cfg: &AppConfig, home_dir: Option<&Path>) -> serde_json::Value {
+    let mut cfg = cfg.clone();
+
+    for p in &mut cfg.prompts {
+        p.prompt_text = format!("<{} chars>", p.prompt_text.chars().count());
+        p.trigger_words = p.trigger_words.iter().map(|_| REDACTED.into()).collect();
+    }
+
+    let matchers = cfg
+        .profiles
+        .iter_mut()
+        .flat_map(|p| p.matchers.iter_mut())
+        .chain(cfg.blocked_apps.iter_mut());
+    for m in matchers {
+        if let AppMatcher::WindowTitleContains(needle) = m {
+            *needle = REDACTED.into();
+        }
+    }
+
+    for pattern in &mut cfg.redaction.custom_patterns {
+        *pattern = REDACTED.into();
+    }
+
+    let mut value = serde_json::to_value(&cfg).unwrap_or_default();
+    if let Some(home) = home_dir
+        .map(|h| h.to_string_lossy().to_string())
+        .filter(|h| !h.is_empty())
+    {
+        replace_home(&mut value, &home);
+    }
+    value
+}
+
+fn replace_home(value: &mut serde_json::Value, home: &str) {
+    match value {
+        serde_json::Value::String(s) if s.contains(home) => *s = s.replace(home, "~"),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| replace_home(v, home)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|v| replace_home(v, home)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voicewin_core::enhancement::{PromptMode, PromptTemplate};
+    use voicewin_core::types::PromptId;
+
+    #[test]
+    fn anonymized_config_hides_user_content_and_home_dir() {
+        let mut cfg = AppConfig {
+            prompts: vec![PromptTemplate {
+                id: PromptId::new(),
+                title: "Default".into(),
+                mode: PromptMode::Enhancer,
+                prompt_text: "Acme internal style guide".into(),
+                trigger_words: vec!["acme".into()],
+                decoding: Default::default(),
+            }],
+            ..AppConfig::for_tests()
+        };
+        cfg.defaults.stt_model = "/home/jane/.local/share/voicewin/models/ggml-base.bin".into();
+        cfg.blocked_apps = vec![
+            AppMatcher::ProcessNameEquals("keepass.exe".into()),
+            AppMatcher::WindowTitleContains("Acme payroll".into()),
+        ];
+        cfg.redaction.custom_patterns = vec!["ACME-\\d+".into()];
+
+        let v = anonymized_config(&cfg, Some(Path::new("/home/jane")));
+        assert_eq!(
+            v["defaults"]["stt_model"],
+            "~/.local/share/voicewin/models/ggml-base.bin"
+        );
+        assert_eq!(v["blocked_apps"][0]["ProcessNameEquals"], "keepass.exe");
+        assert_eq!(v["blocked_apps"][1]["WindowTitleContains"], REDACTED);
+        assert_eq!(v["redaction"]["custom_patterns"][0], REDACTED);
+        assert_eq!(v["prompts"][0]["prompt_text"], "<25 chars>");
+
+        let dump = v.to_string();
+        assert!(!dump.contains("jane"));
+        assert!(!dump.contains("Acme"));
+    }
+}
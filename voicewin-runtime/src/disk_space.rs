@@ -0,0 +1,128 @@
+//! Free-space checks before writing large files.
+//!
+//! Model downloads run to gigabytes and long recordings spill to a temp file; running out of
+//! disk halfway through leaves a truncated download or a lost recording. Callers check the
+//! target volume up front with [`ensure_available`] and fail with [`InsufficientDiskSpaceError`].
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+/// Space kept free on top of what a write needs, so finishing it doesn't fill the disk.
+pub const DISK_HEADROOM_BYTES: u64 = 64 * 1024 * 1024;
+
+/// The volume holding `path` has less free space than a write needs.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "not enough disk space on {}: {required} bytes required, {available} bytes available",
+    path.display()
+)]
+pub struct InsufficientDiskSpaceError {
+    pub path: PathBuf,
+    pub required: u64,
+    pub available: u64,
+}
+
+/// Bytes available to this user on the volume holding `path`.
+///
+/// `path` need not exist yet (e.g. a models dir on first run): its nearest existing
+/// ancestor is queried instead.
+pub fn available_bytes(path: &Path) -> anyhow::Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or_else(|| Path::new("."));
+    query_available(existing)
+        .with_context(|| format!("failed to query free space: {}", existing.display()))
+}
+
+/// Fail unless the volume holding `path` can take `required` bytes plus
+/// [`DISK_HEADROOM_BYTES`].
+///
+/// If free space can't be queried the write is allowed; it will fail on its own if the disk
+/// really is full.
+pub fn ensure_available(path: &Path, required: u64) -> anyhow::Result<()> {
+    let available = match available_bytes(path) {
+        Ok(available) => available,
+        Err(e) => {
+            log::warn!("skipping disk space check: {e:#}");
+            return Ok(());
+        }
+    };
+    let required = required.saturating_add(DISK_HEADROOM_BYTES);
+    if available < required {
+        return Err(InsufficientDiskSpaceError {
+            path: path.to_path_buf(),
+            required,
+            available,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn query_available(path: &Path) -> std::io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `c_path` is NUL-terminated and `stat` is a valid out-pointer.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn query_available(path: &Path) -> std::io::Result<u64> {
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+    use windows::core::HSTRING;
+
+    let mut available = 0u64;
+    // SAFETY: the path is a valid wide string and `available` outlives the call.
+    unsafe {
+        GetDiskFreeSpaceExW(&HSTRING::from(path), Some(&mut available), None, None)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+    }
+    Ok(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn query_available(_path: &Path) -> std::io::Result<u64> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "free space query not supported on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_path_queries_nearest_existing_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("models").join("nested");
+        assert!(!missing.exists());
+        assert!(available_bytes(&missing).is_ok());
+    }
+
+    #[test]
+    fn reports_required_and_available_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        ensure_available(dir.path(), 0).unwrap();
+
+        let err = ensure_available(dir.path(), u64::MAX).unwrap_err();
+        let err = err.downcast_ref::<InsufficientDiskSpaceError>().unwrap();
+        assert_eq!(err.required, u64::MAX);
+        assert!(err.available < err.required);
+        assert!(
+            err.to_string()
+                .contains(&format!("{} bytes required", u64::MAX))
+        );
+    }
+}
@@ -1,10 +1,20 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Context;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
 use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use voicewin_core::note::utc_date_time;
+use voicewin_core::redaction::{RedactionSettings, Redactor};
+use voicewin_core::types::{ProfileId, PromptId};
+use voicewin_engine::session::SessionResult;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::secrets::{SecretKey, get_secret, set_secret};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub ts_unix_ms: i64,
     pub app_process_name: Option<String>,
@@ -20,12 +30,332 @@ pub struct HistoryEntry {
     // Optional error message if the session failed.
     #[serde(default)]
     pub error: Option<String>,
+
+    /// User-assigned labels ("email", "template", ...).
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Favorites are listed first in the UI and never trimmed by `max_entries`.
+    #[serde(default)]
+    pub pinned: bool,
+
+    /// What the STT returned, before the text pipeline and enhancement.
+    #[serde(default)]
+    pub raw_transcript: Option<String>,
+
+    /// What the LLM returned, when the session was enhanced.
+    #[serde(default)]
+    pub enhanced_text: Option<String>,
+
+    #[serde(default)]
+    pub prompt_id: Option<PromptId>,
+
+    /// The Power Mode profile that applied.
+    #[serde(default)]
+    pub profile_id: Option<ProfileId>,
+
+    /// How many times the same text was recorded again right after (a retry after a failed
+    /// insert); those entries were collapsed into this one.
+    #[serde(default)]
+    pub retry_count: u32,
+
+    /// Set when the entry was deleted; it stays in the trash until restored or purged.
+    #[serde(default)]
+    pub deleted_at_unix_ms: Option<i64>,
+}
+
+impl HistoryEntry {
+    /// Keep the transcript, the LLM output and what produced them next to the final text.
+    pub fn with_session_sources(mut self, result: &SessionResult) -> Self {
+        self.raw_transcript = result.transcript.as_ref().map(|t| t.text.clone());
+        self.enhanced_text = result.enhanced.as_ref().map(|e| e.text.clone());
+        self.prompt_id = result.prompt_id.clone();
+        self.profile_id = result.config.matched_profile_id.clone();
+        self
+    }
+
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at_unix_ms.is_some()
+    }
+
+    /// Same text as `prev`, recorded within `window_ms` after it.
+    fn is_retry_of(&self, prev: &HistoryEntry, window_ms: i64) -> bool {
+        window_ms > 0
+            && !self.is_trashed()
+            && !prev.is_trashed()
+            && !self.text.trim().is_empty()
+            && self.text == prev.text
+            && (0..=window_ms).contains(&(self.ts_unix_ms - prev.ts_unix_ms))
+    }
+
+    /// Apply the configured redaction before the entry is persisted.
+    ///
+    /// Invalid custom patterns fail closed: the text is stored as a hash only.
+    pub fn redacted(mut self, settings: &RedactionSettings) -> Self {
+        if !settings.enabled {
+            return self;
+        }
+
+        let (redactor, hash_only) = match Redactor::from_settings(settings) {
+            Ok(r) => (r, settings.hash_only),
+            Err(e) => {
+                log::warn!("{e}; storing history text as a hash");
+                (Redactor::default(), true)
+            }
+        };
+
+        let scrub = |text: &str| {
+            if hash_only {
+                text_digest(text)
+            } else {
+                redactor.redact(text)
+            }
+        };
+        self.text = scrub(&self.text);
+        self.raw_transcript = self.raw_transcript.map(|t| scrub(&t));
+        self.enhanced_text = self.enhanced_text.map(|t| scrub(&t));
+        self.app_window_title = self.app_window_title.map(|t| redactor.redact(&t));
+        self.error = self.error.map(|e| redactor.redact(&e));
+        self
+    }
+}
+
+/// Stable `sha256:<hex>` digest stored in place of text in hash-only mode.
+pub fn text_digest(text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    format!("sha256:{:x}", sha2::Sha256::digest(text.as_bytes()))
+}
+
+/// Which entries `HistoryStore::query` returns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryFilter {
+    /// Only entries carrying this tag (case-insensitive).
+    #[serde(default)]
+    pub tag: Option<String>,
+
+    #[serde(default)]
+    pub pinned_only: bool,
+
+    /// Only entries inside this time window ("yesterday's dictations").
+    #[serde(default, flatten)]
+    pub range: ExportRange,
+
+    /// List the trash instead of the live entries.
+    #[serde(default)]
+    pub trashed: bool,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        let tag_ok = self
+            .tag
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .is_none_or(|t| entry.tags.iter().any(|e| e.eq_ignore_ascii_case(t)));
+        tag_ok
+            && (entry.pinned || !self.pinned_only)
+            && self.range.contains(entry.ts_unix_ms)
+            && entry.is_trashed() == self.trashed
+    }
+}
+
+/// Trimmed, non-empty tags with case-insensitive duplicates removed (first spelling wins).
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(tags.len());
+    for t in tags {
+        let t = t.trim();
+        if !t.is_empty() && !out.iter().any(|o| o.eq_ignore_ascii_case(t)) {
+            out.push(t.to_string());
+        }
+    }
+    out
+}
+
+/// File formats accepted by `HistoryStore::export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Json,
+}
+
+/// Time window for an export; `None` bounds are open. `to_unix_ms` is exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRange {
+    #[serde(default)]
+    pub from_unix_ms: Option<i64>,
+
+    #[serde(default)]
+    pub to_unix_ms: Option<i64>,
+}
+
+impl ExportRange {
+    pub fn contains(&self, ts_unix_ms: i64) -> bool {
+        self.from_unix_ms.is_none_or(|from| ts_unix_ms >= from)
+            && self.to_unix_ms.is_none_or(|to| ts_unix_ms < to)
+    }
+}
+
+/// Serialize entries (oldest first) for a spreadsheet, a notes app, or another tool.
+pub fn export_entries(entries: &[HistoryEntry], format: ExportFormat) -> anyhow::Result<String> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(entries)?),
+        ExportFormat::Csv => Ok(export_csv(entries)),
+        ExportFormat::Markdown => Ok(export_markdown(entries)),
+    }
+}
+
+fn export_csv(entries: &[HistoryEntry]) -> String {
+    let mut out =
+        String::from("time_utc,ts_unix_ms,app,window_title,stage,text,error,tags,pinned\r\n");
+    for e in entries {
+        let (date, time) = utc_date_time(e.ts_unix_ms);
+        let row = [
+            format!("{date} {time}"),
+            e.ts_unix_ms.to_string(),
+            e.app_process_name.clone().unwrap_or_default(),
+            e.app_window_title.clone().unwrap_or_default(),
+            e.stage.clone(),
+            e.text.clone(),
+            e.error.clone().unwrap_or_default(),
+            e.tags.join(";"),
+            e.pinned.to_string(),
+        ];
+        let cells: Vec<String> = row.iter().map(|c| csv_field(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// RFC 4180 quoting: fields with separators, quotes, or line breaks are wrapped in quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn export_markdown(entries: &[HistoryEntry]) -> String {
+    let mut out = String::from("# VoiceWin history\n");
+    let mut current_date = String::new();
+    for e in entries {
+        let (date, time) = utc_date_time(e.ts_unix_ms);
+        if date != current_date {
+            out.push_str(&format!("\n## {date}\n"));
+            current_date = date;
+        }
+
+        let pin = if e.pinned { " ★" } else { "" };
+        match e.app_process_name.as_deref() {
+            Some(app) => out.push_str(&format!("\n### {time} UTC · {app}{pin}\n\n")),
+            None => out.push_str(&format!("\n### {time} UTC{pin}\n\n")),
+        }
+        if !e.text.trim().is_empty() {
+            out.push_str(e.text.trim());
+            out.push('\n');
+        }
+        if let Some(err) = e.error.as_deref().filter(|err| !err.trim().is_empty()) {
+            out.push_str(&format!("\n> Error: {}\n", err.trim()));
+        }
+        if !e.tags.is_empty() {
+            let tags: Vec<String> = e.tags.iter().map(|t| format!("#{t}")).collect();
+            out.push_str(&format!("\n{}\n", tags.join(" ")));
+        }
+    }
+    out
+}
+
+/// Prefix of an encrypted history file: magic, then a random nonce, then AES-256-GCM
+/// ciphertext of the JSON. Plaintext files start with `[` so the two can't be confused.
+const ENCRYPTED_MAGIC: &[u8] = b"VWHISTENC1";
+
+/// 256-bit key for at-rest History encryption.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HistoryKey([u8; 32]);
+
+impl std::fmt::Debug for HistoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HistoryKey(..)")
+    }
+}
+
+impl HistoryKey {
+    pub fn generate() -> anyhow::Result<Self> {
+        let mut key = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| anyhow::anyhow!("failed to generate history key"))?;
+        Ok(Self(key))
+    }
+
+    fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn from_hex(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s.len() != 64 {
+            return None;
+        }
+        let mut key = [0u8; 32];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = u8::from_str_radix(s.get(i * 2..i * 2 + 2)?, 16).ok()?;
+        }
+        Some(Self(key))
+    }
+
+    fn aead(&self) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &self.0).expect("32-byte AES key"))
+    }
+}
+
+/// The History key from the OS keyring. With `create`, a missing key is generated and stored.
+pub fn history_key_from_keyring(create: bool) -> anyhow::Result<Option<HistoryKey>> {
+    if let Some(v) = get_secret(SecretKey::HistoryEncryptionKey)? {
+        let key = HistoryKey::from_hex(&v).context("stored history key is malformed")?;
+        return Ok(Some(key));
+    }
+    if !create {
+        return Ok(None);
+    }
+
+    let key = HistoryKey::generate()?;
+    set_secret(SecretKey::HistoryEncryptionKey, &key.to_hex())?;
+    Ok(Some(key))
+}
+
+/// Deleted entries can be restored for this long before they are purged.
+pub const TRASH_RETENTION_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Entries with the same text this close together are one dictation retried, and are
+/// collapsed into one with a retry count.
+pub const DEFAULT_DEDUP_WINDOW_MS: i64 = 60_000;
+
+/// `retry` folded into the entry before it: the newest outcome wins, tags and pins are kept.
+fn collapse_retry(prev: &HistoryEntry, retry: HistoryEntry) -> HistoryEntry {
+    HistoryEntry {
+        tags: normalize_tags(prev.tags.iter().chain(&retry.tags).cloned().collect()),
+        pinned: prev.pinned || retry.pinned,
+        retry_count: prev.retry_count + retry.retry_count + 1,
+        ..retry
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct HistoryStore {
     path: PathBuf,
     max_entries: usize,
+    dedup_window_ms: i64,
+
+    // Used to read encrypted files, and to write them when `encrypt` is set.
+    key: Option<HistoryKey>,
+    encrypt: bool,
 }
 
 impl HistoryStore {
@@ -33,6 +363,9 @@ impl HistoryStore {
         Self {
             path,
             max_entries: 200,
+            dedup_window_ms: DEFAULT_DEDUP_WINDOW_MS,
+            key: None,
+            encrypt: false,
         }
     }
 
@@ -41,14 +374,58 @@ impl HistoryStore {
         self
     }
 
+    /// Collapse repeats of the same text within `window_ms` of each other; `0` keeps every
+    /// entry.
+    pub fn with_dedup_window_ms(mut self, window_ms: i64) -> Self {
+        self.dedup_window_ms = window_ms.max(0);
+        self
+    }
+
+    /// Encrypt the file on every write. Writes fail (rather than fall back to plaintext)
+    /// when `key` is `None`, e.g. because the keyring is unavailable.
+    pub fn with_encryption(mut self, key: Option<HistoryKey>) -> Self {
+        self.key = key;
+        self.encrypt = true;
+        self
+    }
+
+    /// Read an encrypted file but write plaintext (used after encryption is turned off).
+    pub fn with_decryption_key(mut self, key: HistoryKey) -> Self {
+        self.key = Some(key);
+        self.encrypt = false;
+        self
+    }
+
+    /// Whether the file currently on disk is encrypted.
+    pub fn is_encrypted_on_disk(&self) -> bool {
+        use std::io::Read;
+
+        let mut magic = [0u8; ENCRYPTED_MAGIC.len()];
+        fs::File::open(&self.path)
+            .and_then(|mut f| f.read_exact(&mut magic))
+            .is_ok_and(|()| magic == ENCRYPTED_MAGIC)
+    }
+
+    /// Live entries, oldest first; the trash is left out.
     pub fn load(&self) -> anyhow::Result<Vec<HistoryEntry>> {
+        let mut entries = self.load_all()?;
+        entries.retain(|e| !e.is_trashed());
+        Ok(entries)
+    }
+
+    /// Everything in the file, trash included.
+    fn load_all(&self) -> anyhow::Result<Vec<HistoryEntry>> {
         if !self.path.exists() {
             return Ok(vec![]);
         }
 
-        let raw = fs::read_to_string(&self.path)
+        let raw = fs::read(&self.path)
             .with_context(|| format!("failed to read history: {}", self.path.display()))?;
-        let entries: Vec<HistoryEntry> = serde_json::from_str(&raw)
+        let json = match raw.strip_prefix(ENCRYPTED_MAGIC) {
+            Some(sealed) => self.decrypt(sealed)?,
+            None => raw,
+        };
+        let entries: Vec<HistoryEntry> = serde_json::from_slice(&json)
             .with_context(|| format!("failed to parse history: {}", self.path.display()))?;
         Ok(entries)
     }
@@ -59,48 +436,246 @@ impl HistoryStore {
                 .with_context(|| format!("failed to create dir: {}", parent.display()))?;
         }
 
-        let mut entries = self.load()?;
-        entries.push(entry);
-        if entries.len() > self.max_entries {
-            let start = entries.len() - self.max_entries;
-            entries = entries.split_off(start);
+        let mut entries = self.load_all()?;
+        let purge_before = now_unix_ms() - TRASH_RETENTION_MS;
+        entries.retain(|e| e.deleted_at_unix_ms.is_none_or(|at| at >= purge_before));
+        match entries.last_mut() {
+            Some(last) if entry.is_retry_of(last, self.dedup_window_ms) => {
+                *last = collapse_retry(last, entry);
+            }
+            _ => entries.push(entry),
+        }
+
+        // Drop the oldest unpinned entries first; favorites are only removed explicitly.
+        // The trash doesn't count towards the limit.
+        let live = entries.iter().filter(|e| !e.is_trashed()).count();
+        let mut excess = live.saturating_sub(self.max_entries);
+        entries.retain(|e| {
+            if excess > 0 && !e.pinned && !e.is_trashed() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+
+        self.write(&entries)
+    }
+
+    /// Entries matching `filter`, oldest first (like `load`).
+    pub fn query(&self, filter: &HistoryFilter) -> anyhow::Result<Vec<HistoryEntry>> {
+        Ok(self
+            .load_all()?
+            .into_iter()
+            .filter(|e| filter.matches(e))
+            .collect())
+    }
+
+    /// All tags in use, sorted case-insensitively.
+    pub fn tags(&self) -> anyhow::Result<Vec<String>> {
+        let all = self.load()?.into_iter().flat_map(|e| e.tags).collect();
+        let mut tags = normalize_tags(all);
+        tags.sort_by_key(|t| t.to_lowercase());
+        Ok(tags)
+    }
+
+    /// Replace the tags of the newest entry matching `ts_unix_ms` and `text`.
+    pub fn set_tags(&self, ts_unix_ms: i64, text: &str, tags: Vec<String>) -> anyhow::Result<bool> {
+        let tags = normalize_tags(tags);
+        self.update_entry(ts_unix_ms, text, |e| e.tags = tags)
+    }
+
+    pub fn set_pinned(&self, ts_unix_ms: i64, text: &str, pinned: bool) -> anyhow::Result<bool> {
+        self.update_entry(ts_unix_ms, text, |e| e.pinned = pinned)
+    }
+
+    /// Replace the text of the newest entry matching `ts_unix_ms` and `text` with a new LLM
+    /// output, recording it as the entry's enhancement made with `prompt_id`.
+    pub fn set_enhanced_text(
+        &self,
+        ts_unix_ms: i64,
+        text: &str,
+        enhanced: String,
+        prompt_id: PromptId,
+    ) -> anyhow::Result<bool> {
+        self.update_entry(ts_unix_ms, text, |e| {
+            e.text = enhanced.clone();
+            e.enhanced_text = Some(enhanced);
+            e.prompt_id = Some(prompt_id);
+        })
+    }
+
+    fn update_entry(
+        &self,
+        ts_unix_ms: i64,
+        text: &str,
+        update: impl FnOnce(&mut HistoryEntry),
+    ) -> anyhow::Result<bool> {
+        self.update_matching(ts_unix_ms, text, false, update)
+    }
+
+    /// Update the newest entry matching `ts_unix_ms` and `text`, in the trash or not.
+    fn update_matching(
+        &self,
+        ts_unix_ms: i64,
+        text: &str,
+        trashed: bool,
+        update: impl FnOnce(&mut HistoryEntry),
+    ) -> anyhow::Result<bool> {
+        let mut entries = self.load_all()?;
+        let Some(entry) = entries
+            .iter_mut()
+            .rev()
+            .find(|e| e.ts_unix_ms == ts_unix_ms && e.text == text && e.is_trashed() == trashed)
+        else {
+            return Ok(false);
+        };
+        update(entry);
+        self.write(&entries)?;
+        Ok(true)
+    }
+
+    /// Write the entries inside `range` to `path`; returns how many were exported.
+    pub fn export(
+        &self,
+        format: ExportFormat,
+        range: ExportRange,
+        path: &Path,
+    ) -> anyhow::Result<usize> {
+        let entries: Vec<HistoryEntry> = self
+            .load()?
+            .into_iter()
+            .filter(|e| range.contains(e.ts_unix_ms))
+            .collect();
+        let body = export_entries(&entries, format)?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create dir: {}", parent.display()))?;
+        }
+        fs::write(path, body)
+            .with_context(|| format!("failed to write export: {}", path.display()))?;
+        Ok(entries.len())
+    }
+
+    /// Rewrite the file in the configured format (encrypting or decrypting existing History).
+    pub fn migrate(&self) -> anyhow::Result<()> {
+        if !self.path.exists() || self.is_encrypted_on_disk() == self.encrypt {
+            return Ok(());
         }
+        let entries = self.load_all()?;
+        self.write(&entries)
+    }
+
+    fn write(&self, entries: &[HistoryEntry]) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(entries)?;
+        let bytes = if self.encrypt {
+            self.encrypt(json)?
+        } else {
+            json
+        };
 
         let tmp = self.path.with_extension("tmp");
-        fs::write(&tmp, serde_json::to_string_pretty(&entries)?)
+        fs::write(&tmp, bytes)
             .with_context(|| format!("failed to write history temp: {}", tmp.display()))?;
         crate::models::replace_file(&tmp, &self.path)
             .with_context(|| format!("failed to replace history: {}", self.path.display()))?;
         Ok(())
     }
 
-    pub fn delete_entry(&self, ts_unix_ms: i64, text: &str) -> anyhow::Result<bool> {
-        if !self.path.exists() {
-            return Ok(false);
+    fn encrypt(&self, mut json: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let key = self
+            .key
+            .as_ref()
+            .context("history encryption is enabled but the key is unavailable")?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow::anyhow!("failed to generate history nonce"))?;
+        key.aead()
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut json)
+            .map_err(|_| anyhow::anyhow!("failed to encrypt history"))?;
+
+        let mut out = Vec::with_capacity(ENCRYPTED_MAGIC.len() + NONCE_LEN + json.len());
+        out.extend_from_slice(ENCRYPTED_MAGIC);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&json);
+        Ok(out)
+    }
+
+    fn decrypt(&self, sealed: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let key = self
+            .key
+            .as_ref()
+            .context("history is encrypted but the key is unavailable")?;
+        if sealed.len() < NONCE_LEN {
+            anyhow::bail!("encrypted history is truncated: {}", self.path.display());
         }
 
-        let mut entries = self.load()?;
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| anyhow::anyhow!("invalid history nonce"))?;
+        let mut buf = ciphertext.to_vec();
+        let plain = key
+            .aead()
+            .open_in_place(nonce, Aad::empty(), &mut buf)
+            .map_err(|_| {
+                anyhow::anyhow!("failed to decrypt history (wrong key or corrupted file)")
+            })?;
+        Ok(plain.to_vec())
+    }
+
+    /// Move the newest matching entry (most user-intentful if duplicates exist) to the
+    /// trash. It is purged [`TRASH_RETENTION_MS`] later unless restored.
+    pub fn delete_entry(&self, ts_unix_ms: i64, text: &str) -> anyhow::Result<bool> {
+        let now = now_unix_ms();
+        self.update_matching(ts_unix_ms, text, false, |e| {
+            e.deleted_at_unix_ms = Some(now)
+        })
+    }
+
+    /// Take the newest matching entry back out of the trash.
+    pub fn restore_entry(&self, ts_unix_ms: i64, text: &str) -> anyhow::Result<bool> {
+        self.update_matching(ts_unix_ms, text, true, |e| e.deleted_at_unix_ms = None)
+    }
+
+    /// Permanently remove everything in the trash; returns how many entries were removed.
+    pub fn empty_trash(&self) -> anyhow::Result<usize> {
+        let mut entries = self.load_all()?;
         let before = entries.len();
+        entries.retain(|e| !e.is_trashed());
 
-        // Remove the newest matching entry (most user-intentful if duplicates exist).
-        if let Some(idx) = entries
-            .iter()
-            .rposition(|e| e.ts_unix_ms == ts_unix_ms && e.text == text)
-        {
-            entries.remove(idx);
+        let removed = before - entries.len();
+        if removed > 0 {
+            self.write(&entries)?;
         }
+        Ok(removed)
+    }
 
-        if entries.len() == before {
-            return Ok(false);
+    /// Collapse retried dictations already in the file, e.g. from before deduplication.
+    /// Returns how many entries were merged away.
+    pub fn compact(&self) -> anyhow::Result<usize> {
+        if !self.path.exists() {
+            return Ok(0);
         }
 
-        let tmp = self.path.with_extension("tmp");
-        fs::write(&tmp, serde_json::to_string_pretty(&entries)?)
-            .with_context(|| format!("failed to write history temp: {}", tmp.display()))?;
-        crate::models::replace_file(&tmp, &self.path)
-            .with_context(|| format!("failed to replace history: {}", self.path.display()))?;
+        let entries = self.load_all()?;
+        let before = entries.len();
+        let mut compacted: Vec<HistoryEntry> = Vec::with_capacity(before);
+        for entry in entries {
+            match compacted.last_mut() {
+                Some(last) if entry.is_retry_of(last, self.dedup_window_ms) => {
+                    *last = collapse_retry(last, entry);
+                }
+                _ => compacted.push(entry),
+            }
+        }
 
-        Ok(true)
+        let removed = before - compacted.len();
+        if removed > 0 {
+            self.write(&compacted)?;
+        }
+        Ok(removed)
     }
 
     pub fn clear(&self) -> anyhow::Result<()> {
@@ -116,6 +691,13 @@ impl HistoryStore {
     }
 }
 
+fn now_unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,34 +710,25 @@ mod tests {
         store
             .append(HistoryEntry {
                 ts_unix_ms: 1,
-                app_process_name: None,
-                app_exe_path: None,
-                app_window_title: None,
                 text: "a".into(),
                 stage: "done".into(),
-                error: None,
+                ..Default::default()
             })
             .unwrap();
         store
             .append(HistoryEntry {
                 ts_unix_ms: 2,
-                app_process_name: None,
-                app_exe_path: None,
-                app_window_title: None,
                 text: "b".into(),
                 stage: "done".into(),
-                error: None,
+                ..Default::default()
             })
             .unwrap();
         store
             .append(HistoryEntry {
                 ts_unix_ms: 3,
-                app_process_name: None,
-                app_exe_path: None,
-                app_window_title: None,
                 text: "c".into(),
                 stage: "done".into(),
-                error: None,
+                ..Default::default()
             })
             .unwrap();
 
@@ -164,4 +737,329 @@ mod tests {
         assert_eq!(entries[0].text, "b");
         assert_eq!(entries[1].text, "c");
     }
+
+    #[test]
+    fn encrypted_history_round_trips_and_migrates_from_plaintext() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let entry = |text: &str| HistoryEntry {
+            ts_unix_ms: 1,
+            text: text.into(),
+            stage: "done".into(),
+            ..Default::default()
+        };
+
+        // Existing plaintext history.
+        HistoryStore::at_path(path.clone())
+            .append(entry("secret plan"))
+            .unwrap();
+
+        let key = HistoryKey::generate().unwrap();
+        let store = HistoryStore::at_path(path.clone()).with_encryption(Some(key.clone()));
+        assert!(!store.is_encrypted_on_disk());
+        store.migrate().unwrap();
+        assert!(store.is_encrypted_on_disk());
+
+        let raw = fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("secret plan"));
+
+        store.append(entry("second")).unwrap();
+        let texts: Vec<_> = store.load().unwrap().into_iter().map(|e| e.text).collect();
+        assert_eq!(texts, ["secret plan", "second"]);
+
+        // Without the key the file can't be read; with the wrong key it can't be decrypted.
+        assert!(HistoryStore::at_path(path.clone()).load().is_err());
+        let wrong = HistoryStore::at_path(path.clone())
+            .with_decryption_key(HistoryKey::generate().unwrap());
+        assert!(wrong.load().is_err());
+
+        // Turning encryption off migrates back to plaintext.
+        let plain = HistoryStore::at_path(path.clone()).with_decryption_key(key);
+        plain.migrate().unwrap();
+        assert!(!plain.is_encrypted_on_disk());
+        assert_eq!(HistoryStore::at_path(path).load().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn encryption_without_a_key_refuses_to_write_plaintext() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let store = HistoryStore::at_path(path.clone()).with_encryption(None);
+        let entry = HistoryEntry {
+            ts_unix_ms: 1,
+            text: "a".into(),
+            stage: "done".into(),
+            ..Default::default()
+        };
+        assert!(store.append(entry).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn retried_dictations_collapse_into_one_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let entry = |ts: i64, text: &str, stage: &str| HistoryEntry {
+            ts_unix_ms: ts,
+            text: text.into(),
+            stage: stage.into(),
+            ..Default::default()
+        };
+
+        let store = HistoryStore::at_path(path.clone());
+        store.append(entry(1_000, "Call Bob", "failed")).unwrap();
+        store.set_pinned(1_000, "Call Bob", true).unwrap();
+        store.append(entry(5_000, "Call Bob", "done")).unwrap();
+        // Too late, or not right after: kept apart.
+        store.append(entry(70_000, "Call Bob", "done")).unwrap();
+        store.append(entry(71_000, "Other", "done")).unwrap();
+        store.append(entry(72_000, "Call Bob", "done")).unwrap();
+
+        let entries = store.load().unwrap();
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.ts_unix_ms, e.stage.as_str(), e.retry_count))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (5_000, "done", 1),
+                (70_000, "done", 0),
+                (71_000, "done", 0),
+                (72_000, "done", 0),
+            ]
+        );
+        assert!(entries[0].pinned);
+
+        // Files written without deduplication are collapsed on demand.
+        let path = dir.path().join("old.json");
+        let raw = HistoryStore::at_path(path.clone()).with_dedup_window_ms(0);
+        for ts in [1_000, 2_000, 3_000] {
+            raw.append(entry(ts, "Call Bob", "done")).unwrap();
+        }
+        assert_eq!(raw.load().unwrap().len(), 3);
+        let store = HistoryStore::at_path(path);
+        assert_eq!(store.compact().unwrap(), 2);
+        assert_eq!(store.compact().unwrap(), 0);
+        let entries = store.load().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].ts_unix_ms, entries[0].retry_count), (3_000, 2));
+    }
+
+    #[test]
+    fn deleted_entries_go_to_the_trash_until_restored_or_purged() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::at_path(dir.path().join("history.json")).with_max_entries(2);
+        let entry = |ts: i64, text: &str| HistoryEntry {
+            ts_unix_ms: ts,
+            text: text.into(),
+            stage: "done".into(),
+            ..Default::default()
+        };
+        let texts = |entries: Vec<HistoryEntry>| -> Vec<String> {
+            entries.into_iter().map(|e| e.text).collect()
+        };
+        let trash = HistoryFilter {
+            trashed: true,
+            ..Default::default()
+        };
+
+        store.append(entry(1, "a")).unwrap();
+        store.append(entry(2, "b")).unwrap();
+        assert!(store.delete_entry(1, "a").unwrap());
+        assert!(!store.delete_entry(1, "a").unwrap());
+        assert_eq!(texts(store.load().unwrap()), ["b"]);
+        assert_eq!(texts(store.query(&trash).unwrap()), ["a"]);
+
+        // The trash doesn't count towards `max_entries`.
+        store.append(entry(3, "c")).unwrap();
+        assert_eq!(texts(store.load().unwrap()), ["b", "c"]);
+        assert!(store.restore_entry(1, "a").unwrap());
+        assert!(!store.restore_entry(1, "a").unwrap());
+        assert_eq!(texts(store.load().unwrap()), ["a", "b", "c"]);
+        assert!(store.query(&trash).unwrap().is_empty());
+
+        // Expired trash is purged on the next write.
+        store.delete_entry(1, "a").unwrap();
+        store
+            .append(HistoryEntry {
+                deleted_at_unix_ms: Some(now_unix_ms() - TRASH_RETENTION_MS - 1),
+                ..entry(4, "old")
+            })
+            .unwrap();
+        assert_eq!(texts(store.query(&trash).unwrap()), ["a", "old"]);
+        store.append(entry(5, "d")).unwrap();
+        assert_eq!(texts(store.query(&trash).unwrap()), ["a"]);
+        assert_eq!(texts(store.load().unwrap()), ["c", "d"]);
+
+        assert_eq!(store.empty_trash().unwrap(), 1);
+        assert!(store.query(&trash).unwrap().is_empty());
+    }
+
+    #[test]
+    fn history_key_hex_round_trips() {
+        let key = HistoryKey::generate().unwrap();
+        assert_eq!(HistoryKey::from_hex(&key.to_hex()), Some(key));
+        assert_eq!(HistoryKey::from_hex("zz"), None);
+    }
+
+    #[test]
+    fn redacted_scrubs_text_or_keeps_only_a_hash() {
+        let entry = HistoryEntry {
+            ts_unix_ms: 1,
+            app_window_title: Some("Inbox - jane@example.com".into()),
+            text: "my ssn is 123-45-6789".into(),
+            stage: "done".into(),
+            raw_transcript: Some("my ssn is 123-45-6789".into()),
+            ..Default::default()
+        };
+
+        let mut settings = RedactionSettings::default();
+        assert_eq!(entry.clone().redacted(&settings).text, entry.text);
+
+        settings.enabled = true;
+        let r = entry.clone().redacted(&settings);
+        assert_eq!(r.text, "my ssn is [REDACTED SSN]");
+        assert_eq!(
+            r.raw_transcript.as_deref(),
+            Some("my ssn is [REDACTED SSN]")
+        );
+        assert_eq!(
+            r.app_window_title.as_deref(),
+            Some("Inbox - [REDACTED EMAIL]")
+        );
+
+        settings.hash_only = true;
+        let r = entry.clone().redacted(&settings);
+        assert_eq!(r.text, text_digest(&entry.text));
+        assert!(r.text.starts_with("sha256:"));
+        assert_eq!(r.raw_transcript, Some(text_digest(&entry.text)));
+
+        // A broken user pattern must not leak plaintext.
+        settings.hash_only = false;
+        settings.custom_patterns = vec!["(".into()];
+        assert_eq!(
+            entry.clone().redacted(&settings).text,
+            text_digest(&entry.text)
+        );
+    }
+
+    #[test]
+    fn entries_from_before_session_sources_still_load() {
+        let json = r#"{"ts_unix_ms":1,"app_process_name":null,"app_exe_path":null,"app_window_title":null,"text":"hi","stage":"done"}"#;
+        let entry: HistoryEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.raw_transcript, None);
+        assert_eq!(entry.enhanced_text, None);
+        assert_eq!(entry.prompt_id, None);
+        assert_eq!(entry.profile_id, None);
+    }
+
+    #[test]
+    fn tags_and_pins_filter_queries_and_survive_trimming() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::at_path(dir.path().join("history.json")).with_max_entries(2);
+        let entry = |ts: i64, text: &str| HistoryEntry {
+            ts_unix_ms: ts,
+            text: text.into(),
+            stage: "done".into(),
+            ..Default::default()
+        };
+
+        store.append(entry(1, "signature")).unwrap();
+        assert!(store.set_pinned(1, "signature", true).unwrap());
+        assert!(
+            store
+                .set_tags(
+                    1,
+                    "signature",
+                    vec![" Email ".into(), "email".into(), "".into()]
+                )
+                .unwrap()
+        );
+        assert!(!store.set_pinned(9, "missing", true).unwrap());
+
+        store.append(entry(2, "b")).unwrap();
+        store.append(entry(3, "c")).unwrap();
+        store.set_tags(3, "c", vec!["todo".into()]).unwrap();
+
+        // The pinned entry outlives the trim; the oldest unpinned one goes.
+        let texts: Vec<_> = store.load().unwrap().into_iter().map(|e| e.text).collect();
+        assert_eq!(texts, ["signature", "c"]);
+
+        let pinned = store
+            .query(&HistoryFilter {
+                pinned_only: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].tags, ["Email"]);
+
+        let tagged = store
+            .query(&HistoryFilter {
+                tag: Some("EMAIL".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(tagged[0].text, "signature");
+
+        let recent = store
+            .query(&HistoryFilter {
+                range: ExportRange {
+                    from_unix_ms: Some(3),
+                    to_unix_ms: None,
+                },
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].text, "c");
+
+        assert_eq!(store.tags().unwrap(), ["Email", "todo"]);
+    }
+
+    #[test]
+    fn exports_range_as_csv_markdown_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::at_path(dir.path().join("history.json"));
+        // 2026-10-15T18:24:09Z and one day later.
+        let day1 = 1_792_088_649_000;
+        let day2 = day1 + 24 * 60 * 60 * 1000;
+        for (ts, text) in [(day1, "Hello, \"team\""), (day2, "second")] {
+            store
+                .append(HistoryEntry {
+                    ts_unix_ms: ts,
+                    app_process_name: Some("slack.exe".into()),
+                    text: text.into(),
+                    stage: "done".into(),
+                    tags: vec!["work".into()],
+                    pinned: ts == day1,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let all = store.load().unwrap();
+        let csv = export_entries(&all, ExportFormat::Csv).unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "2026-10-15 18:24,1792088649000,slack.exe,,done,\"Hello, \"\"team\"\"\",,work,true"
+        );
+
+        let md = export_entries(&all, ExportFormat::Markdown).unwrap();
+        assert!(md.contains(
+            "## 2026-10-15\n\n### 18:24 UTC · slack.exe ★\n\nHello, \"team\"\n\n#work\n"
+        ));
+        assert!(md.contains("## 2026-10-16\n"));
+
+        let out = dir.path().join("exports").join("history.json");
+        let range = ExportRange {
+            from_unix_ms: Some(day2),
+            to_unix_ms: None,
+        };
+        assert_eq!(store.export(ExportFormat::Json, range, &out).unwrap(), 1);
+        let exported: Vec<HistoryEntry> = serde_json::from_slice(&fs::read(&out).unwrap()).unwrap();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].text, "second");
+    }
 }
@@ -1,13 +1,66 @@
 use serde::{Deserialize, Serialize};
+use voicewin_core::diff::{DiffSpan, word_diff};
+use voicewin_core::error::VoicewinError;
+use voicewin_core::power_mode::EphemeralOverrides;
+use voicewin_core::types::{AppIdentity, ProfileId, PromptId};
+use voicewin_engine::session::{SessionResult, SessionTimings};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RunSessionRequest {
-    // Reserved for future use (e.g., manual transcript override / debug).
+    // Transcript override: when set, STT is skipped (realtime sessions, debugging).
     pub transcript: String,
 
     // Optional non-fatal warning to attach to the session result (and persist to History).
     #[serde(default)]
     pub warning: Option<String>,
+
+    // Text already typed into the target app during recording (live typing); only the rest
+    // of `transcript` gets inserted.
+    #[serde(default)]
+    pub live_typed: Option<String>,
+
+    // Per-session overrides, on top of the matched (or hotkey-forced) profile.
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub profile_id: Option<ProfileId>,
+    // Selecting a prompt turns enhancement on.
+    #[serde(default)]
+    pub prompt_id: Option<PromptId>,
+    #[serde(default)]
+    pub enable_enhancement: Option<bool>,
+    // A fallback STT provider and model picked at session start (see `stt::fallback`).
+    #[serde(default)]
+    pub stt_provider: Option<String>,
+    #[serde(default)]
+    pub stt_model: Option<String>,
+
+    // The app in front when recording started. Power Mode and insertion target it instead of
+    // whatever has focus when the pipeline runs; unset means "look it up then". The window
+    // handle isn't serialized, so only in-process callers can return focus to the window.
+    #[serde(default)]
+    pub target_app: Option<AppIdentity>,
+}
+
+impl RunSessionRequest {
+    /// The request's overrides; `forced_profile` (the profile switched by hotkey) applies
+    /// unless the request picks one itself.
+    pub fn overrides(&self, forced_profile: Option<ProfileId>) -> EphemeralOverrides {
+        EphemeralOverrides {
+            forced_profile_id: self.profile_id.clone().or(forced_profile),
+            forced_prompt_id: self.prompt_id.clone(),
+            forced_enable_enhancement: self.enable_enhancement,
+            language: self
+                .language
+                .as_deref()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string),
+            stt_provider: self.stt_provider.clone(),
+            stt_model: self.stt_model.clone(),
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -20,12 +73,105 @@ pub struct ToggleRecordingResponse {
     pub status: RecordingStatus,
     pub stage: String,
     pub final_text: Option<String>,
-    pub error: Option<String>,
+    pub error: Option<VoicewinError>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RunSessionResponse {
     pub stage: String,
     pub final_text: Option<String>,
-    pub error: Option<String>,
+    pub error: Option<VoicewinError>,
+
+    // Where the time went and which backends produced the text. Unset for stages that did
+    // not run.
+    #[serde(default)]
+    pub transcription_ms: Option<u64>,
+    #[serde(default)]
+    pub enhancement_ms: Option<u64>,
+    #[serde(default)]
+    pub stt_provider: Option<String>,
+    #[serde(default)]
+    pub stt_model: Option<String>,
+    #[serde(default)]
+    pub llm_model: Option<String>,
+}
+
+impl RunSessionResponse {
+    pub fn from_result(result: &SessionResult) -> Self {
+        let raw = result.transcript.as_ref();
+        Self {
+            stage: result
+                .stage_label
+                .clone()
+                .unwrap_or_else(|| format!("{:?}", result.stage).to_lowercase()),
+            final_text: result.final_text.clone(),
+            error: result.error.clone(),
+            transcription_ms: result.timings.transcription_ms,
+            enhancement_ms: result.timings.enhancement_ms,
+            stt_provider: raw.map(|t| t.provider.clone()),
+            stt_model: raw.map(|t| t.model.clone()),
+            llm_model: result.enhanced.as_ref().map(|e| e.model.clone()),
+        }
+    }
+}
+
+/// The last session as shown in the details window: what was heard, what the LLM made of it,
+/// and a word diff between the two.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionDetails {
+    pub ts_unix_ms: i64,
+    pub stage: String,
+    pub app_process_name: Option<String>,
+    pub profile: Option<String>,
+
+    pub raw_transcript: Option<String>,
+    pub stt_provider: Option<String>,
+    pub stt_model: Option<String>,
+
+    pub enhanced_text: Option<String>,
+    pub llm_provider: Option<String>,
+    pub llm_model: Option<String>,
+    pub prompt_title: Option<String>,
+
+    pub final_text: Option<String>,
+    pub timings: SessionTimings,
+
+    /// Raw transcript against enhanced text; empty when the session was not enhanced.
+    pub diff: Vec<DiffSpan>,
+    pub error: Option<VoicewinError>,
+}
+
+impl SessionDetails {
+    pub fn from_result(
+        result: &SessionResult,
+        prompt_title: Option<String>,
+        ts_unix_ms: i64,
+    ) -> Self {
+        let raw = result.transcript.as_ref();
+        let enhanced = result.enhanced.as_ref();
+        let diff = match (raw, enhanced) {
+            (Some(raw), Some(enhanced)) => word_diff(&raw.text, &enhanced.text),
+            _ => vec![],
+        };
+        Self {
+            ts_unix_ms,
+            stage: result
+                .stage_label
+                .clone()
+                .unwrap_or_else(|| format!("{:?}", result.stage).to_lowercase()),
+            app_process_name: result.app.process_name.as_ref().map(|p| p.0.clone()),
+            profile: result.config.matched_profile_name.clone(),
+            raw_transcript: raw.map(|t| t.text.clone()),
+            stt_provider: raw.map(|t| t.provider.clone()),
+            stt_model: raw.map(|t| t.model.clone()),
+            enhanced_text: enhanced.map(|e| e.text.clone()),
+            llm_provider: enhanced.map(|e| e.provider.clone()),
+            llm_model: enhanced.map(|e| e.model.clone()),
+            prompt_title,
+            final_text: result.final_text.clone(),
+            timings: result.timings.clone(),
+            diff,
+            error: result.error.clone(),
+        }
+    }
 }
@@ -1,11 +1,19 @@
 pub mod config_store;
 pub mod defaults;
+pub mod diagnostics;
+pub mod disk_space;
 pub mod history;
 pub mod ipc;
 pub mod llm;
 pub mod local_stt;
 pub mod models;
+pub mod paths;
+pub mod recovery;
 pub mod runtime_engine;
+pub mod scratchpad;
 pub mod secrets;
+pub mod stats;
 pub mod stt;
 pub mod stt_router;
+pub mod updater;
+pub mod webhook;
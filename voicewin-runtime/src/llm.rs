@@ -1,14 +1,18 @@
+use voicewin_core::enhancement::{LlmDecodingSettings, LlmEndpointFlavor, ResponseFormat};
 use voicewin_engine::traits::EnhancedText;
+use voicewin_providers::error::status_error;
 
 #[derive(Clone)]
 pub struct OpenAiCompatibleLlmProvider {
     api_key: String,
+    flavor: LlmEndpointFlavor,
 }
 
 impl std::fmt::Debug for OpenAiCompatibleLlmProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OpenAiCompatibleLlmProvider")
             .field("api_key", &"[REDACTED]")
+            .field("flavor", &self.flavor)
             .finish()
     }
 }
@@ -17,6 +21,52 @@ impl OpenAiCompatibleLlmProvider {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
+            flavor: LlmEndpointFlavor::default(),
+        }
+    }
+
+    /// Talk to an Azure OpenAI deployment instead of an OpenAI-style endpoint.
+    pub fn with_endpoint_flavor(mut self, flavor: LlmEndpointFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Check that `base_url` answers and accepts the key, without running a completion.
+    pub async fn check_reachable(&self, base_url: &str) -> anyhow::Result<()> {
+        self.list_models(base_url).await.map(|_| ())
+    }
+
+    /// The models the key can use. Empty when the endpoint accepts the key but its model list
+    /// isn't in the OpenAI shape.
+    pub async fn list_models(&self, base_url: &str) -> anyhow::Result<Vec<String>> {
+        let req = voicewin_providers::openai_compatible::build_list_models_request(
+            base_url,
+            &self.api_key,
+            &self.flavor,
+        );
+        let resp = voicewin_providers::runtime::execute(&req).await?;
+
+        match resp.status {
+            200..=299 => Ok(
+                voicewin_providers::parse::parse_openai_model_ids(&resp.body).unwrap_or_default(),
+            ),
+            401 | 403 => Err(status_error(
+                resp.status,
+                format!(
+                    "the LLM endpoint rejected the API key (status={})",
+                    resp.status
+                ),
+            )
+            .into()),
+            status => Err(status_error(
+                status,
+                format!(
+                    "OpenAI-compatible request failed: status={} body={}",
+                    status,
+                    String::from_utf8_lossy(&resp.body)
+                ),
+            )
+            .into()),
         }
     }
 }
@@ -24,17 +74,39 @@ impl OpenAiCompatibleLlmProvider {
 #[async_trait::async_trait]
 impl voicewin_engine::traits::LlmProvider for OpenAiCompatibleLlmProvider {
     async fn enhance(
+        &self,
+        base_url: &str,
+        api_key: &str,
+        model: &str,
+        system_message: &str,
+        user_message: &str,
+    ) -> anyhow::Result<EnhancedText> {
+        self.enhance_with_decoding(
+            base_url,
+            api_key,
+            model,
+            system_message,
+            user_message,
+            &LlmDecodingSettings::default(),
+        )
+        .await
+    }
+
+    async fn enhance_with_decoding(
         &self,
         base_url: &str,
         _api_key: &str,
         model: &str,
         system_message: &str,
         user_message: &str,
+        decoding: &LlmDecodingSettings,
     ) -> anyhow::Result<EnhancedText> {
         let cfg = voicewin_providers::openai_compatible::OpenAiCompatibleChatConfig {
             base_url: base_url.to_string(),
             api_key: self.api_key.clone(),
             model: model.to_string(),
+            decoding: decoding.clone(),
+            flavor: self.flavor.clone(),
         };
 
         let messages = vec![
@@ -53,18 +125,27 @@ impl voicewin_engine::traits::LlmProvider for OpenAiCompatibleLlmProvider {
         let resp = voicewin_providers::runtime::execute(&req).await?;
 
         if !(200..=299).contains(&resp.status) {
-            return Err(anyhow::anyhow!(
-                "OpenAI-compatible request failed: status={} body={}",
+            return Err(status_error(
                 resp.status,
-                String::from_utf8_lossy(&resp.body)
-            ));
+                format!(
+                    "OpenAI-compatible request failed: status={} body={}",
+                    resp.status,
+                    String::from_utf8_lossy(&resp.body)
+                ),
+            )
+            .into());
         }
 
-        let text = voicewin_providers::parse::parse_openai_chat_completion(&resp.body)?;
+        let (mut text, usage) =
+            voicewin_providers::parse::parse_openai_chat_completion_with_usage(&resp.body)?;
+        if decoding.response_format == Some(ResponseFormat::JsonObject) {
+            text = voicewin_providers::parse::json_mode_text(&text);
+        }
         Ok(EnhancedText {
             text,
-            provider: "openai-compatible".into(),
+            provider: voicewin_core::usage::LLM_PROVIDER_OPENAI_COMPATIBLE.into(),
             model: model.into(),
+            usage,
         })
     }
 }
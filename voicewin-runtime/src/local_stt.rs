@@ -1,13 +1,17 @@
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
+use voicewin_core::error::{ErrorKind, VoicewinError};
 use voicewin_engine::traits::{AudioInput, Transcript};
 
 #[derive(Clone)]
 pub struct LocalWhisperSttProvider {
     cache: Arc<Mutex<Option<CachedModel>>>,
+    max_threads: Option<u32>,
+    prefer_quantized: bool,
 }
 
 struct CachedModel {
@@ -15,10 +19,27 @@ struct CachedModel {
     ctx: Arc<WhisperContext>,
 }
 
+/// Sets the flag when dropped. Held by the async side of a transcription, so aborting or
+/// timing out the session future stops whisper.cpp instead of leaving it running on the
+/// blocking pool.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+fn cancelled_error() -> anyhow::Error {
+    anyhow::anyhow!("whisper transcription cancelled")
+}
+
 impl Default for LocalWhisperSttProvider {
     fn default() -> Self {
         Self {
             cache: Arc::new(Mutex::new(None)),
+            max_threads: None,
+            prefer_quantized: false,
         }
     }
 }
@@ -28,6 +49,18 @@ impl LocalWhisperSttProvider {
         Self::default()
     }
 
+    /// Cap the number of inference threads (low-memory / ARM devices).
+    pub fn with_max_threads(mut self, max_threads: Option<u32>) -> Self {
+        self.max_threads = max_threads;
+        self
+    }
+
+    /// Swap full-precision models for an installed quantized sibling when available.
+    pub fn with_prefer_quantized(mut self, prefer_quantized: bool) -> Self {
+        self.prefer_quantized = prefer_quantized;
+        self
+    }
+
     fn get_or_load_context(&self, model_path: &PathBuf) -> anyhow::Result<Arc<WhisperContext>> {
         let mut guard = self.cache.lock().unwrap();
 
@@ -38,19 +71,27 @@ impl LocalWhisperSttProvider {
         }
 
         if !model_path.exists() {
-            return Err(anyhow::anyhow!(
-                "local whisper model does not exist: {}",
-                model_path.display()
-            ));
+            return Err(VoicewinError::new(
+                ErrorKind::Config,
+                format!(
+                    "local whisper model does not exist: {}",
+                    model_path.display()
+                ),
+            )
+            .into());
         }
 
         // User-friendly error: whisper-rs (whisper.cpp) expects the legacy GGML `.bin` format.
         // Our app previously used GGUF models; detect that early so the error is actionable.
         if crate::models::has_gguf_magic(model_path.as_path()).unwrap_or(false) {
-            return Err(anyhow::anyhow!(
-                "local whisper model is GGUF (.gguf), but the local engine requires whisper.cpp GGML (.bin) models: {}",
-                model_path.display()
-            ));
+            return Err(VoicewinError::new(
+                ErrorKind::Config,
+                format!(
+                    "local whisper model is GGUF (.gguf), but the local engine requires whisper.cpp GGML (.bin) models: {}",
+                    model_path.display()
+                ),
+            )
+            .into());
         }
 
         let ctx = WhisperContext::new_with_params(
@@ -74,6 +115,7 @@ impl LocalWhisperSttProvider {
         audio: &AudioInput,
         model_path: PathBuf,
         language: &str,
+        cancel: &Arc<AtomicBool>,
     ) -> anyhow::Result<String> {
         if audio.sample_rate_hz != 16_000 {
             return Err(anyhow::anyhow!(
@@ -82,7 +124,16 @@ impl LocalWhisperSttProvider {
             ));
         }
 
-        let ctx = self.get_or_load_context(&model_path)?;
+        if cancel.load(Ordering::Relaxed) {
+            return Err(cancelled_error());
+        }
+
+        let ctx = tracing::info_span!("whisper.load_model")
+            .in_scope(|| self.get_or_load_context(&model_path))?;
+        // Loading a large model can take seconds; don't start inference for a dead session.
+        if cancel.load(Ordering::Relaxed) {
+            return Err(cancelled_error());
+        }
         let mut state = ctx
             .create_state()
             .map_err(|e| anyhow::anyhow!("failed to create whisper state: {e}"))?;
@@ -93,15 +144,26 @@ impl LocalWhisperSttProvider {
             params.set_language(Some(language));
         }
 
+        if let Some(n) = self.max_threads {
+            params.set_n_threads(n.max(1) as i32);
+        }
+
         // Keep console output disabled.
         params.set_print_special(false);
         params.set_print_progress(false);
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
 
-        state
-            .full(params, &audio.samples)
-            .map_err(|e| anyhow::anyhow!("whisper inference failed: {e}"))?;
+        // Polled by whisper.cpp between compute steps.
+        let abort = cancel.clone();
+        params.set_abort_callback_safe(move || abort.load(Ordering::Relaxed));
+
+        let res = tracing::info_span!("whisper.inference", samples = audio.samples.len())
+            .in_scope(|| state.full(params, &audio.samples));
+        if cancel.load(Ordering::Relaxed) {
+            return Err(cancelled_error());
+        }
+        res.map_err(|e| anyhow::anyhow!("whisper inference failed: {e}"))?;
 
         let n = state.full_n_segments();
 
@@ -125,6 +187,7 @@ impl LocalWhisperSttProvider {
 
 #[async_trait::async_trait]
 impl voicewin_engine::traits::SttProvider for LocalWhisperSttProvider {
+    #[tracing::instrument(name = "stt.local_whisper", skip_all)]
     async fn transcribe(
         &self,
         audio: &AudioInput,
@@ -137,13 +200,23 @@ impl voicewin_engine::traits::SttProvider for LocalWhisperSttProvider {
         }
 
         // MVP convention: for local whisper, `model` is a filesystem path to a whisper.cpp GGML `.bin` model.
-        let model_path = PathBuf::from(model);
+        let mut model_path = PathBuf::from(model);
+        if self.prefer_quantized {
+            model_path = crate::models::prefer_quantized_model_path(&model_path);
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let _cancel_on_drop = CancelOnDrop(cancel.clone());
 
         let text = tokio::task::spawn_blocking({
             let this = self.clone();
             let audio = audio.clone();
             let language = language.to_string();
-            move || this.transcribe_blocking(&audio, model_path, &language)
+            // Spans don't follow work onto the blocking pool by themselves.
+            let span = tracing::Span::current();
+            move || {
+                span.in_scope(|| this.transcribe_blocking(&audio, model_path, &language, &cancel))
+            }
         })
         .await
         .map_err(|e| anyhow::anyhow!("whisper task join failed: {e}"))??;
@@ -152,6 +225,7 @@ impl voicewin_engine::traits::SttProvider for LocalWhisperSttProvider {
             text,
             provider: provider.into(),
             model: model.into(),
+            segments: vec![],
         })
     }
 }
@@ -187,4 +261,20 @@ mod tests {
         let err = stt.transcribe(&audio, "local", "./model.bin", "en").await;
         assert!(err.is_err());
     }
+
+    #[test]
+    fn dropped_transcription_cancels_blocking_work() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        drop(CancelOnDrop(cancel.clone()));
+        assert!(cancel.load(Ordering::Relaxed));
+
+        let audio = AudioInput {
+            sample_rate_hz: 16_000,
+            samples: vec![0.0; 160],
+        };
+        let err = LocalWhisperSttProvider::new()
+            .transcribe_blocking(&audio, PathBuf::from("./model.bin"), "en", &cancel)
+            .unwrap_err();
+        assert!(err.to_string().contains("cancelled"));
+    }
 }
@@ -1,8 +1,11 @@
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
+use serde::Serialize;
 use sha2::Digest;
 
 pub const BOOTSTRAP_MODEL_FILENAME: &str = "bootstrap.bin";
@@ -16,6 +19,11 @@ pub const BOOTSTRAP_MODEL_SHA256: &str =
 // ~75MB for ggml-tiny.bin. Use a conservative lower bound to catch empty/corrupt bundles.
 pub const BOOTSTRAP_MODEL_MIN_BYTES: u64 = 10 * 1024 * 1024;
 
+pub const BOOTSTRAP_MODEL_TITLE: &str = "Whisper Tiny (Bundled)";
+
+/// [`ModelDownloadSpec::language_accuracy`] of the bundled tiny model.
+pub const BOOTSTRAP_MODEL_LANGUAGE_ACCURACY: &[(&str, u8)] = &[("en", 55), ("*", 35)];
+
 // "Preferred" local model filename checked during config initialization.
 // If present, we pick it over the bundled bootstrap.
 pub const PREFERRED_LOCAL_STT_MODEL_FILENAME: &str = "ggml-base-q5_1.bin";
@@ -204,6 +212,37 @@ pub fn choose_default_local_stt_model_path(app_data_dir: &Path) -> PathBuf {
     }
 }
 
+// Smallest first: low-memory mode wants the lightest installed variant.
+const QUANTIZED_SUFFIXES: &[&str] = &["q5_0", "q5_1", "q8_0"];
+
+/// Whether a whisper.cpp model filename denotes a quantized variant (e.g. `ggml-base-q5_1.bin`).
+pub fn is_quantized_model_filename(filename: &str) -> bool {
+    let stem = filename.strip_suffix(".bin").unwrap_or(filename);
+    QUANTIZED_SUFFIXES
+        .iter()
+        .any(|q| stem.ends_with(&format!("-{q}")))
+}
+
+/// For a full-precision model path, return an installed quantized sibling if one exists
+/// (`ggml-base.bin` -> `ggml-base-q5_0.bin`); otherwise return the path unchanged.
+pub fn prefer_quantized_model_path(model_path: &Path) -> PathBuf {
+    let Some(filename) = model_path.file_name().and_then(|f| f.to_str()) else {
+        return model_path.to_path_buf();
+    };
+    if is_quantized_model_filename(filename) {
+        return model_path.to_path_buf();
+    }
+    let Some(stem) = filename.strip_suffix(".bin") else {
+        return model_path.to_path_buf();
+    };
+
+    QUANTIZED_SUFFIXES
+        .iter()
+        .map(|q| model_path.with_file_name(format!("{stem}-{q}.bin")))
+        .find(|p| p.exists())
+        .unwrap_or_else(|| model_path.to_path_buf())
+}
+
 #[derive(Debug, Clone)]
 pub struct ModelDownloadSpec {
     pub id: String,
@@ -221,6 +260,11 @@ pub struct ModelDownloadSpec {
     pub speed_label: Option<String>,
     pub accuracy_label: Option<String>,
     pub recommended: bool,
+
+    /// Relative accuracy (0-100) per language code; `"*"` covers every language not listed.
+    /// Without a `"*"` entry the model only handles the listed languages (English-only
+    /// `.en` and distil models). See [`language_accuracy`].
+    pub language_accuracy: Vec<(&'static str, u8)>,
 }
 
 pub fn whisper_catalog() -> Vec<ModelDownloadSpec> {
@@ -247,10 +291,239 @@ pub fn whisper_catalog() -> Vec<ModelDownloadSpec> {
             speed_label: Some("Medium".into()),
             accuracy_label: Some("High".into()),
             recommended: true,
+            language_accuracy: vec![("en", 70), ("*", 50)],
         },
     ]
 }
 
+/// How accurately a model with the `table` of [`ModelDownloadSpec::language_accuracy`]
+/// transcribes `language` (a config language code: `"de"`, `"en-US"`, `"auto"`); `None` if
+/// it can't. Detecting the language needs a multilingual model, so `"auto"` scores as any
+/// other language.
+pub fn language_accuracy(table: &[(&str, u8)], language: &str) -> Option<u8> {
+    let code = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let lookup = |key: &str| {
+        table
+            .iter()
+            .find(|(l, _)| *l == key)
+            .map(|(_, score)| *score)
+    };
+    if !code.is_empty()
+        && code != "auto"
+        && let Some(score) = lookup(&code)
+    {
+        return Some(score);
+    }
+    lookup("*")
+}
+
+/// The model in `models` most accurate for `language`, with its score; earlier models win
+/// ties. `table` returns a model's [`ModelDownloadSpec::language_accuracy`].
+pub fn best_model_for_language<'a, T>(
+    models: impl IntoIterator<Item = &'a T>,
+    language: &str,
+    table: impl Fn(&T) -> &[(&'static str, u8)],
+) -> Option<(&'a T, u8)> {
+    let mut best: Option<(&'a T, u8)> = None;
+    for model in models {
+        let Some(score) = language_accuracy(table(model), language) else {
+            continue;
+        };
+        if best.is_none_or(|(_, top)| score > top) {
+            best = Some((model, score));
+        }
+    }
+    best
+}
+
+/// Model downloads running at the same time; further requests wait in the queue.
+pub const MAX_PARALLEL_DOWNLOADS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    /// Waiting for a free slot.
+    Queued,
+    Downloading,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DownloadStatus {
+    pub model_id: String,
+    pub state: DownloadState,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+}
+
+/// Every model download in flight, in the order they were requested.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DownloadQueueStatus {
+    pub downloads: Vec<DownloadStatus>,
+    pub max_parallel: usize,
+    pub bandwidth_limit_bytes_per_sec: Option<u64>,
+}
+
+/// Runs model downloads a few at a time and spreads one bandwidth budget over them.
+///
+/// The transfer itself is the caller's: it gets a [`DownloadTicket`] from
+/// [`DownloadManager::enqueue`], waits in [`DownloadTicket::start`] for a slot, and reports
+/// every chunk to [`DownloadTicket::transferred`], which sleeps as long as the cap requires.
+pub struct DownloadManager {
+    max_parallel: usize,
+    slots: Arc<tokio::sync::Semaphore>,
+    inner: Mutex<DownloadQueue>,
+}
+
+#[derive(Debug, Default)]
+struct DownloadQueue {
+    downloads: Vec<DownloadStatus>,
+    limit_bytes_per_sec: Option<u64>,
+    budget: BandwidthBudget,
+}
+
+impl DownloadManager {
+    pub fn new(max_parallel: usize) -> Self {
+        let max_parallel = max_parallel.max(1);
+        Self {
+            max_parallel,
+            slots: Arc::new(tokio::sync::Semaphore::new(max_parallel)),
+            inner: Mutex::new(DownloadQueue::default()),
+        }
+    }
+
+    fn queue(&self) -> std::sync::MutexGuard<'_, DownloadQueue> {
+        self.inner.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Total bytes per second for all downloads together; `None` lifts the cap. Applies to
+    /// running downloads from their next chunk on.
+    pub fn set_bandwidth_limit(&self, bytes_per_sec: Option<u64>) {
+        self.queue().limit_bytes_per_sec = bytes_per_sec.filter(|&b| b > 0);
+    }
+
+    /// Queue `model_id`. Fails when that model is already queued or downloading.
+    pub fn enqueue(self: &Arc<Self>, model_id: &str) -> anyhow::Result<DownloadTicket> {
+        let mut queue = self.queue();
+        if queue.downloads.iter().any(|d| d.model_id == model_id) {
+            anyhow::bail!("model is already downloading");
+        }
+        queue.downloads.push(DownloadStatus {
+            model_id: model_id.to_string(),
+            state: DownloadState::Queued,
+            downloaded_bytes: 0,
+            total_bytes: None,
+        });
+        Ok(DownloadTicket {
+            manager: Arc::clone(self),
+            model_id: model_id.to_string(),
+            permit: None,
+        })
+    }
+
+    /// Whether `model_id` is queued or downloading.
+    pub fn contains(&self, model_id: &str) -> bool {
+        self.queue()
+            .downloads
+            .iter()
+            .any(|d| d.model_id == model_id)
+    }
+
+    pub fn status(&self) -> DownloadQueueStatus {
+        let queue = self.queue();
+        DownloadQueueStatus {
+            downloads: queue.downloads.clone(),
+            max_parallel: self.max_parallel,
+            bandwidth_limit_bytes_per_sec: queue.limit_bytes_per_sec,
+        }
+    }
+
+    fn update(&self, model_id: &str, update: impl FnOnce(&mut DownloadStatus)) {
+        if let Some(d) = self
+            .queue()
+            .downloads
+            .iter_mut()
+            .find(|d| d.model_id == model_id)
+        {
+            update(d);
+        }
+    }
+}
+
+/// One requested download. Dropping it, whether finished, failed or abandoned, frees its
+/// slot and takes it off the queue.
+pub struct DownloadTicket {
+    manager: Arc<DownloadManager>,
+    model_id: String,
+    permit: Option<tokio::sync::OwnedSemaphorePermit>,
+}
+
+impl DownloadTicket {
+    /// Wait until fewer than [`DownloadManager::new`]'s `max_parallel` downloads are running.
+    pub async fn start(&mut self) {
+        if self.permit.is_some() {
+            return;
+        }
+        // The semaphore is never closed.
+        self.permit = Arc::clone(&self.manager.slots).acquire_owned().await.ok();
+        self.manager
+            .update(&self.model_id, |d| d.state = DownloadState::Downloading);
+    }
+
+    /// Record `bytes` more received (of `total_bytes`, if known), then wait as long as the
+    /// shared bandwidth cap requires. Returns the bytes received so far.
+    pub async fn transferred(&self, bytes: u64, total_bytes: Option<u64>) -> u64 {
+        let mut downloaded = 0;
+        self.manager.update(&self.model_id, |d| {
+            d.downloaded_bytes += bytes;
+            d.total_bytes = total_bytes;
+            downloaded = d.downloaded_bytes;
+        });
+
+        let delay = {
+            let mut queue = self.manager.queue();
+            match queue.limit_bytes_per_sec {
+                Some(limit) => queue.budget.reserve(bytes, limit, Instant::now()),
+                None => Duration::ZERO,
+            }
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        downloaded
+    }
+}
+
+impl Drop for DownloadTicket {
+    fn drop(&mut self) {
+        self.manager
+            .queue()
+            .downloads
+            .retain(|d| d.model_id != self.model_id);
+    }
+}
+
+/// A timeline shared by concurrent transfers: each chunk books `bytes / limit` seconds
+/// after the previous booking (or now, if the link was idle), and its transfer waits until
+/// its booking ends. Idle time is not saved up, so there are no bursts above the cap.
+#[derive(Debug, Default)]
+struct BandwidthBudget {
+    booked_until: Option<Instant>,
+}
+
+impl BandwidthBudget {
+    fn reserve(&mut self, bytes: u64, limit_bytes_per_sec: u64, now: Instant) -> Duration {
+        let start = self.booked_until.map_or(now, |t| t.max(now));
+        let end = start + Duration::from_secs_f64(bytes as f64 / limit_bytes_per_sec as f64);
+        self.booked_until = Some(end);
+        end - now
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +552,25 @@ mod tests {
         validate_ggml_file(&path, 4).unwrap();
     }
 
+    #[test]
+    fn prefers_installed_quantized_sibling() {
+        let dir = tempfile::tempdir().unwrap();
+        let full = dir.path().join("ggml-small.bin");
+        fs::write(&full, b"").unwrap();
+
+        // No sibling yet: keep the configured model.
+        assert_eq!(prefer_quantized_model_path(&full), full);
+
+        let q = dir.path().join("ggml-small-q5_1.bin");
+        fs::write(&q, b"").unwrap();
+        assert_eq!(prefer_quantized_model_path(&full), q);
+
+        // Already quantized paths are left alone.
+        assert_eq!(prefer_quantized_model_path(&q), q);
+        assert!(is_quantized_model_filename("ggml-base-q8_0.bin"));
+        assert!(!is_quantized_model_filename("ggml-base.bin"));
+    }
+
     #[test]
     fn rejects_gguf_when_ggml_expected() {
         let dir = tempfile::tempdir().unwrap();
@@ -291,4 +583,80 @@ mod tests {
             .to_string()
             .contains("not whisper.cpp GGML"));
     }
+
+    #[test]
+    fn bandwidth_budget_is_shared_and_not_saved_up() {
+        let t0 = Instant::now();
+        let mut budget = BandwidthBudget::default();
+
+        // Two transfers reporting at the same moment split the budget.
+        assert_eq!(budget.reserve(500, 1000, t0), Duration::from_millis(500));
+        assert_eq!(budget.reserve(500, 1000, t0), Duration::from_secs(1));
+
+        // After an idle period, no burst allowance has built up.
+        let later = t0 + Duration::from_secs(10);
+        assert_eq!(budget.reserve(100, 1000, later), Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn download_queue_limits_parallel_downloads() {
+        let manager = Arc::new(DownloadManager::new(1));
+        let mut first = manager.enqueue("base").unwrap();
+        let mut second = manager.enqueue("small").unwrap();
+        assert!(manager.enqueue("base").is_err());
+
+        first.start().await;
+        assert_eq!(first.transferred(10, Some(100)).await, 10);
+        assert_eq!(first.transferred(15, Some(100)).await, 25);
+
+        let states = |m: &DownloadManager| {
+            m.status()
+                .downloads
+                .iter()
+                .map(|d| (d.model_id.clone(), d.state, d.downloaded_bytes))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            states(&manager),
+            vec![
+                ("base".to_string(), DownloadState::Downloading, 25),
+                ("small".to_string(), DownloadState::Queued, 0),
+            ]
+        );
+
+        // The second one only gets a slot once the first is done.
+        let waiting = tokio::time::timeout(Duration::from_millis(50), second.start()).await;
+        assert!(waiting.is_err());
+        drop(first);
+        second.start().await;
+        assert_eq!(
+            states(&manager),
+            vec![("small".to_string(), DownloadState::Downloading, 0)]
+        );
+        assert!(!manager.contains("base"));
+
+        drop(second);
+        assert!(manager.status().downloads.is_empty());
+    }
+
+    #[test]
+    fn best_model_for_language_skips_models_without_the_language() {
+        let models: [(&str, &[(&str, u8)]); 3] = [
+            ("base", &[("en", 70), ("*", 50)]),
+            ("distil-large-en", &[("en", 90)]),
+            ("small", &[("en", 80), ("de", 72), ("*", 60)]),
+        ];
+        let best = |language: &str| {
+            best_model_for_language(&models, language, |m| m.1).map(|(m, score)| (m.0, score))
+        };
+
+        assert_eq!(best("en"), Some(("distil-large-en", 90)));
+        assert_eq!(best("EN-us"), Some(("distil-large-en", 90)));
+        assert_eq!(best("de"), Some(("small", 72)));
+        assert_eq!(best("fr"), Some(("small", 60)));
+        // Detection needs a multilingual model.
+        assert_eq!(best("auto"), Some(("small", 60)));
+        assert_eq!(best(""), Some(("small", 60)));
+        assert_eq!(best_model_for_language(&models[1..2], "fr", |m| m.1), None);
+    }
 }
@@ -0,0 +1,266 @@
+//! Where VoiceWin keeps its files.
+//!
+//! Normally that is the OS per-user directories the shell resolves (app data, logs, cache).
+//! Small settings live in the roaming app data dir so they follow the user between machines;
+//! large, machine-specific files (models, recorded audio) live in the local app data dir so
+//! corporate roaming profiles do not sync gigabytes. The two only differ on Windows
+//! (`%APPDATA%` vs `%LOCALAPPDATA%`).
+//!
+//! In portable mode everything goes to a `data` folder next to the executable instead, so the
+//! app can run from a USB stick without touching the user profile. Portable mode is on when
+//! `portable.txt` sits next to the executable or `--portable` is passed.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use voicewin_core::config::AppConfig;
+
+/// Marker file next to the executable that turns portable mode on.
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Command-line flag that turns portable mode on for one launch.
+pub const PORTABLE_ARG: &str = "--portable";
+
+/// Folder (next to the executable) holding everything in portable mode.
+pub const PORTABLE_DATA_DIR: &str = "data";
+
+/// Folders under the local data dir that older builds kept in the roaming data dir.
+const LOCAL_ONLY_DIRS: [&str; 2] = ["models", "pending"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppPaths {
+    /// Config and history (roaming).
+    pub data_dir: PathBuf,
+    /// `models/` and crash-recovery audio in `pending/` (never roams).
+    pub local_data_dir: PathBuf,
+    pub log_dir: PathBuf,
+    /// Disposable downloads (update installers).
+    pub cache_dir: PathBuf,
+    pub portable: bool,
+}
+
+impl AppPaths {
+    /// The OS per-user layout, as resolved by the shell.
+    pub fn installed(
+        data_dir: PathBuf,
+        local_data_dir: PathBuf,
+        log_dir: PathBuf,
+        cache_dir: PathBuf,
+    ) -> Self {
+        Self {
+            data_dir,
+            local_data_dir,
+            log_dir,
+            cache_dir,
+            portable: false,
+        }
+    }
+
+    /// Everything under `<exe_dir>/data`.
+    pub fn portable(exe_dir: &Path) -> Self {
+        let data_dir = exe_dir.join(PORTABLE_DATA_DIR);
+        Self {
+            log_dir: data_dir.join("logs"),
+            cache_dir: data_dir.join("cache"),
+            local_data_dir: data_dir.clone(),
+            data_dir,
+            portable: true,
+        }
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.data_dir.join("config.json")
+    }
+
+    pub fn models_dir(&self) -> PathBuf {
+        crate::models::models_dir(&self.local_data_dir)
+    }
+
+    /// Audio of sessions that have not reached History yet.
+    pub fn pending_dir(&self) -> PathBuf {
+        self.local_data_dir.join("pending")
+    }
+
+    /// Move `models/` and `pending/` out of the roaming data dir, where builds before the
+    /// split kept them. Returns how many entries moved; files already present locally win.
+    pub fn migrate_to_local(&self) -> anyhow::Result<usize> {
+        if self.data_dir == self.local_data_dir {
+            return Ok(0);
+        }
+        let mut moved = 0;
+        for name in LOCAL_ONLY_DIRS {
+            let src = self.data_dir.join(name);
+            if src.is_dir() {
+                moved += move_dir_contents(&src, &self.local_data_dir.join(name))?;
+            }
+        }
+        Ok(moved)
+    }
+
+    /// Point model paths in `cfg` at the local models dir when they still name the roaming
+    /// copy and the file has moved. Returns whether anything changed.
+    pub fn relocate_model_paths(&self, cfg: &mut AppConfig) -> bool {
+        let old = crate::models::models_dir(&self.data_dir);
+        let new = self.models_dir();
+        if old == new {
+            return false;
+        }
+        let relocate = |model: &mut String| match Path::new(model.as_str()).strip_prefix(&old) {
+            // Leave paths whose file did not make it across for the usual stale-path repair.
+            Ok(rel) if new.join(rel).exists() => {
+                *model = new.join(rel).to_string_lossy().to_string();
+                true
+            }
+            _ => false,
+        };
+
+        let mut changed = relocate(&mut cfg.defaults.stt_model);
+        for p in &mut cfg.profiles {
+            if let Some(model) = p.overrides.stt_model.as_mut() {
+                changed |= relocate(model);
+            }
+        }
+        changed
+    }
+}
+
+fn move_dir_contents(src: &Path, dst: &Path) -> anyhow::Result<usize> {
+    std::fs::create_dir_all(dst).with_context(|| format!("create dir: {}", dst.display()))?;
+    let mut moved = 0;
+    for entry in std::fs::read_dir(src).with_context(|| format!("read dir: {}", src.display()))? {
+        let from = entry?.path();
+        let Some(name) = from.file_name() else {
+            continue;
+        };
+        let to = dst.join(name);
+        if to.exists() {
+            log::warn!(
+                "not migrating {}: {} already exists",
+                from.display(),
+                to.display()
+            );
+            continue;
+        }
+        move_path(&from, &to)
+            .with_context(|| format!("move {} -> {}", from.display(), to.display()))?;
+        moved += 1;
+    }
+    // Only succeeds once everything moved; leftovers stay put for the next attempt.
+    let _ = std::fs::remove_dir(src);
+    Ok(moved)
+}
+
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // Roaming and local data can sit on different volumes (redirected profiles).
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            move_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        std::fs::remove_dir(from)
+    } else {
+        // Copy to a temp name first so a partial copy never looks like a finished model.
+        let mut tmp = to.as_os_str().to_owned();
+        tmp.push(".part");
+        std::fs::copy(from, &tmp)?;
+        std::fs::rename(&tmp, to)?;
+        std::fs::remove_file(from)
+    }
+}
+
+/// The executable's directory when portable mode is requested by `args` or by a marker
+/// file next to `exe`.
+pub fn portable_exe_dir<I, S>(args: I, exe: &Path) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let exe_dir = exe.parent()?;
+    let by_arg = args.into_iter().any(|a| a.as_ref() == PORTABLE_ARG);
+    (by_arg || exe_dir.join(PORTABLE_MARKER).is_file()).then(|| exe_dir.to_path_buf())
+}
+
+/// [`portable_exe_dir`] for the running process.
+pub fn detect_portable_exe_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    // Resolve symlinks so the marker is looked up next to the real binary.
+    let exe = std::fs::canonicalize(&exe).unwrap_or(exe);
+    portable_exe_dir(std::env::args().skip(1), &exe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_mode_follows_marker_or_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("voicewin.exe");
+        let no_args: [&str; 0] = [];
+
+        assert_eq!(portable_exe_dir(no_args, &exe), None);
+        assert_eq!(
+            portable_exe_dir(["--minimized", PORTABLE_ARG], &exe).as_deref(),
+            Some(dir.path())
+        );
+
+        std::fs::write(dir.path().join(PORTABLE_MARKER), "").unwrap();
+        let root = portable_exe_dir(no_args, &exe).unwrap();
+        let paths = AppPaths::portable(&root);
+        assert!(paths.portable);
+        assert_eq!(
+            paths.config_path(),
+            dir.path().join("data").join("config.json")
+        );
+        assert_eq!(paths.models_dir(), dir.path().join("data").join("models"));
+        assert_eq!(paths.log_dir, dir.path().join("data").join("logs"));
+        assert_eq!(paths.migrate_to_local().unwrap(), 0);
+    }
+
+    #[test]
+    fn migrates_large_files_out_of_roaming() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = AppPaths::installed(
+            dir.path().join("Roaming"),
+            dir.path().join("Local"),
+            dir.path().join("logs"),
+            dir.path().join("cache"),
+        );
+        let old_models = paths.data_dir.join("models");
+        std::fs::create_dir_all(&old_models).unwrap();
+        std::fs::write(old_models.join("a.bin"), "a").unwrap();
+        std::fs::write(old_models.join("b.bin"), "old").unwrap();
+        std::fs::create_dir_all(paths.models_dir()).unwrap();
+        std::fs::write(paths.models_dir().join("b.bin"), "new").unwrap();
+        std::fs::create_dir_all(paths.data_dir.join("pending").join("s1")).unwrap();
+        std::fs::write(paths.config_path().with_file_name("history.json"), "[]").unwrap();
+
+        assert_eq!(paths.migrate_to_local().unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(paths.models_dir().join("a.bin")).unwrap(),
+            "a"
+        );
+        // The local copy wins; the roaming one is left for the user to clean up.
+        assert_eq!(
+            std::fs::read_to_string(paths.models_dir().join("b.bin")).unwrap(),
+            "new"
+        );
+        assert!(old_models.join("b.bin").exists());
+        assert!(paths.pending_dir().join("s1").is_dir());
+        assert!(!paths.data_dir.join("pending").exists());
+        assert!(paths.data_dir.join("history.json").exists());
+
+        let mut cfg = AppConfig::for_tests();
+        cfg.defaults.stt_model = old_models.join("a.bin").to_string_lossy().to_string();
+        assert!(paths.relocate_model_paths(&mut cfg));
+        assert_eq!(
+            Path::new(&cfg.defaults.stt_model),
+            paths.models_dir().join("a.bin")
+        );
+        assert!(!paths.relocate_model_paths(&mut cfg));
+    }
+}
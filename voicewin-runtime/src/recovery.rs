@@ -0,0 +1,338 @@
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+// Canonical header of the capture files (RIFF, `fmt `, then `data`).
+const WAV_HEADER_LEN: u64 = 44;
+
+// Pending sessions this process is still running, so recovery leaves them alone.
+static IN_FLIGHT: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+fn in_flight() -> std::sync::MutexGuard<'static, BTreeSet<String>> {
+    IN_FLIGHT.lock().unwrap_or_else(|p| p.into_inner())
+}
+
+/// Marker for a session whose captured audio has not made it into History yet.
+///
+/// Written when recording starts and removed once the session result is recorded, so
+/// anything on disk that no running session owns belongs to one that crashed mid-flight.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSession {
+    pub id: String,
+    pub started_unix_ms: i64,
+
+    // Last pipeline stage reached (e.g. "transcribing", "enhancing").
+    pub stage: String,
+
+    #[serde(default)]
+    pub app_process_name: Option<String>,
+    #[serde(default)]
+    pub app_exe_path: Option<String>,
+    #[serde(default)]
+    pub app_window_title: Option<String>,
+
+    /// The process running the session; markers from older builds have none.
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+impl PendingSession {
+    /// Whether the session that wrote this marker is still running, here or in another
+    /// VoiceWin process (e.g. the MCP server).
+    fn is_in_flight(&self) -> bool {
+        match self.pid {
+            Some(pid) if pid == std::process::id() => in_flight().contains(&self.id),
+            Some(pid) => process_running(pid),
+            None => false,
+        }
+    }
+}
+
+/// Hands a session registered by [`PendingSessionStore::begin`] over to recovery when
+/// dropped, for sessions that end (or are cancelled) without reaching `complete`.
+pub struct InFlightGuard(String);
+
+impl InFlightGuard {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        in_flight().remove(&self.0);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingSessionStore {
+    dir: PathBuf,
+}
+
+impl PendingSessionStore {
+    pub fn at_dir(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Create a session marker and the named file its capture is written into.
+    ///
+    /// Returns the marker and the open audio file, for the recorder to stream a mono 32-bit
+    /// float WAV into. The session counts as running until [`Self::complete`] or an
+    /// [`InFlightGuard`] drop.
+    pub fn begin(&self, started_unix_ms: i64) -> anyhow::Result<(PendingSession, File)> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create dir: {}", self.dir.display()))?;
+
+        // Let the OS pick a unique name, then keep the file past this scope.
+        let (audio_file, audio_path) = tempfile::Builder::new()
+            .prefix("session-")
+            .suffix(".wav")
+            .tempfile_in(&self.dir)
+            .context("failed to create pending audio file")?
+            .keep()
+            .map_err(|e| anyhow::anyhow!("failed to persist pending audio: {e}"))?;
+
+        let id = audio_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow::anyhow!("invalid pending audio path"))?;
+        // Before the marker exists, so `list` never sees it unowned.
+        in_flight().insert(id.clone());
+
+        let session = PendingSession {
+            id,
+            started_unix_ms,
+            stage: "recording".into(),
+            app_process_name: None,
+            app_exe_path: None,
+            app_window_title: None,
+            pid: Some(std::process::id()),
+        };
+        self.write_marker(&session)?;
+        Ok((session, audio_file))
+    }
+
+    pub fn set_stage(&self, id: &str, stage: &str) -> anyhow::Result<()> {
+        let mut session = self.read_marker(&self.marker_path(id))?;
+        session.stage = stage.to_string();
+        self.write_marker(&session)
+    }
+
+    /// Record the app the session dictates into, once it is known.
+    pub fn set_app(&self, id: &str, app: &voicewin_core::types::AppIdentity) -> anyhow::Result<()> {
+        let mut session = self.read_marker(&self.marker_path(id))?;
+        session.app_process_name = app.process_name.as_ref().map(|p| p.0.clone());
+        session.app_exe_path = app.exe_path.as_ref().map(|p| p.0.clone());
+        session.app_window_title = app.window_title.as_ref().map(|t| t.0.clone());
+        self.write_marker(&session)
+    }
+
+    /// Remove a session's marker and audio (the result is safely in History).
+    pub fn complete(&self, id: &str) -> anyhow::Result<()> {
+        in_flight().remove(id);
+        for path in [self.marker_path(id), self.audio_path(id)] {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove: {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// All abandoned sessions with intact audio, oldest first. Sessions still running are
+    /// left out.
+    pub fn list(&self) -> anyhow::Result<Vec<PendingSession>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut out = Vec::new();
+        for entry in fs::read_dir(&self.dir)
+            .with_context(|| format!("failed to read dir: {}", self.dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match self.read_marker(&path) {
+                Ok(s) if s.is_in_flight() => {}
+                Ok(s) if self.audio_path(&s.id).exists() => out.push(s),
+                Ok(s) => log::warn!("pending session {} has no audio; skipping", s.id),
+                Err(e) => log::warn!("skipping unreadable pending session marker: {e}"),
+            }
+        }
+
+        out.sort_by_key(|s| s.started_unix_ms);
+        Ok(out)
+    }
+
+    /// A session's audio file, ready to decode.
+    ///
+    /// A capture cut off mid-recording never got its WAV sizes written; they are filled in
+    /// from the file length first.
+    pub fn audio_file(&self, id: &str) -> anyhow::Result<PathBuf> {
+        let path = self.audio_path(id);
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("failed to open: {}", path.display()))?;
+        let len = file.metadata()?.len();
+        if len < WAV_HEADER_LEN {
+            anyhow::bail!("pending audio has no WAV header: {}", path.display());
+        }
+
+        let data_len = u32::try_from(len - WAV_HEADER_LEN).unwrap_or(u32::MAX - 36);
+        file.seek(SeekFrom::Start(4))?;
+        file.write_all(&(36 + data_len).to_le_bytes())?;
+        file.seek(SeekFrom::Start(40))?;
+        file.write_all(&data_len.to_le_bytes())?;
+        Ok(path)
+    }
+
+    fn audio_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.wav"))
+    }
+
+    fn marker_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    fn read_marker(&self, path: &Path) -> anyhow::Result<PendingSession> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read: {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("failed to parse: {}", path.display()))
+    }
+
+    fn write_marker(&self, session: &PendingSession) -> anyhow::Result<()> {
+        let path = self.marker_path(&session.id);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(session)?)
+            .with_context(|| format!("failed to write temp: {}", tmp.display()))?;
+        crate::models::replace_file(&tmp, &path)
+            .with_context(|| format!("failed to replace: {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn process_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 only checks that the process exists.
+    let signalled = unsafe { libc::kill(pid, 0) } == 0;
+    // EPERM: it exists but belongs to another user.
+    signalled || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(windows)]
+fn process_running(pid: u32) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // SAFETY: the handle is closed before returning.
+    unsafe {
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return false;
+        };
+        let mut code = 0u32;
+        let running =
+            GetExitCodeProcess(process, &mut code).is_ok() && code == STILL_ACTIVE.0 as u32;
+        let _ = CloseHandle(process);
+        running
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_running(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // What the recorder streams into the file: a header sized when the capture stops.
+    fn write_capture(mut file: File, samples: &[f32], finished: bool) {
+        let mut wav = crate::stt::encode_wav_mono_f32le(samples, 48_000);
+        if !finished {
+            wav[4..8].copy_from_slice(&36u32.to_le_bytes());
+            wav[40..44].copy_from_slice(&0u32.to_le_bytes());
+        }
+        file.write_all(&wav).unwrap();
+    }
+
+    #[test]
+    fn pending_session_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PendingSessionStore::at_dir(dir.path().join("pending"));
+        assert!(store.list().unwrap().is_empty());
+
+        let app = voicewin_core::types::AppIdentity::new().with_process_name("slack.exe");
+        let samples = [0.0, 0.25, -0.5];
+        let (s, file) = store.begin(42).unwrap();
+        assert_eq!(s.stage, "recording");
+        write_capture(file, &samples, true);
+        store.set_app(&s.id, &app).unwrap();
+        store.set_stage(&s.id, "transcribing").unwrap();
+        // The session "crashes": nothing in this process owns it any more.
+        drop(InFlightGuard::new(&s.id));
+
+        let pending = store.list().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].stage, "transcribing");
+        assert_eq!(pending[0].app_process_name.as_deref(), Some("slack.exe"));
+        let audio = fs::read(store.audio_file(&s.id).unwrap()).unwrap();
+        assert_eq!(audio, crate::stt::encode_wav_mono_f32le(&samples, 48_000));
+
+        store.complete(&s.id).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn capture_cut_off_mid_recording_gets_its_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PendingSessionStore::at_dir(dir.path().join("pending"));
+
+        let (s, file) = store.begin(1).unwrap();
+        write_capture(file, &[0.5; 3], false);
+        drop(InFlightGuard::new(&s.id));
+
+        let audio = fs::read(store.audio_file(&s.id).unwrap()).unwrap();
+        assert_eq!(audio, crate::stt::encode_wav_mono_f32le(&[0.5; 3], 48_000));
+    }
+
+    #[test]
+    fn running_sessions_are_not_listed() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PendingSessionStore::at_dir(dir.path().join("pending"));
+
+        let (s, _file) = store.begin(1).unwrap();
+        let guard = InFlightGuard::new(&s.id);
+        assert!(store.list().unwrap().is_empty());
+
+        drop(guard);
+        assert_eq!(store.list().unwrap()[0].id, s.id);
+
+        // So does another process that is still up (here: the test runner's parent).
+        #[cfg(unix)]
+        {
+            let mut foreign = store.list().unwrap().remove(0);
+            foreign.pid = Some(std::os::unix::process::parent_id());
+            store.write_marker(&foreign).unwrap();
+            assert!(store.list().unwrap().is_empty());
+        }
+    }
+}
@@ -8,7 +8,10 @@ use crate::llm::OpenAiCompatibleLlmProvider;
 use crate::local_stt::LocalWhisperSttProvider;
 use crate::secrets::{SecretKey, get_secret};
 use crate::stt::ElevenLabsSttProvider;
+use crate::stt::chunked::{ChunkedSttProvider, ChunkingConfig};
+use crate::stt::command::CommandSttProvider;
 use crate::stt_router::SttRouter;
+use crate::webhook::HttpWebhookSink;
 
 /// Build a runnable engine from config + platform providers.
 ///
@@ -18,20 +21,30 @@ pub async fn build_engine_from_config(
     ctx: Arc<dyn AppContextProvider>,
     inserter: Arc<dyn Inserter>,
 ) -> anyhow::Result<VoicewinEngine> {
-    // Secrets (OS keyring)
-    let llm_api_key = get_secret(SecretKey::OpenAiCompatibleApiKey)?.unwrap_or_default();
-    let eleven_key = get_secret(SecretKey::ElevenLabsApiKey)?.unwrap_or_default();
+    // Secrets (OS keyring). Without a key the pipeline never attempts enhancement.
+    let llm_api_key = if cfg.offline_mode {
+        String::new()
+    } else {
+        get_secret(SecretKey::OpenAiCompatibleApiKey)?.unwrap_or_default()
+    };
 
-    let llm: Arc<dyn LlmProvider> = Arc::new(OpenAiCompatibleLlmProvider::new(llm_api_key.clone()));
+    let llm: Arc<dyn LlmProvider> = Arc::new(
+        OpenAiCompatibleLlmProvider::new(llm_api_key.clone())
+            .with_endpoint_flavor(cfg.llm_endpoint_flavor.clone()),
+    );
 
-    // STT router
-    let local: Arc<dyn SttProvider> = Arc::new(LocalWhisperSttProvider::new());
-    let eleven: Arc<dyn SttProvider> = Arc::new(ElevenLabsSttProvider::new(eleven_key));
+    let router = build_stt_from_config(&cfg)?;
 
-    // Wrap router as a provider.
-    let router = Arc::new(RouterProvider {
-        router: SttRouter::new().with_local(local).with_elevenlabs(eleven),
-    });
+    let webhook = match cfg.webhook.active_url() {
+        Some(url) => {
+            let token = get_secret(SecretKey::WebhookBearerToken)?.unwrap_or_default();
+            Some((HttpWebhookSink::new(url, token), cfg.webhook.mode))
+        }
+        None => None,
+    };
+
+    let guardrails = cfg.enhancement_guardrails.clone();
+    let decoding = cfg.llm_decoding.clone();
 
     let engine_cfg = EngineConfig {
         defaults: cfg.defaults,
@@ -40,9 +53,55 @@ pub async fn build_engine_from_config(
         // Keep the key in the engine config so the pipeline can decide whether
         // enhancement is possible. The actual provider still owns the secret at runtime.
         llm_api_key,
+        spoken_replacements: cfg.spoken_replacements,
+        insert_commands: cfg.insert_commands,
+        snippets: cfg.snippets,
     };
 
-    Ok(VoicewinEngine::new(engine_cfg, ctx, router, llm, inserter))
+    let engine = VoicewinEngine::new(engine_cfg, ctx, router, llm, inserter)
+        .with_output_guardrails(guardrails)
+        .with_llm_decoding(decoding);
+    Ok(match webhook {
+        Some((sink, mode)) => engine.with_webhook(Arc::new(sink), mode),
+        None => engine,
+    })
+}
+
+/// Build the LLM provider alone, e.g. for summarizing History outside a session.
+pub fn build_llm_provider(cfg: &AppConfig) -> anyhow::Result<Arc<dyn LlmProvider>> {
+    let llm_api_key = get_secret(SecretKey::OpenAiCompatibleApiKey)?.unwrap_or_default();
+    Ok(Arc::new(
+        OpenAiCompatibleLlmProvider::new(llm_api_key)
+            .with_endpoint_flavor(cfg.llm_endpoint_flavor.clone()),
+    ))
+}
+
+/// Build the STT router alone (no context/insertion), e.g. for re-transcribing saved audio.
+pub fn build_stt_from_config(cfg: &AppConfig) -> anyhow::Result<Arc<dyn SttProvider>> {
+    let eleven_key = get_secret(SecretKey::ElevenLabsApiKey)?.unwrap_or_default();
+
+    let whisper: Arc<dyn SttProvider> = Arc::new(
+        LocalWhisperSttProvider::new()
+            .with_max_threads(cfg.performance.effective_whisper_threads())
+            .with_prefer_quantized(cfg.performance.low_memory_mode),
+    );
+    // Long captures are transcribed in overlapping windows instead of one huge inference.
+    let local: Arc<dyn SttProvider> = Arc::new(ChunkedSttProvider::new(
+        whisper,
+        ChunkingConfig::for_machine(&cfg.performance),
+    ));
+    let eleven: Arc<dyn SttProvider> =
+        Arc::new(ElevenLabsSttProvider::new(eleven_key).with_diarization(cfg.diarization.clone()));
+
+    let command: Arc<dyn SttProvider> = Arc::new(CommandSttProvider::new(cfg.command_stt.clone()));
+
+    // Wrap router as a provider.
+    Ok(Arc::new(RouterProvider {
+        router: SttRouter::new()
+            .with_local(local)
+            .with_elevenlabs(eleven)
+            .with_command(command),
+    }))
 }
 
 #[derive(Clone)]
@@ -0,0 +1,145 @@
+//! The scratchpad (`scratchpad.json` next to the config): dictations sent to
+//! `InsertTarget::Scratchpad`, kept with timestamps until the user copies them out.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use voicewin_core::types::AppIdentity;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScratchpadEntry {
+    pub ts_unix_ms: i64,
+    pub text: String,
+
+    /// Process name of the app in front when it was dictated.
+    #[serde(default)]
+    pub app: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ScratchpadStore {
+    path: PathBuf,
+    // Sessions and the scratchpad window may write at the same time.
+    lock: Mutex<()>,
+}
+
+impl ScratchpadStore {
+    pub fn at_path(path: PathBuf) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn load(&self) -> anyhow::Result<Vec<ScratchpadEntry>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let raw = fs::read(&self.path)
+            .with_context(|| format!("failed to read scratchpad: {}", self.path.display()))?;
+        serde_json::from_slice(&raw)
+            .with_context(|| format!("failed to parse scratchpad: {}", self.path.display()))
+    }
+
+    /// Add `text` stamped `now_unix_ms`. Timestamps double as ids, so a dictation landing in
+    /// the same millisecond as the previous one is stamped one later.
+    pub fn append(&self, text: &str, app: Option<String>, now_unix_ms: i64) -> anyhow::Result<()> {
+        self.update(|all| {
+            let ts_unix_ms = match all.last() {
+                Some(last) => now_unix_ms.max(last.ts_unix_ms + 1),
+                None => now_unix_ms,
+            };
+            all.push(ScratchpadEntry {
+                ts_unix_ms,
+                text: text.to_string(),
+                app,
+            });
+        })
+    }
+
+    /// Drop the entry with timestamp `ts_unix_ms`; false when there was none.
+    pub fn remove(&self, ts_unix_ms: i64) -> anyhow::Result<bool> {
+        let mut removed = false;
+        self.update(|all| {
+            let before = all.len();
+            all.retain(|e| e.ts_unix_ms != ts_unix_ms);
+            removed = all.len() != before;
+        })?;
+        Ok(removed)
+    }
+
+    pub fn clear(&self) -> anyhow::Result<()> {
+        self.update(|all| all.clear())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Vec<ScratchpadEntry>)) -> anyhow::Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create dir: {}", parent.display()))?;
+        }
+
+        let mut all = self.load()?;
+        f(&mut all);
+
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&all)?)
+            .with_context(|| format!("failed to write scratchpad temp: {}", tmp.display()))?;
+        crate::models::replace_file(&tmp, &self.path)
+            .with_context(|| format!("failed to replace scratchpad: {}", self.path.display()))
+    }
+}
+
+/// All entries as plain text, oldest first and separated by blank lines, for copying out.
+pub fn scratchpad_text(entries: &[ScratchpadEntry]) -> String {
+    entries
+        .iter()
+        .map(|e| e.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[async_trait::async_trait]
+impl voicewin_engine::traits::ScratchpadSink for ScratchpadStore {
+    async fn append(&self, text: &str, app: &AppIdentity) -> anyhow::Result<()> {
+        let now_unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let app = app.process_name.as_ref().map(|p| p.0.clone());
+        ScratchpadStore::append(self, text, app, now_unix_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_removes_and_joins_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ScratchpadStore::at_path(dir.path().join("scratchpad.json"));
+        assert_eq!(store.load().unwrap(), vec![]);
+
+        for text in ["Buy milk", "  ", "Call the dentist\n"] {
+            store.append(text, Some("slack.exe".into()), 1).unwrap();
+        }
+        let all = store.load().unwrap();
+        assert_eq!(
+            all.iter().map(|e| e.ts_unix_ms).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(scratchpad_text(&all), "Buy milk\n\nCall the dentist");
+
+        assert!(store.remove(1).unwrap());
+        assert!(!store.remove(1).unwrap());
+        assert_eq!(store.load().unwrap().len(), 2);
+
+        store.clear().unwrap();
+        assert_eq!(store.load().unwrap(), vec![]);
+    }
+}
@@ -9,6 +9,8 @@ const SERVICE: &str = "voicewin";
 pub enum SecretKey {
     OpenAiCompatibleApiKey,
     ElevenLabsApiKey,
+    HistoryEncryptionKey,
+    WebhookBearerToken,
 }
 
 impl SecretKey {
@@ -16,6 +18,8 @@ impl SecretKey {
         match self {
             SecretKey::OpenAiCompatibleApiKey => "openai_compatible_api_key",
             SecretKey::ElevenLabsApiKey => "elevenlabs_api_key",
+            SecretKey::HistoryEncryptionKey => "history_encryption_key",
+            SecretKey::WebhookBearerToken => "webhook_bearer_token",
         }
     }
 }
@@ -0,0 +1,125 @@
+//! Usage stats kept next to the config (`stats.json`): one record per billable cloud call.
+//!
+//! Records carry only counts (tokens, audio milliseconds), never text, so the file is not
+//! encrypted like History.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use voicewin_core::usage::{CostEstimate, PricingSettings, UsageRecord, estimate_cost};
+
+use crate::history::ExportRange;
+
+/// Oldest records are dropped past this (roughly a year of heavy dictation).
+const MAX_USAGE_RECORDS: usize = 50_000;
+
+#[derive(Debug, Clone)]
+pub struct StatsStore {
+    path: PathBuf,
+    max_records: usize,
+}
+
+impl StatsStore {
+    pub fn at_path(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_records: MAX_USAGE_RECORDS,
+        }
+    }
+
+    pub fn with_max_records(mut self, max: usize) -> Self {
+        self.max_records = max.max(1);
+        self
+    }
+
+    pub fn load(&self) -> anyhow::Result<Vec<UsageRecord>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let raw = fs::read(&self.path)
+            .with_context(|| format!("failed to read stats: {}", self.path.display()))?;
+        serde_json::from_slice(&raw)
+            .with_context(|| format!("failed to parse stats: {}", self.path.display()))
+    }
+
+    pub fn append(&self, records: &[UsageRecord]) -> anyhow::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create dir: {}", parent.display()))?;
+        }
+
+        let mut all = self.load()?;
+        all.extend_from_slice(records);
+        let excess = all.len().saturating_sub(self.max_records);
+        all.drain(..excess);
+
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(&all)?)
+            .with_context(|| format!("failed to write stats temp: {}", tmp.display()))?;
+        crate::models::replace_file(&tmp, &self.path)
+            .with_context(|| format!("failed to replace stats: {}", self.path.display()))
+    }
+
+    /// Cost of the usage recorded inside `range`.
+    pub fn cost_estimate(
+        &self,
+        range: &ExportRange,
+        pricing: &PricingSettings,
+    ) -> anyhow::Result<CostEstimate> {
+        let records = self.load()?;
+        Ok(estimate_cost(
+            records.iter().filter(|r| range.contains(r.ts_unix_ms)),
+            pricing,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voicewin_core::usage::TokenUsage;
+
+    #[test]
+    fn appends_trims_and_prices_a_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = StatsStore::at_path(dir.path().join("stats.json")).with_max_records(3);
+        assert_eq!(store.load().unwrap(), vec![]);
+
+        let tokens = TokenUsage {
+            prompt_tokens: 1_000_000,
+            completion_tokens: 0,
+        };
+        for ts in 1..=4 {
+            store
+                .append(&[UsageRecord::tokens(
+                    ts,
+                    "openai-compatible",
+                    "gpt-4o-mini",
+                    tokens,
+                )])
+                .unwrap();
+        }
+        store.append(&[]).unwrap();
+
+        let all = store.load().unwrap();
+        assert_eq!(
+            all.iter().map(|r| r.ts_unix_ms).collect::<Vec<_>>(),
+            [2, 3, 4]
+        );
+
+        let range = ExportRange {
+            from_unix_ms: Some(3),
+            to_unix_ms: None,
+        };
+        let est = store
+            .cost_estimate(&range, &PricingSettings::default())
+            .unwrap();
+        assert_eq!(est.lines.len(), 1);
+        assert_eq!(est.lines[0].calls, 2);
+        assert_eq!(est.total_micros, 300_000);
+    }
+}
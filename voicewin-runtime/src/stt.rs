@@ -1,3 +1,10 @@
+pub mod chunked;
+pub mod command;
+pub mod fallback;
+pub mod registry;
+
+use tracing::Instrument;
+use voicewin_core::diarization::DiarizationSettings;
 use voicewin_engine::traits::{AudioInput, Transcript};
 
 pub fn encode_wav_mono_f32le(samples: &[f32], sample_rate_hz: u32) -> Vec<u8> {
@@ -55,12 +62,14 @@ pub fn encode_pcm_s16le_mono(samples: &[f32]) -> Vec<u8> {
 #[derive(Clone)]
 pub struct ElevenLabsSttProvider {
     api_key: String,
+    diarization: DiarizationSettings,
 }
 
 impl std::fmt::Debug for ElevenLabsSttProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ElevenLabsSttProvider")
             .field("api_key", &"[REDACTED]")
+            .field("diarization", &self.diarization)
             .finish()
     }
 }
@@ -69,12 +78,19 @@ impl ElevenLabsSttProvider {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
+            diarization: DiarizationSettings::default(),
         }
     }
+
+    pub fn with_diarization(mut self, diarization: DiarizationSettings) -> Self {
+        self.diarization = diarization;
+        self
+    }
 }
 
 #[async_trait::async_trait]
 impl voicewin_engine::traits::SttProvider for ElevenLabsSttProvider {
+    #[tracing::instrument(name = "stt.elevenlabs", skip_all)]
     async fn transcribe(
         &self,
         audio: &AudioInput,
@@ -104,37 +120,30 @@ impl voicewin_engine::traits::SttProvider for ElevenLabsSttProvider {
                 "auto" => None,
                 other => Some(other.to_string()),
             },
+            diarize: self.diarization.enabled,
+            num_speakers: self.diarization.num_speakers,
         };
 
-        if cfg.api_key.trim().is_empty() {
-            return Err(anyhow::anyhow!("missing ElevenLabs API key"));
-        }
-
         let pcm = encode_pcm_s16le_mono(&audio.samples);
 
-        let req = voicewin_providers::elevenlabs::build_elevenlabs_stt_request(
+        let transcription = voicewin_providers::elevenlabs::transcribe_batch(
             &cfg,
             &voicewin_providers::elevenlabs::AudioFile {
                 filename: "input.pcm".into(),
                 mime_type: "application/octet-stream".into(),
                 bytes: pcm,
             },
-        );
-
-        let resp = voicewin_providers::runtime::execute(&req).await?;
-        if !(200..=299).contains(&resp.status) {
-            return Err(anyhow::anyhow!(
-                "ElevenLabs STT failed: status={} body={}",
-                resp.status,
-                String::from_utf8_lossy(&resp.body)
-            ));
-        }
-
-        let text = voicewin_providers::parse::parse_elevenlabs_transcription(&resp.body)?;
+        )
+        .instrument(tracing::info_span!(
+            "http.request",
+            pcm_samples = audio.samples.len()
+        ))
+        .await?;
         Ok(Transcript {
-            text,
+            text: transcription.text,
             provider: provider.into(),
             model: model.into(),
+            segments: transcription.segments,
         })
     }
 }
@@ -157,6 +166,7 @@ impl voicewin_engine::traits::SttProvider for MockSttProvider {
             text: self.text.clone(),
             provider: provider.into(),
             model: model.into(),
+            segments: vec![],
         })
     }
 }
@@ -0,0 +1,335 @@
+//! Long-audio transcription: split captures into overlapping windows, transcribe each, and
+//! stitch the pieces back together.
+//!
+//! Single-shot whisper on several minutes of audio is slow and memory-hungry. Fixed windows
+//! keep both bounded and let multi-core machines transcribe several windows at once. The
+//! overlap makes sure a word cut at a window boundary is heard whole in at least one window;
+//! the duplicated words are removed when stitching.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use tracing::Instrument;
+use voicewin_core::config::PerformanceSettings;
+use voicewin_engine::traits::{AudioInput, SttProvider, Transcript};
+
+/// Whisper's default thread count per inference.
+const DEFAULT_THREADS_PER_JOB: u32 = 4;
+const MAX_PARALLEL_WINDOWS: usize = 4;
+/// Longest word run considered when removing the overlap between neighbouring windows.
+const MAX_OVERLAP_WORDS: usize = 12;
+/// Leading words of a window that may be a garbled half-word from the cut.
+const MAX_SKIPPED_LEADING_WORDS: usize = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkingConfig {
+    /// Captures up to this long are transcribed in one shot.
+    pub min_chunked_secs: f32,
+    pub window_secs: f32,
+    /// Audio shared by neighbouring windows.
+    pub overlap_secs: f32,
+    /// A trailing window shorter than this is merged into the previous one (whisper tends to
+    /// hallucinate on very short clips).
+    pub min_tail_secs: f32,
+    /// Windows transcribed at the same time.
+    pub parallelism: usize,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        Self {
+            min_chunked_secs: 60.0,
+            window_secs: 30.0,
+            overlap_secs: 2.0,
+            min_tail_secs: 5.0,
+            parallelism: 1,
+        }
+    }
+}
+
+impl ChunkingConfig {
+    /// Parallelism sized to this machine: each local inference already uses several threads,
+    /// and low-memory mode keeps a single window in flight.
+    pub fn for_machine(performance: &PerformanceSettings) -> Self {
+        let parallelism = if performance.low_memory_mode {
+            1
+        } else {
+            let cores = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let per_job = performance
+                .effective_whisper_threads()
+                .unwrap_or(DEFAULT_THREADS_PER_JOB)
+                .max(1) as usize;
+            (cores / per_job).clamp(1, MAX_PARALLEL_WINDOWS)
+        };
+        Self {
+            parallelism,
+            ..Default::default()
+        }
+    }
+}
+
+/// Sample ranges of the windows covering `len` samples; a single range for short captures.
+pub fn split_windows(len: usize, sample_rate_hz: u32, cfg: &ChunkingConfig) -> Vec<Range<usize>> {
+    let secs = |s: f32| (s * sample_rate_hz as f32) as usize;
+    let whole = 0..len;
+    if len <= secs(cfg.min_chunked_secs) {
+        return vec![whole];
+    }
+
+    let window = secs(cfg.window_secs).max(1);
+    let overlap = secs(cfg.overlap_secs).min(window / 2);
+    let step = window - overlap;
+
+    let mut out: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = (start + window).min(len);
+        out.push(start..end);
+        if end == len {
+            break;
+        }
+        start += step;
+    }
+
+    if out.len() > 1
+        && out
+            .last()
+            .is_some_and(|r| r.len() < secs(cfg.min_tail_secs))
+    {
+        out.pop();
+        if let Some(prev) = out.last_mut() {
+            prev.end = len;
+        }
+    }
+    out
+}
+
+/// Join window transcripts, dropping the words repeated across each window boundary.
+pub fn stitch(pieces: &[String]) -> String {
+    let mut words: Vec<&str> = Vec::new();
+    for piece in pieces {
+        let next: Vec<&str> = piece.split_whitespace().collect();
+        let skip = overlap_len(&words, &next);
+        words.extend_from_slice(&next[skip..]);
+    }
+    words.join(" ")
+}
+
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// How many leading words of `next` repeat the end of `prev`.
+///
+/// Looks for the longest run of words that ends `prev` and starts `next`, optionally after a
+/// couple of leading words (a word cut in half at the window start).
+fn overlap_len(prev: &[&str], next: &[&str]) -> usize {
+    let prev: Vec<String> = prev
+        .iter()
+        .rev()
+        .take(MAX_OVERLAP_WORDS)
+        .rev()
+        .map(|w| normalize(w))
+        .collect();
+    let next_norm: Vec<String> = next
+        .iter()
+        .take(MAX_OVERLAP_WORDS + MAX_SKIPPED_LEADING_WORDS)
+        .map(|w| normalize(w))
+        .collect();
+
+    let mut best = 0;
+    for skipped in 0..=MAX_SKIPPED_LEADING_WORDS.min(next_norm.len()) {
+        // Skipping words needs a longer match to be trusted.
+        let min_run = if skipped == 0 { 1 } else { 2 };
+        let max_run = prev.len().min(next_norm.len() - skipped);
+        let run = (min_run..=max_run).rev().find(|&n| {
+            prev[prev.len() - n..]
+                .iter()
+                .zip(&next_norm[skipped..skipped + n])
+                .all(|(a, b)| !a.is_empty() && a == b)
+        });
+        if let Some(n) = run
+            && skipped + n > best
+        {
+            best = skipped + n;
+        }
+    }
+    best
+}
+
+/// Wraps a provider so long captures are transcribed window by window.
+#[derive(Clone)]
+pub struct ChunkedSttProvider {
+    inner: Arc<dyn SttProvider>,
+    cfg: ChunkingConfig,
+}
+
+impl ChunkedSttProvider {
+    pub fn new(inner: Arc<dyn SttProvider>, cfg: ChunkingConfig) -> Self {
+        Self { inner, cfg }
+    }
+}
+
+#[async_trait::async_trait]
+impl SttProvider for ChunkedSttProvider {
+    async fn transcribe(
+        &self,
+        audio: &AudioInput,
+        provider: &str,
+        model: &str,
+        language: &str,
+    ) -> anyhow::Result<Transcript> {
+        let windows = split_windows(audio.samples.len(), audio.sample_rate_hz, &self.cfg);
+        if windows.len() <= 1 {
+            return self
+                .inner
+                .transcribe(audio, provider, model, language)
+                .await;
+        }
+
+        let mut texts = vec![String::new(); windows.len()];
+        let mut pending = windows.into_iter().enumerate();
+        // Dropping the set (cancel / timeout) aborts every in-flight window.
+        let mut running = tokio::task::JoinSet::new();
+        loop {
+            while running.len() < self.cfg.parallelism.max(1) {
+                let Some((i, range)) = pending.next() else {
+                    break;
+                };
+                let inner = self.inner.clone();
+                let chunk = AudioInput {
+                    sample_rate_hz: audio.sample_rate_hz,
+                    samples: audio.samples[range].to_vec(),
+                };
+                let (provider, model, language) = (
+                    provider.to_string(),
+                    model.to_string(),
+                    language.to_string(),
+                );
+                let span = tracing::info_span!("stt.chunk", index = i);
+                running.spawn(
+                    async move {
+                        let t = inner.transcribe(&chunk, &provider, &model, &language).await;
+                        (i, t)
+                    }
+                    .instrument(span),
+                );
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            let (i, transcript) =
+                joined.map_err(|e| anyhow::anyhow!("chunk transcription task failed: {e}"))?;
+            texts[i] = transcript?.text;
+        }
+
+        Ok(Transcript {
+            text: stitch(&texts),
+            provider: provider.into(),
+            model: model.into(),
+            segments: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 100;
+
+    /// Each sample holds the second it belongs to; "transcribes" one word per second.
+    struct SecondsStt;
+
+    #[async_trait::async_trait]
+    impl SttProvider for SecondsStt {
+        async fn transcribe(
+            &self,
+            audio: &AudioInput,
+            provider: &str,
+            model: &str,
+            _language: &str,
+        ) -> anyhow::Result<Transcript> {
+            let mut seconds: Vec<usize> = audio.samples.iter().map(|s| *s as usize).collect();
+            seconds.dedup();
+            let text = seconds
+                .iter()
+                .map(|s| format!("w{s}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            Ok(Transcript {
+                text,
+                provider: provider.into(),
+                model: model.into(),
+                segments: vec![],
+            })
+        }
+    }
+
+    fn audio(secs: usize) -> AudioInput {
+        AudioInput {
+            sample_rate_hz: RATE,
+            samples: (0..secs * RATE as usize)
+                .map(|i| (i / RATE as usize) as f32)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn splits_long_audio_into_overlapping_windows() {
+        let cfg = ChunkingConfig::default();
+        let r = |a: usize, b: usize| a * RATE as usize..b * RATE as usize;
+
+        assert_eq!(split_windows(45 * 100, RATE, &cfg), vec![r(0, 45)]);
+        assert_eq!(
+            split_windows(100 * 100, RATE, &cfg),
+            vec![r(0, 30), r(28, 58), r(56, 86), r(84, 100)]
+        );
+        // A 3s tail is folded into the previous window.
+        assert_eq!(
+            split_windows(87 * 100, RATE, &cfg),
+            vec![r(0, 30), r(28, 58), r(56, 87)]
+        );
+    }
+
+    #[test]
+    fn stitch_removes_repeated_and_cut_words_at_boundaries() {
+        let pieces = vec![
+            "We are going to the".to_string(),
+            "To the store, then home.".to_string(),
+            "-ome. then home. And back".to_string(),
+        ];
+        assert_eq!(
+            stitch(&pieces),
+            "We are going to the store, then home. And back"
+        );
+
+        // No overlap: plain concatenation.
+        assert_eq!(
+            stitch(&["one two".to_string(), "three four".to_string()]),
+            "one two three four"
+        );
+    }
+
+    #[tokio::test]
+    async fn long_audio_is_transcribed_per_window_and_stitched() {
+        let cfg = ChunkingConfig {
+            parallelism: 3,
+            ..Default::default()
+        };
+        let stt = ChunkedSttProvider::new(Arc::new(SecondsStt), cfg);
+
+        let t = stt
+            .transcribe(&audio(100), "local", "m", "en")
+            .await
+            .unwrap();
+        let expected: Vec<String> = (0..100).map(|s| format!("w{s}")).collect();
+        assert_eq!(t.text, expected.join(" "));
+    }
+}
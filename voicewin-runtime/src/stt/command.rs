@@ -0,0 +1,166 @@
+//! The `command` STT provider: hands the recording to a user-supplied program as a WAV file
+//! and reads the transcript back as JSON from its stdout (see [`CommandSttSettings`]).
+
+use std::process::Stdio;
+
+use anyhow::Context;
+use serde::Deserialize;
+use voicewin_core::diarization::SpeakerSegment;
+use voicewin_core::error::{ErrorKind, VoicewinError};
+use voicewin_core::stt::CommandSttSettings;
+use voicewin_engine::traits::{AudioInput, Transcript};
+
+/// Stderr kept in the error when the program fails.
+const STDERR_TAIL_CHARS: usize = 500;
+
+#[derive(Debug, Deserialize)]
+struct CommandOutput {
+    text: String,
+    #[serde(default)]
+    segments: Vec<SpeakerSegment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandSttProvider {
+    settings: CommandSttSettings,
+}
+
+impl CommandSttProvider {
+    pub fn new(settings: CommandSttSettings) -> Self {
+        Self { settings }
+    }
+}
+
+fn parse_command_output(stdout: &[u8]) -> anyhow::Result<CommandOutput> {
+    serde_json::from_slice(stdout).context("the STT command did not print {\"text\": ...} JSON")
+}
+
+fn stderr_tail(stderr: &[u8]) -> String {
+    let stderr = String::from_utf8_lossy(stderr);
+    let stderr = stderr.trim();
+    let skip = stderr.chars().count().saturating_sub(STDERR_TAIL_CHARS);
+    stderr.chars().skip(skip).collect()
+}
+
+#[async_trait::async_trait]
+impl voicewin_engine::traits::SttProvider for CommandSttProvider {
+    #[tracing::instrument(name = "stt.command", skip_all)]
+    async fn transcribe(
+        &self,
+        audio: &AudioInput,
+        provider: &str,
+        model: &str,
+        language: &str,
+    ) -> anyhow::Result<Transcript> {
+        if !self.settings.is_configured() {
+            return Err(VoicewinError::new(
+                ErrorKind::Config,
+                "The custom STT command has no program set. Set one in Settings.",
+            )
+            .into());
+        }
+
+        let mut wav = tempfile::Builder::new()
+            .prefix("voicewin-")
+            .suffix(".wav")
+            .tempfile()
+            .context("create WAV for the STT command")?;
+        std::io::Write::write_all(
+            &mut wav,
+            &super::encode_wav_mono_f32le(&audio.samples, audio.sample_rate_hz),
+        )
+        .context("write WAV for the STT command")?;
+        let wav_path = wav.path().to_string_lossy().to_string();
+
+        let program = self.settings.program.trim();
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.args(self.settings.expand_args(&wav_path, language, model))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // A stage timeout or Cancel drops this future; don't leave the program running.
+            .kill_on_drop(true);
+        #[cfg(windows)]
+        {
+            // CREATE_NO_WINDOW: no console flashing up for every dictation.
+            cmd.creation_flags(0x0800_0000);
+        }
+
+        let output = cmd
+            .output()
+            .await
+            .with_context(|| format!("failed to run the STT command {program}"))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "the STT command {program} failed ({}): {}",
+                output.status,
+                stderr_tail(&output.stderr)
+            ));
+        }
+
+        let parsed = parse_command_output(&output.stdout)?;
+        Ok(Transcript {
+            text: parsed.text,
+            provider: provider.into(),
+            model: model.into(),
+            segments: parsed.segments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voicewin_engine::traits::SttProvider;
+
+    #[test]
+    fn parses_text_and_optional_segments() {
+        let plain = parse_command_output(br#"{"text":"hello"}"#).unwrap();
+        assert_eq!(plain.text, "hello");
+        assert!(plain.segments.is_empty());
+
+        let diarized = parse_command_output(
+            br#"{"text":"Hi. Hello.","segments":[{"speaker":1,"text":"Hi."},{"speaker":2,"text":" Hello."}]}"#,
+        )
+        .unwrap();
+        assert_eq!(diarized.segments.len(), 2);
+        assert_eq!(diarized.segments[1].speaker, 2);
+
+        assert!(parse_command_output(b"hello").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_the_program_with_the_wav_path() {
+        let provider = CommandSttProvider::new(CommandSttSettings {
+            program: "sh".into(),
+            args: vec![
+                "-c".into(),
+                r#"test -s "$1" && printf '{"text":"%s"}' "$2""#.into(),
+                "sh".into(),
+                "{wav}".into(),
+                "{language}".into(),
+            ],
+        });
+        let audio = AudioInput {
+            sample_rate_hz: 16_000,
+            samples: vec![0.0; 160],
+        };
+        let t = provider
+            .transcribe(&audio, "command", "default", "de")
+            .await
+            .unwrap();
+        assert_eq!(t.text, "de");
+        assert_eq!(t.provider, "command");
+
+        let failing = CommandSttProvider::new(CommandSttSettings {
+            program: "sh".into(),
+            args: vec!["-c".into(), "echo boom >&2; exit 3".into()],
+        });
+        let err = failing
+            .transcribe(&audio, "command", "default", "de")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("boom"), "{err}");
+    }
+}
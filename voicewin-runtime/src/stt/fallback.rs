@@ -0,0 +1,246 @@
+//! Picking a usable STT provider at session start.
+//!
+//! The configured provider may be unusable right now: no API key, its model deleted, or no
+//! network. With `stt_fallbacks` set, [`resolve_stt`] walks that list and returns the first
+//! provider that passes its checks, so dictation degrades instead of failing on stop.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use voicewin_core::stt::{
+    COMMAND_MODEL_DEFAULT, CommandSttSettings, ELEVENLABS_MODEL_SCRIBE_V2, STT_PROVIDER_COMMAND,
+    STT_PROVIDER_ELEVENLABS, STT_PROVIDER_LOCAL,
+};
+
+use crate::models;
+use crate::secrets::{SecretKey, get_secret};
+
+const ELEVENLABS_API_URL: &str = "https://api.elevenlabs.io";
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Why a provider can't be used right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unavailable {
+    MissingApiKey,
+    ModelNotInstalled,
+    /// The `command` provider has no program set.
+    NotConfigured,
+    Offline,
+    UnknownProvider,
+}
+
+impl Unavailable {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Unavailable::MissingApiKey => "no API key is set",
+            Unavailable::ModelNotInstalled => "its model is not installed",
+            Unavailable::NotConfigured => "it is not set up",
+            Unavailable::Offline => "it can't be reached",
+            Unavailable::UnknownProvider => "it is not a known provider",
+        }
+    }
+}
+
+/// The configured provider was swapped for a fallback for this session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SttDowngrade {
+    pub from_provider: String,
+    pub to_provider: String,
+    pub to_model: String,
+    pub reason: Unavailable,
+}
+
+impl SttDowngrade {
+    /// One line for the session warning and the UI banner.
+    pub fn message(&self) -> String {
+        format!(
+            "Using {} instead of {} because {}.",
+            self.to_provider,
+            self.from_provider,
+            self.reason.describe()
+        )
+    }
+}
+
+/// Availability checks, split out so resolution can be tested without a keyring or network.
+#[async_trait::async_trait]
+pub trait SttProbe: Send + Sync {
+    /// `None` when `provider` can transcribe with `model` right now.
+    async fn check(&self, provider: &str, model: &str) -> Option<Unavailable>;
+
+    /// The model to use when `provider` is picked as a fallback.
+    fn default_model(&self, provider: &str) -> Option<String>;
+}
+
+/// The real checks: keyring, installed model files and a TCP probe.
+pub struct SystemProbe {
+    local_data_dir: PathBuf,
+    command: CommandSttSettings,
+}
+
+impl SystemProbe {
+    pub fn new(local_data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            local_data_dir: local_data_dir.into(),
+            command: CommandSttSettings::default(),
+        }
+    }
+
+    /// The program behind the `command` provider; without one it is never picked.
+    pub fn with_command(mut self, command: CommandSttSettings) -> Self {
+        self.command = command;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl SttProbe for SystemProbe {
+    async fn check(&self, provider: &str, model: &str) -> Option<Unavailable> {
+        match provider {
+            STT_PROVIDER_LOCAL => models::validate_ggml_file(Path::new(model), 1024 * 1024)
+                .err()
+                .map(|_| Unavailable::ModelNotInstalled),
+            STT_PROVIDER_ELEVENLABS => {
+                let has_key = get_secret(SecretKey::ElevenLabsApiKey)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|k| !k.trim().is_empty());
+                if !has_key {
+                    Some(Unavailable::MissingApiKey)
+                } else if !voicewin_providers::runtime::is_reachable(
+                    ELEVENLABS_API_URL,
+                    REACHABILITY_TIMEOUT,
+                )
+                .await
+                {
+                    Some(Unavailable::Offline)
+                } else {
+                    None
+                }
+            }
+            STT_PROVIDER_COMMAND => {
+                (!self.command.is_configured()).then_some(Unavailable::NotConfigured)
+            }
+            _ => Some(Unavailable::UnknownProvider),
+        }
+    }
+
+    fn default_model(&self, provider: &str) -> Option<String> {
+        match provider {
+            STT_PROVIDER_LOCAL => Some(
+                models::choose_default_local_stt_model_path(&self.local_data_dir)
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            STT_PROVIDER_ELEVENLABS => Some(ELEVENLABS_MODEL_SCRIBE_V2.to_string()),
+            STT_PROVIDER_COMMAND => Some(COMMAND_MODEL_DEFAULT.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// The first usable provider for this session, or `None` to keep the configured one.
+///
+/// Nothing is probed without fallbacks, and if no fallback is usable either the configured
+/// provider is kept so the session fails with its own error.
+pub async fn resolve_stt(
+    provider: &str,
+    model: &str,
+    fallbacks: &[String],
+    probe: &dyn SttProbe,
+) -> Option<SttDowngrade> {
+    if fallbacks.is_empty() {
+        return None;
+    }
+    let reason = probe.check(provider, model).await?;
+
+    for fallback in fallbacks {
+        if fallback == provider {
+            continue;
+        }
+        let Some(fallback_model) = probe.default_model(fallback) else {
+            continue;
+        };
+        if probe.check(fallback, &fallback_model).await.is_none() {
+            return Some(SttDowngrade {
+                from_provider: provider.to_string(),
+                to_provider: fallback.clone(),
+                to_model: fallback_model,
+                reason,
+            });
+        }
+    }
+    log::warn!("stt provider {provider} unavailable ({reason:?}) and no fallback is usable");
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeProbe {
+        down: Vec<(&'static str, Unavailable)>,
+    }
+
+    #[async_trait::async_trait]
+    impl SttProbe for FakeProbe {
+        async fn check(&self, provider: &str, _model: &str) -> Option<Unavailable> {
+            self.down
+                .iter()
+                .find(|(p, _)| *p == provider)
+                .map(|(_, reason)| *reason)
+        }
+
+        fn default_model(&self, provider: &str) -> Option<String> {
+            (provider != "unknown").then(|| format!("{provider}-default"))
+        }
+    }
+
+    fn fallbacks(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_first_usable_provider() {
+        let probe = FakeProbe {
+            down: vec![(STT_PROVIDER_ELEVENLABS, Unavailable::Offline)],
+        };
+        let chain = fallbacks(&["unknown", STT_PROVIDER_ELEVENLABS, STT_PROVIDER_LOCAL]);
+        let downgrade = resolve_stt(STT_PROVIDER_ELEVENLABS, "scribe_v2", &chain, &probe)
+            .await
+            .unwrap();
+        assert_eq!(downgrade.from_provider, STT_PROVIDER_ELEVENLABS);
+        assert_eq!(downgrade.to_provider, STT_PROVIDER_LOCAL);
+        assert_eq!(downgrade.to_model, "local-default");
+        assert_eq!(downgrade.reason, Unavailable::Offline);
+        assert!(downgrade.message().contains("can't be reached"));
+    }
+
+    #[tokio::test]
+    async fn keeps_the_configured_provider_when_usable_or_nothing_else_is() {
+        let chain = fallbacks(&[STT_PROVIDER_ELEVENLABS, STT_PROVIDER_LOCAL]);
+
+        let healthy = FakeProbe { down: vec![] };
+        assert_eq!(
+            resolve_stt(STT_PROVIDER_ELEVENLABS, "scribe_v2", &chain, &healthy).await,
+            None
+        );
+
+        let all_down = FakeProbe {
+            down: vec![
+                (STT_PROVIDER_ELEVENLABS, Unavailable::MissingApiKey),
+                (STT_PROVIDER_LOCAL, Unavailable::ModelNotInstalled),
+            ],
+        };
+        assert_eq!(
+            resolve_stt(STT_PROVIDER_ELEVENLABS, "scribe_v2", &chain, &all_down).await,
+            None
+        );
+        assert_eq!(
+            resolve_stt(STT_PROVIDER_ELEVENLABS, "scribe_v2", &[], &all_down).await,
+            None
+        );
+    }
+}
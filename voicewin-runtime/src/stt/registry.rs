@@ -0,0 +1,193 @@
+//! What each STT provider can do, so settings UIs can list providers and their models
+//! instead of hard-coding provider ids.
+
+use std::path::Path;
+
+use serde::Serialize;
+use voicewin_core::stt::{
+    COMMAND_MODEL_DEFAULT, ELEVENLABS_MODEL_SCRIBE_V2, ELEVENLABS_MODEL_SCRIBE_V2_REALTIME,
+    STT_PROVIDER_COMMAND, STT_PROVIDER_ELEVENLABS, STT_PROVIDER_LOCAL,
+};
+
+use crate::models;
+
+/// Where audio goes to be transcribed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderLocation {
+    /// On this computer; works offline.
+    Local,
+    /// Uploaded to the provider.
+    Cloud,
+    /// Handed to a program the user configured.
+    External,
+}
+
+/// A model a provider can run now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SttModelInfo {
+    /// The `stt_model` config value: a file path for local models.
+    pub id: String,
+    pub title: String,
+    /// Streams while recording instead of transcribing on stop.
+    pub realtime: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SttProviderInfo {
+    /// The `stt_provider` config value.
+    pub id: &'static str,
+    pub title: &'static str,
+    pub location: ProviderLocation,
+    /// At least one model streams while recording.
+    pub realtime: bool,
+    /// Language codes it can transcribe; `"*"` for any language it detects. Empty when a
+    /// local provider has no model installed.
+    pub languages: Vec<&'static str>,
+    pub needs_api_key: bool,
+    /// Installed models for local providers; every selectable model for cloud ones.
+    pub models: Vec<SttModelInfo>,
+}
+
+/// Every STT provider, with the local models installed under `local_data_dir`.
+pub fn stt_providers(local_data_dir: &Path) -> Vec<SttProviderInfo> {
+    vec![
+        local_provider(local_data_dir),
+        elevenlabs_provider(),
+        command_provider(),
+    ]
+}
+
+fn local_provider(local_data_dir: &Path) -> SttProviderInfo {
+    let mut models = Vec::new();
+    let mut tables: Vec<Vec<(&'static str, u8)>> = Vec::new();
+
+    let bootstrap = models::installed_bootstrap_model_path(local_data_dir);
+    if models::validate_ggml_file(&bootstrap, 1024 * 1024).is_ok() {
+        models.push(SttModelInfo {
+            id: bootstrap.to_string_lossy().to_string(),
+            title: models::BOOTSTRAP_MODEL_TITLE.into(),
+            realtime: false,
+        });
+        tables.push(models::BOOTSTRAP_MODEL_LANGUAGE_ACCURACY.to_vec());
+    }
+
+    let models_dir = models::models_dir(local_data_dir);
+    for spec in models::whisper_catalog() {
+        let path = models_dir.join(&spec.filename);
+        if !path.exists() {
+            continue;
+        }
+        models.push(SttModelInfo {
+            id: path.to_string_lossy().to_string(),
+            title: spec.title,
+            realtime: false,
+        });
+        tables.push(spec.language_accuracy);
+    }
+
+    let mut languages: Vec<&'static str> = tables.iter().flatten().map(|(l, _)| *l).collect();
+    if languages.contains(&"*") {
+        languages = vec!["*"];
+    } else {
+        languages.sort_unstable();
+        languages.dedup();
+    }
+
+    SttProviderInfo {
+        id: STT_PROVIDER_LOCAL,
+        title: "Local Whisper",
+        location: ProviderLocation::Local,
+        realtime: false,
+        languages,
+        needs_api_key: false,
+        models,
+    }
+}
+
+fn elevenlabs_provider() -> SttProviderInfo {
+    SttProviderInfo {
+        id: STT_PROVIDER_ELEVENLABS,
+        title: "ElevenLabs",
+        location: ProviderLocation::Cloud,
+        realtime: true,
+        languages: vec!["*"],
+        needs_api_key: true,
+        models: vec![
+            SttModelInfo {
+                id: ELEVENLABS_MODEL_SCRIBE_V2.into(),
+                title: "Scribe v2 (Batch)".into(),
+                realtime: false,
+            },
+            SttModelInfo {
+                id: ELEVENLABS_MODEL_SCRIBE_V2_REALTIME.into(),
+                title: "Scribe v2 (Realtime)".into(),
+                realtime: true,
+            },
+        ],
+    }
+}
+
+fn command_provider() -> SttProviderInfo {
+    SttProviderInfo {
+        id: STT_PROVIDER_COMMAND,
+        title: "Custom command",
+        location: ProviderLocation::External,
+        realtime: false,
+        languages: vec!["*"],
+        needs_api_key: false,
+        models: vec![SttModelInfo {
+            id: COMMAND_MODEL_DEFAULT.into(),
+            title: "As configured".into(),
+            realtime: false,
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_provider_lists_only_installed_models() {
+        let dir = tempfile::tempdir().unwrap();
+        let providers = stt_providers(dir.path());
+        let ids: Vec<_> = providers.iter().map(|p| p.id).collect();
+        assert_eq!(
+            ids,
+            [
+                STT_PROVIDER_LOCAL,
+                STT_PROVIDER_ELEVENLABS,
+                STT_PROVIDER_COMMAND
+            ]
+        );
+
+        let local = &providers[0];
+        assert_eq!(local.location, ProviderLocation::Local);
+        assert!(local.models.is_empty());
+        assert!(local.languages.is_empty());
+
+        let spec = &models::whisper_catalog()[0];
+        let models_dir = models::models_dir(dir.path());
+        std::fs::create_dir_all(&models_dir).unwrap();
+        std::fs::write(models_dir.join(&spec.filename), b"model").unwrap();
+
+        let local = local_provider(dir.path());
+        assert_eq!(local.models.len(), 1);
+        assert_eq!(local.models[0].title, spec.title);
+        assert!(local.models[0].id.ends_with(&spec.filename));
+        assert_eq!(local.languages, ["*"]);
+    }
+
+    #[test]
+    fn elevenlabs_needs_a_key_and_streams() {
+        let eleven = elevenlabs_provider();
+        assert!(eleven.needs_api_key);
+        assert!(eleven.realtime);
+        assert_eq!(
+            eleven.models.iter().filter(|m| m.realtime).count(),
+            1,
+            "one realtime model"
+        );
+    }
+}
@@ -7,10 +7,12 @@ use voicewin_engine::traits::{AudioInput, SttProvider, Transcript};
 /// MVP supported providers:
 /// - "local" -> local Whisper (filesystem model path)
 /// - "elevenlabs" -> ElevenLabs cloud STT
+/// - "command" -> a user-supplied program
 #[derive(Clone)]
 pub struct SttRouter {
     local: Option<Arc<dyn SttProvider>>,
     elevenlabs: Option<Arc<dyn SttProvider>>,
+    command: Option<Arc<dyn SttProvider>>,
 }
 
 impl SttRouter {
@@ -18,6 +20,7 @@ impl SttRouter {
         Self {
             local: None,
             elevenlabs: None,
+            command: None,
         }
     }
 
@@ -31,6 +34,11 @@ impl SttRouter {
         self
     }
 
+    pub fn with_command(mut self, provider: Arc<dyn SttProvider>) -> Self {
+        self.command = Some(provider);
+        self
+    }
+
     pub async fn transcribe(
         &self,
         audio: &AudioInput,
@@ -53,6 +61,13 @@ impl SttRouter {
                     .transcribe(audio, provider, model, language)
                     .await
             }
+            "command" => {
+                self.command
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("command STT provider not configured"))?
+                    .transcribe(audio, provider, model, language)
+                    .await
+            }
             other => Err(anyhow::anyhow!("unsupported STT provider: {other}")),
         }
     }
@@ -77,6 +92,7 @@ mod tests {
                 text: format!("p={provider} m={model} l={language}"),
                 provider: provider.into(),
                 model: model.into(),
+                segments: vec![],
             })
         }
     }
@@ -0,0 +1,308 @@
+//! Update checks against the project's GitHub releases.
+//!
+//! The stable channel only considers full releases; beta also offers pre-releases. The shell
+//! downloads the matching installer (NSIS on Windows, DMG on macOS) and hands it to the OS.
+
+use std::cmp::Ordering;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use voicewin_core::config::UpdateChannel;
+use voicewin_providers::request::{Body, HttpRequest};
+
+pub const RELEASES_URL: &str = "https://api.github.com/repos/Clouder0/voicewin/releases";
+
+/// A release newer than the running build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub notes: String,
+    pub release_url: String,
+    pub prerelease: bool,
+    /// Installer for this platform; `None` means the user updates from the release page.
+    /// Only offered when GitHub reports its digest, so the download can always be verified.
+    pub asset: Option<UpdateAsset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateAsset {
+    pub name: String,
+    pub url: String,
+    pub size: u64,
+    /// Lowercase hex digest.
+    pub sha256: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    #[serde(default)]
+    size: u64,
+    /// `"sha256:<hex>"`.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+/// `MAJOR.MINOR.PATCH` with an optional `-pre.release` suffix; a leading `v` is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_start_matches('v');
+        // Build metadata never affects precedence.
+        let s = s.split('+').next().unwrap_or(s);
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return None,
+            None => (s, None),
+        };
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                // A pre-release sorts before its release.
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => cmp_prerelease(a, b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn cmp_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+        }
+    }
+}
+
+/// Ask GitHub for the newest release on `channel` that is newer than `current_version`.
+pub async fn check_for_updates(
+    current_version: &str,
+    channel: UpdateChannel,
+) -> anyhow::Result<Option<UpdateInfo>> {
+    let req = HttpRequest {
+        method: "GET".into(),
+        url: format!("{RELEASES_URL}?per_page=30"),
+        headers: vec![
+            ("Accept".into(), "application/vnd.github+json".into()),
+            // GitHub rejects API calls without a User-Agent.
+            ("User-Agent".into(), format!("VoiceWin/{current_version}")),
+        ],
+        body: Body::Empty,
+    };
+    let resp = voicewin_providers::runtime::execute(&req).await?;
+    if !(200..=299).contains(&resp.status) {
+        return Err(anyhow::anyhow!(
+            "update check failed: status={} body={}",
+            resp.status,
+            String::from_utf8_lossy(&resp.body)
+        ));
+    }
+    newest_update(&resp.body, current_version, channel, is_platform_installer)
+}
+
+/// Pick the newest eligible release from a GitHub `releases` response.
+pub fn newest_update(
+    releases_json: &[u8],
+    current_version: &str,
+    channel: UpdateChannel,
+    is_installer: impl Fn(&str) -> bool,
+) -> anyhow::Result<Option<UpdateInfo>> {
+    let current = Version::parse(current_version)
+        .ok_or_else(|| anyhow::anyhow!("invalid app version: {current_version}"))?;
+    let releases: Vec<GithubRelease> = serde_json::from_slice(releases_json)?;
+
+    let newest = releases
+        .into_iter()
+        .filter(|r| !r.draft && (channel == UpdateChannel::Beta || !r.prerelease))
+        .filter_map(|r| Version::parse(&r.tag_name).map(|v| (v, r)))
+        .filter(|(v, _)| *v > current)
+        .max_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(newest.map(|(version, r)| UpdateInfo {
+        version: version_string(&version),
+        notes: r.body.unwrap_or_default(),
+        release_url: r.html_url,
+        prerelease: r.prerelease,
+        asset: r
+            .assets
+            .into_iter()
+            .find(|a| is_installer(&a.name))
+            .and_then(|a| {
+                let sha256 = a.digest.as_deref()?.strip_prefix("sha256:")?;
+                Some(UpdateAsset {
+                    sha256: sha256.to_ascii_lowercase(),
+                    name: a.name,
+                    url: a.browser_download_url,
+                    size: a.size,
+                })
+            }),
+    }))
+}
+
+fn version_string(v: &Version) -> String {
+    match &v.pre {
+        Some(pre) => format!("{}.{}.{}-{pre}", v.major, v.minor, v.patch),
+        None => format!("{}.{}.{}", v.major, v.minor, v.patch),
+    }
+}
+
+/// Whether a release asset is the installer for this OS and CPU, following the bundler's
+/// naming (`VoiceWin_1.2.0_x64-setup.exe`, `VoiceWin_1.2.0_aarch64.dmg`).
+pub fn is_platform_installer(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x64",
+        other => other,
+    };
+    let kind_ok = if cfg!(windows) {
+        name.ends_with("-setup.exe")
+    } else if cfg!(target_os = "macos") {
+        name.ends_with(".dmg")
+    } else {
+        false
+    };
+    kind_ok && name.contains(arch)
+}
+
+/// Hand a downloaded installer to the OS: run the NSIS setup on Windows, mount the DMG on
+/// macOS. On Windows the caller should exit right after so the installer can replace files.
+pub fn launch_installer(path: &Path) -> anyhow::Result<()> {
+    if cfg!(windows) {
+        std::process::Command::new(path).spawn()?;
+    } else if cfg!(target_os = "macos") {
+        let status = std::process::Command::new("open").arg(path).status()?;
+        if !status.success() {
+            anyhow::bail!("failed to open {}", path.display());
+        }
+    } else {
+        anyhow::bail!("automatic updates are not supported on this platform");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASES: &str = r#"[
+        {"tag_name": "v0.3.0-beta.2", "html_url": "https://example.com/0.3.0-beta.2",
+         "prerelease": true, "body": "Beta", "assets": []},
+        {"tag_name": "v0.4.0", "html_url": "https://example.com/0.4.0", "draft": true},
+        {"tag_name": "v0.2.1", "html_url": "https://example.com/0.2.1", "body": "Fixes",
+         "assets": [
+            {"name": "VoiceWin_0.2.1_x64.dmg", "browser_download_url": "https://example.com/a.dmg", "size": 10},
+            {"name": "VoiceWin_0.2.1_x64-setup.exe", "browser_download_url": "https://example.com/a.exe",
+             "size": 20, "digest": "sha256:ABCD"}
+         ]},
+        {"tag_name": "v0.1.0", "html_url": "https://example.com/0.1.0"}
+    ]"#;
+
+    fn is_exe(name: &str) -> bool {
+        name.ends_with("-setup.exe")
+    }
+
+    #[test]
+    fn versions_order_like_semver() {
+        let v = |s| Version::parse(s).unwrap();
+        assert!(v("v0.10.0") > v("0.9.9"));
+        assert!(v("1.0.0") > v("1.0.0-rc.1"));
+        assert!(v("1.0.0-beta.11") > v("1.0.0-beta.2"));
+        assert!(v("1.0.0-beta") > v("1.0.0-alpha.1"));
+        assert_eq!(v("1.2.3+build.5"), v("1.2.3"));
+        assert_eq!(Version::parse("1.2"), None);
+        assert_eq!(Version::parse("1.2.3-"), None);
+    }
+
+    #[test]
+    fn channels_pick_the_newest_eligible_release() {
+        let stable = newest_update(RELEASES.as_bytes(), "0.1.0", UpdateChannel::Stable, is_exe)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stable.version, "0.2.1");
+        assert_eq!(stable.notes, "Fixes");
+        let asset = stable.asset.unwrap();
+        assert_eq!(asset.url, "https://example.com/a.exe");
+        assert_eq!(asset.sha256, "abcd");
+
+        // An installer without a digest is not offered; the user goes to the release page.
+        let is_dmg = |name: &str| name.ends_with(".dmg");
+        let unverified = newest_update(RELEASES.as_bytes(), "0.1.0", UpdateChannel::Stable, is_dmg)
+            .unwrap()
+            .unwrap();
+        assert_eq!(unverified.release_url, "https://example.com/0.2.1");
+        assert_eq!(unverified.asset, None);
+
+        let beta = newest_update(RELEASES.as_bytes(), "0.1.0", UpdateChannel::Beta, is_exe)
+            .unwrap()
+            .unwrap();
+        assert_eq!(beta.version, "0.3.0-beta.2");
+        assert!(beta.prerelease);
+        assert_eq!(beta.asset, None);
+
+        // Drafts are never offered, and nothing is newer than 0.3.0.
+        assert_eq!(
+            newest_update(RELEASES.as_bytes(), "0.3.0", UpdateChannel::Beta, is_exe).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use voicewin_engine::session::SessionWebhookPayload;
+
+/// Posts finished sessions to the URL from `WebhookSettings`.
+#[derive(Clone)]
+pub struct HttpWebhookSink {
+    url: String,
+    bearer_token: String,
+}
+
+impl std::fmt::Debug for HttpWebhookSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpWebhookSink")
+            .field("url", &self.url)
+            .field("bearer_token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl HttpWebhookSink {
+    pub fn new(url: impl Into<String>, bearer_token: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            bearer_token: bearer_token.into(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl voicewin_engine::traits::WebhookSink for HttpWebhookSink {
+    async fn deliver(&self, payload: &SessionWebhookPayload) -> anyhow::Result<()> {
+        let body = serde_json::to_string(payload)?;
+        let req =
+            voicewin_providers::webhook::build_webhook_request(&self.url, &self.bearer_token, body);
+        let resp = voicewin_providers::runtime::execute(&req).await?;
+
+        if !(200..=299).contains(&resp.status) {
+            let mut body = String::from_utf8_lossy(&resp.body).to_string();
+            if body.len() > 200 {
+                body.truncate(body.floor_char_boundary(200));
+                body.push_str("...");
+            }
+            return Err(anyhow::anyhow!(
+                "webhook returned status={} body={}",
+                resp.status,
+                body
+            ));
+        }
+        Ok(())
+    }
+}
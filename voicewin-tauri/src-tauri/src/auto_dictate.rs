@@ -0,0 +1,79 @@
+// Per-profile auto-dictate (`PowerModeProfile::auto_dictate`): when focus moves into a
+// profile's app, prompt the user or start recording once the profile's delay has passed.
+//
+// Fed by the foreground watch. Moving to another app before the delay is up cancels the
+// pending prompt or start, and nothing happens while a session is already running.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+use voicewin_core::hotkeys::HotkeyAction;
+use voicewin_core::power_mode::{AutoDictateMode, AutoDictateTracker};
+use voicewin_core::types::AppIdentity;
+
+use crate::AppState;
+
+static TRACKER: Mutex<AutoDictateTracker> = Mutex::new(AutoDictateTracker::new());
+
+// Bumped on every focus change; a pending prompt or start only runs if it is still current.
+static FOCUS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub fn focus_changed(app: &tauri::AppHandle, id: &AppIdentity) {
+    let generation = FOCUS_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let Some(cfg) = crate::current_config(app) else {
+        return;
+    };
+    let Some(profile) = TRACKER
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .focus_changed(&cfg.profiles, id)
+        .cloned()
+    else {
+        return;
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(profile.auto_dictate.delay_ms)).await;
+        let state = app.state::<AppState>();
+        if FOCUS_GENERATION.load(Ordering::SeqCst) != generation
+            || state.session.recording_disabled()
+            || !state.session.is_idle().await
+        {
+            return;
+        }
+        match profile.auto_dictate.mode {
+            AutoDictateMode::Off => {}
+            AutoDictateMode::Prompt => {
+                if crate::quiet_mode() {
+                    return;
+                }
+                let body = match crate::hotkeys::shortcut(HotkeyAction::Toggle) {
+                    Some(hotkey) => format!(
+                        "Ready to dictate in {}. Press {hotkey} to start.",
+                        profile.name
+                    ),
+                    None => format!("Ready to dictate in {}.", profile.name),
+                };
+                if let Err(e) = app
+                    .notification()
+                    .builder()
+                    .title("VoiceWin")
+                    .body(body)
+                    .show()
+                {
+                    log::warn!("failed to show notification: {e}");
+                }
+            }
+            AutoDictateMode::Record => {
+                if let Some(svc) = crate::hotkeys::service(&app).await {
+                    log::info!("auto-dictate: recording for profile {}", profile.name);
+                    let _ = state.session.toggle_recording(&app, svc).await;
+                }
+            }
+        }
+    });
+}
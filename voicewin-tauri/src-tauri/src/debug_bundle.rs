@@ -0,0 +1,151 @@
+// One-click diagnostics for bug reports.
+//
+// Collects recent logs, an anonymized config, model status, audio devices, and the last
+// session's stage timings into a zip in the Downloads folder, so users don't have to find
+// the app's log directory themselves.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use tauri::Manager;
+use voicewin_appcore::service::AppService;
+use zip::write::SimpleFileOptions;
+
+use crate::session_controller::SessionController;
+
+// Only the newest few log files, and only their tail, to keep bundles small.
+const MAX_LOG_FILES: usize = 3;
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+pub async fn export(
+    app: &tauri::AppHandle,
+    svc: &AppService,
+    controller: &SessionController,
+) -> anyhow::Result<PathBuf> {
+    let out_dir = match app.path().download_dir() {
+        Ok(dir) => dir,
+        Err(_) => {
+            crate::app_paths(app)
+                .context("no downloads folder")?
+                .data_dir
+        }
+    };
+    std::fs::create_dir_all(&out_dir)
+        .with_context(|| format!("failed to create dir: {}", out_dir.display()))?;
+
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let out_path = out_dir.join(format!("voicewin-debug-{ts}.zip"));
+
+    let file = std::fs::File::create(&out_path)
+        .with_context(|| format!("failed to create: {}", out_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let opts = SimpleFileOptions::default();
+
+    let home = app.path().home_dir().ok();
+
+    let system = serde_json::json!({
+        "app_version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+    });
+    add_json(&mut zip, opts, "system.json", &system)?;
+
+    let config = match svc.load_config() {
+        Ok(cfg) => voicewin_runtime::diagnostics::anonymized_config(&cfg, home.as_deref()),
+        Err(e) => serde_json::json!({ "error": e.to_string() }),
+    };
+    add_json(&mut zip, opts, "config.json", &config)?;
+
+    let models = match crate::get_model_status(app.clone()).await {
+        Ok(status) => serde_json::to_value(status)?,
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+    add_json(&mut zip, opts, "models.json", &models)?;
+
+    let audio = match voicewin_audio::AudioRecorder::list_input_device_names() {
+        Ok(devices) => serde_json::json!({
+            "input_devices": devices,
+            "selected": svc.load_config().ok().and_then(|c| c.defaults.microphone_device),
+            "recording_disabled": svc.recording_disabled(),
+        }),
+        Err(e) => serde_json::json!({ "error": e.to_string() }),
+    };
+    add_json(&mut zip, opts, "audio.json", &audio)?;
+
+    let timeline = controller.last_session_timeline().await;
+    add_json(
+        &mut zip,
+        opts,
+        "last_session.json",
+        &serde_json::json!({ "timeline": timeline }),
+    )?;
+
+    if let Ok(log_dir) = crate::app_paths(app).map(|p| p.log_dir) {
+        for path in recent_log_files(&log_dir) {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            match read_tail(&path, MAX_LOG_BYTES) {
+                Ok(text) => {
+                    let mut text = crate::redact_for_log(&text);
+                    if let Some(h) = home.as_ref().map(|h| h.to_string_lossy().to_string()) {
+                        text = text.replace(&h, "~");
+                    }
+                    zip.start_file(format!("logs/{name}"), opts)?;
+                    zip.write_all(text.as_bytes())?;
+                }
+                Err(e) => log::warn!("skipping log {}: {e}", path.display()),
+            }
+        }
+    }
+
+    zip.finish().context("failed to finish debug bundle")?;
+    log::info!("debug bundle written to {}", out_path.display());
+    Ok(out_path)
+}
+
+fn add_json<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    opts: SimpleFileOptions,
+    name: &str,
+    value: &serde_json::Value,
+) -> anyhow::Result<()> {
+    zip.start_file(name, opts)?;
+    zip.write_all(serde_json::to_string_pretty(value)?.as_bytes())?;
+    Ok(())
+}
+
+fn recent_log_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(rd) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = rd
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "log"))
+        .filter_map(|e| {
+            let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, e.path()))
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files
+        .into_iter()
+        .take(MAX_LOG_FILES)
+        .map(|(_, p)| p)
+        .collect()
+}
+
+fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut f = std::fs::File::open(path)?;
+    let len = f.metadata()?.len();
+    if len > max_bytes {
+        f.seek(SeekFrom::Start(len - max_bytes))?;
+    }
+    let mut buf = Vec::new();
+    f.read_to_end(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
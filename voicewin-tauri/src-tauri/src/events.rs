@@ -0,0 +1,152 @@
+// Every backend -> webview event goes through here.
+//
+// An event is an `AppEvent` wrapped in a `{ v, type, payload }` envelope and emitted on the
+// single `EVENT_CHANNEL`. `v` is bumped when a payload changes incompatibly, so a webview
+// still running an older bundle can drop what it can't read instead of misrendering it.
+// `src/lib/events.ts` is generated from the list below (see the test at the bottom): adding
+// an event is one line here plus its payload type in `src/lib/types.ts`.
+
+use serde::Serialize;
+use tauri::{Emitter, Runtime};
+use voicewin_appcore::service::{BulkEnhanceProgress, QuotaWarning};
+use voicewin_core::config::OverlayAppearance;
+use voicewin_runtime::stt::fallback::SttDowngrade;
+use voicewin_runtime::updater::UpdateInfo;
+
+use crate::session_controller::{MicLevelPayload, SessionStatusPayload};
+
+pub const EVENT_CHANNEL: &str = "voicewin://event";
+pub const EVENT_VERSION: u32 = 1;
+
+macro_rules! app_events {
+    ($($(#[$doc:meta])* $variant:ident($payload:ty) = $name:literal: $ts:literal,)*) => {
+        #[derive(Debug, Clone, Serialize)]
+        #[serde(tag = "type", content = "payload")]
+        pub enum AppEvent {
+            $($(#[$doc])* #[serde(rename = $name)] $variant($payload),)*
+        }
+
+        // `(type, TypeScript payload type)` for every event, in declaration order.
+        #[cfg(test)]
+        const EVENT_TYPES: &[(&str, &str)] = &[$(($name, $ts),)*];
+    };
+}
+
+app_events! {
+    /// The session state; re-sent on `overlay_ready` and `resync_overlay`.
+    SessionStatus(SessionStatusPayload) = "session_status": "SessionStatusPayload",
+    /// Microphone level while recording.
+    MicLevel(MicLevelPayload) = "mic_level": "MicLevelPayload",
+    ModelDownloadProgress(crate::DownloadProgress) = "model_download_progress": "DownloadProgress",
+    /// The id of the model that finished downloading.
+    ModelDownloadDone(String) = "model_download_done": "string",
+    UpdateAvailable(UpdateInfo) = "update_available": "UpdateInfo",
+    UpdateDownloadProgress(crate::UpdateDownloadProgress) = "update_download_progress": "UpdateDownloadProgress",
+    /// The version whose installer finished downloading.
+    UpdateDownloadDone(String) = "update_download_done": "string",
+    BulkEnhanceProgress(BulkEnhanceProgress) = "bulk_enhance_progress": "BulkEnhanceProgress",
+    /// Another VoiceWin process holds the config lock; the config path.
+    ConfigLocked(String) = "config_locked": "string",
+    /// The new toggle shortcut.
+    ToggleHotkeyChanged(String) = "toggle_hotkey_changed": "string",
+    OverlayPrefsChanged(OverlayAppearance) = "overlay_prefs_changed": "OverlayAppearance",
+    /// The app in front changed; our own windows are skipped.
+    ForegroundAppChanged(crate::ActiveProfileInfo) = "foreground_app_changed": "ActiveProfileInfo",
+    /// The page the main window should switch to.
+    Navigate(String) = "navigate": "string",
+    /// The configured STT provider is unusable; this session uses a fallback.
+    SttFallback(SttDowngrade) = "stt_fallback": "SttDowngrade",
+    /// ElevenLabs credits are low; sent at most every 15 minutes while it is in use.
+    ElevenLabsQuotaLow(QuotaWarning) = "elevenlabs_quota_low": "QuotaWarning",
+}
+
+#[derive(Clone, Serialize)]
+struct Envelope<'a> {
+    v: u32,
+    #[serde(flatten)]
+    event: &'a AppEvent,
+}
+
+/// Emit `event` through `target`: the app handle for every window, or a single window.
+pub fn emit<R: Runtime>(target: &impl Emitter<R>, event: AppEvent) -> tauri::Result<()> {
+    target.emit(
+        EVENT_CHANNEL,
+        Envelope {
+            v: EVENT_VERSION,
+            event: &event,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+    use std::path::Path;
+
+    use super::*;
+
+    // Set to rewrite `src/lib/events.ts` instead of checking it.
+    const UPDATE_ENV: &str = "VOICEWIN_UPDATE_EVENTS_TS";
+
+    fn typescript_definition() -> String {
+        let mut imports: Vec<&str> = EVENT_TYPES
+            .iter()
+            .map(|(_, ts)| *ts)
+            .filter(|ts| !matches!(*ts, "string" | "number" | "boolean"))
+            .collect();
+        imports.sort_unstable();
+        imports.dedup();
+
+        let mut out = String::new();
+        out.push_str("// Generated from `src-tauri/src/events.rs`; do not edit by hand.\n");
+        let _ = writeln!(
+            out,
+            "// Regenerate with `{UPDATE_ENV}=1 cargo test generated_typescript`.\n"
+        );
+        let _ = writeln!(
+            out,
+            "import type {{ {} }} from './types';\n",
+            imports.join(", ")
+        );
+        let _ = writeln!(out, "export const EVENT_CHANNEL = '{EVENT_CHANNEL}';");
+        let _ = writeln!(out, "export const EVENT_VERSION = {EVENT_VERSION};\n");
+        out.push_str("export type AppEventMap = {\n");
+        for (name, ts) in EVENT_TYPES {
+            let _ = writeln!(out, "  {name}: {ts};");
+        }
+        out.push_str("};\n\n");
+        out.push_str("export type AppEventType = keyof AppEventMap;\n\n");
+        out.push_str("export type AppEventEnvelope = {\n");
+        out.push_str("  [K in AppEventType]: { v: number; type: K; payload: AppEventMap[K] };\n");
+        out.push_str("}[AppEventType];\n");
+        out
+    }
+
+    #[test]
+    fn envelope_carries_version_type_and_payload() {
+        let json = serde_json::to_value(Envelope {
+            v: EVENT_VERSION,
+            event: &AppEvent::Navigate("history".into()),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "v": EVENT_VERSION, "type": "navigate", "payload": "history" })
+        );
+    }
+
+    #[test]
+    fn generated_typescript_is_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../src/lib/events.ts");
+        let expected = typescript_definition();
+        if std::env::var_os(UPDATE_ENV).is_some() {
+            std::fs::write(&path, expected).unwrap();
+            return;
+        }
+        let actual = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            actual == expected,
+            "src/lib/events.ts is out of date; rerun with {UPDATE_ENV}=1"
+        );
+    }
+}
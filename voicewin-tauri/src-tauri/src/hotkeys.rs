@@ -0,0 +1,316 @@
+// Global shortcuts, one per action (`voicewin_core::hotkeys::HotkeyAction`), so the whole
+// dictation loop can be driven from the keyboard without seeing or clicking the HUD.
+//
+// The map lives in the UI state store. Only the toggle has a default; the rest are opt-in,
+// since any global shortcut we take is one the foreground app no longer receives. A shortcut
+// can be bound to one action at a time.
+//
+// Side mouse buttons and foot pedals are bound the same way, by name ("Mouse4", "Pedal2");
+// they go through `voicewin_platform::trigger_input` instead of the global shortcut plugin.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::Manager;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+use voicewin_appcore::service::AppService;
+use voicewin_core::hotkeys::{HotkeyAction, HotkeyMap, conflicting_action, normalize_shortcut};
+use voicewin_platform::trigger_input::{
+    TriggerEvent, TriggerInput, TriggerWatch, watch_trigger_inputs,
+};
+
+use crate::AppState;
+use crate::events::{self, AppEvent};
+
+const STORE_KEY: &str = "hotkeys";
+// Before the map, only the toggle was stored (and briefly the overlay actions on their own).
+const LEGACY_TOGGLE_STORE_KEY: &str = "toggle_hotkey";
+const LEGACY_COMMANDS_STORE_KEY: &str = "command_hotkeys";
+
+#[cfg(windows)]
+const DEFAULT_TOGGLE_HOTKEY: &str = "Ctrl+Space";
+
+#[cfg(target_os = "macos")]
+const DEFAULT_TOGGLE_HOTKEY: &str = "Alt+Z";
+
+// Ctrl+Space is commonly taken by input method switchers on Linux desktops.
+#[cfg(target_os = "linux")]
+const DEFAULT_TOGGLE_HOTKEY: &str = "Ctrl+Alt+Space";
+
+static ASSIGNED: Mutex<HotkeyMap> = Mutex::new(HotkeyMap::new());
+
+// Started with the first mouse button or pedal binding and kept for the rest of the run.
+static TRIGGER_WATCH: Mutex<Option<TriggerWatch>> = Mutex::new(None);
+
+// The push-to-talk key is down, and whether it started the running session (so releasing
+// it should stop that session).
+static PUSH_TO_TALK_DOWN: AtomicBool = AtomicBool::new(false);
+static PUSH_TO_TALK_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    pub shortcut: Option<String>,
+}
+
+pub fn list() -> Vec<HotkeyBinding> {
+    let assigned = ASSIGNED.lock().unwrap_or_else(|p| p.into_inner());
+    HotkeyAction::ALL
+        .into_iter()
+        .map(|action| HotkeyBinding {
+            action,
+            shortcut: assigned.get(&action).cloned(),
+        })
+        .collect()
+}
+
+pub fn shortcut(action: HotkeyAction) -> Option<String> {
+    ASSIGNED
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .get(&action)
+        .cloned()
+}
+
+/// Register the persisted shortcuts (or the default toggle) at startup. Ones that fail
+/// (taken by another app) are logged and stay listed, so Settings shows what the user chose.
+pub fn restore(app: &tauri::AppHandle) {
+    let store = app.store(crate::ui_state_store_path()).ok();
+    let load = |key: &str| store.as_ref().and_then(|s| s.get(key));
+
+    let map = match load(STORE_KEY).and_then(|v| serde_json::from_value(v).ok()) {
+        Some(map) => map,
+        None => {
+            let mut map: HotkeyMap = load(LEGACY_COMMANDS_STORE_KEY)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let toggle = load(LEGACY_TOGGLE_STORE_KEY)
+                .and_then(|v| v.as_str().map(|s| s.to_string()))
+                .unwrap_or_else(|| DEFAULT_TOGGLE_HOTKEY.into());
+            map.insert(HotkeyAction::Toggle, toggle);
+            map
+        }
+    };
+
+    for (action, shortcut) in &map {
+        match register(app, *action, shortcut) {
+            Ok(()) => log::info!("registered {action:?} hotkey: {shortcut}"),
+            Err(e) => log::error!("failed to register {action:?} hotkey {shortcut}: {e}"),
+        }
+    }
+    *ASSIGNED.lock().unwrap_or_else(|p| p.into_inner()) = map;
+}
+
+/// Bind `shortcut` to `action`; an empty string unbinds it. Fails without changing anything
+/// when another action has the shortcut or the OS refuses it.
+pub fn set(app: &tauri::AppHandle, action: HotkeyAction, shortcut: &str) -> Result<(), String> {
+    // Store trigger names one way, so "XButton1" and "Mouse4" are seen as the same.
+    let canonical = TriggerInput::parse(shortcut).map(TriggerInput::name);
+    let shortcut = canonical.as_deref().unwrap_or(shortcut.trim());
+    let (prev, conflict) = {
+        let assigned = ASSIGNED.lock().unwrap_or_else(|p| p.into_inner());
+        (
+            assigned.get(&action).cloned(),
+            conflicting_action(&assigned, action, shortcut),
+        )
+    };
+    if prev.as_deref().map(normalize_shortcut) == Some(normalize_shortcut(shortcut)) {
+        return Ok(());
+    }
+    if let Some(other) = conflict {
+        return Err(format!(
+            "{shortcut} is already used for \u{201c}{}\u{201d}.",
+            other.label()
+        ));
+    }
+
+    if let Some(prev) = prev.as_deref().filter(|p| TriggerInput::parse(p).is_none()) {
+        let _ = app.global_shortcut().unregister(prev);
+    }
+    if !shortcut.is_empty() {
+        if let Err(e) = register(app, action, shortcut) {
+            if let Some(prev) = &prev {
+                let _ = register(app, action, prev);
+            }
+            return Err(format!("failed to register hotkey: {e}"));
+        }
+    }
+
+    let snapshot = {
+        let mut assigned = ASSIGNED.lock().unwrap_or_else(|p| p.into_inner());
+        if shortcut.is_empty() {
+            assigned.remove(&action);
+        } else {
+            assigned.insert(action, shortcut.to_string());
+        }
+        assigned.clone()
+    };
+    if let Ok(store) = app.store(crate::ui_state_store_path()) {
+        store.set(STORE_KEY, serde_json::json!(snapshot));
+        let _ = store.save();
+    }
+    if action == HotkeyAction::Toggle {
+        let _ = events::emit(app, AppEvent::ToggleHotkeyChanged(shortcut.to_string()));
+    }
+    Ok(())
+}
+
+fn register(app: &tauri::AppHandle, action: HotkeyAction, shortcut: &str) -> Result<(), String> {
+    if TriggerInput::parse(shortcut).is_some() {
+        return watch_triggers(app);
+    }
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            dispatch(app, action, event.state == ShortcutState::Pressed);
+        })
+        .map_err(|e| e.to_string())
+}
+
+fn dispatch(app: &tauri::AppHandle, action: HotkeyAction, pressed: bool) {
+    // Only push-to-talk cares about the key going up.
+    if !pressed && action != HotkeyAction::PushToTalk {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { run(&app, action, pressed).await });
+}
+
+fn watch_triggers(app: &tauri::AppHandle) -> Result<(), String> {
+    let mut watch = TRIGGER_WATCH.lock().unwrap_or_else(|p| p.into_inner());
+    if watch.is_none() {
+        let app = app.clone();
+        *watch = Some(
+            watch_trigger_inputs(move |event| on_trigger(&app, event))
+                .map_err(|e| format!("{e:#}"))?,
+        );
+    }
+    Ok(())
+}
+
+/// Runs on the input hook thread, so it only looks the action up and hands it off.
+/// Bound buttons are consumed; the rest reach the app under the pointer as usual.
+fn on_trigger(app: &tauri::AppHandle, event: TriggerEvent) -> bool {
+    let action = ASSIGNED
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .iter()
+        .find(|(_, s)| TriggerInput::parse(s) == Some(event.input))
+        .map(|(a, _)| *a);
+    match action {
+        Some(action) => {
+            dispatch(app, action, event.pressed);
+            true
+        }
+        None => false,
+    }
+}
+
+async fn run(app: &tauri::AppHandle, action: HotkeyAction, pressed: bool) {
+    let state = app.state::<AppState>();
+    match action {
+        HotkeyAction::Toggle => {
+            if let Some(svc) = service(app).await {
+                let _ = state.session.toggle_recording(app, svc).await;
+            }
+        }
+        HotkeyAction::PushToTalk => {
+            PUSH_TO_TALK_DOWN.store(pressed, Ordering::SeqCst);
+            let Some(svc) = service(app).await else {
+                return;
+            };
+            // Key repeat sends more presses while held; only an idle session starts.
+            if pressed && state.session.is_idle().await {
+                let res = state.session.toggle_recording(app, svc.clone()).await;
+                PUSH_TO_TALK_STARTED.store(res.is_recording, Ordering::SeqCst);
+            }
+            // Also covers a release that arrived while the session was still starting.
+            if !PUSH_TO_TALK_DOWN.load(Ordering::SeqCst)
+                && PUSH_TO_TALK_STARTED.swap(false, Ordering::SeqCst)
+                && state.session.is_recording().await
+            {
+                let _ = state.session.toggle_recording(app, svc).await;
+            }
+        }
+        // Nothing to stop, cancel or undo without a service, so these don't build one.
+        HotkeyAction::Stop => {
+            if let Some(svc) = state.service.get() {
+                if state.session.is_recording().await {
+                    let _ = state.session.toggle_recording(app, svc.clone()).await;
+                }
+            }
+        }
+        HotkeyAction::Cancel => {
+            if let Some(svc) = state.service.get() {
+                let _ = state.session.cancel_recording(app, svc.clone()).await;
+            }
+        }
+        HotkeyAction::Undo => {
+            // Mid-session, the undo would land on whatever the user is typing meanwhile.
+            if let Some(svc) = state.service.get() {
+                if state.session.is_idle().await {
+                    if let Err(e) = svc.undo_last_insertion().await {
+                        log::warn!("undo last insertion failed: {e:#}");
+                    }
+                }
+            }
+        }
+        HotkeyAction::CycleProfile => {
+            let Some(svc) = service(app).await else {
+                return;
+            };
+            let name = match svc.cycle_forced_profile() {
+                Ok(name) => name,
+                Err(e) => {
+                    log::warn!("switching profile failed: {e:#}");
+                    return;
+                }
+            };
+            let body = match &name {
+                Some(name) => format!("Profile: {name}"),
+                None => "Profile: matched to the app in front".to_string(),
+            };
+            log::info!("{body}");
+            crate::emit_active_profile(app);
+            if !crate::quiet_mode() {
+                if let Err(e) = app
+                    .notification()
+                    .builder()
+                    .title("VoiceWin")
+                    .body(body)
+                    .show()
+                {
+                    log::warn!("failed to show notification: {e}");
+                }
+            }
+        }
+        HotkeyAction::Dismiss => {
+            if let Some(w) = app.get_webview_window("recording_overlay") {
+                let _ = w.hide();
+            }
+        }
+        HotkeyAction::OpenHistory => {
+            if let Some(w) = app.get_webview_window("main") {
+                let _ = w.show();
+                let _ = w.set_focus();
+                let _ = events::emit(&w, AppEvent::Navigate("history".into()));
+            }
+        }
+    }
+}
+
+pub(crate) async fn service(app: &tauri::AppHandle) -> Option<AppService> {
+    let state = app.state::<AppState>();
+    match state
+        .service
+        .get_or_try_init(|| async { crate::build_service(app).await })
+        .await
+    {
+        Ok(svc) => Some(svc.clone()),
+        Err(e) => {
+            log::error!("hotkey service init failed: {e}");
+            None
+        }
+    }
+}
@@ -8,9 +8,28 @@ use std::sync::Arc;
 static OVERLAY_IS_DRAGGING: std::sync::OnceLock<std::sync::atomic::AtomicBool> =
     std::sync::OnceLock::new();
 
-use tauri::menu::{MenuBuilder, MenuItemBuilder};
+// Mirrors `AppConfig.overlay`; read by overlay placement and the hover watcher.
+static OVERLAY_SETTINGS: std::sync::OnceLock<std::sync::Mutex<OverlaySettings>> =
+    std::sync::OnceLock::new();
+
+// Mirrors `AppConfig.sound_cues`; read on session stage changes.
+static SOUND_CUE_SETTINGS: std::sync::OnceLock<std::sync::Mutex<SoundCueSettings>> =
+    std::sync::OnceLock::new();
+
+// Mirrors `AppConfig.respect_do_not_disturb`.
+static RESPECT_DO_NOT_DISTURB: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+// Compiled `AppConfig.redaction`; applied to log lines that may contain dictated text.
+static LOG_REDACTOR: std::sync::OnceLock<std::sync::Mutex<Redactor>> = std::sync::OnceLock::new();
+
+// Whether the overlay window is currently ignoring cursor events.
+static OVERLAY_IGNORES_CURSOR: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+use tauri::menu::{CheckMenuItem, CheckMenuItemBuilder, MenuBuilder, MenuItemBuilder};
 use tauri::tray::TrayIconBuilder;
-use tauri::{Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tauri::{Manager, State, WebviewUrl, WebviewWindowBuilder};
 use tauri_plugin_store::StoreExt;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -19,8 +38,8 @@ struct OverlayMovedPayload {
     y: i32,
 }
 
-#[cfg(any(windows, target_os = "macos"))]
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
 #[cfg(windows)]
 use window_vibrancy::apply_tabbed;
@@ -41,9 +60,32 @@ fn load_tray_icon(app: &tauri::AppHandle) -> Option<tauri::image::Image<'static>
 fn load_tray_icon(_app: &tauri::AppHandle) -> Option<tauri::image::Image<'static>> {
     None
 }
-use voicewin_appcore::service::AppService;
-use voicewin_core::config::AppConfig;
-
+use voicewin_appcore::service::{AppService, service_error};
+use voicewin_core::config::{
+    AppConfig, OverlayAppearance, OverlayMode, OverlayPlacement, OverlaySettings, ProxySettings,
+    SoundCueSettings, TlsSettings, UpdateChannel,
+};
+use voicewin_core::error::{ErrorKind, VoicewinError};
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use voicewin_core::hotkeys::HotkeyAction;
+use voicewin_core::onboarding::{
+    OnboardingAction, OnboardingCheck, OnboardingStatus, OnboardingStep, PermissionState,
+    PrivacyPane,
+};
+use voicewin_core::power_mode::{
+    AppMatcher, EffectiveConfig, EphemeralOverrides, PowerModeOverrides, resolve_effective_config,
+};
+use voicewin_core::redaction::{RedactionSettings, Redactor};
+use voicewin_core::types::{AppIdentity, ProfileId};
+use voicewin_platform::foreground_watch::{
+    CachedContextProvider, FOREGROUND_CHANGES_ARE_PUSHED, ForegroundCache, ForegroundWatch,
+};
+use voicewin_runtime::config_store::ConfigLockedError;
+use voicewin_runtime::ipc::SessionDetails;
+use voicewin_runtime::models::DownloadManager;
+use voicewin_runtime::paths::AppPaths;
+use voicewin_runtime::scratchpad::{ScratchpadEntry, scratchpad_text};
+use voicewin_runtime::updater::UpdateInfo;
 
 #[derive(Debug, Clone, serde::Serialize)]
 struct DownloadProgress {
@@ -52,6 +94,13 @@ struct DownloadProgress {
     total_bytes: Option<u64>,
 }
 
+#[derive(Debug, Clone, serde::Serialize)]
+struct UpdateDownloadProgress {
+    version: String,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 struct ModelCatalogEntry {
     id: String,
@@ -65,42 +114,63 @@ struct ModelCatalogEntry {
     installed: bool,
     active: bool,
     downloading: bool,
+    /// Accuracy for the configured language; `None`: the model can't transcribe it.
+    language_accuracy: Option<u8>,
+
+    #[serde(skip)]
+    path: std::path::PathBuf,
+    #[serde(skip)]
+    accuracy_table: Vec<(&'static str, u8)>,
 }
 
-// In-memory download state so Model Library can reflect "Downloading".
-static DOWNLOADING_MODELS: std::sync::OnceLock<std::sync::Mutex<std::collections::HashSet<String>>> =
-    std::sync::OnceLock::new();
+/// The installed model most accurate for a dictation language.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ModelRecommendation {
+    model_id: String,
+    title: String,
+    accuracy: u8,
+    /// Already the active model; nothing to switch.
+    active: bool,
+}
 
-const EVENT_MODEL_DOWNLOAD_PROGRESS: &str = "voicewin://model_download_progress";
-const EVENT_MODEL_DOWNLOAD_DONE: &str = "voicewin://model_download_done";
+// Set while an update installer is downloading.
+static UPDATE_DOWNLOADING: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+// Let startup settle before hitting the network.
+const STARTUP_UPDATE_CHECK_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
 
 const BUNDLED_TINY_MODEL_ID: &str = "whisper-tiny-bundled";
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 use voicewin_audio::AudioRecorder;
 
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod auto_dictate;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod debug_bundle;
+mod events;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod hotkeys;
+mod notifications;
 mod session_controller;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod wake_word;
+use events::AppEvent;
 use session_controller::{SessionController, ToggleResult};
 
 // Design-draft: pill bottom should be 80px above the monitor bottom.
 const OVERLAY_BOTTOM_OFFSET: i32 = 80;
 
-const OVERLAY_POSITION_STORE_PATH: &str = "ui_state.json";
-const OVERLAY_POSITION_STORE_KEY: &str = "overlay_position";
-
-#[cfg(any(windows, target_os = "macos"))]
-const HOTKEY_STORE_KEY: &str = "toggle_hotkey";
+// How often the click-through overlay checks for modifier+hover.
+const OVERLAY_HOVER_POLL: std::time::Duration = std::time::Duration::from_millis(100);
 
-#[cfg(windows)]
-const DEFAULT_TOGGLE_HOTKEY: &str = "Ctrl+Space";
-
-#[cfg(target_os = "macos")]
-const DEFAULT_TOGGLE_HOTKEY: &str = "Alt+Z";
+/// How often the digest job checks whether a digest period has ended.
+const DIGEST_POLL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
 
-pub const EVENT_SESSION_STATUS: &str = "voicewin://session_status";
-#[cfg(any(windows, target_os = "macos"))]
-pub const EVENT_MIC_LEVEL: &str = "voicewin://mic_level";
-pub const EVENT_TOGGLE_HOTKEY_CHANGED: &str = "voicewin://toggle_hotkey_changed";
+const OVERLAY_POSITION_STORE_PATH: &str = "ui_state.json";
+const OVERLAY_POSITION_STORE_KEY: &str = "overlay_position";
+const RECORDING_DISABLED_STORE_KEY: &str = "recording_disabled";
 
 struct AppState {
     // IMPORTANT: `tokio::sync::OnceCell` implements `Clone` by creating a NEW cell.
@@ -109,24 +179,56 @@ struct AppState {
     service: Arc<tokio::sync::OnceCell<AppService>>,
     session: SessionController,
 
-    #[cfg(any(windows, target_os = "macos"))]
-    toggle_hotkey: std::sync::Mutex<String>,
+    // Latest foreground app, kept by the watcher started in `setup`; `other_app` skips
+    // our own windows so Settings can show what was in front before it.
+    foreground: ForegroundCache,
+    foreground_watch: std::sync::Mutex<Option<ForegroundWatch>>,
+    other_app: std::sync::Mutex<Option<AppIdentity>>,
+
+    // Queued and running model downloads; the Model Library reflects them as "Downloading".
+    model_downloads: Arc<DownloadManager>,
 }
 
-fn default_config_path(app: &tauri::AppHandle) -> anyhow::Result<PathBuf> {
-    let dir = app.path().app_data_dir()?;
-    Ok(dir.join("config.json"))
+// `Some(exe_dir)` in portable mode; decided once at startup.
+static PORTABLE_EXE_DIR: std::sync::OnceLock<Option<PathBuf>> = std::sync::OnceLock::new();
+
+fn portable_exe_dir() -> Option<&'static std::path::Path> {
+    PORTABLE_EXE_DIR
+        .get_or_init(voicewin_runtime::paths::detect_portable_exe_dir)
+        .as_deref()
+}
+
+/// Every on-disk location goes through here so portable mode can redirect it.
+fn app_paths(app: &tauri::AppHandle) -> anyhow::Result<AppPaths> {
+    Ok(match portable_exe_dir() {
+        Some(dir) => AppPaths::portable(dir),
+        None => AppPaths::installed(
+            app.path().app_data_dir()?,
+            app.path().app_local_data_dir()?,
+            app.path().app_log_dir()?,
+            app.path().app_cache_dir()?,
+        ),
+    })
+}
+
+/// The store plugin resolves relative paths against the OS app data dir.
+fn ui_state_store_path() -> PathBuf {
+    match portable_exe_dir() {
+        Some(dir) => AppPaths::portable(dir)
+            .data_dir
+            .join(OVERLAY_POSITION_STORE_PATH),
+        None => PathBuf::from(OVERLAY_POSITION_STORE_PATH),
+    }
 }
 
-fn default_history_path(app: &tauri::AppHandle) -> anyhow::Result<PathBuf> {
-    let dir = app.path().app_data_dir()?;
-    Ok(dir.join("history.json"))
+fn default_config_path(app: &tauri::AppHandle) -> anyhow::Result<PathBuf> {
+    Ok(app_paths(app)?.config_path())
 }
 
 fn ensure_bootstrap_model(app: &tauri::AppHandle) -> anyhow::Result<PathBuf> {
-    let app_data_dir = app.path().app_data_dir()?;
+    let local_data_dir = app_paths(app)?.local_data_dir;
 
-    let dst = voicewin_runtime::models::installed_bootstrap_model_path(&app_data_dir);
+    let dst = voicewin_runtime::models::installed_bootstrap_model_path(&local_data_dir);
     log::info!("bootstrap model dst: {}", dst.display());
     if dst.exists() {
         // If the file is present but invalid (partial/corrupt), re-copy from bundled resources.
@@ -196,9 +298,17 @@ fn ensure_bootstrap_model(app: &tauri::AppHandle) -> anyhow::Result<PathBuf> {
 }
 
 async fn build_service(app: &tauri::AppHandle) -> anyhow::Result<AppService> {
-    let config_path = default_config_path(app)?;
+    let paths = app_paths(app)?;
+    let config_path = paths.config_path();
     log::info!("build_service config_path: {}", config_path.display());
 
+    // Older builds kept models and recovery audio in roaming app data.
+    match paths.migrate_to_local() {
+        Ok(0) => {}
+        Ok(n) => log::info!("moved {n} item(s) to {}", paths.local_data_dir.display()),
+        Err(e) => log::warn!("failed to move large files out of roaming app data: {e:#}"),
+    }
+
     // Ensure the bundled bootstrap model is available on disk.
     // The bootstrap model is required for out-of-box local STT.
     let _ = ensure_bootstrap_model(app)?;
@@ -210,39 +320,61 @@ async fn build_service(app: &tauri::AppHandle) -> anyhow::Result<AppService> {
     #[cfg(target_os = "macos")]
     let ctx: Arc<dyn voicewin_engine::traits::AppContextProvider> =
         Arc::new(voicewin_platform::macos::MacosContextProvider::default());
-    #[cfg(all(not(windows), not(target_os = "macos")))]
+    #[cfg(target_os = "linux")]
+    let ctx: Arc<dyn voicewin_engine::traits::AppContextProvider> =
+        Arc::new(voicewin_platform::linux::LinuxContextProvider::default());
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
     let ctx: Arc<dyn voicewin_engine::traits::AppContextProvider> =
         voicewin_platform::test::TestContextProvider::new(
-            voicewin_core::types::AppIdentity::new().with_process_name("linux"),
+            voicewin_core::types::AppIdentity::new().with_process_name("unknown"),
             Default::default(),
         )
         .boxed();
 
+    // With pushed focus changes the watcher's identity is current, so sessions skip the
+    // OS query; Linux polls, and a quick switch right before the hotkey could be missed.
+    let ctx: Arc<dyn voicewin_engine::traits::AppContextProvider> = if FOREGROUND_CHANGES_ARE_PUSHED
+    {
+        Arc::new(CachedContextProvider::new(
+            ctx,
+            app.state::<AppState>().foreground.clone(),
+        ))
+    } else {
+        ctx
+    };
+
     #[cfg(windows)]
     let inserter: Arc<dyn voicewin_engine::traits::Inserter> =
         Arc::new(voicewin_platform::windows::WindowsInserter::default());
     #[cfg(target_os = "macos")]
     let inserter: Arc<dyn voicewin_engine::traits::Inserter> =
         Arc::new(voicewin_platform::macos::MacosInserter::default());
-    #[cfg(all(not(windows), not(target_os = "macos")))]
+    #[cfg(target_os = "linux")]
+    let inserter: Arc<dyn voicewin_engine::traits::Inserter> =
+        Arc::new(voicewin_platform::linux::LinuxInserter::default());
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
     let inserter: Arc<dyn voicewin_engine::traits::Inserter> =
         Arc::new(voicewin_platform::test::StdoutInserter);
 
-    let svc = AppService::new(config_path, ctx, inserter);
+    let svc = AppService::new(config_path, ctx, inserter)
+        .with_local_data_dir(paths.local_data_dir.clone());
 
     // Tray/hotkey flows can start sessions without ever opening the main UI.
     // Ensure config exists (and is valid) during service initialization so
     // `run_session_with_hook` never fails due to a missing config file.
     let mut cfg = load_or_init_config(&svc, app).map_err(anyhow::Error::msg)?;
 
+    if paths.relocate_model_paths(&mut cfg) {
+        save_config_or_report(&svc, app, &cfg).map_err(anyhow::Error::msg)?;
+    }
+
     // If the config is invalid (most commonly: a stale GGUF path), do a targeted migration.
     if let Err(e) = validate_config(&cfg) {
         log::warn!("config invalid; attempting auto-migration: {e}");
 
         let changed = migrate_local_stt_model_path(&mut cfg, app).map_err(anyhow::Error::msg)?;
         if changed {
-            svc.save_config(&cfg)
-                .map_err(|e| anyhow::Error::msg(e.to_string()))?;
+            save_config_or_report(&svc, app, &cfg).map_err(anyhow::Error::msg)?;
         }
 
         // Validate again; if it still fails, surface the error instead of wiping settings.
@@ -252,19 +384,51 @@ async fn build_service(app: &tauri::AppHandle) -> anyhow::Result<AppService> {
         }
     }
 
+    set_overlay_settings(cfg.overlay.clone());
+    set_sound_cue_settings(cfg.sound_cues.clone());
+    RESPECT_DO_NOT_DISTURB.store(
+        cfg.respect_do_not_disturb,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    set_redaction_settings(&cfg.redaction);
+    set_proxy_settings(&cfg.proxy);
+    set_tls_settings(&cfg.tls);
+    voicewin_providers::runtime::set_offline_mode(cfg.offline_mode);
+    voicewin_providers::wire_log::set_settings(&cfg.provider_log);
+    voicewin_platform::clipboard_restore::set_settings(cfg.insertion.clipboard_restore.clone());
+
+    let recording_disabled = persisted_recording_disabled(app);
+    svc.set_recording_disabled(recording_disabled).await;
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    wake_word::apply(app, &cfg, recording_disabled);
+
+    match svc.pending_sessions().list() {
+        Ok(p) if !p.is_empty() => {
+            log::warn!(
+                "{} session(s) did not finish last run; recoverable from History",
+                p.len()
+            )
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("failed to scan pending sessions: {e}"),
+    }
+
     log::info!("build_service done");
     Ok(svc)
 }
 
-fn init_default_config(svc: &AppService, app: &tauri::AppHandle) -> Result<AppConfig, String> {
+fn init_default_config(
+    svc: &AppService,
+    app: &tauri::AppHandle,
+) -> Result<AppConfig, VoicewinError> {
     let mut d = voicewin_runtime::defaults::default_global_defaults();
 
     // Prefer the user-installed "preferred" model if present.
     // Otherwise, fall back to the bundled bootstrap model.
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let preferred = voicewin_runtime::models::choose_default_local_stt_model_path(&app_data_dir);
+    let local_data_dir = app_paths(&app).map_err(|e| e.to_string())?.local_data_dir;
+    let preferred = voicewin_runtime::models::choose_default_local_stt_model_path(&local_data_dir);
 
-    if preferred == voicewin_runtime::models::installed_bootstrap_model_path(&app_data_dir) {
+    if preferred == voicewin_runtime::models::installed_bootstrap_model_path(&local_data_dir) {
         let model_path = ensure_bootstrap_model(app).map_err(|e| e.to_string())?;
         d.stt_model = model_path.to_string_lossy().to_string();
     } else {
@@ -276,13 +440,66 @@ fn init_default_config(svc: &AppService, app: &tauri::AppHandle) -> Result<AppCo
         profiles: vec![],
         prompts: voicewin_runtime::defaults::default_prompt_templates(),
         llm_api_key_present: svc.get_openai_api_key_present().unwrap_or(false),
+        performance: Default::default(),
+        overlay: Default::default(),
+        sound_cues: Default::default(),
+        silent_sessions: Default::default(),
+        blocked_apps: Vec::new(),
+        redaction: Default::default(),
+        encrypt_history: false,
+        stage_timeouts: Default::default(),
+        llm_decoding: Default::default(),
+        llm_endpoint_flavor: Default::default(),
+        enhancement_guardrails: Default::default(),
+        insertion: Default::default(),
+        diarization: Default::default(),
+        spoken_replacements: Default::default(),
+        insert_commands: Default::default(),
+        snippets: vec![],
+        webhook: Default::default(),
+        digest: Default::default(),
+        updates: Default::default(),
+        pricing: Default::default(),
+        proxy: Default::default(),
+        tls: Default::default(),
+        provider_log: Default::default(),
+        offline_mode: false,
+        respect_do_not_disturb: false,
+        meeting: Default::default(),
+        wake_word: Default::default(),
+        auto_select_model_for_language: false,
+        stt_fallbacks: vec![],
+        quota_warning: Default::default(),
+        command_stt: Default::default(),
     };
 
-    svc.save_config(&cfg).map_err(|e| e.to_string())?;
+    save_config_or_report(svc, app, &cfg)?;
     Ok(cfg)
 }
 
-fn load_or_init_config(svc: &AppService, app: &tauri::AppHandle) -> Result<AppConfig, String> {
+// Save, and tell the UI when another process is holding the config lock so it can say why
+// the change did not stick.
+fn save_config_or_report(
+    svc: &AppService,
+    app: &tauri::AppHandle,
+    cfg: &AppConfig,
+) -> Result<(), VoicewinError> {
+    svc.save_config(cfg).map_err(|e| {
+        if let Some(locked) = e.downcast_ref::<ConfigLockedError>() {
+            log::error!("{locked}");
+            let _ = events::emit(
+                app,
+                AppEvent::ConfigLocked(locked.path.to_string_lossy().to_string()),
+            );
+        }
+        service_error(&e)
+    })
+}
+
+fn load_or_init_config(
+    svc: &AppService,
+    app: &tauri::AppHandle,
+) -> Result<AppConfig, VoicewinError> {
     match svc.load_config() {
         Ok(cfg) => Ok(cfg),
         Err(_) => init_default_config(svc, app),
@@ -294,10 +511,11 @@ fn migrate_local_stt_model_path(cfg: &mut AppConfig, app: &tauri::AppHandle) ->
         return Ok(false);
     }
 
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let local_data_dir = app_paths(&app).map_err(|e| e.to_string())?.local_data_dir;
 
     // If the preferred model is present and looks valid, pick it.
-    let preferred = voicewin_runtime::models::installed_preferred_local_stt_model_path(&app_data_dir);
+    let preferred =
+        voicewin_runtime::models::installed_preferred_local_stt_model_path(&local_data_dir);
     if preferred.exists()
         && voicewin_runtime::models::validate_ggml_file(&preferred, 1024 * 1024).is_ok()
     {
@@ -323,7 +541,7 @@ fn migrate_local_stt_model_path(cfg: &mut AppConfig, app: &tauri::AppHandle) ->
 
 
 fn normalize_model_path_to_models_dir(
-    app_data_dir: &std::path::Path,
+    local_data_dir: &std::path::Path,
     path: &str,
 ) -> Option<String> {
     // If the model path points anywhere under our models dir, normalize to the canonical filename.
@@ -333,19 +551,19 @@ fn normalize_model_path_to_models_dir(
         return None;
     }
 
-    let models_dir = voicewin_runtime::models::models_dir(app_data_dir);
+    let models_dir = voicewin_runtime::models::models_dir(local_data_dir);
     if let Ok(rel) = p.strip_prefix(&models_dir) {
         if rel == std::path::Path::new(voicewin_runtime::models::PREFERRED_LOCAL_STT_MODEL_FILENAME)
         {
             return Some(
-                voicewin_runtime::models::installed_preferred_local_stt_model_path(app_data_dir)
+                voicewin_runtime::models::installed_preferred_local_stt_model_path(local_data_dir)
                     .to_string_lossy()
                     .to_string(),
             );
         }
         if rel == std::path::Path::new(voicewin_runtime::models::BOOTSTRAP_MODEL_FILENAME) {
             return Some(
-                voicewin_runtime::models::installed_bootstrap_model_path(app_data_dir)
+                voicewin_runtime::models::installed_bootstrap_model_path(local_data_dir)
                     .to_string_lossy()
                     .to_string(),
             );
@@ -355,38 +573,51 @@ fn normalize_model_path_to_models_dir(
     None
 }
 
-fn validate_config(cfg: &AppConfig) -> Result<(), String> {
+fn validate_config(cfg: &AppConfig) -> Result<(), VoicewinError> {
+    let invalid = |msg: String| VoicewinError::new(ErrorKind::Config, msg);
+
     if cfg.defaults.stt_provider == "local" {
         // For local whisper, `stt_model` must be a path to a whisper.cpp GGML `.bin` model.
         let p = std::path::Path::new(&cfg.defaults.stt_model);
         if !p.exists() {
-            return Err(format!(
+            return Err(invalid(format!(
                 "local STT model does not exist: {}",
                 cfg.defaults.stt_model
-            ));
+            )));
         }
 
         // If the file is GGUF, return a clearer error (this is a common migration issue).
         if voicewin_runtime::models::has_gguf_magic(p).unwrap_or(false) {
-            return Err(format!(
+            return Err(invalid(format!(
                 "local STT model is GGUF (.gguf), but VoiceWin local STT requires whisper.cpp GGML (.bin) models: {}",
                 cfg.defaults.stt_model
-            ));
+            )));
         }
 
-        voicewin_runtime::models::validate_ggml_file(p, 1024 * 1024).map_err(|e| e.to_string())?;
+        voicewin_runtime::models::validate_ggml_file(p, 1024 * 1024)
+            .map_err(|e| invalid(e.to_string()))?;
     }
 
+    Redactor::from_settings(&cfg.redaction).map_err(|e| invalid(e.to_string()))?;
+
     Ok(())
 }
 
+// Commands report failures as `VoicewinError` so the UI can pick an action by kind.
+fn command_error(e: impl Into<anyhow::Error>) -> VoicewinError {
+    service_error(&e.into())
+}
+
 #[tauri::command]
-async fn get_config(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<AppConfig, String> {
+async fn get_config(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<AppConfig, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     let mut cfg = load_or_init_config(svc, &app)?;
     // Reflect current keyring state (not just what's stored on disk).
@@ -399,17 +630,17 @@ async fn set_config(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     mut cfg: AppConfig,
-) -> Result<(), String> {
+) -> Result<(), VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     // Normalize known model filenames in our app models dir.
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
     if let Some(normalized) =
-        normalize_model_path_to_models_dir(&app_data_dir, &cfg.defaults.stt_model)
+        normalize_model_path_to_models_dir(&local_data_dir, &cfg.defaults.stt_model)
     {
         cfg.defaults.stt_model = normalized;
     }
@@ -417,49 +648,227 @@ async fn set_config(
     // Never trust the frontend for secret state; refresh the key-present bit from the keyring.
     cfg.llm_api_key_present = svc.get_openai_api_key_present().unwrap_or(false);
 
+    // Follow a language change with the best installed model, if the user opted in.
+    if cfg.auto_select_model_for_language && cfg.defaults.stt_provider == "local" {
+        let language_changed = svc
+            .load_config()
+            .is_ok_and(|prev| prev.defaults.language != cfg.defaults.language);
+        if language_changed {
+            let entries = model_catalog_entries(&state, &cfg, &local_data_dir);
+            if let Some((best, _)) = best_installed_model(&entries, &cfg.defaults.language) {
+                if !best.active {
+                    log::info!(
+                        "language changed to {}; switching to model {}",
+                        cfg.defaults.language,
+                        best.id
+                    );
+                    cfg.defaults.stt_model = best.path.to_string_lossy().to_string();
+                }
+            }
+        }
+    }
+
     validate_config(&cfg)?;
 
-    svc.save_config(&cfg).map_err(|e| e.to_string())
+    cfg.overlay.appearance = cfg.overlay.appearance.normalized();
+
+    // Rejects a malformed proxy URL or certificate before it is persisted.
+    voicewin_providers::runtime::set_proxy(&cfg.proxy).map_err(command_error)?;
+    voicewin_providers::runtime::set_extra_root_cert(&cfg.tls)
+        .map_err(|e| VoicewinError::new(ErrorKind::Config, format!("{e:#}")))?;
+    save_config_or_report(svc, &app, &cfg)?;
+    voicewin_providers::runtime::set_offline_mode(cfg.offline_mode);
+    voicewin_providers::wire_log::set_settings(&cfg.provider_log);
+    apply_overlay_settings(&app, cfg.overlay.clone());
+    set_sound_cue_settings(cfg.sound_cues.clone());
+    RESPECT_DO_NOT_DISTURB.store(
+        cfg.respect_do_not_disturb,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    set_redaction_settings(&cfg.redaction);
+    voicewin_platform::clipboard_restore::set_settings(cfg.insertion.clipboard_restore.clone());
+    state
+        .model_downloads
+        .set_bandwidth_limit(cfg.performance.download_limit_bytes_per_sec());
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    wake_word::apply(&app, &cfg, state.session.recording_disabled());
+    Ok(())
 }
 
-#[derive(serde::Serialize)]
+#[derive(Clone, serde::Serialize)]
 struct ForegroundAppInfo {
     process_name: Option<String>,
     exe_path: Option<String>,
     window_title: Option<String>,
 }
 
+impl From<AppIdentity> for ForegroundAppInfo {
+    fn from(app: AppIdentity) -> Self {
+        Self {
+            process_name: app.process_name.map(|p| p.0),
+            exe_path: app.exe_path.map(|p| p.0),
+            window_title: app.window_title.map(|t| t.0),
+        }
+    }
+}
+
+/// The app in front and the Power Mode profile a session started now would use
+/// (`None`: global defaults).
+#[derive(Clone, serde::Serialize)]
+struct ActiveProfileInfo {
+    app: ForegroundAppInfo,
+    profile_id: Option<ProfileId>,
+    profile_name: Option<String>,
+}
+
+/// The saved config, read without building the service if it isn't up yet.
+fn current_config(app: &tauri::AppHandle) -> Option<AppConfig> {
+    match app.state::<AppState>().service.get() {
+        Some(svc) => svc.load_config().ok(),
+        None => default_config_path(app).ok().and_then(|p| {
+            voicewin_runtime::config_store::ConfigStore::at_path(p)
+                .load()
+                .ok()
+        }),
+    }
+}
+
+fn active_profile_for(app: &tauri::AppHandle, id: AppIdentity) -> ActiveProfileInfo {
+    let cfg = current_config(app);
+    // A profile switched to by hotkey wins over matching, as in `resolve_effective_config`.
+    let forced = app
+        .state::<AppState>()
+        .service
+        .get()
+        .and_then(|s| s.forced_profile());
+    let profile = cfg.as_ref().and_then(|c| match &forced {
+        Some(forced) => c.profiles.iter().find(|p| &p.id == forced && p.enabled),
+        None => c.profiles.iter().find(|p| p.matches(&id)),
+    });
+    ActiveProfileInfo {
+        profile_id: profile.map(|p| p.id.clone()),
+        profile_name: profile.map(|p| p.name.clone()),
+        app: id.into(),
+    }
+}
+
+/// Re-send `AppEvent::ForegroundAppChanged` for the last app, e.g. after a profile switch.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+fn emit_active_profile(app: &tauri::AppHandle) {
+    let last = app
+        .state::<AppState>()
+        .other_app
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone();
+    if let Some(id) = last {
+        let _ = events::emit(
+            app,
+            AppEvent::ForegroundAppChanged(active_profile_for(app, id)),
+        );
+    }
+}
+
+/// Same as the last `AppEvent::ForegroundAppChanged`, recomputed so profile edits show up.
+#[tauri::command]
+async fn get_active_profile(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Option<ActiveProfileInfo>, VoicewinError> {
+    let last = state
+        .other_app
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone();
+    Ok(last.map(|id| active_profile_for(&app, id)))
+}
+
+/// How Power Mode resolves for an app: the profile and the matcher that picked it, what
+/// the profile overrides, and the settings a session would end up with.
+#[derive(serde::Serialize)]
+struct ProfileResolution {
+    app: ForegroundAppInfo,
+    profile_id: Option<ProfileId>,
+    profile_name: Option<String>,
+    matched_by: Option<AppMatcher>,
+    overrides: Option<PowerModeOverrides>,
+    effective: EffectiveConfig,
+}
+
+/// Resolve Power Mode for the app in front (the last one before our own windows), exactly
+/// as a session starting now would.
 #[tauri::command]
-async fn capture_foreground_app(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<ForegroundAppInfo, String> {
+async fn resolve_profile_for_current_app(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<ProfileResolution, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
-    let app_id = svc
-        .get_foreground_app()
-        .await
-        .map_err(|e| e.to_string())?;
+    let other = state
+        .other_app
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone();
+    let id = match other {
+        Some(id) => id,
+        None => svc.get_foreground_app().await.map_err(command_error)?,
+    };
 
-    Ok(ForegroundAppInfo {
-        process_name: app_id.process_name.map(|p| p.0),
-        exe_path: app_id.exe_path.map(|p| p.0),
-        window_title: app_id.window_title.map(|t| t.0),
+    let cfg = svc.load_session_config().map_err(command_error)?;
+    let effective = resolve_effective_config(
+        &cfg.defaults,
+        &cfg.profiles,
+        &id,
+        &EphemeralOverrides {
+            forced_profile_id: svc.forced_profile(),
+            ..Default::default()
+        },
+    );
+    let profile = effective
+        .matched_profile_id
+        .as_ref()
+        .and_then(|pid| cfg.profiles.iter().find(|p| &p.id == pid));
+
+    Ok(ProfileResolution {
+        profile_id: effective.matched_profile_id.clone(),
+        profile_name: effective.matched_profile_name.clone(),
+        matched_by: profile.and_then(|p| p.matchers.iter().find(|m| m.matches(&id)).cloned()),
+        overrides: profile.map(|p| p.overrides.clone()),
+        effective,
+        app: id.into(),
     })
 }
 
+#[tauri::command]
+async fn capture_foreground_app(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<ForegroundAppInfo, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let app_id = svc.get_foreground_app().await.map_err(command_error)?;
+    Ok(app_id.into())
+}
+
 #[tauri::command]
 async fn cancel_recording(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<ToggleResult, String> {
+) -> Result<ToggleResult, VoicewinError> {
     log::info!("cancel_recording invoked");
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     Ok(state.session.cancel_recording(&app, svc.clone()).await)
 }
@@ -468,188 +877,493 @@ async fn cancel_recording(
 async fn toggle_recording(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<ToggleResult, String> {
+) -> Result<ToggleResult, VoicewinError> {
     log::info!("toggle_recording invoked");
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     Ok(state.session.toggle_recording(&app, svc.clone()).await)
 }
 
+/// Start or stop meeting mode: a recording that is transcribed chunk by chunk into the
+/// scratchpad (or the meeting note) until stopped.
+#[tauri::command]
+async fn toggle_meeting(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<ToggleResult, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    Ok(state.session.toggle_meeting(&app, svc.clone()).await)
+}
+
 #[tauri::command]
 async fn get_session_status(
     state: State<'_, AppState>,
-) -> Result<session_controller::SessionStatusPayload, String> {
+) -> Result<session_controller::SessionStatusPayload, VoicewinError> {
     Ok(state.session.get_status().await)
 }
 
-#[cfg(any(windows, target_os = "macos"))]
+/// Re-emit the session status (and re-show the overlay if a session needs it) for a webview
+/// that reloaded and lost its state.
+#[tauri::command]
+async fn resync_overlay(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), VoicewinError> {
+    state.session.resync_overlay(&app).await;
+    Ok(())
+}
+
+// Tray checkbox mirroring the global "microphone disabled" switch.
+struct RecordingDisabledMenuItem(CheckMenuItem<tauri::Wry>);
+
+fn persisted_recording_disabled(app: &tauri::AppHandle) -> bool {
+    app.store(ui_state_store_path())
+        .ok()
+        .and_then(|s| s.get(RECORDING_DISABLED_STORE_KEY))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+async fn apply_recording_disabled(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    disabled: bool,
+) -> Result<(), String> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(app).await })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    state
+        .session
+        .set_recording_disabled(app, svc.clone(), disabled)
+        .await;
+
+    if let Ok(store) = app.store(ui_state_store_path()) {
+        store.set(
+            RECORDING_DISABLED_STORE_KEY,
+            serde_json::Value::Bool(disabled),
+        );
+        let _ = store.save();
+    }
+
+    if let Some(item) = app.try_state::<RecordingDisabledMenuItem>() {
+        let _ = item.0.set_checked(disabled);
+    }
+
+    // Disabling the microphone silences the wake listener too.
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    if let Ok(cfg) = svc.load_config() {
+        wake_word::apply(app, &cfg, disabled);
+    }
+
+    Ok(())
+}
+
+/// Flip the wake-phrase switch from the tray and start or stop listening.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+async fn toggle_wake_word(app: &tauri::AppHandle) -> Result<(), VoicewinError> {
+    let state = app.state::<AppState>();
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(app).await })
+        .await
+        .map_err(command_error)?;
+
+    let mut cfg = load_or_init_config(svc, app)?;
+    cfg.wake_word.enabled = !cfg.wake_word.enabled;
+    save_config_or_report(svc, app, &cfg)?;
+    wake_word::apply(app, &cfg, state.session.recording_disabled());
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_recording_disabled(state: State<'_, AppState>) -> Result<bool, VoicewinError> {
+    Ok(state.session.recording_disabled())
+}
+
+#[tauri::command]
+async fn set_recording_disabled(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    disabled: bool,
+) -> Result<bool, VoicewinError> {
+    apply_recording_disabled(&app, &state, disabled).await?;
+    Ok(disabled)
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 #[derive(serde::Serialize)]
 struct HotkeyState {
     hotkey: String,
     error: Option<String>,
 }
 
-#[cfg(any(windows, target_os = "macos"))]
-fn current_hotkey(state: &State<'_, AppState>) -> String {
-    state
-        .toggle_hotkey
-        .lock()
-        .unwrap_or_else(|p| p.into_inner())
-        .clone()
-}
-
-#[cfg(any(windows, target_os = "macos"))]
-fn set_hotkey_in_state(state: &State<'_, AppState>, value: String) {
-    let mut guard = state
-        .toggle_hotkey
-        .lock()
-        .unwrap_or_else(|p| p.into_inner());
-    *guard = value;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+fn current_hotkey() -> String {
+    hotkeys::shortcut(HotkeyAction::Toggle).unwrap_or_default()
 }
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 #[tauri::command]
-async fn get_toggle_hotkey(state: State<'_, AppState>) -> Result<HotkeyState, String> {
+async fn get_toggle_hotkey() -> Result<HotkeyState, VoicewinError> {
     Ok(HotkeyState {
-        hotkey: current_hotkey(&state),
+        hotkey: current_hotkey(),
         error: None,
     })
 }
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 #[tauri::command]
 async fn set_toggle_hotkey(
-    state: State<'_, AppState>,
     app: tauri::AppHandle,
     hotkey: String,
-) -> Result<HotkeyState, String> {
-    let prev = current_hotkey(&state);
-
-    // No-op if unchanged.
-    if prev == hotkey {
-        return Ok(HotkeyState {
-            hotkey,
-            error: None,
-        });
-    }
-
-    // Best-effort: unregister previous hotkey.
-    let _ = app.global_shortcut().unregister(prev.as_str());
-
-    // Try registering the new hotkey.
-    let res = app.global_shortcut().on_shortcut(hotkey.as_str(), {
-        let session = state.session.clone();
-        let svc_cell = state.service.clone();
-        move |app, _shortcut, event| {
-            if event.state != ShortcutState::Pressed {
-                return;
-            }
-
-            let app = app.clone();
-            let session = session.clone();
-            let svc_cell = svc_cell.clone();
-
-            tauri::async_runtime::spawn(async move {
-                let svc = match svc_cell
-                    .get_or_try_init(|| async { build_service(&app).await })
-                    .await
-                {
-                    Ok(s) => s,
-                    Err(_) => return,
-                };
-
-                let _ = session.toggle_recording(&app, svc.clone()).await;
-            });
-        }
-    });
+) -> Result<HotkeyState, VoicewinError> {
+    let error = hotkeys::set(&app, HotkeyAction::Toggle, &hotkey).err();
+    Ok(HotkeyState {
+        hotkey: current_hotkey(),
+        error,
+    })
+}
 
-    if let Err(e) = res {
-        // Restore previous hotkey registration (best-effort).
-        let _ = app.global_shortcut().on_shortcut(prev.as_str(), {
-            let session = state.session.clone();
-            let svc_cell = state.service.clone();
-            move |app, _shortcut, event| {
-                if event.state != ShortcutState::Pressed {
-                    return;
-                }
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+#[tauri::command]
+async fn list_hotkeys() -> Result<Vec<hotkeys::HotkeyBinding>, VoicewinError> {
+    Ok(hotkeys::list())
+}
 
-                let app = app.clone();
-                let session = session.clone();
-                let svc_cell = svc_cell.clone();
-
-                tauri::async_runtime::spawn(async move {
-                    let svc = match svc_cell
-                        .get_or_try_init(|| async { build_service(&app).await })
-                        .await
-                    {
-                        Ok(s) => s,
-                        Err(_) => return,
-                    };
-
-                    let _ = session.toggle_recording(&app, svc.clone()).await;
-                });
-            }
-        });
+/// Bind a global shortcut to an action; an empty `shortcut` unbinds it.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+#[tauri::command]
+async fn set_hotkey(
+    app: tauri::AppHandle,
+    action: HotkeyAction,
+    shortcut: String,
+) -> Result<Vec<hotkeys::HotkeyBinding>, VoicewinError> {
+    hotkeys::set(&app, action, &shortcut).map_err(|e| VoicewinError::new(ErrorKind::Config, e))?;
+    Ok(hotkeys::list())
+}
 
-        return Ok(HotkeyState {
-            hotkey: prev,
-            error: Some(format!("failed to register hotkey: {e}")),
-        });
-    }
 
-    set_hotkey_in_state(&state, hotkey.clone());
+#[tauri::command]
+async fn get_history(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    filter: Option<voicewin_runtime::history::HistoryFilter>,
+) -> Result<Vec<voicewin_runtime::history::HistoryEntry>, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .query(&filter.unwrap_or_default())
+        .map_err(command_error)
+}
 
-    if let Ok(store) = app.store(OVERLAY_POSITION_STORE_PATH) {
-        store.set(HOTKEY_STORE_KEY, serde_json::Value::String(hotkey.clone()));
-        let _ = store.save();
-    }
+/// Write History entries in `range` to `path`; returns how many were exported.
+#[tauri::command]
+async fn export_history(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    format: voicewin_runtime::history::ExportFormat,
+    range: Option<voicewin_runtime::history::ExportRange>,
+    path: String,
+) -> Result<usize, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .export(
+            format,
+            range.unwrap_or_default(),
+            std::path::Path::new(&path),
+        )
+        .map_err(command_error)
+}
 
-    let _ = app.emit(EVENT_TOGGLE_HOTKEY_CHANGED, hotkey.clone());
+#[tauri::command]
+async fn get_history_tags(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .tags()
+        .map_err(command_error)
+}
 
-    Ok(HotkeyState {
-        hotkey,
-        error: None,
-    })
+#[tauri::command]
+async fn set_history_entry_tags(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    ts_unix_ms: i64,
+    text: String,
+    tags: Vec<String>,
+) -> Result<bool, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .set_tags(ts_unix_ms, &text, tags)
+        .map_err(command_error)
 }
 
+#[tauri::command]
+async fn set_history_entry_pinned(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    ts_unix_ms: i64,
+    text: String,
+    pinned: bool,
+) -> Result<bool, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .set_pinned(ts_unix_ms, &text, pinned)
+        .map_err(command_error)
+}
 
 
 #[tauri::command]
-async fn get_history(
+async fn clear_history(
+    state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<Vec<voicewin_runtime::history::HistoryEntry>, String> {
-    let path = default_history_path(&app).map_err(|e| e.to_string())?;
-    let store = voicewin_runtime::history::HistoryStore::at_path(path);
-    store.load().map_err(|e| e.to_string())
+) -> Result<(), VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .clear()
+        .map_err(command_error)
 }
 
-
+/// Collapse retried dictations (same text moments apart) in the existing History file.
+/// Returns how many entries were merged away.
 #[tauri::command]
-async fn clear_history(app: tauri::AppHandle) -> Result<(), String> {
-    let path = default_history_path(&app).map_err(|e| e.to_string())?;
-    let store = voicewin_runtime::history::HistoryStore::at_path(path);
-    store.clear().map_err(|e| e.to_string())
+async fn compact_history(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<usize, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .compact()
+        .map_err(command_error)
 }
 
 #[tauri::command]
-async fn delete_history_entry(app: tauri::AppHandle, ts_unix_ms: i64, text: String) -> Result<bool, String> {
-    let path = default_history_path(&app).map_err(|e| e.to_string())?;
-    let store = voicewin_runtime::history::HistoryStore::at_path(path);
-    store
+async fn delete_history_entry(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    ts_unix_ms: i64,
+    text: String,
+) -> Result<bool, VoicewinError> {
+    history_store(&state, &app)
+        .await?
         .delete_entry(ts_unix_ms, &text)
-        .map_err(|e| e.to_string())
+        .map_err(command_error)
 }
 
-#[derive(serde::Serialize)]
-struct ModelStatus {
-    pub bootstrap_ok: bool,
-    pub bootstrap_path: String,
-    pub preferred_ok: bool,
-    pub preferred_path: String,
+/// Take an entry back out of the History trash.
+#[tauri::command]
+async fn restore_history_entry(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    ts_unix_ms: i64,
+    text: String,
+) -> Result<bool, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .restore_entry(ts_unix_ms, &text)
+        .map_err(command_error)
+}
+
+/// Permanently remove the History trash; returns how many entries were removed.
+#[tauri::command]
+async fn empty_history_trash(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<usize, VoicewinError> {
+    history_store(&state, &app)
+        .await?
+        .empty_trash()
+        .map_err(command_error)
+}
+
+// Goes through the service so History encryption settings apply.
+async fn history_store(
+    state: &State<'_, AppState>,
+    app: &tauri::AppHandle,
+) -> Result<voicewin_runtime::history::HistoryStore, String> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(app).await })
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(svc.history_store())
+}
+
+/// Profile drafts for frequently used apps that have no Power Mode profile yet.
+#[tauri::command]
+async fn suggest_profiles(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<voicewin_core::power_mode::ProfileSuggestion>, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.suggest_profiles().map_err(command_error)
+}
+
+#[tauri::command]
+async fn get_pending_session_count(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<usize, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.pending_sessions()
+        .list()
+        .map(|p| p.len())
+        .map_err(command_error)
+}
+
+#[tauri::command]
+async fn recover_pending_sessions(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<voicewin_runtime::history::HistoryEntry>, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.recover_pending_sessions().await.map_err(command_error)
+}
+
+/// Transcribe a dropped or picked audio file (WAV/MP3/M4A/OGG) into History.
+#[tauri::command]
+async fn transcribe_file(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<voicewin_runtime::history::HistoryEntry, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.transcribe_file(std::path::Path::new(&path))
+        .await
+        .map_err(command_error)
+}
+
+/// Re-run enhancement with `prompt_id` over the History entries matching `filter`, emitting
+/// progress as entries finish.
+#[tauri::command]
+async fn bulk_enhance_history(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    filter: voicewin_runtime::history::HistoryFilter,
+    prompt_id: voicewin_core::types::PromptId,
+) -> Result<voicewin_appcore::service::BulkEnhanceProgress, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.bulk_enhance_history(&filter, &prompt_id, |p| {
+        let _ = events::emit(&app, AppEvent::BulkEnhanceProgress(p));
+    })
+    .await
+    .map_err(command_error)
+}
+
+/// Run `transcript` through two prompts side by side; nothing is inserted or saved.
+#[tauri::command]
+async fn compare_prompts(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    transcript: String,
+    prompt_a: voicewin_core::types::PromptId,
+    prompt_b: voicewin_core::types::PromptId,
+) -> Result<voicewin_appcore::service::PromptComparison, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.compare_prompts(&transcript, &prompt_a, &prompt_b)
+        .await
+        .map_err(command_error)
+}
+
+/// Estimated cloud spend inside `range` (all time when omitted).
+#[tauri::command]
+async fn get_cost_estimate(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    range: Option<voicewin_runtime::history::ExportRange>,
+) -> Result<voicewin_core::usage::CostEstimate, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.get_cost_estimate(&range.unwrap_or_default())
+        .map_err(command_error)
+}
+
+/// Per-stage timings of the most recent sessions, newest first.
+#[tauri::command]
+async fn get_session_traces(limit: Option<usize>) -> Vec<voicewin_appcore::traces::SessionTrace> {
+    voicewin_appcore::traces::recent_session_traces(
+        limit.unwrap_or(voicewin_appcore::traces::MAX_SESSION_TRACES),
+    )
+}
+
+/// Provider requests and responses captured while `provider_log` is on, oldest first.
+#[tauri::command]
+async fn get_provider_logs() -> Vec<voicewin_providers::wire_log::WireLogEntry> {
+    voicewin_providers::wire_log::entries()
+}
+
+/// Zip up logs and diagnostics for a bug report; returns the bundle path.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+#[tauri::command]
+async fn export_debug_bundle(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<String, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let path = debug_bundle::export(&app, svc, &state.session)
+        .await
+        .map_err(command_error)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[derive(serde::Serialize)]
+struct ModelStatus {
+    pub bootstrap_ok: bool,
+    pub bootstrap_path: String,
+    pub preferred_ok: bool,
+    pub preferred_path: String,
 }
 
 #[derive(serde::Serialize)]
@@ -658,6 +1372,8 @@ struct ProviderStatus {
     pub openai_api_key_error: Option<String>,
     pub elevenlabs_api_key_present: bool,
     pub elevenlabs_api_key_error: Option<String>,
+    pub webhook_token_present: bool,
+    pub webhook_token_error: Option<String>,
 }
 
 fn provider_status(svc: &AppService) -> ProviderStatus {
@@ -671,21 +1387,31 @@ fn provider_status(svc: &AppService) -> ProviderStatus {
         Err(e) => (false, Some(e.to_string())),
     };
 
+    let (webhook_token_present, webhook_token_error) = match svc.get_webhook_token_present() {
+        Ok(v) => (v, None),
+        Err(e) => (false, Some(e.to_string())),
+    };
+
     ProviderStatus {
         openai_api_key_present,
         openai_api_key_error,
         elevenlabs_api_key_present,
         elevenlabs_api_key_error,
+        webhook_token_present,
+        webhook_token_error,
     }
 }
 
 #[tauri::command]
-async fn get_provider_status(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<ProviderStatus, String> {
+async fn get_provider_status(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<ProviderStatus, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     Ok(ProviderStatus {
         ..provider_status(&svc)
@@ -697,18 +1423,18 @@ async fn set_openai_api_key(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     api_key: String,
-) -> Result<ProviderStatus, String> {
+) -> Result<ProviderStatus, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     let trimmed = api_key.trim();
     if trimmed.is_empty() {
-        svc.clear_openai_api_key().map_err(|e| e.to_string())?;
+        svc.clear_openai_api_key().map_err(command_error)?;
     } else {
-        svc.set_openai_api_key(trimmed).map_err(|e| e.to_string())?;
+        svc.set_openai_api_key(trimmed).map_err(command_error)?;
     }
 
     Ok(provider_status(&svc))
@@ -718,14 +1444,14 @@ async fn set_openai_api_key(
 async fn clear_openai_api_key(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<ProviderStatus, String> {
+) -> Result<ProviderStatus, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
-    svc.clear_openai_api_key().map_err(|e| e.to_string())?;
+    svc.clear_openai_api_key().map_err(command_error)?;
     Ok(provider_status(&svc))
 }
 
@@ -734,19 +1460,18 @@ async fn set_elevenlabs_api_key(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     api_key: String,
-) -> Result<ProviderStatus, String> {
+) -> Result<ProviderStatus, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     let trimmed = api_key.trim();
     if trimmed.is_empty() {
-        svc.clear_elevenlabs_api_key().map_err(|e| e.to_string())?;
+        svc.clear_elevenlabs_api_key().map_err(command_error)?;
     } else {
-        svc.set_elevenlabs_api_key(trimmed)
-            .map_err(|e| e.to_string())?;
+        svc.set_elevenlabs_api_key(trimmed).map_err(command_error)?;
     }
 
     Ok(provider_status(&svc))
@@ -756,30 +1481,103 @@ async fn set_elevenlabs_api_key(
 async fn clear_elevenlabs_api_key(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<ProviderStatus, String> {
+) -> Result<ProviderStatus, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
+
+    svc.clear_elevenlabs_api_key().map_err(command_error)?;
+    Ok(provider_status(&svc))
+}
+
+/// Check an LLM key (or the stored one) against the configured endpoint without saving it.
+#[tauri::command]
+async fn validate_llm_key(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    api_key: Option<String>,
+) -> Result<voicewin_appcore::service::KeyValidation, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.validate_llm_key(api_key.as_deref())
+        .await
+        .map_err(command_error)
+}
+
+/// Check an ElevenLabs key (or the stored one) and report its plan and credit usage.
+#[tauri::command]
+async fn validate_elevenlabs_key(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    api_key: Option<String>,
+) -> Result<voicewin_appcore::service::KeyValidation, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    svc.validate_elevenlabs_key(api_key.as_deref())
+        .await
+        .map_err(command_error)
+}
+
+#[tauri::command]
+async fn set_webhook_token(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    token: String,
+) -> Result<ProviderStatus, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let trimmed = token.trim();
+    if trimmed.is_empty() {
+        svc.clear_webhook_token().map_err(command_error)?;
+    } else {
+        svc.set_webhook_token(trimmed).map_err(command_error)?;
+    }
+
+    Ok(provider_status(&svc))
+}
+
+#[tauri::command]
+async fn clear_webhook_token(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<ProviderStatus, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
 
-    svc.clear_elevenlabs_api_key().map_err(|e| e.to_string())?;
+    svc.clear_webhook_token().map_err(command_error)?;
     Ok(provider_status(&svc))
 }
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 #[tauri::command]
-async fn list_microphones() -> Result<Vec<String>, String> {
-    AudioRecorder::list_input_device_names().map_err(|e| e.to_string())
+async fn list_microphones() -> Result<Vec<String>, VoicewinError> {
+    AudioRecorder::list_input_device_names().map_err(command_error)
 }
 
 #[tauri::command]
-async fn get_model_status(app: tauri::AppHandle) -> Result<ModelStatus, String> {
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+async fn get_model_status(app: tauri::AppHandle) -> Result<ModelStatus, VoicewinError> {
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
 
-    let bootstrap_path = voicewin_runtime::models::installed_bootstrap_model_path(&app_data_dir);
+    let bootstrap_path = voicewin_runtime::models::installed_bootstrap_model_path(&local_data_dir);
     let preferred_path =
-        voicewin_runtime::models::installed_preferred_local_stt_model_path(&app_data_dir);
+        voicewin_runtime::models::installed_preferred_local_stt_model_path(&local_data_dir);
 
     let bootstrap_ok = voicewin_runtime::models::validate_bootstrap_model(&bootstrap_path).is_ok();
     let preferred_ok =
@@ -799,19 +1597,29 @@ async fn get_model_status(app: tauri::AppHandle) -> Result<ModelStatus, String>
 async fn list_models(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<Vec<ModelCatalogEntry>, String> {
+) -> Result<Vec<ModelCatalogEntry>, VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     let cfg = load_or_init_config(svc, &app)?;
 
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let models_dir = voicewin_runtime::models::models_dir(&app_data_dir);
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
+    Ok(model_catalog_entries(&state, &cfg, &local_data_dir))
+}
+
+/// The bundled model followed by the download catalog, as seen from `cfg`.
+fn model_catalog_entries(
+    state: &AppState,
+    cfg: &AppConfig,
+    local_data_dir: &std::path::Path,
+) -> Vec<ModelCatalogEntry> {
+    let models_dir = voicewin_runtime::models::models_dir(local_data_dir);
 
-    let active_path = std::path::PathBuf::from(cfg.defaults.stt_model);
+    let active_path = std::path::PathBuf::from(&cfg.defaults.stt_model);
+    let language = cfg.defaults.language.as_str();
 
     fn paths_equivalent(a: &std::path::Path, b: &std::path::Path) -> bool {
         if a == b {
@@ -844,13 +1652,13 @@ async fn list_models(
     let mut out = Vec::new();
 
     // Include the bundled bootstrap model as a selectable entry.
-    let bootstrap_path = voicewin_runtime::models::installed_bootstrap_model_path(&app_data_dir);
+    let bootstrap_path = voicewin_runtime::models::installed_bootstrap_model_path(local_data_dir);
     let bootstrap_size = std::fs::metadata(&bootstrap_path).map(|m| m.len()).ok();
     let bootstrap_installed = voicewin_runtime::models::validate_ggml_file(&bootstrap_path, 1024 * 1024).is_ok();
     let bootstrap_active = paths_equivalent(&active_path, &bootstrap_path);
     out.push(ModelCatalogEntry {
         id: BUNDLED_TINY_MODEL_ID.into(),
-        title: "Whisper Tiny (Bundled)".into(),
+        title: voicewin_runtime::models::BOOTSTRAP_MODEL_TITLE.into(),
         recommended: false,
         filename: voicewin_runtime::models::BOOTSTRAP_MODEL_FILENAME.into(),
         size_bytes: bootstrap_size,
@@ -859,6 +1667,12 @@ async fn list_models(
         installed: bootstrap_installed,
         active: bootstrap_installed && bootstrap_active,
         downloading: false,
+        language_accuracy: voicewin_runtime::models::language_accuracy(
+            voicewin_runtime::models::BOOTSTRAP_MODEL_LANGUAGE_ACCURACY,
+            language,
+        ),
+        path: bootstrap_path,
+        accuracy_table: voicewin_runtime::models::BOOTSTRAP_MODEL_LANGUAGE_ACCURACY.to_vec(),
     });
 
     for spec in voicewin_runtime::models::whisper_catalog() {
@@ -866,12 +1680,7 @@ async fn list_models(
         let installed = path.exists();
         let active = installed && paths_equivalent(&active_path, &path);
 
-        let downloading = DOWNLOADING_MODELS
-            .get_or_init(|| std::sync::Mutex::new(std::collections::HashSet::new()))
-            .lock()
-            .ok()
-            .map(|g| g.contains(&spec.id))
-            .unwrap_or(false);
+        let downloading = state.model_downloads.contains(&spec.id);
 
         out.push(ModelCatalogEntry {
             id: spec.id,
@@ -884,10 +1693,67 @@ async fn list_models(
             installed,
             active,
             downloading,
+            language_accuracy: voicewin_runtime::models::language_accuracy(
+                &spec.language_accuracy,
+                language,
+            ),
+            path,
+            accuracy_table: spec.language_accuracy,
         });
     }
 
-    Ok(out)
+    out
+}
+
+/// Every STT provider with its capabilities and selectable models, for the settings page.
+#[tauri::command]
+async fn list_stt_providers(
+    app: tauri::AppHandle,
+) -> Result<Vec<voicewin_runtime::stt::registry::SttProviderInfo>, VoicewinError> {
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
+    Ok(voicewin_runtime::stt::registry::stt_providers(
+        &local_data_dir,
+    ))
+}
+
+/// The installed entry most accurate for `language`, with its score.
+fn best_installed_model<'a>(
+    entries: &'a [ModelCatalogEntry],
+    language: &str,
+) -> Option<(&'a ModelCatalogEntry, u8)> {
+    voicewin_runtime::models::best_model_for_language(
+        entries.iter().filter(|e| e.installed),
+        language,
+        |e| e.accuracy_table.as_slice(),
+    )
+}
+
+/// The installed local model to suggest for `language` (`None`: no installed model can
+/// transcribe it).
+#[tauri::command]
+async fn recommend_model_for_language(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    language: String,
+) -> Result<Option<ModelRecommendation>, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let cfg = load_or_init_config(svc, &app)?;
+
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
+    let entries = model_catalog_entries(&state, &cfg, &local_data_dir);
+    Ok(
+        best_installed_model(&entries, &language).map(|(e, accuracy)| ModelRecommendation {
+            model_id: e.id.clone(),
+            title: e.title.clone(),
+            accuracy,
+            active: e.active,
+        }),
+    )
 }
 
 #[tauri::command]
@@ -895,25 +1761,25 @@ async fn set_active_model(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     model_id: String,
-) -> Result<(), String> {
+) -> Result<(), VoicewinError> {
     let svc = state
         .service
         .get_or_try_init(|| async { build_service(&app).await })
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(command_error)?;
 
     let mut cfg = load_or_init_config(svc, &app)?;
 
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let models_dir = voicewin_runtime::models::models_dir(&app_data_dir);
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
+    let models_dir = voicewin_runtime::models::models_dir(&local_data_dir);
 
     if model_id == BUNDLED_TINY_MODEL_ID {
         // Ensure the bundled model exists; if the user deleted it, restore from app resources.
-        let path = ensure_bootstrap_model(&app).map_err(|e| e.to_string())?;
+        let path = ensure_bootstrap_model(&app).map_err(command_error)?;
         cfg.defaults.stt_provider = "local".into();
         cfg.defaults.stt_model = path.to_string_lossy().to_string();
         validate_config(&cfg)?;
-        return svc.save_config(&cfg).map_err(|e| e.to_string());
+        return save_config_or_report(svc, &app, &cfg);
     }
 
     let spec = voicewin_runtime::models::whisper_catalog()
@@ -923,41 +1789,55 @@ async fn set_active_model(
 
     let path = models_dir.join(&spec.filename);
     if !path.exists() {
-        return Err("model not installed".into());
+        return Err(VoicewinError::new(ErrorKind::Config, "model not installed"));
     }
 
     cfg.defaults.stt_provider = "local".into();
     cfg.defaults.stt_model = path.to_string_lossy().to_string();
 
     validate_config(&cfg)?;
-    svc.save_config(&cfg).map_err(|e| e.to_string())
+    save_config_or_report(svc, &app, &cfg)
+}
+
+/// Queued and running model downloads, for the Model Library.
+#[tauri::command]
+async fn get_download_queue(
+    state: State<'_, AppState>,
+) -> Result<voicewin_runtime::models::DownloadQueueStatus, VoicewinError> {
+    Ok(state.model_downloads.status())
 }
 
 #[tauri::command]
-async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), String> {
+async fn download_model(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    model_id: String,
+) -> Result<(), VoicewinError> {
     // NOTE: this uses network access (HuggingFace).
     log::info!("download_model start: {model_id}");
-    let downloading = DOWNLOADING_MODELS.get_or_init(|| {
-        std::sync::Mutex::new(std::collections::HashSet::new())
-    });
-
-    {
-        let mut guard = downloading.lock().map_err(|_| "download lock poisoned".to_string())?;
-        if guard.contains(&model_id) {
-            return Err("model is already downloading".into());
-        }
-        guard.insert(model_id.clone());
+    if let Some(cfg) = current_config(&app) {
+        state
+            .model_downloads
+            .set_bandwidth_limit(cfg.performance.download_limit_bytes_per_sec());
     }
+    let mut ticket = state
+        .model_downloads
+        .enqueue(&model_id)
+        .map_err(|e| e.to_string())?;
 
     let result = async {
-        let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-        let models_dir = voicewin_runtime::models::models_dir(&app_data_dir);
+        // Wait for a free slot; the Model Library shows the model as downloading meanwhile.
+        ticket.start().await;
+
+        let local_data_dir = app_paths(&app).map_err(|e| e.to_string())?.local_data_dir;
+        let models_dir = voicewin_runtime::models::models_dir(&local_data_dir);
         voicewin_runtime::models::ensure_dir(&models_dir).map_err(|e| e.to_string())?;
 
         let spec = voicewin_runtime::models::whisper_catalog()
             .into_iter()
             .find(|s| s.id == model_id)
             .ok_or_else(|| "unknown model id".to_string())?;
+        voicewin_providers::runtime::ensure_online(&spec.url).map_err(|e| e.to_string())?;
 
         let dst = models_dir.join(&spec.filename);
         log::info!("download_model dst: {}", dst.display());
@@ -967,6 +1847,12 @@ async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), S
         }
         let expected_sha = spec.sha256.to_lowercase();
 
+        // Fail before fetching anything if the model can't fit.
+        if let Some(size) = spec.size_bytes {
+            voicewin_runtime::disk_space::ensure_available(&models_dir, size)
+                .map_err(|e| format!("{e:#}"))?;
+        }
+
         // Stream download into a temp file.
         let tmp = dst.with_extension("download");
         if tmp.exists() {
@@ -975,7 +1861,9 @@ async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), S
 
         let mut f = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
 
-        let client = reqwest::Client::new();
+        let client = voicewin_providers::runtime::http_client_builder()
+            .build()
+            .map_err(|e| e.to_string())?;
 
         let mut last_err: Option<String> = None;
         let mut used_url = spec.url.clone();
@@ -1016,6 +1904,12 @@ async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), S
 
         let total = resp.content_length();
         log::info!("download_model content_length: {:?}", total);
+        if let (None, Some(size)) = (spec.size_bytes, total) {
+            if let Err(e) = voicewin_runtime::disk_space::ensure_available(&models_dir, size) {
+                let _ = std::fs::remove_file(&tmp);
+                return Err(format!("{e:#}"));
+            }
+        }
         let mut stream = resp.bytes_stream();
 
         use futures_util::StreamExt;
@@ -1034,7 +1928,6 @@ async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), S
                 }
             };
 
-            downloaded += chunk.len() as u64;
             hasher.update(&chunk);
 
             if let Err(e) = std::io::Write::write_all(&mut f, &chunk) {
@@ -1042,28 +1935,31 @@ async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), S
                 return Err(e.to_string());
             }
 
+            // Also waits out the shared bandwidth cap.
+            downloaded = ticket.transferred(chunk.len() as u64, total).await;
+
             // Throttle progress events to avoid spamming the UI.
             if last_emit.elapsed() >= std::time::Duration::from_millis(120) {
                 last_emit = std::time::Instant::now();
-                let _ = app.emit(
-                    EVENT_MODEL_DOWNLOAD_PROGRESS,
-                    DownloadProgress {
+                let _ = events::emit(
+                    &app,
+                    AppEvent::ModelDownloadProgress(DownloadProgress {
                         model_id: model_id.clone(),
                         downloaded_bytes: downloaded,
                         total_bytes: total,
-                    },
+                    }),
                 );
             }
         }
 
         // Final progress emit.
-        let _ = app.emit(
-            EVENT_MODEL_DOWNLOAD_PROGRESS,
-            DownloadProgress {
+        let _ = events::emit(
+            &app,
+            AppEvent::ModelDownloadProgress(DownloadProgress {
                 model_id: model_id.clone(),
                 downloaded_bytes: downloaded,
                 total_bytes: total,
-            },
+            }),
         );
 
         f.sync_all().ok();
@@ -1085,90 +1981,506 @@ async fn download_model(app: tauri::AppHandle, model_id: String) -> Result<(), S
         // Replace into final destination.
         voicewin_runtime::models::replace_file(&tmp, &dst).map_err(|e| e.to_string())?;
 
-        let _ = app.emit(EVENT_MODEL_DOWNLOAD_DONE, model_id.clone());
+        let _ = events::emit(&app, AppEvent::ModelDownloadDone(model_id.clone()));
         Ok(())
     }
     .await;
 
-    // Clear downloading state.
-    let _ = downloading
-        .lock()
-        .map(|mut g| {
-            g.remove(&model_id);
-        })
-        .map_err(|_| "download lock poisoned".to_string());
+    // Frees the slot for the next queued download.
+    drop(ticket);
 
     match &result {
         Ok(()) => log::info!("download_model done: {model_id}"),
         Err(e) => log::error!("download_model failed: {model_id}: {e}"),
     }
 
-    result
+    // Mostly network trouble; trying again is the usual fix.
+    result.map_err(|e| VoicewinError::from(e).with_retryable(true))
 }
 
-#[tauri::command]
-async fn overlay_drag_begin(_app: tauri::AppHandle) -> Result<(), String> {
-    // Mark that subsequent window moved events are user-driven.
-    let flag = OVERLAY_IS_DRAGGING.get_or_init(|| std::sync::atomic::AtomicBool::new(false));
-    flag.store(true, std::sync::atomic::Ordering::SeqCst);
-    Ok(())
+fn app_version(app: &tauri::AppHandle) -> String {
+    app.package_info().version.to_string()
 }
 
 #[tauri::command]
-async fn overlay_drag_end(app: tauri::AppHandle) -> Result<(), String> {
-    let flag = OVERLAY_IS_DRAGGING.get_or_init(|| std::sync::atomic::AtomicBool::new(false));
-    flag.store(false, std::sync::atomic::Ordering::SeqCst);
-
-    // Persist current position at the end of the drag.
-    if let Some(w) = app.get_webview_window("recording_overlay") {
-        if let Ok(pos) = w.outer_position() {
-            if let Ok(store) = app.store(OVERLAY_POSITION_STORE_PATH) {
-                let payload = OverlayMovedPayload { x: pos.x, y: pos.y };
-                if let Ok(v) = serde_json::to_value(&payload) {
-                    store.set(OVERLAY_POSITION_STORE_KEY, v);
-                    let _ = store.save();
-                }
-            }
-        }
-    }
+async fn check_for_updates(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Option<UpdateInfo>, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+    let cfg = load_or_init_config(svc, &app)?;
 
-    Ok(())
+    voicewin_runtime::updater::check_for_updates(&app_version(&app), cfg.updates.channel)
+        .await
+        .map_err(command_error)
 }
 
+/// Download the newest installer for the configured channel and launch it.
+///
+/// The release is looked up again here rather than taken from the UI, so only installers
+/// published on the project's releases page are ever run.
 #[tauri::command]
-async fn overlay_set_size(app: tauri::AppHandle, width: f64, height: f64) -> Result<(), String> {
-    if let Some(w) = app.get_webview_window("recording_overlay") {
-        // JS measures in CSS pixels (logical units), so resize in logical units.
-        let _ = w.set_size(tauri::Size::Logical(tauri::LogicalSize::new(width, height)));
+async fn install_update(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), VoicewinError> {
+    use std::sync::atomic::Ordering;
+
+    let update = check_for_updates(state, app.clone())
+        .await?
+        .ok_or_else(|| "VoiceWin is up to date".to_string())?;
+    let asset = update.asset.clone().ok_or_else(|| {
+        format!(
+            "no verified installer for this platform; download it from {}",
+            update.release_url
+        )
+    })?;
 
-        // If the user has not dragged the overlay (no stored position), keep it centered after
-        // fit-content resizes so it doesn't drift.
-        let has_saved_position = app
-            .store(OVERLAY_POSITION_STORE_PATH)
-            .ok()
-            .and_then(|s| s.get(OVERLAY_POSITION_STORE_KEY))
-            .is_some();
+    if UPDATE_DOWNLOADING.swap(true, Ordering::SeqCst) {
+        return Err("update is already downloading".into());
+    }
 
-        if !has_saved_position {
-            if let Ok(Some(monitor)) = w.current_monitor().or_else(|_| w.primary_monitor()) {
-                let work = monitor.work_area();
-                if let Ok(size) = w.outer_size() {
-                    let x = work.position.x + (work.size.width as i32 / 2) - (size.width as i32 / 2);
+    let result = async {
+        let dir = app_paths(&app)
+            .map_err(|e| e.to_string())?
+            .cache_dir
+            .join("updates");
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let dst = dir.join(&asset.name);
+        let tmp = dst.with_extension("download");
+        let _ = std::fs::remove_file(&tmp);
+
+        log::info!(
+            "install_update: downloading {} from {}",
+            update.version,
+            asset.url
+        );
+        let resp = voicewin_providers::runtime::http_client_builder()
+            .build()
+            .map_err(|e| e.to_string())?
+            .get(&asset.url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!(
+                "download failed: status={}",
+                resp.status().as_u16()
+            ));
+        }
+
+        let total = resp
+            .content_length()
+            .or(Some(asset.size).filter(|s| *s > 0));
+        let mut stream = resp.bytes_stream();
+        let mut f = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
+
+        use futures_util::StreamExt;
+        use sha2::Digest;
+
+        let mut hasher = sha2::Sha256::new();
+        let mut downloaded: u64 = 0;
+        let mut last_emit = std::time::Instant::now();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = match chunk {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = std::fs::remove_file(&tmp);
+                    return Err(e.to_string());
+                }
+            };
+
+            downloaded += chunk.len() as u64;
+            hasher.update(&chunk);
+
+            if let Err(e) = std::io::Write::write_all(&mut f, &chunk) {
+                let _ = std::fs::remove_file(&tmp);
+                return Err(e.to_string());
+            }
+
+            // Throttle progress events to avoid spamming the UI.
+            if last_emit.elapsed() >= std::time::Duration::from_millis(120) {
+                last_emit = std::time::Instant::now();
+                let _ = events::emit(
+                    &app,
+                    AppEvent::UpdateDownloadProgress(UpdateDownloadProgress {
+                        version: update.version.clone(),
+                        downloaded_bytes: downloaded,
+                        total_bytes: total,
+                    }),
+                );
+            }
+        }
+
+        let _ = events::emit(
+            &app,
+            AppEvent::UpdateDownloadProgress(UpdateDownloadProgress {
+                version: update.version.clone(),
+                downloaded_bytes: downloaded,
+                total_bytes: total,
+            }),
+        );
+
+        f.sync_all().ok();
+        drop(f);
+
+        let got_sha = format!("{:x}", hasher.finalize());
+        if got_sha != asset.sha256 {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(format!(
+                "checksum mismatch (expected {}, got {got_sha})",
+                asset.sha256
+            ));
+        }
+
+        voicewin_runtime::models::replace_file(&tmp, &dst).map_err(|e| e.to_string())?;
+        let _ = events::emit(&app, AppEvent::UpdateDownloadDone(update.version.clone()));
+
+        voicewin_runtime::updater::launch_installer(&dst).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+    .await;
 
-                    // Place the pill so its bottom is 80px above the monitor bottom.
-                    // (We align the window bottom accordingly; the webview itself includes shadow padding.)
-                    let y = work.position.y + work.size.height as i32 - OVERLAY_BOTTOM_OFFSET - (size.height as i32);
+    UPDATE_DOWNLOADING.store(false, Ordering::SeqCst);
 
-                    let _ = w.set_position(tauri::Position::Physical(tauri::PhysicalPosition::new(x, y)));
+    match &result {
+        Ok(()) => {
+            log::info!("install_update: launched installer for {}", update.version);
+            // The NSIS installer cannot replace a running executable.
+            #[cfg(windows)]
+            app.exit(0);
+        }
+        Err(e) => log::error!("install_update failed: {e}"),
+    }
+
+    result.map_err(|e| VoicewinError::from(e).with_retryable(true))
+}
+
+fn spawn_startup_update_check(app: tauri::AppHandle, channel: UpdateChannel) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_UPDATE_CHECK_DELAY).await;
+        match voicewin_runtime::updater::check_for_updates(&app_version(&app), channel).await {
+            Ok(Some(update)) => {
+                log::info!("update available: {}", update.version);
+                let _ = events::emit(&app, AppEvent::UpdateAvailable(update));
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("update check failed: {e}"),
+        }
+    });
+}
+
+#[tauri::command]
+async fn overlay_drag_begin(_app: tauri::AppHandle) -> Result<(), VoicewinError> {
+    // Mark that subsequent window moved events are user-driven.
+    let flag = OVERLAY_IS_DRAGGING.get_or_init(|| std::sync::atomic::AtomicBool::new(false));
+    flag.store(true, std::sync::atomic::Ordering::SeqCst);
+    Ok(())
+}
+
+#[tauri::command]
+async fn overlay_drag_end(app: tauri::AppHandle) -> Result<(), VoicewinError> {
+    let flag = OVERLAY_IS_DRAGGING.get_or_init(|| std::sync::atomic::AtomicBool::new(false));
+    flag.store(false, std::sync::atomic::Ordering::SeqCst);
+
+    // Persist current position at the end of the drag.
+    if let Some(w) = app.get_webview_window("recording_overlay") {
+        if let Ok(pos) = w.outer_position() {
+            if let Ok(store) = app.store(ui_state_store_path()) {
+                let payload = OverlayMovedPayload { x: pos.x, y: pos.y };
+                if let Ok(v) = serde_json::to_value(&payload) {
+                    store.set(OVERLAY_POSITION_STORE_KEY, v);
+                    let _ = store.save();
                 }
             }
         }
     }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn overlay_set_size(
+    app: tauri::AppHandle,
+    width: f64,
+    height: f64,
+) -> Result<(), VoicewinError> {
+    if let Some(w) = app.get_webview_window("recording_overlay") {
+        // JS measures in CSS pixels (logical units), so resize in logical units.
+        let _ = w.set_size(tauri::Size::Logical(tauri::LogicalSize::new(width, height)));
+
+        // If the user has not dragged the overlay (no stored position), keep it centered after
+        // fit-content resizes so it doesn't drift.
+        let has_saved_position = app
+            .store(ui_state_store_path())
+            .ok()
+            .and_then(|s| s.get(OVERLAY_POSITION_STORE_KEY))
+            .is_some();
+
+        if !has_saved_position {
+            if let Ok(Some(monitor)) = w.current_monitor().or_else(|_| w.primary_monitor()) {
+                center_overlay_on_monitor(&w, &monitor);
+            }
+        }
+    }
     Ok(())
 }
 
 #[tauri::command]
-async fn overlay_ready(state: State<'_, AppState>, app: tauri::AppHandle) -> Result<(), String> {
+async fn get_overlay_prefs(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<OverlayAppearance, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let cfg = load_or_init_config(svc, &app)?;
+    Ok(cfg.overlay.appearance)
+}
+
+#[tauri::command]
+async fn set_overlay_prefs(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    prefs: OverlayAppearance,
+) -> Result<OverlayAppearance, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let mut cfg = load_or_init_config(svc, &app)?;
+    cfg.overlay.appearance = prefs.normalized();
+    save_config_or_report(svc, &app, &cfg)?;
+    apply_overlay_settings(&app, cfg.overlay.clone());
+    Ok(cfg.overlay.appearance)
+}
+
+// Update the cached overlay settings and tell the webviews when the look changed.
+fn apply_overlay_settings(app: &tauri::AppHandle, settings: OverlaySettings) {
+    let appearance_changed = overlay_settings().appearance != settings.appearance;
+    let mode_changed = overlay_settings().mode != settings.mode;
+    let appearance = settings.appearance.clone();
+    set_overlay_settings(settings);
+
+    if appearance_changed {
+        if let Err(e) = events::emit(app, AppEvent::OverlayPrefsChanged(appearance)) {
+            log::warn!("failed to emit overlay prefs change: {e}");
+        }
+    }
+    if mode_changed {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let session = app.state::<AppState>().session.clone();
+            session.apply_overlay_mode(&app).await;
+        });
+    }
+}
+
+pub(crate) fn overlay_mode() -> OverlayMode {
+    overlay_settings().mode
+}
+
+fn overlay_settings() -> OverlaySettings {
+    OVERLAY_SETTINGS
+        .get_or_init(|| std::sync::Mutex::new(OverlaySettings::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone()
+}
+
+fn set_overlay_settings(settings: OverlaySettings) {
+    *OVERLAY_SETTINGS
+        .get_or_init(|| std::sync::Mutex::new(OverlaySettings::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner()) = settings;
+}
+
+pub(crate) fn sound_cue_settings() -> SoundCueSettings {
+    SOUND_CUE_SETTINGS
+        .get_or_init(|| std::sync::Mutex::new(SoundCueSettings::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone()
+}
+
+/// Whether to stay quiet: the user opted in and the OS is in do-not-disturb.
+pub(crate) fn quiet_mode() -> bool {
+    RESPECT_DO_NOT_DISTURB.load(std::sync::atomic::Ordering::Relaxed)
+        && voicewin_platform::do_not_disturb::do_not_disturb_active()
+}
+
+fn set_sound_cue_settings(settings: SoundCueSettings) {
+    *SOUND_CUE_SETTINGS
+        .get_or_init(|| std::sync::Mutex::new(SoundCueSettings::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner()) = settings;
+}
+
+/// Scrub dictated content out of a log message according to the redaction settings.
+pub(crate) fn redact_for_log(text: &str) -> String {
+    LOG_REDACTOR
+        .get_or_init(|| std::sync::Mutex::new(Redactor::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .redact(text)
+}
+
+fn set_redaction_settings(settings: &RedactionSettings) {
+    let redactor = match Redactor::from_settings(settings) {
+        Ok(r) => r,
+        Err(e) => {
+            log::warn!("{e}; keeping previous log redaction");
+            return;
+        }
+    };
+    voicewin_providers::wire_log::set_redactor(redactor.clone());
+    *LOG_REDACTOR
+        .get_or_init(|| std::sync::Mutex::new(Redactor::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner()) = redactor;
+}
+
+fn set_proxy_settings(settings: &ProxySettings) {
+    if let Err(e) = voicewin_providers::runtime::set_proxy(settings) {
+        log::warn!("{e:#}; keeping previous proxy settings");
+    }
+}
+
+fn set_tls_settings(settings: &TlsSettings) {
+    if let Err(e) = voicewin_providers::runtime::set_extra_root_cert(settings) {
+        log::warn!("{e:#}; keeping previous root certificates");
+    }
+}
+
+fn monitor_contains(m: &tauri::Monitor, x: i32, y: i32) -> bool {
+    let pos = m.position();
+    let size = m.size();
+    x >= pos.x && x < pos.x + size.width as i32 && y >= pos.y && y < pos.y + size.height as i32
+}
+
+// Resolve the monitor the overlay should appear on from `overlay.placement`.
+fn overlay_target_monitor(
+    app: &tauri::AppHandle,
+    w: &tauri::WebviewWindow,
+) -> Option<tauri::Monitor> {
+    let monitors = w.available_monitors().unwrap_or_default();
+    let containing =
+        |(x, y): (i32, i32)| monitors.iter().find(|m| monitor_contains(m, x, y)).cloned();
+    let under_cursor = || {
+        app.cursor_position()
+            .ok()
+            .and_then(|c| containing((c.x as i32, c.y as i32)))
+    };
+
+    let picked = match overlay_settings().placement {
+        OverlayPlacement::FollowCursor => under_cursor(),
+        OverlayPlacement::FollowFocusedWindow => {
+            voicewin_platform::foreground::foreground_window_center()
+                .and_then(containing)
+                .or_else(under_cursor)
+        }
+        OverlayPlacement::FixedMonitor { index } => monitors.get(index).cloned(),
+    };
+
+    picked.or_else(|| w.primary_monitor().ok().flatten())
+}
+
+fn center_overlay_on_monitor(w: &tauri::WebviewWindow, monitor: &tauri::Monitor) {
+    let work = monitor.work_area();
+    if let Ok(size) = w.outer_size() {
+        let x = work.position.x + (work.size.width as i32 / 2) - (size.width as i32 / 2);
+
+        // Place the pill so its bottom is 80px above the monitor bottom.
+        // (We align the window bottom accordingly; the webview itself includes shadow padding.)
+        let y = work.position.y + work.size.height as i32
+            - OVERLAY_BOTTOM_OFFSET
+            - (size.height as i32);
+
+        let _ = w.set_position(tauri::Position::Physical(tauri::PhysicalPosition::new(
+            x, y,
+        )));
+    }
+}
+
+/// Move the overlay onto the monitor chosen by `overlay.placement`.
+///
+/// A position the user dragged the overlay to is kept as long as it lies on that monitor;
+/// otherwise the overlay is centered near the bottom of the monitor's work area.
+pub(crate) fn position_overlay(app: &tauri::AppHandle) {
+    let Some(w) = app.get_webview_window("recording_overlay") else {
+        return;
+    };
+    let Some(monitor) = overlay_target_monitor(app, &w) else {
+        return;
+    };
+
+    let saved = app
+        .store(ui_state_store_path())
+        .ok()
+        .and_then(|s| s.get(OVERLAY_POSITION_STORE_KEY))
+        .and_then(|v| serde_json::from_value::<OverlayMovedPayload>(v).ok());
+
+    if let Some(p) = saved {
+        // Conservative bounds: ensure the overlay top-left is on the target work area.
+        let work = monitor.work_area();
+        let on_monitor = p.x >= work.position.x
+            && p.x <= work.position.x + work.size.width as i32
+            && p.y >= work.position.y
+            && p.y <= work.position.y + work.size.height as i32;
+        if on_monitor {
+            let _ = w.set_position(tauri::Position::Physical(tauri::PhysicalPosition::new(
+                p.x, p.y,
+            )));
+            return;
+        }
+    }
+
+    center_overlay_on_monitor(&w, &monitor);
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct MonitorInfo {
+    index: usize,
+    name: Option<String>,
+    width: u32,
+    height: u32,
+    is_primary: bool,
+}
+
+#[tauri::command]
+async fn list_monitors(app: tauri::AppHandle) -> Result<Vec<MonitorInfo>, VoicewinError> {
+    let monitors = app.available_monitors().map_err(command_error)?;
+    let primary = app.primary_monitor().ok().flatten();
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, m)| MonitorInfo {
+            index,
+            name: m.name().cloned(),
+            width: m.size().width,
+            height: m.size().height,
+            is_primary: primary
+                .as_ref()
+                .is_some_and(|p| p.position() == m.position() && p.size() == m.size()),
+        })
+        .collect())
+}
+
+#[tauri::command]
+async fn overlay_ready(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), VoicewinError> {
     // The overlay webview calls this after it has mounted and registered event listeners.
     // This lets us re-emit the current session status and avoid "missed first emit" races.
     state.session.mark_overlay_ready(&app).await;
@@ -1176,15 +2488,257 @@ async fn overlay_ready(state: State<'_, AppState>, app: tauri::AppHandle) -> Res
 }
 
 #[tauri::command]
-async fn overlay_dismiss(app: tauri::AppHandle) -> Result<(), String> {
+async fn overlay_set_click_through(
+    app: tauri::AppHandle,
+    ignore: bool,
+) -> Result<(), VoicewinError> {
+    let Some(w) = app.get_webview_window("recording_overlay") else {
+        return Ok(());
+    };
+    set_overlay_ignores_cursor(&w, ignore).map_err(command_error)
+}
+
+fn set_overlay_ignores_cursor(w: &tauri::WebviewWindow, ignore: bool) -> tauri::Result<()> {
+    w.set_ignore_cursor_events(ignore)?;
+    OVERLAY_IGNORES_CURSOR.store(ignore, std::sync::atomic::Ordering::SeqCst);
+    Ok(())
+}
+
+fn overlay_is_hovered(app: &tauri::AppHandle, w: &tauri::WebviewWindow) -> bool {
+    let (Ok(cursor), Ok(pos), Ok(size)) =
+        (app.cursor_position(), w.outer_position(), w.outer_size())
+    else {
+        return false;
+    };
+    let (x, y) = (cursor.x as i32, cursor.y as i32);
+    x >= pos.x && x < pos.x + size.width as i32 && y >= pos.y && y < pos.y + size.height as i32
+}
+
+// A click-through window never sees the pointer, so the webview can't detect hover itself.
+// Poll cursor position and modifier state instead and flip click-through as needed.
+fn spawn_overlay_click_through_watcher(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut tick = tokio::time::interval(OVERLAY_HOVER_POLL);
+        loop {
+            tick.tick().await;
+
+            let Some(w) = app.get_webview_window("recording_overlay") else {
+                continue;
+            };
+
+            let ignore = overlay_settings().click_through
+                && !(voicewin_platform::modifiers::overlay_modifier_held()
+                    && overlay_is_hovered(&app, &w));
+
+            if ignore != OVERLAY_IGNORES_CURSOR.load(std::sync::atomic::Ordering::SeqCst) {
+                if let Err(e) = set_overlay_ignores_cursor(&w, ignore) {
+                    log::warn!("failed to update overlay click-through: {e}");
+                }
+            }
+        }
+    });
+}
+
+// Runs for the app's lifetime; without a watcher sessions query the foreground app
+// themselves and the UI just gets no change events.
+fn start_foreground_watch(app: &tauri::AppHandle) {
+    let state = app.state::<AppState>();
+    let cache = state.foreground.clone();
+    let handle = app.clone();
+    let watch = voicewin_platform::foreground_watch::watch_foreground_app(move |id| {
+        cache.set(id.clone());
+        if voicewin_platform::foreground_watch::is_own_app(&id) {
+            return;
+        }
+        let state = handle.state::<AppState>();
+        *state.other_app.lock().unwrap_or_else(|p| p.into_inner()) = Some(id.clone());
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        auto_dictate::focus_changed(&handle, &id);
+        let _ = events::emit(
+            &handle,
+            AppEvent::ForegroundAppChanged(active_profile_for(&handle, id)),
+        );
+    });
+    match watch {
+        Ok(w) => {
+            *state
+                .foreground_watch
+                .lock()
+                .unwrap_or_else(|p| p.into_inner()) = Some(w)
+        }
+        Err(e) => log::warn!("foreground app watch unavailable: {e:#}"),
+    }
+}
+
+// The first tick fires right away, so a period that ended while the app was closed is
+// summarized at startup. The service skips periods that already have a digest.
+fn spawn_digest_scheduler(app: tauri::AppHandle) {
+    let svc_cell = app.state::<AppState>().service.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut tick = tokio::time::interval(DIGEST_POLL);
+        loop {
+            tick.tick().await;
+
+            let svc = match svc_cell
+                .get_or_try_init(|| async { build_service(&app).await })
+                .await
+            {
+                Ok(s) => s,
+                Err(e) => {
+                    log::warn!("digest job skipped: {e}");
+                    continue;
+                }
+            };
+
+            match svc.generate_due_digest().await {
+                Ok(Some(_)) => log::info!("generated history digest"),
+                Ok(None) => {}
+                Err(e) => log::warn!("failed to generate history digest: {e}"),
+            }
+        }
+    });
+}
+
+#[tauri::command]
+async fn overlay_dismiss(app: tauri::AppHandle) -> Result<(), VoicewinError> {
     if let Some(w) = app.get_webview_window("recording_overlay") {
         let _ = w.hide();
     }
     Ok(())
 }
 
+/// Copy the transcript of the last failed dictation; false when there is none.
+#[tauri::command]
+async fn copy_failed_text(app: tauri::AppHandle) -> Result<bool, VoicewinError> {
+    Ok(notifications::copy_failed_text(&app)?)
+}
+
+/// Show the scratchpad window, creating it on first use.
+fn open_scratchpad_window(app: &tauri::AppHandle) -> anyhow::Result<()> {
+    let w = match app.get_webview_window("scratchpad") {
+        Some(w) => w,
+        None => WebviewWindowBuilder::new(
+            app,
+            "scratchpad",
+            WebviewUrl::App("src/scratchpad.html".into()),
+        )
+        .title("VoiceWin Scratchpad")
+        .inner_size(420.0, 560.0)
+        .build()?,
+    };
+    let _ = w.show();
+    let _ = w.set_focus();
+    Ok(())
+}
+
+#[tauri::command]
+async fn open_scratchpad(app: tauri::AppHandle) -> Result<(), VoicewinError> {
+    open_scratchpad_window(&app).map_err(command_error)
+}
+
+#[tauri::command]
+async fn get_scratchpad(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<ScratchpadEntry>, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+    svc.scratchpad().load().map_err(command_error)
+}
+
+/// Copy one entry (by timestamp), or all of them when `ts_unix_ms` is `None`.
+#[tauri::command]
+async fn copy_scratchpad(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    ts_unix_ms: Option<i64>,
+) -> Result<(), VoicewinError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+    let mut entries = svc.scratchpad().load().map_err(command_error)?;
+    if let Some(ts) = ts_unix_ms {
+        entries.retain(|e| e.ts_unix_ms == ts);
+    }
+    app.clipboard()
+        .write_text(scratchpad_text(&entries))
+        .map_err(command_error)
+}
+
+#[tauri::command]
+async fn delete_scratchpad_entry(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    ts_unix_ms: i64,
+) -> Result<bool, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+    svc.scratchpad().remove(ts_unix_ms).map_err(command_error)
+}
+
+#[tauri::command]
+async fn clear_scratchpad(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+    svc.scratchpad().clear().map_err(command_error)
+}
+
+/// Show the session details window, creating it on first use.
+fn open_session_details_window(app: &tauri::AppHandle) -> anyhow::Result<()> {
+    let w = match app.get_webview_window("session_details") {
+        Some(w) => w,
+        None => WebviewWindowBuilder::new(
+            app,
+            "session_details",
+            WebviewUrl::App("src/details.html".into()),
+        )
+        .title("VoiceWin Session Details")
+        .inner_size(560.0, 640.0)
+        .build()?,
+    };
+    let _ = w.show();
+    let _ = w.set_focus();
+    Ok(())
+}
+
+#[tauri::command]
+async fn open_session_details(app: tauri::AppHandle) -> Result<(), VoicewinError> {
+    open_session_details_window(&app).map_err(command_error)
+}
+
+/// Raw transcript, enhanced text, prompt and timings of the last session; `None` before the
+/// first session since startup.
+#[tauri::command]
+async fn get_last_session_result(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Option<SessionDetails>, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+    Ok(svc.last_session_result())
+}
+
 #[tauri::command]
-async fn show_main_window(app: tauri::AppHandle) -> Result<(), String> {
+async fn show_main_window(app: tauri::AppHandle) -> Result<(), VoicewinError> {
     if let Some(w) = app.get_webview_window("main") {
         let _ = w.show();
         let _ = w.set_focus();
@@ -1192,34 +2746,94 @@ async fn show_main_window(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-#[cfg(target_os = "macos")]
 #[tauri::command]
-async fn open_macos_accessibility_settings() -> Result<(), String> {
-    use std::process::Command;
+async fn open_privacy_settings(pane: PrivacyPane) -> Result<(), VoicewinError> {
+    voicewin_platform::permissions::open_privacy_settings(pane).map_err(command_error)
+}
 
-    let url = "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility";
-    let status = Command::new("open").arg(url).status().map_err(|e| e.to_string())?;
-    if status.success() {
-        Ok(())
-    } else {
-        Err("failed to open Accessibility settings".into())
-    }
+#[tauri::command]
+async fn request_mic_permission() -> Result<PermissionState, VoicewinError> {
+    // Blocks on the system dialog where there is one.
+    tauri::async_runtime::spawn_blocking(
+        voicewin_platform::permissions::request_microphone_permission,
+    )
+    .await
+    .map_err(command_error)
 }
 
-#[cfg(target_os = "macos")]
 #[tauri::command]
-async fn open_macos_microphone_settings() -> Result<(), String> {
-    use std::process::Command;
+async fn get_onboarding_status(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<OnboardingStatus, VoicewinError> {
+    let svc = state
+        .service
+        .get_or_try_init(|| async { build_service(&app).await })
+        .await
+        .map_err(command_error)?;
+
+    let local_data_dir = app_paths(&app).map_err(command_error)?.local_data_dir;
+    let model_path = voicewin_runtime::models::installed_bootstrap_model_path(&local_data_dir);
+    let model = match voicewin_runtime::models::validate_bootstrap_model(&model_path) {
+        Ok(_) => OnboardingCheck::ok(OnboardingStep::Model),
+        Err(e) => OnboardingCheck::pending(
+            OnboardingStep::Model,
+            format!("No usable speech model: {e}"),
+            OnboardingAction::DownloadModel,
+        ),
+    };
 
-    let url = "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone";
-    let status = Command::new("open").arg(url).status().map_err(|e| e.to_string())?;
-    if status.success() {
-        Ok(())
+    #[allow(unused_mut)]
+    let mut microphone = OnboardingCheck::permission(
+        PrivacyPane::Microphone,
+        voicewin_platform::permissions::microphone_permission(),
+    );
+    // Name the exact switch; Windows has three that can block us.
+    #[cfg(windows)]
+    if let Ok(Some(block)) = voicewin_platform::windows::microphone_block() {
+        microphone.detail = Some(block.user_message().into());
+    }
+
+    Ok(OnboardingStatus::new(vec![
+        microphone,
+        OnboardingCheck::permission(
+            PrivacyPane::Accessibility,
+            voicewin_platform::permissions::accessibility_permission(),
+        ),
+        model,
+        hotkey_onboarding_check(&app),
+        svc.llm_onboarding_check().await,
+    ]))
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+fn hotkey_onboarding_check(app: &tauri::AppHandle) -> OnboardingCheck {
+    let hotkey = current_hotkey();
+    if hotkey.trim().is_empty() {
+        OnboardingCheck::pending(
+            OnboardingStep::Hotkey,
+            "No recording hotkey is set.",
+            OnboardingAction::ChangeHotkey,
+        )
+    } else if app.global_shortcut().is_registered(hotkey.as_str()) {
+        OnboardingCheck::ok(OnboardingStep::Hotkey)
     } else {
-        Err("failed to open Microphone settings".into())
+        OnboardingCheck::failed(
+            OnboardingStep::Hotkey,
+            format!("{hotkey} could not be registered; another app may be using it."),
+            Some(OnboardingAction::ChangeHotkey),
+        )
     }
 }
 
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn hotkey_onboarding_check(_app: &tauri::AppHandle) -> OnboardingCheck {
+    OnboardingCheck::skipped(
+        OnboardingStep::Hotkey,
+        "Global hotkeys are not supported on this platform.",
+    )
+}
+
 fn main() {
     // If we crash/panic on end-user machines, a stderr backtrace is often not available.
     // Write panics to a predictable temp file to aid debugging.
@@ -1248,17 +2862,27 @@ fn main() {
     // `windows_subsystem = "windows"` builds (no console output).
     use tauri_plugin_log::{Target, TargetKind};
 
+    let log_target = match portable_exe_dir() {
+        Some(dir) => TargetKind::Folder {
+            path: AppPaths::portable(dir).log_dir,
+            file_name: Some("voicewin".into()),
+        },
+        None => TargetKind::LogDir {
+            file_name: Some("voicewin".into()),
+        },
+    };
+
     tauri::Builder::default()
         .plugin(
             tauri_plugin_log::Builder::new()
                 .level(log::LevelFilter::Info)
-                .targets([Target::new(TargetKind::LogDir {
-                    file_name: Some("voicewin".into()),
-                })])
+                .targets([Target::new(log_target)])
                 .build(),
         )
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             // If a second instance is launched, bring the existing window to the front.
@@ -1271,50 +2895,103 @@ fn main() {
             service: Arc::new(tokio::sync::OnceCell::new()),
             session: SessionController::new(),
 
-            #[cfg(any(windows, target_os = "macos"))]
-            toggle_hotkey: std::sync::Mutex::new(DEFAULT_TOGGLE_HOTKEY.into()),
+            foreground: ForegroundCache::default(),
+            foreground_watch: std::sync::Mutex::new(None),
+            other_app: std::sync::Mutex::new(None),
+            model_downloads: Arc::new(DownloadManager::new(
+                voicewin_runtime::models::MAX_PARALLEL_DOWNLOADS,
+            )),
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
             set_config,
             toggle_recording,
+            toggle_meeting,
             cancel_recording,
             get_session_status,
-            #[cfg(any(windows, target_os = "macos"))]
+            resync_overlay,
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
             get_toggle_hotkey,
-            #[cfg(any(windows, target_os = "macos"))]
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
             set_toggle_hotkey,
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+            list_hotkeys,
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+            set_hotkey,
 
             get_history,
             clear_history,
+            compact_history,
             delete_history_entry,
+            restore_history_entry,
+            empty_history_trash,
+            get_history_tags,
+            set_history_entry_tags,
+            set_history_entry_pinned,
+            export_history,
+            suggest_profiles,
+            get_pending_session_count,
+            recover_pending_sessions,
+            transcribe_file,
+            bulk_enhance_history,
+            compare_prompts,
+            get_cost_estimate,
             get_provider_status,
             set_openai_api_key,
             clear_openai_api_key,
             set_elevenlabs_api_key,
             clear_elevenlabs_api_key,
+            validate_llm_key,
+            validate_elevenlabs_key,
+            set_webhook_token,
+            clear_webhook_token,
             get_model_status,
-            #[cfg(any(windows, target_os = "macos"))]
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
             list_microphones,
             list_models,
+            recommend_model_for_language,
+            list_stt_providers,
             download_model,
+            get_download_queue,
+            check_for_updates,
+            install_update,
             set_active_model,
             capture_foreground_app,
+            get_active_profile,
+            resolve_profile_for_current_app,
+            open_scratchpad,
+            get_scratchpad,
+            copy_scratchpad,
+            delete_scratchpad_entry,
+            clear_scratchpad,
+            open_session_details,
+            get_last_session_result,
             overlay_drag_begin,
             overlay_drag_end,
             overlay_set_size,
             overlay_ready,
             overlay_dismiss,
+            copy_failed_text,
+            overlay_set_click_through,
+            list_monitors,
+            get_overlay_prefs,
+            set_overlay_prefs,
+            get_recording_disabled,
+            set_recording_disabled,
+            export_debug_bundle,
+            get_session_traces,
+            get_provider_logs,
             show_main_window,
-
-            #[cfg(target_os = "macos")]
-            open_macos_accessibility_settings,
-            #[cfg(target_os = "macos")]
-            open_macos_microphone_settings,
+            get_onboarding_status,
+            request_mic_permission,
+            open_privacy_settings,
         ])
         .setup(|app| {
             let handle = app.handle();
 
+            // Collect pipeline spans for the performance panel.
+            voicewin_appcore::traces::install();
+
             // Overlay window (hidden by default). This is the primary UX feedback surface.
             // Default size is only used until the webview measures the HUD pill.
             let overlay = WebviewWindowBuilder::new(
@@ -1349,69 +3026,44 @@ fn main() {
 
             // IMPORTANT: do not set the overlay window as click-through by default.
             // The HUD contains interactive controls (Stop/Cancel/History/Dismiss) and must
-            // receive pointer events.
-
-            // If the user previously moved the overlay, restore that position.
-            // Otherwise, center on the current monitor (or primary) and move it near the bottom.
-            let mut restored = false;
-            if let Ok(store) = app.store(OVERLAY_POSITION_STORE_PATH) {
-                if let Some(v) = store.get(OVERLAY_POSITION_STORE_KEY) {
-                    if let Ok(p) = serde_json::from_value::<OverlayMovedPayload>(v) {
-                        // Validate against the available monitor work areas.
-                        if let Ok(monitors) = overlay.available_monitors() {
-                            let fits_any = monitors.iter().any(|m| {
-                                let work = m.work_area();
-                                let left = work.position.x;
-                                let top = work.position.y;
-                                let right = work.position.x + work.size.width as i32;
-                                let bottom = work.position.y + work.size.height as i32;
-
-                                // Conservative bounds: ensure the overlay top-left is on-screen.
-                                // The overlay is resized dynamically after the webview measures content.
-                                p.x >= left && p.x <= right && p.y >= top && p.y <= bottom
-                            });
-
-                            if fits_any {
-                                let _ = overlay.set_position(tauri::Position::Physical(
-                                    tauri::PhysicalPosition::new(p.x, p.y),
-                                ));
-                                restored = true;
-                            }
-                        }
+            // receive pointer events. Click-through is opt-in (`overlay.click_through`) and
+            // the watcher re-enables input while the modifier is held over the pill.
+            spawn_overlay_click_through_watcher(handle.clone());
+            spawn_digest_scheduler(handle.clone());
+            start_foreground_watch(handle);
+
+            // Load overlay settings before the service exists so startup placement honors them.
+            if let Ok(path) = default_config_path(handle) {
+                if let Ok(cfg) = voicewin_runtime::config_store::ConfigStore::at_path(path).load() {
+                    set_redaction_settings(&cfg.redaction);
+                    set_proxy_settings(&cfg.proxy);
+                    set_tls_settings(&cfg.tls);
+                    voicewin_providers::runtime::set_offline_mode(cfg.offline_mode);
+                    voicewin_providers::wire_log::set_settings(&cfg.provider_log);
+                    set_overlay_settings(cfg.overlay);
+                    set_sound_cue_settings(cfg.sound_cues);
+                    RESPECT_DO_NOT_DISTURB.store(
+                        cfg.respect_do_not_disturb,
+                        std::sync::atomic::Ordering::Relaxed,
+                    );
+                    if cfg.updates.check_on_startup && !cfg.offline_mode {
+                        spawn_startup_update_check(handle.clone(), cfg.updates.channel);
                     }
                 }
             }
 
-            if !restored {
-                // Center on the current monitor (or primary), then move it near the bottom.
-                if let Ok(Some(monitor)) = overlay
-                    .current_monitor()
-                    .or_else(|_| overlay.primary_monitor())
-                {
-                    let work = monitor.work_area();
-                    let size = &work.size;
-                    let pos = &work.position;
-
-                    if let Ok(size_px) = overlay.outer_size() {
-                        let x = pos.x + (size.width as i32 / 2) - (size_px.width as i32 / 2);
-
-                        // Align the overlay window bottom so the pill appears ~80px above the monitor bottom.
-                        let y = pos.y + size.height as i32
-                            - OVERLAY_BOTTOM_OFFSET
-                            - (size_px.height as i32);
-
-                        let _ = overlay.set_position(tauri::Position::Physical(
-                            tauri::PhysicalPosition::new(x, y),
-                        ));
-                    }
+            // Restore the user's dragged position if it is on the configured monitor;
+            // otherwise center near the bottom of that monitor.
+            position_overlay(handle);
 
-                    // Overlay must remain interactive; do not enable click-through.
-                }
+            // A persistent overlay is up from the start, showing Idle.
+            if overlay_mode() == OverlayMode::Persistent {
+                let _ = overlay.show();
             }
 
             // Persist overlay position only while user is actively dragging.
             // This avoids accidentally persisting position on normal clicks or programmatic moves.
-            let store_for_events = app.store(OVERLAY_POSITION_STORE_PATH).ok();
+            let store_for_events = app.store(ui_state_store_path()).ok();
             overlay.on_window_event({
                 let store_for_events = store_for_events.clone();
                 move |event| {
@@ -1446,12 +3098,44 @@ fn main() {
             let toggle = MenuItemBuilder::new("Start Recording")
                 .id("toggle_recording")
                 .build(handle)?;
+            let meeting = MenuItemBuilder::new("Start Meeting Transcription")
+                .id("toggle_meeting")
+                .build(handle)?;
             let cancel = MenuItemBuilder::new("Cancel Recording")
                 .id("cancel_recording")
                 .build(handle)?;
+            // Restore the persisted mic switch before any hotkey/tray action can start a session.
+            let recording_disabled = persisted_recording_disabled(handle);
+            app.state::<AppState>()
+                .session
+                .restore_recording_disabled(recording_disabled);
+            let disable_mic = CheckMenuItemBuilder::new("Disable Microphone")
+                .id("toggle_recording_disabled")
+                .checked(recording_disabled)
+                .build(handle)?;
+            app.manage(RecordingDisabledMenuItem(disable_mic.clone()));
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+            let wake_word_item = {
+                let item = CheckMenuItemBuilder::new("Listen for Wake Phrase")
+                    .id(wake_word::TRAY_WAKE_WORD_ID)
+                    .checked(wake_word::is_listening())
+                    .build(handle)?;
+                app.manage(wake_word::WakeWordMenuItem(item.clone()));
+                item
+            };
+
             let open_history = MenuItemBuilder::new("Open History")
                 .id("open_history")
                 .build(handle)?;
+            let open_scratchpad = MenuItemBuilder::new("Open Scratchpad")
+                .id("open_scratchpad")
+                .build(handle)?;
+            let open_session_details = MenuItemBuilder::new("Last Session Details")
+                .id("open_session_details")
+                .build(handle)?;
+            let copy_failed_text = MenuItemBuilder::new("Copy Text (Last Failed Dictation)")
+                .id(notifications::TRAY_COPY_FAILED_TEXT_ID)
+                .build(handle)?;
             let open_logs = MenuItemBuilder::new("Open Logs Folder")
                 .id("open_logs")
                 .build(handle)?;
@@ -1464,8 +3148,15 @@ fn main() {
                 .items(&[
                     &show_main,
                     &toggle,
+                    &meeting,
                     &cancel,
+                    &disable_mic,
+                    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+                    &wake_word_item,
                     &open_history,
+                    &open_scratchpad,
+                    &open_session_details,
+                    &copy_failed_text,
                     &open_logs,
                     &reset_hud_position,
                     &quit,
@@ -1517,6 +3208,31 @@ fn main() {
                                 });
                             });
                         }
+                        "toggle_meeting" => {
+                            let app = app.clone();
+                            let session = session.clone();
+                            let state = app.state::<AppState>();
+                            let svc_cell = state.service.clone();
+                            let meeting_item = meeting.clone();
+
+                            tauri::async_runtime::spawn(async move {
+                                let svc = match svc_cell
+                                    .get_or_try_init(|| async { build_service(&app).await })
+                                    .await
+                                {
+                                    Ok(s) => s,
+                                    Err(_) => return,
+                                };
+
+                                let res = session.toggle_meeting(&app, svc.clone()).await;
+
+                                let _ = meeting_item.set_text(if res.is_recording {
+                                    "Stop Meeting Transcription"
+                                } else {
+                                    "Start Meeting Transcription"
+                                });
+                            });
+                        }
                         "cancel_recording" => {
                             let app = app.clone();
                             let session = session.clone();
@@ -1540,12 +3256,50 @@ fn main() {
                                 let _ = w.show();
                                 let _ = w.set_focus();
                                 // Best-effort: switch to the history tab.
-                                let _ = w.emit("voicewin://navigate", "history");
+                                let _ = events::emit(&w, AppEvent::Navigate("history".into()));
+                            }
+                        }
+                        "open_scratchpad" => {
+                            if let Err(e) = open_scratchpad_window(app) {
+                                log::error!("failed to open scratchpad: {e}");
+                            }
+                        }
+                        "open_session_details" => {
+                            if let Err(e) = open_session_details_window(app) {
+                                log::error!("failed to open session details: {e}");
+                            }
+                        }
+                        "toggle_recording_disabled" => {
+                            let app = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let state = app.state::<AppState>();
+                                let disabled = !state.session.recording_disabled();
+                                if let Err(e) =
+                                    apply_recording_disabled(&app, &state, disabled).await
+                                {
+                                    log::error!("failed to toggle recording disabled: {e}");
+                                }
+                            });
+                        }
+                        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+                        wake_word::TRAY_WAKE_WORD_ID => {
+                            let app = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = toggle_wake_word(&app).await {
+                                    log::error!("failed to toggle wake phrase: {}", e.user_message);
+                                }
+                            });
+                        }
+                        notifications::TRAY_COPY_FAILED_TEXT_ID => {
+                            match notifications::copy_failed_text(app) {
+                                Ok(true) => {}
+                                Ok(false) => log::info!("no failed dictation text to copy"),
+                                Err(e) => log::error!("failed to copy text: {e}"),
                             }
                         }
                         "open_logs" => {
                             // Best-effort: open the app log directory in the OS file manager.
-                            if let Ok(dir) = app.path().app_log_dir() {
+                            if let Ok(dir) = app_paths(app).map(|p| p.log_dir) {
                                 #[cfg(windows)]
                                 {
                                     let _ = std::process::Command::new("explorer")
@@ -1580,36 +3334,12 @@ fn main() {
                             }
                         }
                         "reset_hud_position" => {
-                            if let Ok(store) = app.store(OVERLAY_POSITION_STORE_PATH) {
+                            if let Ok(store) = app.store(ui_state_store_path()) {
                                 store.delete(OVERLAY_POSITION_STORE_KEY);
                                 let _ = store.save();
                             }
 
-                            if let Some(overlay) = app.get_webview_window("recording_overlay") {
-                                if let Ok(Some(monitor)) = overlay
-                                    .current_monitor()
-                                    .or_else(|_| overlay.primary_monitor())
-                                {
-                                    let work = monitor.work_area();
-
-                                    if let Ok(size) = overlay.outer_size() {
-                                        let x = work.position.x
-                                            + (work.size.width as i32 / 2)
-                                            - (size.width as i32 / 2);
-
-                                        // Align the overlay window bottom so the pill appears ~80px above the
-                                        // monitor bottom (the window itself includes shadow padding).
-                                        let y = work.position.y
-                                            + work.size.height as i32
-                                            - OVERLAY_BOTTOM_OFFSET
-                                            - (size.height as i32);
-
-                                        let _ = overlay.set_position(tauri::Position::Physical(
-                                            tauri::PhysicalPosition::new(x, y),
-                                        ));
-                                    }
-                                }
-                            }
+                            position_overlay(app);
                         }
                         "quit" => {
                             app.exit(0);
@@ -1619,67 +3349,11 @@ fn main() {
                 })
                 .build(handle)?;
 
-            #[cfg(any(windows, target_os = "macos"))]
+            #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
             {
-                // Register the persisted (or default) toggle hotkey.
-                // If registration fails (conflict), we keep running without a hotkey until the
-                // user changes it from the UI.
-                let handle = handle.clone();
-                let app_handle = handle.clone();
-
-                // Load persisted hotkey from store.
-                let persisted = app
-                    .store(OVERLAY_POSITION_STORE_PATH)
-                    .ok()
-                    .and_then(|s| s.get(HOTKEY_STORE_KEY))
-                    .and_then(|v| v.as_str().map(|s| s.to_string()));
-
-                let hotkey = persisted.unwrap_or_else(|| DEFAULT_TOGGLE_HOTKEY.into());
-
-                // Keep in state for UI to query.
-                if let Ok(mut guard) = app_state.toggle_hotkey.lock() {
-                    *guard = hotkey.clone();
-                } else {
-                    *app_state
-                        .toggle_hotkey
-                        .lock()
-                        .unwrap_or_else(|p| p.into_inner()) = hotkey.clone();
-                }
-
-                // Register with handler.
-                let session = session.clone();
-                let svc_cell = app_state.service.clone();
-
-                match app_handle.global_shortcut().on_shortcut(
-                    hotkey.as_str(),
-                    move |app, _shortcut, event| {
-                        if event.state != ShortcutState::Pressed {
-                            return;
-                        }
-
-                        let app = app.clone();
-                        let session = session.clone();
-                        let svc_cell = svc_cell.clone();
-
-                        tauri::async_runtime::spawn(async move {
-                            let svc = match svc_cell
-                                .get_or_try_init(|| async { build_service(&app).await })
-                                .await
-                            {
-                                Ok(s) => s,
-                                Err(e) => {
-                                    log::error!("hotkey service init failed: {e}");
-                                    return;
-                                }
-                            };
-
-                            let _ = session.toggle_recording(&app, svc.clone()).await;
-                        });
-                    },
-                ) {
-                    Ok(_) => log::info!("registered hotkey: {hotkey}"),
-                    Err(e) => log::error!("failed to register hotkey {hotkey}: {e}"),
-                }
+                // Register the persisted (or default) hotkeys. If one fails (conflict), we keep
+                // running without it until the user changes it from the UI.
+                hotkeys::restore(handle);
             }
 
             let _ = tray;
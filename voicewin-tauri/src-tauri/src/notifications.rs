@@ -0,0 +1,83 @@
+// Native notifications for failures the user would otherwise miss.
+//
+// The HUD only shows errors for a few seconds. When the overlay was dismissed or the
+// user has switched to another app, we also raise an OS notification. The desktop
+// notification backends don't deliver action buttons, so "Copy text" lives in the tray
+// menu (and on the HUD) and copies the transcript of the last failed session.
+
+use std::sync::Mutex;
+
+use tauri::Manager;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_notification::NotificationExt;
+use voicewin_appcore::service::AppService;
+use voicewin_core::types::AppIdentity;
+
+pub const TRAY_COPY_FAILED_TEXT_ID: &str = "copy_failed_text";
+
+// Transcript from the most recent failed session, for the tray's "Copy text" item.
+static FAILED_TEXT: Mutex<Option<String>> = Mutex::new(None);
+
+/// True when an error shown only in the HUD would likely go unnoticed: the overlay is
+/// hidden, or the foreground app is no longer the one the user dictated into.
+pub async fn user_moved_on(
+    app: &tauri::AppHandle,
+    svc: &AppService,
+    target_app: Option<&AppIdentity>,
+) -> bool {
+    let overlay_visible = app
+        .get_webview_window("recording_overlay")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+    if !overlay_visible {
+        return true;
+    }
+
+    let Some(target) = target_app else {
+        return false;
+    };
+    match svc.get_foreground_app().await {
+        // Titles change while typing, so compare the process only.
+        Ok(now) => now.process_name != target.process_name || now.exe_path != target.exe_path,
+        Err(_) => false,
+    }
+}
+
+/// Show a failure notification. `text` (if any) becomes available via the tray's
+/// "Copy text" item.
+pub fn notify_failure(app: &tauri::AppHandle, title: &str, message: &str, text: Option<&str>) {
+    let text = text.map(str::trim).filter(|t| !t.is_empty());
+
+    let mut body = message.trim().to_string();
+    if let Some(t) = text {
+        *FAILED_TEXT.lock().unwrap_or_else(|p| p.into_inner()) = Some(t.to_string());
+        body.push_str("\nUse \"Copy text\" in the VoiceWin tray menu to copy your dictation.");
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("failed to show notification: {e}");
+    }
+}
+
+/// Keep `text` for "Copy text" without a notification (the HUD is showing the failure).
+pub fn remember_failed_text(text: Option<&str>) {
+    if let Some(t) = text.map(str::trim).filter(|t| !t.is_empty()) {
+        *FAILED_TEXT.lock().unwrap_or_else(|p| p.into_inner()) = Some(t.to_string());
+    }
+}
+
+/// Copy the last failed session's transcript to the clipboard. Returns false when there
+/// is nothing to copy.
+pub fn copy_failed_text(app: &tauri::AppHandle) -> Result<bool, String> {
+    let Some(text) = FAILED_TEXT
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone()
+    else {
+        return Ok(false);
+    };
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
@@ -1,28 +1,40 @@
 use std::sync::Arc;
-#[cfg(any(windows, target_os = "macos"))]
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 use std::sync::Mutex as StdMutex;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
-use tauri::{Emitter, Manager};
+use tauri::Manager;
 use tokio::sync::Mutex;
 
-use voicewin_appcore::service::AppService;
+use voicewin_appcore::service::{AppService, service_error};
+use voicewin_core::config::OverlayMode;
+use voicewin_core::error::{ErrorKind, VoicewinError};
 
-#[cfg(any(windows, target_os = "macos"))]
+use crate::events::{self, AppEvent};
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use voicewin_core::meeting::{ChunkCut, ChunkSegmenter};
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 use voicewin_runtime::secrets::{SecretKey, get_secret};
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 use voicewin_providers::elevenlabs_realtime::{
-    ElevenLabsRealtimeConfig, ElevenLabsRealtimeHandle, RealtimeEvent, spawn_realtime_session,
+    ElevenLabsRealtimeConfig, ElevenLabsRealtimeHandle, RealtimeEvent,
 };
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use voicewin_providers::realtime_pool::RealtimeConnectionManager;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SessionStage {
     Idle,
     Recording,
+    // Meeting mode: recording with no time limit, transcribed chunk by chunk into the
+    // scratchpad or the meeting note while it runs (see `voicewin_core::meeting`).
+    Meeting,
     // Realtime-only: we already streamed audio during recording, so after stop we may be
     // finalizing the transcript (and/or running post-processing) rather than doing batch STT.
     Finalizing,
@@ -38,20 +50,57 @@ pub enum SessionStage {
     Cancelled,
 }
 
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+fn stage_cue(prev: SessionStage, stage: SessionStage) -> Option<voicewin_audio::SoundCue> {
+    use voicewin_audio::SoundCue;
+
+    match stage {
+        SessionStage::Recording | SessionStage::Meeting => Some(SoundCue::Start),
+        SessionStage::Error => Some(SoundCue::Error),
+        SessionStage::Success => Some(SoundCue::Success),
+        _ if matches!(prev, SessionStage::Recording | SessionStage::Meeting) => {
+            Some(SoundCue::Stop)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+fn play_stage_cue(prev: SessionStage, stage: SessionStage) {
+    use voicewin_audio::SoundCue;
+
+    let settings = crate::sound_cue_settings();
+    if !settings.enabled || crate::quiet_mode() {
+        return;
+    }
+    let Some(cue) = stage_cue(prev, stage) else {
+        return;
+    };
+    let wanted = match cue {
+        SoundCue::Start => settings.on_start,
+        SoundCue::Stop => settings.on_stop,
+        SoundCue::Success => settings.on_success,
+        SoundCue::Error => settings.on_error,
+    };
+    if wanted {
+        voicewin_audio::play_cue(cue, settings.volume());
+    }
+}
+
 // `cargo check` on Linux doesn't compile the Windows/macOS recording path; keep warnings down.
-#[cfg(not(any(windows, target_os = "macos")))]
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
 #[allow(dead_code)]
 const _STAGE_KEEPALIVE_FINALIZING: SessionStage = SessionStage::Finalizing;
-#[cfg(not(any(windows, target_os = "macos")))]
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
 #[allow(dead_code)]
 const _STAGE_KEEPALIVE_TRANSCRIBING: SessionStage = SessionStage::Transcribing;
-#[cfg(not(any(windows, target_os = "macos")))]
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
 #[allow(dead_code)]
 const _STAGE_KEEPALIVE_ENHANCING: SessionStage = SessionStage::Enhancing;
-#[cfg(not(any(windows, target_os = "macos")))]
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
 #[allow(dead_code)]
 const _STAGE_KEEPALIVE_INSERTING: SessionStage = SessionStage::Inserting;
-#[cfg(not(any(windows, target_os = "macos")))]
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
 #[allow(dead_code)]
 const _STAGE_KEEPALIVE_SUCCESS: SessionStage = SessionStage::Success;
 
@@ -68,19 +117,28 @@ pub struct SessionStatusPayload {
     pub is_recording: bool,
     pub elapsed_ms: Option<u64>,
     pub error: Option<String>,
+    // Set alongside `error` when it is a failure (not a toast), so the overlay can offer actions.
+    pub error_info: Option<VoicewinError>,
     // Reserved for future use (e.g. transcript preview in the main window).
     pub last_text_preview: Option<String>,
     pub last_text_available: bool,
+    // Microphone globally disabled (tray toggle); toggling is refused while set.
+    pub recording_disabled: bool,
+    // OS do-not-disturb is on and the user asked us to respect it: no cues, no pop-ups.
+    pub quiet_mode: bool,
+    // Chunks appended so far when the session is a meeting.
+    pub meeting_chunks: Option<u32>,
+    // The state as a short sentence for screen readers, which the overlay announces.
+    pub accessible_label: String,
 }
 
-#[cfg(any(windows, target_os = "macos"))]
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct MicLevelPayload {
     pub rms: f32,
     pub peak: f32,
 }
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 struct RealtimeSttState {
     handle: ElevenLabsRealtimeHandle,
     sender_task: tauri::async_runtime::JoinHandle<()>,
@@ -91,18 +149,50 @@ struct RealtimeSttState {
     // Best-effort diagnostics/warnings to surface on stop (and persist to History).
     last_error: Arc<StdMutex<Option<String>>>,
     last_warning: Arc<StdMutex<Option<String>>>,
+
+    // Set when committed segments are typed into the target app as they arrive.
+    live_typing: Option<Arc<StdMutex<voicewin_core::live_typing::LiveTyping>>>,
+}
+
+/// Sent from the mic level callback (cuts) and `stop_meeting` to the meeting's chunk loop.
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+#[derive(Debug, Clone, Copy)]
+enum MeetingSignal {
+    Cut(ChunkCut),
+    Stop,
+}
+
+/// A stage transition, relative to the start of the session's recording.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StageTiming {
+    pub stage: SessionStage,
+    pub at_ms: u64,
 }
 
 #[derive(Default)]
 struct Inner {
     stage: SessionStage,
     recording_started_at: Option<Instant>,
+    // Unlike `recording_started_at`, kept until the next session starts.
+    session_started_at: Option<Instant>,
+    timeline: Vec<StageTiming>,
     recording_elapsed_ms: Option<u64>,
     last_text: Option<String>,
     status_message: Option<String>,
+    status_error: Option<VoicewinError>,
     status_message_expires_at: Option<Instant>,
     session_id: u64,
 
+    // The app in front when the hotkey started recording. The session is resolved and
+    // inserted against it even if focus has moved since (e.g. to the overlay).
+    target_app: Option<voicewin_core::types::AppIdentity>,
+
+    // Set when `stt_fallbacks` swapped the configured STT provider for this session.
+    stt_fallback: Option<voicewin_runtime::stt::fallback::SttDowngrade>,
+
+    // Last ElevenLabs credit check; see `check_elevenlabs_quota`.
+    quota_checked_at: Option<Instant>,
+
     // Set by the overlay webview calling `overlay_ready`.
     // We use it to make status delivery more reliable (re-emit after listeners attach).
     overlay_ready: bool,
@@ -111,14 +201,26 @@ struct Inner {
     // in a background task so the UI stays responsive and we can cancel it.
     processing_task: Option<tauri::async_runtime::JoinHandle<()>>,
 
-    #[cfg(any(windows, target_os = "macos"))]
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     realtime_stt: Option<RealtimeSttState>,
+
+    // Set for meeting sessions; the chunk loop itself runs as `processing_task`.
+    meeting_chunks: Option<u32>,
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    meeting_signals: Option<tokio::sync::mpsc::UnboundedSender<MeetingSignal>>,
 }
 
 #[derive(Clone, Default)]
 pub struct SessionController {
     #[allow(dead_code)]
     inner: Arc<Mutex<Inner>>,
+
+    // Kept outside `inner` so the tray and hotkey can check it without awaiting the lock.
+    recording_disabled: Arc<AtomicBool>,
+
+    // Realtime websockets opened ahead of time (see `PerformanceSettings::realtime_preconnect`).
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    realtime_pool: Arc<RealtimeConnectionManager>,
 }
 
 impl SessionController {
@@ -126,6 +228,9 @@ impl SessionController {
     const BUSY_TOAST_TTL: Duration = Duration::from_secs(1);
     // Design-draft: Success state must remain visible for 1500ms before exit.
     const OVERLAY_HIDE_DELAY: Duration = Duration::from_millis(1500);
+    const REFUSAL_TOAST_TTL: Duration = Duration::from_millis(2500);
+    const QUOTA_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+    const QUOTA_TOAST_TTL: Duration = Duration::from_secs(6);
 
     pub fn new() -> Self {
         Self::default()
@@ -135,17 +240,17 @@ impl SessionController {
         if let Some(expires_at) = inner.status_message_expires_at {
             if Instant::now() >= expires_at {
                 inner.status_message = None;
+                inner.status_error = None;
                 inner.status_message_expires_at = None;
             }
         }
     }
 
-    #[allow(dead_code)]
     pub async fn get_status(&self) -> SessionStatusPayload {
         let mut inner = self.inner.lock().await;
         Self::prune_status_message(&mut inner);
 
-        let elapsed_ms = if inner.stage == SessionStage::Recording {
+        let elapsed_ms = if matches!(inner.stage, SessionStage::Recording | SessionStage::Meeting) {
             inner
                 .recording_started_at
                 .map(|t| t.elapsed())
@@ -162,13 +267,70 @@ impl SessionController {
             is_recording: inner.stage == SessionStage::Recording,
             elapsed_ms,
             error: inner.status_message.clone(),
+            error_info: inner.status_error.clone(),
             last_text_preview,
             last_text_available: inner
                 .last_text
                 .as_ref()
                 .map(|t| !t.is_empty())
                 .unwrap_or(false),
+            recording_disabled: self.recording_disabled(),
+            quiet_mode: crate::quiet_mode(),
+            meeting_chunks: inner.meeting_chunks,
+            accessible_label: accessible_label(
+                inner.stage,
+                inner.status_message.as_deref(),
+                inner.meeting_chunks,
+            ),
+        }
+    }
+
+    /// No session is running, so the next toggle would start one.
+    pub async fn is_idle(&self) -> bool {
+        matches!(
+            self.inner.lock().await.stage,
+            SessionStage::Idle
+                | SessionStage::Error
+                | SessionStage::Cancelled
+                | SessionStage::Success
+        )
+    }
+
+    /// A dictation or meeting is capturing audio, so the next toggle would stop it.
+    pub async fn is_recording(&self) -> bool {
+        matches!(
+            self.inner.lock().await.stage,
+            SessionStage::Recording | SessionStage::Meeting
+        )
+    }
+
+    pub fn recording_disabled(&self) -> bool {
+        self.recording_disabled.load(Ordering::SeqCst)
+    }
+
+    /// Seed the flag from persisted state at startup (no side effects).
+    pub fn restore_recording_disabled(&self, disabled: bool) {
+        self.recording_disabled.store(disabled, Ordering::SeqCst);
+    }
+
+    /// Globally disable or re-enable recording. Disabling cancels an in-progress
+    /// recording and releases the microphone.
+    pub async fn set_recording_disabled(
+        &self,
+        app: &tauri::AppHandle,
+        svc: AppService,
+        disabled: bool,
+    ) {
+        self.recording_disabled.store(disabled, Ordering::SeqCst);
+        log::info!("recording disabled: {disabled}");
+
+        let stage = { self.inner.lock().await.stage };
+        if disabled && matches!(stage, SessionStage::Recording | SessionStage::Meeting) {
+            let _ = self.cancel_recording(app, svc.clone()).await;
         }
+        svc.set_recording_disabled(disabled).await;
+
+        self.emit_status(app).await;
     }
 
     pub async fn mark_overlay_ready(&self, app: &tauri::AppHandle) {
@@ -185,49 +347,21 @@ impl SessionController {
     }
 
     pub async fn emit_status(&self, app: &tauri::AppHandle) {
-        let payload = {
-            let mut inner = self.inner.lock().await;
-            Self::prune_status_message(&mut inner);
-
-            let elapsed_ms = if inner.stage == SessionStage::Recording {
-                inner
-                    .recording_started_at
-                    .map(|t| t.elapsed())
-                    .map(|d| d.as_millis() as u64)
-            } else {
-                inner.recording_elapsed_ms
-            };
-
-            let last_text_preview = inner.last_text.as_ref().map(|t| preview_text(t));
-
-            SessionStatusPayload {
-                stage: inner.stage,
-                stage_label: stage_label(inner.stage).into(),
-                is_recording: inner.stage == SessionStage::Recording,
-                elapsed_ms,
-                error: inner.status_message.clone(),
-                last_text_preview,
-                last_text_available: inner
-                    .last_text
-                    .as_ref()
-                    .map(|t| !t.is_empty())
-                    .unwrap_or(false),
-            }
-        };
+        let payload = self.get_status().await;
 
         // Best-effort: emit directly to the overlay window for reliability.
         if let Some(w) = app.get_webview_window("recording_overlay") {
-            if let Err(e) = w.emit(crate::EVENT_SESSION_STATUS, payload.clone()) {
+            if let Err(e) = events::emit(&w, AppEvent::SessionStatus(payload.clone())) {
                 log::warn!("emit session status to overlay failed: {e}");
             }
         }
 
-        if let Err(e) = app.emit(crate::EVENT_SESSION_STATUS, payload) {
+        if let Err(e) = events::emit(app, AppEvent::SessionStatus(payload)) {
             log::warn!("emit session status failed: {e}");
         }
     }
 
-    #[cfg(any(windows, target_os = "macos"))]
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
     pub async fn emit_mic_level(&self, app: &tauri::AppHandle, rms: f32, peak: f32) {
         let payload = MicLevelPayload {
             rms: rms.clamp(0.0, 1.0),
@@ -235,12 +369,12 @@ impl SessionController {
         };
 
         if let Some(w) = app.get_webview_window("recording_overlay") {
-            if let Err(e) = w.emit(crate::EVENT_MIC_LEVEL, payload.clone()) {
+            if let Err(e) = events::emit(&w, AppEvent::MicLevel(payload.clone())) {
                 log::warn!("emit mic level to overlay failed: {e}");
             }
         }
 
-        if let Err(e) = app.emit(crate::EVENT_MIC_LEVEL, payload) {
+        if let Err(e) = events::emit(app, AppEvent::MicLevel(payload)) {
             log::warn!("emit mic level failed: {e}");
         }
     }
@@ -253,7 +387,9 @@ impl SessionController {
 
             // If we're leaving Recording, preserve the final elapsed time so the overlay timer
             // doesn't jump back to 0 immediately.
-            if inner.stage == SessionStage::Recording && stage != SessionStage::Recording {
+            let recording =
+                |s: SessionStage| matches!(s, SessionStage::Recording | SessionStage::Meeting);
+            if recording(inner.stage) && !recording(stage) {
                 if let Some(start) = inner.recording_started_at {
                     inner.recording_elapsed_ms = Some(start.elapsed().as_millis() as u64);
                 }
@@ -264,14 +400,21 @@ impl SessionController {
 
             if prev != stage {
                 log::info!("session stage: {:?} -> {:?}", prev, stage);
+
+                #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+                play_stage_cue(prev, stage);
             }
 
-            if stage == SessionStage::Recording {
+            if recording(stage) {
                 inner.session_id = inner.session_id.wrapping_add(1);
+                inner.meeting_chunks = (stage == SessionStage::Meeting).then_some(0);
                 inner.recording_started_at = Some(Instant::now());
+                inner.session_started_at = inner.recording_started_at;
+                inner.timeline.clear();
                 inner.recording_elapsed_ms = None;
                 inner.last_text = None;
                 inner.status_message = None;
+                inner.status_error = None;
                 inner.status_message_expires_at = None;
             }
 
@@ -279,12 +422,25 @@ impl SessionController {
                 inner.recording_started_at = None;
                 inner.recording_elapsed_ms = None;
                 inner.status_message = None;
+                inner.status_error = None;
                 inner.status_message_expires_at = None;
             }
+
+            if prev != stage {
+                if let Some(start) = inner.session_started_at {
+                    let at_ms = start.elapsed().as_millis() as u64;
+                    inner.timeline.push(StageTiming { stage, at_ms });
+                }
+            }
         }
         self.emit_status(app).await;
     }
 
+    /// Stage transitions of the current (or most recent) session.
+    pub async fn last_session_timeline(&self) -> Vec<StageTiming> {
+        self.inner.lock().await.timeline.clone()
+    }
+
     #[allow(dead_code)]
     pub async fn set_last_text(&self, text: Option<String>) {
         let mut inner = self.inner.lock().await;
@@ -297,6 +453,51 @@ impl SessionController {
         }
     }
 
+    /// For the stages after recording (processing, cancelling). Minimal mode keeps the
+    /// overlay out of sight for those; errors still bring it up.
+    fn show_overlay_after_recording(app: &tauri::AppHandle) {
+        if crate::overlay_mode() != OverlayMode::Minimal {
+            Self::show_overlay(app);
+        } else if let Some(w) = app.get_webview_window("recording_overlay") {
+            let _ = w.hide();
+        }
+    }
+
+    /// For results that arrive after the user moved on; skipped in quiet mode, where
+    /// they stay in History and the tray instead.
+    fn pop_up_overlay(app: &tauri::AppHandle) {
+        if !crate::quiet_mode() {
+            Self::show_overlay(app);
+        }
+    }
+
+    /// For a webview that reloaded (devtools, crash recovery): bring the overlay back if the
+    /// current session (or persistent mode) needs it and push the current status to every
+    /// window. A finished or dismissed result is not brought back.
+    pub async fn resync_overlay(&self, app: &tauri::AppHandle) {
+        let stage = { self.inner.lock().await.stage };
+        let needs_overlay = match crate::overlay_mode() {
+            OverlayMode::Persistent => true,
+            OverlayMode::Minimal => {
+                matches!(stage, SessionStage::Recording | SessionStage::Meeting)
+            }
+            OverlayMode::Auto => matches!(
+                stage,
+                SessionStage::Recording
+                    | SessionStage::Meeting
+                    | SessionStage::Finalizing
+                    | SessionStage::Transcribing
+                    | SessionStage::Enhancing
+                    | SessionStage::Inserting
+            ),
+        };
+        if needs_overlay {
+            self.show_overlay_and_sync(app).await;
+        } else {
+            self.emit_status(app).await;
+        }
+    }
+
     async fn show_overlay_and_sync(&self, app: &tauri::AppHandle) {
         // On some platforms a hidden webview may miss events; showing first and
         // then emitting status makes the overlay self-healing.
@@ -318,7 +519,29 @@ impl SessionController {
             inner.session_id == session_id && matches!(inner.stage, SessionStage::Success | SessionStage::Cancelled)
         };
 
-        if should_hide {
+        if !should_hide {
+            return;
+        }
+        // A persistent overlay stays up and goes back to Idle instead.
+        if crate::overlay_mode() == OverlayMode::Persistent {
+            self.set_stage(app, SessionStage::Idle).await;
+        } else if let Some(w) = app.get_webview_window("recording_overlay") {
+            let _ = w.hide();
+        }
+    }
+
+    /// Apply a changed `overlay.mode`: a persistent overlay comes up right away; otherwise it
+    /// is hidden unless a session is still using it.
+    pub async fn apply_overlay_mode(&self, app: &tauri::AppHandle) {
+        if crate::overlay_mode() == OverlayMode::Persistent {
+            self.show_overlay_and_sync(app).await;
+            return;
+        }
+        let stage = { self.inner.lock().await.stage };
+        if matches!(
+            stage,
+            SessionStage::Idle | SessionStage::Success | SessionStage::Cancelled
+        ) {
             if let Some(w) = app.get_webview_window("recording_overlay") {
                 let _ = w.hide();
             }
@@ -329,16 +552,71 @@ impl SessionController {
         {
             let mut inner = self.inner.lock().await;
             inner.status_message = Some(message);
+            inner.status_error = None;
+            inner.status_message_expires_at = Some(Instant::now() + ttl);
+        }
+        self.emit_status(app).await;
+    }
+
+    async fn set_status_error(&self, app: &tauri::AppHandle, error: VoicewinError, ttl: Duration) {
+        {
+            let mut inner = self.inner.lock().await;
+            inner.status_message = Some(error.user_message.clone());
+            inner.status_error = Some(error);
             inner.status_message_expires_at = Some(Instant::now() + ttl);
         }
         self.emit_status(app).await;
     }
 
+    /// Warn when ElevenLabs credits run low, checking at most once per `QUOTA_CHECK_INTERVAL`.
+    /// The request runs in the background so recording doesn't wait on it.
+    async fn check_elevenlabs_quota(
+        &self,
+        app: &tauri::AppHandle,
+        svc: &AppService,
+        cfg: &voicewin_core::config::AppConfig,
+    ) {
+        {
+            let mut inner = self.inner.lock().await;
+            if inner
+                .quota_checked_at
+                .is_some_and(|t| t.elapsed() < Self::QUOTA_CHECK_INTERVAL)
+            {
+                return;
+            }
+            inner.quota_checked_at = Some(Instant::now());
+        }
+
+        let controller = self.clone();
+        let app = app.clone();
+        let svc = svc.clone();
+        let cfg = cfg.clone();
+        tauri::async_runtime::spawn(async move {
+            match svc.elevenlabs_quota_warning(&cfg).await {
+                Ok(Some(warning)) => {
+                    let msg = warning.message();
+                    log::warn!("{msg}");
+                    let _ = events::emit(&app, AppEvent::ElevenLabsQuotaLow(warning));
+                    controller
+                        .set_status_message(&app, msg, Self::QUOTA_TOAST_TTL)
+                        .await;
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("ElevenLabs quota check failed: {e:#}"),
+            }
+        });
+    }
+
     #[allow(dead_code)]
-    async fn mark_error(&self, app: &tauri::AppHandle, error: String) {
-        log::error!("session error: {error}");
+    async fn mark_error(&self, app: &tauri::AppHandle, error: impl Into<VoicewinError>) {
+        let error = error.into();
+        log::error!(
+            "session error ({:?}): {}",
+            error.kind,
+            crate::redact_for_log(error.detail.as_deref().unwrap_or(&error.user_message))
+        );
         self.set_stage(app, SessionStage::Error).await;
-        self.set_status_message(app, error, Duration::from_secs(6))
+        self.set_status_error(app, error, Duration::from_secs(6))
             .await;
 
         // Always surface errors in the HUD.
@@ -348,9 +626,11 @@ impl SessionController {
     pub async fn cancel_recording(&self, app: &tauri::AppHandle, svc: AppService) -> ToggleResult {
         let stage = { self.inner.lock().await.stage };
         match stage {
-            SessionStage::Recording => {
-                #[cfg(any(windows, target_os = "macos"))]
+            SessionStage::Recording | SessionStage::Meeting => {
+                #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
                 {
+                    self.inner.lock().await.meeting_signals = None;
+
                     // Stop any realtime streaming immediately.
                     let rt = {
                         let mut inner = self.inner.lock().await;
@@ -366,17 +646,18 @@ impl SessionController {
                     }
 
                     if let Err(e) = svc.cancel_recording().await {
-                        self.mark_error(app, e.to_string()).await;
+                        let err = voicewin_appcore::service::user_facing_audio_error(&e);
+                        self.mark_error(app, err.clone()).await;
                         return ToggleResult {
                             stage: "error".into(),
                             final_text: None,
-                            error: Some(e.to_string()),
+                            error: Some(err),
                             is_recording: false,
                         };
                     }
                 }
 
-                #[cfg(not(any(windows, target_os = "macos")))]
+                #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
                 {
                     let _ = svc;
                 }
@@ -394,7 +675,7 @@ impl SessionController {
                 };
 
                 // Show first to avoid missing the stage update.
-                Self::show_overlay(app);
+                Self::show_overlay_after_recording(app);
                 self.set_stage(app, SessionStage::Cancelled).await;
 
                 {
@@ -424,7 +705,7 @@ impl SessionController {
             | SessionStage::Inserting => {
                 let _ = svc;
 
-                #[cfg(any(windows, target_os = "macos"))]
+                #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
                 {
                     // Defensive: if any realtime session is still around, shut it down.
                     let rt = {
@@ -453,7 +734,7 @@ impl SessionController {
                 }
 
                 // Show first to avoid missing the stage update.
-                Self::show_overlay(app);
+                Self::show_overlay_after_recording(app);
                 self.set_stage(app, SessionStage::Cancelled).await;
 
                 {
@@ -484,13 +765,64 @@ impl SessionController {
                 ToggleResult {
                     stage: "idle".into(),
                     final_text: None,
-                    error: Some("not recording".into()),
+                    error: Some(VoicewinError::new(ErrorKind::Audio, "not recording")),
                     is_recording: false,
                 }
             }
         }
     }
 
+    /// Briefly explain on the overlay why a session didn't start, then hide it again.
+    async fn refuse_start(&self, app: &tauri::AppHandle, stage: &str, msg: String) -> ToggleResult {
+        let err = VoicewinError::new(ErrorKind::Config, msg);
+        self.set_status_error(app, err.clone(), Self::REFUSAL_TOAST_TTL)
+            .await;
+        self.show_overlay_and_sync(app).await;
+
+        // Hide again once the message expires, unless a session started meanwhile.
+        let session_id = { self.inner.lock().await.session_id };
+        let controller = self.clone();
+        let app_handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Self::REFUSAL_TOAST_TTL).await;
+            let still_idle = {
+                let inner = controller.inner.lock().await;
+                inner.session_id == session_id && inner.stage != SessionStage::Recording
+            };
+            if still_idle && crate::overlay_mode() != OverlayMode::Persistent {
+                if let Some(w) = app_handle.get_webview_window("recording_overlay") {
+                    let _ = w.hide();
+                }
+            }
+        });
+
+        ToggleResult {
+            stage: stage.into(),
+            final_text: None,
+            error: Some(err),
+            is_recording: false,
+        }
+    }
+
+    /// Explanation to show when the foreground app is on the `blocked_apps` deny-list.
+    async fn blocked_app_message(svc: &AppService) -> Option<String> {
+        let cfg = svc.load_config().ok()?;
+        if cfg.blocked_apps.is_empty() {
+            return None;
+        }
+        let app_id = svc.get_foreground_app().await.ok()?;
+        cfg.blocked_app_matcher(&app_id)?;
+
+        let name = app_id
+            .process_name
+            .as_ref()
+            .map(|p| p.0.clone())
+            .unwrap_or_else(|| "this app".to_string());
+        Some(format!(
+            "Dictation is blocked in {name}. Edit blocked apps in Settings to change this."
+        ))
+    }
+
     pub async fn toggle_recording(&self, app: &tauri::AppHandle, svc: AppService) -> ToggleResult {
         // Minimal controller behavior:
         // - idle -> start recording
@@ -499,10 +831,40 @@ impl SessionController {
         let stage = { self.inner.lock().await.stage };
 
         match stage {
+            SessionStage::Idle
+            | SessionStage::Error
+            | SessionStage::Cancelled
+            | SessionStage::Success
+                if self.recording_disabled() =>
+            {
+                let _ = svc;
+                self.refuse_start(
+                    app,
+                    "disabled",
+                    "Microphone is disabled. Re-enable it from the tray menu.".to_string(),
+                )
+                .await
+            }
             SessionStage::Idle | SessionStage::Error | SessionStage::Cancelled | SessionStage::Success => {
+                // Deny-list check: never open the mic over password managers, banking apps, etc.
+                if let Some(msg) = Self::blocked_app_message(&svc).await {
+                    return self.refuse_start(app, "blocked", msg).await;
+                }
+
+                // Capture the target before anything of ours can take focus.
+                let target_app = svc.get_foreground_app().await.ok();
+
+                // Move the HUD to the configured monitor (e.g. the one the user is dictating on).
+                crate::position_overlay(app);
+
                 // Show first so the overlay doesn't miss the stage update.
                 Self::show_overlay(app);
                 self.set_stage(app, SessionStage::Recording).await;
+                {
+                    let mut inner = self.inner.lock().await;
+                    inner.target_app = target_app;
+                    inner.stt_fallback = None;
+                }
 
                 // Snapshot the current session id for the watchdog.
                 let session_id = { self.inner.lock().await.session_id };
@@ -535,7 +897,7 @@ impl SessionController {
                     });
                 }
 
-                #[cfg(any(windows, target_os = "macos"))]
+                #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
                 {
                     let controller = self.clone();
                     let app_handle = app.clone();
@@ -545,7 +907,14 @@ impl SessionController {
                     // NOTE: Use effective config so Power Mode profiles can enable realtime.
                     let mut wants_realtime = false;
                     let mut effective_language: Option<String> = None;
-                    if let Ok(cfg) = svc.load_config() {
+                    let mut realtime_vad = voicewin_core::stt::RealtimeVadSettings::default();
+                    let mut live_typing = false;
+                    let mut preconnect = voicewin_core::config::RealtimePreconnect::Off;
+                    let mut stt_provider = String::new();
+                    let mut stt_probe = None;
+                    let mut session_cfg = None;
+                    if let Ok(cfg) = svc.load_session_config() {
+                        preconnect = cfg.performance.realtime_preconnect;
                         let app_id = svc
                             .get_foreground_app()
                             .await
@@ -554,34 +923,48 @@ impl SessionController {
                             &cfg.defaults,
                             &cfg.profiles,
                             &app_id,
-                            &voicewin_core::power_mode::EphemeralOverrides::default(),
+                            &voicewin_core::power_mode::EphemeralOverrides {
+                                forced_profile_id: svc.forced_profile(),
+                                ..Default::default()
+                            },
                         );
+                        // Probing a fallback can take a keyring read and a network round trip;
+                        // run it while the microphone opens so the first words are not clipped.
+                        stt_probe = Some(tauri::async_runtime::spawn({
+                            let svc = svc.clone();
+                            let cfg = cfg.clone();
+                            let (provider, model) =
+                                (eff.stt_provider.clone(), eff.stt_model.clone());
+                            async move { svc.resolve_session_stt(&cfg, &provider, &model).await }
+                        }));
                         wants_realtime = voicewin_core::stt::is_elevenlabs_realtime_selected(
                             &eff.stt_provider,
                             &eff.stt_model,
                         );
+                        stt_provider = eff.stt_provider.clone();
+                        // Typing as we go only makes sense where the text would be typed
+                        // or pasted at the end anyway.
+                        live_typing = cfg.insertion.live_typing
+                            && eff.insert_target == voicewin_core::types::InsertTarget::Foreground
+                            && eff.insert_mode != voicewin_core::types::InsertMode::ClipboardOnly;
                         effective_language = Some(eff.language);
+                        realtime_vad = eff.realtime_vad;
+                        session_cfg = Some(cfg);
                     }
 
-                    let eleven_key = if wants_realtime {
-                        get_secret(SecretKey::ElevenLabsApiKey).ok().flatten().unwrap_or_default()
-                    } else {
-                        String::new()
-                    };
-
-                    if wants_realtime && eleven_key.trim().is_empty() {
-                        let msg = "ElevenLabs is selected but no API key is set. Open Settings -> ElevenLabs.".to_string();
-                        controller.mark_error(&app_handle, msg.clone()).await;
-                        return ToggleResult {
-                            stage: "error".into(),
-                            final_text: None,
-                            error: Some(msg),
-                            is_recording: false,
-                        };
+                    // Start the websocket handshake now so it overlaps opening the microphone.
+                    // The previous session's config is a good guess; `connect` below falls back
+                    // to a fresh connection if the device rate or key changed.
+                    if preconnect == voicewin_core::config::RealtimePreconnect::Off {
+                        controller.realtime_pool.discard_warm().await;
+                    } else if wants_realtime {
+                        controller.realtime_pool.prewarm_last().await;
                     }
 
-                    // Realtime streaming plumbing.
-                    let streaming_enabled = Arc::new(AtomicBool::new(wants_realtime));
+                    // Realtime streaming plumbing. Chunks are queued while the probe runs, in
+                    // case it falls back to realtime; they are dropped below if it does not.
+                    let streaming_enabled =
+                        Arc::new(AtomicBool::new(wants_realtime || stt_probe.is_some()));
                     let dropped_chunks = Arc::new(AtomicU64::new(0));
                     let (audio_tx, mut audio_rx) = tokio::sync::mpsc::channel::<Vec<f32>>(64);
 
@@ -658,12 +1041,69 @@ impl SessionController {
                         .await
                     {
                         log::error!("start_recording failed: {e}");
-                        let msg = voicewin_appcore::service::user_facing_audio_error(&e);
-                        controller.mark_error(&app_handle, msg.clone()).await;
+                        let err = voicewin_appcore::service::user_facing_audio_error(&e);
+                        controller.mark_error(&app_handle, err.clone()).await;
+                        return ToggleResult {
+                            stage: "error".into(),
+                            final_text: None,
+                            error: Some(err),
+                            is_recording: false,
+                        };
+                    }
+
+                    // The microphone is open; settle the STT choice the probe made meanwhile.
+                    let downgrade = match stt_probe {
+                        Some(probe) => probe.await.ok().flatten(),
+                        None => None,
+                    };
+                    if let Some(downgrade) = downgrade {
+                        log::warn!("{}", downgrade.message());
+                        let _ = crate::events::emit(
+                            &app_handle,
+                            crate::events::AppEvent::SttFallback(downgrade.clone()),
+                        );
+                        wants_realtime = voicewin_core::stt::is_elevenlabs_realtime_selected(
+                            &downgrade.to_provider,
+                            &downgrade.to_model,
+                        );
+                        stt_provider = downgrade.to_provider.clone();
+                        controller.inner.lock().await.stt_fallback = Some(downgrade);
+                    }
+                    streaming_enabled.store(wants_realtime, Ordering::Relaxed);
+                    if !wants_realtime {
+                        // Batch STT reads the recording itself; nothing queued is needed.
+                        while audio_rx.try_recv().is_ok() {}
+                        dropped_chunks.store(0, Ordering::Relaxed);
+                    }
+                    if let Some(cfg) = session_cfg
+                        .as_ref()
+                        .filter(|_| stt_provider == voicewin_core::stt::STT_PROVIDER_ELEVENLABS)
+                    {
+                        controller
+                            .check_elevenlabs_quota(&app_handle, &svc, cfg)
+                            .await;
+                    }
+
+                    let eleven_key = if wants_realtime {
+                        get_secret(SecretKey::ElevenLabsApiKey)
+                            .ok()
+                            .flatten()
+                            .unwrap_or_default()
+                    } else {
+                        String::new()
+                    };
+
+                    if wants_realtime && eleven_key.trim().is_empty() {
+                        let _ = svc.cancel_recording().await;
+                        let err = VoicewinError::new(
+                            ErrorKind::Config,
+                            "ElevenLabs is selected but no API key is set. Open Settings -> ElevenLabs.",
+                        );
+                        controller.mark_error(&app_handle, err.clone()).await;
                         return ToggleResult {
                             stage: "error".into(),
                             final_text: None,
-                            error: Some(msg),
+                            error: Some(err),
                             is_recording: false,
                         };
                     }
@@ -671,12 +1111,13 @@ impl SessionController {
                     // Start ElevenLabs realtime session after the recorder is opened, so we can
                     // determine the device sample rate.
                     if wants_realtime {
-                        let sr = svc
-                            .recording_sample_rate_hz()
-                            .await
-                            .unwrap_or(16_000);
+                        let sr = svc.recording_sample_rate_hz().await.unwrap_or(16_000);
 
-                        let mut rt_cfg = match ElevenLabsRealtimeConfig::production(eleven_key, sr) {
+                        let mut rt_cfg = match ElevenLabsRealtimeConfig::production(
+                            eleven_key,
+                            sr,
+                            &realtime_vad,
+                        ) {
                             Ok(c) => c,
                             Err(e) => {
                                 log::warn!("elevenlabs realtime disabled: {e}");
@@ -704,30 +1145,54 @@ impl SessionController {
                             other => Some(other.to_string()),
                         };
 
-                        match spawn_realtime_session(rt_cfg).await {
+                        match controller.realtime_pool.connect(rt_cfg).await {
                             Ok((handle, mut events)) => {
                                 let last_error = Arc::new(StdMutex::new(None));
                                 let last_warning = Arc::new(StdMutex::new(None));
                                 let session_id_for_realtime = { controller.inner.lock().await.session_id };
 
-                                // Sender task: convert f32 -> PCM16 and stream to WS.
+                                // Sender task: convert f32 -> PCM16 and stream to WS. With manual
+                                // commits, our own pause detection decides when a segment is final.
                                 let handle_for_sender = handle.clone();
                                 let streaming_enabled_for_sender = streaming_enabled.clone();
+                                let mut pauses = (realtime_vad.commit_strategy
+                                    == voicewin_core::stt::RealtimeCommitStrategy::Manual)
+                                    .then(|| {
+                                        voicewin_audio::PauseDetector::new(
+                                            sr,
+                                            realtime_vad.min_speech_duration_ms,
+                                            realtime_vad.vad_silence_threshold_ms,
+                                        )
+                                    });
                                 let sender_task = tauri::async_runtime::spawn(async move {
                                     while let Some(chunk) = audio_rx.recv().await {
                                         if !streaming_enabled_for_sender.load(Ordering::Relaxed) {
                                             continue;
                                         }
+                                        let commit =
+                                            pauses.as_mut().is_some_and(|p| p.push(&chunk));
                                         let pcm = pcm_s16le_from_f32(&chunk);
-                                        if !handle_for_sender.send_audio_chunk(pcm).await {
+                                        if !handle_for_sender
+                                            .send_audio_chunk_with_commit(pcm, commit)
+                                            .await
+                                        {
                                             // Realtime session died; disable streaming so the audio callback stops enqueueing.
-                                            streaming_enabled_for_sender.store(false, Ordering::Relaxed);
+                                            streaming_enabled_for_sender
+                                                .store(false, Ordering::Relaxed);
                                             break;
                                         }
                                     }
                                 });
 
-                                // Receiver task: update overlay with live preview.
+                                // Receiver task: update overlay with live preview, and type committed
+                                // segments right away with live typing.
+                                let live_typing = live_typing.then(|| {
+                                    Arc::new(StdMutex::new(
+                                        voicewin_core::live_typing::LiveTyping::default(),
+                                    ))
+                                });
+                                let live_typing_for_receiver = live_typing.clone();
+                                let svc_for_receiver = svc.clone();
                                 let receiver_controller = controller.clone();
                                 let receiver_app = app_handle.clone();
                                 let streaming_enabled_for_receiver = streaming_enabled.clone();
@@ -744,6 +1209,31 @@ impl SessionController {
                                         match evt {
                                             RealtimeEvent::SessionStarted { .. } => {}
                                             RealtimeEvent::LiveText { committed, partial } => {
+                                                if let Some(live) =
+                                                    live_typing_for_receiver.as_ref()
+                                                {
+                                                    // Advance before typing, so a stop that aborts
+                                                    // this task mid-way doesn't type it twice.
+                                                    let (segment, first) = {
+                                                        let mut guard = live
+                                                            .lock()
+                                                            .unwrap_or_else(|p| p.into_inner());
+                                                        let first = guard.typed().is_empty();
+                                                        (guard.advance(&committed), first)
+                                                    };
+                                                    if let Some(segment) = segment {
+                                                        if let Err(e) = svc_for_receiver
+                                                            .type_live_segment(&segment, first)
+                                                            .await
+                                                        {
+                                                            log::warn!("live typing failed: {e}");
+                                                            live.lock()
+                                                                .unwrap_or_else(|p| p.into_inner())
+                                                                .rewind(&segment);
+                                                        }
+                                                    }
+                                                }
+
                                                 let c = committed.trim();
                                                 let p = partial.trim();
                                                 let live = if c.is_empty() {
@@ -761,10 +1251,19 @@ impl SessionController {
                                                 receiver_controller.set_last_text(Some(live)).await;
                                                 receiver_controller.emit_status(&receiver_app).await;
                                             }
-                                            RealtimeEvent::Warning { kind: _, message } => {
+                                            RealtimeEvent::Warning { kind, message } => {
                                                 // Persist the latest warning so stop-time History can reflect it.
-                                                if let Ok(mut guard) = last_warning_for_receiver.lock() {
-                                                    *guard = Some(message.clone());
+                                                // A recovered connection drop is only worth a HUD notice.
+                                                let transient = matches!(
+                                                    kind.as_str(),
+                                                    "reconnecting" | "reconnected"
+                                                );
+                                                if !transient {
+                                                    if let Ok(mut guard) =
+                                                        last_warning_for_receiver.lock()
+                                                    {
+                                                        *guard = Some(message.clone());
+                                                    }
                                                 }
                                                 receiver_controller
                                                     .set_status_message(
@@ -809,6 +1308,7 @@ impl SessionController {
                                         dropped_chunks: dropped_chunks.clone(),
                                         last_error,
                                         last_warning,
+                                        live_typing,
                                     });
                                 }
                             }
@@ -834,16 +1334,21 @@ impl SessionController {
                     is_recording: true,
                 }
             }
+            SessionStage::Meeting => self.stop_meeting(app).await,
             SessionStage::Recording => {
                 // Show first so the overlay doesn't miss the stage update.
-                Self::show_overlay(app);
+                Self::show_overlay_after_recording(app);
 
-                #[cfg(any(windows, target_os = "macos"))]
+                #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
                 {
                     // Stop any realtime streaming for this session.
-                    let realtime = {
+                    let (realtime, target_app, stt_fallback) = {
                         let mut inner = self.inner.lock().await;
-                        inner.realtime_stt.take()
+                        (
+                            inner.realtime_stt.take(),
+                            inner.target_app.take(),
+                            inner.stt_fallback.take(),
+                        )
                     };
 
                     if realtime.is_some() {
@@ -862,11 +1367,12 @@ impl SessionController {
                         Ok(a) => a,
                         Err(e) => {
                             log::error!("stop_recording failed: {e}");
-                            self.mark_error(app, e.to_string()).await;
+                            let err = voicewin_appcore::service::user_facing_audio_error(&e);
+                            self.mark_error(app, err.clone()).await;
                             return ToggleResult {
                                 stage: "error".into(),
                                 final_text: None,
-                                error: Some(e.to_string()),
+                                error: Some(err),
                                 is_recording: false,
                             };
                         }
@@ -876,12 +1382,16 @@ impl SessionController {
                     let ms = (n as f64 / 16_000.0) * 1000.0;
                     log::info!("captured audio: {n} samples (~{ms:.0}ms)");
                     if n < 160 {
-                        let msg = "No audio captured from the microphone.".to_string();
-                        self.mark_error(app, msg.clone()).await;
+                        let err = VoicewinError::new(
+                            ErrorKind::Audio,
+                            "No audio captured from the microphone.",
+                        )
+                        .with_retryable(true);
+                        self.mark_error(app, err.clone()).await;
                         return ToggleResult {
                             stage: "error".into(),
                             final_text: None,
-                            error: Some(msg),
+                            error: Some(err),
                             is_recording: false,
                         };
                     }
@@ -903,6 +1413,7 @@ impl SessionController {
                         // If it fails, fall back to batch STT using the captured audio.
                         let mut transcript_override = String::new();
                         let mut warning: Option<String> = None;
+                        let mut live_typed: Option<String> = None;
 
                         fn merge_warning(dst: &mut Option<String>, msg: String) {
                             let msg = msg.trim().to_string();
@@ -917,6 +1428,10 @@ impl SessionController {
                             };
                         }
 
+                        if let Some(downgrade) = stt_fallback.as_ref() {
+                            merge_warning(&mut warning, downgrade.message());
+                        }
+
                         if let Some(rt) = realtime {
                             let dropped = rt.dropped_chunks.load(Ordering::Relaxed);
                             if dropped > 0 {
@@ -975,8 +1490,34 @@ impl SessionController {
 
                             rt.receiver_task.abort();
                             rt.handle.shutdown().await;
+
+                            live_typed = rt
+                                .live_typing
+                                .as_ref()
+                                .map(|l| {
+                                    l.lock()
+                                        .unwrap_or_else(|p| p.into_inner())
+                                        .typed()
+                                        .to_string()
+                                })
+                                .filter(|t| !t.is_empty());
+
+                            let keep_warm = svc_for_task.load_config().is_ok_and(|c| {
+                                c.performance.realtime_preconnect
+                                    == voicewin_core::config::RealtimePreconnect::KeepWarm
+                            });
+                            if keep_warm {
+                                controller.realtime_pool.prewarm_last().await;
+                            }
                         }
 
+                        // Where the user was dictating: Power Mode and insertion aim there, and
+                        // failures can tell whether they've moved on (and would miss the HUD).
+                        let target_app = match target_app {
+                            Some(app) => Some(app),
+                            None => svc_for_task.get_foreground_app().await.ok(),
+                        };
+
                         let using_override = !transcript_override.trim().is_empty();
 
                         let res = svc_for_task
@@ -985,6 +1526,13 @@ impl SessionController {
                                 voicewin_runtime::ipc::RunSessionRequest {
                                     transcript: transcript_override,
                                     warning,
+                                    live_typed,
+                                    stt_provider: stt_fallback
+                                        .as_ref()
+                                        .map(|d| d.to_provider.clone()),
+                                    stt_model: stt_fallback.map(|d| d.to_model),
+                                    target_app: target_app.clone(),
+                                    ..Default::default()
                                 },
                                 audio,
                                 move |stage| {
@@ -1034,12 +1582,67 @@ impl SessionController {
                         }
 
                         match res {
+                            Ok(r) if r.stage == "cancelled" => {
+                                // Nothing was said; keep the previous last text and say why the
+                                // overlay went away.
+                                let msg = r
+                                    .error
+                                    .map(|e| e.user_message)
+                                    .unwrap_or_else(|| "No speech detected.".into());
+                                controller
+                                    .set_status_message(
+                                        &app_handle,
+                                        msg,
+                                        Duration::from_millis(1500),
+                                    )
+                                    .await;
+                                controller
+                                    .set_stage(&app_handle, SessionStage::Cancelled)
+                                    .await;
+
+                                let session_id = { controller.inner.lock().await.session_id };
+                                controller
+                                    .hide_overlay_if_session_matches(
+                                        &app_handle,
+                                        session_id,
+                                        Duration::from_millis(1500),
+                                    )
+                                    .await;
+                            }
                             Ok(r) => {
                                 controller.set_last_text(r.final_text.clone()).await;
 
                                 if r.stage == "done" {
+                                    log::info!(
+                                        "session done: stt={}/{} ({:?} ms), llm={} ({:?} ms)",
+                                        r.stt_provider.as_deref().unwrap_or("-"),
+                                        r.stt_model.as_deref().unwrap_or("-"),
+                                        r.transcription_ms,
+                                        r.llm_model.as_deref().unwrap_or("-"),
+                                        r.enhancement_ms,
+                                    );
+
                                     // If we have a non-fatal warning (e.g. enhancement failed), show it briefly.
-                                    let delay = if let Some(msg) = r.error.as_ref().filter(|s| !s.trim().is_empty()) {
+                                    let delay = if let Some(msg) = r
+                                        .error
+                                        .as_ref()
+                                        .map(|e| &e.user_message)
+                                        .filter(|s| !s.trim().is_empty())
+                                    {
+                                        if crate::notifications::user_moved_on(
+                                            &app_handle,
+                                            &svc_for_task,
+                                            target_app.as_ref(),
+                                        )
+                                        .await
+                                        {
+                                            crate::notifications::notify_failure(
+                                                &app_handle,
+                                                "VoiceWin: dictation inserted with a warning",
+                                                msg,
+                                                r.final_text.as_deref(),
+                                            );
+                                        }
                                         controller
                                             .set_status_message(
                                                 &app_handle,
@@ -1072,30 +1675,75 @@ impl SessionController {
                                     // Insertion failed but the text should be recoverable via History.
                                     controller.set_stage(&app_handle, SessionStage::Error).await;
 
-                                    // Preserve the underlying error string so the overlay can provide
-                                    // actionable shortcuts (e.g. Accessibility settings on macOS).
-                                    let msg = r
-                                        .error
-                                        .clone()
-                                        .unwrap_or_else(|| "Could not insert. Saved to History.".into());
+                                    // Keep the error kind so the overlay can offer the matching
+                                    // action (e.g. Accessibility settings on macOS, Copy).
+                                    let err = r.error.clone().unwrap_or_else(|| {
+                                        VoicewinError::new(
+                                            ErrorKind::Insertion,
+                                            "Could not insert. Saved to History.",
+                                        )
+                                    });
+                                    let msg = err.user_message.clone();
 
-                                    log::error!("session failed stage=failed: {msg}");
-                                    controller
-                                        .set_status_message(
+                                    log::error!(
+                                        "session failed stage=failed: {}",
+                                        crate::redact_for_log(&msg)
+                                    );
+
+                                    crate::notifications::remember_failed_text(
+                                        r.final_text.as_deref(),
+                                    );
+
+                                    // Check before re-showing the overlay below. A closed target
+                                    // always notifies: the text is waiting on the clipboard.
+                                    if err.kind == ErrorKind::TargetClosed {
+                                        crate::notifications::notify_failure(
                                             &app_handle,
-                                            msg,
-                                            Duration::from_secs(6),
-                                        )
+                                            "VoiceWin: text copied to the clipboard",
+                                            &msg,
+                                            r.final_text.as_deref(),
+                                        );
+                                    } else if crate::notifications::user_moved_on(
+                                        &app_handle,
+                                        &svc_for_task,
+                                        target_app.as_ref(),
+                                    )
+                                    .await
+                                    {
+                                        crate::notifications::notify_failure(
+                                            &app_handle,
+                                            "VoiceWin: could not insert text",
+                                            &msg,
+                                            r.final_text.as_deref(),
+                                        );
+                                    }
+                                    controller
+                                        .set_status_error(&app_handle, err, Duration::from_secs(6))
                                         .await;
-                                    Self::show_overlay(&app_handle);
+                                    Self::pop_up_overlay(&app_handle);
                                 } else {
                                     controller.set_stage(&app_handle, SessionStage::Error).await;
-                                    Self::show_overlay(&app_handle);
+                                    Self::pop_up_overlay(&app_handle);
                                 }
                             }
                             Err(e) => {
-                                controller.mark_error(&app_handle, e.to_string()).await;
-                                Self::show_overlay(&app_handle);
+                                let err = service_error(&e);
+                                if crate::notifications::user_moved_on(
+                                    &app_handle,
+                                    &svc_for_task,
+                                    target_app.as_ref(),
+                                )
+                                .await
+                                {
+                                    crate::notifications::notify_failure(
+                                        &app_handle,
+                                        "VoiceWin: dictation failed",
+                                        &err.user_message,
+                                        None,
+                                    );
+                                }
+                                controller.mark_error(&app_handle, err).await;
+                                Self::pop_up_overlay(&app_handle);
                             }
                         }
                     });
@@ -1116,7 +1764,7 @@ impl SessionController {
                     }
                 }
 
-                #[cfg(not(any(windows, target_os = "macos")))]
+                #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
                 {
                     let _ = svc;
                     self.set_stage(app, SessionStage::Error).await;
@@ -1129,7 +1777,10 @@ impl SessionController {
                     ToggleResult {
                         stage: "error".into(),
                         final_text: None,
-                        error: Some("recording supported on Windows and macOS".into()),
+                        error: Some(VoicewinError::new(
+                            ErrorKind::Audio,
+                            "recording supported on Windows and macOS",
+                        )),
                         is_recording: false,
                     }
                 }
@@ -1141,19 +1792,253 @@ impl SessionController {
                 ToggleResult {
                     stage: "busy".into(),
                     final_text: None,
-                    error: Some("busy".into()),
+                    error: Some(
+                        VoicewinError::new(ErrorKind::Internal, "busy").with_retryable(true),
+                    ),
+                    is_recording: stage == SessionStage::Recording,
+                }
+            }
+        }
+    }
+
+    /// Start a meeting transcription, or stop the running one.
+    pub async fn toggle_meeting(&self, app: &tauri::AppHandle, svc: AppService) -> ToggleResult {
+        let stage = { self.inner.lock().await.stage };
+
+        match stage {
+            SessionStage::Meeting => self.stop_meeting(app).await,
+            SessionStage::Idle
+            | SessionStage::Error
+            | SessionStage::Cancelled
+            | SessionStage::Success
+                if self.recording_disabled() =>
+            {
+                self.refuse_start(
+                    app,
+                    "disabled",
+                    "Microphone is disabled. Re-enable it from the tray menu.".to_string(),
+                )
+                .await
+            }
+            SessionStage::Idle
+            | SessionStage::Error
+            | SessionStage::Cancelled
+            | SessionStage::Success => self.start_meeting(app, svc).await,
+            _ => {
+                self.set_status_message(app, "busy".into(), Self::BUSY_TOAST_TTL)
+                    .await;
+                ToggleResult {
+                    stage: "busy".into(),
+                    final_text: None,
+                    error: Some(
+                        VoicewinError::new(ErrorKind::Internal, "busy").with_retryable(true),
+                    ),
                     is_recording: stage == SessionStage::Recording,
                 }
             }
         }
     }
+
+    async fn start_meeting(&self, app: &tauri::AppHandle, svc: AppService) -> ToggleResult {
+        crate::position_overlay(app);
+        Self::show_overlay(app);
+        self.set_stage(app, SessionStage::Meeting).await;
+
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        {
+            let (signals_tx, signals_rx) = tokio::sync::mpsc::unbounded_channel();
+
+            // The segmenter measures chunks by the time between level callbacks.
+            let segmenter = StdMutex::new((ChunkSegmenter::default(), Instant::now()));
+            let last_level_emit = StdMutex::new(Instant::now());
+            let controller = self.clone();
+            let app_handle = app.clone();
+            let cuts = signals_tx.clone();
+            let started = svc
+                .start_recording_with_level_callback(move |chunk: &[f32]| {
+                    let now = Instant::now();
+                    let (rms, peak) = compute_levels(chunk);
+
+                    {
+                        let mut guard = segmenter.lock().unwrap_or_else(|p| p.into_inner());
+                        let (segmenter, last) = &mut *guard;
+                        let dt_ms = now.duration_since(*last).as_millis() as u64;
+                        *last = now;
+                        if let Some(cut) = segmenter.observe(rms, dt_ms) {
+                            let _ = cuts.send(MeetingSignal::Cut(cut));
+                        }
+                    }
+
+                    let mut last_emit = last_level_emit.lock().unwrap_or_else(|p| p.into_inner());
+                    if now.duration_since(*last_emit) < Duration::from_millis(50) {
+                        return;
+                    }
+                    *last_emit = now;
+                    let controller = controller.clone();
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        controller.emit_mic_level(&app_handle, rms, peak).await;
+                    });
+                })
+                .await;
+
+            if let Err(e) = started {
+                log::error!("start_recording (meeting) failed: {e}");
+                let err = voicewin_appcore::service::user_facing_audio_error(&e);
+                self.mark_error(app, err.clone()).await;
+                return ToggleResult {
+                    stage: "error".into(),
+                    final_text: None,
+                    error: Some(err),
+                    is_recording: false,
+                };
+            }
+
+            let started_unix_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            let task = tauri::async_runtime::spawn(self.clone().run_meeting(
+                app.clone(),
+                svc,
+                signals_rx,
+                started_unix_ms,
+            ));
+
+            let mut inner = self.inner.lock().await;
+            inner.meeting_signals = Some(signals_tx);
+            if let Some(prev) = inner.processing_task.replace(task) {
+                prev.abort();
+            }
+
+            ToggleResult {
+                stage: "meeting".into(),
+                final_text: None,
+                error: None,
+                is_recording: true,
+            }
+        }
+
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+        {
+            let _ = svc;
+            self.set_stage(app, SessionStage::Error).await;
+            self.set_status_message(
+                app,
+                "recording supported on Windows and macOS".into(),
+                Duration::from_secs(3),
+            )
+            .await;
+            ToggleResult {
+                stage: "error".into(),
+                final_text: None,
+                error: Some(VoicewinError::new(
+                    ErrorKind::Audio,
+                    "recording supported on Windows and macOS",
+                )),
+                is_recording: false,
+            }
+        }
+    }
+
+    /// Close the microphone; the chunk loop transcribes what is left and then finishes.
+    async fn stop_meeting(&self, app: &tauri::AppHandle) -> ToggleResult {
+        Self::show_overlay_after_recording(app);
+        self.set_stage(app, SessionStage::Transcribing).await;
+
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        if let Some(signals) = self.inner.lock().await.meeting_signals.take() {
+            let _ = signals.send(MeetingSignal::Stop);
+        }
+
+        ToggleResult {
+            stage: "transcribing".into(),
+            final_text: None,
+            error: None,
+            is_recording: false,
+        }
+    }
+
+    /// Transcribe and append each chunk as it is cut, in order, until the meeting stops.
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    async fn run_meeting(
+        self,
+        app: tauri::AppHandle,
+        svc: AppService,
+        mut signals: tokio::sync::mpsc::UnboundedReceiver<MeetingSignal>,
+        started_unix_ms: i64,
+    ) {
+        let mut offset_ms = 0;
+        while let Some(signal) = signals.recv().await {
+            let (audio, transcribe, duration_ms) = match signal {
+                MeetingSignal::Cut(cut) => (
+                    svc.take_recording_chunk().await,
+                    cut.had_speech,
+                    cut.duration_ms,
+                ),
+                MeetingSignal::Stop => (svc.stop_recording().await, true, 0),
+            };
+            let audio = match audio {
+                Ok(a) => a,
+                Err(e) => {
+                    log::error!("meeting chunk capture failed: {e}");
+                    let _ = svc.cancel_recording().await;
+                    self.inner.lock().await.meeting_signals = None;
+                    self.mark_error(&app, voicewin_appcore::service::user_facing_audio_error(&e))
+                        .await;
+                    return;
+                }
+            };
+
+            if transcribe {
+                match svc
+                    .append_meeting_chunk(&audio, offset_ms, started_unix_ms)
+                    .await
+                {
+                    Ok(Some(line)) => {
+                        {
+                            let mut inner = self.inner.lock().await;
+                            inner.meeting_chunks = Some(inner.meeting_chunks.unwrap_or(0) + 1);
+                            inner.last_text = Some(line);
+                        }
+                        self.emit_status(&app).await;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        // Keep recording; one lost chunk shouldn't end the meeting.
+                        let err = service_error(&e);
+                        log::warn!(
+                            "meeting chunk failed: {}",
+                            crate::redact_for_log(&err.user_message)
+                        );
+                        self.set_status_message(
+                            &app,
+                            format!("A meeting chunk was not saved: {}", err.user_message),
+                            Duration::from_secs(4),
+                        )
+                        .await;
+                    }
+                }
+            }
+            offset_ms += duration_ms;
+
+            if matches!(signal, MeetingSignal::Stop) {
+                break;
+            }
+        }
+
+        self.set_stage(&app, SessionStage::Success).await;
+        let session_id = { self.inner.lock().await.session_id };
+        self.hide_overlay_if_session_matches(&app, session_id, Self::OVERLAY_HIDE_DELAY)
+            .await;
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ToggleResult {
     pub stage: String,
     pub final_text: Option<String>,
-    pub error: Option<String>,
+    pub error: Option<VoicewinError>,
     pub is_recording: bool,
 }
 
@@ -1161,6 +2046,7 @@ fn stage_label(stage: SessionStage) -> &'static str {
     match stage {
         SessionStage::Idle => "idle",
         SessionStage::Recording => "recording",
+        SessionStage::Meeting => "meeting",
         SessionStage::Finalizing => "finalizing",
         SessionStage::Transcribing => "transcribing",
         SessionStage::Enhancing => "enhancing",
@@ -1171,6 +2057,31 @@ fn stage_label(stage: SessionStage) -> &'static str {
     }
 }
 
+fn accessible_label(
+    stage: SessionStage,
+    message: Option<&str>,
+    meeting_chunks: Option<u32>,
+) -> String {
+    match stage {
+        SessionStage::Idle => "Ready".into(),
+        SessionStage::Recording => "Recording, press the hotkey again to stop".into(),
+        SessionStage::Meeting => match meeting_chunks.unwrap_or(0) {
+            1 => "Meeting transcription running, 1 part saved".into(),
+            n => format!("Meeting transcription running, {n} parts saved"),
+        },
+        SessionStage::Finalizing => "Finishing recording".into(),
+        SessionStage::Transcribing => "Transcribing".into(),
+        SessionStage::Enhancing => "Enhancing text".into(),
+        SessionStage::Inserting => "Inserting text".into(),
+        SessionStage::Success => "Done".into(),
+        SessionStage::Error => match message {
+            Some(msg) => format!("Error: {msg}"),
+            None => "Error".into(),
+        },
+        SessionStage::Cancelled => "Cancelled".into(),
+    }
+}
+
 fn preview_text(text: &str) -> String {
     const MAX: usize = 120;
     let trimmed = text.trim();
@@ -1181,7 +2092,7 @@ fn preview_text(text: &str) -> String {
     trimmed.chars().take(MAX).collect::<String>() + "…"
 }
 
-#[cfg(any(windows, target_os = "macos"))]
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
 fn pcm_s16le_from_f32(samples: &[f32]) -> Vec<u8> {
     // Convert mono float samples to PCM16 little-endian bytes for ElevenLabs realtime.
     let mut out = Vec::with_capacity(samples.len() * 2);
@@ -0,0 +1,123 @@
+// Hands-free activation: say the wake phrase ("Hey VoiceWin") to start dictating.
+//
+// Opt-in (`AppConfig::wake_word`). While enabled and the microphone isn't disabled, a
+// `WakeListener` keeps its own input stream open. Short utterances heard while no session
+// runs are transcribed with the local model and then dropped; only a match does anything.
+// The tray's check item and tooltip show whether we are listening.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::Manager;
+use tauri::menu::CheckMenuItem;
+use voicewin_audio::WakeListener;
+use voicewin_core::config::AppConfig;
+use voicewin_core::wake_word::heard_wake_phrase;
+
+use crate::AppState;
+
+pub const TRAY_WAKE_WORD_ID: &str = "toggle_wake_word";
+
+pub struct WakeWordMenuItem(pub CheckMenuItem<tauri::Wry>);
+
+struct Listening {
+    listener: WakeListener,
+    device: Option<String>,
+}
+
+static LISTENER: Mutex<Option<Listening>> = Mutex::new(None);
+static PHRASE: Mutex<String> = Mutex::new(String::new());
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+// Utterances waiting for the worker; more arriving meanwhile are dropped.
+const QUEUE: usize = 2;
+
+pub fn is_listening() -> bool {
+    LISTENING.load(Ordering::Relaxed)
+}
+
+/// Start, restart or stop listening to match `cfg` and the global microphone switch.
+pub fn apply(app: &tauri::AppHandle, cfg: &AppConfig, mic_disabled: bool) {
+    let settings = &cfg.wake_word;
+    *PHRASE.lock().unwrap_or_else(|p| p.into_inner()) = settings.phrase().to_string();
+
+    let device = cfg.defaults.microphone_device.clone();
+    let mut current = LISTENER.lock().unwrap_or_else(|p| p.into_inner());
+    let wanted = settings.enabled && !mic_disabled;
+    let unchanged = current.as_ref().map(|l| l.device == device);
+    if wanted && unchanged == Some(true) {
+        return;
+    }
+
+    if let Some(old) = current.take() {
+        if let Err(e) = old.listener.close() {
+            log::warn!("failed to close wake listener: {e}");
+        }
+    }
+    if wanted {
+        match open_listener(app, device.as_deref()) {
+            Ok(listener) => *current = Some(Listening { listener, device }),
+            Err(e) => log::error!("wake listener unavailable: {e}"),
+        }
+    }
+    let listening = current.is_some();
+    drop(current);
+
+    if LISTENING.swap(listening, Ordering::Relaxed) != listening {
+        log::info!("wake phrase listening: {listening}");
+    }
+    update_tray(app, listening);
+}
+
+fn open_listener(
+    app: &tauri::AppHandle,
+    device: Option<&str>,
+) -> Result<WakeListener, voicewin_audio::AudioCaptureError> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<f32>>(QUEUE);
+    let listener = WakeListener::open(device, move |samples| {
+        let _ = tx.try_send(samples);
+    })?;
+
+    // Ends once the listener (and with it the sender) is closed.
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(samples) = rx.recv().await {
+            let state = app.state::<AppState>();
+            if !state.session.is_idle().await {
+                continue;
+            }
+            let Some(svc) = state.service.get() else {
+                continue;
+            };
+            let text = match svc.transcribe_wake_utterance(samples).await {
+                Ok(t) => t,
+                Err(e) => {
+                    log::warn!("wake utterance not transcribed: {e:#}");
+                    continue;
+                }
+            };
+            let phrase = PHRASE.lock().unwrap_or_else(|p| p.into_inner()).clone();
+            // Checked again: a hotkey press may have started a session meanwhile.
+            if heard_wake_phrase(&text, &phrase) && state.session.is_idle().await {
+                log::info!("wake phrase heard");
+                let _ = state.session.toggle_recording(&app, svc.clone()).await;
+            }
+        }
+    });
+    Ok(listener)
+}
+
+fn update_tray(app: &tauri::AppHandle, listening: bool) {
+    if let Some(item) = app.try_state::<WakeWordMenuItem>() {
+        let _ = item.0.set_checked(listening);
+    }
+    if let Some(tray) = app.tray_by_id("tray") {
+        let tooltip = if listening {
+            let phrase = PHRASE.lock().unwrap_or_else(|p| p.into_inner()).clone();
+            format!("VoiceWin (listening for \u{201c}{phrase}\u{201d})")
+        } else {
+            "VoiceWin".to_string()
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
@@ -0,0 +1,12 @@
+import React from 'react';
+import ReactDOM from 'react-dom/client';
+
+import { SessionDetailsWindow } from './ui/SessionDetailsWindow';
+import './ui/tokens.css';
+import './ui/styles.css';
+
+ReactDOM.createRoot(document.getElementById('root') as HTMLElement).render(
+  <React.StrictMode>
+    <SessionDetailsWindow />
+  </React.StrictMode>,
+);
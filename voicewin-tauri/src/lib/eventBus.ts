@@ -0,0 +1,22 @@
+import { EVENT_CHANNEL, EVENT_VERSION } from './events';
+import type { AppEventEnvelope, AppEventMap, AppEventType } from './events';
+
+// Subscribe to one backend event (see `src-tauri/src/events.rs`). Envelopes of another
+// `EVENT_VERSION` are dropped. Rejects outside Tauri, like `listen` itself.
+export async function listenEvent<K extends AppEventType>(
+  type: K,
+  handler: (payload: AppEventMap[K]) => void,
+): Promise<() => void> {
+  const { listen } = await import('@tauri-apps/api/event');
+  return listen<AppEventEnvelope>(EVENT_CHANNEL, (e) => {
+    const event = e.payload;
+    if (event.v !== EVENT_VERSION || event.type !== type) return;
+    handler(event.payload as AppEventMap[K]);
+  });
+}
+
+// Send an event to every webview the way the backend would.
+export async function emitEvent<K extends AppEventType>(type: K, payload: AppEventMap[K]): Promise<void> {
+  const { emit } = await import('@tauri-apps/api/event');
+  await emit(EVENT_CHANNEL, { v: EVENT_VERSION, type, payload });
+}
@@ -0,0 +1,31 @@
+// Generated from `src-tauri/src/events.rs`; do not edit by hand.
+// Regenerate with `VOICEWIN_UPDATE_EVENTS_TS=1 cargo test generated_typescript`.
+
+import type { ActiveProfileInfo, BulkEnhanceProgress, DownloadProgress, MicLevelPayload, OverlayAppearance, QuotaWarning, SessionStatusPayload, SttDowngrade, UpdateDownloadProgress, UpdateInfo } from './types';
+
+export const EVENT_CHANNEL = 'voicewin://event';
+export const EVENT_VERSION = 1;
+
+export type AppEventMap = {
+  session_status: SessionStatusPayload;
+  mic_level: MicLevelPayload;
+  model_download_progress: DownloadProgress;
+  model_download_done: string;
+  update_available: UpdateInfo;
+  update_download_progress: UpdateDownloadProgress;
+  update_download_done: string;
+  bulk_enhance_progress: BulkEnhanceProgress;
+  config_locked: string;
+  toggle_hotkey_changed: string;
+  overlay_prefs_changed: OverlayAppearance;
+  foreground_app_changed: ActiveProfileInfo;
+  navigate: string;
+  stt_fallback: SttDowngrade;
+  elevenlabs_quota_low: QuotaWarning;
+};
+
+export type AppEventType = keyof AppEventMap;
+
+export type AppEventEnvelope = {
+  [K in AppEventType]: { v: number; type: K; payload: AppEventMap[K] };
+}[AppEventType];
@@ -9,7 +9,7 @@ export type ContextToggles = {
 export type GlobalDefaults = {
   enable_enhancement: boolean;
   prompt_id?: string | null;
-  insert_mode: 'Paste' | 'PasteAndEnter' | 'ShiftInsert';
+  insert_mode: InsertMode;
   stt_provider: string;
   stt_model: string;
   language: string;
@@ -17,17 +17,85 @@ export type GlobalDefaults = {
   llm_model: string;
   microphone_device?: string | null;
   history_enabled: boolean;
+  basic_cleanup?: boolean;
+  output_format?: OutputFormat;
+  disfluency_filter?: DisfluencyFilter;
+  text_pipeline?: TextStage[];
+  realtime_vad?: RealtimeVadSettings;
   context: ContextToggles;
 };
 
+export type RealtimeCommitStrategy = 'vad' | 'manual';
+
+// Mirrors `voicewin_core::stt::RealtimeVadSettings`: server-side pause detection for
+// ElevenLabs realtime.
+export type RealtimeVadSettings = {
+  commit_strategy: RealtimeCommitStrategy;
+  vad_silence_threshold_ms: number;
+  // 0..1000 = 0.0..1.0
+  vad_threshold_milli: number;
+  min_speech_duration_ms: number;
+  min_silence_duration_ms: number;
+};
+
+export const DEFAULT_REALTIME_VAD: RealtimeVadSettings = {
+  commit_strategy: 'vad',
+  vad_silence_threshold_ms: 600,
+  vad_threshold_milli: 400,
+  min_speech_duration_ms: 100,
+  min_silence_duration_ms: 150,
+};
+
+export type InsertMode = 'Paste' | 'PasteAndEnter' | 'ShiftInsert' | 'ClipboardOnly';
+
+export type OutputFormat = 'plain' | 'markdown' | 'code';
+
+export type DisfluencyFilter = 'off' | 'light' | 'aggressive';
+
+// Post-STT text stages, run in array order.
+export type TextStage =
+  | 'disfluency_filter'
+  | 'insert_commands'
+  | 'snippets'
+  | 'triggers'
+  | 'replacements'
+  | 'llm'
+  | 'casing';
+
+// Rust serializes `InsertTarget` as "foreground", { "app": "Obsidian.exe" },
+// { "note_file": "C:/Notes/{date}.md" }, or "scratchpad".
+export type InsertTarget = 'foreground' | { app: string } | { note_file: string } | 'scratchpad';
+
+// One dictation kept in the scratchpad window.
+export type ScratchpadEntry = {
+  ts_unix_ms: number;
+  text: string;
+  app?: string | null;
+};
+
 export type PromptTemplate = {
   id: string;
   title: string;
   mode: 'Enhancer' | 'Assistant';
   prompt_text: string;
   trigger_words: string[];
+  decoding?: LlmDecodingSettings;
 };
 
+// Mirrors `voicewin_core::enhancement::LlmDecodingSettings`; unset fields fall back to the
+// global `llm_decoding`, then to the provider default. Config file only for now.
+export type LlmDecodingSettings = {
+  temperature_pct?: number | null;
+  max_tokens?: number | null;
+  stop?: string[];
+  response_format?: 'text' | 'json_object' | null;
+};
+
+// Mirrors `voicewin_core::enhancement::LlmEndpointFlavor`; config file only for now.
+// For Azure, `defaults.llm_base_url` is the resource endpoint and `defaults.llm_model` the
+// deployment name.
+export type LlmEndpointFlavor = { kind: 'open_ai' } | { kind: 'azure'; api_version: string };
+
 // Rust serializes `AppMatcher` as an externally tagged enum.
 // Example: { "ProcessNameEquals": "slack.exe" }
 export type AppMatcherWire =
@@ -35,24 +103,42 @@ export type AppMatcherWire =
   | { ProcessNameEquals: string }
   | { WindowTitleContains: string };
 
+export type InsertionStrategy = 'clipboard_paste' | 'type_keys' | 'uia_direct' | 'macos_ax';
+
 // Rust serializes `PowerModeOverrides` as an object with optional fields.
 export type PowerModeOverridesWire = {
   enable_enhancement?: boolean;
   prompt_id?: string;
-  insert_mode?: 'Paste' | 'PasteAndEnter' | 'ShiftInsert';
+  insert_mode?: InsertMode;
   stt_provider?: string;
   stt_model?: string;
   language?: string;
   llm_base_url?: string;
   llm_model?: string;
+  output_format?: OutputFormat;
+  insert_target?: InsertTarget;
+  insertion_strategy?: InsertionStrategy;
+  paste_keys?: string;
+  post_insert_keys?: string[];
+  text_pipeline?: TextStage[];
+  realtime_vad?: RealtimeVadSettings;
   context?: Partial<ContextToggles>;
 };
 
+export type AutoDictateMode = 'off' | 'prompt' | 'record';
+
+export type AutoDictate = {
+  mode: AutoDictateMode;
+  delay_ms: number;
+};
+
 export type PowerModeProfileWire = {
   id: string;
   name: string;
   enabled: boolean;
   matchers: AppMatcherWire[];
+  // Missing in configs saved before auto-dictate existed.
+  auto_dictate?: AutoDictate;
   overrides: PowerModeOverridesWire;
 };
 
@@ -64,12 +150,19 @@ export type AppMatcher =
 export type PowerModeOverrides = {
   enable_enhancement?: boolean | null;
   prompt_id?: string | null;
-  insert_mode?: 'Paste' | 'PasteAndEnter' | 'ShiftInsert' | null;
+  insert_mode?: InsertMode | null;
   stt_provider?: string | null;
   stt_model?: string | null;
   language?: string | null;
   llm_base_url?: string | null;
   llm_model?: string | null;
+  output_format?: OutputFormat | null;
+  insert_target?: InsertTarget | null;
+  insertion_strategy?: InsertionStrategy | null;
+  paste_keys?: string | null;
+  post_insert_keys?: string[] | null;
+  text_pipeline?: TextStage[] | null;
+  realtime_vad?: RealtimeVadSettings | null;
   context?: Partial<ContextToggles> | null;
 };
 
@@ -78,14 +171,434 @@ export type PowerModeProfile = {
   name: string;
   enabled: boolean;
   matchers: AppMatcher[];
+  auto_dictate: AutoDictate;
   overrides: PowerModeOverrides;
 };
 
+// Settings a session resolves to after Power Mode; the fields the Profiles page shows.
+export type EffectiveConfig = {
+  enable_enhancement: boolean;
+  prompt_id?: string | null;
+  insert_mode: InsertMode;
+  stt_provider: string;
+  stt_model: string;
+  language: string;
+  llm_model: string;
+  output_format: OutputFormat;
+  insertion_strategy: InsertionStrategy;
+  paste_keys?: string | null;
+  post_insert_keys: string[];
+  text_pipeline: TextStage[];
+};
+
+// Result of `resolve_profile_for_current_app`.
+export type ProfileResolution = {
+  app: { process_name?: string | null; exe_path?: string | null; window_title?: string | null };
+  profile_id?: string | null;
+  profile_name?: string | null;
+  matched_by?: AppMatcherWire | null;
+  overrides?: PowerModeOverridesWire | null;
+  effective: EffectiveConfig;
+};
+
+export type ProfileSuggestion = {
+  profile: PowerModeProfileWire;
+  session_count: number;
+};
+
+export type PerformanceSettings = {
+  low_memory_mode: boolean;
+  max_whisper_threads?: number | null;
+  realtime_preconnect?: RealtimePreconnect;
+  // Shared by all model downloads; null (or 0) is unlimited.
+  max_download_kib_per_sec?: number | null;
+};
+
+export type RealtimePreconnect = 'off' | 'on_hotkey' | 'keep_warm';
+
+export type OverlayPlacement =
+  | { mode: 'follow_cursor' }
+  | { mode: 'follow_focused_window' }
+  | { mode: 'fixed_monitor'; index: number };
+
+export type OverlayAppearance = {
+  style: 'full' | 'compact';
+  theme: 'system' | 'light' | 'dark';
+  accent_color?: string | null;
+  opacity_pct: number;
+};
+
+// When the pill is on screen: per session, always, or only while recording and for errors.
+export type OverlayMode = 'auto' | 'persistent' | 'minimal';
+
+export type OverlaySettings = {
+  click_through: boolean;
+  placement?: OverlayPlacement;
+  mode?: OverlayMode;
+  appearance?: OverlayAppearance;
+};
+
+export type MonitorInfo = {
+  index: number;
+  name?: string | null;
+  width: number;
+  height: number;
+  is_primary: boolean;
+};
+
+export type SoundCueSettings = {
+  enabled: boolean;
+  on_start: boolean;
+  on_stop: boolean;
+  on_success: boolean;
+  on_error: boolean;
+  volume_pct: number;
+};
+
+// Mirrors `voicewin_core::config::SilentSessionSettings`; config file only for now.
+export type SilentSessionSettings = {
+  auto_cancel: boolean;
+  noise_floor_dbfs: number;
+};
+
+export type RedactionPreset = 'credit_card' | 'ssn' | 'email';
+
+export type RedactionSettings = {
+  enabled: boolean;
+  presets: RedactionPreset[];
+  custom_patterns: string[];
+  hash_only: boolean;
+};
+
+export type StageTimeouts = {
+  transcribe_secs: number;
+  enhance_secs: number;
+  insert_secs: number;
+};
+
+// Mirrors `voicewin_core::guardrails::OutputGuardrailSettings`; config file only for now.
+export type OutputGuardrailSettings = {
+  enabled: boolean;
+  max_growth_percent: number;
+  min_shrink_percent: number;
+  check_language: boolean;
+  reject_chat_replies: boolean;
+};
+
+// Mirrors `voicewin_core::config::InsertionSettings`; config file only for now.
+export type InsertionSettings = {
+  clipboard_restore: {
+    delay_ms?: number | null;
+    skip_if_changed: boolean;
+  };
+  // Experimental: type committed realtime segments while still recording.
+  live_typing?: boolean;
+};
+
 export type AppConfig = {
   defaults: GlobalDefaults;
   profiles: PowerModeProfileWire[];
   prompts: PromptTemplate[];
   llm_api_key_present: boolean;
+  performance?: PerformanceSettings;
+  overlay?: OverlaySettings;
+  sound_cues?: SoundCueSettings;
+  silent_sessions?: SilentSessionSettings;
+  blocked_apps?: AppMatcherWire[];
+  redaction?: RedactionSettings;
+  encrypt_history?: boolean;
+  stage_timeouts?: StageTimeouts;
+  enhancement_guardrails?: OutputGuardrailSettings;
+  llm_decoding?: LlmDecodingSettings;
+  llm_endpoint_flavor?: LlmEndpointFlavor;
+  insertion?: InsertionSettings;
+  diarization?: DiarizationSettings;
+  spoken_replacements?: SpokenReplacementSettings;
+  insert_commands?: InsertCommandSettings;
+  snippets?: Snippet[];
+  webhook?: WebhookSettings;
+  digest?: DigestSettings;
+  updates?: UpdateSettings;
+  pricing?: PricingSettings;
+  proxy?: ProxySettings;
+  tls?: TlsSettings;
+  provider_log?: ProviderLogSettings;
+  offline_mode?: boolean;
+  respect_do_not_disturb?: boolean;
+  meeting?: MeetingSettings;
+  wake_word?: WakeWordSettings;
+  auto_select_model_for_language?: boolean;
+  // STT providers to try, in order, when the selected one is unusable at session start.
+  stt_fallbacks?: string[];
+  quota_warning?: QuotaWarningSettings;
+  command_stt?: CommandSttSettings;
+};
+
+// Actions that can be bound to a global shortcut, one shortcut each.
+export type HotkeyAction =
+  | 'toggle'
+  | 'push_to_talk'
+  | 'stop'
+  | 'cancel'
+  | 'undo'
+  | 'cycle_profile'
+  | 'dismiss'
+  | 'open_history';
+
+export type HotkeyBinding = {
+  action: HotkeyAction;
+  // Unbound when null.
+  shortcut: string | null;
+};
+
+export type WakeWordSettings = {
+  enabled: boolean;
+  // Empty uses "Hey VoiceWin".
+  phrase: string;
+};
+
+export type MeetingSettings = {
+  // Markdown file for meeting chunks; unset sends them to the scratchpad.
+  note_path?: string | null;
+};
+
+export type ProxySettings = {
+  url: string;
+  no_proxy: string;
+};
+
+export type TlsSettings = {
+  extra_root_cert: string;
+};
+
+// Mirrors `voicewin_core::config::ProviderLogSettings`; config file only for now.
+export type ProviderLogSettings = {
+  enabled: boolean;
+  max_entries: number;
+  max_body_bytes: number;
+};
+
+// The program behind the `command` STT provider. `{wav}`, `{language}` and `{model}` in
+// `args` are filled in; without `{wav}` the WAV path is appended.
+export type CommandSttSettings = {
+  program: string;
+  args: string[];
+};
+
+// Warn before ElevenLabs credits run out.
+export type QuotaWarningSettings = {
+  enabled: boolean;
+  min_minutes: number;
+  credits_per_minute: number;
+};
+
+// The `elevenlabs_quota_low` event.
+export type QuotaWarning = {
+  remaining_minutes: number;
+  plan: string;
+  resets_at_unix?: number | null;
+};
+
+export type DigestPeriod = 'daily' | 'weekly';
+
+export type DigestSettings = {
+  enabled: boolean;
+  period: DigestPeriod;
+  prompt: string;
+  note_path?: string | null;
+};
+
+export type WebhookMode = 'after_insert' | 'instead_of_insert';
+
+export type WebhookSettings = {
+  enabled: boolean;
+  url: string;
+  mode: WebhookMode;
+};
+
+export type DiarizationSettings = {
+  enabled: boolean;
+  num_speakers?: number | null;
+};
+
+export type SpokenReplacement = {
+  phrase: string;
+  replacement: string;
+  language?: string | null;
+};
+
+export type Snippet = {
+  name: string;
+  text: string;
+};
+
+export type SpokenReplacementSettings = {
+  enabled: boolean;
+  use_builtin: boolean;
+  custom: SpokenReplacement[];
+};
+
+export type InsertCommandSettings = {
+  enabled: boolean;
+  commands: { phrase: string; mode: InsertMode }[];
+};
+
+// Mirrors `voicewin_core::error::VoicewinError`; commands reject with it.
+export type ErrorKind =
+  | 'config'
+  | 'microphone_access'
+  | 'accessibility_access'
+  | 'audio'
+  | 'no_speech'
+  | 'network'
+  | 'provider'
+  | 'insertion'
+  | 'elevated_target'
+  | 'target_closed'
+  | 'internal';
+
+export type VoicewinError = {
+  kind: ErrorKind;
+  user_message: string;
+  detail?: string | null;
+  retryable: boolean;
+};
+
+export function isVoicewinError(e: unknown): e is VoicewinError {
+  return (
+    typeof e === 'object' &&
+    e !== null &&
+    typeof (e as VoicewinError).kind === 'string' &&
+    typeof (e as VoicewinError).user_message === 'string'
+  );
+}
+
+// Text to show for a rejected `invoke` (a `VoicewinError`, or a string from the webview side).
+export function errorMessage(e: unknown): string {
+  return isVoicewinError(e) ? e.user_message : String(e);
+}
+
+// Payload of the `session_status` event and `get_session_status`.
+export type SessionStage =
+  | 'idle'
+  | 'recording'
+  | 'meeting'
+  | 'finalizing'
+  | 'transcribing'
+  | 'enhancing'
+  | 'inserting'
+  | 'success'
+  | 'done'
+  | 'error'
+  | 'cancelled'
+  | 'busy';
+
+export type SessionStatusPayload = {
+  stage: SessionStage;
+  stage_label: string;
+  is_recording: boolean;
+  elapsed_ms?: number | null;
+  error?: string | null;
+  // Set when `error` is a failure rather than a toast.
+  error_info?: VoicewinError | null;
+  last_text_preview?: string | null;
+  last_text_available: boolean;
+  recording_disabled?: boolean;
+  // OS do-not-disturb is respected: no sound cues, no result pop-ups.
+  quiet_mode?: boolean;
+  // Meeting mode: chunks saved so far (null for ordinary dictation).
+  meeting_chunks?: number | null;
+  // The state as a sentence for screen readers.
+  accessible_label?: string;
+};
+
+// Payload of the `mic_level` event while recording.
+export type MicLevelPayload = {
+  rms: number;
+  peak: number;
+};
+
+// Mirrors `ForegroundAppInfo` (`capture_foreground_app`).
+export type ForegroundAppInfo = {
+  process_name?: string | null;
+  exe_path?: string | null;
+  window_title?: string | null;
+};
+
+// Payload of the `foreground_app_changed` event; no profile means global defaults apply.
+export type ActiveProfileInfo = {
+  app: ForegroundAppInfo;
+  profile_id?: string | null;
+  profile_name?: string | null;
+};
+
+// Payload of the `model_download_progress` event.
+export type DownloadProgress = {
+  model_id: string;
+  downloaded_bytes: number;
+  total_bytes?: number | null;
+};
+
+// Mirrors `voicewin_runtime::models::DownloadQueueStatus` (`get_download_queue`).
+export type DownloadState = 'queued' | 'downloading';
+
+export type DownloadStatus = {
+  model_id: string;
+  state: DownloadState;
+  downloaded_bytes: number;
+  total_bytes?: number | null;
+};
+
+export type DownloadQueueStatus = {
+  downloads: DownloadStatus[];
+  max_parallel: number;
+  bandwidth_limit_bytes_per_sec?: number | null;
+};
+
+export type ProviderLocation = 'local' | 'cloud' | 'external';
+
+export type SttModelInfo = {
+  // The `stt_model` config value: a file path for local models.
+  id: string;
+  title: string;
+  realtime: boolean;
+};
+
+// An STT provider as listed by `list_stt_providers`.
+export type SttProviderInfo = {
+  // The `stt_provider` config value.
+  id: string;
+  title: string;
+  location: ProviderLocation;
+  realtime: boolean;
+  // Language codes; '*' for any language it detects.
+  languages: string[];
+  needs_api_key: boolean;
+  // Installed models for local providers; every selectable model for cloud ones.
+  models: SttModelInfo[];
+};
+
+export type SttUnavailable =
+  | 'missing_api_key'
+  | 'model_not_installed'
+  | 'not_configured'
+  | 'offline'
+  | 'unknown_provider';
+
+// The `stt_fallback` event: this session uses a fallback STT provider.
+export type SttDowngrade = {
+  from_provider: string;
+  to_provider: string;
+  to_model: string;
+  reason: SttUnavailable;
+};
+
+// The installed local model most accurate for a dictation language.
+export type ModelRecommendation = {
+  model_id: string;
+  title: string;
+  accuracy: number;
+  active: boolean;
 };
 
 export type HistoryEntry = {
@@ -96,6 +609,62 @@ export type HistoryEntry = {
   text: string;
   stage: string;
   error?: string | null;
+  tags?: string[];
+  pinned?: boolean;
+  raw_transcript?: string | null;
+  enhanced_text?: string | null;
+  prompt_id?: string | null;
+  profile_id?: string | null;
+  // Times the same text was dictated again right after; collapsed into this entry.
+  retry_count?: number;
+  // Set while the entry is in the trash; purged 30 days later.
+  deleted_at_unix_ms?: number | null;
+};
+
+// Audio files `transcribe_file` accepts (see `voicewin_audio::SUPPORTED_AUDIO_EXTENSIONS`).
+export const TRANSCRIBABLE_AUDIO_EXTENSIONS = ['wav', 'mp3', 'm4a', 'ogg'];
+
+export function isTranscribableAudioPath(path: string): boolean {
+  const ext = path.split('.').pop()?.toLowerCase() ?? '';
+  return TRANSCRIBABLE_AUDIO_EXTENSIONS.includes(ext);
+}
+
+export type HistoryFilter = {
+  tag?: string | null;
+  pinned_only?: boolean;
+  from_unix_ms?: number | null;
+  to_unix_ms?: number | null;
+  // List the trash instead of the live entries.
+  trashed?: boolean;
+};
+
+// Progress of `bulk_enhance_history`; `done` includes failed entries.
+export type BulkEnhanceProgress = {
+  total: number;
+  done: number;
+  failed: number;
+};
+
+// One side of `compare_prompts`; a failed call has `error` and no `text`.
+export type PromptRun = {
+  prompt_id: string;
+  prompt_title: string;
+  text: string | null;
+  model: string | null;
+  elapsed_ms: number;
+  error: VoicewinError | null;
+};
+
+export type PromptComparison = {
+  a: PromptRun;
+  b: PromptRun;
+};
+
+export type ExportFormat = 'csv' | 'markdown' | 'json';
+
+export type ExportRange = {
+  from_unix_ms?: number | null;
+  to_unix_ms?: number | null;
 };
 
 export function decodeAppMatcherWire(m: AppMatcherWire): AppMatcher {
@@ -121,6 +690,7 @@ export function decodePowerModeProfile(p: PowerModeProfileWire): PowerModeProfil
     name: p.name,
     enabled: p.enabled,
     matchers: p.matchers.map(decodeAppMatcherWire),
+    auto_dictate: p.auto_dictate ?? { mode: 'off', delay_ms: 0 },
     overrides: {
       enable_enhancement: p.overrides.enable_enhancement ?? null,
       prompt_id: p.overrides.prompt_id ?? null,
@@ -130,6 +700,13 @@ export function decodePowerModeProfile(p: PowerModeProfileWire): PowerModeProfil
       language: p.overrides.language ?? null,
       llm_base_url: p.overrides.llm_base_url ?? null,
       llm_model: p.overrides.llm_model ?? null,
+      output_format: p.overrides.output_format ?? null,
+      insert_target: p.overrides.insert_target ?? null,
+      insertion_strategy: p.overrides.insertion_strategy ?? null,
+      paste_keys: p.overrides.paste_keys ?? null,
+      post_insert_keys: p.overrides.post_insert_keys ?? null,
+      text_pipeline: p.overrides.text_pipeline ?? null,
+      realtime_vad: p.overrides.realtime_vad ?? null,
       context: p.overrides.context ?? null,
     },
   };
@@ -141,6 +718,7 @@ export function encodePowerModeProfile(p: PowerModeProfile): PowerModeProfileWir
     name: p.name,
     enabled: p.enabled,
     matchers: p.matchers.map(encodeAppMatcherWire),
+    auto_dictate: p.auto_dictate,
     overrides: {
       enable_enhancement: p.overrides.enable_enhancement ?? undefined,
       prompt_id: p.overrides.prompt_id ?? undefined,
@@ -150,6 +728,13 @@ export function encodePowerModeProfile(p: PowerModeProfile): PowerModeProfileWir
       language: p.overrides.language ?? undefined,
       llm_base_url: p.overrides.llm_base_url ?? undefined,
       llm_model: p.overrides.llm_model ?? undefined,
+      output_format: p.overrides.output_format ?? undefined,
+      insert_target: p.overrides.insert_target ?? undefined,
+      insertion_strategy: p.overrides.insertion_strategy ?? undefined,
+      paste_keys: p.overrides.paste_keys ?? undefined,
+      post_insert_keys: p.overrides.post_insert_keys ?? undefined,
+      text_pipeline: p.overrides.text_pipeline ?? undefined,
+      realtime_vad: p.overrides.realtime_vad ?? undefined,
       context: p.overrides.context ?? undefined,
     },
   };
@@ -160,4 +745,155 @@ export type ProviderStatus = {
   openai_api_key_error?: string | null;
   elevenlabs_api_key_present: boolean;
   elevenlabs_api_key_error?: string | null;
+  webhook_token_present: boolean;
+  webhook_token_error?: string | null;
+};
+
+// What `validate_llm_key` / `validate_elevenlabs_key` learned about a key the provider accepted.
+export type KeyValidation = {
+  plan?: string | null;
+  // Credits used and allowed this billing period, when the provider reports them.
+  quota_used?: number | null;
+  quota_limit?: number | null;
+  quota_resets_at_unix?: number | null;
+  // Models the key can use (LLM endpoints only).
+  models: string[];
+};
+
+export type SpanTiming = {
+  name: string;
+  depth: number;
+  start_ms: number;
+  duration_ms: number;
+  fields: Record<string, string>;
+};
+
+// Prices are in micro-dollars (millionths of a USD).
+export type ProviderPricing = {
+  provider: string;
+  model?: string | null;
+  input_micros_per_million_tokens: number;
+  output_micros_per_million_tokens: number;
+  audio_micros_per_hour: number;
+};
+
+export type PricingSettings = {
+  prices: ProviderPricing[];
+};
+
+export type CostLine = {
+  provider: string;
+  model: string;
+  calls: number;
+  prompt_tokens: number;
+  completion_tokens: number;
+  audio_ms: number;
+  // null when no price is configured for this provider/model.
+  cost_micros: number | null;
+};
+
+export type CostEstimate = {
+  lines: CostLine[];
+  total_micros: number;
+};
+
+export type SessionTrace = {
+  started_unix_ms: number;
+  total_ms: number;
+  fields: Record<string, string>;
+  spans: SpanTiming[];
+};
+
+// Returned by `get_provider_logs`; keys are masked and bodies redacted and truncated.
+export type WireLogEntry = {
+  ts_unix_ms: number;
+  method: string;
+  url: string;
+  request_headers: [string, string][];
+  request_body: string;
+  status?: number | null;
+  response_body?: string | null;
+  elapsed_ms: number;
+  error?: string | null;
+};
+
+// Mirrors `voicewin_core::diff::DiffSpan`: a run of words the LLM kept, removed or added.
+export type DiffSpan = {
+  op: 'equal' | 'removed' | 'added';
+  text: string;
+};
+
+// Returned by `get_last_session_result` for the session details window.
+export type SessionDetails = {
+  ts_unix_ms: number;
+  stage: string;
+  app_process_name?: string | null;
+  profile?: string | null;
+  raw_transcript?: string | null;
+  stt_provider?: string | null;
+  stt_model?: string | null;
+  enhanced_text?: string | null;
+  llm_provider?: string | null;
+  llm_model?: string | null;
+  prompt_title?: string | null;
+  final_text?: string | null;
+  timings: { transcription_ms?: number | null; enhancement_ms?: number | null };
+  diff: DiffSpan[];
+  error?: VoicewinError | null;
+};
+
+export type PermissionState = 'granted' | 'denied' | 'not_determined' | 'not_required' | 'unknown';
+
+export type PrivacyPane = 'microphone' | 'accessibility';
+
+export type OnboardingStep = 'microphone' | 'accessibility' | 'model' | 'hotkey' | 'llm';
+
+export type CheckStatus = 'ok' | 'pending' | 'failed' | 'skipped';
+
+export type OnboardingAction =
+  | 'request_mic_permission'
+  | 'open_microphone_settings'
+  | 'open_accessibility_settings'
+  | 'download_model'
+  | 'change_hotkey'
+  | 'configure_llm';
+
+export type OnboardingCheck = {
+  step: OnboardingStep;
+  status: CheckStatus;
+  detail?: string | null;
+  action?: OnboardingAction | null;
+};
+
+export type OnboardingStatus = {
+  checks: OnboardingCheck[];
+  ready: boolean;
+};
+
+export type UpdateChannel = 'stable' | 'beta';
+
+export type UpdateSettings = {
+  channel: UpdateChannel;
+  check_on_startup: boolean;
+};
+
+export type UpdateAsset = {
+  name: string;
+  url: string;
+  size: number;
+  sha256: string;
+};
+
+export type UpdateInfo = {
+  version: string;
+  notes: string;
+  release_url: string;
+  prerelease: boolean;
+  asset?: UpdateAsset | null;
+};
+
+export type UpdateDownloadProgress = {
+  version: string;
+  downloaded_bytes: number;
+  total_bytes?: number | null;
 };
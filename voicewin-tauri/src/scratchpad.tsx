@@ -0,0 +1,12 @@
+import React from 'react';
+import ReactDOM from 'react-dom/client';
+
+import { ScratchpadWindow } from './ui/ScratchpadWindow';
+import './ui/tokens.css';
+import './ui/styles.css';
+
+ReactDOM.createRoot(document.getElementById('root') as HTMLElement).render(
+  <React.StrictMode>
+    <ScratchpadWindow />
+  </React.StrictMode>,
+);
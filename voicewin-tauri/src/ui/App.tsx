@@ -1,32 +1,122 @@
-import { useEffect, useMemo, useState } from 'react';
+import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
 import { HistoryPage } from './HistoryPage';
 import { ModelsPage } from './ModelsPage';
 import { OverviewPage } from './OverviewPage';
 import { ProfilesPage } from './ProfilesPage';
 import { SettingsPage } from './SettingsPage';
+import type { QuotaWarning, SttDowngrade, SttUnavailable, UpdateInfo } from '../lib/types';
+import { errorMessage, isTranscribableAudioPath } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
 
 type Page = 'overview' | 'profiles' | 'models' | 'history' | 'settings';
 
 
+const STT_UNAVAILABLE_REASONS: Record<SttUnavailable, string> = {
+  missing_api_key: 'no API key is set',
+  model_not_installed: 'its model is not installed',
+  not_configured: 'it is not set up',
+  offline: "it can't be reached",
+  unknown_provider: 'it is not a known provider',
+};
+
+function sttFallbackMessage(d: SttDowngrade): string {
+  return `Using ${d.to_provider} instead of ${d.from_provider} because ${STT_UNAVAILABLE_REASONS[d.reason]}.`;
+}
+
 function PageContainer({ children }: { children: React.ReactNode }) {
   return <div className="vw-page vw-pageEnter">{children}</div>;
 }
 
 export function App() {
   const [page, setPage] = useState<Page>('overview');
+  const [availableUpdate, setAvailableUpdate] = useState<UpdateInfo | null>(null);
+  const [lockedConfigPath, setLockedConfigPath] = useState<string | null>(null);
+  const [sttFallback, setSttFallback] = useState<SttDowngrade | null>(null);
+  const [quotaWarning, setQuotaWarning] = useState<QuotaWarning | null>(null);
+  const [dragActive, setDragActive] = useState(false);
+  const [fileStatus, setFileStatus] = useState<string | null>(null);
+  const [historyVersion, setHistoryVersion] = useState(0);
+  const [sessionAnnouncement, setSessionAnnouncement] = useState({ text: '', urgent: false });
+  const fileQueue = useRef(Promise.resolve());
+
+  // Files are transcribed one at a time, in drop order; History refreshes after each.
+  const transcribeFiles = useCallback((paths: string[]) => {
+    const audio = paths.filter(isTranscribableAudioPath);
+    if (audio.length < paths.length) {
+      setFileStatus('Only WAV, MP3, M4A, and OGG files can be transcribed.');
+    }
+    if (audio.length === 0) return;
+    setPage('history');
+
+    for (const path of audio) {
+      const name = path.split(/[\\/]/).pop() ?? path;
+      fileQueue.current = fileQueue.current.then(async () => {
+        setFileStatus(`Transcribing ${name}…`);
+        try {
+          const { invoke } = await import('@tauri-apps/api/core');
+          await invoke('transcribe_file', { path });
+          setFileStatus(`Transcribed ${name}.`);
+        } catch (e) {
+          setFileStatus(`Could not transcribe ${name}: ${errorMessage(e)}`);
+        }
+        setHistoryVersion((v) => v + 1);
+      });
+    }
+  }, []);
 
   useEffect(() => {
     let unlisten: null | (() => void) = null;
+    let unlistenUpdate: null | (() => void) = null;
+    let unlistenLocked: null | (() => void) = null;
+    let unlistenDrop: null | (() => void) = null;
+    let unlistenStatus: null | (() => void) = null;
+    let unlistenFallback: null | (() => void) = null;
+    let unlistenQuota: null | (() => void) = null;
 
     async function start() {
         try {
-          const { listen } = await import('@tauri-apps/api/event');
-          unlisten = await listen<Page>('voicewin://navigate', (e) => {
-            const dest = e.payload;
+          unlisten = await listenEvent('navigate', (dest) => {
             if (dest === 'overview' || dest === 'profiles' || dest === 'models' || dest === 'history' || dest === 'settings') {
               setPage(dest);
             }
           });
+          // Startup update check (see `updates.check_on_startup`).
+          unlistenUpdate = await listenEvent('update_available', (payload) => {
+            setAvailableUpdate(payload);
+          });
+          // A save failed because another VoiceWin process holds the config lock.
+          unlistenLocked = await listenEvent('config_locked', (payload) => {
+            setLockedConfigPath(payload);
+          });
+          // The selected STT provider was unusable at session start (see `stt_fallbacks`).
+          unlistenFallback = await listenEvent('stt_fallback', (payload) => {
+            setSttFallback(payload);
+          });
+          // ElevenLabs credits are running low; the user may want to switch to a local model.
+          unlistenQuota = await listenEvent('elevenlabs_quota_low', (payload) => {
+            setQuotaWarning(payload);
+          });
+          // Mirror the overlay's announcements here: this is the window a screen reader is in.
+          unlistenStatus = await listenEvent('session_status', (payload) => {
+            setSessionAnnouncement({ text: payload.accessible_label ?? '', urgent: payload.stage === 'error' });
+          });
+
+          const { getCurrentWebview } = await import('@tauri-apps/api/webview');
+          unlistenDrop = await getCurrentWebview().onDragDropEvent((e) => {
+            if (e.payload.type === 'enter' || e.payload.type === 'over') {
+              setDragActive(true);
+            } else if (e.payload.type === 'drop') {
+              setDragActive(false);
+              transcribeFiles(e.payload.paths);
+            } else {
+              setDragActive(false);
+            }
+          });
+
+          // After a reload (devtools, crash recovery) the pages would otherwise wait for the
+          // next stage change; ask the backend to push the current status now.
+          const { invoke } = await import('@tauri-apps/api/core');
+          await invoke('resync_overlay');
         } catch {
           // Not running inside Tauri.
         }
@@ -36,15 +126,21 @@ export function App() {
 
     return () => {
       if (unlisten) unlisten();
+      if (unlistenUpdate) unlistenUpdate();
+      if (unlistenLocked) unlistenLocked();
+      if (unlistenDrop) unlistenDrop();
+      if (unlistenStatus) unlistenStatus();
+      if (unlistenFallback) unlistenFallback();
+      if (unlistenQuota) unlistenQuota();
     };
-  }, []);
+  }, [transcribeFiles]);
 
   const content = useMemo(() => {
     switch (page) {
       case 'overview':
         return (
           <PageContainer>
-            <OverviewPage />
+            <OverviewPage onNavigate={setPage} />
           </PageContainer>
         );
       case 'profiles':
@@ -62,20 +158,23 @@ export function App() {
       case 'history':
         return (
           <PageContainer>
-            <HistoryPage />
+            <HistoryPage refreshKey={historyVersion} fileStatus={fileStatus} onTranscribeFiles={transcribeFiles} />
           </PageContainer>
         );
       case 'settings':
         return (
           <PageContainer>
-            <SettingsPage />
+            <SettingsPage availableUpdate={availableUpdate} />
           </PageContainer>
         );
     }
-  }, [page]);
+  }, [page, availableUpdate, historyVersion, fileStatus, transcribeFiles]);
 
   return (
     <div className="vw-shell">
+      <div className="vw-srOnly" role="status" aria-live={sessionAnnouncement.urgent ? 'assertive' : 'polite'}>
+        {sessionAnnouncement.text}
+      </div>
       <nav className="vw-navRail" aria-label="Navigation">
         <button
           type="button"
@@ -123,13 +222,53 @@ export function App() {
           data-active={page === 'settings'}
           onClick={() => setPage('settings')}
           aria-label="Settings"
-          title="Settings"
+          title={availableUpdate ? `Settings (VoiceWin ${availableUpdate.version} is available)` : 'Settings'}
         >
-          ⚙
+          {availableUpdate ? '⚙•' : '⚙'}
         </button>
       </nav>
 
-      <main className="vw-content">{content}</main>
+      <main className="vw-content" data-drag-active={dragActive}>
+        {dragActive ? (
+          <div className="vw-type-caption" role="status" style={{ padding: 'var(--space-8) var(--space-12)' }}>
+            Drop a voice memo (WAV, MP3, M4A, OGG) to transcribe it into History.
+          </div>
+        ) : null}
+        {lockedConfigPath ? (
+          <div
+            className="vw-type-caption"
+            role="alert"
+            style={{ padding: 'var(--space-8) var(--space-12)', color: 'var(--color-danger-fg)' }}
+          >
+            Settings were not saved: another VoiceWin process is using {lockedConfigPath}. Close other copies of
+            VoiceWin (check the tray or Task Manager) and try again.{' '}
+            <button type="button" className="vw-button vw-button--ghost" onClick={() => setLockedConfigPath(null)}>
+              Dismiss
+            </button>
+          </div>
+        ) : null}
+        {sttFallback ? (
+          <div className="vw-type-caption" role="status" style={{ padding: 'var(--space-8) var(--space-12)' }}>
+            {sttFallbackMessage(sttFallback)}{' '}
+            <button type="button" className="vw-button vw-button--ghost" onClick={() => setSttFallback(null)}>
+              Dismiss
+            </button>
+          </div>
+        ) : null}
+        {quotaWarning ? (
+          <div className="vw-type-caption" role="status" style={{ padding: 'var(--space-8) var(--space-12)' }}>
+            About {quotaWarning.remaining_minutes} minutes of ElevenLabs transcription left on the {quotaWarning.plan}{' '}
+            plan. Switch to a local model in Settings to keep dictating.{' '}
+            <button type="button" className="vw-button vw-button--ghost" onClick={() => setPage('settings')}>
+              Open Settings
+            </button>
+            <button type="button" className="vw-button vw-button--ghost" onClick={() => setQuotaWarning(null)}>
+              Dismiss
+            </button>
+          </div>
+        ) : null}
+        {content}
+      </main>
     </div>
   );
 }
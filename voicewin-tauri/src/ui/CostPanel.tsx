@@ -0,0 +1,92 @@
+import { useCallback, useEffect, useState } from 'react';
+
+import type { CostEstimate, ExportRange } from '../lib/types';
+import { errorMessage } from '../lib/types';
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+function formatUsd(micros: number): string {
+  const usd = micros / 1_000_000;
+  return usd > 0 && usd < 0.01 ? '< $0.01' : `$${usd.toFixed(2)}`;
+}
+
+function formatUsage(l: CostEstimate['lines'][number]): string {
+  const parts: string[] = [];
+  if (l.audio_ms > 0) parts.push(`${(l.audio_ms / 60_000).toFixed(1)} min audio`);
+  if (l.prompt_tokens + l.completion_tokens > 0) {
+    parts.push(`${l.prompt_tokens.toLocaleString()} in / ${l.completion_tokens.toLocaleString()} out tokens`);
+  }
+  return parts.join(' · ');
+}
+
+// Estimated spend on cloud STT and LLM calls, priced with `pricing` in config.json.
+export function CostPanel() {
+  const [days, setDays] = useState(30);
+  const [estimate, setEstimate] = useState<CostEstimate | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      const range: ExportRange = days > 0 ? { from_unix_ms: Date.now() - days * DAY_MS } : {};
+      setEstimate(await invoke<CostEstimate>('get_cost_estimate', { range }));
+      setError(null);
+    } catch (e) {
+      setError(errorMessage(e));
+    }
+  }, [days]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  return (
+    <div style={{ padding: 'var(--space-12)' }}>
+      <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', gap: 'var(--space-8)' }}>
+        <span className="vw-type-caption">
+          {estimate ? `Estimated total: ${formatUsd(estimate.total_micros)}` : 'Estimated total'}
+        </span>
+        <div style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+          <select className="vw-input" aria-label="Cost period" value={days} onChange={(e) => setDays(Number(e.target.value))}>
+            <option value={1}>Last 24 hours</option>
+            <option value={7}>Last 7 days</option>
+            <option value={30}>Last 30 days</option>
+            <option value={0}>All time</option>
+          </select>
+          <button type="button" className="vw-button vw-button--secondary" onClick={() => void refresh()}>
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      {error ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
+          {error}
+        </div>
+      ) : null}
+
+      {estimate && estimate.lines.length === 0 && !error ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)' }}>
+          No cloud usage in this period.
+        </div>
+      ) : null}
+
+      {estimate?.lines.map((l) => (
+        <div
+          key={`${l.provider}:${l.model}`}
+          className="vw-type-caption"
+          style={{ marginTop: 'var(--space-8)', display: 'flex', justifyContent: 'space-between', gap: 'var(--space-12)' }}
+        >
+          <span>
+            {l.provider} · {l.model} · {l.calls === 1 ? '1 call' : `${l.calls} calls`}
+            <br />
+            <span style={{ color: 'var(--text-secondary)' }}>{formatUsage(l)}</span>
+          </span>
+          <span title={l.cost_micros === null ? 'No price configured for this model' : undefined}>
+            {l.cost_micros === null ? '—' : formatUsd(l.cost_micros)}
+          </span>
+        </div>
+      ))}
+    </div>
+  );
+}
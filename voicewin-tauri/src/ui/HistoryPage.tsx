@@ -1,6 +1,30 @@
 import { useCallback, useEffect, useMemo, useState } from 'react';
 
-import type { HistoryEntry } from '../lib/types';
+import type {
+  AppConfig,
+  BulkEnhanceProgress,
+  ExportFormat,
+  ExportRange,
+  HistoryEntry,
+  HistoryFilter,
+  PromptTemplate,
+} from '../lib/types';
+import { errorMessage, TRANSCRIBABLE_AUDIO_EXTENSIONS } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+const EXPORT_EXTENSIONS: Record<ExportFormat, string> = { csv: 'csv', markdown: 'md', json: 'json' };
+
+type DayFilter = 'all' | 'today' | 'yesterday';
+
+function dayRange(day: DayFilter): ExportRange {
+  if (day === 'all') return {};
+  const midnight = new Date();
+  midnight.setHours(0, 0, 0, 0);
+  const start = midnight.getTime();
+  return day === 'today' ? { from_unix_ms: start } : { from_unix_ms: start - DAY_MS, to_unix_ms: start };
+}
 
 function formatTime(tsUnixMs: number): string {
   const d = new Date(tsUnixMs);
@@ -9,47 +33,340 @@ function formatTime(tsUnixMs: number): string {
   return `${hh}:${mm}`;
 }
 
-export function HistoryPage() {
+export function HistoryPage({
+  refreshKey = 0,
+  fileStatus = null,
+  onTranscribeFiles,
+}: {
+  // Bumped by the shell whenever a transcribed file lands in History.
+  refreshKey?: number;
+  fileStatus?: string | null;
+  onTranscribeFiles?: (paths: string[]) => void;
+}) {
   const [entries, setEntries] = useState<HistoryEntry[] | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [pendingCount, setPendingCount] = useState(0);
+  const [tags, setTags] = useState<string[]>([]);
+  const [tagFilter, setTagFilter] = useState('');
+  const [pinnedOnly, setPinnedOnly] = useState(false);
+  const [showTrash, setShowTrash] = useState(false);
+  const [dayFilter, setDayFilter] = useState<DayFilter>('all');
+  const [prompts, setPrompts] = useState<PromptTemplate[]>([]);
+  const [enhancePromptId, setEnhancePromptId] = useState('');
+  const [enhanceProgress, setEnhanceProgress] = useState<BulkEnhanceProgress | null>(null);
+  const [enhancing, setEnhancing] = useState(false);
+  const [exportFormat, setExportFormat] = useState<ExportFormat>('csv');
+  const [exportDays, setExportDays] = useState(0);
+  const [exportNote, setExportNote] = useState<string | null>(null);
+
+  const filter = useMemo<HistoryFilter>(
+    () => ({ tag: tagFilter || null, pinned_only: pinnedOnly, trashed: showTrash, ...dayRange(dayFilter) }),
+    [tagFilter, pinnedOnly, showTrash, dayFilter],
+  );
 
   const refresh = useCallback(async () => {
     try {
       const { invoke } = await import('@tauri-apps/api/core');
-      const list = await invoke<HistoryEntry[]>('get_history');
+      const list = await invoke<HistoryEntry[]>('get_history', { filter });
       setEntries(list.slice().reverse());
+      setTags(await invoke<string[]>('get_history_tags'));
+      setPendingCount(await invoke<number>('get_pending_session_count'));
       setError(null);
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
       setEntries([]);
     }
-  }, []);
+  }, [filter]);
 
   useEffect(() => {
     void refresh();
-  }, [refresh]);
+  }, [refresh, refreshKey]);
+
+  useEffect(() => {
+    void (async () => {
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        const cfg = await invoke<AppConfig>('get_config');
+        setPrompts(cfg.prompts);
+        setEnhancePromptId((cur) => cur || cfg.defaults.prompt_id || cfg.prompts[0]?.id || '');
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+  }, []);
+
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+    void (async () => {
+      try {
+        unlisten = await listenEvent('bulk_enhance_progress', (payload) => {
+          setEnhanceProgress(payload);
+        });
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
 
-  const rows = useMemo(() => entries ?? [], [entries]);
+  const reEnhance = async () => {
+    if (!enhancePromptId || !entries || entries.length === 0) return;
+    const title = prompts.find((p) => p.id === enhancePromptId)?.title ?? 'the selected prompt';
+    const noun = entries.length === 1 ? '1 entry' : `${entries.length} entries`;
+    if (!window.confirm(`Re-enhance ${noun} with "${title}"? Their current text will be replaced.`)) return;
+
+    setEnhancing(true);
+    setEnhanceProgress(null);
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      setEnhanceProgress(await invoke<BulkEnhanceProgress>('bulk_enhance_history', { filter, promptId: enhancePromptId }));
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setEnhancing(false);
+      await refresh();
+    }
+  };
+
+  // Pinned entries stay on top; the sort is stable so newest-first order is kept within each group.
+  const rows = useMemo(
+    () => (entries ?? []).slice().sort((a, b) => Number(!!b.pinned) - Number(!!a.pinned)),
+    [entries],
+  );
 
   return (
     <div style={{ padding: 'var(--space-32)' }}>
       <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
         <div className="vw-type-title">History</div>
-        <button
-          type="button"
-          className="vw-button vw-button--secondary"
-          onClick={async () => {
-            try {
-              const { invoke } = await import('@tauri-apps/api/core');
-              await invoke('clear_history');
-              await refresh();
-            } catch (e) {
-              setError(String(e));
-            }
-          }}
+        <div style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+          <select
+            className="vw-input"
+            aria-label="Export range"
+            value={exportDays}
+            onChange={(e) => setExportDays(Number(e.target.value))}
+          >
+            <option value={0}>All time</option>
+            <option value={1}>Last 24 hours</option>
+            <option value={7}>Last 7 days</option>
+            <option value={30}>Last 30 days</option>
+          </select>
+          <select
+            className="vw-input"
+            aria-label="Export format"
+            value={exportFormat}
+            onChange={(e) => setExportFormat(e.target.value as ExportFormat)}
+          >
+            <option value="csv">CSV</option>
+            <option value="markdown">Markdown</option>
+            <option value="json">JSON</option>
+          </select>
+          <button
+            type="button"
+            className="vw-button vw-button--secondary"
+            onClick={async () => {
+              try {
+                const { save } = await import('@tauri-apps/plugin-dialog');
+                const ext = EXPORT_EXTENSIONS[exportFormat];
+                const path = await save({
+                  defaultPath: `voicewin-history.${ext}`,
+                  filters: [{ name: exportFormat.toUpperCase(), extensions: [ext] }],
+                });
+                if (!path) return;
+
+                const range: ExportRange = exportDays > 0 ? { from_unix_ms: Date.now() - exportDays * DAY_MS } : {};
+                const { invoke } = await import('@tauri-apps/api/core');
+                const count = await invoke<number>('export_history', { format: exportFormat, range, path });
+                setExportNote(count === 1 ? `Exported 1 entry to ${path}` : `Exported ${count} entries to ${path}`);
+              } catch (e) {
+                setError(errorMessage(e));
+              }
+            }}
+          >
+            Export
+          </button>
+          {onTranscribeFiles ? (
+            <button
+              type="button"
+              className="vw-button vw-button--secondary"
+              title="Or drop audio files onto the window"
+              onClick={async () => {
+                try {
+                  const { open } = await import('@tauri-apps/plugin-dialog');
+                  const picked = await open({
+                    multiple: true,
+                    filters: [{ name: 'Audio', extensions: TRANSCRIBABLE_AUDIO_EXTENSIONS }],
+                  });
+                  if (!picked) return;
+                  onTranscribeFiles(Array.isArray(picked) ? picked : [picked]);
+                } catch (e) {
+                  setError(errorMessage(e));
+                }
+              }}
+            >
+              Transcribe File…
+            </button>
+          ) : null}
+          <button
+            type="button"
+            className="vw-button vw-button--secondary"
+            onClick={async () => {
+              try {
+                const { invoke } = await import('@tauri-apps/api/core');
+                await invoke('clear_history');
+                await refresh();
+              } catch (e) {
+                setError(errorMessage(e));
+              }
+            }}
+          >
+            Clear All
+          </button>
+          <button
+            type="button"
+            className="vw-button vw-button--secondary"
+            title="Merge the same text dictated again right after"
+            onClick={async () => {
+              try {
+                const { invoke } = await import('@tauri-apps/api/core');
+                const merged = await invoke<number>('compact_history');
+                setExportNote(merged > 0 ? `Merged ${merged} repeated ${merged === 1 ? 'entry' : 'entries'}.` : 'No repeats to merge.');
+                await refresh();
+              } catch (e) {
+                setError(errorMessage(e));
+              }
+            }}
+          >
+            Merge Repeats
+          </button>
+          {showTrash ? (
+            <button
+              type="button"
+              className="vw-button vw-button--secondary"
+              title="Permanently delete everything in the trash"
+              onClick={async () => {
+                if (!window.confirm('Permanently delete everything in the trash?')) return;
+                try {
+                  const { invoke } = await import('@tauri-apps/api/core');
+                  const removed = await invoke<number>('empty_history_trash');
+                  setExportNote(`Removed ${removed} ${removed === 1 ? 'entry' : 'entries'} from the trash.`);
+                  await refresh();
+                } catch (e) {
+                  setError(errorMessage(e));
+                }
+              }}
+            >
+              Empty Trash
+            </button>
+          ) : null}
+        </div>
+      </div>
+
+      {fileStatus ? (
+        <div className="vw-type-caption" role="status" style={{ marginTop: 'var(--space-12)' }}>
+          {fileStatus}
+        </div>
+      ) : null}
+
+      {exportNote ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)' }}>
+          {exportNote}
+        </div>
+      ) : null}
+
+      {pendingCount > 0 ? (
+        <div
+          className="vw-type-caption"
+          style={{ marginTop: 'var(--space-12)', display: 'flex', alignItems: 'center', gap: 'var(--space-12)' }}
         >
-          Clear All
-        </button>
+          <span>
+            {pendingCount === 1
+              ? '1 recording was interrupted before it finished.'
+              : `${pendingCount} recordings were interrupted before they finished.`}
+          </span>
+          <button
+            type="button"
+            className="vw-button vw-button--secondary"
+            onClick={async () => {
+              try {
+                const { invoke } = await import('@tauri-apps/api/core');
+                await invoke('recover_pending_sessions');
+                await refresh();
+              } catch (e) {
+                setError(errorMessage(e));
+              }
+            }}
+          >
+            Recover
+          </button>
+        </div>
+      ) : null}
+
+      <div
+        className="vw-type-caption"
+        style={{ marginTop: 'var(--space-12)', display: 'flex', alignItems: 'center', gap: 'var(--space-12)' }}
+      >
+        <select className="vw-input" value={tagFilter} onChange={(e) => setTagFilter(e.target.value)}>
+          <option value="">All tags</option>
+          {tags.map((t) => (
+            <option key={t} value={t}>
+              {t}
+            </option>
+          ))}
+        </select>
+        <label style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+          <input type="checkbox" checked={pinnedOnly} onChange={(e) => setPinnedOnly(e.target.checked)} />
+          Pinned only
+        </label>
+        <label
+          style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}
+          title="Deleted entries are kept here for 30 days"
+        >
+          <input type="checkbox" checked={showTrash} onChange={(e) => setShowTrash(e.target.checked)} />
+          Trash
+        </label>
+        <select
+          className="vw-input"
+          aria-label="Day"
+          value={dayFilter}
+          onChange={(e) => setDayFilter(e.target.value as DayFilter)}
+        >
+          <option value="all">Any day</option>
+          <option value="today">Today</option>
+          <option value="yesterday">Yesterday</option>
+        </select>
+        {prompts.length > 0 ? (
+          <>
+            <select
+              className="vw-input"
+              aria-label="Prompt"
+              value={enhancePromptId}
+              onChange={(e) => setEnhancePromptId(e.target.value)}
+            >
+              {prompts.map((p) => (
+                <option key={p.id} value={p.id}>
+                  {p.title}
+                </option>
+              ))}
+            </select>
+            <button
+              type="button"
+              className="vw-button vw-button--secondary"
+              title="Run the prompt again over the entries shown below"
+              disabled={enhancing || !entries || entries.length === 0}
+              onClick={() => void reEnhance()}
+            >
+              Re-enhance
+            </button>
+          </>
+        ) : null}
+        {enhanceProgress ? (
+          <span role="status">
+            {enhancing ? 'Enhancing' : 'Enhanced'} {enhanceProgress.done}/{enhanceProgress.total}
+            {enhanceProgress.failed > 0 ? ` (${enhanceProgress.failed} failed)` : ''}
+          </span>
+        ) : null}
       </div>
 
       {error ? (
@@ -64,7 +381,7 @@ export function HistoryPage() {
             height: 32,
             borderBottom: '1px solid var(--stroke-card)',
             display: 'grid',
-            gridTemplateColumns: '100px 150px 1fr 80px',
+            gridTemplateColumns: '100px 150px 1fr 140px',
             alignItems: 'center',
             padding: '0 var(--space-12)',
           }}
@@ -84,7 +401,8 @@ export function HistoryPage() {
         </div>
 
         {rows.map((r) => {
-          const app = r.app_process_name ?? '—';
+          // Transcribed files have no app; show the file name instead.
+          const app = r.app_process_name ?? (r.stage === 'file' ? r.app_window_title : null) ?? '—';
           const text = r.text && r.text.trim().length > 0 ? r.text : (r.error ?? '');
 
           return (
@@ -95,7 +413,7 @@ export function HistoryPage() {
                 height: 56,
                 borderBottom: '1px solid var(--stroke-card)',
                 display: 'grid',
-                gridTemplateColumns: '100px 150px 1fr 80px',
+                gridTemplateColumns: '100px 150px 1fr 140px',
                 alignItems: 'center',
                 padding: '0 var(--space-12)',
               }}
@@ -104,45 +422,117 @@ export function HistoryPage() {
               <div className="vw-type-caption" title={app} style={{ overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>
                 {app}
               </div>
-              <div
-                className="vw-type-body"
-                title={text}
-                style={{ overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}
-              >
-                {text}
+              <div style={{ overflow: 'hidden' }}>
+                <div
+                  className="vw-type-body"
+                  title={text}
+                  style={{ overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}
+                >
+                  {text}
+                  {r.retry_count ? (
+                    <span className="vw-type-caption" style={{ color: 'var(--text-secondary)' }} title="Dictated again right after">
+                      {` ×${r.retry_count + 1}`}
+                    </span>
+                  ) : null}
+                </div>
+                {r.tags && r.tags.length > 0 ? (
+                  <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+                    {r.tags.map((t) => `#${t}`).join(' ')}
+                  </div>
+                ) : null}
               </div>
               <div className="vw-historyActions" style={{ display: 'flex', gap: 'var(--space-8)', justifyContent: 'flex-end' }}>
                 <button
                   type="button"
                   className="vw-button vw-button--ghost vw-iconButton"
-                  aria-label="Copy"
+                  aria-label={r.pinned ? 'Unpin' : 'Pin'}
+                  aria-pressed={!!r.pinned}
                   onClick={async () => {
                     try {
-                      await navigator.clipboard.writeText(text);
-                    } catch {
-                      // ignore
+                      const { invoke } = await import('@tauri-apps/api/core');
+                      await invoke('set_history_entry_pinned', { tsUnixMs: r.ts_unix_ms, text: r.text, pinned: !r.pinned });
+                      await refresh();
+                    } catch (e) {
+                      setError(errorMessage(e));
                     }
                   }}
                 >
-                  ⧉
+                  {r.pinned ? '★' : '☆'}
                 </button>
 
                 <button
                   type="button"
                   className="vw-button vw-button--ghost vw-iconButton"
-                  aria-label="Delete"
+                  aria-label="Edit tags"
                   onClick={async () => {
+                    const input = window.prompt('Tags (comma separated)', (r.tags ?? []).join(', '));
+                    if (input === null) return;
                     try {
                       const { invoke } = await import('@tauri-apps/api/core');
-                      await invoke('delete_history_entry', { tsUnixMs: r.ts_unix_ms, text });
+                      await invoke('set_history_entry_tags', {
+                        tsUnixMs: r.ts_unix_ms,
+                        text: r.text,
+                        tags: input.split(','),
+                      });
                       await refresh();
                     } catch (e) {
-                      setError(String(e));
+                      setError(errorMessage(e));
                     }
                   }}
                 >
-                  🗑
+                  #
                 </button>
+
+                <button
+                  type="button"
+                  className="vw-button vw-button--ghost vw-iconButton"
+                  aria-label="Copy"
+                  onClick={async () => {
+                    try {
+                      await navigator.clipboard.writeText(text);
+                    } catch {
+                      // ignore
+                    }
+                  }}
+                >
+                  ⧉
+                </button>
+
+                {r.deleted_at_unix_ms ? (
+                  <button
+                    type="button"
+                    className="vw-button vw-button--ghost vw-iconButton"
+                    aria-label="Restore"
+                    onClick={async () => {
+                      try {
+                        const { invoke } = await import('@tauri-apps/api/core');
+                        await invoke('restore_history_entry', { tsUnixMs: r.ts_unix_ms, text: r.text });
+                        await refresh();
+                      } catch (e) {
+                        setError(errorMessage(e));
+                      }
+                    }}
+                  >
+                    ↩
+                  </button>
+                ) : (
+                  <button
+                    type="button"
+                    className="vw-button vw-button--ghost vw-iconButton"
+                    aria-label="Delete"
+                    onClick={async () => {
+                      try {
+                        const { invoke } = await import('@tauri-apps/api/core');
+                        await invoke('delete_history_entry', { tsUnixMs: r.ts_unix_ms, text });
+                        await refresh();
+                      } catch (e) {
+                        setError(errorMessage(e));
+                      }
+                    }}
+                  >
+                    🗑
+                  </button>
+                )}
               </div>
             </div>
           );
@@ -1,5 +1,9 @@
 import { useCallback, useEffect, useMemo, useState } from 'react';
 
+import type { DownloadProgress, DownloadQueueStatus } from '../lib/types';
+import { errorMessage } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
+
 type ModelCatalogEntry = {
   id: string;
   title: string;
@@ -11,12 +15,8 @@ type ModelCatalogEntry = {
   installed: boolean;
   active: boolean;
   downloading: boolean;
-};
-
-type DownloadProgress = {
-  model_id: string;
-  downloaded_bytes: number;
-  total_bytes?: number | null;
+  // For the configured language; null: the model can't transcribe it.
+  language_accuracy?: number | null;
 };
 
 function formatBytes(n: number | null | undefined): string {
@@ -32,6 +32,7 @@ export function ModelsPage() {
   const [models, setModels] = useState<ModelCatalogEntry[] | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [progress, setProgress] = useState<Record<string, DownloadProgress>>({});
+  const [queued, setQueued] = useState<Set<string>>(new Set());
   const [sttProvider, setSttProvider] = useState<string | null>(null);
 
   const refresh = useCallback(async () => {
@@ -40,6 +41,14 @@ export function ModelsPage() {
       const list = await invoke<ModelCatalogEntry[]>('list_models');
       setModels(list);
 
+      // Downloads beyond the parallel limit wait for a slot.
+      try {
+        const queue = await invoke<DownloadQueueStatus>('get_download_queue');
+        setQueued(new Set(queue.downloads.filter((d) => d.state === 'queued').map((d) => d.model_id)));
+      } catch {
+        // Ignore: older backend.
+      }
+
        // Also read current STT provider so we can avoid surprising provider switches.
        try {
          const cfg = await invoke<{ defaults: { stt_provider: string } }>('get_config');
@@ -50,7 +59,7 @@ export function ModelsPage() {
 
       setError(null);
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
       setModels([]);
     }
   }, []);
@@ -64,15 +73,11 @@ export function ModelsPage() {
       await refresh();
 
       try {
-        const { listen } = await import('@tauri-apps/api/event');
-
-        unlisten1 = await listen<DownloadProgress>('voicewin://model_download_progress', (e) => {
-          const p = e.payload;
+        unlisten1 = await listenEvent('model_download_progress', (p) => {
           setProgress((prev) => ({ ...prev, [p.model_id]: p }));
         });
 
-        unlisten2 = await listen<string>('voicewin://model_download_done', (e) => {
-          const id = e.payload;
+        unlisten2 = await listenEvent('model_download_done', (id) => {
           setProgress((prev) => {
             const next = { ...prev };
             delete next[id];
@@ -173,7 +178,12 @@ export function ModelsPage() {
               </div>
 
               <div className="vw-type-caption">
-                {[formatBytes(m.size_bytes ?? null), m.speed_label ?? '', m.accuracy_label ?? '']
+                {[
+                  formatBytes(m.size_bytes ?? null),
+                  m.speed_label ?? '',
+                  m.accuracy_label ?? '',
+                  m.language_accuracy === null ? 'Not for your language' : '',
+                ]
                   .filter((x) => x && x.length > 0)
                   .join(' • ')}
               </div>
@@ -197,7 +207,7 @@ export function ModelsPage() {
                         await invoke('set_active_model', { modelId: m.id });
                         await refresh();
                       } catch (e) {
-                        setError(String(e));
+                        setError(errorMessage(e));
                       }
                     }}
                   >
@@ -217,7 +227,7 @@ export function ModelsPage() {
                         await invoke('set_active_model', { modelId: m.id });
                         await refresh();
                       } catch (e) {
-                        setError(String(e));
+                        setError(errorMessage(e));
                       }
                     }}
                   >
@@ -225,7 +235,7 @@ export function ModelsPage() {
                   </button>
                 ) : isDownloading ? (
                   <button type="button" className="vw-button vw-button--primary" disabled>
-                    Downloading…
+                    {queued.has(m.id) && !p?.downloaded_bytes ? 'Queued' : 'Downloading…'}
                   </button>
                 ) : (
                   <button
@@ -242,7 +252,7 @@ export function ModelsPage() {
                         }));
                         // Fire-and-forget: progress comes via events, completion via done event.
                         void invoke('download_model', { modelId: m.id }).catch((e) => {
-                          setError(String(e));
+                          setError(errorMessage(e));
                           setProgress((prev) => {
                             const next = { ...prev };
                             delete next[m.id];
@@ -251,7 +261,7 @@ export function ModelsPage() {
                           void refresh();
                         });
                       } catch (e) {
-                        setError(String(e));
+                        setError(errorMessage(e));
                       }
                     }}
                   >
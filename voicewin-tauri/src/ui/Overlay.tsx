@@ -1,32 +1,8 @@
 import { useEffect, useMemo, useState } from 'react';
 
-  type SessionStage =
-    | 'idle'
-    | 'recording'
-    | 'finalizing'
-    | 'transcribing'
-    | 'enhancing'
-    | 'inserting'
-    | 'success'
-    | 'done'
-    | 'error'
-    | 'cancelled'
-    | 'busy';
-
-type SessionStatusPayload = {
-  stage: SessionStage;
-  stage_label: string;
-  is_recording: boolean;
-  elapsed_ms?: number | null;
-  error?: string | null;
-  last_text_preview?: string | null;
-  last_text_available: boolean;
-};
-
-type MicLevelPayload = {
-  rms: number;
-  peak: number;
-};
+import type { MicLevelPayload, OverlayAppearance, SessionStatusPayload } from '../lib/types';
+import { errorMessage } from '../lib/types';
+import { emitEvent, listenEvent } from '../lib/eventBus';
 
 type BridgeState = {
   isTauri: boolean;
@@ -71,6 +47,7 @@ function levelToMeter(level: number): number {
 
 export function Overlay() {
   const isMac = typeof navigator !== 'undefined' && /Mac/i.test(navigator.userAgent);
+  const isWindows = typeof navigator !== 'undefined' && /Windows/i.test(navigator.userAgent);
 
   // If we never receive a status update (e.g. event bridge fails), show a minimal
   // fallback pill so the overlay window is never a blank "stuck" rectangle.
@@ -92,6 +69,54 @@ export function Overlay() {
 
   const [levels, setLevels] = useState<MicLevelPayload>({ rms: 0, peak: 0 });
 
+  const [prefs, setPrefs] = useState<OverlayAppearance>({
+    style: 'full',
+    theme: 'system',
+    accent_color: null,
+    opacity_pct: 100,
+  });
+
+  // Appearance prefs are owned by the backend; fetch once and follow change events.
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+
+    async function start() {
+      try {
+        const core = await import('@tauri-apps/api/core');
+        if (!core.isTauri()) return;
+
+        unlisten = await listenEvent('overlay_prefs_changed', (payload) => {
+          setPrefs(payload);
+        });
+        setPrefs(await core.invoke<OverlayAppearance>('get_overlay_prefs'));
+      } catch {
+        // Keep defaults.
+      }
+    }
+
+    void start();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
+
+  useEffect(() => {
+    const root = document.documentElement;
+    if (prefs.theme === 'system') {
+      delete root.dataset.vwTheme;
+    } else {
+      root.dataset.vwTheme = prefs.theme;
+    }
+
+    if (prefs.accent_color) {
+      root.style.setProperty('--color-accent', prefs.accent_color);
+    } else {
+      root.style.removeProperty('--color-accent');
+    }
+  }, [prefs.theme, prefs.accent_color]);
+
+  const compact = prefs.style === 'compact';
+
   const [bridge, setBridge] = useState<BridgeState>({
     isTauri: true,
     listenOk: false,
@@ -114,19 +139,18 @@ export function Overlay() {
         setBridge((b) => ({ ...b, isTauri: true }));
 
         try {
-          const { listen } = await import('@tauri-apps/api/event');
-          unlistenStatus = await listen<SessionStatusPayload>('voicewin://session_status', (e) => {
-            setStatus(e.payload);
+          unlistenStatus = await listenEvent('session_status', (payload) => {
+            setStatus(payload);
           });
 
           // Optional: only emitted when backend supports mic levels.
-          unlistenLevel = await listen<MicLevelPayload>('voicewin://mic_level', (e) => {
-            setLevels(e.payload);
+          unlistenLevel = await listenEvent('mic_level', (payload) => {
+            setLevels(payload);
           });
 
           setBridge((b) => ({ ...b, listenOk: true }));
         } catch (e) {
-          setBridge((b) => ({ ...b, listenOk: false, lastError: String(e) }));
+          setBridge((b) => ({ ...b, listenOk: false, lastError: errorMessage(e) }));
         }
 
         // Tell the backend we're ready so it can re-emit the current status.
@@ -135,7 +159,7 @@ export function Overlay() {
           await core.invoke('overlay_ready');
           setBridge((b) => ({ ...b, overlayReadyOk: true }));
         } catch (e) {
-          setBridge((b) => ({ ...b, overlayReadyOk: false, lastError: String(e) }));
+          setBridge((b) => ({ ...b, overlayReadyOk: false, lastError: errorMessage(e) }));
         }
 
         // Best-effort: fetch current status in case we missed the first emit.
@@ -144,10 +168,10 @@ export function Overlay() {
           setStatus(current);
           setBridge((b) => ({ ...b, invokeOk: true }));
         } catch (e) {
-          setBridge((b) => ({ ...b, invokeOk: false, lastError: String(e) }));
+          setBridge((b) => ({ ...b, invokeOk: false, lastError: errorMessage(e) }));
         }
       } catch (e) {
-        setBridge((b) => ({ ...b, isTauri: false, lastError: String(e) }));
+        setBridge((b) => ({ ...b, isTauri: false, lastError: errorMessage(e) }));
       }
     }
 
@@ -326,7 +350,8 @@ export function Overlay() {
 
   const meter = useMemo(() => {
     // Spec: 5 bars, height 4px..24px during recording.
-    const raw = status.stage === 'recording' ? Math.max(levels.rms, levels.peak) : 0;
+    const listening = status.stage === 'recording' || status.stage === 'meeting';
+    const raw = listening ? Math.max(levels.rms, levels.peak) : 0;
     return meterBars(levelToMeter(raw), 5);
   }, [levels.peak, levels.rms, status.stage]);
 
@@ -357,12 +382,16 @@ export function Overlay() {
 
   const pillText = (() => {
     if (status.stage === 'idle') return 'Connecting…';
-    if (status.stage === 'recording') return 'Listening...';
+    if (status.stage === 'recording') return status.quiet_mode ? 'Listening (quiet)...' : 'Listening...';
+    if (status.stage === 'meeting') {
+      const n = status.meeting_chunks ?? 0;
+      return n === 1 ? 'Meeting · 1 part saved' : `Meeting · ${n} parts saved`;
+    }
     if (status.stage === 'finalizing') return 'Finalizing...';
     if (status.stage === 'enhancing') return 'Enhancing...';
     if (status.stage === 'transcribing') return 'Transcribing...';
     if (status.stage === 'inserting') return 'Pasting...';
-    if (status.stage === 'success') return 'Inserted';
+    if (status.stage === 'success') return status.meeting_chunks != null ? 'Meeting saved' : 'Inserted';
     if (status.stage === 'cancelled') return 'Cancelled';
     if (status.stage === 'error') return status.error ? status.error : 'Error';
     return '';
@@ -379,8 +408,8 @@ export function Overlay() {
       return status.last_text_preview;
     }
 
-    // Realtime STT: show partial transcript while listening.
-    if (status.stage === 'recording' && status.last_text_preview && status.last_text_preview.trim().length > 0) {
+    // Realtime STT: show partial transcript while listening. Meetings show the last saved chunk.
+    if ((status.stage === 'recording' || status.stage === 'meeting') && status.last_text_preview && status.last_text_preview.trim().length > 0) {
       return status.last_text_preview;
     }
 
@@ -399,27 +428,38 @@ export function Overlay() {
 
   const leftKind = (() => {
     if (status.stage === 'idle') return 'spinner';
-    if (status.stage === 'recording') return 'mic';
+    if (status.stage === 'recording' || status.stage === 'meeting') return 'mic';
     if (status.stage === 'finalizing' || status.stage === 'transcribing' || status.stage === 'enhancing' || status.stage === 'inserting') return 'spinner';
     if (status.stage === 'success') return 'check';
     if (status.stage === 'error') return 'error';
     return 'none';
   })();
 
-  const showStop = status.stage === 'recording';
+  // Stopping a meeting goes through `toggle_recording` too.
+  const showStop = status.stage === 'recording' || status.stage === 'meeting';
   const showCancel = status.stage === 'finalizing' || status.stage === 'transcribing' || status.stage === 'enhancing';
 
-  const needsAccessibility =
-    isMac && typeof status.error === 'string' && status.error.toLowerCase().includes('accessibility');
-  const needsMicrophone =
-    isMac && typeof status.error === 'string' && status.error.toLowerCase().includes('microphone');
+  const errorKind = status.error_info?.kind ?? null;
+  const needsAccessibility = isMac && errorKind === 'accessibility_access';
+  const needsMicrophone = (isMac || isWindows) && errorKind === 'microphone_access';
+  const needsSettings = errorKind === 'config';
+  // Elevated windows don't take our paste, but do take the user's own Ctrl+V. A closed
+  // target already put the text on the clipboard; Copy is there in case it was overwritten.
+  const canCopy = errorKind === 'insertion' || errorKind === 'elevated_target' || errorKind === 'target_closed';
+  const canRetry = Boolean(status.error_info?.retryable) && !canCopy;
 
   return (
-    <div className="vw-overlayRoot">
+    // Opacity lives on the root so it composes with the pill's enter/exit animations.
+    <div className="vw-overlayRoot" style={{ opacity: prefs.opacity_pct / 100 }}>
+      {/* Announced even while the pill is hidden, so the HUD never has to be seen. */}
+      <div className="vw-srOnly" role="status" aria-live={status.stage === 'error' ? 'assertive' : 'polite'}>
+        {status.accessible_label ?? ''}
+      </div>
       {isVisible ? (
         <div
           className="vw-hud"
           data-stage={status.stage}
+          data-style={prefs.style}
           data-vw-overlay-pill
           data-exiting={isExiting ? 'true' : 'false'}
           onPointerDown={handlePointerDown}
@@ -432,7 +472,7 @@ export function Overlay() {
           </div>
 
             <div className="vw-hudCenter">
-              {status.stage === 'recording' ? (
+              {status.stage === 'recording' || status.stage === 'meeting' ? (
                 <div className="vw-hudVisualizer" aria-hidden="true">
                   {meter.map((on, i) => {
                     const id = i + 1;
@@ -441,17 +481,19 @@ export function Overlay() {
                 </div>
               ) : null}
 
-              <div
-                className={
-                  status.stage === 'recording' || status.stage === 'success'
-                    ? 'vw-type-bodyStrong'
-                    : 'vw-type-body'
-                }
-              >
-                {pillText}
-              </div>
+              {!compact ? (
+                <div
+                  className={
+                    status.stage === 'recording' || status.stage === 'meeting' || status.stage === 'success'
+                      ? 'vw-type-bodyStrong'
+                      : 'vw-type-body'
+                  }
+                >
+                  {pillText}
+                </div>
+              ) : null}
 
-              {subtitle ? (
+              {subtitle && !compact ? (
                 <div className="vw-type-caption" style={{ marginTop: 2, color: 'var(--text-secondary)' }}>
                   {subtitle}
                 </div>
@@ -506,7 +548,7 @@ export function Overlay() {
                     onClick={async () => {
                       try {
                         const { invoke } = await import('@tauri-apps/api/core');
-                        await invoke('open_macos_accessibility_settings');
+                        await invoke('open_privacy_settings', { pane: 'accessibility' });
                       } catch {
                         // Ignore.
                       }
@@ -525,7 +567,7 @@ export function Overlay() {
                     onClick={async () => {
                       try {
                         const { invoke } = await import('@tauri-apps/api/core');
-                        await invoke('open_macos_microphone_settings');
+                        await invoke('open_privacy_settings', { pane: 'microphone' });
                       } catch {
                         // Ignore.
                       }
@@ -535,6 +577,66 @@ export function Overlay() {
                   </button>
                 ) : null}
 
+                {needsSettings ? (
+                  <button
+                    type="button"
+                    className="vw-button vw-button--ghost"
+                    style={{ height: 'var(--hud-button-size)', padding: '0 var(--space-12)' }}
+                    aria-label="Open Settings"
+                    onClick={async () => {
+                      try {
+                        const { invoke } = await import('@tauri-apps/api/core');
+                        await emitEvent('navigate', 'settings');
+                        await invoke('show_main_window');
+                        await invoke('overlay_dismiss');
+                      } catch {
+                        // Ignore.
+                      }
+                    }}
+                  >
+                    Settings
+                  </button>
+                ) : null}
+
+                {canCopy ? (
+                  <button
+                    type="button"
+                    className="vw-button vw-button--ghost"
+                    style={{ height: 'var(--hud-button-size)', padding: '0 var(--space-12)' }}
+                    aria-label="Copy text"
+                    onClick={async () => {
+                      try {
+                        const { invoke } = await import('@tauri-apps/api/core');
+                        await invoke('copy_failed_text');
+                        await invoke('overlay_dismiss');
+                      } catch {
+                        // Ignore.
+                      }
+                    }}
+                  >
+                    Copy
+                  </button>
+                ) : null}
+
+                {canRetry ? (
+                  <button
+                    type="button"
+                    className="vw-button vw-button--ghost"
+                    style={{ height: 'var(--hud-button-size)', padding: '0 var(--space-12)' }}
+                    aria-label="Try again"
+                    onClick={async () => {
+                      try {
+                        const { invoke } = await import('@tauri-apps/api/core');
+                        await invoke('toggle_recording');
+                      } catch {
+                        // Ignore.
+                      }
+                    }}
+                  >
+                    Retry
+                  </button>
+                ) : null}
+
                 <button
                   type="button"
                   className="vw-button vw-button--ghost"
@@ -543,10 +645,9 @@ export function Overlay() {
                   onClick={async () => {
                     try {
                       const { invoke } = await import('@tauri-apps/api/core');
-                      const { emit } = await import('@tauri-apps/api/event');
 
                       // Bring main window forward and switch to History.
-                      await emit('voicewin://navigate', 'history');
+                      await emitEvent('navigate', 'history');
                       await invoke('show_main_window');
 
                       // Dismiss overlay.
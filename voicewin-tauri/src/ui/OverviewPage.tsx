@@ -1,4 +1,7 @@
-import { useEffect, useMemo, useState } from 'react';
+import { useCallback, useEffect, useMemo, useState } from 'react';
+import type { OnboardingAction, OnboardingCheck, OnboardingStatus, OnboardingStep } from '../lib/types';
+import { errorMessage } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
 
 type HotkeyState = {
   hotkey: string;
@@ -93,6 +96,67 @@ function StatusCard({ icon, title, subtitle, subtitleColor }: { icon: string; ti
   );
 }
 
+const STEP_TITLES: Record<OnboardingStep, string> = {
+  microphone: 'Microphone access',
+  accessibility: 'Accessibility access',
+  model: 'Speech model',
+  hotkey: 'Recording hotkey',
+  llm: 'AI enhancement',
+};
+
+const ACTION_LABELS: Record<OnboardingAction, string> = {
+  request_mic_permission: 'Allow',
+  open_microphone_settings: 'Open Settings',
+  open_accessibility_settings: 'Open Settings',
+  download_model: 'Models',
+  change_hotkey: 'Change',
+  configure_llm: 'Settings',
+};
+
+function SetupChecklist({
+  checks,
+  error,
+  onAction,
+}: {
+  checks: OnboardingCheck[];
+  error: string | null;
+  onAction: (action: OnboardingAction) => void;
+}) {
+  return (
+    <div className="vw-card" style={{ marginTop: 'var(--space-24)', padding: 'var(--space-12)' }}>
+      <div className="vw-type-bodyStrong">Finish Setup</div>
+      <div style={{ marginTop: 'var(--space-12)', display: 'grid', gap: 'var(--space-8)' }}>
+        {checks.map((c) => (
+          <div key={c.step} style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+            <div style={{ width: 16 }}>{c.status === 'failed' ? '✕' : '○'}</div>
+            <div style={{ flex: 1, minWidth: 0 }}>
+              <div className="vw-type-body">{STEP_TITLES[c.step]}</div>
+              {c.detail ? (
+                <div
+                  className="vw-type-caption"
+                  style={{ color: c.status === 'failed' ? 'var(--color-danger-fg)' : 'var(--text-secondary)' }}
+                >
+                  {c.detail}
+                </div>
+              ) : null}
+            </div>
+            {c.action ? (
+              <button type="button" className="vw-button vw-button--secondary" onClick={() => onAction(c.action!)}>
+                {ACTION_LABELS[c.action]}
+              </button>
+            ) : null}
+          </div>
+        ))}
+      </div>
+      {error ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-8)', color: 'var(--color-danger-fg)' }}>
+          {error}
+        </div>
+      ) : null}
+    </div>
+  );
+}
+
 function MicHero({ onClick }: { onClick: () => void }) {
   return (
     <button
@@ -107,7 +171,7 @@ function MicHero({ onClick }: { onClick: () => void }) {
   );
 }
 
-export function OverviewPage() {
+export function OverviewPage({ onNavigate }: { onNavigate?: (page: 'models' | 'settings') => void }) {
   const initialIsMac = typeof navigator !== 'undefined' && /Mac/i.test(navigator.userAgent);
   const initialHotkey = initialIsMac ? 'Alt+Z' : 'Ctrl+Space';
 
@@ -125,8 +189,64 @@ export function OverviewPage() {
 
   const [modelStatus, setModelStatus] = useState<ModelStatus | null>(null);
 
+  const [onboarding, setOnboarding] = useState<OnboardingStatus | null>(null);
+  const [onboardingError, setOnboardingError] = useState<string | null>(null);
+
+  const [micDisabled, setMicDisabled] = useState(false);
+  const [micDisabledError, setMicDisabledError] = useState<string | null>(null);
+
+  const refreshOnboarding = useCallback(async () => {
+    try {
+      const { isTauri, invoke } = await import('@tauri-apps/api/core');
+      if (!isTauri()) return;
+      setOnboarding(await invoke<OnboardingStatus>('get_onboarding_status'));
+    } catch {
+      // best-effort
+    }
+  }, []);
+
+  useEffect(() => {
+    void refreshOnboarding();
+  }, [refreshOnboarding]);
+
+  const onOnboardingAction = async (action: OnboardingAction) => {
+    setOnboardingError(null);
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      switch (action) {
+        case 'request_mic_permission':
+          await invoke('request_mic_permission');
+          break;
+        case 'open_microphone_settings':
+          await invoke('open_privacy_settings', { pane: 'microphone' });
+          break;
+        case 'open_accessibility_settings':
+          await invoke('open_privacy_settings', { pane: 'accessibility' });
+          break;
+        case 'change_hotkey':
+          setHotkeyEditorOpen(true);
+          return;
+        case 'download_model':
+          onNavigate?.('models');
+          return;
+        case 'configure_llm':
+          onNavigate?.('settings');
+          return;
+      }
+    } catch (e) {
+      setOnboardingError(errorMessage(e));
+    }
+    await refreshOnboarding();
+  };
+
+  const outstandingChecks = useMemo(
+    () => (onboarding?.checks ?? []).filter((c) => c.status === 'pending' || c.status === 'failed'),
+    [onboarding],
+  );
+
   useEffect(() => {
     let unlisten: null | (() => void) = null;
+    let unlistenStatus: null | (() => void) = null;
 
     async function start() {
       try {
@@ -150,17 +270,26 @@ export function OverviewPage() {
         }
 
         try {
-          const { listen } = await import('@tauri-apps/api/event');
-          unlisten = await listen<string>('voicewin://toggle_hotkey_changed', (e) => {
-            if (typeof e.payload === 'string' && e.payload.length > 0) {
-              setToggleHotkey(e.payload);
-              setHotkeyDraft(e.payload);
+          unlisten = await listenEvent('toggle_hotkey_changed', (payload) => {
+            if (typeof payload === 'string' && payload.length > 0) {
+              setToggleHotkey(payload);
+              setHotkeyDraft(payload);
             }
           });
         } catch {
           // ignore
         }
 
+        // Global mic switch (also toggled from the tray).
+        try {
+          setMicDisabled(await invoke<boolean>('get_recording_disabled'));
+          unlistenStatus = await listenEvent('session_status', (payload) => {
+            setMicDisabled(Boolean(payload.recording_disabled));
+          });
+        } catch {
+          // ignore
+        }
+
         // Model status
         try {
           const ms = await invoke<ModelStatus>('get_model_status');
@@ -177,6 +306,7 @@ export function OverviewPage() {
 
     return () => {
       if (unlisten) unlisten();
+      if (unlistenStatus) unlistenStatus();
     };
   }, []);
 
@@ -226,10 +356,39 @@ export function OverviewPage() {
 
   return (
     <div style={{ maxWidth: 600, margin: '0 auto', paddingTop: 64 }}>
-      <div className="vw-type-display">Ready to Dictate</div>
+      <div className="vw-type-display">{micDisabled ? 'Microphone Disabled' : 'Ready to Dictate'}</div>
       <div className="vw-type-body" style={{ marginTop: 'var(--space-8)' }}>
-        Press <HotkeyKbd hotkey={toggleHotkey} isMac={isMac} onClick={() => setHotkeyEditorOpen(true)} /> to start.
+        {micDisabled ? (
+          'Recording and the hotkey are off until you re-enable the microphone.'
+        ) : (
+          <>
+            Press <HotkeyKbd hotkey={toggleHotkey} isMac={isMac} onClick={() => setHotkeyEditorOpen(true)} /> to start.
+          </>
+        )}
+      </div>
+
+      <div style={{ marginTop: 'var(--space-12)', display: 'flex', justifyContent: 'center' }}>
+        <button
+          type="button"
+          className={micDisabled ? 'vw-button vw-button--primary' : 'vw-button vw-button--secondary'}
+          onClick={async () => {
+            try {
+              const { invoke } = await import('@tauri-apps/api/core');
+              setMicDisabled(await invoke<boolean>('set_recording_disabled', { disabled: !micDisabled }));
+              setMicDisabledError(null);
+            } catch (e) {
+              setMicDisabledError(errorMessage(e));
+            }
+          }}
+        >
+          {micDisabled ? 'Enable Microphone' : 'Disable Microphone'}
+        </button>
       </div>
+      {micDisabledError ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-8)', color: 'var(--color-danger-fg)' }}>
+          {micDisabledError}
+        </div>
+      ) : null}
 
       <div style={{ marginTop: 'var(--space-24)', display: 'flex', justifyContent: 'center' }}>
         <MicHero
@@ -243,7 +402,7 @@ export function OverviewPage() {
               setMicNames(names);
               setMicError(null);
             } catch (e) {
-              setMicError(String(e));
+              setMicError(errorMessage(e));
               setMicNames([]);
             }
           }}
@@ -277,7 +436,7 @@ export function OverviewPage() {
                     await invoke('set_config', { cfg });
                     setMicPickerOpen(false);
                   } catch (e) {
-                    setMicError(String(e));
+                    setMicError(errorMessage(e));
                   }
                 }}
               >
@@ -333,9 +492,10 @@ export function OverviewPage() {
                     setToggleHotkey(res.hotkey ?? hotkeyDraft);
                     setHotkeyEditorOpen(false);
                     setHotkeyError(null);
+                    void refreshOnboarding();
                   }
                 } catch (e) {
-                  setHotkeyError(String(e));
+                  setHotkeyError(errorMessage(e));
                 } finally {
                   setHotkeySaving(false);
                 }
@@ -348,6 +508,10 @@ export function OverviewPage() {
         </div>
       ) : null}
 
+      {outstandingChecks.length > 0 ? (
+        <SetupChecklist checks={outstandingChecks} error={onboardingError} onAction={(a) => void onOnboardingAction(a)} />
+      ) : null}
+
       <div
         style={{
           marginTop: 'var(--space-24)',
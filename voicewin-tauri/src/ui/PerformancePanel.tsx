@@ -0,0 +1,70 @@
+import { useCallback, useEffect, useState } from 'react';
+
+import type { SessionTrace } from '../lib/types';
+import { errorMessage } from '../lib/types';
+
+function formatMs(ms: number): string {
+  return ms >= 1000 ? `${(ms / 1000).toFixed(1)}s` : `${ms}ms`;
+}
+
+// Timeline of the most recent sessions (capture -> resample -> transcribe -> enhance -> insert).
+export function PerformancePanel() {
+  const [traces, setTraces] = useState<SessionTrace[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      setTraces(await invoke<SessionTrace[]>('get_session_traces', { limit: 5 }));
+      setError(null);
+    } catch (e) {
+      setError(errorMessage(e));
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  return (
+    <div style={{ padding: 'var(--space-12)' }}>
+      <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+        <span className="vw-type-caption">Recent sessions</span>
+        <button type="button" className="vw-button vw-button--secondary" onClick={() => void refresh()}>
+          Refresh
+        </button>
+      </div>
+
+      {error ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
+          {error}
+        </div>
+      ) : null}
+
+      {traces.length === 0 && !error ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)' }}>
+          No sessions recorded since VoiceWin started.
+        </div>
+      ) : null}
+
+      {traces.map((t) => (
+        <div key={t.started_unix_ms} style={{ marginTop: 'var(--space-12)' }}>
+          <div className="vw-type-caption">
+            {new Date(t.started_unix_ms).toLocaleTimeString()} · total {formatMs(t.total_ms)}
+            {t.fields.audio_ms ? ` · audio ${formatMs(Number(t.fields.audio_ms))}` : ''}
+          </div>
+          {t.spans.map((s, i) => (
+            <div
+              key={`${s.name}:${i}`}
+              className="vw-type-caption"
+              style={{ display: 'flex', justifyContent: 'space-between', paddingLeft: 12 + s.depth * 12 }}
+            >
+              <span>{s.name}</span>
+              <span>{formatMs(s.duration_ms)}</span>
+            </div>
+          ))}
+        </div>
+      ))}
+    </div>
+  );
+}
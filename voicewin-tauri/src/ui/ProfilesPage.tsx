@@ -1,13 +1,19 @@
 import { useCallback, useEffect, useMemo, useState } from 'react';
 
-import type { AppConfig, PowerModeProfile } from '../lib/types';
-import { decodePowerModeProfile, encodePowerModeProfile } from '../lib/types';
-
-type ForegroundAppInfo = {
-  process_name?: string | null;
-  exe_path?: string | null;
-  window_title?: string | null;
-};
+import type {
+  ActiveProfileInfo,
+  AppConfig,
+  AutoDictate,
+  AutoDictateMode,
+  ForegroundAppInfo,
+  InsertionStrategy,
+  OutputFormat,
+  PowerModeProfile,
+  ProfileResolution,
+  ProfileSuggestion,
+} from '../lib/types';
+import { decodeAppMatcherWire, decodePowerModeProfile, encodePowerModeProfile, errorMessage } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
 
 function newProfile(): PowerModeProfile {
   const id = crypto.randomUUID();
@@ -16,6 +22,7 @@ function newProfile(): PowerModeProfile {
     name: 'New Profile',
     enabled: true,
     matchers: [{ kind: 'ProcessNameEquals', value: '' }],
+    auto_dictate: { mode: 'off', delay_ms: 0 },
     overrides: {},
   };
 }
@@ -25,6 +32,12 @@ export function ProfilesPage() {
   const [profiles, setProfiles] = useState<PowerModeProfile[] | null>(null);
   const [selectedId, setSelectedId] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
+  // Raw text of the key macro field while it is being edited.
+  const [keysDraft, setKeysDraft] = useState<string | null>(null);
+  const [pasteKeysDraft, setPasteKeysDraft] = useState<string | null>(null);
+  const [suggestions, setSuggestions] = useState<ProfileSuggestion[]>([]);
+  const [active, setActive] = useState<ActiveProfileInfo | null>(null);
+  const [resolution, setResolution] = useState<ProfileResolution | null>(null);
 
   const refresh = useCallback(async () => {
     try {
@@ -36,9 +49,11 @@ export function ProfilesPage() {
       if (decoded.length > 0 && !selectedId) {
         setSelectedId(decoded[0].id);
       }
+      // Suggestions are a nicety; an unreadable History must not break the page.
+      setSuggestions(await invoke<ProfileSuggestion[]>('suggest_profiles').catch(() => []));
       setError(null);
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
       setCfg(null);
       setProfiles([]);
     }
@@ -48,6 +63,34 @@ export function ProfilesPage() {
     void refresh();
   }, [refresh]);
 
+  // Re-read on profile edits: the matching profile may have changed for the same app.
+  useEffect(() => {
+    void (async () => {
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        setActive(await invoke<ActiveProfileInfo | null>('get_active_profile'));
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+  }, [profiles]);
+
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+    void (async () => {
+      try {
+        unlisten = await listenEvent('foreground_app_changed', (payload) => {
+          setActive(payload);
+        });
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
+
   const selected = useMemo(() => {
     if (!profiles || !selectedId) return null;
     return profiles.find((p) => p.id === selectedId) ?? null;
@@ -65,9 +108,10 @@ export function ProfilesPage() {
         await invoke('set_config', { cfg: nextCfg });
         setCfg(nextCfg);
         setProfiles(nextProfiles);
+        setSuggestions(await invoke<ProfileSuggestion[]>('suggest_profiles').catch(() => []));
         setError(null);
       } catch (e) {
-        setError(String(e));
+        setError(errorMessage(e));
       }
     },
     [cfg],
@@ -117,6 +161,33 @@ export function ProfilesPage() {
           </button>
         </div>
 
+        {active ? (
+          <div
+            className="vw-type-caption"
+            style={{ marginTop: 'var(--space-8)', color: 'var(--text-secondary)' }}
+            title={active.app.window_title ?? undefined}
+          >
+            {active.app.process_name || 'Unknown app'} → {active.profile_name ?? 'Global defaults'}{' '}
+            <button
+              type="button"
+              className="vw-button vw-button--ghost"
+              onClick={async () => {
+                try {
+                  const { invoke } = await import('@tauri-apps/api/core');
+                  setResolution(await invoke<ProfileResolution>('resolve_profile_for_current_app'));
+                  setError(null);
+                } catch (e) {
+                  setError(errorMessage(e));
+                }
+              }}
+            >
+              Why?
+            </button>
+          </div>
+        ) : null}
+
+        {resolution ? <ResolutionDetails resolution={resolution} onClose={() => setResolution(null)} /> : null}
+
         <div style={{ marginTop: 'var(--space-12)', display: 'grid', gap: 'var(--space-8)' }}>
           {profiles.map((p) => {
             const selected = p.id === selectedId;
@@ -163,6 +234,40 @@ export function ProfilesPage() {
             );
           })}
         </div>
+
+        {suggestions.length > 0 ? (
+          <div style={{ marginTop: 'var(--space-24)' }}>
+            <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+              Suggested from History
+            </div>
+            <div style={{ marginTop: 'var(--space-8)', display: 'grid', gap: 'var(--space-8)' }}>
+              {suggestions.map((s) => (
+                <div
+                  key={s.profile.id}
+                  style={{ display: 'grid', gridTemplateColumns: '1fr auto', alignItems: 'center', gap: 'var(--space-8)' }}
+                >
+                  <div style={{ overflow: 'hidden' }}>
+                    <div className="vw-type-body" style={{ overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>
+                      {s.profile.name}
+                    </div>
+                    <div className="vw-type-caption">{s.session_count} dictations</div>
+                  </div>
+                  <button
+                    type="button"
+                    className="vw-button vw-button--secondary"
+                    onClick={async () => {
+                      const p = decodePowerModeProfile(s.profile);
+                      setSelectedId(p.id);
+                      await save([...profiles, p]);
+                    }}
+                  >
+                    Add
+                  </button>
+                </div>
+              ))}
+            </div>
+          </div>
+        ) : null}
       </div>
 
       <div style={{ padding: 'var(--space-32)' }}>
@@ -233,7 +338,7 @@ export function ProfilesPage() {
                       setProfiles(next);
                       await save(next);
                     } catch (e) {
-                      setError(String(e));
+                      setError(errorMessage(e));
                     }
                   }}
                 >
@@ -242,10 +347,197 @@ export function ProfilesPage() {
               </div>
             </div>
 
+            <div>
+              <div className="vw-type-bodyStrong">Auto-dictate</div>
+              <div style={{ marginTop: 'var(--space-8)', display: 'grid', gridTemplateColumns: 'auto 1fr', gap: 'var(--space-12)', alignItems: 'center' }}>
+                <span className="vw-type-caption">When this app gains focus</span>
+                <select
+                  className="vw-input"
+                  value={selected.auto_dictate.mode}
+                  onChange={async (e) => {
+                    const auto_dictate: AutoDictate = { ...selected.auto_dictate, mode: e.target.value as AutoDictateMode };
+                    const next = profiles.map((p) => (p.id === selected.id ? { ...p, auto_dictate } : p));
+                    setProfiles(next);
+                    await save(next);
+                  }}
+                >
+                  <option value="off">Do nothing</option>
+                  <option value="prompt">Show a ready prompt</option>
+                  <option value="record">Start recording</option>
+                </select>
+                <span className="vw-type-caption">Delay (seconds)</span>
+                <input
+                  className="vw-input"
+                  type="number"
+                  min={0}
+                  step={0.5}
+                  disabled={selected.auto_dictate.mode === 'off'}
+                  value={selected.auto_dictate.delay_ms / 1000}
+                  onChange={(e) => {
+                    const seconds = Math.max(0, Number(e.target.value) || 0);
+                    const auto_dictate: AutoDictate = { ...selected.auto_dictate, delay_ms: Math.round(seconds * 1000) };
+                    setProfiles(profiles.map((p) => (p.id === selected.id ? { ...p, auto_dictate } : p)));
+                  }}
+                  onBlur={async () => {
+                    await save(profiles);
+                  }}
+                />
+              </div>
+              <div className="vw-type-caption" style={{ marginTop: 'var(--space-8)' }}>
+                For data entry: switching to a matching window prompts you or starts recording once the delay has passed.
+                Switching away before then cancels it, and nothing happens while you are already dictating.
+              </div>
+            </div>
+
             <div>
               <div className="vw-type-bodyStrong">Overrides</div>
+              <div style={{ marginTop: 'var(--space-8)', display: 'grid', gridTemplateColumns: 'auto 1fr', gap: 'var(--space-12)', alignItems: 'center' }}>
+                <span className="vw-type-caption">Output format</span>
+                <select
+                  className="vw-input"
+                  value={selected.overrides.output_format ?? ''}
+                  onChange={async (e) => {
+                    const value = e.target.value === '' ? null : (e.target.value as OutputFormat);
+                    const next = profiles.map((p) =>
+                      p.id === selected.id ? { ...p, overrides: { ...p.overrides, output_format: value } } : p,
+                    );
+                    setProfiles(next);
+                    await save(next);
+                  }}
+                >
+                  <option value="">Default</option>
+                  <option value="plain">Plain text</option>
+                  <option value="markdown">Markdown</option>
+                  <option value="code">Code</option>
+                </select>
+                <span className="vw-type-caption">Insert by</span>
+                <select
+                  className="vw-input"
+                  value={selected.overrides.insertion_strategy ?? ''}
+                  onChange={async (e) => {
+                    const value = e.target.value === '' ? null : (e.target.value as InsertionStrategy);
+                    const next = profiles.map((p) =>
+                      p.id === selected.id ? { ...p, overrides: { ...p.overrides, insertion_strategy: value } } : p,
+                    );
+                    setProfiles(next);
+                    await save(next);
+                  }}
+                >
+                  <option value="">Default (clipboard paste)</option>
+                  <option value="type_keys">Typing the text</option>
+                  <option value="uia_direct">UI Automation (Windows)</option>
+                  <option value="macos_ax">Accessibility (macOS)</option>
+                </select>
+                <span className="vw-type-caption">Paste with</span>
+                <input
+                  className="vw-input"
+                  placeholder="Ctrl+V / Cmd+V (e.g. ctrl+shift+v or shift+insert)"
+                  value={pasteKeysDraft ?? selected.overrides.paste_keys ?? ''}
+                  onChange={(e) => setPasteKeysDraft(e.target.value)}
+                  onBlur={async () => {
+                    if (pasteKeysDraft === null) return;
+                    const value = pasteKeysDraft.trim() === '' ? null : pasteKeysDraft.trim();
+                    const next = profiles.map((p) =>
+                      p.id === selected.id ? { ...p, overrides: { ...p.overrides, paste_keys: value } } : p,
+                    );
+                    setPasteKeysDraft(null);
+                    setProfiles(next);
+                    await save(next);
+                  }}
+                />
+                <span className="vw-type-caption">Send dictation to</span>
+                <input
+                  className="vw-input"
+                  placeholder="Focused window (e.g. Obsidian.exe)"
+                  value={
+                    selected.overrides.insert_target && typeof selected.overrides.insert_target === 'object' && 'app' in selected.overrides.insert_target
+                      ? selected.overrides.insert_target.app
+                      : ''
+                  }
+                  onChange={(e) => {
+                    const name = e.target.value;
+                    const value = name.trim() === '' ? null : { app: name };
+                    setProfiles(
+                      profiles.map((p) =>
+                        p.id === selected.id ? { ...p, overrides: { ...p.overrides, insert_target: value } } : p,
+                      ),
+                    );
+                  }}
+                  onBlur={async () => {
+                    await save(profiles);
+                  }}
+                />
+                <span className="vw-type-caption">Keys after paste</span>
+                <input
+                  className="vw-input"
+                  placeholder="None (e.g. tab, enter or ctrl+enter)"
+                  value={keysDraft ?? (selected.overrides.post_insert_keys ?? []).join(', ')}
+                  onChange={(e) => setKeysDraft(e.target.value)}
+                  onBlur={async () => {
+                    if (keysDraft === null) return;
+                    const keys = keysDraft
+                      .split(',')
+                      .map((k) => k.trim())
+                      .filter((k) => k !== '');
+                    const next = profiles.map((p) =>
+                      p.id === selected.id
+                        ? { ...p, overrides: { ...p.overrides, post_insert_keys: keys.length > 0 ? keys : null } }
+                        : p,
+                    );
+                    setKeysDraft(null);
+                    setProfiles(next);
+                    await save(next);
+                  }}
+                />
+                <span className="vw-type-caption">Append to note</span>
+                <input
+                  className="vw-input"
+                  placeholder="Not set (e.g. C:/Notes/Journal/{date}.md)"
+                  value={
+                    selected.overrides.insert_target && typeof selected.overrides.insert_target === 'object' && 'note_file' in selected.overrides.insert_target
+                      ? selected.overrides.insert_target.note_file
+                      : ''
+                  }
+                  onChange={(e) => {
+                    const path = e.target.value;
+                    const value = path.trim() === '' ? null : { note_file: path };
+                    setProfiles(
+                      profiles.map((p) =>
+                        p.id === selected.id ? { ...p, overrides: { ...p.overrides, insert_target: value } } : p,
+                      ),
+                    );
+                  }}
+                  onBlur={async () => {
+                    await save(profiles);
+                  }}
+                />
+                <span className="vw-type-caption">Scratchpad</span>
+                <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+                  <input
+                    type="checkbox"
+                    checked={selected.overrides.insert_target === 'scratchpad'}
+                    onChange={async (e) => {
+                      const value = e.target.checked ? ('scratchpad' as const) : null;
+                      const next = profiles.map((p) =>
+                        p.id === selected.id ? { ...p, overrides: { ...p.overrides, insert_target: value } } : p,
+                      );
+                      setProfiles(next);
+                      await save(next);
+                    }}
+                  />
+                  <span className="vw-type-caption">Collect dictations there instead of inserting</span>
+                </label>
+              </div>
               <div className="vw-type-caption" style={{ marginTop: 'var(--space-8)' }}>
-                Override UI is stubbed for now; core matching + target app capture is implemented.
+                Markdown and Code keep brackets and backticks, map spoken symbols (“dash dash”, “open paren”), and skip
+                prose-style capitalization. With an app name set, the app's window is focused before pasting, wherever
+                you were when you dictated. A note path skips inserting and appends each dictation under a UTC timestamp;
+                {'{date}'} in the path starts a new file each day. The scratchpad (tray → Open Scratchpad) keeps
+                dictations until you copy them out. Keys after paste are pressed in order once the text is pasted
+                (modifiers: ctrl, shift, alt, cmd). Typing suits terminals and remote desktops (RDP, Citrix) that
+                mangle pastes; UI Automation and Accessibility write into the focused field directly. Set Paste with for
+                apps that don't paste on Ctrl+V, such as terminals (ctrl+shift+v). Other overrides are not editable here
+                yet.
               </div>
             </div>
 
@@ -269,3 +561,58 @@ export function ProfilesPage() {
     </div>
   );
 }
+
+function describeMatcher(m: ProfileResolution['matched_by']): string {
+  if (!m) return '';
+  const d = decodeAppMatcherWire(m);
+  switch (d.kind) {
+    case 'ExePathEquals':
+      return `executable is ${d.value}`;
+    case 'ProcessNameEquals':
+      return `process is ${d.value}`;
+    case 'WindowTitleContains':
+      return `window title contains “${d.value}”`;
+  }
+}
+
+function ResolutionDetails({ resolution, onClose }: { resolution: ProfileResolution; onClose: () => void }) {
+  const eff = resolution.effective;
+  // Only the fields the profile actually sets.
+  const overridden = Object.entries(resolution.overrides ?? {})
+    .filter(([, v]) => v !== null && v !== undefined)
+    .map(([k]) => k);
+
+  return (
+    <div
+      className="vw-type-caption"
+      style={{
+        marginTop: 'var(--space-8)',
+        padding: 'var(--space-8)',
+        borderRadius: 'var(--radius-card)',
+        background: 'rgba(0,0,0,0.04)',
+        display: 'grid',
+        gap: 4,
+      }}
+    >
+      <div>
+        {resolution.profile_name
+          ? `“${resolution.profile_name}” applies because the ${describeMatcher(resolution.matched_by)}.`
+          : 'No enabled profile matches; global defaults apply.'}
+      </div>
+      {overridden.length > 0 ? <div>Overrides: {overridden.join(', ')}</div> : null}
+      <div>
+        Insert: {eff.insert_mode}
+        {eff.insertion_strategy !== 'clipboard_paste' ? ` (${eff.insertion_strategy.replace('_', ' ')})` : ''}
+        {eff.insertion_strategy === 'clipboard_paste' && eff.paste_keys ? ` (pasted with ${eff.paste_keys})` : ''}
+      </div>
+      <div>
+        Speech: {eff.stt_provider} / {eff.stt_model} ({eff.language})
+      </div>
+      <div>Enhancement: {eff.enable_enhancement ? `on (${eff.llm_model})` : 'off'}</div>
+      {eff.post_insert_keys.length > 0 ? <div>Then press: {eff.post_insert_keys.join(', ')}</div> : null}
+      <button type="button" className="vw-button vw-button--ghost" onClick={onClose}>
+        Close
+      </button>
+    </div>
+  );
+}
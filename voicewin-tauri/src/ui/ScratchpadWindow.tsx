@@ -0,0 +1,139 @@
+import { useCallback, useEffect, useState } from 'react';
+
+import type { ScratchpadEntry } from '../lib/types';
+import { errorMessage } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
+
+function formatStamp(tsUnixMs: number): string {
+  const d = new Date(tsUnixMs);
+  return d.toLocaleString(undefined, { month: 'short', day: 'numeric', hour: '2-digit', minute: '2-digit' });
+}
+
+// Dictations sent to the scratchpad target, newest at the bottom like a notepad.
+export function ScratchpadWindow() {
+  const [entries, setEntries] = useState<ScratchpadEntry[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [copied, setCopied] = useState<number | 'all' | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      setEntries(await invoke<ScratchpadEntry[]>('get_scratchpad'));
+      setError(null);
+    } catch (e) {
+      setError(errorMessage(e));
+      setEntries([]);
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  // A finished session, or a running meeting, may have added an entry.
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+    void (async () => {
+      try {
+        unlisten = await listenEvent('session_status', (payload) => {
+          if (payload.stage === 'success' || payload.stage === 'meeting') void refresh();
+        });
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [refresh]);
+
+  const run = async (cmd: string, args?: Record<string, unknown>) => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      await invoke(cmd, args);
+      setError(null);
+      return true;
+    } catch (e) {
+      setError(errorMessage(e));
+      return false;
+    }
+  };
+
+  const copy = async (tsUnixMs: number | null) => {
+    if (await run('copy_scratchpad', { tsUnixMs })) setCopied(tsUnixMs ?? 'all');
+  };
+
+  const empty = entries !== null && entries.length === 0;
+
+  return (
+    <div style={{ padding: 'var(--space-16)', display: 'grid', gap: 'var(--space-12)' }}>
+      <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', gap: 8 }}>
+        <div className="vw-type-subtitle">Scratchpad</div>
+        <div style={{ display: 'flex', gap: 8 }}>
+          <button type="button" className="vw-button vw-button--secondary" disabled={empty} onClick={() => void copy(null)}>
+            {copied === 'all' ? 'Copied' : 'Copy all'}
+          </button>
+          <button
+            type="button"
+            className="vw-button vw-button--ghost"
+            disabled={empty}
+            onClick={async () => {
+              if (!window.confirm('Clear the scratchpad? This cannot be undone.')) return;
+              if (await run('clear_scratchpad')) await refresh();
+            }}
+          >
+            Clear
+          </button>
+        </div>
+      </div>
+
+      {error ? (
+        <div className="vw-type-caption" style={{ color: 'var(--color-danger-fg)' }}>
+          {error}
+        </div>
+      ) : null}
+
+      {entries === null ? <div className="vw-type-caption">Loading…</div> : null}
+      {empty ? (
+        <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+          Nothing here yet. Set a profile's target to Scratchpad and dictations collect here instead of being typed.
+        </div>
+      ) : null}
+
+      {(entries ?? []).map((e) => (
+        <div
+          key={e.ts_unix_ms}
+          style={{
+            padding: 'var(--space-12)',
+            borderRadius: 'var(--radius-card)',
+            border: '1px solid var(--stroke-card)',
+            display: 'grid',
+            gap: 'var(--space-8)',
+          }}
+        >
+          <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+            {formatStamp(e.ts_unix_ms)}
+            {e.app ? ` · ${e.app}` : ''}
+          </div>
+          <div className="vw-type-body" style={{ whiteSpace: 'pre-wrap' }}>
+            {e.text}
+          </div>
+          <div style={{ display: 'flex', gap: 8 }}>
+            <button type="button" className="vw-button vw-button--ghost" onClick={() => void copy(e.ts_unix_ms)}>
+              {copied === e.ts_unix_ms ? 'Copied' : 'Copy'}
+            </button>
+            <button
+              type="button"
+              className="vw-button vw-button--ghost"
+              onClick={async () => {
+                if (await run('delete_scratchpad_entry', { tsUnixMs: e.ts_unix_ms })) await refresh();
+              }}
+            >
+              Delete
+            </button>
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -0,0 +1,181 @@
+import { useCallback, useEffect, useState } from 'react';
+
+import type { DiffSpan, SessionDetails } from '../lib/types';
+import { errorMessage } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
+
+function formatMs(ms: number | null | undefined): string {
+  return typeof ms === 'number' ? `${(ms / 1000).toFixed(2)}s` : '—';
+}
+
+function source(provider?: string | null, model?: string | null): string {
+  return [provider, model].filter(Boolean).join(' · ') || '—';
+}
+
+function DiffView({ spans }: { spans: DiffSpan[] }) {
+  return (
+    <div className="vw-type-body" style={{ lineHeight: 1.6 }}>
+      {spans.map((s, i) => {
+        const style =
+          s.op === 'removed'
+            ? { color: 'var(--color-danger-fg)', textDecoration: 'line-through' }
+            : s.op === 'added'
+              ? { color: 'var(--color-success-fg)', fontWeight: 600 }
+              : undefined;
+        return (
+          <span key={i} style={style}>
+            {i > 0 ? ' ' : ''}
+            {s.text}
+          </span>
+        );
+      })}
+    </div>
+  );
+}
+
+function Section({ title, caption, children }: { title: string; caption?: string; children: React.ReactNode }) {
+  return (
+    <div
+      style={{
+        padding: 'var(--space-12)',
+        borderRadius: 'var(--radius-card)',
+        border: '1px solid var(--stroke-card)',
+        display: 'grid',
+        gap: 'var(--space-8)',
+      }}
+    >
+      <div style={{ display: 'flex', justifyContent: 'space-between', gap: 8 }}>
+        <div className="vw-type-body" style={{ fontWeight: 600 }}>{title}</div>
+        {caption ? (
+          <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+            {caption}
+          </div>
+        ) : null}
+      </div>
+      {children}
+    </div>
+  );
+}
+
+// What the last session heard, what the LLM turned it into, and a word diff between the two.
+export function SessionDetailsWindow() {
+  const [details, setDetails] = useState<SessionDetails | null | undefined>(undefined);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      setDetails(await invoke<SessionDetails | null>('get_last_session_result'));
+      setError(null);
+    } catch (e) {
+      setError(errorMessage(e));
+      setDetails(null);
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  // Follow new sessions while the window is open.
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+    void (async () => {
+      try {
+        unlisten = await listenEvent('session_status', (payload) => {
+          if (payload.stage === 'success' || payload.stage === 'error') void refresh();
+        });
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [refresh]);
+
+  return (
+    <div style={{ padding: 'var(--space-16)', display: 'grid', gap: 'var(--space-12)' }}>
+      <div className="vw-type-subtitle">Last session</div>
+
+      {error ? (
+        <div className="vw-type-caption" style={{ color: 'var(--color-danger-fg)' }}>
+          {error}
+        </div>
+      ) : null}
+
+      {details === undefined ? <div className="vw-type-caption">Loading…</div> : null}
+      {details === null && !error ? (
+        <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+          No dictation yet. Details of the next session will show up here.
+        </div>
+      ) : null}
+
+      {details ? (
+        <>
+          <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+            {new Date(details.ts_unix_ms).toLocaleString()}
+            {details.app_process_name ? ` · ${details.app_process_name}` : ''}
+            {details.profile ? ` · ${details.profile}` : ''}
+            {` · ${details.stage}`}
+          </div>
+
+          {details.error ? (
+            <div className="vw-type-caption" style={{ color: 'var(--color-danger-fg)' }}>
+              {details.error.user_message}
+            </div>
+          ) : null}
+
+          <Section
+            title="Transcript"
+            caption={`${source(details.stt_provider, details.stt_model)} · ${formatMs(details.timings.transcription_ms)}`}
+          >
+            <div className="vw-type-body" style={{ whiteSpace: 'pre-wrap' }}>
+              {details.raw_transcript || '—'}
+            </div>
+          </Section>
+
+          {details.enhanced_text != null ? (
+            <>
+              <Section
+                title="Enhanced"
+                caption={`${source(details.llm_provider, details.llm_model)} · ${formatMs(details.timings.enhancement_ms)}`}
+              >
+                {details.prompt_title ? (
+                  <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+                    Prompt: {details.prompt_title}
+                  </div>
+                ) : null}
+                <div className="vw-type-body" style={{ whiteSpace: 'pre-wrap' }}>
+                  {details.enhanced_text}
+                </div>
+              </Section>
+
+              <Section title="Changes">
+                {details.diff.some((s) => s.op !== 'equal') ? (
+                  <DiffView spans={details.diff} />
+                ) : (
+                  <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+                    The LLM left the transcript unchanged.
+                  </div>
+                )}
+              </Section>
+            </>
+          ) : (
+            <div className="vw-type-caption" style={{ color: 'var(--text-secondary)' }}>
+              This session was not enhanced.
+            </div>
+          )}
+
+          {details.final_text && details.final_text !== (details.enhanced_text ?? details.raw_transcript) ? (
+            <Section title="Inserted">
+              <div className="vw-type-body" style={{ whiteSpace: 'pre-wrap' }}>
+                {details.final_text}
+              </div>
+            </Section>
+          ) : null}
+        </>
+      ) : null}
+    </div>
+  );
+}
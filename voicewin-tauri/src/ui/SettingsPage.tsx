@@ -1,6 +1,53 @@
 import { useCallback, useEffect, useMemo, useState } from 'react';
 
-import type { AppConfig, ProviderStatus } from '../lib/types';
+import {
+  DEFAULT_REALTIME_VAD,
+  decodeAppMatcherWire,
+  encodeAppMatcherWire,
+  errorMessage,
+  isVoicewinError,
+} from '../lib/types';
+import { CostPanel } from './CostPanel';
+import { PerformancePanel } from './PerformancePanel';
+import { UpdatesPanel } from './UpdatesPanel';
+import type {
+  AppConfig,
+  AppMatcher,
+  CommandSttSettings,
+  DisfluencyFilter,
+  HotkeyAction,
+  HotkeyBinding,
+  KeyValidation,
+  MeetingSettings,
+  ModelRecommendation,
+  MonitorInfo,
+  OverlayAppearance,
+  OverlayMode,
+  OverlayPlacement,
+  ProviderStatus,
+  QuotaWarningSettings,
+  RealtimePreconnect,
+  RealtimeVadSettings,
+  RedactionPreset,
+  RedactionSettings,
+  Snippet,
+  SoundCueSettings,
+  SpokenReplacementSettings,
+  StageTimeouts,
+  SttProviderInfo,
+  WebhookMode,
+  WebhookSettings,
+  DigestPeriod,
+  DigestSettings,
+  UpdateChannel,
+  UpdateInfo,
+  UpdateSettings,
+  ProxySettings,
+  TlsSettings,
+  VoicewinError,
+  WakeWordSettings,
+} from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
 
 type ModelStatus = {
   bootstrap_ok: boolean;
@@ -57,10 +104,91 @@ function Section({ title, subtitle, children }: { title: string; subtitle?: stri
   );
 }
 
-export function SettingsPage() {
+function placementValue(p: OverlayPlacement | undefined): string {
+  if (!p) return 'follow_cursor';
+  return p.mode === 'fixed_monitor' ? `fixed_monitor:${p.index}` : p.mode;
+}
+
+function parsePlacement(value: string): OverlayPlacement {
+  if (value.startsWith('fixed_monitor:')) {
+    return { mode: 'fixed_monitor', index: Number(value.slice('fixed_monitor:'.length)) || 0 };
+  }
+  return value === 'follow_focused_window' ? { mode: 'follow_focused_window' } : { mode: 'follow_cursor' };
+}
+
+// The Speech-to-Text fields of the settings draft for `cfg`: local providers keep a model
+// path, cloud ones pick from the models the provider lists.
+function sttDraftFromConfig(cfg: AppConfig, sttProviders: SttProviderInfo[], modelStatus: ModelStatus | null) {
+  const localDefault = modelStatus?.preferred_ok ? modelStatus.preferred_path : modelStatus?.bootstrap_path ?? '';
+  const provider = sttProviders.find((p) => p.id === cfg.defaults.stt_provider);
+  const isLocal = provider?.location === 'local';
+  const cloudModels = (isLocal ? sttProviders.find((p) => p.location === 'cloud') : provider)?.models ?? [];
+  return {
+    stt_provider: cfg.defaults.stt_provider,
+    local_stt_model_path: isLocal ? cfg.defaults.stt_model : localDefault,
+    cloud_stt_model: cloudModels.some((m) => m.id === cfg.defaults.stt_model)
+      ? cfg.defaults.stt_model
+      : cloudModels[0]?.id ?? cfg.defaults.stt_model,
+  };
+}
+
+// Check a key with the provider before it is saved. A rejected key throws; when the provider
+// can't be reached the key is still saved and the returned notice says so.
+async function checkKey(command: 'validate_llm_key' | 'validate_elevenlabs_key', apiKey: string): Promise<string> {
+  const { invoke } = await import('@tauri-apps/api/core');
+  let v: KeyValidation;
+  try {
+    v = await invoke<KeyValidation>(command, { api_key: apiKey });
+  } catch (e) {
+    if (isVoicewinError(e) && e.kind === 'config') throw e;
+    return `Not verified: ${errorMessage(e)}`;
+  }
+  const parts = ['Key verified'];
+  if (v.plan) parts.push(`${v.plan} plan`);
+  if (v.quota_used != null && v.quota_limit != null) {
+    parts.push(`${v.quota_used.toLocaleString()} of ${v.quota_limit.toLocaleString()} credits used`);
+  }
+  if (v.models.length > 0) parts.push(`${v.models.length} models available`);
+  return `${parts.join(' · ')}.`;
+}
+
+// Dictation languages offered in the picker; `auto` lets the model detect it.
+const LANGUAGES: [string, string][] = [
+  ['auto', 'Detect automatically'],
+  ['en', 'English'],
+  ['de', 'German'],
+  ['es', 'Spanish'],
+  ['fr', 'French'],
+  ['it', 'Italian'],
+  ['pt', 'Portuguese'],
+  ['nl', 'Dutch'],
+  ['pl', 'Polish'],
+  ['ru', 'Russian'],
+  ['uk', 'Ukrainian'],
+  ['ja', 'Japanese'],
+  ['ko', 'Korean'],
+  ['zh', 'Chinese'],
+];
+
+const HOTKEY_LABELS: Record<HotkeyAction, { title: string; description: string }> = {
+  toggle: { title: 'Start/stop dictation', description: 'Same as the hotkey on the Overview page.' },
+  push_to_talk: { title: 'Push to talk', description: 'Records while held and inserts when released.' },
+  stop: { title: 'Stop recording', description: 'Stops and transcribes; does nothing when not recording.' },
+  cancel: { title: 'Cancel', description: 'Discards the recording or the transcription in progress.' },
+  undo: { title: 'Undo last insertion', description: "Presses the app's undo shortcut, e.g. right after a dictation." },
+  cycle_profile: {
+    title: 'Switch profile',
+    description: 'Uses the next profile for new dictations whatever app is in front, then back to matching by app.',
+  },
+  dismiss: { title: 'Dismiss overlay', description: 'Hides the overlay after a result or an error.' },
+  open_history: { title: 'Open History', description: 'Brings up this window on the History page.' },
+};
+
+export function SettingsPage({ availableUpdate }: { availableUpdate?: UpdateInfo | null }) {
   const [cfg, setCfg] = useState<AppConfig | null>(null);
   const [providers, setProviders] = useState<ProviderStatus | null>(null);
   const [modelStatus, setModelStatus] = useState<ModelStatus | null>(null);
+  const [monitors, setMonitors] = useState<MonitorInfo[]>([]);
   const [error, setError] = useState<string | null>(null);
   const [saving, setSaving] = useState(false);
 
@@ -78,11 +206,34 @@ export function SettingsPage() {
 
     stt_provider: 'local',
     local_stt_model_path: '',
-    elevenlabs_stt_model: 'scribe_v2',
+    cloud_stt_model: '',
   });
+  const [sttProviders, setSttProviders] = useState<SttProviderInfo[]>([]);
 
   const [openaiApiKeyDraft, setOpenaiApiKeyDraft] = useState('');
   const [elevenApiKeyDraft, setElevenApiKeyDraft] = useState('');
+  const [blockedAppDraft, setBlockedAppDraft] = useState('');
+  const [replacementPhraseDraft, setReplacementPhraseDraft] = useState('');
+  const [replacementTextDraft, setReplacementTextDraft] = useState('');
+  const [snippetNameDraft, setSnippetNameDraft] = useState('');
+  const [snippetTextDraft, setSnippetTextDraft] = useState('');
+  const [webhookUrlDraft, setWebhookUrlDraft] = useState<string | null>(null);
+  const [proxyUrlDraft, setProxyUrlDraft] = useState<string | null>(null);
+  const [commandProgramDraft, setCommandProgramDraft] = useState<string | null>(null);
+  const [commandArgsDraft, setCommandArgsDraft] = useState<string | null>(null);
+  const [noProxyDraft, setNoProxyDraft] = useState<string | null>(null);
+  const [rootCertDraft, setRootCertDraft] = useState<string | null>(null);
+  const [webhookTokenDraft, setWebhookTokenDraft] = useState('');
+  const [digestNoteDraft, setDigestNoteDraft] = useState<string | null>(null);
+  const [digestPromptDraft, setDigestPromptDraft] = useState<string | null>(null);
+  const [meetingNoteDraft, setMeetingNoteDraft] = useState<string | null>(null);
+  const [meetingRunning, setMeetingRunning] = useState(false);
+  const [wakePhraseDraft, setWakePhraseDraft] = useState<string | null>(null);
+  const [modelSuggestion, setModelSuggestion] = useState<ModelRecommendation | null>(null);
+  const [hotkeys, setHotkeys] = useState<HotkeyBinding[]>([]);
+  const [hotkeyDrafts, setHotkeyDrafts] = useState<Partial<Record<HotkeyAction, string>>>({});
+  const [hotkeyError, setHotkeyError] = useState<string | null>(null);
+  const [debugBundlePath, setDebugBundlePath] = useState<string | null>(null);
 
   const refresh = useCallback(async () => {
     try {
@@ -96,9 +247,12 @@ export function SettingsPage() {
       setCfg(nextCfg);
       setProviders(nextProviders);
       setModelStatus(nextModelStatus);
+      setSttProviders(await invoke<SttProviderInfo[]>('list_stt_providers').catch(() => []));
+      setMonitors(await invoke<MonitorInfo[]>('list_monitors').catch(() => []));
+      setHotkeys(await invoke<HotkeyBinding[]>('list_hotkeys').catch(() => []));
       setError(null);
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
     }
   }, []);
 
@@ -114,7 +268,7 @@ export function SettingsPage() {
         await refresh();
         return true;
       } catch (e) {
-        setError(String(e));
+        setError(errorMessage(e));
         return false;
       } finally {
         setSaving(false);
@@ -123,33 +277,80 @@ export function SettingsPage() {
     [refresh],
   );
 
+  const saveOverlayPrefs = useCallback(async (prefs: OverlayAppearance) => {
+    setSaving(true);
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      const saved = await invoke<OverlayAppearance>('set_overlay_prefs', { prefs });
+      setCfg((c) =>
+        c ? { ...c, overlay: { click_through: false, ...c.overlay, appearance: saved } } : c,
+      );
+      setError(null);
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setSaving(false);
+    }
+  }, []);
+
   useEffect(() => {
     void refresh();
   }, [refresh]);
 
+  // The tray and the overlay can stop a meeting too.
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+    void (async () => {
+      try {
+        unlisten = await listenEvent('session_status', (payload) => {
+          setMeetingRunning(payload.stage === 'meeting');
+        });
+        const { invoke } = await import('@tauri-apps/api/core');
+        const current = await invoke<{ stage: string }>('get_session_status');
+        setMeetingRunning(current.stage === 'meeting');
+      } catch {
+        // Not running inside Tauri.
+      }
+    })();
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
+
+  const saveHotkey = async (action: HotkeyAction, shortcut: string) => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      setHotkeys(await invoke<HotkeyBinding[]>('set_hotkey', { action, shortcut }));
+      setHotkeyError(null);
+    } catch (e) {
+      setHotkeyError(errorMessage(e));
+    }
+  };
+
+  const toggleMeeting = async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      const res = await invoke<{ is_recording: boolean; error?: VoicewinError | null }>('toggle_meeting');
+      setMeetingRunning(res.is_recording);
+      setError(res.error ? errorMessage(res.error) : null);
+    } catch (e) {
+      setError(errorMessage(e));
+    }
+  };
+
   useEffect(() => {
     if (!cfg) return;
     // Only overwrite drafts when the user has no pending edits.
     if (dirty) return;
 
-    const localDefault = modelStatus?.preferred_ok
-      ? modelStatus.preferred_path
-      : modelStatus?.bootstrap_path ?? '';
-
-    const isLocal = cfg.defaults.stt_provider === 'local';
-    const currentEleven = cfg.defaults.stt_provider === 'elevenlabs' ? cfg.defaults.stt_model : 'scribe_v2';
-    const normalizedEleven = currentEleven === 'scribe_v2_realtime' ? 'scribe_v2_realtime' : 'scribe_v2';
-
     setDraft({
       enable_enhancement: Boolean(cfg.defaults.enable_enhancement),
       llm_base_url: cfg.defaults.llm_base_url ?? '',
       llm_model: cfg.defaults.llm_model ?? '',
 
-      stt_provider: (cfg.defaults.stt_provider === 'elevenlabs' ? 'elevenlabs' : 'local') as 'local' | 'elevenlabs',
-      local_stt_model_path: isLocal ? cfg.defaults.stt_model : localDefault,
-      elevenlabs_stt_model: normalizedEleven as 'scribe_v2' | 'scribe_v2_realtime',
+      ...sttDraftFromConfig(cfg, sttProviders, modelStatus),
     });
-  }, [cfg, dirty, modelStatus]);
+  }, [cfg, dirty, modelStatus, sttProviders]);
 
   const openaiKeyStatus = useMemo(() => {
     if (!providers) return 'Unknown';
@@ -203,6 +404,166 @@ export function SettingsPage() {
     );
   }
 
+  const appearance: OverlayAppearance = cfg.overlay?.appearance ?? {
+    style: 'full',
+    theme: 'system',
+    accent_color: null,
+    opacity_pct: 100,
+  };
+
+  const cues: SoundCueSettings = cfg.sound_cues ?? {
+    enabled: false,
+    on_start: true,
+    on_stop: true,
+    on_success: true,
+    on_error: true,
+    volume_pct: 50,
+  };
+  const saveCues = (next: SoundCueSettings) => {
+    void saveConfig({ ...cfg, sound_cues: next });
+  };
+
+  const redaction: RedactionSettings = cfg.redaction ?? {
+    enabled: false,
+    presets: ['credit_card', 'ssn', 'email'],
+    custom_patterns: [],
+    hash_only: false,
+  };
+  const saveRedaction = (next: RedactionSettings) => {
+    void saveConfig({ ...cfg, redaction: next });
+  };
+
+  const timeouts: StageTimeouts = cfg.stage_timeouts ?? {
+    transcribe_secs: 120,
+    enhance_secs: 30,
+    insert_secs: 15,
+  };
+  const quotaWarning: QuotaWarningSettings = cfg.quota_warning ?? {
+    enabled: true,
+    min_minutes: 30,
+    credits_per_minute: 67,
+  };
+  const commandStt: CommandSttSettings = cfg.command_stt ?? { program: '', args: [] };
+  const saveCommandStt = (next: CommandSttSettings) => {
+    void saveConfig({ ...cfg, command_stt: next });
+  };
+  const sttProvider = sttProviders.find((p) => p.id === draft.stt_provider);
+  const isLocalStt = sttProvider?.location === 'local';
+  const cloudSttModel = isLocalStt ? undefined : sttProvider?.models.find((m) => m.id === draft.cloud_stt_model);
+
+  const realtimeVad: RealtimeVadSettings = cfg.defaults.realtime_vad ?? DEFAULT_REALTIME_VAD;
+  const saveRealtimeVad = (next: RealtimeVadSettings) => {
+    void saveConfig({ ...cfg, defaults: { ...cfg.defaults, realtime_vad: next } });
+  };
+
+  const saveLanguage = async (language: string) => {
+    setModelSuggestion(null);
+    const ok = await saveConfig({ ...cfg, defaults: { ...cfg.defaults, language } });
+    // With auto-select on, the backend already switched models.
+    const provider = sttProviders.find((p) => p.id === cfg.defaults.stt_provider);
+    if (!ok || cfg.auto_select_model_for_language || provider?.location !== 'local') return;
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      const rec = await invoke<ModelRecommendation | null>('recommend_model_for_language', { language });
+      setModelSuggestion(rec && !rec.active ? rec : null);
+    } catch {
+      // Only a suggestion.
+    }
+  };
+
+  const saveTimeouts = (next: StageTimeouts) => {
+    void saveConfig({ ...cfg, stage_timeouts: next });
+  };
+
+  const webhook: WebhookSettings = cfg.webhook ?? { enabled: false, url: '', mode: 'after_insert' };
+  const digest: DigestSettings = cfg.digest ?? { enabled: false, period: 'daily', prompt: '', note_path: null };
+  const saveDigest = (next: DigestSettings) => {
+    void saveConfig({ ...cfg, digest: next });
+  };
+
+  const meeting: MeetingSettings = cfg.meeting ?? { note_path: null };
+  const wakeWord: WakeWordSettings = cfg.wake_word ?? { enabled: false, phrase: '' };
+  const saveWakeWord = (next: WakeWordSettings) => {
+    void saveConfig({ ...cfg, wake_word: next });
+  };
+
+  const updates: UpdateSettings = cfg.updates ?? { channel: 'stable', check_on_startup: true };
+  const saveUpdates = (next: UpdateSettings) => {
+    void saveConfig({ ...cfg, updates: next });
+  };
+  const proxy: ProxySettings = cfg.proxy ?? { url: '', no_proxy: '' };
+  const saveProxy = (next: ProxySettings) => {
+    void saveConfig({ ...cfg, proxy: next });
+  };
+  const tls: TlsSettings = cfg.tls ?? { extra_root_cert: '' };
+  const saveTls = (next: TlsSettings) => {
+    void saveConfig({ ...cfg, tls: next });
+  };
+  const saveWebhook = (next: WebhookSettings) => {
+    void saveConfig({ ...cfg, webhook: next });
+  };
+  const webhookTokenStatus = providers?.webhook_token_error
+    ? 'Unavailable'
+    : providers?.webhook_token_present
+      ? 'Set'
+      : 'Not set';
+  const setWebhookToken = async (command: 'set_webhook_token' | 'clear_webhook_token') => {
+    try {
+      setSaving(true);
+      const { invoke } = await import('@tauri-apps/api/core');
+      const next = await invoke<ProviderStatus>(command, { token: webhookTokenDraft });
+      setProviders(next);
+      setWebhookTokenDraft('');
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const blockedApps: AppMatcher[] = (cfg.blocked_apps ?? []).map(decodeAppMatcherWire);
+  const saveBlockedApps = (next: AppMatcher[]) => {
+    void saveConfig({ ...cfg, blocked_apps: next.map(encodeAppMatcherWire) });
+  };
+  const addBlockedApp = () => {
+    const value = blockedAppDraft.trim();
+    if (!value) return;
+    saveBlockedApps([...blockedApps, { kind: 'ProcessNameEquals', value }]);
+    setBlockedAppDraft('');
+  };
+
+  const replacements: SpokenReplacementSettings = cfg.spoken_replacements ?? {
+    enabled: false,
+    use_builtin: true,
+    custom: [],
+  };
+  const saveReplacements = (next: SpokenReplacementSettings) => {
+    void saveConfig({ ...cfg, spoken_replacements: next });
+  };
+  const addReplacement = () => {
+    const phrase = replacementPhraseDraft.trim();
+    if (!phrase) return;
+    // Stored unescaped; "\n" typed in the box means a line break.
+    const replacement = replacementTextDraft.replace(/\\n/g, '\n');
+    saveReplacements({ ...replacements, custom: [...replacements.custom, { phrase, replacement }] });
+    setReplacementPhraseDraft('');
+    setReplacementTextDraft('');
+  };
+
+  const snippets: Snippet[] = cfg.snippets ?? [];
+  const saveSnippets = (next: Snippet[]) => {
+    void saveConfig({ ...cfg, snippets: next });
+  };
+  const addSnippet = () => {
+    const name = snippetNameDraft.trim();
+    if (!name || !snippetTextDraft.trim()) return;
+    // Same convention as replacements: "\n" typed in the box means a line break.
+    const text = snippetTextDraft.replace(/\\n/g, '\n');
+    saveSnippets([...snippets.filter((s) => s.name.toLowerCase() !== name.toLowerCase()), { name, text }]);
+    setSnippetNameDraft('');
+    setSnippetTextDraft('');
+  };
+
   return (
     <div
       style={{
@@ -223,7 +584,7 @@ export function SettingsPage() {
 
       <Section
         title="Speech-to-Text"
-        subtitle="Choose the transcription engine. Local Whisper runs on-device; ElevenLabs uses cloud STT."
+        subtitle="Choose the transcription engine. Local Whisper runs on-device; ElevenLabs uses cloud STT; Custom command runs a program you choose."
       >
         <SettingRow
           title="Provider"
@@ -234,44 +595,271 @@ export function SettingsPage() {
               value={draft.stt_provider}
               disabled={saving}
               onChange={(e) => {
-                const next = e.target.value === 'elevenlabs' ? 'elevenlabs' : 'local';
+                const next = sttProviders.find((p) => p.id === e.target.value);
+                if (!next) return;
                 setDirty(true);
-                setDraft((d) => ({ ...d, stt_provider: next }));
+                setDraft((d) => ({
+                  ...d,
+                  stt_provider: next.id,
+                  cloud_stt_model:
+                    next.location === 'local' || next.models.some((m) => m.id === d.cloud_stt_model)
+                      ? d.cloud_stt_model
+                      : next.models[0]?.id ?? '',
+                }));
               }}
             >
-              <option value="local">Local Whisper</option>
-              <option value="elevenlabs">ElevenLabs</option>
+              {sttProviders.some((p) => p.id === draft.stt_provider) ? null : (
+                <option value={draft.stt_provider}>{draft.stt_provider}</option>
+              )}
+              {sttProviders.map((p) => (
+                <option key={p.id} value={p.id}>
+                  {p.title}
+                </option>
+              ))}
             </select>
           }
         />
 
-        {draft.stt_provider === 'local' ? (
-          <SettingRow
-            title="Local model"
-            description="Use the Models tab to download/switch local Whisper models."
-            right={<span className="vw-type-caption">Configured</span>}
-          />
-        ) : (
+        <SettingRow
+          title="Language"
+          description="The language you dictate in. Picking it instead of detecting it is faster and more accurate."
+          right={
+            <select
+              className="vw-input"
+              value={cfg.defaults.language}
+              disabled={saving}
+              onChange={(e) => void saveLanguage(e.target.value)}
+            >
+              {LANGUAGES.some(([code]) => code === cfg.defaults.language) ? null : (
+                <option value={cfg.defaults.language}>{cfg.defaults.language}</option>
+              )}
+              {LANGUAGES.map(([code, label]) => (
+                <option key={code} value={code}>
+                  {label}
+                </option>
+              ))}
+            </select>
+          }
+        />
+
+        {isLocalStt ? (
+          <>
+            <SettingRow
+              title="Local model"
+              description="Use the Models tab to download/switch local Whisper models."
+              right={<span className="vw-type-caption">Configured</span>}
+            />
+            <SettingRow
+              title="Match model to language"
+              description="When you change the language, switch to the installed model that is most accurate for it."
+              right={
+                <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+                  <input
+                    type="checkbox"
+                    checked={cfg.auto_select_model_for_language ?? false}
+                    disabled={saving}
+                    onChange={(e) => {
+                      setModelSuggestion(null);
+                      void saveConfig({ ...cfg, auto_select_model_for_language: e.target.checked });
+                    }}
+                  />
+                  <span className="vw-type-caption">{cfg.auto_select_model_for_language ? 'On' : 'Off'}</span>
+                </label>
+              }
+            />
+            {modelSuggestion ? (
+              <SettingRow
+                title="Suggested model"
+                description={`${modelSuggestion.title} is the most accurate installed model for this language.`}
+                right={
+                  <div style={{ display: 'flex', gap: 8 }}>
+                    <button
+                      type="button"
+                      className="vw-button vw-button--primary"
+                      disabled={saving}
+                      onClick={async () => {
+                        try {
+                          const { invoke } = await import('@tauri-apps/api/core');
+                          await invoke('set_active_model', { modelId: modelSuggestion.model_id });
+                          setModelSuggestion(null);
+                          await refresh();
+                        } catch (e) {
+                          setError(errorMessage(e));
+                        }
+                      }}
+                    >
+                      Switch
+                    </button>
+                    <button
+                      type="button"
+                      className="vw-button vw-button--secondary"
+                      onClick={() => setModelSuggestion(null)}
+                    >
+                      Keep current
+                    </button>
+                  </div>
+                }
+              />
+            ) : null}
+          </>
+        ) : sttProvider?.location === 'external' ? (
+          <>
+            <SettingRow
+              title="Program"
+              description='Runs with the recording as a 16 kHz WAV file and must print {"text": "..."} as JSON. Audio stays on this computer unless the program sends it elsewhere.'
+              right={
+                <input
+                  className="vw-input"
+                  placeholder="/usr/local/bin/transcribe"
+                  value={commandProgramDraft ?? commandStt.program}
+                  onChange={(e) => setCommandProgramDraft(e.target.value)}
+                  onBlur={() => {
+                    if (commandProgramDraft === null) return;
+                    saveCommandStt({ ...commandStt, program: commandProgramDraft.trim() });
+                    setCommandProgramDraft(null);
+                  }}
+                  style={{ width: 320 }}
+                  disabled={saving}
+                />
+              }
+            />
+            <SettingRow
+              title="Arguments"
+              description="One per line. {wav}, {language} and {model} are filled in; without {wav} the file path goes last."
+              right={
+                <textarea
+                  className="vw-input"
+                  rows={3}
+                  placeholder={'--language\n{language}\n{wav}'}
+                  value={commandArgsDraft ?? commandStt.args.join('\n')}
+                  onChange={(e) => setCommandArgsDraft(e.target.value)}
+                  onBlur={() => {
+                    if (commandArgsDraft === null) return;
+                    saveCommandStt({
+                      ...commandStt,
+                      args: commandArgsDraft
+                        .split('\n')
+                        .map((a) => a.trim())
+                        .filter((a) => a.length > 0),
+                    });
+                    setCommandArgsDraft(null);
+                  }}
+                  style={{ width: 320 }}
+                  disabled={saving}
+                />
+              }
+            />
+          </>
+        ) : sttProvider ? (
           <SettingRow
-            title="ElevenLabs model"
+            title={`${sttProvider.title} model`}
             description="Batch sends audio on stop. Realtime streams during recording (VAD + stop flush) but still inserts only on stop."
             right={
               <select
                 className="vw-input"
-                value={draft.elevenlabs_stt_model}
+                value={draft.cloud_stt_model}
                 disabled={saving}
                 onChange={(e) => {
-                  const v = e.target.value === 'scribe_v2_realtime' ? 'scribe_v2_realtime' : 'scribe_v2';
+                  const v = e.target.value;
                   setDirty(true);
-                  setDraft((d) => ({ ...d, elevenlabs_stt_model: v }));
+                  setDraft((d) => ({ ...d, cloud_stt_model: v }));
+                }}
+              >
+                {sttProvider.models.map((m) => (
+                  <option key={m.id} value={m.id}>
+                    {m.title}
+                  </option>
+                ))}
+              </select>
+            }
+          />
+        ) : null}
+        {cloudSttModel?.realtime ? (
+          <SettingRow
+            title="Pre-connect"
+            description="Open the streaming connection early so the first words aren't delayed. Keep warm holds an idle connection between sessions."
+            right={
+              <select
+                className="vw-input"
+                value={cfg.performance?.realtime_preconnect ?? 'off'}
+                disabled={saving}
+                onChange={(e) => {
+                  void saveConfig({
+                    ...cfg,
+                    performance: {
+                      low_memory_mode: Boolean(cfg.performance?.low_memory_mode),
+                      max_whisper_threads: cfg.performance?.max_whisper_threads ?? null,
+                      realtime_preconnect: e.target.value as RealtimePreconnect,
+                      max_download_kib_per_sec: cfg.performance?.max_download_kib_per_sec ?? null,
+                    },
+                  });
                 }}
               >
-                <option value="scribe_v2">Scribe v2 (Batch)</option>
-                <option value="scribe_v2_realtime">Scribe v2 (Realtime)</option>
+                <option value="off">Off</option>
+                <option value="on_hotkey">On hotkey press</option>
+                <option value="keep_warm">Keep warm</option>
               </select>
             }
           />
-        )}
+        ) : null}
+        {cloudSttModel?.realtime ? (
+          <>
+            <SettingRow
+              title="Segmenting"
+              description="Server VAD lets ElevenLabs detect pauses. Manual detects them on this computer, using the pause length and minimum speech below."
+              right={
+                <select
+                  className="vw-input"
+                  value={realtimeVad.commit_strategy}
+                  disabled={saving}
+                  onChange={(e) =>
+                    saveRealtimeVad({ ...realtimeVad, commit_strategy: e.target.value === 'manual' ? 'manual' : 'vad' })
+                  }
+                >
+                  <option value="vad">Server VAD</option>
+                  <option value="manual">Manual</option>
+                </select>
+              }
+            />
+            {(
+              [
+                ['vad_threshold_milli', 'Voice threshold', 'Raise in noisy rooms so background sound is not taken for speech.', '‰', 1000, false],
+                ['vad_silence_threshold_ms', 'Pause length', 'Silence that ends a segment.', 'ms', 5000, true],
+                ['min_speech_duration_ms', 'Minimum speech', 'Shorter bursts of sound are ignored.', 'ms', 5000, true],
+                ['min_silence_duration_ms', 'Minimum silence', 'Shorter gaps do not count as a pause.', 'ms', 5000, false],
+              ] as const
+            )
+              .filter(([, , , , , manual]) => realtimeVad.commit_strategy === 'vad' || manual)
+              .map(([key, label, description, unit, max]) => (
+                <SettingRow
+                  key={key}
+                  title={label}
+                  description={description}
+                  right={
+                    <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+                      <input
+                        className="vw-input"
+                        type="number"
+                        min={0}
+                        max={max}
+                        step={50}
+                        style={{ width: 80 }}
+                        value={realtimeVad[key]}
+                        disabled={saving}
+                        onChange={(e) =>
+                          saveRealtimeVad({
+                            ...realtimeVad,
+                            [key]: Math.min(max, Math.max(0, Math.floor(Number(e.target.value) || 0))),
+                          })
+                        }
+                      />
+                      <span className="vw-type-caption">{unit}</span>
+                    </label>
+                  }
+                />
+              ))}
+          </>
+        ) : null}
       </Section>
 
       <Section
@@ -301,16 +889,19 @@ export function SettingsPage() {
                     setSaving(true);
                     setElevenKeyError(null);
                     setElevenKeyNotice(null);
+                    const check = elevenApiKeyDraft.trim()
+                      ? await checkKey('validate_elevenlabs_key', elevenApiKeyDraft)
+                      : '';
                     const { invoke } = await import('@tauri-apps/api/core');
                     const next = await invoke<ProviderStatus>('set_elevenlabs_api_key', { api_key: elevenApiKeyDraft });
                     setProviders(next);
                     setElevenApiKeyDraft('');
 
-                    setElevenKeyNotice('Saved');
-                    window.setTimeout(() => setElevenKeyNotice(null), 2000);
+                    setElevenKeyNotice(check ? `Saved. ${check}` : 'Saved');
+                    window.setTimeout(() => setElevenKeyNotice(null), check ? 6000 : 2000);
                     await refresh();
                   } catch (e) {
-                    const msg = String(e);
+                    const msg = errorMessage(e);
                     setError(msg);
                     setElevenKeyError(msg);
                   } finally {
@@ -338,7 +929,7 @@ export function SettingsPage() {
                     window.setTimeout(() => setElevenKeyNotice(null), 2000);
                     await refresh();
                   } catch (e) {
-                    const msg = String(e);
+                    const msg = errorMessage(e);
                     setError(msg);
                     setElevenKeyError(msg);
                   } finally {
@@ -351,6 +942,56 @@ export function SettingsPage() {
             </>
           }
         />
+        <SettingRow
+          title="Label speakers"
+          description="Batch transcription only. Marks who said what (Speaker 1, Speaker 2, …) before enhancement, e.g. for meeting summaries."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cfg.diarization?.enabled ?? false}
+                disabled={saving}
+                onChange={(e) =>
+                  void saveConfig({
+                    ...cfg,
+                    diarization: { num_speakers: cfg.diarization?.num_speakers ?? null, enabled: e.target.checked },
+                  })
+                }
+              />
+              <span className="vw-type-caption">{cfg.diarization?.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Low credit warning"
+          description="Checks your ElevenLabs balance when dictation starts (at most every 15 minutes) and warns when fewer minutes than this are left."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={quotaWarning.enabled}
+                disabled={saving}
+                onChange={(e) => void saveConfig({ ...cfg, quota_warning: { ...quotaWarning, enabled: e.target.checked } })}
+              />
+              <input
+                className="vw-input"
+                type="number"
+                min={0}
+                step={5}
+                style={{ width: 80 }}
+                value={quotaWarning.min_minutes}
+                disabled={saving || !quotaWarning.enabled}
+                onChange={(e) =>
+                  void saveConfig({
+                    ...cfg,
+                    quota_warning: { ...quotaWarning, min_minutes: Math.max(0, Math.floor(Number(e.target.value) || 0)) },
+                  })
+                }
+              />
+              <span className="vw-type-caption">minutes</span>
+            </label>
+          }
+        />
 
         {elevenKeyStatusError ? (
           <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
@@ -403,163 +1044,1261 @@ export function SettingsPage() {
             </label>
           }
         />
-      </Section>
-
-      <Section
-        title="OpenAI-Compatible"
-        subtitle="Configure the endpoint used for enhancement (base URL + model) and store your API key in the OS keyring."
-      >
         <SettingRow
-          title="API key"
-          description={`Status: ${openaiKeyStatus}. The key is stored in the OS keyring (not in config.json).`}
+          title="Basic cleanup"
+          description="Without enhancement, fix sentence casing, spacing around punctuation, and numbers like “5 percent”. Runs locally."
           right={
-            <>
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
               <input
-                className="vw-input"
-                type="password"
-                placeholder="Paste key…"
-                value={openaiApiKeyDraft}
-                onChange={(e) => setOpenaiApiKeyDraft(e.target.value)}
-                style={{ width: 260 }}
+                type="checkbox"
+                checked={Boolean(cfg.defaults.basic_cleanup)}
                 disabled={saving}
+                onChange={(e) =>
+                  void saveConfig({ ...cfg, defaults: { ...cfg.defaults, basic_cleanup: e.target.checked } })
+                }
               />
-              <button
-                type="button"
-                className="vw-button vw-button--secondary"
-                disabled={saving}
-                onClick={async () => {
-                  try {
-                    setSaving(true);
-                    setOpenaiKeyError(null);
-                    setOpenaiKeyNotice(null);
-                    const { invoke } = await import('@tauri-apps/api/core');
-                    const next = await invoke<ProviderStatus>('set_openai_api_key', { api_key: openaiApiKeyDraft });
-                    setProviders(next);
-                    setOpenaiApiKeyDraft('');
-
-                    setOpenaiKeyNotice('Saved');
-                    window.setTimeout(() => setOpenaiKeyNotice(null), 2000);
-                    await refresh();
-                  } catch (e) {
-                    const msg = String(e);
-                    setError(msg);
-                    setOpenaiKeyError(msg);
-                  } finally {
-                    setSaving(false);
-                  }
-                }}
-              >
-                Save
-              </button>
-              <button
-                type="button"
-                className="vw-button vw-button--secondary"
-                disabled={saving}
-                onClick={async () => {
-                  try {
-                    setSaving(true);
-                    setOpenaiKeyError(null);
-                    setOpenaiKeyNotice(null);
-                    const { invoke } = await import('@tauri-apps/api/core');
-                    const next = await invoke<ProviderStatus>('clear_openai_api_key');
-                    setProviders(next);
-                    setOpenaiApiKeyDraft('');
-
-                    setOpenaiKeyNotice('Cleared');
-                    window.setTimeout(() => setOpenaiKeyNotice(null), 2000);
-                    await refresh();
-                  } catch (e) {
-                    const msg = String(e);
-                    setError(msg);
-                    setOpenaiKeyError(msg);
-                  } finally {
-                    setSaving(false);
-                  }
-                }}
-              >
-                Clear
-              </button>
-            </>
+              <span className="vw-type-caption">{cfg.defaults.basic_cleanup ? 'On' : 'Off'}</span>
+            </label>
           }
         />
-
-        {openaiKeyStatusError ? (
-          <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
-            Keyring error: {openaiKeyStatusError}
-          </div>
-        ) : null}
-        {openaiKeyError ? (
-          <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
-            {openaiKeyError}
-          </div>
-        ) : null}
-        {openaiKeyNotice ? (
-          <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-accent)' }}>
-            {openaiKeyNotice}
-          </div>
-        ) : null}
-
         <SettingRow
-          title="Base URL"
-          description="Example: https://api.openai.com/v1 or http://localhost:11434/v1"
+          title="Filler words"
+          description="Light removes “um” and “uh”. Aggressive also drops “like,”, “you know,” and stutters such as “I I think”. Runs locally, before enhancement."
           right={
-            <div style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
-              <input
-                className="vw-input"
-                type="text"
-                value={draft.llm_base_url}
-                onChange={(e) => {
-                  setDirty(true);
-                  setDraft((d) => ({ ...d, llm_base_url: e.target.value }));
-                }}
-                style={{ width: 420 }}
-                disabled={saving}
-              />
-              <button
-                type="button"
-                className="vw-button vw-button--secondary"
-                disabled={saving}
-                onClick={() => {
-                  setDirty(true);
-                  setDraft((d) => ({ ...d, llm_base_url: 'https://api.openai.com/v1' }));
-                }}
-              >
-                Reset
-              </button>
-            </div>
+            <select
+              className="vw-input"
+              value={cfg.defaults.disfluency_filter ?? 'light'}
+              disabled={saving}
+              onChange={(e) =>
+                void saveConfig({
+                  ...cfg,
+                  defaults: { ...cfg.defaults, disfluency_filter: e.target.value as DisfluencyFilter },
+                })
+              }
+            >
+              <option value="off">Keep</option>
+              <option value="light">Light</option>
+              <option value="aggressive">Aggressive</option>
+            </select>
           }
         />
+      </Section>
 
+      <Section title="Overlay" subtitle="How the recording pill behaves while it is on screen.">
         <SettingRow
-          title="Model"
-          description="Example: gpt-4o-mini"
+          title="Click-through"
+          description="Let clicks pass through the pill to the app underneath. Hold Ctrl (Cmd on macOS) while hovering to use its buttons."
           right={
-            <div style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
               <input
-                className="vw-input"
-                type="text"
-                value={draft.llm_model}
+                type="checkbox"
+                checked={Boolean(cfg.overlay?.click_through)}
                 onChange={(e) => {
-                  setDirty(true);
-                  setDraft((d) => ({ ...d, llm_model: e.target.value }));
+                  void saveConfig({
+                    ...cfg,
+                    overlay: { ...cfg.overlay, click_through: e.target.checked },
+                  });
                 }}
-                style={{ width: 260 }}
                 disabled={saving}
               />
-              <button
-                type="button"
-                className="vw-button vw-button--secondary"
-                disabled={saving}
-                onClick={() => {
-                  setDirty(true);
-                  setDraft((d) => ({ ...d, llm_model: 'gpt-4o-mini' }));
-                }}
-              >
-                Reset
-              </button>
-            </div>
+              <span className="vw-type-caption">{cfg.overlay?.click_through ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Show on"
+          description="Which display the pill appears on when recording starts."
+          right={
+            <select
+              className="vw-input"
+              value={placementValue(cfg.overlay?.placement)}
+              disabled={saving}
+              onChange={(e) => {
+                void saveConfig({
+                  ...cfg,
+                  overlay: {
+                    click_through: Boolean(cfg.overlay?.click_through),
+                    ...cfg.overlay,
+                    placement: parsePlacement(e.target.value),
+                  },
+                });
+              }}
+            >
+              <option value="follow_cursor">Display with the mouse cursor</option>
+              <option value="follow_focused_window">Display with the focused window</option>
+              {monitors.map((m) => (
+                <option key={m.index} value={`fixed_monitor:${m.index}`}>
+                  {`Display ${m.index + 1}${m.name ? ` (${m.name})` : ''} — ${m.width}×${m.height}${m.is_primary ? ', primary' : ''}`}
+                </option>
+              ))}
+            </select>
+          }
+        />
+        <SettingRow
+          title="Visibility"
+          description="Always keeps the pill on screen between dictations. Minimal shows it only while recording and when something goes wrong."
+          right={
+            <select
+              className="vw-input"
+              value={cfg.overlay?.mode ?? 'auto'}
+              disabled={saving}
+              onChange={(e) => {
+                void saveConfig({
+                  ...cfg,
+                  overlay: {
+                    click_through: Boolean(cfg.overlay?.click_through),
+                    ...cfg.overlay,
+                    mode: e.target.value as OverlayMode,
+                  },
+                });
+              }}
+            >
+              <option value="auto">During dictation</option>
+              <option value="persistent">Always</option>
+              <option value="minimal">Minimal</option>
+            </select>
+          }
+        />
+        <SettingRow
+          title="Style"
+          description="Compact hides the status text and keeps only the icon, level meter, and buttons."
+          right={
+            <select
+              className="vw-input"
+              value={appearance.style}
+              disabled={saving}
+              onChange={(e) => {
+                void saveOverlayPrefs({ ...appearance, style: e.target.value === 'compact' ? 'compact' : 'full' });
+              }}
+            >
+              <option value="full">Full</option>
+              <option value="compact">Compact</option>
+            </select>
+          }
+        />
+        <SettingRow
+          title="Theme"
+          right={
+            <select
+              className="vw-input"
+              value={appearance.theme}
+              disabled={saving}
+              onChange={(e) => {
+                const theme = e.target.value === 'light' || e.target.value === 'dark' ? e.target.value : 'system';
+                void saveOverlayPrefs({ ...appearance, theme });
+              }}
+            >
+              <option value="system">System</option>
+              <option value="light">Light</option>
+              <option value="dark">Dark</option>
+            </select>
+          }
+        />
+        <SettingRow
+          title="Accent color"
+          right={
+            <div style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="color"
+                value={appearance.accent_color ?? '#2b6aff'}
+                disabled={saving}
+                onChange={(e) => {
+                  void saveOverlayPrefs({ ...appearance, accent_color: e.target.value });
+                }}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving || !appearance.accent_color}
+                onClick={() => {
+                  void saveOverlayPrefs({ ...appearance, accent_color: null });
+                }}
+              >
+                Reset
+              </button>
+            </div>
+          }
+        />
+        <SettingRow
+          title="Opacity"
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="range"
+                min={20}
+                max={100}
+                step={5}
+                value={appearance.opacity_pct}
+                disabled={saving}
+                onChange={(e) => {
+                  void saveOverlayPrefs({ ...appearance, opacity_pct: Number(e.target.value) });
+                }}
+              />
+              <span className="vw-type-caption">{appearance.opacity_pct}%</span>
+            </label>
+          }
+        />
+      </Section>
+
+      <Section title="Sound" subtitle="Short beeps when recording starts/stops and when a dictation finishes or fails.">
+        <SettingRow
+          title="Sound cues"
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cues.enabled}
+                disabled={saving}
+                onChange={(e) => saveCues({ ...cues, enabled: e.target.checked })}
+              />
+              <span className="vw-type-caption">{cues.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        {cues.enabled ? (
+          <>
+            <SettingRow
+              title="Play on"
+              right={
+                <div style={{ display: 'flex', alignItems: 'center', gap: 12 }}>
+                  {(
+                    [
+                      ['on_start', 'Start'],
+                      ['on_stop', 'Stop'],
+                      ['on_success', 'Success'],
+                      ['on_error', 'Error'],
+                    ] as const
+                  ).map(([key, label]) => (
+                    <label key={key} style={{ display: 'flex', alignItems: 'center', gap: 4 }}>
+                      <input
+                        type="checkbox"
+                        checked={cues[key]}
+                        disabled={saving}
+                        onChange={(e) => saveCues({ ...cues, [key]: e.target.checked })}
+                      />
+                      <span className="vw-type-caption">{label}</span>
+                    </label>
+                  ))}
+                </div>
+              }
+            />
+            <SettingRow
+              title="Volume"
+              right={
+                <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+                  <input
+                    type="range"
+                    min={0}
+                    max={100}
+                    step={5}
+                    value={cues.volume_pct}
+                    disabled={saving}
+                    onChange={(e) => saveCues({ ...cues, volume_pct: Number(e.target.value) })}
+                  />
+                  <span className="vw-type-caption">{cues.volume_pct}%</span>
+                </label>
+              }
+            />
+          </>
+        ) : null}
+        <SettingRow
+          title="Respect Do Not Disturb"
+          description="While Focus Assist or Do Not Disturb is on, play no cues and don't bring the overlay back for results."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cfg.respect_do_not_disturb ?? false}
+                disabled={saving}
+                onChange={(e) => void saveConfig({ ...cfg, respect_do_not_disturb: e.target.checked })}
+              />
+              <span className="vw-type-caption">{cfg.respect_do_not_disturb ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+      </Section>
+
+      <Section
+        title="Privacy"
+        subtitle="Protect dictated text stored in History and logs. Inserted text is not affected."
+      >
+        <SettingRow
+          title="Encrypt History"
+          description="Encrypts history.json on disk with a key stored in the OS keyring."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cfg.encrypt_history ?? false}
+                disabled={saving}
+                onChange={(e) => void saveConfig({ ...cfg, encrypt_history: e.target.checked })}
+              />
+              <span className="vw-type-caption">{cfg.encrypt_history ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Redact History"
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={redaction.enabled}
+                disabled={saving}
+                onChange={(e) => saveRedaction({ ...redaction, enabled: e.target.checked })}
+              />
+              <span className="vw-type-caption">{redaction.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        {redaction.enabled ? (
+          <>
+            <SettingRow
+              title="Patterns"
+              right={
+                <div style={{ display: 'flex', alignItems: 'center', gap: 12 }}>
+                  {(
+                    [
+                      ['credit_card', 'Card numbers'],
+                      ['ssn', 'SSNs'],
+                      ['email', 'Emails'],
+                    ] as [RedactionPreset, string][]
+                  ).map(([preset, label]) => (
+                    <label key={preset} style={{ display: 'flex', alignItems: 'center', gap: 4 }}>
+                      <input
+                        type="checkbox"
+                        checked={redaction.presets.includes(preset)}
+                        disabled={saving}
+                        onChange={(e) =>
+                          saveRedaction({
+                            ...redaction,
+                            presets: e.target.checked
+                              ? [...redaction.presets, preset]
+                              : redaction.presets.filter((p) => p !== preset),
+                          })
+                        }
+                      />
+                      <span className="vw-type-caption">{label}</span>
+                    </label>
+                  ))}
+                </div>
+              }
+            />
+            <SettingRow
+              title="Custom patterns"
+              description="One regular expression per line."
+              right={
+                <textarea
+                  className="vw-input"
+                  rows={3}
+                  style={{ width: 260 }}
+                  defaultValue={redaction.custom_patterns.join('\n')}
+                  disabled={saving}
+                  onBlur={(e) =>
+                    saveRedaction({
+                      ...redaction,
+                      custom_patterns: e.target.value
+                        .split('\n')
+                        .map((l) => l.trim())
+                        .filter(Boolean),
+                    })
+                  }
+                />
+              }
+            />
+            <SettingRow
+              title="Store hash only"
+              description="History keeps a SHA-256 hash instead of the text, so entries can't be copied later."
+              right={
+                <input
+                  type="checkbox"
+                  checked={redaction.hash_only}
+                  disabled={saving}
+                  onChange={(e) => saveRedaction({ ...redaction, hash_only: e.target.checked })}
+                />
+              }
+            />
+          </>
+        ) : null}
+      </Section>
+
+      <Section
+        title="Spoken replacements"
+        subtitle="Turn spoken phrases into text, e.g. “new line”, “new paragraph”, “smiley face” → 🙂. Applied before insertion."
+      >
+        <SettingRow
+          title="Enabled"
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={replacements.enabled}
+                disabled={saving}
+                onChange={(e) => saveReplacements({ ...replacements, enabled: e.target.checked })}
+              />
+              <span className="vw-type-caption">{replacements.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Built-in phrases"
+          description="Line breaks and common emoji in English, German, Spanish and French (picked by the dictation language)."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={replacements.use_builtin}
+                disabled={saving || !replacements.enabled}
+                onChange={(e) => saveReplacements({ ...replacements, use_builtin: e.target.checked })}
+              />
+              <span className="vw-type-caption">{replacements.use_builtin ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Insert commands"
+          description="End a dictation with “send it” to paste and press Enter, or “just copy” to only copy it. Applies to that dictation only."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cfg.insert_commands?.enabled ?? true}
+                disabled={saving}
+                onChange={(e) =>
+                  void saveConfig({
+                    ...cfg,
+                    insert_commands: {
+                      commands: cfg.insert_commands?.commands ?? [
+                        { phrase: 'send it', mode: 'PasteAndEnter' },
+                        { phrase: 'just copy', mode: 'ClipboardOnly' },
+                      ],
+                      enabled: e.target.checked,
+                    },
+                  })
+                }
+              />
+              <span className="vw-type-caption">{(cfg.insert_commands?.enabled ?? true) ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        {replacements.custom.map((r, i) => (
+          <SettingRow
+            key={`${r.phrase}:${i}`}
+            title={`“${r.phrase}”`}
+            description={`→ ${JSON.stringify(r.replacement).slice(1, -1)}`}
+            right={
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() =>
+                  saveReplacements({ ...replacements, custom: replacements.custom.filter((_, j) => j !== i) })
+                }
+              >
+                Remove
+              </button>
+            }
+          />
+        ))}
+        <SettingRow
+          title="Add phrase"
+          description="Custom phrases take precedence over built-ins. Use \n for a line break."
+          right={
+            <>
+              <input
+                className="vw-input"
+                placeholder="Spoken phrase…"
+                value={replacementPhraseDraft}
+                onChange={(e) => setReplacementPhraseDraft(e.target.value)}
+                style={{ width: 160 }}
+                disabled={saving}
+              />
+              <input
+                className="vw-input"
+                placeholder="Replacement…"
+                value={replacementTextDraft}
+                onChange={(e) => setReplacementTextDraft(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === 'Enter') addReplacement();
+                }}
+                style={{ width: 140 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving || !replacementPhraseDraft.trim()}
+                onClick={addReplacement}
+              >
+                Add
+              </button>
+            </>
+          }
+        />
+      </Section>
+
+      <Section
+        title="Snippets"
+        subtitle="Say “insert <name>” to paste stored text, e.g. “insert my address”. A dictation that is only the command is inserted exactly as stored, without AI enhancement."
+      >
+        {snippets.map((s, i) => (
+          <SettingRow
+            key={`${s.name}:${i}`}
+            title={`“insert ${s.name}”`}
+            description={`→ ${JSON.stringify(s.text).slice(1, -1)}`}
+            right={
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() => saveSnippets(snippets.filter((_, j) => j !== i))}
+              >
+                Remove
+              </button>
+            }
+          />
+        ))}
+        <SettingRow
+          title="Add snippet"
+          description="Use \n for a line break. Adding an existing name replaces it."
+          right={
+            <>
+              <input
+                className="vw-input"
+                placeholder="Name, e.g. my address…"
+                value={snippetNameDraft}
+                onChange={(e) => setSnippetNameDraft(e.target.value)}
+                style={{ width: 160 }}
+                disabled={saving}
+              />
+              <input
+                className="vw-input"
+                placeholder="Text…"
+                value={snippetTextDraft}
+                onChange={(e) => setSnippetTextDraft(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === 'Enter') addSnippet();
+                }}
+                style={{ width: 140 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving || !snippetNameDraft.trim() || !snippetTextDraft.trim()}
+                onClick={addSnippet}
+              >
+                Add
+              </button>
+            </>
+          }
+        />
+      </Section>
+
+      <Section
+        title="Webhook"
+        subtitle="POST each finished dictation (text, app, profile, timings) as JSON to a URL, e.g. an n8n or Zapier hook. The token is stored in the OS keyring."
+      >
+        <SettingRow
+          title="Send to webhook"
+          description="Off by default. Clipboard and selection context are never sent."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={webhook.enabled}
+                disabled={saving}
+                onChange={(e) => saveWebhook({ ...webhook, enabled: e.target.checked })}
+              />
+              <span className="vw-type-caption">{webhook.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="URL"
+          right={
+            <input
+              className="vw-input"
+              placeholder="https://…"
+              value={webhookUrlDraft ?? webhook.url}
+              onChange={(e) => setWebhookUrlDraft(e.target.value)}
+              onBlur={() => {
+                if (webhookUrlDraft === null) return;
+                saveWebhook({ ...webhook, url: webhookUrlDraft.trim() });
+                setWebhookUrlDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+        <SettingRow
+          title="Insertion"
+          description="“Instead of inserting” only posts the text; if the webhook fails the dictation is kept in History."
+          right={
+            <select
+              className="vw-input"
+              value={webhook.mode}
+              disabled={saving}
+              onChange={(e) => saveWebhook({ ...webhook, mode: e.target.value as WebhookMode })}
+            >
+              <option value="after_insert">Insert, then post</option>
+              <option value="instead_of_insert">Instead of inserting</option>
+            </select>
+          }
+        />
+        <SettingRow
+          title="Bearer token"
+          description={`Status: ${webhookTokenStatus}. Sent as “Authorization: Bearer …” when set.`}
+          right={
+            <>
+              <input
+                className="vw-input"
+                type="password"
+                placeholder="Paste token…"
+                value={webhookTokenDraft}
+                onChange={(e) => setWebhookTokenDraft(e.target.value)}
+                style={{ width: 220 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() => void setWebhookToken('set_webhook_token')}
+              >
+                Save
+              </button>
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() => void setWebhookToken('clear_webhook_token')}
+              >
+                Clear
+              </button>
+            </>
+          }
+        />
+      </Section>
+
+      <Section
+        title="Digest"
+        subtitle="Summarize each day's or week's dictations with your LLM. Digests appear in History tagged “digest”; periods follow UTC."
+      >
+        <SettingRow
+          title="Generate digests"
+          description="Uses the LLM endpoint and API key configured above. Days without dictation are skipped."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={digest.enabled}
+                disabled={saving}
+                onChange={(e) => saveDigest({ ...digest, enabled: e.target.checked })}
+              />
+              <span className="vw-type-caption">{digest.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Period"
+          right={
+            <select
+              className="vw-input"
+              value={digest.period}
+              disabled={saving}
+              onChange={(e) => saveDigest({ ...digest, period: e.target.value as DigestPeriod })}
+            >
+              <option value="daily">Daily</option>
+              <option value="weekly">Weekly (Monday to Sunday)</option>
+            </select>
+          }
+        />
+        <SettingRow
+          title="Also append to note"
+          description="Optional Markdown file; {date} expands to the first day of the period."
+          right={
+            <input
+              className="vw-input"
+              placeholder="~/Notes/Digests/{date}.md"
+              value={digestNoteDraft ?? digest.note_path ?? ''}
+              onChange={(e) => setDigestNoteDraft(e.target.value)}
+              onBlur={() => {
+                if (digestNoteDraft === null) return;
+                saveDigest({ ...digest, note_path: digestNoteDraft.trim() || null });
+                setDigestNoteDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+        <SettingRow
+          title="Digest prompt"
+          description="Leave empty for the built-in summary prompt."
+          right={
+            <textarea
+              className="vw-input"
+              rows={3}
+              value={digestPromptDraft ?? digest.prompt}
+              onChange={(e) => setDigestPromptDraft(e.target.value)}
+              onBlur={() => {
+                if (digestPromptDraft === null) return;
+                saveDigest({ ...digest, prompt: digestPromptDraft });
+                setDigestPromptDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+      </Section>
+
+      {hotkeys.length > 0 ? (
+        <Section
+          title="Keyboard shortcuts"
+          subtitle="Global shortcuts work in any app, so dictation can be run from the keyboard alone. Each shortcut can do one thing; status changes are announced to screen readers. On Windows and macOS, side mouse buttons (Mouse4, Mouse5) and USB foot pedals (Pedal1, Pedal2, Pedal3) can be entered too."
+        >
+          {hotkeyError ? (
+            <div className="vw-type-caption" style={{ color: 'var(--color-danger-fg)' }}>
+              {hotkeyError}
+            </div>
+          ) : null}
+          {hotkeys.map(({ action, shortcut }) => (
+            <SettingRow
+              key={action}
+              title={HOTKEY_LABELS[action].title}
+              description={HOTKEY_LABELS[action].description}
+              right={
+                <input
+                  className="vw-input"
+                  placeholder="Not set"
+                  aria-label={`${HOTKEY_LABELS[action].title} shortcut`}
+                  value={hotkeyDrafts[action] ?? shortcut ?? ''}
+                  onChange={(e) => setHotkeyDrafts((d) => ({ ...d, [action]: e.target.value }))}
+                  onBlur={async () => {
+                    const draftValue = hotkeyDrafts[action];
+                    if (draftValue === undefined) return;
+                    await saveHotkey(action, draftValue.trim());
+                    setHotkeyDrafts((d) => {
+                      const next = { ...d };
+                      delete next[action];
+                      return next;
+                    });
+                  }}
+                  style={{ width: 200 }}
+                />
+              }
+            />
+          ))}
+        </Section>
+      ) : null}
+
+      <Section
+        title="Wake phrase"
+        subtitle="Start dictating by voice instead of the hotkey. Helpful when a keyboard is hard to use."
+      >
+        <SettingRow
+          title="Listen for the wake phrase"
+          description="Keeps the microphone open while idle. What it hears is checked with the local model on this computer, then discarded; nothing is saved or uploaded. The tray shows when it is listening."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={wakeWord.enabled}
+                disabled={saving}
+                onChange={(e) => saveWakeWord({ ...wakeWord, enabled: e.target.checked })}
+              />
+              <span className="vw-type-caption">{wakeWord.enabled ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Phrase"
+          description="A short, distinctive phrase works best. Needs a downloaded local model."
+          right={
+            <input
+              className="vw-input"
+              placeholder="Hey VoiceWin"
+              value={wakePhraseDraft ?? wakeWord.phrase}
+              onChange={(e) => setWakePhraseDraft(e.target.value)}
+              onBlur={() => {
+                if (wakePhraseDraft === null) return;
+                saveWakeWord({ ...wakeWord, phrase: wakePhraseDraft.trim() });
+                setWakePhraseDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+      </Section>
+
+      <Section
+        title="Meeting transcription"
+        subtitle="Record a whole meeting. It is cut at pauses, and each part is transcribed and saved with its time into the meeting, never typed into an app."
+      >
+        <SettingRow
+          title="Meeting mode"
+          description="Runs until you stop it here, from the tray or on the overlay. Uses your default transcription model."
+          right={
+            <button type="button" className="vw-button vw-button--secondary" onClick={() => void toggleMeeting()}>
+              {meetingRunning ? 'Stop meeting' : 'Start meeting'}
+            </button>
+          }
+        />
+        <SettingRow
+          title="Save to note"
+          description="Optional Markdown file; {date} expands to the meeting's date. Leave empty to collect in the Scratchpad."
+          right={
+            <input
+              className="vw-input"
+              placeholder="~/Notes/Meetings/{date}.md"
+              value={meetingNoteDraft ?? meeting.note_path ?? ''}
+              onChange={(e) => setMeetingNoteDraft(e.target.value)}
+              onBlur={() => {
+                if (meetingNoteDraft === null) return;
+                void saveConfig({ ...cfg, meeting: { ...meeting, note_path: meetingNoteDraft.trim() || null } });
+                setMeetingNoteDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+      </Section>
+
+      <Section
+        title="Blocked apps"
+        subtitle="Dictation never starts while one of these apps is in the foreground (e.g. password managers, banking apps)."
+      >
+        {blockedApps.map((m, i) => (
+          <SettingRow
+            key={`${m.kind}:${m.value}:${i}`}
+            title={m.value}
+            description={
+              m.kind === 'ProcessNameEquals'
+                ? 'Process name'
+                : m.kind === 'ExePathEquals'
+                  ? 'Executable path'
+                  : 'Window title contains'
+            }
+            right={
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() => saveBlockedApps(blockedApps.filter((_, j) => j !== i))}
+              >
+                Remove
+              </button>
+            }
+          />
+        ))}
+        <SettingRow
+          title="Add app"
+          description="Process name, e.g. 1Password.exe or KeePassXC."
+          right={
+            <>
+              <input
+                className="vw-input"
+                placeholder="Process name…"
+                value={blockedAppDraft}
+                onChange={(e) => setBlockedAppDraft(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === 'Enter') addBlockedApp();
+                }}
+                style={{ width: 220 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving || !blockedAppDraft.trim()}
+                onClick={addBlockedApp}
+              >
+                Add
+              </button>
+            </>
+          }
+        />
+      </Section>
+
+      <Section
+        title="OpenAI-Compatible"
+        subtitle="Configure the endpoint used for enhancement (base URL + model) and store your API key in the OS keyring."
+      >
+        <SettingRow
+          title="API key"
+          description={`Status: ${openaiKeyStatus}. The key is stored in the OS keyring (not in config.json).`}
+          right={
+            <>
+              <input
+                className="vw-input"
+                type="password"
+                placeholder="Paste key…"
+                value={openaiApiKeyDraft}
+                onChange={(e) => setOpenaiApiKeyDraft(e.target.value)}
+                style={{ width: 260 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={async () => {
+                  try {
+                    setSaving(true);
+                    setOpenaiKeyError(null);
+                    setOpenaiKeyNotice(null);
+                    // Checked against the saved endpoint, so only once one is set.
+                    const check =
+                      openaiApiKeyDraft.trim() && cfg.defaults.llm_base_url.trim()
+                        ? await checkKey('validate_llm_key', openaiApiKeyDraft)
+                        : '';
+                    const { invoke } = await import('@tauri-apps/api/core');
+                    const next = await invoke<ProviderStatus>('set_openai_api_key', { api_key: openaiApiKeyDraft });
+                    setProviders(next);
+                    setOpenaiApiKeyDraft('');
+
+                    setOpenaiKeyNotice(check ? `Saved. ${check}` : 'Saved');
+                    window.setTimeout(() => setOpenaiKeyNotice(null), check ? 6000 : 2000);
+                    await refresh();
+                  } catch (e) {
+                    const msg = errorMessage(e);
+                    setError(msg);
+                    setOpenaiKeyError(msg);
+                  } finally {
+                    setSaving(false);
+                  }
+                }}
+              >
+                Save
+              </button>
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={async () => {
+                  try {
+                    setSaving(true);
+                    setOpenaiKeyError(null);
+                    setOpenaiKeyNotice(null);
+                    const { invoke } = await import('@tauri-apps/api/core');
+                    const next = await invoke<ProviderStatus>('clear_openai_api_key');
+                    setProviders(next);
+                    setOpenaiApiKeyDraft('');
+
+                    setOpenaiKeyNotice('Cleared');
+                    window.setTimeout(() => setOpenaiKeyNotice(null), 2000);
+                    await refresh();
+                  } catch (e) {
+                    const msg = errorMessage(e);
+                    setError(msg);
+                    setOpenaiKeyError(msg);
+                  } finally {
+                    setSaving(false);
+                  }
+                }}
+              >
+                Clear
+              </button>
+            </>
+          }
+        />
+
+        {openaiKeyStatusError ? (
+          <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
+            Keyring error: {openaiKeyStatusError}
+          </div>
+        ) : null}
+        {openaiKeyError ? (
+          <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
+            {openaiKeyError}
+          </div>
+        ) : null}
+        {openaiKeyNotice ? (
+          <div className="vw-type-caption" style={{ padding: 'var(--space-12)', color: 'var(--color-accent)' }}>
+            {openaiKeyNotice}
+          </div>
+        ) : null}
+
+        <SettingRow
+          title="Base URL"
+          description="Example: https://api.openai.com/v1 or http://localhost:11434/v1"
+          right={
+            <div style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+              <input
+                className="vw-input"
+                type="text"
+                value={draft.llm_base_url}
+                onChange={(e) => {
+                  setDirty(true);
+                  setDraft((d) => ({ ...d, llm_base_url: e.target.value }));
+                }}
+                style={{ width: 420 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() => {
+                  setDirty(true);
+                  setDraft((d) => ({ ...d, llm_base_url: 'https://api.openai.com/v1' }));
+                }}
+              >
+                Reset
+              </button>
+            </div>
+          }
+        />
+
+        <SettingRow
+          title="Model"
+          description="Example: gpt-4o-mini"
+          right={
+            <div style={{ display: 'flex', alignItems: 'center', gap: 'var(--space-8)' }}>
+              <input
+                className="vw-input"
+                type="text"
+                value={draft.llm_model}
+                onChange={(e) => {
+                  setDirty(true);
+                  setDraft((d) => ({ ...d, llm_model: e.target.value }));
+                }}
+                style={{ width: 260 }}
+                disabled={saving}
+              />
+              <button
+                type="button"
+                className="vw-button vw-button--secondary"
+                disabled={saving}
+                onClick={() => {
+                  setDirty(true);
+                  setDraft((d) => ({ ...d, llm_model: 'gpt-4o-mini' }));
+                }}
+              >
+                Reset
+              </button>
+            </div>
+          }
+        />
+      </Section>
+
+      <Section
+        title="Timeouts"
+        subtitle="Give up on a stage that takes too long (e.g. a network request that never answers). 0 disables the limit."
+      >
+        {(
+          [
+            ['transcribe_secs', 'Transcription'],
+            ['enhance_secs', 'Enhancement'],
+            ['insert_secs', 'Insertion'],
+          ] as const
+        ).map(([key, label]) => (
+          <SettingRow
+            key={key}
+            title={label}
+            right={
+              <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+                <input
+                  className="vw-input"
+                  type="number"
+                  min={0}
+                  step={5}
+                  style={{ width: 80 }}
+                  value={timeouts[key]}
+                  disabled={saving}
+                  onChange={(e) => saveTimeouts({ ...timeouts, [key]: Math.max(0, Math.floor(Number(e.target.value) || 0)) })}
+                />
+                <span className="vw-type-caption">seconds</span>
+              </label>
+            }
+          />
+        ))}
+      </Section>
+
+      <Section title="Updates" subtitle="New versions are published on GitHub. Installers are downloaded and started for you.">
+        <SettingRow
+          title="Channel"
+          description="Beta also offers pre-releases."
+          right={
+            <select
+              className="vw-input"
+              value={updates.channel}
+              disabled={saving}
+              onChange={(e) => saveUpdates({ ...updates, channel: e.target.value as UpdateChannel })}
+            >
+              <option value="stable">Stable</option>
+              <option value="beta">Beta</option>
+            </select>
+          }
+        />
+        <SettingRow
+          title="Check on startup"
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={updates.check_on_startup}
+                disabled={saving}
+                onChange={(e) => saveUpdates({ ...updates, check_on_startup: e.target.checked })}
+              />
+              <span className="vw-type-caption">{updates.check_on_startup ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <UpdatesPanel initialUpdate={availableUpdate ?? null} />
+      </Section>
+
+      <Section
+        title="Network"
+        subtitle="How cloud transcription, enhancement, model downloads and updates reach the internet. Leave the proxy empty to use the HTTPS_PROXY environment variable."
+      >
+        <SettingRow
+          title="Offline mode"
+          description="Never connect to the internet: dictation uses the local model, enhancement, the webhook, model downloads and update checks are off."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cfg.offline_mode ?? false}
+                disabled={saving}
+                onChange={(e) => void saveConfig({ ...cfg, offline_mode: e.target.checked })}
+              />
+              <span className="vw-type-caption">{cfg.offline_mode ? 'On' : 'Off'}</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Model download limit"
+          description="Total speed for model downloads, shared when several run at once. 0 is unlimited."
+          right={
+            <label style={{ display: 'flex', alignItems: 'center', gap: 8 }}>
+              <input
+                className="vw-input"
+                type="number"
+                min={0}
+                step={256}
+                style={{ width: 80 }}
+                value={cfg.performance?.max_download_kib_per_sec ?? 0}
+                disabled={saving}
+                onChange={(e) => {
+                  const kib = Math.max(0, Math.floor(Number(e.target.value) || 0));
+                  void saveConfig({
+                    ...cfg,
+                    performance: {
+                      low_memory_mode: Boolean(cfg.performance?.low_memory_mode),
+                      max_whisper_threads: cfg.performance?.max_whisper_threads ?? null,
+                      realtime_preconnect: cfg.performance?.realtime_preconnect ?? 'off',
+                      max_download_kib_per_sec: kib > 0 ? kib : null,
+                    },
+                  });
+                }}
+              />
+              <span className="vw-type-caption">KB/s</span>
+            </label>
+          }
+        />
+        <SettingRow
+          title="Proxy URL"
+          description="http://host:port, optionally with user:password@."
+          right={
+            <input
+              className="vw-input"
+              placeholder="http://proxy.example:3128"
+              value={proxyUrlDraft ?? proxy.url}
+              onChange={(e) => setProxyUrlDraft(e.target.value)}
+              onBlur={() => {
+                if (proxyUrlDraft === null) return;
+                saveProxy({ ...proxy, url: proxyUrlDraft.trim() });
+                setProxyUrlDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+        <SettingRow
+          title="Bypass for"
+          description="Comma-separated hosts reached directly; .example.com covers subdomains. localhost always bypasses."
+          right={
+            <input
+              className="vw-input"
+              placeholder="intranet.example, .corp.example"
+              value={noProxyDraft ?? proxy.no_proxy}
+              onChange={(e) => setNoProxyDraft(e.target.value)}
+              onBlur={() => {
+                if (noProxyDraft === null) return;
+                saveProxy({ ...proxy, no_proxy: noProxyDraft.trim() });
+                setNoProxyDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+        <SettingRow
+          title="Extra root certificate"
+          description="For self-hosted gateways signed by an internal CA: a path to a .pem/.cer file, or the PEM itself. Trusted in addition to the built-in roots."
+          right={
+            <textarea
+              className="vw-input"
+              rows={3}
+              placeholder="C:\certs\corp-root.pem"
+              value={rootCertDraft ?? tls.extra_root_cert}
+              onChange={(e) => setRootCertDraft(e.target.value)}
+              onBlur={() => {
+                if (rootCertDraft === null) return;
+                saveTls({ ...tls, extra_root_cert: rootCertDraft.trim() });
+                setRootCertDraft(null);
+              }}
+              style={{ width: 320 }}
+              disabled={saving}
+            />
+          }
+        />
+      </Section>
+
+      <Section
+        title="Usage & cost"
+        subtitle="Cloud transcription time and LLM tokens, priced with the pricing table in config.json. Local dictation is free and not counted."
+      >
+        <CostPanel />
+      </Section>
+
+      <Section title="Troubleshooting" subtitle="Collect logs and diagnostics to attach to a bug report, and see where recent sessions spent their time.">
+        <SettingRow
+          title="Debug bundle"
+          description={
+            debugBundlePath
+              ? `Saved to ${debugBundlePath}`
+              : 'Saves a zip to your Downloads folder. Config is anonymized; API keys are never included.'
+          }
+          right={
+            <button
+              type="button"
+              className="vw-button vw-button--secondary"
+              disabled={saving}
+              onClick={async () => {
+                try {
+                  setSaving(true);
+                  const { invoke } = await import('@tauri-apps/api/core');
+                  setDebugBundlePath(await invoke<string>('export_debug_bundle'));
+                } catch (e) {
+                  setError(errorMessage(e));
+                } finally {
+                  setSaving(false);
+                }
+              }}
+            >
+              Export
+            </button>
           }
         />
+        <PerformancePanel />
       </Section>
 
       {baseUrlLooksMissingV1 ? (
@@ -588,19 +2327,7 @@ export function SettingsPage() {
                 llm_base_url: cfg.defaults.llm_base_url ?? '',
                 llm_model: cfg.defaults.llm_model ?? '',
 
-                stt_provider: (cfg.defaults.stt_provider === 'elevenlabs' ? 'elevenlabs' : 'local') as
-                  | 'local'
-                  | 'elevenlabs',
-                local_stt_model_path:
-                  cfg.defaults.stt_provider === 'local'
-                    ? cfg.defaults.stt_model
-                    : (modelStatus?.preferred_ok
-                        ? modelStatus.preferred_path
-                        : modelStatus?.bootstrap_path ?? ''),
-                elevenlabs_stt_model:
-                  cfg.defaults.stt_provider === 'elevenlabs' && cfg.defaults.stt_model === 'scribe_v2_realtime'
-                    ? 'scribe_v2_realtime'
-                    : 'scribe_v2',
+                ...sttDraftFromConfig(cfg, sttProviders, modelStatus),
               });
             }}
           >
@@ -621,13 +2348,12 @@ export function SettingsPage() {
                   llm_model: draft.llm_model.trim() || cfg.defaults.llm_model,
 
                   stt_provider: draft.stt_provider,
-                  stt_model:
-                    draft.stt_provider === 'local'
-                      ? (draft.local_stt_model_path.trim() ||
-                          (modelStatus?.preferred_ok
-                            ? modelStatus.preferred_path
-                            : modelStatus?.bootstrap_path ?? cfg.defaults.stt_model))
-                      : draft.elevenlabs_stt_model,
+                  stt_model: isLocalStt
+                    ? (draft.local_stt_model_path.trim() ||
+                        (modelStatus?.preferred_ok
+                          ? modelStatus.preferred_path
+                          : modelStatus?.bootstrap_path ?? cfg.defaults.stt_model))
+                    : draft.cloud_stt_model,
                 },
               };
               void (async () => {
@@ -0,0 +1,114 @@
+import { useEffect, useState } from 'react';
+
+import type { UpdateDownloadProgress, UpdateInfo } from '../lib/types';
+import { errorMessage } from '../lib/types';
+import { listenEvent } from '../lib/eventBus';
+
+// Manual update check plus installer download; progress arrives via events like model downloads.
+export function UpdatesPanel({ initialUpdate }: { initialUpdate: UpdateInfo | null }) {
+  const [checking, setChecking] = useState(false);
+  const [checked, setChecked] = useState(initialUpdate !== null);
+  const [update, setUpdate] = useState<UpdateInfo | null>(initialUpdate);
+  const [progress, setProgress] = useState<UpdateDownloadProgress | null>(null);
+  const [installing, setInstalling] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: null | (() => void) = null;
+
+    async function start() {
+      try {
+        unlisten = await listenEvent('update_download_progress', (payload) => {
+          setProgress(payload);
+        });
+      } catch {
+        // Not running in Tauri.
+      }
+    }
+
+    void start();
+
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
+
+  const frac =
+    progress?.total_bytes ? Math.max(0, Math.min(1, progress.downloaded_bytes / progress.total_bytes)) : null;
+
+  return (
+    <div style={{ padding: 'var(--space-12)' }}>
+      <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', gap: 'var(--space-12)' }}>
+        <span className="vw-type-caption">
+          {update
+            ? `VoiceWin ${update.version}${update.prerelease ? ' (beta)' : ''} is available.`
+            : checked
+              ? 'VoiceWin is up to date.'
+              : 'Check GitHub releases for a newer version.'}
+        </span>
+        <div style={{ display: 'flex', gap: 'var(--space-8)' }}>
+          <button
+            type="button"
+            className="vw-button vw-button--secondary"
+            disabled={checking || installing}
+            onClick={async () => {
+              setChecking(true);
+              try {
+                const { invoke } = await import('@tauri-apps/api/core');
+                setUpdate(await invoke<UpdateInfo | null>('check_for_updates'));
+                setChecked(true);
+                setError(null);
+              } catch (e) {
+                setError(errorMessage(e));
+              } finally {
+                setChecking(false);
+              }
+            }}
+          >
+            {checking ? 'Checking…' : 'Check Now'}
+          </button>
+          {update?.asset ? (
+            <button
+              type="button"
+              className="vw-button vw-button--primary"
+              disabled={installing}
+              onClick={async () => {
+                setInstalling(true);
+                setProgress(null);
+                try {
+                  const { invoke } = await import('@tauri-apps/api/core');
+                  await invoke('install_update');
+                  setError(null);
+                } catch (e) {
+                  setError(errorMessage(e));
+                } finally {
+                  setInstalling(false);
+                }
+              }}
+            >
+              {installing ? (frac !== null ? `Downloading ${Math.round(frac * 100)}%` : 'Downloading…') : 'Install'}
+            </button>
+          ) : null}
+        </div>
+      </div>
+
+      {update && !update.asset ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)' }}>
+          No verified installer for this platform; download it from {update.release_url}
+        </div>
+      ) : null}
+
+      {update?.notes ? (
+        <pre className="vw-type-caption" style={{ marginTop: 'var(--space-12)', whiteSpace: 'pre-wrap' }}>
+          {update.notes}
+        </pre>
+      ) : null}
+
+      {error ? (
+        <div className="vw-type-caption" style={{ marginTop: 'var(--space-12)', color: 'var(--color-danger-fg)' }}>
+          {error}
+        </div>
+      ) : null}
+    </div>
+  );
+}
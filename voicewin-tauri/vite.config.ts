@@ -10,6 +10,8 @@ export default defineConfig({
       input: {
         main: 'index.html',
         overlay: 'src/overlay.html',
+        scratchpad: 'src/scratchpad.html',
+        details: 'src/details.html',
       },
     },
   },
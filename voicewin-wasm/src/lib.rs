@@ -0,0 +1,57 @@
+// Browser bindings for the settings webview: trigger-word detection and prompt previews run
+// on the UI thread instead of a Tauri command per keystroke.
+//
+// Build with `wasm-pack build voicewin-wasm --target web`. Values cross the boundary as plain
+// JS objects shaped like the Rust config types (see `lib/types.ts`).
+
+use serde::{Deserialize, Serialize};
+use voicewin_core::enhancement::{
+    EnhancementContext, PromptTemplate, build_enhancement_prompt, detect_trigger_word,
+};
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Serialize)]
+struct TriggerPreview {
+    /// `None` when no trigger word matched.
+    prompt_id: Option<String>,
+    trigger_word: Option<String>,
+    /// The transcript with the trigger word removed.
+    transcript: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptPreviewRequest {
+    prompt: PromptTemplate,
+    #[serde(default)]
+    context: EnhancementContext,
+}
+
+#[derive(Debug, Serialize)]
+struct PromptPreview {
+    system_message: String,
+    user_message: String,
+}
+
+/// Which prompt `transcript` would switch to. `prompts` is a `PromptTemplate[]`.
+#[wasm_bindgen(js_name = detectTriggerWord)]
+pub fn detect_trigger_word_js(transcript: &str, prompts: JsValue) -> Result<JsValue, JsError> {
+    let prompts: Vec<PromptTemplate> = serde_wasm_bindgen::from_value(prompts)?;
+    let detection = detect_trigger_word(transcript, &prompts);
+    Ok(serde_wasm_bindgen::to_value(&TriggerPreview {
+        prompt_id: detection.selected_prompt_id.map(|id| id.0.to_string()),
+        trigger_word: detection.detected_trigger_word,
+        transcript: detection.processed_transcript,
+    })?)
+}
+
+/// The system and user messages the LLM would receive for `transcript`. `request` is
+/// `{ prompt: PromptTemplate, context?: EnhancementContext }`.
+#[wasm_bindgen(js_name = previewPrompt)]
+pub fn preview_prompt_js(transcript: &str, request: JsValue) -> Result<JsValue, JsError> {
+    let request: PromptPreviewRequest = serde_wasm_bindgen::from_value(request)?;
+    let built = build_enhancement_prompt(transcript, &request.prompt, &request.context);
+    Ok(serde_wasm_bindgen::to_value(&PromptPreview {
+        system_message: built.system_message,
+        user_message: built.user_message,
+    })?)
+}